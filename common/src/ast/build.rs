@@ -0,0 +1,453 @@
+//! Provides an API for building ASTs in code, so that code generators can construct a program directly and hand it
+//! to semantic analysis and IR generation without writing source text for the parser.
+//!
+//! Expressions and statements are built by the free functions of this module, functions by `function`, and the
+//! program by `program`:
+//!
+//! ```
+//! use common::ast::{build, data_type::DataType};
+//!
+//! let add = build::function("add", DataType::Integer)
+//!     .param("a", DataType::Integer)
+//!     .param("b", DataType::Integer)
+//!     .body(vec![build::ret(Some(build::binary(build::identifier("a"), "+", build::identifier("b"))))])
+//!     .build()
+//!     .expect("add is well-formed");
+//! let ast = build::program(vec![add]).expect("the program is well-formed");
+//! ```
+//!
+//! The nodes are shaped as the parser shapes them. Because nodes can also be put together by hand, `function` and
+//! `program` validate what they are given, reporting names that are not identifiers or are reserved keywords,
+//! operators the language does not have, nodes with the wrong children, and duplicate parameters or functions.
+
+use std::collections::HashSet;
+
+use crate::{
+    ast::{core::{ASTNode, AST}, data_type::DataType, node_type::NodeType},
+    error::ErrorType,
+    literal::ByteString,
+};
+
+/// The keywords that cannot be used as names.
+const RESERVED_KEYWORDS: &[&str] = &[
+    "struct", "enum", "union", "if", "else", "return", "for", "while", "do", "break", "continue", "switch", "case", "default",
+    "int", "bool", "double", "float", "char", "void", "signed", "unsigned", "long", "const", "volatile", "true",
+    "false", "static", "extern", "inline",
+];
+
+/// The operators of binary expressions.
+const BINARY_OPERATORS: &[&str] = &[
+    "+", "-", "*", "/", "%", "<", ">", "<=", ">=", "==", "!=", "&&", "||", "&", "|", "^", "<<", ">>",
+];
+
+/// The operators of unary expressions.
+const UNARY_OPERATORS: &[&str] = &["-", "!", "~", "++", "--", "&", "*"];
+
+/// The operators of compound assignments.
+const COMPOUND_ASSIGNMENT_OPERATORS: &[&str] = &["+=", "-=", "*=", "/=", "%=", "<<=", ">>=", "&=", "|=", "^="];
+
+/// Builds a function declaration, collecting its parameters and body before validating it.
+#[derive(Debug, Clone)]
+pub struct FunctionBuilder {
+    name: String,
+    return_type: DataType,
+    params: Vec<(String, DataType)>,
+    variadic: bool,
+    body: Vec<ASTNode>,
+}
+
+impl FunctionBuilder {
+    /// Adds a parameter after the parameters added so far.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the parameter.
+    /// * `data_type` - The type of the parameter.
+    pub fn param(mut self, name: &str, data_type: DataType) -> Self {
+        self.params.push((name.to_string(), data_type));
+        self
+    }
+
+    /// Makes the function variadic, taking any number of arguments after its parameters.
+    pub fn variadic(mut self) -> Self {
+        self.variadic = true;
+        self
+    }
+
+    /// Adds statements to the end of the function's body.
+    ///
+    /// # Parameters
+    ///
+    /// * `statements` - The statements to add, in order.
+    pub fn body(mut self, statements: Vec<ASTNode>) -> Self {
+        self.body.extend(statements);
+        self
+    }
+
+    /// Builds and validates the function declaration.
+    ///
+    /// # Returns
+    ///
+    /// The `FunctionDeclaration` node, or every error found in it.
+    pub fn build(self) -> Result<ASTNode, Vec<ErrorType>> {
+        let mut function = ASTNode::new(NodeType::FunctionDeclaration);
+        function.add_child(identifier(&self.name));
+        for (name, data_type) in self.params {
+            let mut parameter = ASTNode::new(NodeType::Parameter);
+            parameter.add_child(identifier(&name));
+            parameter.add_child(ASTNode::new(NodeType::Type(data_type)));
+            function.add_child(parameter);
+        }
+        if self.variadic {
+            function.add_child(ASTNode::new(NodeType::Variadic));
+        }
+        function.add_child(ASTNode::new(NodeType::Type(self.return_type)));
+        function.add_child(block(self.body));
+
+        let errors = validate(&function);
+        if errors.is_empty() {
+            Ok(function)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Starts building a function declaration with no parameters and an empty body.
+///
+/// # Parameters
+///
+/// * `name` - The name of the function.
+/// * `return_type` - The type the function returns, or `DataType::Void`.
+pub fn function(name: &str, return_type: DataType) -> FunctionBuilder {
+    FunctionBuilder { name: name.to_string(), return_type, params: Vec::new(), variadic: false, body: Vec::new() }
+}
+
+/// Builds a program from top-level functions and declarations, in order, and validates it.
+///
+/// # Parameters
+///
+/// * `items` - The top-level nodes of the program.
+///
+/// # Returns
+///
+/// The program's AST, or every error found in it.
+pub fn program(items: Vec<ASTNode>) -> Result<AST, Vec<ErrorType>> {
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_children(items);
+
+    let errors = validate(&root);
+    if errors.is_empty() {
+        Ok(AST::new(root))
+    } else {
+        Err(errors)
+    }
+}
+
+/// Builds a reference to a variable or function by name.
+pub fn identifier(name: &str) -> ASTNode {
+    ASTNode::new(NodeType::Identifier(name.to_string()))
+}
+
+/// Builds a literal as written in source, such as `3`, `2.5`, or `'c'`.
+pub fn literal(value: &str) -> ASTNode {
+    ASTNode::new(NodeType::Literal(value.to_string()))
+}
+
+/// Builds a string literal with the given contents, which are not escaped. The literal is written as in source, with
+/// its special characters escaped.
+pub fn string(value: &str) -> ASTNode {
+    literal(&format!("\"{}\"", ByteString::from(value).escaped()))
+}
+
+/// Builds the binary expression `left op right`.
+pub fn binary(left: ASTNode, op: &str, right: ASTNode) -> ASTNode {
+    let mut expression = ASTNode::new(NodeType::BinaryExpression);
+    expression.add_child(left);
+    expression.add_child(ASTNode::new(NodeType::Operator(op.to_string())));
+    expression.add_child(right);
+    expression
+}
+
+/// Builds the unary expression `op operand`.
+pub fn unary(op: &str, operand: ASTNode) -> ASTNode {
+    let mut expression = ASTNode::new(NodeType::UnaryExpression);
+    expression.add_child(ASTNode::new(NodeType::Operator(op.to_string())));
+    expression.add_child(operand);
+    expression
+}
+
+/// Builds the postfix increment or decrement `operand op`, where `op` is `++` or `--`.
+pub fn postfix(operand: ASTNode, op: &str) -> ASTNode {
+    let mut expression = ASTNode::new(NodeType::PostfixExpression);
+    expression.add_child(operand);
+    expression.add_child(ASTNode::new(NodeType::Operator(op.to_string())));
+    expression
+}
+
+/// Builds the conditional expression `condition ? then_branch : else_branch`.
+pub fn ternary(condition: ASTNode, then_branch: ASTNode, else_branch: ASTNode) -> ASTNode {
+    let mut expression = ASTNode::new(NodeType::TernaryExpression);
+    expression.add_child(condition);
+    expression.add_child(then_branch);
+    expression.add_child(else_branch);
+    expression
+}
+
+/// Builds the call `name(args...)`.
+pub fn call(name: &str, args: Vec<ASTNode>) -> ASTNode {
+    let mut call = ASTNode::new(NodeType::FunctionCall);
+    call.add_child(identifier(name));
+    call.add_children(args);
+    call
+}
+
+/// Builds the member access `base.field`, or `base->field` when `op` is `->`.
+pub fn member(base: ASTNode, op: &str, field: &str) -> ASTNode {
+    let mut access = ASTNode::new(NodeType::MemberAccess);
+    access.add_child(base);
+    access.add_child(ASTNode::new(NodeType::Operator(op.to_string())));
+    access.add_child(identifier(field));
+    access
+}
+
+/// Builds a statement that evaluates an expression for its side effects, such as a call.
+pub fn expression_statement(expression: ASTNode) -> ASTNode {
+    let mut statement = ASTNode::new(NodeType::ExpressionStatement);
+    statement.add_child(expression);
+    statement
+}
+
+/// Builds the declaration `type name;`, or `type name = value;` with an initializer.
+pub fn declare(name: &str, data_type: DataType, value: Option<ASTNode>) -> ASTNode {
+    let mut variable = ASTNode::new(NodeType::Variable);
+    variable.add_child(identifier(name));
+    variable.add_child(ASTNode::new(NodeType::Type(data_type)));
+    let mut initialization = ASTNode::new(NodeType::Initialization);
+    initialization.add_child(variable);
+    if let Some(value) = value {
+        let mut assigned_value = ASTNode::new(NodeType::AssignedValue);
+        assigned_value.add_child(value);
+        initialization.add_child(assigned_value);
+    }
+    initialization
+}
+
+/// Builds the assignment `name = value;`.
+pub fn assign(name: &str, value: ASTNode) -> ASTNode {
+    let mut assignment = ASTNode::new(NodeType::Assignment);
+    assignment.add_child(identifier(name));
+    assignment.add_child(value);
+    assignment
+}
+
+/// Builds the compound assignment `name op value`, such as `x += 5`.
+pub fn compound_assign(name: &str, op: &str, value: ASTNode) -> ASTNode {
+    let mut assignment = ASTNode::new(NodeType::CompoundAssignment);
+    assignment.add_child(identifier(name));
+    assignment.add_child(ASTNode::new(NodeType::Operator(op.to_string())));
+    assignment.add_child(value);
+    assignment
+}
+
+/// Builds `return;`, or `return value;` with a value.
+pub fn ret(value: Option<ASTNode>) -> ASTNode {
+    let mut return_node = ASTNode::new(NodeType::Return);
+    if let Some(value) = value {
+        let mut assigned_value = ASTNode::new(NodeType::AssignedValue);
+        assigned_value.add_child(value);
+        return_node.add_child(assigned_value);
+    }
+    return_node
+}
+
+/// Builds a block holding the given statements.
+pub fn block(statements: Vec<ASTNode>) -> ASTNode {
+    let mut block = ASTNode::new(NodeType::BlockExpression);
+    block.add_children(statements);
+    block
+}
+
+/// Builds `if (condition) { then } else { otherwise }`, without the `else` when `otherwise` is `None`.
+pub fn if_else(condition: ASTNode, then: Vec<ASTNode>, otherwise: Option<Vec<ASTNode>>) -> ASTNode {
+    let mut if_statement = ASTNode::new(NodeType::IfStatement);
+    if_statement.add_child(wrap_condition(condition));
+    if_statement.add_child(block(then));
+    if let Some(otherwise) = otherwise {
+        if_statement.add_child(block(otherwise));
+    }
+    if_statement
+}
+
+/// Builds `while (condition) { body }`.
+pub fn while_loop(condition: ASTNode, body: Vec<ASTNode>) -> ASTNode {
+    let mut while_loop = ASTNode::new(NodeType::WhileLoop);
+    while_loop.add_child(wrap_condition(condition));
+    while_loop.add_child(block(body));
+    while_loop
+}
+
+fn wrap_condition(expression: ASTNode) -> ASTNode {
+    let mut condition = ASTNode::new(NodeType::Condition);
+    condition.add_child(expression);
+    condition
+}
+
+/// Checks whether a name can name a variable, parameter, or function.
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !RESERVED_KEYWORDS.contains(&name)
+}
+
+/// Builds the error reporting a malformed node.
+fn malformed(message: String) -> ErrorType {
+    ErrorType::SyntaxError { message }
+}
+
+/// Validates a node and its children, checking that names are identifiers, operators exist, and each kind of node
+/// has the children the parser gives it. Nodes of kinds not listed here are only checked through their children.
+///
+/// # Parameters
+///
+/// * `node` - The node to validate.
+///
+/// # Returns
+///
+/// The errors found, in program order.
+pub fn validate(node: &ASTNode) -> Vec<ErrorType> {
+    let mut errors = Vec::new();
+    validate_into(node, &mut errors);
+    errors
+}
+
+fn validate_into(node: &ASTNode, errors: &mut Vec<ErrorType>) {
+    let children = node.get_children();
+    let kinds: Vec<NodeType> = children.iter().map(|c| c.get_node_type()).collect();
+    let is_operator = |kind: Option<&NodeType>, operators: &[&str]| {
+        matches!(kind, Some(NodeType::Operator(op)) if operators.contains(&op.as_str()))
+    };
+
+    match node.get_node_type() {
+        NodeType::Identifier(name) if RESERVED_KEYWORDS.contains(&name.as_str()) => {
+            errors.push(ErrorType::ReservedKeyword { keyword: name });
+        }
+        NodeType::Identifier(name) if !is_valid_name(&name) => {
+            errors.push(malformed(format!("'{}' is not a valid identifier", name)));
+        }
+        NodeType::Literal(value) if value.is_empty() => errors.push(malformed("Empty literal".to_string())),
+        NodeType::TopLevelExpression => {
+            let mut functions = HashSet::new();
+            for child in &children {
+                let name = child.get_children().first().map(|c| c.get_node_type());
+                if let (NodeType::FunctionDeclaration, Some(NodeType::Identifier(name))) = (child.get_node_type(), name) {
+                    if !functions.insert(name.clone()) {
+                        errors.push(malformed(format!("Function '{}' is declared more than once", name)));
+                    }
+                }
+            }
+        }
+        NodeType::FunctionDeclaration | NodeType::FunctionPrototype => {
+            let has_body = node.get_node_type() == NodeType::FunctionDeclaration;
+            let parameters = kinds.iter().skip(1).take_while(|kind| **kind == NodeType::Parameter).count();
+            // A variadic function is marked after its parameters
+            let signature = parameters + usize::from(kinds.get(parameters + 1) == Some(&NodeType::Variadic));
+            let well_formed = children.len() == signature + 2 + usize::from(has_body)
+                && matches!(kinds[0], NodeType::Identifier(_))
+                && matches!(kinds[signature + 1], NodeType::Type(_))
+                && (!has_body || kinds[signature + 2] == NodeType::BlockExpression);
+            if !well_formed && has_body {
+                errors.push(malformed("A function declaration needs a name, parameters, a type, and a body".into()));
+            } else if !well_formed {
+                errors.push(malformed("A function prototype needs a name, parameters, and a type".into()));
+            }
+            let mut names = HashSet::new();
+            for parameter in children.iter().skip(1).take(parameters) {
+                let parameter = parameter.get_children();
+                if let Some(NodeType::Type(DataType::Void)) = parameter.get(1).map(|c| c.get_node_type()) {
+                    errors.push(malformed("A parameter cannot have type void".to_string()));
+                }
+                if let Some(NodeType::Identifier(name)) = parameter.first().map(|c| c.get_node_type()) {
+                    if !names.insert(name.clone()) {
+                        errors.push(malformed(format!("Parameter '{}' is declared more than once", name)));
+                    }
+                }
+            }
+        }
+        NodeType::Parameter | NodeType::Variable
+            if !(kinds.len() == 2 && matches!(kinds[0], NodeType::Identifier(_)) && matches!(kinds[1], NodeType::Type(_))) =>
+        {
+            errors.push(malformed("A parameter or variable needs a name and a type".to_string()));
+        }
+        NodeType::Initialization => {
+            let well_formed = kinds.first() == Some(&NodeType::Variable)
+                && (kinds.len() == 1 || (kinds.len() == 2 && kinds[1] == NodeType::AssignedValue));
+            if !well_formed {
+                errors.push(malformed("A declaration needs a variable and an optional initializer".to_string()));
+            }
+        }
+        NodeType::AssignedValue | NodeType::Condition | NodeType::ExpressionStatement if children.len() != 1 => {
+            errors.push(malformed(format!("{} needs exactly one expression", node.get_node_type())));
+        }
+        NodeType::Return if !(kinds.is_empty() || kinds == [NodeType::AssignedValue]) => {
+            errors.push(malformed("A return needs at most one value".to_string()));
+        }
+        NodeType::Assignment if children.len() != 2 => {
+            errors.push(malformed("An assignment needs a target and a value".to_string()));
+        }
+        NodeType::CompoundAssignment
+            if !(children.len() == 3 && is_operator(kinds.get(1), COMPOUND_ASSIGNMENT_OPERATORS)) =>
+        {
+            errors.push(malformed("A compound assignment needs a target, an assignment operator and a value".to_string()));
+        }
+        NodeType::BinaryExpression if !(children.len() == 3 && is_operator(kinds.get(1), BINARY_OPERATORS)) => {
+            errors.push(malformed("A binary expression needs two operands around a binary operator".to_string()));
+        }
+        NodeType::UnaryExpression if !(children.len() == 2 && is_operator(kinds.first(), UNARY_OPERATORS)) => {
+            errors.push(malformed("A unary expression needs a unary operator and an operand".to_string()));
+        }
+        NodeType::PostfixExpression if !(children.len() == 2 && is_operator(kinds.get(1), &["++", "--"])) => {
+            errors.push(malformed("A postfix expression needs an operand and `++` or `--`".to_string()));
+        }
+        NodeType::TernaryExpression if children.len() != 3 => {
+            errors.push(malformed("A conditional expression needs a condition and two branches".to_string()));
+        }
+        NodeType::MemberAccess
+            if !(children.len() == 3 && is_operator(kinds.get(1), &[".", "->"]) && matches!(kinds[2], NodeType::Identifier(_))) =>
+        {
+            errors.push(malformed("A member access needs a base, `.` or `->`, and a field name".to_string()));
+        }
+        NodeType::FunctionCall if !matches!(kinds.first(), Some(NodeType::Identifier(_))) => {
+            errors.push(malformed("A call needs the name of the function it calls".to_string()));
+        }
+        NodeType::IfStatement => {
+            let branches = kinds.iter().skip(2).take_while(|kind| **kind == NodeType::ElseIf).count();
+            let well_formed = children.len() >= 2
+                && kinds[0] == NodeType::Condition
+                && kinds[1] == NodeType::BlockExpression
+                && kinds[2 + branches..].iter().all(|kind| *kind == NodeType::BlockExpression)
+                && children.len() <= branches + 3;
+            if !well_formed {
+                errors.push(malformed(
+                    "An if statement needs a condition, a block, any else ifs, and an optional else".to_string(),
+                ));
+            }
+        }
+        NodeType::ElseIf if kinds != [NodeType::Condition, NodeType::BlockExpression] => {
+            errors.push(malformed("An else if needs a condition and a block".to_string()));
+        }
+        NodeType::WhileLoop if kinds != [NodeType::Condition, NodeType::BlockExpression] => {
+            errors.push(malformed("A while loop needs a condition and a block".to_string()));
+        }
+        _ => {}
+    }
+
+    for (index, child) in children.iter().enumerate() {
+        // The condition of `for (;;)` is empty, and has nothing else to check
+        let empty_for_condition = node.get_node_type() == NodeType::ForLoop && index == 1 && child.get_children().is_empty();
+        if !empty_for_condition {
+            validate_into(child, errors);
+        }
+    }
+}
@@ -2,8 +2,9 @@
 //! and the nodes that make up the ASTs.
 
 use core::fmt;
-use std::cmp::{Eq, PartialEq};
+use std::{cmp::{Eq, PartialEq}, hash::{Hash, Hasher}};
 use crate::ast::node_type::NodeType;
+use crate::location::Span;
 
 /// The `AST` struct is an Abstract Syntax Tree.
 /// 
@@ -50,10 +51,16 @@ pub struct AST {
 /// # Fields
 /// * `element` - The syntactic element this node represents.
 /// * `children` - A vector of child nodes, which further define the structure of the syntax tree.
-#[derive(Debug, Clone, PartialEq, Default, Eq, Hash)]
+/// * `parenthesized` - Whether the expression this node represents was written in parentheses. Parentheses only
+///   group, so they do not take part in comparing or hashing nodes.
+/// * `span` - The range of the source the node was parsed from, or None if it was built in code or its tokens had no
+///   source ranges. Like parentheses, it does not take part in comparing or hashing nodes.
+#[derive(Debug, Clone, Default)]
 pub struct ASTNode {
     node_type: NodeType,
     children: Vec<ASTNode>, 
+    parenthesized: bool,
+    span: Option<Span>,
 }
 
 impl AST {
@@ -83,6 +90,11 @@ impl AST {
         self.root.clone()
     }
 
+    /// Borrows the root of the `AST` instance, for walks that should not clone the tree.
+    pub(crate) fn root(&self) -> &ASTNode {
+        &self.root
+    }
+
 }
 
 /// Formats an `AST` starting from a specific node and appends the formatted string to the provided output string.
@@ -136,6 +148,8 @@ impl ASTNode {
         ASTNode {
             node_type,
             children: Vec::new(),
+            parenthesized: false,
+            span: None,
         }
     }
 
@@ -159,6 +173,11 @@ impl ASTNode {
         self.children.clone()
     }
 
+    /// Borrows the children of the given `ASTNode` instance, for walks that should not clone the tree.
+    pub(crate) fn children(&self) -> &[ASTNode] {
+        &self.children
+    }
+
     /// Sets the children of this `ASTNode` to a specified vector.
     /// 
     /// # Parameters
@@ -189,6 +208,67 @@ impl ASTNode {
         self.children.extend(to_add);
     }
 
+    /// Builds a copy of this `ASTNode` with other children, keeping its node type, whether it was parenthesized, and
+    /// its source range, so that passes rewriting a node's children do not lose where it came from.
+    ///
+    /// # Parameters
+    ///
+    /// - `children`: The children of the copy.
+    ///
+    /// # Returns
+    ///
+    /// Returns the copy.
+    ///
+    pub fn with_children(&self, children: Vec<ASTNode>) -> ASTNode {
+        ASTNode {
+            node_type: self.node_type.clone(),
+            children,
+            parenthesized: self.parenthesized,
+            span: self.span,
+        }
+    }
+
+    /// Checks if the expression this `ASTNode` represents was written in parentheses, as in `(a + b) * c`.
+    ///
+    /// # Returns
+    ///
+    /// Returns boolean `true` if the expression was parenthesized, `false` otherwise.
+    ///
+    pub fn is_parenthesized(&self) -> bool {
+        self.parenthesized
+    }
+
+    /// Records whether the expression this `ASTNode` represents was written in parentheses.
+    ///
+    /// # Parameters
+    ///
+    /// - `parenthesized`: Whether the expression was parenthesized.
+    ///
+    pub fn set_parenthesized(&mut self, parenthesized: bool) {
+        self.parenthesized = parenthesized;
+    }
+
+    /// Retrieves the range of the source this `ASTNode` was parsed from, so that diagnostics about it can point at
+    /// the code it came from.
+    ///
+    /// # Returns
+    ///
+    /// Returns the node's source range, or None if it was built in code or parsed from tokens without source ranges.
+    ///
+    pub fn get_span(&self) -> Option<Span> {
+        self.span
+    }
+
+    /// Records the range of the source this `ASTNode` was parsed from.
+    ///
+    /// # Parameters
+    ///
+    /// - `span`: The node's source range.
+    ///
+    pub fn set_span(&mut self, span: Span) {
+        self.span = Some(span);
+    }
+
     /// Checks if this `ASTNode` is a return statement.
     /// 
     /// # Returns
@@ -205,6 +285,24 @@ impl ASTNode {
     }
 }
 
+/// Compares the node types and children of two `ASTNode`s, ignoring whether either was parenthesized and where
+/// either was parsed from.
+impl PartialEq for ASTNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.node_type == other.node_type && self.children == other.children
+    }
+}
+
+impl Eq for ASTNode {}
+
+/// Hashes the node type and children of an `ASTNode`, consistently with its `PartialEq` implementation.
+impl Hash for ASTNode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.node_type.hash(state);
+        self.children.hash(state);
+    }
+}
+
 /// Provides a display implementation for `ASTNode`.
 ///
 /// # Parameters
@@ -34,6 +34,16 @@ pub enum DataType {
     Unsign,
     /// A signed integer.
     Sign,
+    /// An unsigned long integer.
+    UnsignedLong,
+    /// A long long integer.
+    LongLong,
+    /// An unsigned long long integer.
+    UnsignedLongLong,
+    /// A signed character.
+    SignedChar,
+    /// An unsigned character.
+    UnsignedChar,
 }
 
 /// Provides a display implementation for `DataType`.
@@ -60,6 +70,11 @@ impl fmt::Display for DataType {
             DataType::Void => write!(f, "Void"),
             DataType::Unsign => write!(f, "Unsigned"),
             DataType::Sign => write!(f, "Signed"),
+            DataType::UnsignedLong => write!(f, "UnsignedLong"),
+            DataType::LongLong => write!(f, "LongLong"),
+            DataType::UnsignedLongLong => write!(f, "UnsignedLongLong"),
+            DataType::SignedChar => write!(f, "SignedChar"),
+            DataType::UnsignedChar => write!(f, "UnsignedChar"),
         }
     }
 }
@@ -0,0 +1,298 @@
+//! A compact binary encoding of ASTs, for caches that keep the result of parsing between compilations, which is
+//! much faster to read back than re-parsing the source.
+//!
+//! An encoding starts with a magic number and a format version, followed by a table of the strings it uses, each
+//! written once however often it occurs, and then the encoded values. A node is its tag, its payload if it has one,
+//! and the number of its children, followed by its children in order, except that a node without children, as about
+//! half of them are, has its tag marked instead of a count written. Numbers are written as LEB128 variable-length
+//! integers, so that small ones, which most are, take a single byte:
+//!
+//! ```
+//! use common::ast::{build, core::AST, encode};
+//!
+//! let ast = AST::new(build::block(vec![build::assign("x", build::binary(build::identifier("x"), "+", build::literal("1")))]));
+//! let bytes = encode::encode(&ast);
+//! assert_eq!(encode::decode(&bytes), Some(ast));
+//! ```
+//!
+//! Whether an expression was written in parentheses is not encoded. It only matters to lints, which run on the
+//! parsed AST before it is cached, and nodes that differ in it alone are equal.
+//!
+//! `Encoder` and `Decoder` are public so that other caches, such as the one for symbol tables, can write their own
+//! values in the same format. An encoding that is truncated, corrupted, or of another version is rejected rather than
+//! decoded into a different AST, and the source should then be parsed again.
+//!
+//! The `schema` module describes the format for consumers outside this crate, and tells encodings of another version
+//! apart from corrupted ones.
+
+use std::collections::HashMap;
+
+use crate::ast::{
+    core::{ASTNode, AST},
+    data_type::DataType,
+    node_type::NodeType,
+};
+
+/// The bytes every encoding starts with.
+pub(crate) const MAGIC: &[u8] = b"SIAST";
+
+/// The version of the format, which changes whenever encodings of an older version would be decoded differently.
+pub(crate) const VERSION: u8 = 9;
+
+/// The node types without a payload, tagged by their index. New ones are only added at the end, with a new version.
+pub(crate) const UNIT_NODES: &[NodeType] = &[
+    NodeType::NoExpression, NodeType::IfStatement, NodeType::ElseStatement, NodeType::ForLoop, NodeType::WhileLoop,
+    NodeType::DoWhileLoop, NodeType::Break, NodeType::Continue, NodeType::Return, NodeType::SwitchStatement,
+    NodeType::Case, NodeType::Default, NodeType::Assignment, NodeType::Initialization, NodeType::FunctionDeclaration,
+    NodeType::StructDeclaration, NodeType::EnumDeclaration, NodeType::ModuleExpression, NodeType::TopLevelExpression,
+    NodeType::BlockExpression, NodeType::Condition, NodeType::Action, NodeType::Variant, NodeType::AssignedValue,
+    NodeType::Field, NodeType::Parameter, NodeType::Variable, NodeType::BinaryExpression, NodeType::UnaryExpression,
+    NodeType::FunctionCall, NodeType::ExpressionStatement, NodeType::Operand, NodeType::LoopInitializer,
+    NodeType::LoopIncrement, NodeType::TypeDefinition, NodeType::MemberAccess, NodeType::PostfixExpression,
+    NodeType::TernaryExpression, NodeType::CompoundAssignment, NodeType::UnionDeclaration,
+    NodeType::FunctionPrototype, NodeType::ElseIf, NodeType::Variadic,
+];
+
+/// The tags of the node types with a payload, kept apart from the tags of `UNIT_NODES` to leave room for new ones.
+pub(crate) const LITERAL_TAG: u8 = 100;
+pub(crate) const IDENTIFIER_TAG: u8 = 101;
+pub(crate) const OPERATOR_TAG: u8 = 102;
+pub(crate) const TYPE_TAG: u8 = 103;
+pub(crate) const QUALIFIER_TAG: u8 = 104;
+pub(crate) const CONSTANT_TAG: u8 = 105;
+pub(crate) const ATTRIBUTE_TAG: u8 = 106;
+
+/// Marks the tag of a node without children.
+pub(crate) const LEAF: u8 = 0x80;
+
+/// The data types, tagged by their index.
+pub(crate) const DATA_TYPES: &[DataType] = &[
+    DataType::Integer, DataType::Float, DataType::Double, DataType::Long, DataType::Boolean, DataType::String,
+    DataType::Char, DataType::Function, DataType::Struct, DataType::Enum, DataType::None, DataType::Void,
+    DataType::Unsign, DataType::Sign, DataType::UnsignedLong, DataType::LongLong, DataType::UnsignedLongLong,
+    DataType::SignedChar, DataType::UnsignedChar,
+];
+
+/// Writes values in the binary format.
+#[derive(Debug, Default)]
+pub struct Encoder {
+    /// The strings written so far, in the order they were first written.
+    strings: Vec<String>,
+    /// The index of each string in `strings`.
+    indices: HashMap<String, usize>,
+    bytes: Vec<u8>,
+}
+
+impl Encoder {
+    /// Starts an encoding with nothing written.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes a length, count, or other unsigned number.
+    pub fn write_usize(&mut self, mut value: usize) {
+        while value >= 0x80 {
+            self.bytes.push((value as u8 & 0x7f) | 0x80);
+            value >>= 7;
+        }
+        self.bytes.push(value as u8);
+    }
+
+    /// Writes a string, as its index in the string table.
+    pub fn write_str(&mut self, value: &str) {
+        let index = match self.indices.get(value) {
+            Some(index) => *index,
+            None => {
+                self.strings.push(value.to_string());
+                self.indices.insert(value.to_string(), self.strings.len() - 1);
+                self.strings.len() - 1
+            }
+        };
+        self.write_usize(index);
+    }
+
+    /// Writes a data type.
+    pub fn write_data_type(&mut self, data_type: DataType) {
+        let tag = DATA_TYPES.iter().position(|candidate| *candidate == data_type).expect("every data type has a tag");
+        self.bytes.push(tag as u8);
+    }
+
+    /// Writes a node and its children.
+    pub fn write_node(&mut self, node: &ASTNode) {
+        let leaf = if node.children().is_empty() { LEAF } else { 0 };
+        match &node.get_node_type() {
+            NodeType::Literal(value) => self.write_tagged(LITERAL_TAG | leaf, value),
+            NodeType::Identifier(name) => self.write_tagged(IDENTIFIER_TAG | leaf, name),
+            NodeType::Operator(op) => self.write_tagged(OPERATOR_TAG | leaf, op),
+            NodeType::Qualifier(qualifier) => self.write_tagged(QUALIFIER_TAG | leaf, qualifier),
+            NodeType::Constant(value) => self.write_tagged(CONSTANT_TAG | leaf, value),
+            NodeType::Attribute(name) => self.write_tagged(ATTRIBUTE_TAG | leaf, name),
+            NodeType::Type(data_type) => {
+                self.bytes.push(TYPE_TAG | leaf);
+                self.write_data_type(*data_type);
+            }
+            unit => {
+                let tag = UNIT_NODES.iter().position(|candidate| candidate == unit).expect("every node type has a tag");
+                self.bytes.push(tag as u8 | leaf);
+            }
+        }
+        if leaf == 0 {
+            self.write_usize(node.children().len());
+        }
+        for child in node.children() {
+            self.write_node(child);
+        }
+    }
+
+    fn write_tagged(&mut self, tag: u8, value: &str) {
+        self.bytes.push(tag);
+        self.write_str(value);
+    }
+
+    /// Ends the encoding.
+    ///
+    /// # Returns
+    ///
+    /// The header and string table followed by the values written.
+    pub fn finish(self) -> Vec<u8> {
+        let mut header = Encoder::new();
+        header.bytes.extend_from_slice(MAGIC);
+        header.bytes.push(VERSION);
+        header.write_usize(self.strings.len());
+        for string in &self.strings {
+            header.write_usize(string.len());
+            header.bytes.extend_from_slice(string.as_bytes());
+        }
+        header.bytes.extend(self.bytes);
+        header.bytes
+    }
+}
+
+/// Reads values written by an `Encoder`, in the order they were written. Every read returns None if the encoding
+/// does not hold a valid value there.
+#[derive(Debug)]
+pub struct Decoder<'a> {
+    strings: Vec<&'a str>,
+    bytes: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    /// Starts reading an encoding after its header and string table.
+    ///
+    /// # Parameters
+    ///
+    /// * `bytes` - The encoding.
+    ///
+    /// # Returns
+    ///
+    /// The decoder, or None if the encoding is not of this format or is of a later version.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        let mut decoder = Decoder { strings: Vec::new(), bytes: bytes.strip_prefix(MAGIC)? };
+        // Nodes are only added to later versions, so an encoding of an earlier version decodes the same
+        if !(1..=VERSION).contains(&decoder.read_byte()?) {
+            return None;
+        }
+        for _ in 0..decoder.read_usize()? {
+            let len = decoder.read_usize()?;
+            if len > decoder.bytes.len() {
+                return None;
+            }
+            let (string, rest) = decoder.bytes.split_at(len);
+            decoder.strings.push(std::str::from_utf8(string).ok()?);
+            decoder.bytes = rest;
+        }
+        Some(decoder)
+    }
+
+    /// Checks whether every value of the encoding has been read.
+    pub fn is_finished(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    fn read_byte(&mut self) -> Option<u8> {
+        let (byte, rest) = self.bytes.split_first()?;
+        self.bytes = rest;
+        Some(*byte)
+    }
+
+    /// Reads a number written by `Encoder::write_usize`.
+    pub fn read_usize(&mut self) -> Option<usize> {
+        let mut value: usize = 0;
+        for shift in (0..usize::BITS).step_by(7) {
+            let byte = self.read_byte()?;
+            value |= usize::from(byte & 0x7f).checked_shl(shift)?;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    /// Reads a string written by `Encoder::write_str`.
+    pub fn read_str(&mut self) -> Option<&'a str> {
+        let index = self.read_usize()?;
+        self.strings.get(index).copied()
+    }
+
+    /// Reads a data type written by `Encoder::write_data_type`.
+    pub fn read_data_type(&mut self) -> Option<DataType> {
+        DATA_TYPES.get(usize::from(self.read_byte()?)).copied()
+    }
+
+    /// Reads a node and its children written by `Encoder::write_node`.
+    pub fn read_node(&mut self) -> Option<ASTNode> {
+        let tag = self.read_byte()?;
+        let node_type = match tag & !LEAF {
+            LITERAL_TAG => NodeType::Literal(self.read_str()?.to_string()),
+            IDENTIFIER_TAG => NodeType::Identifier(self.read_str()?.to_string()),
+            OPERATOR_TAG => NodeType::Operator(self.read_str()?.to_string()),
+            QUALIFIER_TAG => NodeType::Qualifier(self.read_str()?.to_string()),
+            CONSTANT_TAG => NodeType::Constant(self.read_str()?.to_string()),
+            ATTRIBUTE_TAG => NodeType::Attribute(self.read_str()?.to_string()),
+            TYPE_TAG => NodeType::Type(self.read_data_type()?),
+            tag => UNIT_NODES.get(usize::from(tag))?.clone(),
+        };
+        let mut node = ASTNode::new(node_type);
+        if tag & LEAF != 0 {
+            return Some(node);
+        }
+        let count = self.read_usize()?;
+        // Each child takes at least one byte, which bounds what a corrupted count can allocate
+        let mut children = Vec::with_capacity(count.min(self.bytes.len()));
+        for _ in 0..count {
+            children.push(self.read_node()?);
+        }
+        node.set_children(children);
+        Some(node)
+    }
+}
+
+/// Encodes an AST.
+///
+/// # Parameters
+///
+/// * `ast` - The AST to encode.
+///
+/// # Returns
+///
+/// The encoding.
+pub fn encode(ast: &AST) -> Vec<u8> {
+    let mut encoder = Encoder::new();
+    encoder.write_node(ast.root());
+    encoder.finish()
+}
+
+/// Decodes an AST encoded by `encode`.
+///
+/// # Parameters
+///
+/// * `bytes` - The encoding.
+///
+/// # Returns
+///
+/// The AST, or None if the bytes are not exactly the encoding of an AST in this format and version.
+pub fn decode(bytes: &[u8]) -> Option<AST> {
+    let mut decoder = Decoder::new(bytes)?;
+    let root = decoder.read_node()?;
+    decoder.is_finished().then(|| AST::new(root))
+}
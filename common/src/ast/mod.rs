@@ -9,3 +9,15 @@ pub mod data_type;
 
 /// Defines different types of syntax elements.
 pub mod node_type;
+
+/// Builds well-formed ASTs in code, for code generators that do not go through source text.
+pub mod build;
+
+/// Rewrites ASTs by replacing, inserting, and deleting nodes, for refactoring tools.
+pub mod rewrite;
+
+/// Encodes ASTs in a compact binary format, for caches that keep them between compilations.
+pub mod encode;
+
+/// Describes the binary AST format as a versioned schema, and checks the version of encodings.
+pub mod schema;
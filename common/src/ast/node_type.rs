@@ -13,7 +13,8 @@ pub enum NodeType {
     #[default]
     NoExpression,
 
-    /// A literal value, stored as a string.
+    /// A literal value, stored as a string. String literals are stored quoted and escaped as in source, since their
+    /// contents are bytes that need not be valid UTF-8.
     Literal(String),
 
     /// An identifier, such as a variable name, stored as a string.
@@ -25,12 +26,24 @@ pub enum NodeType {
     /// A data type, linking directly to the `DataType` enum.
     Type(DataType),
 
+    /// A type qualifier such as `volatile`, stored as a string and attached as a child of a `Type` node.
+    Qualifier(String),
+
     /// A constant value, stored as a string.
     Constant(String),
 
-    /// An `if` statement.
+    /// An `if` statement. Its children are its condition, its block, an `ElseIf` for each `else if` that follows it,
+    /// and the block of its final `else`, if it has one.
     IfStatement,
 
+    /// Marks a function as variadic, taking any number of arguments after its parameters, as a trailing `...` does
+    /// in C. It follows the parameters of a `FunctionDeclaration` or `FunctionPrototype`.
+    Variadic,
+
+    /// An `else if` branch of an `if` statement, whose children are its condition and its block. The branches of an
+    /// `else if` chain are kept in one flat list rather than nested in each other's `else`.
+    ElseIf,
+
     /// An `else` statement.
     ElseStatement,
 
@@ -64,16 +77,30 @@ pub enum NodeType {
     /// An assignment of a value to an existing variable.
     Assignment,
 
+    /// A compound assignment such as `x += 5`, with the target, the operator, and the value combined with the
+    /// target's current value.
+    CompoundAssignment,
+
     /// The initialization of a variable.
     Initialization,
 
     /// A function declaration.
     FunctionDeclaration,
 
-    /// A struct declaration.
+    /// A function prototype, which declares a function without a body so it can be called before it is defined or
+    /// defined elsewhere. Its children are the function's name, its parameters, and its return type.
+    FunctionPrototype,
+
+    /// A struct declaration. A struct declared without a name inside another struct is an anonymous member, whose
+    /// fields are accessed as fields of the struct containing it.
     StructDeclaration,
 
-    /// An enum declaration.
+    /// An anonymous union inside a struct, whose fields share storage and are accessed as fields of the struct
+    /// containing it.
+    UnionDeclaration,
+
+    /// An enum declaration, whose children are its name, the `Type` its values are stored as if one is chosen, and
+    /// its variants.
     EnumDeclaration,
 
     /// A module-level expression, often used for scoping.
@@ -112,9 +139,23 @@ pub enum NodeType {
     /// A unary expression, involving one operand and an operator.
     UnaryExpression,
 
+    /// A postfix increment or decrement, with the operand and then the operator `++` or `--`, whose value is that of
+    /// the operand before it changes.
+    PostfixExpression,
+
+    /// A conditional expression `condition ? then : else`, with the condition and then the two branches, of which
+    /// only the one the condition picks is evaluated.
+    TernaryExpression,
+
     /// A function call.
     FunctionCall,
 
+    /// An access to a member of a struct, with the base expression, the operator `.` or `->`, and the field name.
+    MemberAccess,
+
+    /// An expression evaluated as a statement for its effects, such as `foo(x);`.
+    ExpressionStatement,
+
     /// An operand in an expression.
     Operand,
 
@@ -123,6 +164,12 @@ pub enum NodeType {
 
     /// The increment section of a loop.
     LoopIncrement,
+
+    /// A `typedef` type alias declaration.
+    TypeDefinition,
+
+    /// An attribute such as `@packed` attached to a declaration, stored with its name.
+    Attribute(String),
 }
 
 /// Provides a display implementation for `DataType`.
@@ -139,6 +186,8 @@ impl fmt::Display for NodeType {
             NodeType::Identifier(id) => write!(f, "Identifier({})", id),
             NodeType::Operator(op) => write!(f, "Operator({})", op),
             NodeType::Type(data_type) => write!(f, "Type({})", data_type),
+            NodeType::Qualifier(qualifier) => write!(f, "Qualifier({})", qualifier),
+            NodeType::Attribute(name) => write!(f, "Attribute({})", name),
             NodeType::Constant(value) => write!(f, "Constant({})", value),
             _ => write!(f, "{:?}", self) 
         }
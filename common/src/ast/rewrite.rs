@@ -0,0 +1,232 @@
+//! Provides an API for rewriting ASTs, which refactoring tools such as renaming a symbol or extracting a function
+//! build on.
+//!
+//! A node is addressed by its path, the index of each child taken on the way down from the root, and `find` gives
+//! the paths of the nodes matching a predicate. Edits are gathered in a `Transaction`, all addressed by paths into
+//! the AST as it was before any of them, and `commit` applies them together to a copy of that AST. Either every edit
+//! applies or none does:
+//!
+//! ```
+//! use common::ast::{build, core::AST, node_type::NodeType, rewrite::{self, Transaction}};
+//!
+//! let ast = AST::new(build::block(vec![build::assign("x", build::identifier("x"))]));
+//! let renames = rewrite::find(&ast, |node| node.get_node_type() == NodeType::Identifier("x".to_string()));
+//! let renamed = renames.iter()
+//!     .fold(Transaction::new(), |transaction, path| transaction.replace(path, build::identifier("y")))
+//!     .commit(&ast)
+//!     .expect("every path is in the AST");
+//! assert_eq!(renamed, AST::new(build::block(vec![build::assign("y", build::identifier("y"))])));
+//! ```
+//!
+//! Nodes own their children, so a rewritten AST has no links to keep up to date: each new node is simply a child of
+//! the node it was put under. Inserted and replacement nodes are validated as `build` validates nodes.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::{
+    ast::{build::validate, core::{ASTNode, AST}},
+    error::ErrorType,
+};
+
+/// An edit of a transaction.
+#[derive(Debug, Clone)]
+enum Edit {
+    /// Replaces the node at the path.
+    Replace(Vec<usize>, ASTNode),
+    /// Inserts a node among the children of the node at the path, before the child at the index.
+    Insert(Vec<usize>, usize, ASTNode),
+    /// Deletes the node at the path.
+    Delete(Vec<usize>),
+}
+
+/// Gathers edits to an AST so that they are applied together.
+#[derive(Debug, Clone, Default)]
+pub struct Transaction {
+    edits: Vec<Edit>,
+}
+
+impl Transaction {
+    /// Starts a transaction with no edits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the node at a path, along with its children, by another node.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The path of the node to replace.
+    /// * `node` - The node that takes its place.
+    pub fn replace(mut self, path: &[usize], node: ASTNode) -> Self {
+        self.edits.push(Edit::Replace(path.to_vec(), node));
+        self
+    }
+
+    /// Inserts a node among the children of the node at a path. Nodes inserted at the same index keep the order
+    /// they were inserted in.
+    ///
+    /// # Parameters
+    ///
+    /// * `parent` - The path of the node to insert under.
+    /// * `index` - The index of the child to insert before, or the number of children to insert after the last.
+    /// * `node` - The node to insert.
+    pub fn insert(mut self, parent: &[usize], index: usize, node: ASTNode) -> Self {
+        self.edits.push(Edit::Insert(parent.to_vec(), index, node));
+        self
+    }
+
+    /// Deletes the node at a path, along with its children.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - The path of the node to delete.
+    pub fn delete(mut self, path: &[usize]) -> Self {
+        self.edits.push(Edit::Delete(path.to_vec()));
+        self
+    }
+
+    /// Applies the edits to a copy of an AST.
+    ///
+    /// # Parameters
+    ///
+    /// * `ast` - The AST the paths of the edits refer to.
+    ///
+    /// # Returns
+    ///
+    /// The rewritten AST, or every error found in the edits, in which case no edit is applied.
+    ///
+    /// # Errors
+    ///
+    /// * Returns a `SyntaxError` for a path with no node, an index past the children of a node, an edit of the
+    ///   root, two edits of the same node, or an edit inside a node that is replaced or deleted.
+    /// * Returns the errors `build::validate` finds in inserted and replacement nodes.
+    pub fn commit(&self, ast: &AST) -> Result<AST, Vec<ErrorType>> {
+        let root = ast.root();
+        let mut errors = Vec::new();
+        // The node replacing or deleting each replaced or deleted path
+        let mut removals: HashMap<&[usize], Option<&ASTNode>> = HashMap::new();
+        // The nodes inserted under each path, by the index they are inserted at
+        let mut insertions: HashMap<&[usize], BTreeMap<usize, Vec<&ASTNode>>> = HashMap::new();
+
+        for edit in &self.edits {
+            match edit {
+                Edit::Replace(path, _) | Edit::Delete(path) => {
+                    if path.is_empty() {
+                        errors.push(malformed("The root of an AST cannot be replaced or deleted".to_string()));
+                    } else if node_at(root, path).is_none() {
+                        errors.push(malformed(format!("No node at path {:?}", path)));
+                    } else if removals.contains_key(path.as_slice()) {
+                        errors.push(malformed(format!("The node at path {:?} is edited more than once", path)));
+                    } else {
+                        let replacement = match edit {
+                            Edit::Replace(_, node) => Some(node),
+                            _ => None,
+                        };
+                        removals.insert(path, replacement);
+                    }
+                }
+                Edit::Insert(parent, index, node) => match node_at(root, parent) {
+                    None => errors.push(malformed(format!("No node at path {:?}", parent))),
+                    Some(parent_node) if *index > parent_node.children().len() => errors.push(malformed(
+                        format!("Cannot insert at index {} of the node at path {:?}", index, parent),
+                    )),
+                    Some(_) => insertions.entry(parent).or_default().entry(*index).or_default().push(node),
+                },
+            }
+        }
+
+        // An edit inside a removed node would be lost with it
+        let removed: HashSet<&[usize]> = removals.keys().copied().collect();
+        for edit in &self.edits {
+            let (path, ancestors) = match edit {
+                Edit::Replace(path, _) | Edit::Delete(path) => (path, &path[..path.len().saturating_sub(1)]),
+                Edit::Insert(parent, _, _) => (parent, &parent[..]),
+            };
+            if (1..=ancestors.len()).any(|len| removed.contains(&ancestors[..len])) {
+                errors.push(malformed(format!("The edit at path {:?} is inside a node that is replaced or deleted", path)));
+            }
+        }
+
+        for edit in &self.edits {
+            if let Edit::Replace(_, node) | Edit::Insert(_, _, node) = edit {
+                errors.extend(validate(node));
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(AST::new(apply(root, &mut Vec::new(), &removals, &insertions)))
+    }
+}
+
+/// Rebuilds the node at `path` with the edits under it applied.
+fn apply(
+    node: &ASTNode,
+    path: &mut Vec<usize>,
+    removals: &HashMap<&[usize], Option<&ASTNode>>,
+    insertions: &HashMap<&[usize], BTreeMap<usize, Vec<&ASTNode>>>,
+) -> ASTNode {
+    let no_insertions = BTreeMap::new();
+    let inserted = insertions.get(path.as_slice()).unwrap_or(&no_insertions);
+    let children = node.children();
+    let mut rewritten = Vec::with_capacity(children.len());
+
+    for (index, child) in children.iter().enumerate() {
+        rewritten.extend(inserted.get(&index).into_iter().flatten().map(|node| (*node).clone()));
+        path.push(index);
+        match removals.get(path.as_slice()) {
+            Some(Some(replacement)) => rewritten.push((*replacement).clone()),
+            Some(None) => {}
+            None => rewritten.push(apply(child, path, removals, insertions)),
+        }
+        path.pop();
+    }
+    rewritten.extend(inserted.get(&children.len()).into_iter().flatten().map(|node| (*node).clone()));
+
+    node.with_children(rewritten)
+}
+
+/// Finds the node at a path.
+///
+/// # Parameters
+///
+/// * `root` - The node the path starts at.
+/// * `path` - The index of each child taken on the way down from `root`.
+///
+/// # Returns
+///
+/// The node at the path, or None if there is none.
+pub fn node_at<'a>(root: &'a ASTNode, path: &[usize]) -> Option<&'a ASTNode> {
+    path.iter().try_fold(root, |node, index| node.children().get(*index))
+}
+
+/// Finds the paths of the nodes of an AST that match a predicate.
+///
+/// # Parameters
+///
+/// * `ast` - The AST to search.
+/// * `predicate` - Whether a node matches.
+///
+/// # Returns
+///
+/// The paths of the matching nodes, with each node before its children and its children in order.
+pub fn find(ast: &AST, predicate: impl Fn(&ASTNode) -> bool) -> Vec<Vec<usize>> {
+    let mut paths = Vec::new();
+    find_into(ast.root(), &predicate, &mut Vec::new(), &mut paths);
+    paths
+}
+
+fn find_into(node: &ASTNode, predicate: &impl Fn(&ASTNode) -> bool, path: &mut Vec<usize>, paths: &mut Vec<Vec<usize>>) {
+    if predicate(node) {
+        paths.push(path.clone());
+    }
+    for (index, child) in node.children().iter().enumerate() {
+        path.push(index);
+        find_into(child, predicate, path, paths);
+        path.pop();
+    }
+}
+
+fn malformed(message: String) -> ErrorType {
+    ErrorType::SyntaxError { message }
+}
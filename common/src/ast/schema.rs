@@ -0,0 +1,206 @@
+//! A machine-readable description of the binary AST format written by `encode`, for consumers outside this crate.
+//!
+//! The schema lists every kind of node with its tag and the kind of payload it carries, and every data type with its
+//! tag, under the version of the format. Its fingerprint changes whenever any of them does, so a consumer that
+//! recorded the fingerprint of the schema it was written against can tell that the format has changed even before
+//! reading an encoding:
+//!
+//! ```
+//! use common::ast::{build, core::AST, encode, schema};
+//!
+//! let bytes = encode::encode(&AST::new(build::block(vec![])));
+//! assert_eq!(schema::format_version(&bytes), Ok(schema::FORMAT_VERSION));
+//! assert_eq!(schema::negotiate(&[1, schema::FORMAT_VERSION]), Some(schema::FORMAT_VERSION));
+//! assert!(schema::decode_checked(&bytes).is_ok());
+//! ```
+
+use std::{fmt, ops::RangeInclusive};
+
+use crate::ast::{
+    core::AST,
+    encode::{
+        self, ATTRIBUTE_TAG, CONSTANT_TAG, DATA_TYPES, IDENTIFIER_TAG, LEAF, LITERAL_TAG, MAGIC, OPERATOR_TAG,
+        QUALIFIER_TAG, TYPE_TAG, UNIT_NODES, VERSION,
+    },
+};
+
+/// The version of the format `encode` writes.
+pub const FORMAT_VERSION: u8 = VERSION;
+
+/// The versions of the format `decode_checked` reads.
+pub const SUPPORTED_VERSIONS: RangeInclusive<u8> = 1..=VERSION;
+
+/// What a node carries besides its children.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Payload {
+    /// Nothing.
+    None,
+    /// A string, written as its index in the string table.
+    String,
+    /// A data type, written as its tag.
+    DataType,
+}
+
+/// A kind of node in the format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeKind {
+    /// The name of the node type, as in `IfStatement` or `Literal`.
+    pub name: String,
+    /// The tag the node is written with, before it is marked as a leaf.
+    pub tag: u8,
+    /// What the node carries after its tag.
+    pub payload: Payload,
+}
+
+/// The description of one version of the format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schema {
+    /// The version of the format.
+    pub version: u8,
+    /// The bit set in the tag of a node without children, which is written without a child count.
+    pub leaf_flag: u8,
+    /// Every kind of node, ordered by tag.
+    pub node_kinds: Vec<NodeKind>,
+    /// The name and tag of every data type, ordered by tag.
+    pub data_types: Vec<(String, u8)>,
+}
+
+/// Returns the schema of the format `encode` writes.
+pub fn schema() -> Schema {
+    let mut node_kinds: Vec<NodeKind> = UNIT_NODES.iter().enumerate()
+        .map(|(tag, node_type)| NodeKind { name: format!("{:?}", node_type), tag: tag as u8, payload: Payload::None })
+        .collect();
+    let payload_kinds = [
+        ("Literal", LITERAL_TAG, Payload::String),
+        ("Identifier", IDENTIFIER_TAG, Payload::String),
+        ("Operator", OPERATOR_TAG, Payload::String),
+        ("Type", TYPE_TAG, Payload::DataType),
+        ("Qualifier", QUALIFIER_TAG, Payload::String),
+        ("Constant", CONSTANT_TAG, Payload::String),
+        ("Attribute", ATTRIBUTE_TAG, Payload::String),
+    ];
+    node_kinds.extend(payload_kinds.into_iter().map(|(name, tag, payload)| NodeKind { name: name.to_string(), tag, payload }));
+    let data_types = DATA_TYPES.iter().enumerate()
+        .map(|(tag, data_type)| (format!("{:?}", data_type), tag as u8))
+        .collect();
+    Schema { version: VERSION, leaf_flag: LEAF, node_kinds, data_types }
+}
+
+impl Schema {
+    /// Renders the schema as JSON, ending with its fingerprint.
+    pub fn to_json(&self) -> String {
+        let body = self.body();
+        format!("{}  \"fingerprint\": \"{:016x}\"\n}}\n", body, fnv1a(body.as_bytes()))
+    }
+
+    /// Returns a hash of the schema, which changes whenever a node kind, data type, or the version does.
+    pub fn fingerprint(&self) -> u64 {
+        fnv1a(self.body().as_bytes())
+    }
+
+    /// Renders every field of the schema as JSON, up to the fingerprint.
+    fn body(&self) -> String {
+        let mut json = String::from("{\n");
+        json.push_str(&format!("  \"magic\": \"{}\",\n", String::from_utf8_lossy(MAGIC)));
+        json.push_str(&format!("  \"version\": {},\n", self.version));
+        json.push_str(&format!("  \"leaf_flag\": {},\n", self.leaf_flag));
+        json.push_str("  \"node_kinds\": [\n");
+        let node_kinds: Vec<String> = self.node_kinds.iter().map(|kind| {
+            let payload = match kind.payload {
+                Payload::None => "null",
+                Payload::String => "\"string\"",
+                Payload::DataType => "\"data_type\"",
+            };
+            format!("    {{\"name\": \"{}\", \"tag\": {}, \"payload\": {}}}", kind.name, kind.tag, payload)
+        }).collect();
+        json.push_str(&node_kinds.join(",\n"));
+        json.push_str("\n  ],\n  \"data_types\": [\n");
+        let data_types: Vec<String> = self.data_types.iter()
+            .map(|(name, tag)| format!("    {{\"name\": \"{}\", \"tag\": {}}}", name, tag))
+            .collect();
+        json.push_str(&data_types.join(",\n"));
+        json.push_str("\n  ],\n");
+        json
+    }
+}
+
+/// Why an encoding could not be read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatError {
+    /// The bytes do not start with the header of the format.
+    NotAnEncoding,
+    /// The encoding is of a version this crate does not read.
+    UnsupportedVersion {
+        /// The version of the encoding.
+        found: u8,
+    },
+    /// The encoding is of a supported version, but is truncated or corrupted.
+    Malformed,
+}
+
+/// Provides a display implementation for `FormatError`, naming the supported versions when the version is not one.
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FormatError::NotAnEncoding => write!(f, "not an encoded AST"),
+            FormatError::UnsupportedVersion { found } => write!(
+                f,
+                "encoded AST has format version {}, but versions {} to {} are supported",
+                found,
+                SUPPORTED_VERSIONS.start(),
+                SUPPORTED_VERSIONS.end()
+            ),
+            FormatError::Malformed => write!(f, "encoded AST is truncated or corrupted"),
+        }
+    }
+}
+
+/// Reads the version of an encoding from its header, without decoding the rest.
+///
+/// # Parameters
+///
+/// * `bytes` - The encoding.
+///
+/// # Returns
+///
+/// The version, or `NotAnEncoding` if the bytes are not an encoding, or `Malformed` if the header is cut short.
+pub fn format_version(bytes: &[u8]) -> Result<u8, FormatError> {
+    let rest = bytes.strip_prefix(MAGIC).ok_or(FormatError::NotAnEncoding)?;
+    rest.first().copied().ok_or(FormatError::Malformed)
+}
+
+/// Picks the version to exchange encodings in with a consumer that reads the given versions. Only the current
+/// version is written, so it is picked if the consumer reads it.
+///
+/// # Parameters
+///
+/// * `accepted` - The versions the consumer reads.
+///
+/// # Returns
+///
+/// The version to write, or None if the consumer reads none that this crate writes.
+pub fn negotiate(accepted: &[u8]) -> Option<u8> {
+    accepted.contains(&FORMAT_VERSION).then_some(FORMAT_VERSION)
+}
+
+/// Decodes an AST encoded by `encode`, reporting why an encoding cannot be read rather than only that it cannot.
+///
+/// # Parameters
+///
+/// * `bytes` - The encoding.
+///
+/// # Returns
+///
+/// The AST, or the reason the encoding cannot be read.
+pub fn decode_checked(bytes: &[u8]) -> Result<AST, FormatError> {
+    let version = format_version(bytes)?;
+    if !SUPPORTED_VERSIONS.contains(&version) {
+        return Err(FormatError::UnsupportedVersion { found: version });
+    }
+    encode::decode(bytes).ok_or(FormatError::Malformed)
+}
+
+/// Hashes bytes with 64-bit FNV-1a, which is stable across platforms and compiler versions.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3))
+}
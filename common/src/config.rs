@@ -0,0 +1,338 @@
+//! Defines the language dialect accepted by the lexer and parser, and the options that shape generated code.
+
+use std::{path::PathBuf, str::FromStr};
+
+use crate::constants::DEFAULT_SWITCH_TABLE_THRESHOLD;
+
+/// Selects which optional language features the lexer and parser accept.
+///
+/// The default configuration is the strict C subset, where every optional feature is disabled. Enabling features
+/// turns the compiler into an extended teaching language.
+///
+/// # Fields
+/// * `struct_methods` - Allows function declarations inside struct declarations.
+/// * `braceless_bodies` - Allows a single statement without braces as the body of `if`, `else`, and loops.
+/// * `typedef` - Treats `typedef` as a keyword and allows type aliases to be declared and used.
+/// * `attributes` - Allows `@name` and `@name(args)` attributes before declarations.
+/// * `lexer` - The sets of keywords the lexer recognizes. Words of the other sets are lexed as identifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LanguageConfig {
+    pub struct_methods: bool,
+    pub braceless_bodies: bool,
+    pub typedef: bool,
+    pub attributes: bool,
+    pub lexer: LexerConfig,
+}
+
+impl LanguageConfig {
+    /// Creates a configuration for the strict C subset, with every optional feature disabled.
+    pub fn strict() -> Self {
+        Self::default()
+    }
+
+    /// Creates a configuration for the extended teaching language, with every optional feature enabled.
+    pub fn extended() -> Self {
+        Self {
+            struct_methods: true,
+            braceless_bodies: true,
+            typedef: true,
+            attributes: true,
+            lexer: LexerConfig::default(),
+        }
+    }
+}
+
+/// A group of related keywords that can be recognized or lexed as identifiers together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeywordSet {
+    /// `if`, `else`, `for`, `while`, `do`, `break`, `continue`, and `return`.
+    Control,
+    /// `switch` and `case`.
+    Switch,
+    /// The type names `int`, `bool`, `double`, `float`, `char`, `void`, `signed`, `unsigned`, and `long`.
+    Types,
+    /// `struct`, `enum`, and `union`.
+    Aggregates,
+    /// The type qualifiers `const` and `volatile`.
+    Qualifiers,
+    /// The storage classes and specifiers `static`, `extern`, and `inline`.
+    StorageClasses,
+    /// The boolean literal `true`.
+    Booleans,
+}
+
+impl KeywordSet {
+    /// Every keyword set.
+    pub const ALL: [KeywordSet; 7] = [
+        KeywordSet::Control,
+        KeywordSet::Switch,
+        KeywordSet::Types,
+        KeywordSet::Aggregates,
+        KeywordSet::Qualifiers,
+        KeywordSet::StorageClasses,
+        KeywordSet::Booleans,
+    ];
+
+    /// Returns the keywords in this set.
+    pub fn keywords(self) -> &'static [&'static str] {
+        match self {
+            KeywordSet::Control => &["if", "else", "for", "while", "do", "break", "continue", "return"],
+            KeywordSet::Switch => &["switch", "case", "default"],
+            KeywordSet::Types => &["int", "bool", "double", "float", "char", "void", "signed", "unsigned", "long"],
+            KeywordSet::Aggregates => &["struct", "enum", "union"],
+            KeywordSet::Qualifiers => &["const", "volatile"],
+            KeywordSet::StorageClasses => &["static", "extern", "inline"],
+            KeywordSet::Booleans => &["true"],
+        }
+    }
+
+    /// Returns the set a keyword belongs to, or None if the word is not a keyword of any set.
+    ///
+    /// # Parameters
+    /// * `word` - The word to look up.
+    pub fn containing(word: &str) -> Option<KeywordSet> {
+        KeywordSet::ALL.into_iter().find(|set| set.keywords().contains(&word))
+    }
+
+    /// Returns the bit standing for this set in a `LexerConfig`.
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+impl FromStr for KeywordSet {
+    type Err = String;
+
+    /// Parses a keyword set from its name, such as `control` or `storage-classes`.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_ascii_lowercase().as_str() {
+            "control" => Ok(KeywordSet::Control),
+            "switch" => Ok(KeywordSet::Switch),
+            "types" => Ok(KeywordSet::Types),
+            "aggregates" => Ok(KeywordSet::Aggregates),
+            "qualifiers" => Ok(KeywordSet::Qualifiers),
+            "storage-classes" => Ok(KeywordSet::StorageClasses),
+            "booleans" => Ok(KeywordSet::Booleans),
+            _ => Err(format!(
+                "unsupported keyword set `{}` (expected `control`, `switch`, `types`, `aggregates`, `qualifiers`, \
+                 `storage-classes`, or `booleans`)",
+                name
+            )),
+        }
+    }
+}
+
+/// Selects which sets of keywords the lexer recognizes, so dialects can reuse a keyword as a name, as with
+/// `switch` in a language without switch statements, without changing the lexer. Every set is recognized by
+/// default. `typedef` is not part of any set, since whether it is a keyword is decided by `LanguageConfig::typedef`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LexerConfig {
+    disabled: u8,
+}
+
+impl LexerConfig {
+    /// Creates a configuration recognizing no keyword set, where every word is an identifier.
+    pub fn none() -> Self {
+        Self { disabled: KeywordSet::ALL.iter().fold(0, |bits, set| bits | set.bit()) }
+    }
+
+    /// Recognizes the keywords of a set.
+    ///
+    /// # Parameters
+    /// * `set` - The keyword set to recognize.
+    pub fn enable(mut self, set: KeywordSet) -> Self {
+        self.disabled &= !set.bit();
+        self
+    }
+
+    /// Lexes the keywords of a set as identifiers.
+    ///
+    /// # Parameters
+    /// * `set` - The keyword set to stop recognizing.
+    pub fn disable(mut self, set: KeywordSet) -> Self {
+        self.disabled |= set.bit();
+        self
+    }
+
+    /// Returns true if the keywords of a set are recognized.
+    pub fn is_enabled(&self, set: KeywordSet) -> bool {
+        self.disabled & set.bit() == 0
+    }
+
+    /// Returns true if a word is lexed as a keyword of an enabled set.
+    ///
+    /// # Parameters
+    /// * `word` - The word to check.
+    pub fn is_keyword(&self, word: &str) -> bool {
+        KeywordSet::containing(word).is_some_and(|set| self.is_enabled(set))
+    }
+}
+
+/// Configures the preprocessing stage, which expands directives such as `#include` before parsing.
+///
+/// # Fields
+/// * `include_paths` - The directories searched, in order, for included files that are not found next to the file
+///   including them. Files included as `<file>` are only searched for here.
+/// * `language` - The optional language features the source and included files are lexed with.
+/// * `defines` - The name and replacement of each macro defined before the source is read, as with `-D`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PreprocessorConfig {
+    pub include_paths: Vec<PathBuf>,
+    pub language: LanguageConfig,
+    pub defines: Vec<(String, String)>,
+}
+
+impl PreprocessorConfig {
+    /// Defines a macro before the source is read, given as the argument of a `-D` flag.
+    ///
+    /// # Parameters
+    /// * `definition` - `NAME=replacement`, or `NAME` alone to define it as `1`.
+    pub fn define(mut self, definition: &str) -> Self {
+        let (name, replacement) = definition.split_once('=').unwrap_or((definition, "1"));
+        self.defines.push((name.to_string(), replacement.to_string()));
+        self
+    }
+}
+
+/// Selects what signed integer `+`, `-`, and `*` do when the result does not fit in the integer type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowMode {
+    /// The result wraps around in two's complement, using plain `add`, `sub`, and `mul`.
+    #[default]
+    Wrap,
+    /// The program traps at runtime, using the `llvm.s*.with.overflow` intrinsics to detect overflow.
+    Trap,
+    /// Overflow is undefined behavior as in C, using `add nsw`, `sub nsw`, and `mul nsw`.
+    Undefined,
+}
+
+impl FromStr for OverflowMode {
+    type Err = String;
+
+    /// Parses an overflow mode from its name, `wrap`, `trap`, or `undefined`.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_ascii_lowercase().as_str() {
+            "wrap" => Ok(OverflowMode::Wrap),
+            "trap" => Ok(OverflowMode::Trap),
+            "undefined" => Ok(OverflowMode::Undefined),
+            _ => Err(format!("unsupported overflow mode `{}` (expected `wrap`, `trap`, or `undefined`)", name)),
+        }
+    }
+}
+
+/// Selects what happens to stores to local variables whose value is never read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeadStoreMode {
+    /// Dead stores are compiled as written.
+    #[default]
+    Keep,
+    /// Dead stores without side effects are removed before generating IR.
+    Remove,
+    /// Dead stores are compiled as written and reported as warnings.
+    Warn,
+}
+
+impl FromStr for DeadStoreMode {
+    type Err = String;
+
+    /// Parses a dead store mode from its name, `keep`, `remove`, or `warn`.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_ascii_lowercase().as_str() {
+            "keep" => Ok(DeadStoreMode::Keep),
+            "remove" => Ok(DeadStoreMode::Remove),
+            "warn" => Ok(DeadStoreMode::Warn),
+            _ => Err(format!("unsupported dead store mode `{}` (expected `keep`, `remove`, or `warn`)", name)),
+        }
+    }
+}
+
+/// Selects which optimization passes run on a program before IR is generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum OptLevel {
+    /// No passes run.
+    #[default]
+    O0,
+    /// Loop-invariant declarations are hoisted out of loops, and an `if` or `while` whose condition is a constant is
+    /// generated without the branch it never takes.
+    O1,
+    /// Constants passed for the same parameter by every call are also propagated into the called function, and
+    /// multiplications by a loop's induction variable are reduced to additions.
+    O2,
+}
+
+impl FromStr for OptLevel {
+    type Err = String;
+
+    /// Parses an optimization level from its number, `0`, `1`, or `2`.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "0" => Ok(OptLevel::O0),
+            "1" => Ok(OptLevel::O1),
+            "2" => Ok(OptLevel::O2),
+            _ => Err(format!("unsupported optimization level `{}` (expected `0`, `1`, or `2`)", name)),
+        }
+    }
+}
+
+/// Selects the environment a program is compiled for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Environment {
+    /// The program runs on top of the C library, which it may call, and starts at `main`.
+    #[default]
+    Hosted,
+    /// The program, such as a kernel, runs without the C library and is started however its environment starts it,
+    /// so `main` is an ordinary function and library functions such as `printf` are not assumed. Nothing the program
+    /// does is lowered to a call into the C library, so comparing strings, which calls `strcmp`, is an error.
+    Freestanding,
+}
+
+/// Selects options that change the IR generated for a program without changing the language it accepts.
+///
+/// # Fields
+/// * `overflow` - What signed integer arithmetic does on overflow.
+/// * `annotate_ir` - Marks the IR of functions, loops, `if`s, and `switch`es with a comment naming the construct and
+///   its source line.
+/// * `switch_table_threshold` - The minimum number of cases for a switch over dense constant cases to be lowered to
+///   an LLVM `switch`, which can become a jump table; smaller switches are lowered to a chain of compares.
+/// * `hoist_loop_invariants` - Moves declarations whose initializer does not change between iterations out of loop
+///   bodies before generating IR.
+/// * `dead_stores` - Whether stores to local variables whose value is never read are kept, removed, or reported.
+/// * `opt_level` - The optimization passes to run on the program before generating IR.
+/// * `environment` - Whether the program runs on top of the C library or without it.
+/// * `require_main` - Whether a hosted program is linked into an executable, which the C library's startup code
+///   starts at `main`. It must then define `main`, and its other functions are given internal linkage, since nothing
+///   outside the module calls them. Libraries, and programs that are only inspected rather than run, are not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodegenConfig {
+    pub overflow: OverflowMode,
+    pub annotate_ir: bool,
+    pub switch_table_threshold: usize,
+    pub hoist_loop_invariants: bool,
+    pub dead_stores: DeadStoreMode,
+    pub opt_level: OptLevel,
+    pub environment: Environment,
+    pub require_main: bool,
+}
+
+impl Default for CodegenConfig {
+    fn default() -> Self {
+        Self {
+            overflow: OverflowMode::default(),
+            annotate_ir: false,
+            switch_table_threshold: DEFAULT_SWITCH_TABLE_THRESHOLD,
+            hoist_loop_invariants: false,
+            dead_stores: DeadStoreMode::default(),
+            opt_level: OptLevel::default(),
+            environment: Environment::default(),
+            require_main: false,
+        }
+    }
+}
+
+impl CodegenConfig {
+    /// Checks whether the program is an executable that the C library starts at `main`, which it must then define:
+    /// it is when it is hosted and linked into an executable.
+    pub fn requires_main(&self) -> bool {
+        self.environment == Environment::Hosted && self.require_main
+    }
+}
@@ -42,6 +42,37 @@ pub const DEFAULT_ELSE_LABEL: &str = "elseID";
 /// Default label for the merging point after branches of an if condition.
 pub const DEFAULT_MERGE_LABEL: &str = "mergeID";
 
+/// Default label for the comparison of a switch scrutinee against one case value.
+pub const DEFAULT_SWITCH_TEST_LABEL: &str = "switch_testID";
+
+/// Default label for the body of a switch case.
+pub const DEFAULT_SWITCH_CASE_LABEL: &str = "switch_caseID";
+
+/// Default label for the body of a switch default.
+pub const DEFAULT_SWITCH_DEFAULT_LABEL: &str = "switch_defaultID";
+
+/// Default label for the end of a switch statement.
+pub const DEFAULT_SWITCH_END_LABEL: &str = "switch_endID";
+
+/// Default label for the block that traps when signed arithmetic overflows.
+pub const DEFAULT_OVERFLOW_TRAP_LABEL: &str = "overflow_trapID";
+
+/// Default label for the block that continues after signed arithmetic did not overflow.
+pub const DEFAULT_OVERFLOW_CONTINUE_LABEL: &str = "overflow_contID";
+
+/// Default label for the right operand of a short-circuiting `&&` or `||`.
+pub const DEFAULT_LOGIC_RHS_LABEL: &str = "logic_rhsID";
+
+/// Default label for the end of a short-circuiting `&&` or `||`, where both paths meet.
+pub const DEFAULT_LOGIC_END_LABEL: &str = "logic_endID";
+
+/// Default minimum number of cases for a switch to be lowered to an LLVM `switch` rather than a chain of compares.
+pub const DEFAULT_SWITCH_TABLE_THRESHOLD: usize = 4;
+
+/// Minimum fraction of the values between the smallest and largest case that must have a case for a switch to be
+/// lowered to an LLVM `switch`, which LLVM can turn into a jump table.
+pub const SWITCH_TABLE_MIN_DENSITY: f64 = 0.5;
+
 /// Default priority setting for a `ModElement`.
 pub const DEFAULT_PRIORITY_MODELEMENT: i32 = -1;
 
@@ -50,3 +81,8 @@ pub const DEFAULT_MUTABLITY_LITERALS: bool = false;
 
 /// Default mutability setting for variables.
 pub const DEFAULT_MUTABILITY_VARIABLES: bool = false;
+
+/// Default maximum nesting depth of blocks and expressions accepted by the parser. A level of the costliest
+/// constructs, such as a `for` or `if` statement or a call, takes about 11 KiB of stack in a debug build, so this
+/// leaves a third of the 2 MiB stack of rayon workers, server workers and test threads free.
+pub const DEFAULT_MAX_NESTING_DEPTH: usize = 128;
@@ -0,0 +1,311 @@
+//! This file defines the diagnostic sink that every phase of the compiler reports its errors and warnings to, so a
+//! compilation ends with one list of diagnostics in the order they were found, whichever phases found them.
+//!
+//! The sink can instead keep its diagnostics sorted by where in the source they were found or grouped by their
+//! code, and can pass each one to a callback as soon as it is reported, so every frontend orders and streams
+//! diagnostics the same way.
+
+use std::{fmt, str::FromStr, sync::Arc};
+
+use crate::{error::ErrorType, localization::Language, location::SourceLocation, warning::WarningType};
+
+/// Selects the order a `DiagnosticSink` keeps its errors and warnings in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiagnosticOrder {
+    /// In the order they were reported.
+    #[default]
+    Reported,
+    /// By the file and line, then column, they were reported at. Diagnostics reported without a location follow
+    /// those with one.
+    Location,
+    /// Grouped by their code, with the groups in the order of their codes.
+    Code,
+}
+
+impl FromStr for DiagnosticOrder {
+    type Err = String;
+
+    /// Parses a diagnostic order from its name, `reported`, `location`, or `code`.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_ascii_lowercase().as_str() {
+            "reported" => Ok(DiagnosticOrder::Reported),
+            "location" => Ok(DiagnosticOrder::Location),
+            "code" => Ok(DiagnosticOrder::Code),
+            _ => Err(format!("unsupported diagnostic order `{}` (expected `reported`, `location`, or `code`)", name)),
+        }
+    }
+}
+
+/// A diagnostic as it is passed to the callback of a streaming sink.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Diagnostic<'a> {
+    /// An error, with the location it was reported at, if any.
+    Error(&'a ErrorType, Option<&'a SourceLocation>),
+    /// A warning, with the location it was reported at, if any.
+    Warning(&'a WarningType, Option<&'a SourceLocation>),
+}
+
+impl Diagnostic<'_> {
+    /// Renders the diagnostic's message in the given language, prefixed with its location if it has one, as in
+    /// `3:9: error[E0007]: ...`.
+    ///
+    /// # Parameters
+    /// * `language` - The language to render the message in.
+    pub fn localized(&self, language: Language) -> String {
+        let (message, location) = match self {
+            Diagnostic::Error(error, location) => (error.localized(language), location),
+            Diagnostic::Warning(warning, location) => (warning.localized(language), location),
+        };
+        match location {
+            Some(location) => format!("{}: {}", location, message),
+            None => message,
+        }
+    }
+}
+
+/// The callback a streaming sink passes each new diagnostic to.
+#[derive(Clone)]
+struct Stream(Arc<dyn Fn(Diagnostic<'_>) + Send + Sync>);
+
+impl fmt::Debug for Stream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Stream")
+    }
+}
+
+/// Two streams are equal when they are the same callback.
+impl PartialEq for Stream {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Collects the errors and warnings reported by each phase of a compilation, in the order they were reported unless
+/// the sink was created with another `DiagnosticOrder`.
+///
+/// A diagnostic equal to one already reported at the same location is dropped, so a problem found by two phases, or
+/// by the same phase while revisiting a node, is reported once. The same diagnostic at two locations is two problems,
+/// and both are kept.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DiagnosticSink {
+    errors: Vec<ErrorType>,
+    error_locations: Vec<Option<SourceLocation>>,
+    warnings: Vec<WarningType>,
+    warning_locations: Vec<Option<SourceLocation>>,
+    order: DiagnosticOrder,
+    stream: Option<Stream>,
+}
+
+impl DiagnosticSink {
+    /// Creates a sink with no diagnostics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a sink with no diagnostics that keeps the diagnostics reported to it in the given order.
+    ///
+    /// # Parameters
+    ///
+    /// * `order` - The order to keep diagnostics in.
+    pub fn with_order(order: DiagnosticOrder) -> Self {
+        Self { order, ..Self::default() }
+    }
+
+    /// Makes the sink pass every diagnostic to `callback` as soon as it is reported, before it is sorted, so a
+    /// frontend can show diagnostics while the compilation goes on. Diagnostics dropped as repeats are not passed.
+    ///
+    /// # Parameters
+    ///
+    /// * `callback` - The function to pass each new diagnostic to.
+    pub fn streaming(mut self, callback: impl Fn(Diagnostic<'_>) + Send + Sync + 'static) -> Self {
+        self.stream = Some(Stream(Arc::new(callback)));
+        self
+    }
+
+    /// Reports an error, unless an equal error was already reported without a location.
+    ///
+    /// # Parameters
+    ///
+    /// * `error` - The error to report.
+    pub fn error(&mut self, error: ErrorType) {
+        self.report_error(error, None);
+    }
+
+    /// Reports an error found at a location in the source, unless an equal error was already reported at the
+    /// same location.
+    ///
+    /// # Parameters
+    ///
+    /// * `error` - The error to report.
+    /// * `location` - Where in the source the error was found.
+    pub fn error_at(&mut self, error: ErrorType, location: SourceLocation) {
+        self.report_error(error, Some(location));
+    }
+
+    /// Reports each of the given errors in order, skipping those already reported.
+    ///
+    /// # Parameters
+    ///
+    /// * `errors` - The errors to report.
+    pub fn errors_from(&mut self, errors: impl IntoIterator<Item = ErrorType>) {
+        for error in errors {
+            self.error(error);
+        }
+    }
+
+    /// Reports a warning, unless an equal warning was already reported without a location.
+    ///
+    /// # Parameters
+    ///
+    /// * `warning` - The warning to report.
+    pub fn warning(&mut self, warning: WarningType) {
+        self.report_warning(warning, None);
+    }
+
+    /// Reports a warning found at a location in the source, unless an equal warning was already reported at the
+    /// same location.
+    ///
+    /// # Parameters
+    ///
+    /// * `warning` - The warning to report.
+    /// * `location` - Where in the source the warning was found.
+    pub fn warning_at(&mut self, warning: WarningType, location: SourceLocation) {
+        self.report_warning(warning, Some(location));
+    }
+
+    /// Reports each of the given warnings in order, skipping those already reported.
+    ///
+    /// # Parameters
+    ///
+    /// * `warnings` - The warnings to report.
+    pub fn warnings_from(&mut self, warnings: impl IntoIterator<Item = WarningType>) {
+        for warning in warnings {
+            self.warning(warning);
+        }
+    }
+
+    /// Takes the output of a phase that returns its errors, reporting the errors if it failed.
+    ///
+    /// # Parameters
+    ///
+    /// * `result` - The result of the phase.
+    ///
+    /// # Returns
+    ///
+    /// The output of the phase, or `None` if it failed.
+    pub fn absorb<T>(&mut self, result: Result<T, Vec<ErrorType>>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(errors) => {
+                self.errors_from(errors);
+                None
+            }
+        }
+    }
+
+    /// Returns true if any error was reported.
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// Returns the errors reported so far, in the sink's order.
+    pub fn errors(&self) -> &[ErrorType] {
+        &self.errors
+    }
+
+    /// Returns the warnings reported so far, in the sink's order.
+    pub fn warnings(&self) -> &[WarningType] {
+        &self.warnings
+    }
+
+    /// Returns the location each error was reported at, if any, in the same order as `errors`.
+    pub fn error_locations(&self) -> &[Option<SourceLocation>] {
+        &self.error_locations
+    }
+
+    /// Returns the location each warning was reported at, if any, in the same order as `warnings`.
+    pub fn warning_locations(&self) -> &[Option<SourceLocation>] {
+        &self.warning_locations
+    }
+
+    /// Consumes the sink, returning its errors and warnings.
+    pub fn into_parts(self) -> (Vec<ErrorType>, Vec<WarningType>) {
+        (self.errors, self.warnings)
+    }
+
+    /// Consumes the sink, returning `value` if no error was reported, or the errors otherwise. Warnings are dropped.
+    ///
+    /// # Parameters
+    ///
+    /// * `value` - The output of the compilation.
+    pub fn finish<T>(self, value: T) -> Result<T, Vec<ErrorType>> {
+        if self.errors.is_empty() {
+            Ok(value)
+        } else {
+            Err(self.errors)
+        }
+    }
+
+    /// Streams and inserts an error that was not already reported at its location.
+    fn report_error(&mut self, error: ErrorType, location: Option<SourceLocation>) {
+        if self.errors.iter().zip(&self.error_locations).any(|kept| kept == (&error, &location)) {
+            return;
+        }
+        if let Some(stream) = &self.stream {
+            (stream.0)(Diagnostic::Error(&error, location.as_ref()));
+        }
+        let index = insertion_index(self.order, error.code(), &location, &self.errors, &self.error_locations, ErrorType::code);
+        self.errors.insert(index, error);
+        self.error_locations.insert(index, location);
+    }
+
+    /// Streams and inserts a warning that was not already reported at its location.
+    fn report_warning(&mut self, warning: WarningType, location: Option<SourceLocation>) {
+        if self.warnings.iter().zip(&self.warning_locations).any(|kept| kept == (&warning, &location)) {
+            return;
+        }
+        if let Some(stream) = &self.stream {
+            (stream.0)(Diagnostic::Warning(&warning, location.as_ref()));
+        }
+        let index = insertion_index(
+            self.order, warning.code(), &location, &self.warnings, &self.warning_locations, WarningType::code,
+        );
+        self.warnings.insert(index, warning);
+        self.warning_locations.insert(index, location);
+    }
+}
+
+/// Finds where a new diagnostic goes among those already kept in `order`, after every diagnostic it does not come
+/// before, so diagnostics that tie keep the order they were reported in.
+///
+/// # Parameters
+///
+/// * `order` - The order the diagnostics are kept in.
+/// * `code` - The code of the new diagnostic.
+/// * `location` - Where the new diagnostic was reported, if anywhere.
+/// * `kept` - The diagnostics already kept.
+/// * `locations` - The location of each kept diagnostic.
+/// * `code_of` - Returns the code of a kept diagnostic.
+///
+/// # Returns
+///
+/// The index to insert the new diagnostic at.
+fn insertion_index<T>(
+    order: DiagnosticOrder,
+    code: &str,
+    location: &Option<SourceLocation>,
+    kept: &[T],
+    locations: &[Option<SourceLocation>],
+    code_of: impl Fn(&T) -> &'static str,
+) -> usize {
+    match order {
+        DiagnosticOrder::Reported => kept.len(),
+        DiagnosticOrder::Location => {
+            // None sorts before Some, so locations are compared with the missing ones flipped to the end
+            let key = |location: &Option<SourceLocation>| (location.is_none(), location.clone());
+            let new_key = key(location);
+            locations.partition_point(|kept_location| key(kept_location) <= new_key)
+        }
+        DiagnosticOrder::Code => kept.partition_point(|kept| code_of(kept) <= code),
+    }
+}
@@ -1,5 +1,9 @@
 //! This file defines the errors that can occur during the compilation process.
 
+use std::fmt;
+
+use crate::localization::{self, Language};
+
 /// Defines types of errors that can occur during compilation.
 ///
 /// Each error represents a different kind of issue that can be encountered during the lexing, parsing, analysis, 
@@ -52,6 +56,122 @@ pub enum ErrorType {
         token: String,
     },
 
+    /// Occurs due to a malformed escape sequence inside a character or string literal.
+    InvalidEscapeSequence {
+        /// The escape sequence as written in the source, including the leading backslash.
+        sequence: String,
+    },
+
+    /// Occurs due to a character or string literal that is unterminated or otherwise malformed.
+    InvalidLiteral {
+        /// The literal as far as it was read.
+        literal: String,
+        /// Describes what is wrong with the literal.
+        reason: String,
+    },
+
+    /// Occurs due to a block comment that is not closed by `*/` before the end of the input.
+    UnterminatedBlockComment,
+
+    /// Occurs due to a reserved keyword being used where a name is expected, as in `int if = 3;`.
+    ReservedKeyword {
+        /// The keyword as written in the source.
+        keyword: String,
+    },
+
+    /// Occurs due to two cases of one switch statement having the same label, as in `case "a": ... case "a":`.
+    DuplicateCaseLabel {
+        /// The repeated label as written in the source.
+        label: String,
+    },
+
+    /// Occurs due to a switch statement that cannot be compiled, such as one whose labels mix strings and numbers.
+    InvalidSwitch {
+        /// Describes what is wrong with the switch statement.
+        reason: String,
+    },
+
+    /// Occurs due to a case of a switch statement using a variable declared in an earlier case, as in
+    /// `case 1: int x = 1; break; case 2: x++;`, where jumping to the later case skips the initialization.
+    JumpPastInitialization {
+        /// The name of the variable.
+        variable: String,
+        /// The label jumped to, such as `case 2` or `default`.
+        label: String,
+    },
+
+    /// Occurs due to a struct with two fields of the same name, including fields of its anonymous struct and union
+    /// members, which are accessed as if they were fields of the struct itself.
+    DuplicateMember {
+        /// The name of the struct.
+        structure: String,
+        /// The name declared twice.
+        member: String,
+    },
+
+    /// Occurs due to an enum constant whose value does not fit in the underlying type chosen for its enum, as in
+    /// the 129th constant of `enum Big : char`.
+    EnumeratorOutOfRange {
+        /// The name of the enum.
+        enumeration: String,
+        /// The name of the constant.
+        enumerator: String,
+        /// The value of the constant.
+        value: String,
+        /// The underlying type of the enum.
+        underlying: String,
+    },
+
+    /// Occurs due to an assignment, compound assignment, increment, or decrement of a variable declared `const`.
+    AssignmentToConst {
+        /// The name of the variable.
+        variable: String,
+    },
+
+    /// Occurs due to `==` or `!=` between two strings, or a `switch` over a string, in a freestanding program. They
+    /// are lowered to calls to the C library's `strcmp`, which a freestanding program cannot call.
+    FreestandingStringComparison,
+
+    /// Occurs due to an `#include` directive whose file cannot be included, such as one that is not found or that
+    /// includes itself.
+    InvalidInclude {
+        /// The file as named by the directive.
+        file: String,
+        /// Describes why the file cannot be included.
+        reason: String,
+    },
+
+    /// Occurs due to a symbol that cannot be renamed as asked, such as when the new name is already declared in the
+    /// same scope or would shadow another declaration.
+    InvalidRename {
+        /// The name of the symbol being renamed.
+        symbol: String,
+        /// The name it was to be renamed to.
+        name: String,
+        /// Describes why the symbol cannot be renamed.
+        reason: String,
+    },
+
+    /// Occurs due to a `#define` directive that gives an already defined macro a different replacement.
+    MacroRedefinition {
+        /// The name of the macro.
+        name: String,
+        /// The replacement it was defined with first.
+        previous: String,
+        /// The replacement the directive gives it.
+        replacement: String,
+    },
+
+    /// Occurs due to a hosted program that defines no `main` function to start at, when it is not compiled as a
+    /// library.
+    MissingMain,
+
+    /// Occurs due to bytes in the source that are not valid UTF-8.
+    InvalidUtf8 {
+        /// The invalid bytes, written as escapes such as `\xff`.
+        bytes: String,
+    },
+
     /// A placeholder error for development use.
     DevError {
         /// A message describing what needs to be addressed.
@@ -59,6 +179,102 @@ pub enum ErrorType {
     },
 }
 
+impl ErrorType {
+    /// Returns the error code identifying this kind of error, which keys its message in the message catalogs.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorType::TypeMismatch { .. } => "E0001",
+            ErrorType::UndefinedVariable { .. } => "E0002",
+            ErrorType::UnsupportedOperator { .. } => "E0003",
+            ErrorType::SyntaxError { .. } => "E0004",
+            ErrorType::DivisionByZero { .. } => "E0005",
+            ErrorType::InvalidAssignment { .. } => "E0006",
+            ErrorType::UnrecognizedToken { .. } => "E0007",
+            ErrorType::InvalidEscapeSequence { .. } => "E0008",
+            ErrorType::InvalidLiteral { .. } => "E0009",
+            ErrorType::DevError { .. } => "E0010",
+            ErrorType::ReservedKeyword { .. } => "E0011",
+            ErrorType::UnterminatedBlockComment => "E0012",
+            ErrorType::DuplicateCaseLabel { .. } => "E0013",
+            ErrorType::InvalidSwitch { .. } => "E0014",
+            ErrorType::InvalidInclude { .. } => "E0015",
+            ErrorType::InvalidRename { .. } => "E0016",
+            ErrorType::MacroRedefinition { .. } => "E0017",
+            ErrorType::MissingMain => "E0018",
+            ErrorType::InvalidUtf8 { .. } => "E0019",
+            ErrorType::JumpPastInitialization { .. } => "E0020",
+            ErrorType::DuplicateMember { .. } => "E0021",
+            ErrorType::EnumeratorOutOfRange { .. } => "E0022",
+            ErrorType::AssignmentToConst { .. } => "E0023",
+            ErrorType::FreestandingStringComparison => "E0024",
+        }
+    }
+
+    /// Returns the named parameters that are interpolated into this error's message template.
+    pub fn params(&self) -> Vec<(&'static str, String)> {
+        match self {
+            ErrorType::TypeMismatch { left_type, right_type } => {
+                vec![("left_type", left_type.clone()), ("right_type", right_type.clone())]
+            }
+            ErrorType::UndefinedVariable { variable_name } => vec![("variable_name", variable_name.clone())],
+            ErrorType::UnsupportedOperator { operator, operand_type } => {
+                vec![("operator", operator.clone()), ("operand_type", operand_type.clone())]
+            }
+            ErrorType::SyntaxError { message } | ErrorType::DevError { message } => vec![("message", message.clone())],
+            ErrorType::DivisionByZero { operation } => vec![("operation", operation.clone())],
+            ErrorType::InvalidAssignment { target } => vec![("target", target.clone())],
+            ErrorType::UnrecognizedToken { token } => vec![("token", token.clone())],
+            ErrorType::InvalidEscapeSequence { sequence } => vec![("sequence", sequence.clone())],
+            ErrorType::InvalidLiteral { literal, reason } => {
+                vec![("literal", literal.clone()), ("reason", reason.clone())]
+            }
+            ErrorType::ReservedKeyword { keyword } => vec![("keyword", keyword.clone())],
+            ErrorType::UnterminatedBlockComment | ErrorType::MissingMain | ErrorType::FreestandingStringComparison => {
+                Vec::new()
+            }
+            ErrorType::DuplicateCaseLabel { label } => vec![("label", label.clone())],
+            ErrorType::InvalidSwitch { reason } => vec![("reason", reason.clone())],
+            ErrorType::InvalidInclude { file, reason } => vec![("file", file.clone()), ("reason", reason.clone())],
+            ErrorType::InvalidRename { symbol, name, reason } => {
+                vec![("symbol", symbol.clone()), ("name", name.clone()), ("reason", reason.clone())]
+            }
+            ErrorType::MacroRedefinition { name, previous, replacement } => {
+                vec![("name", name.clone()), ("previous", previous.clone()), ("replacement", replacement.clone())]
+            }
+            ErrorType::InvalidUtf8 { bytes } => vec![("bytes", bytes.clone())],
+            ErrorType::JumpPastInitialization { variable, label } => {
+                vec![("variable", variable.clone()), ("label", label.clone())]
+            }
+            ErrorType::DuplicateMember { structure, member } => {
+                vec![("structure", structure.clone()), ("member", member.clone())]
+            }
+            ErrorType::EnumeratorOutOfRange { enumeration, enumerator, value, underlying } => vec![
+                ("enumeration", enumeration.clone()),
+                ("enumerator", enumerator.clone()),
+                ("value", value.clone()),
+                ("underlying", underlying.clone()),
+            ],
+            ErrorType::AssignmentToConst { variable } => vec![("variable", variable.clone())],
+        }
+    }
+
+    /// Renders this error's message in the given language, prefixed with its error code.
+    ///
+    /// # Parameters
+    /// * `language` - The language to render the message in. Missing translations fall back to English.
+    pub fn localized(&self, language: Language) -> String {
+        let template = localization::template(self.code(), language).unwrap_or("{message}");
+        format!("error[{}]: {}", self.code(), localization::interpolate(template, &self.params()))
+    }
+}
+
+/// Provides a display implementation for `ErrorType`, rendering its message in the default language.
+impl fmt::Display for ErrorType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.localized(Language::default()))
+    }
+}
+
 impl From<ErrorType> for Vec<ErrorType> {
     /// Converts an `ErrorType` into a vector containing that error.
     fn from(err: ErrorType) -> Self {
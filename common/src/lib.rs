@@ -5,9 +5,32 @@
 /// Contains the abstract syntax tree (`AST`) structure and utilities.
 pub mod ast;
 
+/// Defines the language dialect accepted by the lexer and parser.
+pub mod config;
+
 /// Defines constants.
 pub mod constants;
 
 /// Defines error types.
 pub mod error;
 
+/// Defines warning types.
+pub mod warning;
+
+/// Defines the sink that collects the diagnostics of every phase.
+pub mod diagnostic;
+
+/// Defines interned strings.
+pub mod symbol;
+
+/// Defines source locations.
+pub mod location;
+
+/// Maps byte offsets in a source to lines and columns.
+pub mod source;
+
+/// Defines how the bytes of string literals are kept in their tokens.
+pub mod literal;
+
+/// Defines the message catalogs used to render diagnostics.
+pub mod localization;
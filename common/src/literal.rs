@@ -0,0 +1,129 @@
+//! This file defines the contents of string literals, which are bytes rather than text.
+//!
+//! A byte escape such as `\xFF` puts a byte in a string literal that need not be part of valid UTF-8, so the lexer
+//! decodes a literal into a [`ByteString`] holding the bytes it stands for. The AST keeps a string literal in its
+//! escaped spelling, which [`ByteString::escaped`] writes and [`string_bytes`] reads back into the same bytes.
+
+use std::fmt;
+
+/// The decoded contents of a string literal, the bytes it stands for without the terminating NUL.
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct ByteString(Vec<u8>);
+
+impl ByteString {
+    /// Creates the contents of a string literal from its bytes.
+    ///
+    /// # Parameters
+    ///
+    /// * `bytes` - The bytes the literal stands for.
+    pub fn new(bytes: Vec<u8>) -> Self {
+        ByteString(bytes)
+    }
+
+    /// Returns the bytes the literal stands for.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the contents spelled as in source, without quotes. Valid UTF-8 is kept as its characters, except for
+    /// the ones [`escape_char`] escapes, and every other byte is spelled as a three-digit octal escape.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use common::literal::ByteString;
+    ///
+    /// assert_eq!(ByteString::new(vec![b'a', b'\n', 0xFF, 0xC3, 0xA9]).escaped(), "a\\n\\377é");
+    /// ```
+    pub fn escaped(&self) -> String {
+        let mut escaped = String::with_capacity(self.0.len());
+        for chunk in self.0.utf8_chunks() {
+            escaped.extend(chunk.valid().chars().map(|c| escape_char(c, '"')));
+            escaped.extend(chunk.invalid().iter().map(|byte| format!("\\{:03o}", byte)));
+        }
+        escaped
+    }
+}
+
+impl From<&str> for ByteString {
+    /// Creates the contents of a string literal standing for the UTF-8 bytes of a string.
+    fn from(contents: &str) -> Self {
+        ByteString(contents.as_bytes().to_vec())
+    }
+}
+
+/// Prints the contents as a quoted string literal, as in `"a\n"`.
+impl fmt::Debug for ByteString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\"{}\"", self.escaped())
+    }
+}
+
+/// Spells a character inside a literal delimited by `quote`, escaping the delimiter, backslashes, and control
+/// characters. Control characters up to 0x7F use three-digit octal escapes, which end on their own, since a `\x` escape
+/// would take in any hex digits that follow it. Other control characters use `\u{..}` escapes, since an octal escape
+/// in a string literal stands for a single byte.
+pub fn escape_char(c: char, quote: char) -> String {
+    match c {
+        '\\' => "\\\\".to_string(),
+        '\n' => "\\n".to_string(),
+        '\t' => "\\t".to_string(),
+        '\r' => "\\r".to_string(),
+        c if c == quote => format!("\\{}", c),
+        c if c.is_ascii_control() => format!("\\{:03o}", c as u32),
+        c if c.is_control() => format!("\\u{{{:x}}}", c as u32),
+        c => c.to_string(),
+    }
+}
+
+/// Returns the bytes a string literal spelled as [`ByteString::escaped`] writes it stands for, without the terminating
+/// NUL.
+///
+/// # Parameters
+///
+/// * `spelling` - The escaped contents of the literal, without quotes.
+///
+/// # Examples
+///
+/// ```
+/// use common::literal::string_bytes;
+///
+/// assert_eq!(string_bytes("a\\n\\377é"), vec![b'a', b'\n', 0xFF, 0xC3, 0xA9]);
+/// ```
+pub fn string_bytes(spelling: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(spelling.len());
+    let mut chars = spelling.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+            continue;
+        }
+        match chars.next() {
+            Some('n') => bytes.push(b'\n'),
+            Some('t') => bytes.push(b'\t'),
+            Some('r') => bytes.push(b'\r'),
+            Some(digit @ '0'..='7') => {
+                let mut value = digit.to_digit(8).unwrap_or(0);
+                for _ in 0..2 {
+                    match chars.peek().and_then(|c| c.to_digit(8)) {
+                        Some(digit) => {
+                            value = value * 8 + digit;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                bytes.push(value as u8);
+            }
+            Some('u') => {
+                let digits: String = chars.by_ref().skip(1).take_while(|c| *c != '}').collect();
+                if let Some(c) = u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32) {
+                    bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+            }
+            Some(c) => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+            None => bytes.push(b'\\'),
+        }
+    }
+    bytes
+}
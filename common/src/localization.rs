@@ -0,0 +1,161 @@
+//! Defines the message catalogs used to render diagnostics, keyed by error code, so that messages can be
+//! translated without changing the phases that report them.
+
+use std::str::FromStr;
+
+/// A language that diagnostics can be rendered in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    /// English, the default language and the fallback for missing translations.
+    #[default]
+    English,
+    /// Spanish.
+    Spanish,
+}
+
+impl Language {
+    /// Returns the message catalog for this language, pairing each error and warning code with its message template.
+    fn catalog(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Language::English => ENGLISH,
+            Language::Spanish => SPANISH,
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    /// Parses a language from its ISO 639-1 code, such as `en` or `es`.
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        match code.to_ascii_lowercase().as_str() {
+            "en" => Ok(Language::English),
+            "es" => Ok(Language::Spanish),
+            _ => Err(format!("unsupported language `{}` (expected `en` or `es`)", code)),
+        }
+    }
+}
+
+/// English message templates. Every error and warning code must have an entry here.
+const ENGLISH: &[(&str, &str)] = &[
+    ("E0001", "type mismatch: cannot combine `{left_type}` with `{right_type}`"),
+    ("E0002", "use of undeclared variable `{variable_name}`"),
+    ("E0003", "operator `{operator}` cannot be applied to type `{operand_type}`"),
+    ("E0004", "syntax error: {message}"),
+    ("E0005", "division by zero in `{operation}`"),
+    ("E0006", "cannot assign to `{target}`"),
+    ("E0007", "unrecognized token `{token}`"),
+    ("E0008", "invalid escape sequence `{sequence}`"),
+    ("E0009", "invalid literal `{literal}`: {reason}"),
+    ("E0010", "internal compiler error: {message}"),
+    ("E0011", "'{keyword}' is a reserved keyword and cannot be used as a variable name"),
+    ("E0012", "unterminated block comment: expected `*/` before the end of the file"),
+    ("E0013", "duplicate case label `{label}` in switch statement"),
+    ("E0014", "invalid switch statement: {reason}"),
+    ("E0015", "cannot include `{file}`: {reason}"),
+    ("E0016", "cannot rename `{symbol}` to `{name}`: {reason}"),
+    ("E0017", "macro `{name}` redefined as `{replacement}`, but it was already defined as `{previous}`"),
+    ("E0018", "no `main` function is defined; a hosted program starts at `main` unless it is compiled as a library"),
+    ("E0019", "source contains bytes that are not valid UTF-8: `{bytes}`"),
+    ("E0020", "jump to `{label}` skips the initialization of `{variable}`, which is declared in an earlier case"),
+    ("E0021", "struct `{structure}` declares `{member}` more than once, counting the fields of its anonymous members"),
+    ("E0022", "constant `{enumerator}` of enum `{enumeration}` has the value {value}, which does not fit in its underlying type `{underlying}`"),
+    ("E0023", "cannot assign to `{variable}`, which is declared `const`"),
+    ("E0024", "strings are compared by calling the C library's `strcmp`, which a freestanding program cannot call"),
+    ("W0001", "`{function}` format expects {expected} argument(s), but {found} were given"),
+    ("W0002", "argument {argument_index} (`{argument}`) of `{function}` has type `{found}`, but `{specifier}` expects `{expected}`"),
+    ("W0003", "unknown conversion specifier `{specifier}` in `{function}` format string"),
+    ("W0004", "result of expression `{expression}` is unused"),
+    ("W0005", "`{expression}` compares the contents of two strings with `strcmp`; call `strcmp` explicitly to make this clear"),
+    ("W0006", "value stored to `{variable}` is never read"),
+    ("W0007", "`{expression}` is parsed as `{parsed}`; add parentheses to make the intended grouping clear"),
+    ("W0008", "condition `{expression}` is an assignment; write `{comparison}` to compare, or `({expression})` if the assignment is intended"),
+    ("W0009", "condition `{expression}` is always {value}, so the code it would skip to is never run and is not generated"),
+    ("W0010", "`{expression}` of type `{from}` is implicitly converted to the narrower type `{to}` of `{variable}`, which may lose part of its value; note: write `({to}) {expression}` to convert it explicitly"),
+];
+
+/// Spanish message templates.
+const SPANISH: &[(&str, &str)] = &[
+    ("E0001", "tipos incompatibles: no se puede combinar `{left_type}` con `{right_type}`"),
+    ("E0002", "uso de la variable no declarada `{variable_name}`"),
+    ("E0003", "el operador `{operator}` no se puede aplicar al tipo `{operand_type}`"),
+    ("E0004", "error de sintaxis: {message}"),
+    ("E0005", "división por cero en `{operation}`"),
+    ("E0006", "no se puede asignar a `{target}`"),
+    ("E0007", "símbolo no reconocido `{token}`"),
+    ("E0008", "secuencia de escape no válida `{sequence}`"),
+    ("E0009", "literal no válido `{literal}`: {reason}"),
+    ("E0010", "error interno del compilador: {message}"),
+    ("E0011", "'{keyword}' es una palabra reservada y no se puede usar como nombre de variable"),
+    ("E0012", "comentario de bloque sin terminar: se esperaba `*/` antes del final del archivo"),
+    ("E0013", "etiqueta de caso duplicada `{label}` en la sentencia switch"),
+    ("E0014", "sentencia switch no válida: {reason}"),
+    ("E0015", "no se puede incluir `{file}`: {reason}"),
+    ("E0016", "no se puede renombrar `{symbol}` a `{name}`: {reason}"),
+    ("E0017", "la macro `{name}` se redefine como `{replacement}`, pero ya estaba definida como `{previous}`"),
+    ("E0018", "no se define ninguna función `main`; un programa alojado comienza en `main` salvo que se compile como biblioteca"),
+    ("E0019", "el código fuente contiene bytes que no son UTF-8 válido: `{bytes}`"),
+    ("E0020", "el salto a `{label}` omite la inicialización de `{variable}`, que se declara en un caso anterior"),
+    ("E0021", "la estructura `{structure}` declara `{member}` más de una vez, contando los campos de sus miembros anónimos"),
+    ("E0022", "la constante `{enumerator}` del enum `{enumeration}` tiene el valor {value}, que no cabe en su tipo subyacente `{underlying}`"),
+    ("E0023", "no se puede asignar a `{variable}`, que está declarada `const`"),
+    ("E0024", "las cadenas se comparan llamando a `strcmp` de la biblioteca de C, que un programa independiente no puede llamar"),
+    ("W0001", "el formato de `{function}` espera {expected} argumento(s), pero se dieron {found}"),
+    ("W0002", "el argumento {argument_index} (`{argument}`) de `{function}` es de tipo `{found}`, pero `{specifier}` espera `{expected}`"),
+    ("W0003", "especificador de conversión desconocido `{specifier}` en el formato de `{function}`"),
+    ("W0004", "el resultado de la expresión `{expression}` no se usa"),
+    ("W0005", "`{expression}` compara el contenido de dos cadenas con `strcmp`; llame a `strcmp` explícitamente para que quede claro"),
+    ("W0006", "el valor almacenado en `{variable}` nunca se lee"),
+    ("W0007", "`{expression}` se analiza como `{parsed}`; añada paréntesis para que la agrupación deseada quede clara"),
+    ("W0008", "la condición `{expression}` es una asignación; escriba `{comparison}` para comparar, o `({expression})` si la asignación es intencionada"),
+    ("W0009", "la condición `{expression}` siempre es {value}, así que el código al que saltaría nunca se ejecuta y no se genera"),
+    ("W0010", "`{expression}` de tipo `{from}` se convierte implícitamente al tipo más estrecho `{to}` de `{variable}`, lo que puede perder parte de su valor; nota: escriba `({to}) {expression}` para convertirlo explícitamente"),
+];
+
+/// Looks up the message template for an error or warning code, falling back to English when the language has no translation.
+///
+/// # Parameters
+/// * `code` - The error or warning code, such as `E0004`.
+/// * `language` - The language to render the message in.
+///
+/// # Returns
+/// The message template, or `None` if the code is not in the English catalog.
+pub fn template(code: &str, language: Language) -> Option<&'static str> {
+    let find = |catalog: &'static [(&'static str, &'static str)]| {
+        catalog.iter().find(|(key, _)| *key == code).map(|(_, template)| *template)
+    };
+    find(language.catalog()).or_else(|| find(ENGLISH))
+}
+
+/// Replaces each `{name}` placeholder in `template` with the value of the parameter of the same name.
+/// Placeholders without a matching parameter are left as they are, and parameter values are not themselves
+/// searched for placeholders.
+///
+/// # Parameters
+/// * `template` - The message template.
+/// * `params` - The parameter names and their values.
+///
+/// # Returns
+/// The message with its placeholders filled in.
+pub fn interpolate(template: &str, params: &[(&str, String)]) -> String {
+    let mut message = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        message.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        match after.find('}').and_then(|close| {
+            params.iter().find(|(name, _)| *name == &after[..close]).map(|(_, value)| (close, value))
+        }) {
+            Some((close, value)) => {
+                message.push_str(value);
+                rest = &after[close + 1..];
+            }
+            None => {
+                message.push('{');
+                rest = after;
+            }
+        }
+    }
+    message.push_str(rest);
+    message
+}
@@ -0,0 +1,108 @@
+//! This file defines source locations, which tie tokens and diagnostics back to the source they came from.
+
+use std::fmt;
+
+/// A position in the original source of a program.
+///
+/// Lines honor `#line` directives, so for generated sources they refer to the file the code was generated from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SourceLocation {
+    /// The file named by the most recent `#line` directive, or None if no directive named one.
+    pub file: Option<String>,
+    /// The 1-based line number.
+    pub line: usize,
+    /// The 1-based column number.
+    pub column: usize,
+}
+
+impl SourceLocation {
+    /// Creates a new `SourceLocation`.
+    ///
+    /// # Parameters
+    ///
+    /// - `file`: The name of the source file, if known.
+    /// - `line`: The 1-based line number.
+    /// - `column`: The 1-based column number.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `SourceLocation` incorporating the input parameters.
+    pub fn new(file: Option<String>, line: usize, column: usize) -> Self {
+        Self { file, line, column }
+    }
+}
+
+/// Provides a display implementation for `SourceLocation` in the usual `file:line:column` form.
+///
+/// # Parameters
+/// * `f` - The formatter.
+///
+/// # Returns
+/// * `fmt::Result` - The result of the formatting operation.
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:{}:{}", file, self.line, self.column),
+            None => write!(f, "{}:{}", self.line, self.column),
+        }
+    }
+}
+
+/// A range of bytes in the source of a program, which lets a token refer to its text without owning a copy of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Span {
+    /// The byte offset of the first byte in the range.
+    pub offset: usize,
+    /// The number of bytes in the range.
+    pub len: usize,
+}
+
+impl Span {
+    /// Creates a new `Span`.
+    ///
+    /// # Parameters
+    ///
+    /// - `offset`: The byte offset the range starts at.
+    /// - `len`: The number of bytes in the range.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `Span` incorporating the input parameters.
+    pub fn new(offset: usize, len: usize) -> Self {
+        Self { offset, len }
+    }
+
+    /// Returns the byte offset just past the end of the range.
+    pub fn end(&self) -> usize {
+        self.offset + self.len
+    }
+
+    /// Returns the smallest range covering both this range and `other`, such as the range of an expression from the
+    /// ranges of its first and last tokens.
+    ///
+    /// # Parameters
+    ///
+    /// - `other`: The range to join with this one.
+    ///
+    /// # Returns
+    ///
+    /// Returns the joined range.
+    pub fn join(&self, other: &Span) -> Span {
+        let offset = self.offset.min(other.offset);
+        Span::new(offset, self.end().max(other.end()) - offset)
+    }
+
+    /// Resolves the text the range covers in the source it was taken from.
+    ///
+    /// # Parameters
+    ///
+    /// - `source`: The source the range was taken from.
+    ///
+    /// # Returns
+    ///
+    /// Returns the slice of `source` the range covers, or None if the range is not within `source` or does not fall
+    /// on character boundaries.
+    pub fn text<'a>(&self, source: &'a str) -> Option<&'a str> {
+        source.get(self.offset..self.end())
+    }
+}
@@ -0,0 +1,135 @@
+//! Maps byte offsets in the source of a program to lines and columns.
+//!
+//! Every layer that reports positions, from the lexer's token locations to diagnostics and debug information,
+//! computes them with a `LineIndex`, so a byte offset names the same line and column everywhere. Lines are ended by
+//! `\n`, `\r\n`, or a lone `\r`, and a byte order mark at the start of the source is not part of the first line.
+//! Lines and columns both start at 1, and columns count characters rather than bytes:
+//!
+//! ```
+//! use common::source::LineIndex;
+//!
+//! let index = LineIndex::new("int x;\r\nchar *s = \"é\";\n");
+//! assert_eq!(index.line_col(8), (2, 1));
+//! assert_eq!(index.line_col(22), (2, 14));
+//! assert_eq!(index.offset(2, 14), Some(22));
+//! ```
+//!
+//! The lexer adds the `#line` directives it reads to the index, and `location` renumbers the lines after each one, so
+//! locations in generated sources point at the file they were generated from.
+
+use crate::location::SourceLocation;
+
+/// The byte order mark some editors write at the start of UTF-8 files.
+pub const BOM: char = '\u{feff}';
+
+/// Checks whether a character ends a line, as `\n` and `\r` do. A `\r\n` pair is a single line break, which ends at
+/// the `\n`.
+pub fn is_line_break(c: char) -> bool {
+    c == '\n' || c == '\r'
+}
+
+/// The byte offset at which each line of a source starts, and the `#line` directives that renumber them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+    line_directives: Vec<LineDirective>,
+}
+
+/// A `#line` directive, which renumbers the lines that follow it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LineDirective {
+    /// The 0-based physical line the directive itself is on.
+    physical_line: usize,
+    /// The line number given to the line after the directive.
+    line: usize,
+    /// The file named by the directive, or inherited from the previous directive.
+    file: Option<String>,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Indexes the lines of a source.
+    ///
+    /// # Parameters
+    ///
+    /// * `source` - The source text.
+    pub fn new(source: &'a str) -> Self {
+        let mut line_starts = vec![if source.starts_with(BOM) { BOM.len_utf8() } else { 0 }];
+        let bytes = source.as_bytes();
+        for (i, byte) in bytes.iter().enumerate() {
+            if *byte == b'\n' || (*byte == b'\r' && bytes.get(i + 1) != Some(&b'\n')) {
+                line_starts.push(i + 1);
+            }
+        }
+        Self { source, line_starts, line_directives: Vec::new() }
+    }
+
+    /// Renumbers the lines after a `#line` directive, which must come after every directive already added.
+    ///
+    /// # Parameters
+    ///
+    /// * `physical_line` - The 0-based index of the line the directive is on.
+    /// * `line` - The line number the directive gives the line after it.
+    /// * `file` - The file the directive names, or None to keep the file of the previous directive.
+    pub fn add_line_directive(&mut self, physical_line: usize, line: usize, file: Option<String>) {
+        let file = file.or_else(|| self.line_directives.last().and_then(|directive| directive.file.clone()));
+        self.line_directives.push(LineDirective { physical_line, line, file });
+    }
+
+    /// Returns the number of lines, counting the empty line after a final line break.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the 0-based index of the line a byte offset is on. Offsets past the end are on the last line.
+    pub fn line(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|start| *start <= offset).saturating_sub(1)
+    }
+
+    /// Returns the byte offset the line with the given 0-based index starts at, or None if there is no such line.
+    pub fn line_start(&self, line: usize) -> Option<usize> {
+        self.line_starts.get(line).copied()
+    }
+
+    /// Returns the 1-based line and column of a byte offset. An offset inside a character is in the column of that
+    /// character.
+    pub fn line_col(&self, offset: usize) -> (usize, usize) {
+        let line = self.line(offset);
+        let start = self.line_starts[line];
+        let characters = self.source[start..].char_indices()
+            .take_while(|(i, c)| start + i + c.len_utf8() <= offset)
+            .count();
+        // Offsets past the end count a column per byte, as if the source went on
+        let past_end = offset.saturating_sub(self.source.len().max(start));
+        (line + 1, characters + past_end + 1)
+    }
+
+    /// Returns the location of a byte offset, renumbered by the last `#line` directive before its line. Without one,
+    /// the location has no file name.
+    pub fn location(&self, offset: usize) -> SourceLocation {
+        let (line, column) = self.line_col(offset);
+        let physical_line = line - 1;
+        match self.line_directives.iter().rev().find(|directive| directive.physical_line < physical_line) {
+            Some(directive) => SourceLocation::new(
+                directive.file.clone(),
+                // A directive may give any line number, which must not overflow as the lines after it are counted
+                directive.line.saturating_add(physical_line - directive.physical_line - 1),
+                column,
+            ),
+            None => SourceLocation::new(None, line, column),
+        }
+    }
+
+    /// Returns the byte offset of a 1-based line and column, the inverse of `line_col`.
+    ///
+    /// # Returns
+    ///
+    /// The offset, or None if the line does not exist or is shorter than the column. The column just past the end of
+    /// a line is the start of the next line.
+    pub fn offset(&self, line: usize, column: usize) -> Option<usize> {
+        let start = self.line_start(line.checked_sub(1)?)?;
+        let end = self.line_start(line).unwrap_or(self.source.len());
+        let mut offsets = self.source[start..end].char_indices().map(|(i, _)| start + i).chain(std::iter::once(end));
+        offsets.nth(column.checked_sub(1)?)
+    }
+}
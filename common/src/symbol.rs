@@ -0,0 +1,76 @@
+//! This file defines interned strings, which tokens use to carry identifiers, numbers, and string literals.
+//!
+//! Interning stores each distinct string once for the whole process, so a `Symbol` is a small copyable handle that
+//! compares and hashes as an integer, and its text can be borrowed for as long as the program runs without
+//! allocating a new `String` each time it is read.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Mutex, OnceLock},
+};
+
+/// A handle to an interned string. Two symbols are equal exactly when their strings are.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Symbol(u32);
+
+/// Stores every interned string, indexed both by its text and by its symbol.
+#[derive(Default)]
+struct Interner {
+    symbols: HashMap<&'static str, Symbol>,
+    strings: Vec<&'static str>,
+}
+
+/// Returns the interner shared by the whole process.
+fn interner() -> &'static Mutex<Interner> {
+    static INTERNER: OnceLock<Mutex<Interner>> = OnceLock::new();
+    INTERNER.get_or_init(Default::default)
+}
+
+impl Symbol {
+    /// Interns a string, returning the symbol of the string if it was interned before.
+    ///
+    /// # Parameters
+    ///
+    /// * `string` - The string to intern.
+    pub fn intern(string: &str) -> Self {
+        // The interner is left consistent between statements, so one poisoned by a panic elsewhere is still usable
+        let mut interner = interner().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(symbol) = interner.symbols.get(string) {
+            return *symbol;
+        }
+        // Interned strings live until the process exits, so they are leaked to hand out `'static` borrows
+        let string: &'static str = Box::leak(string.to_string().into_boxed_str());
+        let symbol = Symbol(interner.strings.len() as u32);
+        interner.strings.push(string);
+        interner.symbols.insert(string, symbol);
+        symbol
+    }
+
+    /// Returns the interned string.
+    pub fn as_str(self) -> &'static str {
+        let interner = interner().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        interner.strings[self.0 as usize]
+    }
+}
+
+impl From<&str> for Symbol {
+    /// Interns a string.
+    fn from(string: &str) -> Self {
+        Symbol::intern(string)
+    }
+}
+
+/// Prints a symbol as its quoted string, as in `"x"`.
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+
+/// Prints a symbol as its string.
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
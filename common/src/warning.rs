@@ -0,0 +1,190 @@
+//! This file defines the warnings that can be reported during the compilation process. Unlike errors, warnings
+//! do not stop compilation.
+
+use std::fmt;
+
+use crate::localization::{self, Language};
+
+/// Defines types of warnings that can be reported during compilation.
+#[derive(Debug, PartialEq, Clone)]
+pub enum WarningType {
+    /// Occurs when a printf-like call is given a different number of arguments than its format string consumes.
+    FormatArgumentCount {
+        /// The name of the printf-like function.
+        function: String,
+        /// The number of arguments consumed by the format string.
+        expected: usize,
+        /// The number of arguments given after the format string.
+        found: usize,
+    },
+
+    /// Occurs when an argument of a printf-like call does not match the type its conversion specifier expects.
+    FormatArgumentType {
+        /// The name of the printf-like function.
+        function: String,
+        /// The 1-based position of the mismatched argument in the call, counting the format string.
+        argument_index: usize,
+        /// The mismatched argument as written.
+        argument: String,
+        /// The conversion specifier the argument was matched against, such as `%d`.
+        specifier: String,
+        /// The type the conversion specifier expects.
+        expected: String,
+        /// The type of the argument.
+        found: String,
+    },
+
+    /// Occurs when the format string of a printf-like call contains an unknown conversion specifier.
+    InvalidFormatSpecifier {
+        /// The name of the printf-like function.
+        function: String,
+        /// The conversion specifier as written.
+        specifier: String,
+    },
+
+    /// Occurs when an expression statement has no effect and its result is discarded, as in `x + 1;`.
+    UnusedResult {
+        /// The discarded expression as written.
+        expression: String,
+    },
+
+    /// Occurs when two strings are compared with `==` or `!=`, which compares their contents with `strcmp` rather
+    /// than the pointers themselves.
+    StringComparison {
+        /// The comparison as written.
+        expression: String,
+    },
+
+    /// Occurs when a value is stored to a local variable and never read before the variable is overwritten or goes
+    /// out of scope.
+    DeadStore {
+        /// The name of the variable stored to.
+        variable: String,
+    },
+
+    /// Occurs when an operator of lower precedence than is commonly expected has an unparenthesized operand that
+    /// uses another operator, as in `a & b == c`, which is parsed as `a & (b == c)`.
+    OperatorPrecedence {
+        /// The expression as written.
+        expression: String,
+        /// The expression with parentheses showing how it is parsed.
+        parsed: String,
+    },
+
+    /// Occurs when the condition of an `if` or a loop is an unparenthesized assignment, as in
+    /// `if (x = 0)`, which is commonly a mistyped comparison.
+    AssignmentInCondition {
+        /// The assignment as written.
+        expression: String,
+        /// The comparison the assignment was likely meant to be, with `==` in place of `=`.
+        comparison: String,
+    },
+
+    /// Occurs when the condition of an `if` or a `while` is a constant, as in `if (1 > 2)`, so one of the ways it
+    /// could go is never taken.
+    ConstantCondition {
+        /// The condition as written.
+        expression: String,
+        /// The value the condition always has.
+        value: bool,
+    },
+
+    /// Occurs when a value is implicitly converted to a narrower type when it is stored, as in `char c = total;`
+    /// with an `int` total, which may lose part of its value.
+    ImplicitNarrowing {
+        /// The variable or other target stored to, as written.
+        variable: String,
+        /// The value stored, as written.
+        expression: String,
+        /// The type of the value.
+        from: String,
+        /// The type of the target, which the value is converted to.
+        to: String,
+    },
+}
+
+impl WarningType {
+    /// Returns the warning code identifying this kind of warning, which keys its message in the message catalogs.
+    pub fn code(&self) -> &'static str {
+        match self {
+            WarningType::FormatArgumentCount { .. } => "W0001",
+            WarningType::FormatArgumentType { .. } => "W0002",
+            WarningType::InvalidFormatSpecifier { .. } => "W0003",
+            WarningType::UnusedResult { .. } => "W0004",
+            WarningType::StringComparison { .. } => "W0005",
+            WarningType::DeadStore { .. } => "W0006",
+            WarningType::OperatorPrecedence { .. } => "W0007",
+            WarningType::AssignmentInCondition { .. } => "W0008",
+            WarningType::ConstantCondition { .. } => "W0009",
+            WarningType::ImplicitNarrowing { .. } => "W0010",
+        }
+    }
+
+    /// Returns the named parameters that are interpolated into this warning's message template.
+    pub fn params(&self) -> Vec<(&'static str, String)> {
+        match self {
+            WarningType::FormatArgumentCount { function, expected, found } => vec![
+                ("function", function.clone()),
+                ("expected", expected.to_string()),
+                ("found", found.to_string()),
+            ],
+            WarningType::FormatArgumentType { function, argument_index, argument, specifier, expected, found } => vec![
+                ("function", function.clone()),
+                ("argument_index", argument_index.to_string()),
+                ("argument", argument.clone()),
+                ("specifier", specifier.clone()),
+                ("expected", expected.clone()),
+                ("found", found.clone()),
+            ],
+            WarningType::InvalidFormatSpecifier { function, specifier } => {
+                vec![("function", function.clone()), ("specifier", specifier.clone())]
+            }
+            WarningType::UnusedResult { expression } | WarningType::StringComparison { expression } => {
+                vec![("expression", expression.clone())]
+            }
+            WarningType::DeadStore { variable } => vec![("variable", variable.clone())],
+            WarningType::OperatorPrecedence { expression, parsed } => {
+                vec![("expression", expression.clone()), ("parsed", parsed.clone())]
+            }
+            WarningType::AssignmentInCondition { expression, comparison } => {
+                vec![("expression", expression.clone()), ("comparison", comparison.clone())]
+            }
+            WarningType::ConstantCondition { expression, value } => {
+                vec![("expression", expression.clone()), ("value", value.to_string())]
+            }
+            WarningType::ImplicitNarrowing { variable, expression, from, to } => vec![
+                ("variable", variable.clone()),
+                ("expression", expression.clone()),
+                ("from", from.clone()),
+                ("to", to.clone()),
+            ],
+        }
+    }
+
+    /// Returns the fix-its for this warning: replacements for the reported code, as written in the warning, that
+    /// would resolve it, most likely first. Warnings without an obvious fix have none.
+    pub fn fixes(&self) -> Vec<String> {
+        match self {
+            WarningType::AssignmentInCondition { expression, comparison } => {
+                vec![comparison.clone(), format!("({})", expression)]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Renders this warning's message in the given language, prefixed with its warning code.
+    ///
+    /// # Parameters
+    /// * `language` - The language to render the message in. Missing translations fall back to English.
+    pub fn localized(&self, language: Language) -> String {
+        let template = localization::template(self.code(), language).unwrap_or_default();
+        format!("warning[{}]: {}", self.code(), localization::interpolate(template, &self.params()))
+    }
+}
+
+/// Provides a display implementation for `WarningType`, rendering its message in the default language.
+impl fmt::Display for WarningType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.localized(Language::default()))
+    }
+}
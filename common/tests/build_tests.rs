@@ -0,0 +1,79 @@
+//! This file contains tests for building ASTs in code, making sure malformed nodes are reported by validation.
+
+use common::{
+    ast::{build, core::ASTNode, data_type::DataType, node_type::NodeType},
+    error::ErrorType,
+};
+
+/// This test checks that a well-formed program builds without errors.
+#[test]
+fn test_build_program() {
+    let main = build::function("main", DataType::Integer)
+        .body(vec![
+            build::declare("x", DataType::Integer, Some(build::literal("3"))),
+            build::while_loop(build::binary(build::identifier("x"), ">", build::literal("0")), vec![
+                build::assign("x", build::binary(build::identifier("x"), "-", build::literal("1"))),
+            ]),
+            build::if_else(build::identifier("x"), vec![build::ret(Some(build::literal("1")))], None),
+            build::expression_statement(build::call("puts", vec![build::string("done")])),
+            build::ret(Some(build::unary("-", build::identifier("x")))),
+        ])
+        .build()
+        .expect("main should be well-formed");
+
+    let ast = build::program(vec![main]).expect("The program should be well-formed");
+    assert_eq!(ast.get_root().get_children().len(), 1);
+}
+
+/// This test checks that a variadic function is marked after its parameters.
+#[test]
+fn test_build_variadic_function() {
+    let log = build::function("log", DataType::Integer)
+        .param("level", DataType::Integer)
+        .variadic()
+        .build()
+        .expect("log should be well-formed");
+
+    let kinds: Vec<NodeType> = log.get_children().iter().map(|c| c.get_node_type()).collect();
+    assert_eq!(kinds[2..], [NodeType::Variadic, NodeType::Type(DataType::Integer), NodeType::BlockExpression]);
+}
+
+/// This test checks that names which are not identifiers or are keywords, and operators that do not exist, are
+/// reported.
+#[test]
+fn test_build_invalid_names_and_operators() {
+    let errors = build::function("2fast", DataType::Void)
+        .param("if", DataType::Integer)
+        .body(vec![build::expression_statement(build::binary(build::literal("1"), "<>", build::literal("2")))])
+        .build()
+        .expect_err("The function should be malformed");
+
+    assert_eq!(errors, vec![
+        ErrorType::SyntaxError { message: "'2fast' is not a valid identifier".to_string() },
+        ErrorType::ReservedKeyword { keyword: "if".to_string() },
+        ErrorType::SyntaxError { message: "A binary expression needs two operands around a binary operator".to_string() },
+    ]);
+}
+
+/// This test checks that duplicate and void parameters, duplicate functions, and nodes with missing children are
+/// reported.
+#[test]
+fn test_build_malformed_declarations() {
+    let errors = build::function("f", DataType::Void)
+        .param("a", DataType::Integer)
+        .param("a", DataType::Void)
+        .build()
+        .expect_err("The function should be malformed");
+    assert_eq!(errors, vec![
+        ErrorType::SyntaxError { message: "A parameter cannot have type void".to_string() },
+        ErrorType::SyntaxError { message: "Parameter 'a' is declared more than once".to_string() },
+    ]);
+
+    let f = build::function("f", DataType::Void).build().expect("f should be well-formed");
+    let errors = build::program(vec![f.clone(), f, ASTNode::new(NodeType::Assignment)])
+        .expect_err("The program should be malformed");
+    assert_eq!(errors, vec![
+        ErrorType::SyntaxError { message: "Function 'f' is declared more than once".to_string() },
+        ErrorType::SyntaxError { message: "An assignment needs a target and a value".to_string() },
+    ]);
+}
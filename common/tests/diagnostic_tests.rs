@@ -0,0 +1,137 @@
+//! This file contains tests for the diagnostic sink, making sure diagnostics keep the order they were reported in,
+//! or the order the sink was configured with, and that repeated ones are reported once.
+
+use std::sync::{Arc, Mutex};
+
+use common::{
+    diagnostic::{Diagnostic, DiagnosticOrder, DiagnosticSink},
+    error::ErrorType,
+    localization::Language,
+    location::SourceLocation,
+    warning::WarningType,
+};
+
+/// Builds a syntax error with the given message.
+fn syntax_error(message: &str) -> ErrorType {
+    ErrorType::SyntaxError { message: message.to_string() }
+}
+
+/// This test checks that errors and warnings from several phases are kept in order, without duplicates.
+#[test]
+fn test_ordered_and_deduplicated() {
+    let mut diagnostics = DiagnosticSink::new();
+    diagnostics.error(syntax_error("first"));
+    diagnostics.errors_from(vec![syntax_error("second"), syntax_error("first"), syntax_error("third")]);
+    diagnostics.warning(WarningType::DeadStore { variable: "x".to_string() });
+    diagnostics.warnings_from(vec![
+        WarningType::DeadStore { variable: "x".to_string() },
+        WarningType::DeadStore { variable: "y".to_string() },
+    ]);
+
+    assert_eq!(diagnostics.errors(), &[syntax_error("first"), syntax_error("second"), syntax_error("third")]);
+    assert_eq!(diagnostics.warnings(), &[
+        WarningType::DeadStore { variable: "x".to_string() },
+        WarningType::DeadStore { variable: "y".to_string() },
+    ]);
+}
+
+/// This test checks that an error repeated at the same location is reported once, while the same error at another
+/// location, or without one, is reported again.
+#[test]
+fn test_deduplicated_by_location() {
+    let unrecognized = || ErrorType::UnrecognizedToken { token: "@".to_string() };
+    let mut diagnostics = DiagnosticSink::new();
+    diagnostics.error_at(unrecognized(), SourceLocation::new(None, 1, 11));
+    diagnostics.error_at(unrecognized(), SourceLocation::new(None, 2, 9));
+    diagnostics.error_at(unrecognized(), SourceLocation::new(None, 1, 11));
+    diagnostics.error(unrecognized());
+    diagnostics.warning_at(WarningType::DeadStore { variable: "x".to_string() }, SourceLocation::new(None, 3, 5));
+    diagnostics.warning_at(WarningType::DeadStore { variable: "x".to_string() }, SourceLocation::new(None, 4, 5));
+
+    assert_eq!(diagnostics.errors(), &[unrecognized(), unrecognized(), unrecognized()]);
+    assert_eq!(diagnostics.error_locations(), &[
+        Some(SourceLocation::new(None, 1, 11)),
+        Some(SourceLocation::new(None, 2, 9)),
+        None,
+    ]);
+    assert_eq!(diagnostics.warnings().len(), 2);
+}
+
+/// This test checks that the output of a phase is passed through when it succeeds, and that its errors are reported
+/// when it fails.
+#[test]
+fn test_absorb_and_finish() {
+    let mut diagnostics = DiagnosticSink::new();
+    assert_eq!(diagnostics.absorb::<i32>(Ok(1)), Some(1));
+    assert!(!diagnostics.has_errors());
+    assert_eq!(diagnostics.clone().finish("done"), Ok("done"));
+
+    assert_eq!(diagnostics.absorb::<i32>(Err(vec![syntax_error("failed")])), None);
+    assert!(diagnostics.has_errors());
+    assert_eq!(diagnostics.finish("done"), Err(vec![syntax_error("failed")]));
+}
+
+/// This test checks that diagnostics are sorted by file, line, and column with those without a location last, and
+/// that diagnostics at the same location keep the order they were reported in.
+#[test]
+fn test_sorted_by_location() {
+    let at = |file: Option<&str>, line: usize, column: usize| SourceLocation::new(file.map(str::to_string), line, column);
+    let mut diagnostics = DiagnosticSink::with_order(DiagnosticOrder::Location);
+    diagnostics.error(syntax_error("unlocated"));
+    diagnostics.error_at(syntax_error("b.c line 1"), at(Some("b.c"), 1, 1));
+    diagnostics.error_at(syntax_error("line 7"), at(None, 7, 2));
+    diagnostics.error_at(syntax_error("line 3"), at(None, 3, 9));
+    diagnostics.error_at(syntax_error("line 3 again"), at(None, 3, 9));
+    diagnostics.error_at(syntax_error("line 3 earlier"), at(None, 3, 1));
+
+    assert_eq!(diagnostics.errors(), &[
+        syntax_error("line 3 earlier"),
+        syntax_error("line 3"),
+        syntax_error("line 3 again"),
+        syntax_error("line 7"),
+        syntax_error("b.c line 1"),
+        syntax_error("unlocated"),
+    ]);
+    assert_eq!(diagnostics.error_locations()[5], None);
+    assert_eq!(diagnostics.error_locations()[0], Some(at(None, 3, 1)));
+}
+
+/// This test checks that grouping by code keeps the diagnostics of each code in the order they were reported in.
+#[test]
+fn test_grouped_by_code() {
+    let mut diagnostics = DiagnosticSink::with_order(DiagnosticOrder::Code);
+    diagnostics.error(syntax_error("first"));
+    diagnostics.error(ErrorType::MissingMain);
+    diagnostics.error(ErrorType::UndefinedVariable { variable_name: "x".to_string() });
+    diagnostics.error(syntax_error("second"));
+
+    assert_eq!(diagnostics.errors(), &[
+        ErrorType::UndefinedVariable { variable_name: "x".to_string() },
+        syntax_error("first"),
+        syntax_error("second"),
+        ErrorType::MissingMain,
+    ]);
+    assert_eq!("code".parse(), Ok(DiagnosticOrder::Code));
+    assert!("severity".parse::<DiagnosticOrder>().is_err());
+}
+
+/// This test checks that a streaming sink passes on each diagnostic when it is reported, before sorting and without
+/// repeats.
+#[test]
+fn test_streaming() {
+    let streamed = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&streamed);
+    let mut diagnostics = DiagnosticSink::with_order(DiagnosticOrder::Location)
+        .streaming(move |diagnostic: Diagnostic<'_>| sink.lock().unwrap().push(diagnostic.localized(Language::English)));
+    diagnostics.error_at(syntax_error("later"), SourceLocation::new(None, 9, 1));
+    diagnostics.error_at(syntax_error("earlier"), SourceLocation::new(None, 2, 5));
+    diagnostics.error_at(syntax_error("later"), SourceLocation::new(None, 9, 1));
+    diagnostics.warning(WarningType::DeadStore { variable: "x".to_string() });
+
+    let streamed = streamed.lock().unwrap();
+    assert_eq!(streamed.len(), 3);
+    assert!(streamed[0].starts_with("9:1: error[E0004]"), "{}", streamed[0]);
+    assert!(streamed[1].starts_with("2:5: error[E0004]"), "{}", streamed[1]);
+    assert!(streamed[2].starts_with("warning["), "{}", streamed[2]);
+    assert_eq!(diagnostics.errors(), &[syntax_error("earlier"), syntax_error("later")]);
+}
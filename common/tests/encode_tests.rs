@@ -0,0 +1,128 @@
+//! This file contains tests for encoding ASTs in the binary format, making sure they decode to the same AST and that
+//! invalid encodings are rejected.
+
+use common::ast::{
+    build,
+    core::{ASTNode, AST},
+    data_type::DataType,
+    encode::{self, Decoder, Encoder},
+    node_type::NodeType,
+};
+
+/// Builds a program with a node of every kind that has a payload.
+fn program() -> AST {
+    let mut qualified = ASTNode::new(NodeType::Type(DataType::UnsignedLong));
+    qualified.add_child(ASTNode::new(NodeType::Qualifier("volatile".to_string())));
+    let mut variable = ASTNode::new(NodeType::Variable);
+    variable.add_child(build::identifier("total"));
+    variable.add_child(qualified);
+    let mut attribute = ASTNode::new(NodeType::Attribute("inline".to_string()));
+    attribute.add_child(ASTNode::new(NodeType::Constant("MAX".to_string())));
+
+    let main = build::function("main", DataType::Integer)
+        .param("argc", DataType::Integer)
+        .body(vec![
+            variable,
+            build::assign("total", build::binary(build::identifier("argc"), "*", build::literal("2.5"))),
+            build::expression_statement(build::call("puts", vec![build::string("héllo\n")])),
+            build::ret(Some(build::unary("-", build::identifier("total")))),
+        ])
+        .build()
+        .expect("main should be well-formed");
+    AST::new({
+        let mut root = ASTNode::new(NodeType::TopLevelExpression);
+        root.add_children(vec![attribute, main]);
+        root
+    })
+}
+
+/// This test checks that an AST decodes to itself, and that a repeated string is stored once.
+#[test]
+fn test_round_trip() {
+    let ast = program();
+    let bytes = encode::encode(&ast);
+    assert_eq!(encode::decode(&bytes), Some(ast.clone()));
+
+    let mut repeated = ast.get_root();
+    repeated.add_child(build::identifier("total"));
+    let repeated_bytes = encode::encode(&AST::new(repeated));
+    // The new identifier takes its tag and the index of the string, and the root's child count does not grow a byte
+    assert_eq!(repeated_bytes.len(), bytes.len() + 2);
+}
+
+/// This test checks that a program of many functions with nested control flow decodes to itself, and that the
+/// strings its functions share are not stored again for each function.
+#[test]
+fn test_round_trip_many_functions() {
+    let functions = |count: usize| (0..count).map(|i| build::function(&format!("f{}", i), DataType::Integer)
+        .param("a", DataType::Integer)
+        .param("b", DataType::Integer)
+        .body(vec![
+            build::declare("c", DataType::Integer, Some(build::identifier("a"))),
+            build::if_else(build::identifier("a"), vec![
+                build::assign("c", build::binary(build::identifier("c"), "-", build::identifier("b"))),
+            ], None),
+            build::while_loop(build::identifier("b"), vec![
+                build::assign("b", build::binary(build::identifier("b"), "-", build::literal("1"))),
+            ]),
+            build::ret(Some(build::identifier("c"))),
+        ])
+        .build()
+        .expect("the function should be well-formed"))
+        .collect::<Vec<_>>();
+
+    let ast = build::program(functions(200)).expect("the program should be well-formed");
+    let bytes = encode::encode(&ast);
+    assert_eq!(encode::decode(&bytes), Some(ast));
+
+    let encoded_len = |count: usize| encode::encode(&build::program(functions(count)).unwrap()).len();
+    let first = encoded_len(1) - encoded_len(0);
+    let last = bytes.len() - encoded_len(199);
+    assert!(last < first, "the last function took {} bytes, the first {}", last, first);
+}
+
+/// This test checks that truncated, extended, corrupted, and foreign encodings are rejected.
+#[test]
+fn test_invalid_encodings() {
+    let bytes = encode::encode(&program());
+
+    for len in 0..bytes.len() {
+        assert_eq!(encode::decode(&bytes[..len]), None, "decoded the first {} bytes", len);
+    }
+    let mut extended = bytes.clone();
+    extended.push(0);
+    assert_eq!(encode::decode(&extended), None);
+    let mut other_version = bytes.clone();
+    other_version[5] += 1;
+    assert_eq!(encode::decode(&other_version), None);
+    assert_eq!(encode::decode(b"{\"node_type\": \"Break\"}"), None);
+}
+
+/// This test checks that an encoding of the first version, which has no node kinds the current version lacks, is
+/// still decoded.
+#[test]
+fn test_decodes_earlier_version() {
+    let mut first_version = encode::encode(&program());
+    first_version[5] = 1;
+    assert_eq!(encode::decode(&first_version), Some(program()));
+}
+
+/// This test checks that other values can be written around nodes and read back in order.
+#[test]
+fn test_encoder_and_decoder() {
+    let mut encoder = Encoder::new();
+    encoder.write_usize(300);
+    encoder.write_str("x");
+    encoder.write_node(&build::identifier("x"));
+    encoder.write_data_type(DataType::Double);
+    let bytes = encoder.finish();
+
+    let mut decoder = Decoder::new(&bytes).expect("The header should be valid");
+    assert_eq!(decoder.read_usize(), Some(300));
+    assert_eq!(decoder.read_str(), Some("x"));
+    assert_eq!(decoder.read_node(), Some(build::identifier("x")));
+    assert!(!decoder.is_finished());
+    assert_eq!(decoder.read_data_type(), Some(DataType::Double));
+    assert!(decoder.is_finished());
+    assert_eq!(decoder.read_usize(), None);
+}
@@ -0,0 +1,87 @@
+//! This file contains tests for rewriting ASTs, making sure edits apply together and invalid edits are reported.
+
+use common::{
+    ast::{
+        build,
+        core::{ASTNode, AST},
+        data_type::DataType,
+        node_type::NodeType,
+        rewrite::{self, Transaction},
+    },
+    error::ErrorType,
+};
+
+/// Builds `int main() { int x = 1; x = x + 2; return x; }`.
+fn program() -> AST {
+    let main = build::function("main", DataType::Integer)
+        .body(vec![
+            build::declare("x", DataType::Integer, Some(build::literal("1"))),
+            build::assign("x", build::binary(build::identifier("x"), "+", build::literal("2"))),
+            build::ret(Some(build::identifier("x"))),
+        ])
+        .build()
+        .expect("main should be well-formed");
+    build::program(vec![main]).expect("The program should be well-formed")
+}
+
+fn is_identifier(node: &ASTNode, name: &str) -> bool {
+    node.get_node_type() == NodeType::Identifier(name.to_string())
+}
+
+/// This test checks that every reference to a variable can be renamed in one transaction.
+#[test]
+fn test_rename_variable() {
+    let ast = program();
+    let paths = rewrite::find(&ast, |node| is_identifier(node, "x"));
+    assert_eq!(paths.len(), 4);
+
+    let renamed = paths.iter()
+        .fold(Transaction::new(), |transaction, path| transaction.replace(path, build::identifier("total")))
+        .commit(&ast)
+        .expect("The rename should apply");
+
+    assert!(rewrite::find(&renamed, |node| is_identifier(node, "x")).is_empty());
+    assert_eq!(rewrite::find(&renamed, |node| is_identifier(node, "total")), paths);
+}
+
+/// This test checks that insertions and deletions addressed against the same AST apply together, with indices
+/// referring to the AST before any edit.
+#[test]
+fn test_insert_and_delete() {
+    let ast = program();
+    let body = [0, 2];
+    let rewritten = Transaction::new()
+        .delete(&[0, 2, 1])
+        .insert(&body, 1, build::expression_statement(build::call("log", vec![build::identifier("x")])))
+        .insert(&body, 3, build::ret(Some(build::literal("0"))))
+        .commit(&ast)
+        .expect("The edits should apply");
+
+    let statements = rewrite::node_at(&rewritten.get_root(), &body).expect("The body should remain").get_children();
+    let kinds: Vec<NodeType> = statements.iter().map(|statement| statement.get_node_type()).collect();
+    assert_eq!(kinds, vec![NodeType::Initialization, NodeType::ExpressionStatement, NodeType::Return, NodeType::Return]);
+    // The original AST is left as it was
+    assert_eq!(ast, program());
+}
+
+/// This test checks that a transaction with an invalid edit applies none of its edits and reports each problem.
+#[test]
+fn test_invalid_edits() {
+    let ast = program();
+    let errors = Transaction::new()
+        .replace(&[0, 9], build::identifier("y"))
+        .delete(&[0, 2])
+        .replace(&[0, 2, 0], build::identifier("if"))
+        .insert(&[0], 7, build::literal("1"))
+        .commit(&ast)
+        .expect_err("The edits should be rejected");
+
+    assert_eq!(errors, vec![
+        ErrorType::SyntaxError { message: "No node at path [0, 9]".to_string() },
+        ErrorType::SyntaxError { message: "Cannot insert at index 7 of the node at path [0]".to_string() },
+        ErrorType::SyntaxError {
+            message: "The edit at path [0, 2, 0] is inside a node that is replaced or deleted".to_string(),
+        },
+        ErrorType::ReservedKeyword { keyword: "if".to_string() },
+    ]);
+}
@@ -0,0 +1,42 @@
+//! This file contains tests for the schema of the binary AST format, making sure the published schema matches the
+//! format and that encodings of other versions are told apart from corrupted ones.
+
+use common::ast::{
+    build,
+    core::AST,
+    encode,
+    schema::{self, decode_checked, format_version, negotiate, FormatError, Payload, FORMAT_VERSION},
+};
+
+/// This test checks that the schema published with the crate is the schema of the format. When it fails after a
+/// node type or data type was added, bump the format version and publish the new schema.
+#[test]
+fn test_published_schema() {
+    let schema = schema::schema();
+    assert_eq!(include_str!("../ast-schema.json"), schema.to_json());
+
+    let literal = schema.node_kinds.iter().find(|kind| kind.name == "Literal").unwrap();
+    assert_eq!((literal.tag, literal.payload), (100, Payload::String));
+    assert_eq!(schema.node_kinds.iter().find(|kind| kind.name == "IfStatement").unwrap().payload, Payload::None);
+    assert!(schema.to_json().ends_with(&format!("\"fingerprint\": \"{:016x}\"\n}}\n", schema.fingerprint())));
+}
+
+/// This test checks that the version of an encoding is read from its header, and that encodings of an unsupported
+/// version, corrupted encodings, and other bytes are each rejected for their own reason.
+#[test]
+fn test_version_checks() {
+    let ast = AST::new(build::block(vec![build::assign("x", build::literal("1"))]));
+    let bytes = encode::encode(&ast);
+    assert_eq!(format_version(&bytes), Ok(FORMAT_VERSION));
+    assert_eq!(decode_checked(&bytes), Ok(ast));
+
+    let mut future = bytes.clone();
+    future[5] = FORMAT_VERSION + 1;
+    assert_eq!(decode_checked(&future), Err(FormatError::UnsupportedVersion { found: FORMAT_VERSION + 1 }));
+    assert_eq!(decode_checked(&bytes[..bytes.len() - 1]), Err(FormatError::Malformed));
+    assert_eq!(decode_checked(b"SIAST"), Err(FormatError::Malformed));
+    assert_eq!(decode_checked(b"int main() {}"), Err(FormatError::NotAnEncoding));
+
+    assert_eq!(negotiate(&[FORMAT_VERSION, FORMAT_VERSION + 1]), Some(FORMAT_VERSION));
+    assert_eq!(negotiate(&[FORMAT_VERSION + 1]), None);
+}
@@ -0,0 +1,51 @@
+//! This file contains tests for the line index, making sure byte offsets map to the same lines and columns whatever
+//! the line endings, and that lines and columns map back to the offsets they came from.
+
+use common::source::LineIndex;
+
+/// This test checks that `\n`, `\r\n`, and `\r` each end one line, that a byte order mark is not part of the first
+/// line, and that columns count characters rather than bytes.
+#[test]
+fn test_line_col() {
+    let index = LineIndex::new("a\nb\r\nc\rd\r\n\ré");
+    assert_eq!(index.line_count(), 6);
+    let expected = [(0, (1, 1)), (2, (2, 1)), (3, (2, 2)), (4, (2, 3)), (5, (3, 1)), (7, (4, 1)), (10, (5, 1)), (11, (6, 1))];
+    for (offset, line_col) in expected {
+        assert_eq!(index.line_col(offset), line_col, "offset {}", offset);
+    }
+    // The end of the source and the second byte of `é` are in the column after and of `é`
+    assert_eq!(index.line_col(13), (6, 2));
+    assert_eq!(index.line_col(12), (6, 1));
+
+    let index = LineIndex::new("\u{feff}int x;");
+    assert_eq!(index.line_col(3), (1, 1));
+    assert_eq!(index.location(7).to_string(), "1:5");
+}
+
+/// This test checks that a `#line` directive renumbers the lines after it but not its own, and that a directive
+/// without a file keeps the file of the one before it.
+#[test]
+fn test_line_directives() {
+    let mut index = LineIndex::new("a\n#line 40 \"gen.c\"\nb\nc\n#line 7\nd");
+    index.add_line_directive(1, 40, Some("gen.c".to_string()));
+    index.add_line_directive(4, 7, None);
+    let locations: Vec<String> = [0, 2, 19, 21, 31].iter().map(|offset| index.location(*offset).to_string()).collect();
+    assert_eq!(locations, vec!["1:1", "2:1", "gen.c:40:1", "gen.c:41:1", "gen.c:7:1"]);
+    assert_eq!(index.line_col(31), (6, 1));
+}
+
+/// This test checks that every character's line and column map back to its offset, and that positions outside the
+/// source do not.
+#[test]
+fn test_offset_round_trip() {
+    let source = "int main() {\r\n\tchar *s = \"héllo\";\r\n\treturn 0;\n}";
+    let index = LineIndex::new(source);
+    for (offset, _) in source.char_indices() {
+        let (line, column) = index.line_col(offset);
+        assert_eq!(index.offset(line, column), Some(offset), "offset {}", offset);
+    }
+    assert_eq!(index.offset(4, 2), Some(source.len()));
+    assert_eq!(index.offset(4, 3), None);
+    assert_eq!(index.offset(5, 1), None);
+    assert_eq!(index.offset(0, 1), None);
+}
@@ -0,0 +1,20 @@
+//! This file contains tests for interned strings.
+
+use common::symbol::Symbol;
+
+/// This test checks that interning the same string twice gives the same symbol, and different strings different ones.
+#[test]
+fn test_intern_same_string() {
+    assert_eq!(Symbol::intern("count"), Symbol::intern(&String::from("count")));
+    assert_ne!(Symbol::intern("count"), Symbol::intern("total"));
+}
+
+/// This test checks that a symbol gives back its string, and prints as it.
+#[test]
+fn test_symbol_text() {
+    let symbol = Symbol::from("hello world");
+
+    assert_eq!(symbol.as_str(), "hello world");
+    assert_eq!(symbol.to_string(), "hello world");
+    assert_eq!(format!("{:?}", symbol), "\"hello world\"");
+}
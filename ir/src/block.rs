@@ -1,15 +1,19 @@
 //! This file hosts all of the functions necessary for generating LLVM IR
 //! for "block" nodes, nodes that generate and manipulate basic blocks.
 
+use std::collections::HashSet;
+
 use common::{
     ast::{
         core::ASTNode, data_type::DataType, node_type::NodeType
-    }, constants::{DEFAULT_DO_BODY_LABEL, DEFAULT_DO_CONDITION_LABEL, DEFAULT_DO_WHILE_END_LABEL, DEFAULT_ELSE_LABEL, DEFAULT_ENTRY_LABEL, DEFAULT_FOR_BODY_LABEL, DEFAULT_FOR_COND_LABEL, DEFAULT_FOR_END_LABEL, DEFAULT_FOR_INCREMENT_LABEL, DEFAULT_MERGE_LABEL, DEFAULT_THEN_LABEL, DEFAULT_WHILE_BODY_LABEL, DEFAULT_WHILE_COND_LABEL, DEFAULT_WHILE_END_LABEL}, error::ErrorType
+    }, config::OptLevel, constants::{DEFAULT_DO_BODY_LABEL, DEFAULT_DO_CONDITION_LABEL, DEFAULT_DO_WHILE_END_LABEL, DEFAULT_ELSE_LABEL, DEFAULT_ENTRY_LABEL, DEFAULT_FOR_BODY_LABEL, DEFAULT_FOR_COND_LABEL, DEFAULT_FOR_END_LABEL, DEFAULT_FOR_INCREMENT_LABEL, DEFAULT_MERGE_LABEL, DEFAULT_SWITCH_CASE_LABEL, DEFAULT_SWITCH_DEFAULT_LABEL, DEFAULT_SWITCH_END_LABEL, DEFAULT_SWITCH_TEST_LABEL, DEFAULT_THEN_LABEL, DEFAULT_WHILE_BODY_LABEL, DEFAULT_WHILE_COND_LABEL, DEFAULT_WHILE_END_LABEL, SWITCH_TABLE_MIN_DENSITY}, error::ErrorType,
+    warning::WarningType,
 };
 
-use safe_llvm::ir::core::{Tag, ValueTag};
+use safe_llvm::ir::core::{BasicBlockTag, Tag, TypeTag, ValueTag};
 use safe_llvm::common::pointer::{LLVMRef, LLVMRefType};
-use crate::core::IRGenerator;
+use sts::lint::describe;
+use crate::{context::{BlockExit, CodegenCtx}, core::{IRGenerator, SourceConstruct}, primitive::constant_integer_value};
 
 impl IRGenerator {
     /// Generates LLVM IR for a function declaration.
@@ -17,6 +21,7 @@ impl IRGenerator {
     /// # Parameters
     ///
     /// - `node`: A reference to an `ASTNode` to generate IR for a function declaration.
+    /// - `ctx`: The context of the function being generated.
     ///
     /// # Returns
     ///
@@ -31,28 +36,176 @@ impl IRGenerator {
     /// 
     /// ```
     /// //let a_node: ASTNode = /* Some ASTNode we want to generate a function declaration from */
-    /// //let result = self.generate_fn_declaration_ir(&a_node);
+    /// //let result = self.generate_fn_declaration_ir(&a_node, &mut ctx);
     /// /* check if type_result was Ok or Err, if Ok, it will contain the Tag that houses the 
     /// function's ValueTag. */
     /// ```
-    pub fn generate_fn_declaration_ir(&mut self, node: &ASTNode) -> Result<Option<Tag>, ErrorType> {
+    pub fn generate_fn_declaration_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
         let children = node.get_children();
-        if children.len() != 3 {
+        if children.len() < 3 {
             return Err(ErrorType::DevError { message: "Invalid function declaration node".to_string() });
         }
 
+        // Children are the name, any parameters, the return type, and the body
         let name_node = &children[0];
-        let type_node = &children[1];
-        let block_node = &children[2];
+        let param_nodes = &children[1..children.len() - 2];
+        let type_node = &children[children.len() - 2];
+        let block_node = &children[children.len() - 1];
 
         let name = match name_node.get_node_type() {
             NodeType::Identifier(name) => name,
             _ => return Err(ErrorType::DevError { message: "Expected identifier node".to_string() })
         };
 
-        let type_ptr = self.ir_router(type_node)?;
+        let (func_tag, params, param_data_types) = self.generate_fn_signature_ir(&name, param_nodes, type_node, ctx)?;
+        // In an executable, the C library's startup code calls `main` and nothing outside the module calls the rest
+        if self.get_codegen_config().requires_main() && name != "main" {
+            let resource_pools = self.get_resource_pools();
+            let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in function declaration!");
+            resource_pools.set_internal_linkage(func_tag)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to set function linkage".to_string() })?;
+        }
+        // Returns are converted to the declared return type
+        let return_data_type = match type_node.get_node_type() {
+            NodeType::Type(data_type) => data_type,
+            _ => DataType::Integer,
+        };
+
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in function declaration!");
+
+        // Locals are allocated in a block of their own that starts the function and falls through to the code of
+        // the entry block once the body is generated, while their stores stay where they are declared
+        let next_id = self.get_next_label_id();
+        let allocas_label = format!("allocasID{}", next_id);
+        self.annotate_block(&allocas_label, SourceConstruct::Function(name.clone()));
+        let allocas_block = resource_pools.create_basic_block(self.get_context(), func_tag, &allocas_label)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to create allocas block".to_string() })?;
+        let entry_label = format!("entryID{}", next_id);
+        let entry_block = resource_pools.create_basic_block_after(self.get_context(), func_tag, allocas_block, &entry_label)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to create entry block".to_string() })?;
+
+        // The body is generated in a context of its own, so nothing from another function is carried into it
+        let mut ctx = CodegenCtx::for_function(func_tag, return_data_type, allocas_block);
+
+        resource_pools.position_builder_at_end(self.get_alloca_builder(&ctx), allocas_block)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
+        resource_pools.position_builder_at_end(self.get_builder(), entry_block)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
+
+        // Spill each parameter into a local so it can be read and assigned like any other variable. A struct
+        // parameter is copied out of the caller's memory, so changes to it are never seen by the caller.
+        for (index, (param_name, param_type, struct_type)) in params.into_iter().enumerate() {
+            let param = resource_pools.get_param(func_tag, index as u32)
+                .ok_or_else(|| ErrorType::DevError { message: format!("Failed to get parameter {}", param_name) })?;
+            let (local_type, value) = match struct_type {
+                Some(struct_type) => {
+                    let copy = resource_pools.get_var(self.get_builder(), struct_type, param, &format!("{}.byval", param_name))
+                        .ok_or_else(|| ErrorType::DevError { message: "Failed to copy struct parameter".to_string() })?;
+                    (struct_type, copy)
+                },
+                None => (param_type, param),
+            };
+            let alloca = resource_pools.init_var(self.get_alloca_builder(&ctx), &param_name, local_type, None)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to initialize parameter".to_string() })?;
+            resource_pools.reassign_var(self.get_builder(), alloca, value)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to store parameter".to_string() })?;
+            let data_type = match struct_type {
+                Some(_) => DataType::Struct,
+                None => param_data_types[index],
+            };
+            let span = param_nodes.iter()
+                .find(|param_node| param_node.get_children().first().map(|name| name.get_node_type()) == Some(NodeType::Identifier(param_name.clone())))
+                .and_then(Self::declared_name_span);
+            self.add_tag_to_store_table(param_name.clone(), alloca, Some(data_type), true, false, span);
+            if let Some(struct_type) = struct_type {
+                self.add_struct_variable_type(param_name, struct_type);
+            }
+        }
+
+        // Release lock before processing block
+        drop(resource_pools);
+
+        let generated = self.ir_router(block_node, &mut ctx);
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in function declaration!");
+        let closed = resource_pools.create_br(self.get_alloca_builder(&ctx), entry_block);
+        drop(resource_pools);
+        generated?;
+        closed.ok_or_else(|| ErrorType::DevError { message: "Failed to branch from allocas block".to_string() })?;
+
+        Ok(None)
+    }
+
+    /// Generates LLVM IR for a function prototype, declaring the function without a body so that it can be called
+    /// before it is defined, or defined outside the module, as `int foo(int a);` does in C.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to a `FunctionPrototype` `ASTNode`, whose children are the function's identifier, its
+    /// parameters, and its return type.
+    /// - `ctx`: The context the prototype is in.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Option<Tag>, ErrorType>` containing the Tag of the declared function.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if the prototype is malformed or generation failed.
+    pub fn generate_fn_prototype_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
+        let children = node.get_children();
+        let name = match children.first().map(|c| c.get_node_type()) {
+            Some(NodeType::Identifier(name)) if children.len() >= 2 => name,
+            _ => return Err(ErrorType::DevError { message: "Invalid function prototype node".to_string() })
+        };
+        let param_nodes = &children[1..children.len() - 1];
+        let type_node = &children[children.len() - 1];
+        let (func_tag, _, _) = self.generate_fn_signature_ir(&name, param_nodes, type_node, ctx)?;
+        Ok(Some(Tag::Value(func_tag)))
+    }
+
+    /// Declares a function with its parameters and return type, recording it in the function table and its
+    /// parameter types for calls. A function already declared by a prototype is not declared again, so its
+    /// definition gives the declaration a body. A variadic function is declared with a variadic LLVM function type,
+    /// so calls may pass arguments after its parameters.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the function.
+    /// - `param_nodes`: The function's `Parameter` nodes, followed by a `Variadic` node if it is variadic.
+    /// - `type_node`: The function's return type.
+    /// - `ctx`: The context the function is declared in.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the function's `ValueTag`, how each parameter is passed, and the declared type
+    /// of each parameter.
+    fn generate_fn_signature_ir(&mut self, name: &str, param_nodes: &[ASTNode], type_node: &ASTNode, ctx: &mut CodegenCtx) -> Result<(ValueTag, Vec<(String, TypeTag, Option<TypeTag>)>, Vec<DataType>), ErrorType> {
+        let type_ptr = self.ir_router(type_node, ctx)?;
         let type_ptr = type_ptr.expect("Missing type");
 
+        let variadic = param_nodes.last().is_some_and(|node| node.get_node_type() == NodeType::Variadic);
+        let param_nodes = if variadic { &param_nodes[..param_nodes.len() - 1] } else { param_nodes };
+        let mut params = Vec::new();
+        for param_node in param_nodes {
+            params.push(self.generate_parameter_ir(param_node, ctx)?);
+        }
+        let param_types: Vec<TypeTag> = params.iter().map(|(_, param_type, _)| *param_type).collect();
+
+        // Calls convert their arguments to the declared parameter types
+        let param_data_types: Vec<DataType> = param_nodes.iter()
+            .map(|param_node| match param_node.get_children().get(1).map(|t| t.get_node_type()) {
+                Some(NodeType::Type(data_type)) => data_type,
+                _ => DataType::Integer,
+            })
+            .collect();
+        self.add_function_parameter_types(name.to_string(), param_data_types.clone());
+
+        if let Some((func_tag, _)) = self.search_function_table(name) {
+            return Ok((func_tag, params, param_data_types));
+        }
+
         let resource_pools = self.get_resource_pools();
         let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in function declaration!");
 
@@ -61,31 +214,65 @@ impl IRGenerator {
             _ => return Err(ErrorType::DevError { message: "Expected type tag".to_string() })
         };
 
-        // Create a function type with the return type and no parameters (for now)
-        let fn_type = resource_pools.create_function(Some(return_type), &[], false, self.get_context())
+        let fn_type = resource_pools.create_function(Some(return_type), &param_types, variadic, self.get_context())
             .ok_or_else(|| ErrorType::DevError { message: "Failed to create function type".to_string() })?;
 
         let module_tag = self.get_module();
 
-        let func_tag = resource_pools.add_function_to_module(module_tag, &name, fn_type)
+        let func_tag = resource_pools.add_function_to_module(module_tag, name, fn_type.clone())
             .ok_or_else(|| ErrorType::DevError { message: "Failed to add function to module".to_string() })?;
+        self.add_function_to_table(name.to_string(), func_tag.clone(), fn_type);
 
-        // Set this as the current function
-        self.set_function(func_tag);
-
-        let label = format!("entryID{}", self.get_next_label_id());
-        let entry_block = resource_pools.create_basic_block(self.get_context(), func_tag, &label)
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to create entry block".to_string() })?;
-
-        resource_pools.position_builder_at_end(self.get_builder(), entry_block)
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
+        // Structs are passed by value as a pointer to a copy made by the caller, per the C ABI
+        for (index, (_, _, struct_type)) in params.iter().enumerate() {
+            if let Some(struct_type) = struct_type {
+                resource_pools.add_byval_attribute(func_tag, index as u32, *struct_type)
+                    .ok_or_else(|| ErrorType::DevError { message: "Failed to add byval attribute".to_string() })?;
+            }
+        }
+        Ok((func_tag, params, param_data_types))
+    }
 
-        // Release lock before processing block
-        drop(resource_pools);
+    /// Determines how a function parameter is passed.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to a `Parameter` `ASTNode`, whose children are the parameter's identifier and type.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the parameter's name, its LLVM parameter type, and, for a struct passed by
+    /// value, the struct type the parameter points to.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if the parameter is malformed or its struct type was not declared.
+    fn generate_parameter_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<(String, TypeTag, Option<TypeTag>), ErrorType> {
+        let children = node.get_children();
+        let (name, type_node) = match (children.first().map(|c| c.get_node_type()), children.get(1)) {
+            (Some(NodeType::Identifier(name)), Some(type_node)) => (name, type_node),
+            _ => return Err(ErrorType::DevError { message: "Invalid parameter node".to_string() })
+        };
 
-        let _ = self.ir_router(block_node)?;
+        if type_node.get_node_type() == NodeType::Type(DataType::Struct) {
+            let struct_name = match type_node.get_children().first().map(|c| c.get_node_type()) {
+                Some(NodeType::Identifier(struct_name)) => struct_name,
+                _ => return Err(ErrorType::DevError { message: "Expected struct name in parameter type".to_string() })
+            };
+            let struct_type = self.search_struct_type(&struct_name)
+                .ok_or_else(|| ErrorType::DevError { message: format!("Struct not found: {}", struct_name) })?;
+            let resource_pools = self.get_resource_pools();
+            let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in parameter!");
+            let ptr_type = resource_pools.pointer_type(self.get_context())
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create pointer type".to_string() })?;
+            return Ok((name, ptr_type, Some(struct_type)));
+        }
 
-        Ok(None)
+        match self.ir_router(type_node, ctx)? {
+            Some(Tag::Type(param_type)) => Ok((name, param_type, None)),
+            _ => Err(ErrorType::DevError { message: "Expected type tag".to_string() })
+        }
     }
     
     /// Generates LLVM IR for a block expression.
@@ -93,6 +280,7 @@ impl IRGenerator {
     /// # Parameters
     ///
     /// - `node`: A reference to an `ASTNode` to generate IR for a block expression.
+    /// - `ctx`: The context of the function being generated.
     ///
     /// # Returns
     ///
@@ -107,21 +295,61 @@ impl IRGenerator {
     /// 
     /// ```
     /// //let a_node: ASTNode = /* Some ASTNode we want to generate a block expression from */
-    /// //let result = self.generate_block_exp(&a_node);
+    /// //let result = self.generate_block_exp(&a_node, &mut ctx);
     /// /* check if type_result was Ok or Err, if Ok, it will contain None. */
     /// ```
-    pub fn generate_block_exp(&mut self, node: &ASTNode) -> Result<Option<Tag>, ErrorType> {
-        for child in node.get_children() {
-            let _ = self.ir_router(&child)?;
-        }
+    pub fn generate_block_exp(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
+        self.generate_body_ir(node, ctx)?;
         Ok(None)
     }
 
+    /// Generates LLVM IR for a block, or a single statement, that is the body of a branch, loop or case, reporting
+    /// whether it ended in a terminator. Statements after a `return`, `break` or `continue` in the same block can
+    /// never run, and are not generated, since nothing may follow a terminator in a basic block.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to the body `ASTNode`, such as a `BlockExpression` or an `ElseStatement`.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<BlockExit, ErrorType>` telling whether the code after the body is reached from its end.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if generation failed.
+    pub fn generate_body_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<BlockExit, ErrorType> {
+        match node.get_node_type() {
+            NodeType::BlockExpression | NodeType::ElseStatement => {
+                for child in node.get_children() {
+                    if self.generate_body_ir(&child, ctx)? == BlockExit::Terminated {
+                        return Ok(BlockExit::Terminated);
+                    }
+                }
+                Ok(BlockExit::FallsThrough)
+            },
+            NodeType::Return | NodeType::Break | NodeType::Continue => {
+                self.ir_router(node, ctx)?;
+                Ok(BlockExit::Terminated)
+            },
+            _ => {
+                self.ir_router(node, ctx)?;
+                Ok(BlockExit::FallsThrough)
+            }
+        }
+    }
+
     /// Generates LLVM IR for a do while loop.
+    ///
+    /// The condition is generated once, into its own block, which is entered after every iteration and by every
+    /// `continue`. Its side effects, such as the increment in `do { ... } while (i++ != n);`, therefore happen exactly
+    /// once each time it is checked, after the body has run.
     /// 
     /// # Parameters
     ///
     /// - `node`: A reference to an `ASTNode` to generate IR for a do while loop.
+    /// - `ctx`: The context of the function being generated.
     ///
     /// # Returns
     ///
@@ -136,10 +364,10 @@ impl IRGenerator {
     /// 
     /// ```
     /// //let a_node: ASTNode = /* Some ASTNode we want to generate a do while loop from */
-    /// //let result = self.generate_do_while_ir(&a_node);
+    /// //let result = self.generate_do_while_ir(&a_node, &mut ctx);
     /// /* check if type_result was Ok or Err, if Ok, it will contain None. */
     /// ```
-    pub fn generate_do_while_ir(&mut self, node: &ASTNode) -> Result<Option<Tag>, ErrorType> {
+    pub fn generate_do_while_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
         let children = node.get_children();
         
         // Handle do-while loops with different numbers of children
@@ -152,7 +380,7 @@ impl IRGenerator {
             })
         };
 
-        let function = self.get_function().unwrap();
+        let function = ctx.get_function().unwrap();
         let resource_pools = self.get_resource_pools();
         let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in do-while!");
 
@@ -160,9 +388,10 @@ impl IRGenerator {
         let body_label = format!("do_bodyID{}", next_id);
         let cond_label = format!("do_condID{}", next_id);
         let end_label = format!("do_endID{}", next_id);
+        self.annotate_block(&body_label, SourceConstruct::DoWhile);
 
         // Create blocks in the correct order
-        let current_insert = self.get_current_insert_block().unwrap_or_else(|| {
+        let current_insert = ctx.get_current_insert_block().unwrap_or_else(|| {
             resource_pools.get_current_block(self.get_builder()).expect("No current block!")
         });
 
@@ -181,48 +410,38 @@ impl IRGenerator {
         drop(resource_pools);
 
         // Process body with break/continue targets, if it exists
-        self.push_break_continue_target(end_block.clone(), cond_block.clone());
-        if let Some(body_node) = body_node_opt {
-            let _ = self.ir_router(body_node)?;
-        }
-        self.pop_target();
+        ctx.push_break_continue_target(end_block.clone(), cond_block.clone());
+        let body_exit = match body_node_opt {
+            Some(body_node) => self.generate_body_ir(body_node, ctx)?,
+            None => BlockExit::FallsThrough,
+        };
+        ctx.pop_target();
 
-        // Branch to condition block
+        // Branch to condition block, unless the body already left it
         let resource_pools = self.get_resource_pools();
         let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in do-while!");
-        resource_pools.create_br(self.get_builder(), cond_block.clone())
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to create branch".to_string() })?;
+        if body_exit == BlockExit::FallsThrough {
+            resource_pools.create_br(self.get_builder(), cond_block.clone())
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create branch".to_string() })?;
+        }
         resource_pools.position_builder_at_end(self.get_builder(), cond_block.clone())
             .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
         drop(resource_pools);
 
-        // Process condition if it exists, otherwise use true (1) as default
-        let llvm_cond = if let Some(cond_node) = cond_node_opt {
-            let cond_ptr = self.ir_router(cond_node)?;
-            let cond_ptr = cond_ptr.expect("Missing condition");
-            match cond_ptr {
-                Tag::Value(value) => value,
-                _ => return Err(ErrorType::DevError { message: "Expected value tag".to_string() })
-            }
-        } else {
-            // Default to true (1) if no condition provided
-            let resource_pools = self.get_resource_pools();
-            let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in do-while condition!");
-            let true_value = resource_pools.create_integer(self.get_context(), 1)
-                .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer constant for true".to_string() })?;
-            drop(resource_pools);
-            true_value
+        // Process condition if it exists, otherwise use true as default. An empty condition, as in `for (;;)`,
+        // is also true
+        let default_cond = ASTNode::new(NodeType::Literal("true".to_string()));
+        let cond_node = cond_node_opt.filter(|cond| !cond.get_children().is_empty()).unwrap_or(&default_cond);
+        let cond_ptr = self.ir_router(cond_node, ctx)?;
+        let llvm_cond = match cond_ptr {
+            Some(Tag::Value(value)) => value,
+            _ => return Err(ErrorType::DevError { message: "Expected value tag".to_string() })
         };
+        let bool_cond = self.lower_condition_value(cond_node, llvm_cond)?;
 
         // Re-acquire lock
         let resource_pools = self.get_resource_pools();
         let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in do-while!");
-        let zero = resource_pools.create_integer(self.get_context(), 0)
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer constant".to_string() })?;
-        let eq = resource_pools.build_icmp_eq(self.get_builder(), llvm_cond, zero, "cmptmp")
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to create comparison".to_string() })?;
-        let bool_cond = resource_pools.build_logical_not(self.get_builder(), self.get_context(), eq, "nottmp")
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to create logical not".to_string() })?;
 
         // Create conditional branch
         resource_pools.create_cond_br(self.get_builder(), bool_cond, body_block, end_block.clone())
@@ -234,10 +453,18 @@ impl IRGenerator {
     }
 
     /// Generates LLVM IR for a while loop.
+    ///
+    /// The condition is generated once, into its own block, which is entered before every iteration and by every
+    /// `continue`. Its side effects, such as the increment in `while (i++ != n)`, therefore happen exactly once each
+    /// time it is checked, before the body runs, including the final check that leaves the loop.
+    ///
+    /// From `-O1`, a loop whose condition is a constant is generated without it: a false one generates nothing, and
+    /// a true one branches from the end of its body straight back to the start.
     /// 
     /// # Parameters
     ///
     /// - `node`: A reference to an `ASTNode` to generate IR for a while loop.
+    /// - `ctx`: The context of the function being generated.
     ///
     /// # Returns
     ///
@@ -252,10 +479,10 @@ impl IRGenerator {
     /// 
     /// ```
     /// //let a_node: ASTNode = /* Some ASTNode we want to generate a while loop from */
-    /// //let result = self.generate_while_ir(&a_node);
+    /// //let result = self.generate_while_ir(&a_node, &mut ctx);
     /// /* check if type_result was Ok or Err, if Ok, it will contain None. */
     /// ```
-    pub fn generate_while_ir(&mut self, node: &ASTNode) -> Result<Option<Tag>, ErrorType> {
+    pub fn generate_while_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
         let children = node.get_children();
         
         // Handle while loops with different numbers of children
@@ -267,8 +494,13 @@ impl IRGenerator {
                 message: format!("Invalid while node: unexpected number of children {}", children.len()) 
             })
         };
+        match cond_node_opt.and_then(|cond_node| self.constant_branch(cond_node)) {
+            Some(false) => return Ok(None),
+            Some(true) => return self.generate_endless_loop_ir(body_node_opt, ctx),
+            None => {}
+        }
 
-        let function = self.get_function().unwrap();
+        let function = ctx.get_function().unwrap();
         let resource_pools = self.get_resource_pools();
         let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in while!");
 
@@ -276,9 +508,10 @@ impl IRGenerator {
         let cond_label = format!("while_condID{}", next_id);
         let body_label = format!("while_bodyID{}", next_id);
         let end_label = format!("while_endID{}", next_id);
+        self.annotate_block(&cond_label, SourceConstruct::While);
 
         // Create blocks in the correct order
-        let current_insert = self.get_current_insert_block().unwrap_or_else(|| {
+        let current_insert = ctx.get_current_insert_block().unwrap_or_else(|| {
             resource_pools.get_current_block(self.get_builder()).expect("No current block!")
         });
         let cond_block = resource_pools.create_basic_block_after(self.get_context(), function, current_insert, &cond_label)
@@ -295,33 +528,19 @@ impl IRGenerator {
             .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
         drop(resource_pools);
         
-        // Process condition if it exists, otherwise use true (1) as default
-        let llvm_cond = if let Some(cond_node) = cond_node_opt {
-            let cond_ptr = self.ir_router(cond_node)?;
-            let cond_ptr = cond_ptr.expect("Missing condition");
-            match cond_ptr {
-                Tag::Value(value) => value,
-                _ => return Err(ErrorType::DevError { message: "Expected value tag".to_string() })
-            }
-        } else {
-            // Default to true (1) if no condition provided
-            let resource_pools = self.get_resource_pools();
-            let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in while condition!");
-            let true_value = resource_pools.create_integer(self.get_context(), 1)
-                .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer constant for true".to_string() })?;
-            drop(resource_pools);
-            true_value
+        // Process condition if it exists, otherwise use true as default. An empty condition, as in `for (;;)`,
+        // is also true
+        let default_cond = ASTNode::new(NodeType::Literal("true".to_string()));
+        let cond_node = cond_node_opt.filter(|cond| !cond.get_children().is_empty()).unwrap_or(&default_cond);
+        let cond_ptr = self.ir_router(cond_node, ctx)?;
+        let llvm_cond = match cond_ptr {
+            Some(Tag::Value(value)) => value,
+            _ => return Err(ErrorType::DevError { message: "Expected value tag".to_string() })
         };
+        let bool_cond = self.lower_condition_value(cond_node, llvm_cond)?;
 
-        // Create conditional branch
         let resource_pools = self.get_resource_pools();
         let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in while!");
-        let zero = resource_pools.create_integer(self.get_context(), 0)
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer constant".to_string() })?;
-        let eq = resource_pools.build_icmp_eq(self.get_builder(), llvm_cond, zero, "cmptmp")
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to create comparison".to_string() })?;
-        let bool_cond = resource_pools.build_logical_not(self.get_builder(), self.get_context(), eq, "nottmp")
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to create logical not".to_string() })?;
         resource_pools.create_cond_br(self.get_builder(), bool_cond, body_block.clone(), end_block.clone())
             .ok_or_else(|| ErrorType::DevError { message: "Failed to create conditional branch".to_string() })?;
         resource_pools.position_builder_at_end(self.get_builder(), body_block.clone())
@@ -329,27 +548,86 @@ impl IRGenerator {
         drop(resource_pools);
 
         // Process body with break/continue targets, if it exists
-        self.push_break_continue_target(end_block.clone(), cond_block.clone());
-        if let Some(body_node) = body_node_opt {
-            let _ = self.ir_router(body_node)?;
-        }
-        self.pop_target();
+        ctx.push_break_continue_target(end_block.clone(), cond_block.clone());
+        let body_exit = match body_node_opt {
+            Some(body_node) => self.generate_body_ir(body_node, ctx)?,
+            None => BlockExit::FallsThrough,
+        };
+        ctx.pop_target();
 
-        // Branch back to condition block
+        // Branch back to condition block, unless the body already left it
         let resource_pools = self.get_resource_pools();
         let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in while!");
-        resource_pools.create_br(self.get_builder(), cond_block)
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to create branch".to_string() })?;
+        if body_exit == BlockExit::FallsThrough {
+            resource_pools.create_br(self.get_builder(), cond_block)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create branch".to_string() })?;
+        }
         resource_pools.position_builder_at_end(self.get_builder(), end_block)
             .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
         Ok(None)
     }
     
+    /// Generates LLVM IR for a while loop whose condition is always true, as a body block that branches back to
+    /// itself. `break` leaves it for the end block, which has no other predecessors.
+    ///
+    /// # Parameters
+    ///
+    /// - `body_node`: The body of the loop, if it has one.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Option<Tag>, ErrorType>` containing None
+    /// if generation went smoothly or an Error if there was a problem generating the loop.
+    fn generate_endless_loop_ir(&mut self, body_node: Option<&ASTNode>, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
+        let function = ctx.get_function().unwrap();
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in while!");
+
+        let next_id = self.get_next_label_id();
+        let body_label = format!("while_bodyID{}", next_id);
+        let end_label = format!("while_endID{}", next_id);
+        self.annotate_block(&body_label, SourceConstruct::While);
+
+        let current_insert = ctx.get_current_insert_block().unwrap_or_else(|| {
+            resource_pools.get_current_block(self.get_builder()).expect("No current block!")
+        });
+        let body_block = resource_pools.create_basic_block_after(self.get_context(), function, current_insert, &body_label)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to create body block".to_string() })?;
+        let end_block = resource_pools.create_basic_block_after(self.get_context(), function, body_block, &end_label)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to create end block".to_string() })?;
+
+        resource_pools.create_br(self.get_builder(), body_block.clone())
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to create branch".to_string() })?;
+        resource_pools.position_builder_at_end(self.get_builder(), body_block.clone())
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
+        drop(resource_pools);
+
+        // `continue` starts the next iteration, as there is no condition to check
+        ctx.push_break_continue_target(end_block.clone(), body_block.clone());
+        let body_exit = match body_node {
+            Some(body_node) => self.generate_body_ir(body_node, ctx)?,
+            None => BlockExit::FallsThrough,
+        };
+        ctx.pop_target();
+
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in while!");
+        if body_exit == BlockExit::FallsThrough {
+            resource_pools.create_br(self.get_builder(), body_block)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create branch".to_string() })?;
+        }
+        resource_pools.position_builder_at_end(self.get_builder(), end_block)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
+        Ok(None)
+    }
+
     /// Generates LLVM IR for a for loop.
     /// 
     /// # Parameters
     ///
     /// - `node`: A reference to an `ASTNode` to generate IR for a for loop.
+    /// - `ctx`: The context of the function being generated.
     ///
     /// # Returns
     ///
@@ -364,10 +642,10 @@ impl IRGenerator {
     /// 
     /// ```
     /// //let a_node: ASTNode = /* Some ASTNode we want to generate a for loop from */
-    /// //let result = self.generate_for_ir(&a_node);
+    /// //let result = self.generate_for_ir(&a_node, &mut ctx);
     /// /* check if type_result was Ok or Err, if Ok, it will contain None. */
     /// ```
-    pub fn generate_for_ir(&mut self, node: &ASTNode) -> Result<Option<Tag>, ErrorType> {
+    pub fn generate_for_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
         let children = node.get_children();
         
         // Handle for loops with different numbers of children
@@ -386,16 +664,15 @@ impl IRGenerator {
         if let Some(init_node) = init_node_opt {
             let _ = match init_node.get_node_type() {
                 NodeType::LoopInitializer => {
-                    if let Some(child) = init_node.get_children().first() {
-                        self.ir_router(child)
-                    } else {
-                        Ok(None)
+                    for child in init_node.get_children() {
+                        self.ir_router(&child, ctx)?;
                     }
+                    Ok(None)
                 },
-                _ => self.ir_router(init_node)
+                _ => self.ir_router(init_node, ctx)
             }?;
         }
-        let function = self.get_function().unwrap();
+        let function = ctx.get_function().unwrap();
         let resource_pools = self.get_resource_pools();
         let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in for!");
         
@@ -404,9 +681,10 @@ impl IRGenerator {
         let body_label = format!("for_bodyID{}", next_id);
         let inc_label = format!("for_incID{}", next_id);
         let end_label = format!("for_endID{}", next_id);
+        self.annotate_block(&cond_label, SourceConstruct::For);
         
         // Create blocks in the correct order
-        let current_insert = self.get_current_insert_block().unwrap_or_else(|| {
+        let current_insert = ctx.get_current_insert_block().unwrap_or_else(|| {
             resource_pools.get_current_block(self.get_builder()).expect("No current block!")
         });
         
@@ -425,32 +703,19 @@ impl IRGenerator {
         resource_pools.position_builder_at_end(self.get_builder(), cond_block.clone())
             .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
         drop(resource_pools);
-        // Process condition if it exists, otherwise use true (1) as default
-        let llvm_cond = if let Some(cond_node) = cond_node_opt {
-            let cond_ptr = self.ir_router(cond_node)?;
-            let cond_ptr = cond_ptr.expect("Missing condition");
-            match cond_ptr {
-                Tag::Value(value) => value,
-                _ => return Err(ErrorType::DevError { message: "Expected value tag".to_string() })
-            }
-        } else {
-            // Default to true (1) if no condition provided
-            let resource_pools = self.get_resource_pools();
-            let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in for condition!");
-            let true_value = resource_pools.create_integer(self.get_context(), 1)
-                .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer constant for true".to_string() })?;
-            drop(resource_pools);
-            true_value
+        // Process condition if it exists, otherwise use true as default. An empty condition, as in `for (;;)`,
+        // is also true
+        let default_cond = ASTNode::new(NodeType::Literal("true".to_string()));
+        let cond_node = cond_node_opt.filter(|cond| !cond.get_children().is_empty()).unwrap_or(&default_cond);
+        let cond_ptr = self.ir_router(cond_node, ctx)?;
+        let llvm_cond = match cond_ptr {
+            Some(Tag::Value(value)) => value,
+            _ => return Err(ErrorType::DevError { message: "Expected value tag".to_string() })
         };
-        
+        let bool_cond = self.lower_condition_value(cond_node, llvm_cond)?;
+
         let resource_pools = self.get_resource_pools();
         let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in for!");
-        let zero = resource_pools.create_integer(self.get_context(), 0)
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer constant".to_string() })?;
-        let eq = resource_pools.build_icmp_eq(self.get_builder(), llvm_cond, zero, "cmptmp")
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to create comparison".to_string() })?;
-        let bool_cond = resource_pools.build_logical_not(self.get_builder(), self.get_context(), eq, "nottmp")
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to create logical not".to_string() })?;
         
         // Create conditional branch
         resource_pools.create_cond_br(self.get_builder(), bool_cond, body_block.clone(), end_block.clone())
@@ -459,17 +724,20 @@ impl IRGenerator {
             .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
         drop(resource_pools);
         // Process body with break/continue targets, if it exists
-        self.push_break_continue_target(end_block.clone(), inc_block.clone());
-        if let Some(body_node) = body_node_opt {
-            let _ = self.ir_router(body_node)?;
-        }
-        self.pop_target();
+        ctx.push_break_continue_target(end_block.clone(), inc_block.clone());
+        let body_exit = match body_node_opt {
+            Some(body_node) => self.generate_body_ir(body_node, ctx)?,
+            None => BlockExit::FallsThrough,
+        };
+        ctx.pop_target();
         
-        // Branch to increment block
+        // Branch to increment block, unless the body already left it
         let resource_pools = self.get_resource_pools();
         let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in for!");
-        resource_pools.create_br(self.get_builder(), inc_block.clone())
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to create branch".to_string() })?;
+        if body_exit == BlockExit::FallsThrough {
+            resource_pools.create_br(self.get_builder(), inc_block.clone())
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create branch".to_string() })?;
+        }
         resource_pools.position_builder_at_end(self.get_builder(), inc_block.clone())
             .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
         drop(resource_pools);
@@ -477,13 +745,12 @@ impl IRGenerator {
         if let Some(inc_node) = inc_node_opt {
             let _ = match inc_node.get_node_type() {
                 NodeType::LoopIncrement => {
-                    if let Some(child) = inc_node.get_children().first() {
-                        self.ir_router(child)
-                    } else {
-                        Ok(None)
+                    for child in inc_node.get_children() {
+                        self.ir_router(&child, ctx)?;
                     }
+                    Ok(None)
                 },
-                _ => self.ir_router(inc_node)
+                _ => self.ir_router(inc_node, ctx)
             }?;
         }
         
@@ -497,11 +764,17 @@ impl IRGenerator {
         Ok(None)
     }
 
-    /// Generates LLVM IR for an if statement.
+    /// Generates LLVM IR for an if statement, with any `else if` branches and final `else` it has. Each branch tests
+    /// its condition in the else block of the branch before it, and every branch continues to one merge block, so a
+    /// long chain is generated in a loop rather than by recursion. An if without an else gets no else block for its
+    /// last branch; its false edge branches straight to the merge block. From `-O1`, a branch whose condition is
+    /// constantly false is dropped, and one whose condition is constantly true becomes the else of the branches
+    /// before it, so an if whose conditions are all constants generates only the branch it takes, in place.
     /// 
     /// # Parameters
     ///
     /// - `node`: A reference to an `ASTNode` to generate IR for an if statement.
+    /// - `ctx`: The context of the function being generated.
     ///
     /// # Returns
     ///
@@ -516,91 +789,427 @@ impl IRGenerator {
     /// 
     /// ```
     /// //let a_node: ASTNode = /* Some ASTNode we want to generate an if statement from */
-    /// //let result = self.generate_for_ir(&a_node);
+    /// //let result = self.generate_for_ir(&a_node, &mut ctx);
     /// /* check if type_result was Ok or Err, if Ok, it will contain None. */
     /// ```
-    pub fn generate_if_ir(&mut self, node: &ASTNode) -> Result<Option<Tag>, ErrorType> {
+    pub fn generate_if_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
         let children = node.get_children();
-        if children.len() != 2 && children.len() != 3 {
+        if children.len() < 2 {
             return Err(ErrorType::DevError { message: "Invalid if node".to_string() });
         }
-        let cond_node = &children[0];
-        let then_node = &children[1];
-        let function = self.get_function().unwrap();
+        let mut branches = vec![(children[0].clone(), children[1].clone())];
+        let mut otherwise = None;
+        for child in &children[2..] {
+            match child.get_node_type() {
+                NodeType::ElseIf => match child.get_children().as_slice() {
+                    [cond_node, then_node] => branches.push((cond_node.clone(), then_node.clone())),
+                    _ => return Err(ErrorType::DevError { message: "Invalid else if node".to_string() }),
+                },
+                _ => otherwise = Some(child.clone()),
+            }
+        }
+
+        // Branches that are never taken are dropped, and a branch that is always taken ends the chain
+        let mut kept = Vec::new();
+        for (cond_node, then_node) in branches {
+            match self.constant_branch(&cond_node) {
+                Some(false) => {}
+                Some(true) => {
+                    otherwise = Some(then_node);
+                    break;
+                }
+                None => kept.push((cond_node, then_node)),
+            }
+        }
+        if kept.is_empty() {
+            return self.generate_taken_branch_ir(otherwise.as_ref(), ctx);
+        }
+
+        let function = ctx.get_function().unwrap();
         let resource_pools = self.get_resource_pools();
         let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in if!");
-        let next_id = self.get_next_label_id();
-        let then_label = format!("thenID{}", next_id);
-        let else_label = format!("elseID{}", next_id);
-        let merge_label = format!("mergeID{}", next_id);
-        let current_insert = self.get_current_insert_block().unwrap_or_else(|| {
+        let mut after = ctx.get_current_insert_block().unwrap_or_else(|| {
             resource_pools.get_current_block(self.get_builder()).expect("No current block!")
         });
-        let then_block = resource_pools.create_basic_block_after(self.get_context(), function, current_insert, &then_label)
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to create then block".to_string() })?;
-        let else_block = resource_pools.create_basic_block_after(self.get_context(), function, then_block, &else_label)
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to create else block".to_string() })?;
-        let merge_block = resource_pools.create_basic_block_after(self.get_context(), function, else_block, &merge_label)
+        // The else block of each branch tests the condition of the next one, or holds the final else
+        let mut blocks = Vec::new();
+        let mut merge_label = None;
+        for index in 0..kept.len() {
+            let next_id = self.get_next_label_id();
+            let then_label = format!("thenID{}", next_id);
+            self.annotate_block(&then_label, SourceConstruct::If);
+            let then_block = resource_pools.create_basic_block_after(self.get_context(), function, after, &then_label)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create then block".to_string() })?;
+            after = then_block;
+            let else_block = if index + 1 < kept.len() || otherwise.is_some() {
+                let else_label = format!("elseID{}", next_id);
+                after = resource_pools.create_basic_block_after(self.get_context(), function, then_block, &else_label)
+                    .ok_or_else(|| ErrorType::DevError { message: "Failed to create else block".to_string() })?;
+                Some(after)
+            } else {
+                None
+            };
+            blocks.push((then_block, else_block));
+            merge_label.get_or_insert_with(|| format!("mergeID{}", next_id));
+        }
+        let merge_label = merge_label.expect("An if has at least one branch");
+        let merge_block = resource_pools.create_basic_block_after(self.get_context(), function, after, &merge_label)
             .ok_or_else(|| ErrorType::DevError { message: "Failed to create merge block".to_string() })?;
-        self.set_current_insert_block(merge_block);
+        ctx.set_current_insert_block(merge_block);
         drop(resource_pools);
-        let cond_ptr = self.ir_router(cond_node)?;
-        let cond_ptr = cond_ptr.expect("Missing condition");
-        let llvm_cond = match cond_ptr {
-            Tag::Value(value) => value,
-            _ => return Err(ErrorType::DevError { message: "Expected value tag".to_string() })
-        };
+
+        for ((cond_node, then_node), (then_block, else_block)) in kept.iter().zip(&blocks) {
+            let cond_ptr = self.ir_router(cond_node, ctx)?;
+            let cond_ptr = cond_ptr.expect("Missing condition");
+            let llvm_cond = match cond_ptr {
+                Tag::Value(value) => value,
+                _ => return Err(ErrorType::DevError { message: "Expected value tag".to_string() })
+            };
+            let bool_cond = self.lower_condition_value(cond_node, llvm_cond)?;
+            let resource_pools = self.get_resource_pools();
+            let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in if!");
+            resource_pools.create_cond_br(self.get_builder(), bool_cond, *then_block, else_block.unwrap_or(merge_block))
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create conditional branch".to_string() })?;
+            resource_pools.position_builder_at_end(self.get_builder(), *then_block)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
+            drop(resource_pools);
+            // Each branch continues to the merge block, unless it ends in a terminator of its own
+            let then_exit = self.generate_body_ir(then_node, ctx)?;
+            let resource_pools = self.get_resource_pools();
+            let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in if!");
+            if then_exit == BlockExit::FallsThrough {
+                resource_pools.create_br(self.get_builder(), merge_block)
+                    .ok_or_else(|| ErrorType::DevError { message: "Failed to create branch".to_string() })?;
+            }
+            if let Some(else_block) = else_block {
+                resource_pools.position_builder_at_end(self.get_builder(), *else_block)
+                    .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
+            }
+        }
+        if let Some(else_node) = otherwise {
+            let else_exit = self.generate_body_ir(&else_node, ctx)?;
+            let resource_pools = self.get_resource_pools();
+            let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in if!");
+            if else_exit == BlockExit::FallsThrough {
+                resource_pools.create_br(self.get_builder(), merge_block)
+                    .ok_or_else(|| ErrorType::DevError { message: "Failed to create branch".to_string() })?;
+            }
+        }
         let resource_pools = self.get_resource_pools();
         let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in if!");
-        let bool_cond = if let NodeType::Literal(value) = cond_node.get_node_type() {
-            if value == "true" || value == "false" {
-                llvm_cond
-            } else {
-                let zero = resource_pools.create_integer(self.get_context(), 0)
-                    .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer constant".to_string() })?;
-                let eq = resource_pools.build_icmp_eq(self.get_builder(), llvm_cond, zero, "cmptmp")
-                    .ok_or_else(|| ErrorType::DevError { message: "Failed to create comparison".to_string() })?;
-                resource_pools.build_logical_not(self.get_builder(), self.get_context(), eq, "nottmp")
-                    .ok_or_else(|| ErrorType::DevError { message: "Failed to create logical not".to_string() })?
-            }
+        resource_pools.position_builder_at_end(self.get_builder(), merge_block)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
+        Ok(None)
+    }
+
+    /// Generates the branch an if with a constant condition always takes, in the current block. When the branch
+    /// ends in a terminator, such as a `return`, the code after the if is generated into a new merge block, which
+    /// has no predecessors.
+    ///
+    /// # Parameters
+    ///
+    /// - `taken`: The branch taken, or None for a false condition without an else.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Option<Tag>, ErrorType>` containing None
+    /// if generation went smoothly or an Error if there was a problem generating the branch.
+    fn generate_taken_branch_ir(&mut self, taken: Option<&ASTNode>, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
+        let exit = match taken {
+            Some(branch) => self.generate_body_ir(branch, ctx)?,
+            None => BlockExit::FallsThrough,
+        };
+        if exit == BlockExit::Terminated {
+            let function = ctx.get_function().unwrap();
+            let resource_pools = self.get_resource_pools();
+            let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in if!");
+            let merge_label = format!("mergeID{}", self.get_next_label_id());
+            let current_block = resource_pools.get_current_block(self.get_builder()).expect("No current block!");
+            let merge_block = resource_pools.create_basic_block_after(self.get_context(), function, current_block, &merge_label)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create merge block".to_string() })?;
+            resource_pools.position_builder_at_end(self.get_builder(), merge_block)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
+        }
+        Ok(None)
+    }
+
+    /// Determines whether the condition of an `if` or `while` is a constant whose branches should be dropped,
+    /// which they are from `-O1`, and reports a warning when it is, as the branch it never takes is dead code.
+    ///
+    /// # Parameters
+    ///
+    /// - `cond_node`: The condition.
+    ///
+    /// # Returns
+    ///
+    /// Returns the value of the condition, or None if it is not a constant or branches are not being dropped.
+    fn constant_branch(&mut self, cond_node: &ASTNode) -> Option<bool> {
+        if self.get_codegen_config().opt_level < OptLevel::O1 {
+            return None;
+        }
+        let value = constant_integer_value(cond_node)? != 0;
+        let expression = match cond_node.get_node_type() {
+            NodeType::Condition => describe(cond_node.get_children().first()?),
+            _ => describe(cond_node),
+        };
+        self.report_warning(WarningType::ConstantCondition { expression, value });
+        Some(value)
+    }
+
+    /// Generates LLVM IR for a switch statement. The scrutinee is evaluated once and compared against each case
+    /// value in order, branching to the first matching case, or to the default (or the end of the switch) if none
+    /// match. A switch with at least `switch_table_threshold` distinct constant cases covering a dense range is
+    /// lowered to an LLVM `switch` instead, which LLVM can turn into a jump table. Each case falls through to the next
+    /// one unless it ends in a `break`, `continue` or `return`.
+    ///
+    /// Case values may be integer or character literals or enum constants. A `char` scrutinee is promoted to an
+    /// integer as in C, by sign extension, and each constant case value is truncated to a `char` so that it is
+    /// compared with the same extended value.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to a `SwitchStatement` `ASTNode`, whose children are the scrutinee expression and a
+    /// block of `Case` and `Default` nodes.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Option<Tag>, ErrorType>` containing None
+    /// if generation went smoothly or an Error if there was a problem generating the switch statement.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if generation failed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// //let a_node: ASTNode = /* Some ASTNode we want to generate a switch statement from */
+    /// //let result = self.generate_switch_ir(&a_node, &mut ctx);
+    /// /* check if type_result was Ok or Err, if Ok, it will contain None. */
+    /// ```
+    pub fn generate_switch_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
+        let children = node.get_children();
+        if children.len() != 2 {
+            return Err(ErrorType::DevError { message: "Invalid switch node".to_string() });
+        }
+        let arms = children[1].get_children();
+
+        // The scrutinee is evaluated once, before any comparison
+        let scrutinee = self.generate_operand_ir(&children[0], ctx)?;
+        let char_scrutinee = matches!(
+            self.value_data_type(&children[0]),
+            Some(DataType::Char | DataType::SignedChar | DataType::UnsignedChar)
+        );
+        let scrutinee = if char_scrutinee {
+            let resource_pools = self.get_resource_pools();
+            let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in switch!");
+            let long_type = resource_pools.int_type(self.get_context(), 64)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create i64 type".to_string() })?;
+            resource_pools.build_sext(self.get_builder(), scrutinee, long_type, "switchext")
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to extend switch scrutinee".to_string() })?
         } else {
-            let zero = resource_pools.create_integer(self.get_context(), 0)
-                .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer constant".to_string() })?;
-            let eq = resource_pools.build_icmp_eq(self.get_builder(), llvm_cond, zero, "cmptmp")
-                .ok_or_else(|| ErrorType::DevError { message: "Failed to create comparison".to_string() })?;
-            resource_pools.build_logical_not(self.get_builder(), self.get_context(), eq, "nottmp")
-                .ok_or_else(|| ErrorType::DevError { message: "Failed to create logical not".to_string() })?
+            scrutinee
         };
-        resource_pools.create_cond_br(self.get_builder(), bool_cond, then_block, else_block)
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to create conditional branch".to_string() })?;
-        resource_pools.position_builder_at_end(self.get_builder(), then_block)
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
-        drop(resource_pools);
-        let then_result = self.ir_router(then_node)?;
-        let has_return = matches!(then_result, Some(Tag::Value(_)));
+
+        let function = ctx.get_function().unwrap();
         let resource_pools = self.get_resource_pools();
-        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in if!");
-        if !has_return {
-            resource_pools.create_br(self.get_builder(), merge_block)
-                .ok_or_else(|| ErrorType::DevError { message: "Failed to create branch".to_string() })?;
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in switch!");
+
+        // Create one block per case or default in source order, so fallthrough reaches the next arm
+        let current_insert = ctx.get_current_insert_block().unwrap_or_else(|| {
+            resource_pools.get_current_block(self.get_builder()).expect("No current block!")
+        });
+        let mut previous_block = current_insert;
+        let mut arm_blocks = Vec::new();
+        for arm in &arms {
+            let label = match arm.get_node_type() {
+                NodeType::Case => format!("{}{}", DEFAULT_SWITCH_CASE_LABEL, self.get_next_label_id()),
+                NodeType::Default => format!("{}{}", DEFAULT_SWITCH_DEFAULT_LABEL, self.get_next_label_id()),
+                _ => return Err(ErrorType::DevError { message: "Expected case or default in switch".to_string() })
+            };
+            if arm_blocks.is_empty() {
+                self.annotate_block(&label, SourceConstruct::Switch);
+            }
+            let arm_block = resource_pools.create_basic_block_after(self.get_context(), function, previous_block, &label)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create case block".to_string() })?;
+            arm_blocks.push(arm_block);
+            previous_block = arm_block;
         }
-        resource_pools.position_builder_at_end(self.get_builder(), else_block)
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
+        let end_label = format!("{}{}", DEFAULT_SWITCH_END_LABEL, self.get_next_label_id());
+        if arm_blocks.is_empty() {
+            self.annotate_block(&end_label, SourceConstruct::Switch);
+        }
+        let end_block = resource_pools.create_basic_block_after(self.get_context(), function, previous_block, &end_label)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to create end block".to_string() })?;
         drop(resource_pools);
-        let else_result = if let Some(else_node) = children.get(2) {
-            self.ir_router(else_node)?
-        } else {
-            None
-        };
-        let has_return = matches!(else_result, Some(Tag::Value(_)));
+
+        let default_block = arms.iter().position(|arm| arm.get_node_type() == NodeType::Default)
+            .map_or(end_block, |index| arm_blocks[index]);
+
+        // Enough dense constant cases become an LLVM `switch`, which LLVM can lower to a jump table
+        if let Some(case_values) = self.switch_table_values(&arms, char_scrutinee) {
+            let case_blocks = arms.iter().zip(&arm_blocks)
+                .filter(|(arm, _)| arm.get_node_type() == NodeType::Case)
+                .map(|(_, block)| *block);
+            let resource_pools = self.get_resource_pools();
+            let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in switch!");
+            let switch = resource_pools.build_switch(self.get_builder(), scrutinee, default_block, case_values.len() as u32)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create switch".to_string() })?;
+            for (value, case_block) in case_values.into_iter().zip(case_blocks) {
+                let case_value = resource_pools.create_integer(self.get_context(), value)
+                    .ok_or_else(|| ErrorType::DevError { message: "Failed to create case value".to_string() })?;
+                resource_pools.add_case(switch, case_value, case_block)
+                    .ok_or_else(|| ErrorType::DevError { message: "Failed to add switch case".to_string() })?;
+            }
+            drop(resource_pools);
+            return self.generate_switch_arms_ir(&arms, &arm_blocks, end_block, ctx);
+        }
+
+        // Otherwise, compare the scrutinee against each case value in turn
+        for (arm, arm_block) in arms.iter().zip(&arm_blocks) {
+            if arm.get_node_type() != NodeType::Case {
+                continue;
+            }
+            let value_node = arm.get_children().first().cloned()
+                .ok_or_else(|| ErrorType::DevError { message: "Case node has no value".to_string() })?;
+            let case_value = match self.case_value(&value_node, char_scrutinee) {
+                Some(value) => {
+                    let resource_pools = self.get_resource_pools();
+                    let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in switch!");
+                    resource_pools.create_integer(self.get_context(), value)
+                        .ok_or_else(|| ErrorType::DevError { message: "Failed to create case value".to_string() })?
+                },
+                None => self.generate_operand_ir(&value_node, ctx)?,
+            };
+
+            let resource_pools = self.get_resource_pools();
+            let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in switch!");
+            let matches = resource_pools.build_icmp_eq(self.get_builder(), scrutinee, case_value, "casecmp")
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create case comparison".to_string() })?;
+            let current_block = resource_pools.get_current_block(self.get_builder())
+                .ok_or_else(|| ErrorType::DevError { message: "No current block".to_string() })?;
+            let test_label = format!("{}{}", DEFAULT_SWITCH_TEST_LABEL, self.get_next_label_id());
+            let next_test = resource_pools.create_basic_block_after(self.get_context(), function, current_block, &test_label)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create case test block".to_string() })?;
+            resource_pools.create_cond_br(self.get_builder(), matches, *arm_block, next_test)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create conditional branch".to_string() })?;
+            resource_pools.position_builder_at_end(self.get_builder(), next_test)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
+        }
+
+        // No case matched
         let resource_pools = self.get_resource_pools();
-        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in if!");
-        if !has_return {
-            resource_pools.create_br(self.get_builder(), merge_block)
-                .ok_or_else(|| ErrorType::DevError { message: "Failed to create branch".to_string() })?;
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in switch!");
+        resource_pools.create_br(self.get_builder(), default_block)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to create branch".to_string() })?;
+        drop(resource_pools);
+        self.generate_switch_arms_ir(&arms, &arm_blocks, end_block, ctx)
+    }
+
+    /// Generates the bodies of the arms of a switch statement, each in its own block, and leaves the builder at the
+    /// end of the switch. An arm falls through to the next one unless it ends in a `break`, `continue` or `return`.
+    ///
+    /// # Parameters
+    ///
+    /// - `arms`: The `Case` and `Default` nodes of the switch, in source order.
+    /// - `arm_blocks`: The block of each arm.
+    /// - `end_block`: The block after the switch.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Option<Tag>, ErrorType>` containing None if generation went smoothly.
+    fn generate_switch_arms_ir(&mut self, arms: &[ASTNode], arm_blocks: &[BasicBlockTag], end_block: BasicBlockTag, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
+        // `break` leaves the switch, while `continue` still belongs to the enclosing loop
+        match ctx.get_break_continue_target() {
+            Some(targets) if targets.len() > 1 => ctx.push_break_continue_target(end_block, targets[1]),
+            _ => ctx.push_break_target(end_block),
         }
-        resource_pools.position_builder_at_end(self.get_builder(), merge_block)
+        for (index, arm) in arms.iter().enumerate() {
+            let resource_pools = self.get_resource_pools();
+            let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in switch!");
+            resource_pools.position_builder_at_end(self.get_builder(), arm_blocks[index])
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
+            drop(resource_pools);
+
+            let body = arm.get_children().last().cloned()
+                .ok_or_else(|| ErrorType::DevError { message: "Case node has no body".to_string() })?;
+            // Each case is its own scope, so a declaration in one case does not leak into the cases after it
+            self.make_new_store_table();
+            let body_exit = self.generate_body_ir(&body, ctx);
+            self.delete_store_table();
+
+            if body_exit? == BlockExit::FallsThrough {
+                let next_block = arm_blocks.get(index + 1).copied().unwrap_or(end_block);
+                let resource_pools = self.get_resource_pools();
+                let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in switch!");
+                resource_pools.create_br(self.get_builder(), next_block)
+                    .ok_or_else(|| ErrorType::DevError { message: "Failed to create branch".to_string() })?;
+            }
+        }
+        ctx.pop_target();
+
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in switch!");
+        resource_pools.position_builder_at_end(self.get_builder(), end_block)
             .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
         Ok(None)
     }
-} 
\ No newline at end of file
+
+    /// Determines the value of a constant case label: an integer or character literal, or an enum constant.
+    ///
+    /// # Parameters
+    ///
+    /// - `label`: The case label.
+    /// - `truncate_to_char`: Whether the switch is on a `char`, so that the value is truncated to a `char` and sign
+    /// extended as the scrutinee is.
+    ///
+    /// # Returns
+    ///
+    /// Returns the value, or None if the label is not a constant.
+    fn case_value(&self, label: &ASTNode, truncate_to_char: bool) -> Option<i64> {
+        let value = match label.get_node_type() {
+            NodeType::Literal(value) => value.parse::<i64>().ok().or_else(|| {
+                let body = value.strip_prefix('\'')?.strip_suffix('\'')?;
+                let mut chars = body.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Some(c as i64),
+                    _ => None,
+                }
+            }),
+            NodeType::Identifier(name) => self.search_enum_constant(&name).map(|(value, _)| value),
+            _ => None,
+        }?;
+        Some(if truncate_to_char { value as i8 as i64 } else { value })
+    }
+
+    /// Determines whether a switch should be lowered to an LLVM `switch`: every case value must be a distinct integer
+    /// or character constant or enum constant, there must be at least as many cases as the configured threshold, and
+    /// the cases must cover at least `SWITCH_TABLE_MIN_DENSITY` of the values between the smallest and the largest.
+    ///
+    /// # Parameters
+    ///
+    /// - `arms`: The `Case` and `Default` nodes of the switch.
+    /// - `char_scrutinee`: Whether the switch is on a `char`, whose case values are truncated to a `char`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the value of each case in order, or None if the switch should be lowered to a chain of compares.
+    fn switch_table_values(&self, arms: &[ASTNode], char_scrutinee: bool) -> Option<Vec<i64>> {
+        let values = arms.iter()
+            .filter(|arm| arm.get_node_type() == NodeType::Case)
+            .map(|arm| self.case_value(arm.get_children().first()?, char_scrutinee))
+            .collect::<Option<Vec<i64>>>()?;
+        if values.is_empty() || values.len() < self.get_codegen_config().switch_table_threshold {
+            return None;
+        }
+
+        let distinct: HashSet<i64> = values.iter().copied().collect();
+        let min = *values.iter().min()? as i128;
+        let max = *values.iter().max()? as i128;
+        let density = values.len() as f64 / (max - min + 1) as f64;
+        (distinct.len() == values.len() && density >= SWITCH_TABLE_MIN_DENSITY).then_some(values)
+    }
+}
@@ -0,0 +1,141 @@
+//! The state of IR generation that belongs to one function: the function itself, where its locals are allocated,
+//! which block new blocks go after, and where `break` and `continue` branch to. A `CodegenCtx` is created for each
+//! function and passed down the recursion that generates its body, so nothing is carried over from one function to
+//! the next, and a construct only sees the targets of the constructs it is nested in.
+
+use common::ast::data_type::DataType;
+use safe_llvm::ir::core::{BasicBlockTag, ValueTag};
+
+#[derive(Clone)]
+/// Stores branch targets to be used in IR generation
+pub enum BranchTarget {
+    /// Holds a tag to break to, and a tag to continue to
+    BreakAndContinueTarget(Option<BasicBlockTag>, Option<BasicBlockTag>)
+}
+
+/// Whether the code generated for a statement or block can continue to the code after it, which decides whether a
+/// branch to the code after it, such as to the merge block of an `if`, is generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockExit {
+    /// The generated code falls through to whatever follows it.
+    FallsThrough,
+    /// The generated code ends in a terminator, the `ret` of a `return` or the `br` of a `break` or `continue`, so
+    /// nothing may be generated after it in the same basic block.
+    Terminated,
+}
+
+/// The state of generating IR for one function, or for the top level of a module outside any function.
+#[derive(Clone, Default)]
+pub struct CodegenCtx {
+    /// The function being generated, or None at the top level.
+    function: Option<ValueTag>,
+    /// Declared return type of the function, which returned values are converted to.
+    function_return_type: Option<DataType>,
+    /// Block at the start of the function holding the allocas of its locals, or None at the top level.
+    alloca_block: Option<BasicBlockTag>,
+    /// The block new blocks are inserted after, or None to insert after the builder's current block.
+    current_insert_block: Option<BasicBlockTag>,
+    /// Targets of `break` and `continue` in the constructs being generated, innermost last.
+    target_stack: Vec<BranchTarget>,
+}
+
+impl CodegenCtx {
+    /// Creates the context of the top level of a module, outside any function.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the context of a function whose locals are allocated in `alloca_block`.
+    ///
+    /// # Parameters
+    ///
+    /// - `function`: The ValueTag of the function.
+    /// - `return_type`: The `DataType` the function returns.
+    /// - `alloca_block`: The block at the start of the function that holds the allocas of its locals.
+    pub fn for_function(function: ValueTag, return_type: DataType, alloca_block: BasicBlockTag) -> Self {
+        Self {
+            function: Some(function),
+            function_return_type: Some(return_type),
+            alloca_block: Some(alloca_block),
+            ..Self::default()
+        }
+    }
+
+    /// Retrieves the function being built, or None at the top level.
+    pub fn get_function(&self) -> Option<ValueTag> {
+        self.function.clone()
+    }
+
+    /// Retrieves the declared return type of the function being built, or None at the top level.
+    pub fn get_function_return_type(&self) -> Option<DataType> {
+        self.function_return_type
+    }
+
+    /// Retrieves the allocas block of the function being built, or None at the top level.
+    pub fn get_alloca_block(&self) -> Option<BasicBlockTag> {
+        self.alloca_block.clone()
+    }
+
+    /// Retrieves the block to insert new blocks after, which is None until a construct sets one.
+    pub fn get_current_insert_block(&self) -> Option<BasicBlockTag> {
+        self.current_insert_block.clone()
+    }
+
+    /// Sets the block to insert new blocks after.
+    ///
+    /// # Parameters
+    ///
+    /// - `to_set`: The BasicBlockTag of the new basic block to be set as the insert block
+    pub fn set_current_insert_block(&mut self, to_set: BasicBlockTag) {
+        self.current_insert_block = Some(to_set);
+    }
+
+    /// Retrieves the current break and potentially the current continue target
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of basic block tags where index 0 is the break target and index 1
+    /// is the continue target (if one exists, otherwise the vector will only have index 0).
+    /// This is None outside any loop or switch.
+    pub fn get_break_continue_target(&self) -> Option<Vec<BasicBlockTag>> {
+        match self.target_stack.last()? {
+            BranchTarget::BreakAndContinueTarget(break_target, continue_target) => {
+                let mut targets = vec![break_target.clone()?];
+                targets.extend(continue_target.clone());
+                Some(targets)
+            }
+        }
+    }
+
+    /// Retrieves the innermost target of `continue`, skipping any switches between it and the `continue`, which
+    /// can only be broken out of.
+    pub fn get_continue_target(&self) -> Option<BasicBlockTag> {
+        self.target_stack.iter().rev().find_map(|target| match target {
+            BranchTarget::BreakAndContinueTarget(_, continue_target) => continue_target.clone(),
+        })
+    }
+
+    /// Pushes a tag for break; and a tag for continue; on the target stack.
+    ///
+    /// # Parameters
+    ///
+    /// - `break_block_tag`: The BasicBlockTag of the tag to branch to when break; is encountered.
+    /// - `continue_block_tag`: The BasicBlockTag of the tag to branch to when continue; is encountered.
+    pub fn push_break_continue_target(&mut self, break_block_tag: BasicBlockTag, continue_block_tag: BasicBlockTag) {
+        self.target_stack.push(BranchTarget::BreakAndContinueTarget(Some(break_block_tag), Some(continue_block_tag)));
+    }
+
+    /// Pushes only a tag for break; on the target stack.
+    ///
+    /// # Parameters
+    ///
+    /// - `break_block_tag`: The BasicBlockTag of the tag to branch to when break; is encountered.
+    pub fn push_break_target(&mut self, break_block_tag: BasicBlockTag) {
+        self.target_stack.push(BranchTarget::BreakAndContinueTarget(Some(break_block_tag), None));
+    }
+
+    /// Pops current break continue target off the stack
+    pub fn pop_target(&mut self) {
+        self.target_stack.pop();
+    }
+}
@@ -3,16 +3,24 @@
 //! and semantic analysis, and generates LLVM IR from it, completing the final step for
 //! turning code into a (one step above a) directly runnable format.
 
+use std::collections::HashMap;
+use std::fmt;
 use std::sync::{Arc, Mutex};
 use integration::module::{ModElement, Module};
 use sts::core::SymbolTableStack;
 use common::ast::core::{ASTNode, AST};
 use common::ast::node_type::NodeType;
 use common::ast::data_type::DataType;
+use common::config::CodegenConfig;
+use common::diagnostic::DiagnosticSink;
+use common::warning::WarningType;
 use common::error::ErrorType;
-use safe_llvm::ir::core::{BasicBlockTag, BuilderTag, ContextTag, ModuleTag, IRManager, Tag, ValueTag};
-use safe_llvm::common::pointer::{LLVMRef, LLVMRefType};
-use crate::store::Store;
+use common::location::Span;
+use safe_llvm::ir::core::{BasicBlockTag, BuilderTag, ContextTag, ModuleTag, IRManager, Tag, TypeTag, ValueTag};
+use safe_llvm::common::{io, pointer::{LLVMRef, LLVMRefType}};
+use crate::context::CodegenCtx;
+use crate::layout::StructLayout;
+use crate::store::{Store, StoreEntry};
 
 /// A struct for generating LLVM Intermediate Representation (IR) from a module of abstract syntax trees (AST) and symbol table stacks (STS).
 pub struct IRGenerator {
@@ -24,29 +32,60 @@ pub struct IRGenerator {
     module: Option<ModuleTag>,
     /// Current builder tag for IR generation, with the builder itself stored in `resource_pools`. 
     builder: Option<BuilderTag>,
-    /// Current function tag for IR generation, with the function stored in `resource_pools`. 
-    function: Option<ValueTag>,
+    /// Builder kept at the end of the allocas block of the function being generated, which every local variable is
+    /// allocated with.
+    alloca_builder: Option<BuilderTag>,
     /// Current symbol table stack (sts) from the module.
     sts: Option<SymbolTableStack>,
     /// Index pointing to the current symbol table within the sts.
     sts_pointer: usize,
-    /// Stack of targets to keep track of the current target blocks for branch statements.
-    current_target_stack: Option<Vec<BranchTarget>>, 
     /// Integer to make sure labels are unique. 
     current_label_id: usize,
     /// Allocation store for managing variable allocations across scopes. 
     store: Store,
-    /// Stores a pointer to the current block to insert after.
-    current_insert_block: Option<BasicBlockTag>
+    /// Functions declared in the module, by name, with their function types for direct calls.
+    function_table: HashMap<String, (ValueTag, TypeTag)>,
+    /// Named struct types declared in the module, by struct name.
+    struct_types: HashMap<String, TypeTag>,
+    /// Memory layouts of the structs declared in the module, by struct name.
+    struct_layouts: HashMap<String, StructLayout>,
+    /// Declared parameter types of the functions in the module, by function name, which arguments are converted to.
+    function_parameter_types: HashMap<String, Vec<DataType>>,
+    /// Values of the enum constants declared in the module, by constant name, with the underlying type of their enum.
+    enum_constants: HashMap<String, (i64, DataType)>,
+    /// Options that change the IR generated, such as what signed arithmetic does on overflow.
+    codegen_config: CodegenConfig,
+    /// Labels of the first block of each annotated source construct, in the order they were generated.
+    annotations: Vec<(String, SourceConstruct)>,
+    /// Warnings found while generating IR, such as conditions that are always true, in the order they were found.
+    warnings: Vec<WarningType>,
 }
 
-#[derive(Clone)]
-/// Stores branch targets to be used in IR generation
-pub enum BranchTarget {
-    /// Holds a tag to break to, and a tag to continue to
-    BreakAndContinueTarget(Option<BasicBlockTag>, Option<BasicBlockTag>)
+/// A source construct whose IR can be annotated with a comment naming it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SourceConstruct {
+    /// A function definition, with the function's name.
+    Function(String),
+    For,
+    While,
+    DoWhile,
+    If,
+    Switch,
 }
 
+/// Names the construct as it appears in IR comments, such as `for-loop` or ``function `main` ``.
+impl fmt::Display for SourceConstruct {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SourceConstruct::Function(name) => write!(f, "function `{}`", name),
+            SourceConstruct::For => write!(f, "for-loop"),
+            SourceConstruct::While => write!(f, "while-loop"),
+            SourceConstruct::DoWhile => write!(f, "do-while loop"),
+            SourceConstruct::If => write!(f, "if statement"),
+            SourceConstruct::Switch => write!(f, "switch statement"),
+        }
+    }
+}
 
 impl IRGenerator {
     /// Creates a new `IRGenerator` instance.
@@ -55,30 +94,21 @@ impl IRGenerator {
     /// which are necessary components of writing IR. The most important to understand is the builder,
     /// which performs the function of writing IR into the module you are creating. 
     /// 
-    /// The current function is not initialized yet, and is set to None. This will change when you
-    /// generate a function. Once you do so, you must set this so that you can know where to write IR into
-    /// for each function. Remember, main() is a function!
+    /// The generator holds no function of its own. The function being generated, where its locals are
+    /// allocated, the block to insert new blocks after, and the targets of break; and continue; are kept in
+    /// a `CodegenCtx` that each function declaration creates and passes down as it generates its body.
+    /// Remember, main() is a function!
     /// 
     /// sts is not set yet, and is None. Additionally, the sts stack pointer is set to 0. Set the STS 
     /// when you have one and make sure to increment and decrement the stack pointer as you generate IR
     /// to stay in step with the STS.
     /// 
-    /// current_target_stack is initialized as an empty stack. In C, in a while loop for example, break;
-    /// will end the loop entirely upon reaching that statement, and continue; will jump back to checking
-    /// the loop conditions. The targets for break and continue are stored here, so you know where to point to
-    /// when you hit one of these.
-    /// 
     /// current_label_id starts at 0 and is the suffix for labels in IR. Jumping to labels is how we do complex
     /// logic like loops, and all labels have to be unique (otherwise how would we know which label was which?)
     /// so this along with its supporting functions allow unique IDs to be created for every label.
     /// 
     /// store is initialized to a new store. The store is a space for managing variables, allowing you
     /// to assign and reassign variables in IR while keeping track of their tags.
-    /// 
-    /// current_insert_block is set to None, this is a space to hold the "basic block" (labeled area of code)
-    /// that new blocks should be inserted after. ResourcePools contains a function that can create basic blocks
-    /// after a given basic block, and this helps support that. This will be necessary for inserting code blocks
-    /// in the body of a while loop for example.
     ///
     /// # Returns
     ///
@@ -92,6 +122,7 @@ impl IRGenerator {
         let context = resource_pools_guard.create_context().expect("Failed to create context");
         let module = resource_pools_guard.create_module("dummy_module", context.clone()).expect("Failed to create module");
         let builder = resource_pools_guard.create_builder(context.clone()).expect("Failed to create builder");
+        let alloca_builder = resource_pools_guard.create_builder(context.clone()).expect("Failed to create builder");
 
         drop(resource_pools_guard);
 
@@ -103,16 +134,70 @@ impl IRGenerator {
             context: Some(context),
             module: Some(module),
             builder: Some(builder),
-            function: None,
+            alloca_builder: Some(alloca_builder),
             sts: None,
             sts_pointer: 0,
-            current_target_stack: Some(Vec::new()),
             current_label_id: 0,
             store,
-            current_insert_block: None
+            function_table: HashMap::new(),
+            struct_types: HashMap::new(),
+            struct_layouts: HashMap::new(),
+            function_parameter_types: HashMap::new(),
+            enum_constants: HashMap::new(),
+            codegen_config: CodegenConfig::default(),
+            annotations: Vec::new(),
+            warnings: Vec::new()
+        }
+    }
+
+    /// Creates a new `IRGenerator` instance that generates IR according to the given options, such as
+    /// trapping on signed overflow instead of wrapping.
+    ///
+    /// # Parameters
+    ///
+    /// - `codegen_config`: The `CodegenConfig` to generate IR with.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `IRGenerator` instance, set up as by `new`.
+    pub fn with_config(codegen_config: CodegenConfig) -> Self {
+        let mut generator = Self::new();
+        generator.codegen_config = codegen_config;
+        generator
+    }
+
+    /// Returns the options IR is generated with.
+    pub fn get_codegen_config(&self) -> CodegenConfig {
+        self.codegen_config
+    }
+
+    /// Records that a block is the first one generated for a source construct, if IR annotation is enabled.
+    ///
+    /// # Parameters
+    ///
+    /// - `label`: The label of the block.
+    /// - `construct`: The construct the block was generated for.
+    pub fn annotate_block(&mut self, label: &str, construct: SourceConstruct) {
+        if self.codegen_config.annotate_ir {
+            self.annotations.push((label.to_string(), construct));
         }
     }
 
+    /// Records a warning found while generating IR, which `generate_ir_reporting` reports.
+    ///
+    /// # Parameters
+    ///
+    /// - `warning`: The warning.
+    pub fn report_warning(&mut self, warning: WarningType) {
+        self.warnings.push(warning);
+    }
+
+    /// Returns the label of the first block of each annotated source construct, in the order the constructs were
+    /// generated, which is their order in the source.
+    pub fn get_annotations(&self) -> &[(String, SourceConstruct)] {
+        &self.annotations
+    }
+
     /// Returns a protected reference to the resource pools.
     pub fn get_resource_pools(&mut self) -> Arc<Mutex<IRManager>> {
         self.resource_pools.clone()
@@ -141,131 +226,69 @@ impl IRGenerator {
         self.module.clone().expect("Missing module")
     }
 
-    /// Returns the builder used for constructing IR statements.
-    /// 
-    /// # Returns
-    ///
-    /// Returns this IRGenerator's builder's tag for use with functions that require the builder.
-    ///
-    pub fn get_builder(&self) -> BuilderTag {
-        self.builder.clone().expect("Missing builder")
-    }
-
-    /// Retrieves the current function being built.
-    /// 
-    /// # Returns
-    ///
-    /// Returns the tag of the function currently being built for use
-    /// with functions that need this information, could be None if called
-    /// before initialized.
-    ///
-    pub fn get_function(&self) -> Option<ValueTag> {
-        self.function.clone()
-    }
-
-    /// Sets the current function being built.
+    /// Returns the IR of one function in the module generated so far, as the module prints it: from its `define`
+    /// line to its closing brace, or just its `declare` line if it is defined outside the module. Tests can assert on
+    /// a single function this way, rather than on the whole module.
     ///
     /// # Parameters
     ///
-    /// - `function`: The ValueTag of the new function to be set as the current function
+    /// - `name`: The name of the function.
     ///
-    pub fn set_function(&mut self, function: ValueTag) {
-        self.function = Some(function)
-    }
-
-    /// Retrieves the current block to insert after
-    /// 
     /// # Returns
     ///
-    /// Returns the tag of the basic block which is set as the block to insert
-    /// after currently. Could be None if this is called when no basic block
-    /// is currently set.
-    ///
-    pub fn get_current_insert_block(&self) -> Option<BasicBlockTag> {
-        self.current_insert_block.clone()
-    }
-
-    /// Sets the current block to insert after
-    /// 
-    /// # Parameters
-    ///
-    /// - `to_set`: The BasicBlockTag of the new baic block to be set as the insert block
-    ///
-    pub fn set_current_insert_block(&mut self, to_set: BasicBlockTag) {
-        self.current_insert_block = Some(to_set);
-    }
-
-    /// Retrieves the current break and potentially the current continue target
-    /// 
-    /// # Returns
-    ///
-    /// Returns a vector of basic block tags where index 0 is the break target and index 1
-    /// is the continue target (if one exists, otherwise the vector will only have index 0).
-    /// This may return None if an error occurred getting the targets from the target stack.
-    ///
-    pub fn get_break_continue_target(&self) -> Option<Vec<BasicBlockTag>> {
-        let target = if let Some(current_target_stack) = self.current_target_stack.as_ref() {
-            current_target_stack.last().cloned().expect("Failed to get break cont target!")
-        } else {
-            return None
+    /// Returns the function's IR, ending in a newline, or an empty string if the module has no such function.
+    pub fn function_ir_text(&mut self, name: &str) -> String {
+        let module_tag = self.get_module();
+        let resource_pools = self.get_resource_pools();
+        let resource_pools = resource_pools.lock().expect("Failed to lock mutex in function IR!");
+        let ir = match resource_pools.get_module(module_tag).map(io::write_to_string) {
+            Some(Ok(ir)) => ir,
+            _ => return String::new(),
         };
 
-        match target {
-            BranchTarget::BreakAndContinueTarget(t1, t2) => {
-                let mut out_vec: Vec<BasicBlockTag> = Vec::new();
-
-                if t1.is_none() {
-                    return None
-                }
-
-                out_vec.push(t1.unwrap());
-
-                if !t2.is_none() {
-                    out_vec.push(t2.unwrap());
+        let signature = format!("@{}(", name);
+        let mut lines = ir.lines().skip_while(|line| {
+            !((line.starts_with("define ") || line.starts_with("declare ")) && line.contains(&signature))
+        });
+        let mut text = String::new();
+        if let Some(first) = lines.next() {
+            text.push_str(first);
+            text.push('\n');
+            if first.starts_with("define ") {
+                for line in lines {
+                    text.push_str(line);
+                    text.push('\n');
+                    if line == "}" {
+                        break;
+                    }
                 }
-
-                Some(out_vec)
-            },
+            }
         }
+        text
     }
 
-    /// Pushes a tag for break; and a tag for continue; on the target stack.
+    /// Returns the builder used for constructing IR statements.
     /// 
-    /// # Parameters
+    /// # Returns
     ///
-    /// - `break_block_tag`: The BasicBlockTag of the tag to branch to when break; is encountered.
-    /// - `continue_block_tag`: The BasicBlockTag of the tag to branch to when continue; is encountered.
+    /// Returns this IRGenerator's builder's tag for use with functions that require the builder.
     ///
-    pub fn push_break_continue_target(&mut self, break_block_tag: BasicBlockTag, continue_block_tag: BasicBlockTag) {
-        if let Some(current_target_stack) = self.current_target_stack.as_mut() {
-            current_target_stack.push(BranchTarget::BreakAndContinueTarget(Some(break_block_tag), Some(continue_block_tag)));
-        } else {
-            panic!("Failed to get current target stack");
-        }
+    pub fn get_builder(&self) -> BuilderTag {
+        self.builder.clone().expect("Missing builder")
     }
 
-    /// Pushes only a tag for break; on the target stack.
-    /// 
-    /// # Parameters
+    /// Returns the builder local variables are allocated with. Inside a function it is positioned at the end of the
+    /// function's allocas block, so that every alloca is in the entry block however deeply its variable is declared,
+    /// as mem2reg requires and so that loops do not grow the stack on every iteration. Outside a function it is the
+    /// builder of `get_builder`.
     ///
-    /// - `break_block_tag`: The BasicBlockTag of the tag to branch to when break; is encountered.
+    /// # Parameters
     ///
-    pub fn push_break_target(&mut self, break_block_tag: BasicBlockTag) {
-        if let Some(current_target_stack) = self.current_target_stack.as_mut() {
-            current_target_stack.push(BranchTarget::BreakAndContinueTarget(Some(break_block_tag), None));
-        } else {
-            panic!("Failed to get current target stack");
-        }
-    }
-
-
-    /// Pops current break continue target off the stack
-    /// 
-    pub fn pop_target(&mut self) {
-        if let Some(current_target_stack) = self.current_target_stack.as_mut() {
-            current_target_stack.pop();
-        } else {
-            panic!("Failed to get current target stack");
+    /// - `ctx`: The context of the function being generated.
+    pub fn get_alloca_builder(&self, ctx: &CodegenCtx) -> BuilderTag {
+        match ctx.get_alloca_block() {
+            Some(_) => self.alloca_builder.clone().expect("Missing alloca builder"),
+            None => self.get_builder(),
         }
     }
 
@@ -328,6 +351,209 @@ impl IRGenerator {
         self.store.delete_table().expect("Failed to delete table");
     }
 
+    /// Checks whether the variable of a name in the current scope was declared `volatile`.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the variable.
+    ///
+    /// # Returns
+    ///
+    /// Returns true if loads and stores of the variable must be marked volatile.
+    pub fn is_volatile_var(&self, name: &str) -> bool {
+        self.search_store_entry(name).is_some_and(|entry| entry.volatile)
+    }
+
+    /// Finds the type the variable of a name in the current scope was declared with.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the variable.
+    ///
+    /// # Returns
+    ///
+    /// Returns the declared `DataType` from the variable's store entry, or None if no variable of the name is in
+    /// scope or its declaration did not name a type.
+    pub fn search_declared_type(&self, name: &str) -> Option<DataType> {
+        self.search_store_entry(name).and_then(|entry| entry.data_type)
+    }
+
+    /// Records a function declared in the module so it can be called or have its address taken.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: A `String` name of the function.
+    /// - `function`: The `ValueTag` of the function.
+    /// - `fn_type`: The `TypeTag` of the function's type.
+    pub fn add_function_to_table(&mut self, name: String, function: ValueTag, fn_type: TypeTag) {
+        self.function_table.insert(name, (function, fn_type));
+    }
+
+    /// Looks up a function declared in the module.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the function.
+    ///
+    /// # Returns
+    ///
+    /// Returns the function's `ValueTag` and function `TypeTag`, or None if no such function was declared.
+    pub fn search_function_table(&self, name: &str) -> Option<(ValueTag, TypeTag)> {
+        self.function_table.get(name).cloned()
+    }
+
+    /// Records the function type of a function pointer variable, which is needed to call through it. The type is
+    /// kept with the variable in the store, so it goes out of scope with the variable and never applies to another
+    /// variable of the same name.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: A `String` name of the function pointer variable, which must already be in the store.
+    /// - `fn_type`: The `TypeTag` of the function type it points to.
+    /// - `param_types`: The declared types of the function type's parameters, which arguments are converted to.
+    pub fn add_function_pointer_type(&mut self, name: String, fn_type: TypeTag, param_types: Vec<DataType>) {
+        let entry = self.store.search_for_entry_mut(name).expect("Failed to find function pointer in table");
+        entry.function_type = Some((fn_type, param_types));
+    }
+
+    /// Looks up the function type of a function pointer variable in the current scope.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the variable.
+    ///
+    /// # Returns
+    ///
+    /// Returns the function `TypeTag` the variable points to, with the declared types of its parameters, or None if
+    /// the innermost variable of that name is not a function pointer or there is none.
+    pub fn search_function_pointer_type(&self, name: &str) -> Option<(TypeTag, Vec<DataType>)> {
+        self.search_store_entry(name).and_then(|entry| entry.function_type.clone())
+    }
+
+    /// Records a named struct type declared in the module.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: A `String` name of the struct.
+    /// - `struct_type`: The `TypeTag` of the struct type.
+    pub fn add_struct_type(&mut self, name: String, struct_type: TypeTag) {
+        self.struct_types.insert(name, struct_type);
+    }
+
+    /// Looks up a named struct type declared in the module.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the struct.
+    ///
+    /// # Returns
+    ///
+    /// Returns the struct's `TypeTag`, or None if no such struct was declared.
+    pub fn search_struct_type(&self, name: &str) -> Option<TypeTag> {
+        self.struct_types.get(name).cloned()
+    }
+
+    /// Records the memory layout of a struct declared in the module.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: A `String` name of the struct.
+    /// - `layout`: The `StructLayout` of the struct.
+    pub fn add_struct_layout(&mut self, name: String, layout: StructLayout) {
+        self.struct_layouts.insert(name, layout);
+    }
+
+    /// Looks up the memory layout of a struct declared in the module.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the struct.
+    ///
+    /// # Returns
+    ///
+    /// Returns the struct's `StructLayout`, or None if no such struct was declared.
+    pub fn search_struct_layout(&self, name: &str) -> Option<&StructLayout> {
+        self.struct_layouts.get(name)
+    }
+
+    /// Records the struct type of a struct variable or parameter, which its loads and by-value arguments need. The
+    /// type is kept with the variable in the store, so it goes out of scope with the variable and never applies to
+    /// another variable of the same name.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: A `String` name of the variable, which must already be in the store.
+    /// - `struct_type`: The `TypeTag` of the variable's struct type.
+    pub fn add_struct_variable_type(&mut self, name: String, struct_type: TypeTag) {
+        let entry = self.store.search_for_entry_mut(name).expect("Failed to find struct variable in table");
+        entry.struct_type = Some(struct_type);
+    }
+
+    /// Looks up the struct type of a struct variable or parameter in the current scope.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the variable.
+    ///
+    /// # Returns
+    ///
+    /// Returns the variable's struct `TypeTag`, or None if the innermost variable of that name is not a struct or
+    /// there is none.
+    pub fn search_struct_variable_type(&self, name: &str) -> Option<TypeTag> {
+        self.search_store_entry(name).and_then(|entry| entry.struct_type)
+    }
+
+    /// Records the declared parameter types of a function.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: A `String` name of the function.
+    /// - `parameter_types`: The `DataType` of each parameter, in order.
+    pub fn add_function_parameter_types(&mut self, name: String, parameter_types: Vec<DataType>) {
+        self.function_parameter_types.insert(name, parameter_types);
+    }
+
+    /// Looks up the declared parameter types of a function.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the function.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `DataType` of each parameter, or None if no such function was declared.
+    pub fn search_function_parameter_types(&self, name: &str) -> Option<Vec<DataType>> {
+        self.function_parameter_types.get(name).cloned()
+    }
+
+    /// Records the value of an enum constant declared in the module.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: A `String` name of the constant.
+    /// - `value`: The constant's value.
+    /// - `underlying`: The underlying type of the constant's enum.
+    pub fn add_enum_constant(&mut self, name: String, value: i64, underlying: DataType) {
+        self.enum_constants.insert(name, (value, underlying));
+    }
+
+    /// Looks up an enum constant declared in the module. A variable in scope with the same name hides the constant,
+    /// as in C.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the constant.
+    ///
+    /// # Returns
+    ///
+    /// Returns the constant's value and the underlying type of its enum, or None if no such constant is visible.
+    pub fn search_enum_constant(&self, name: &str) -> Option<(i64, DataType)> {
+        if self.search_store_entry(name).is_some() {
+            return None;
+        }
+        self.enum_constants.get(name).copied()
+    }
+
     /// Searches the store table for a variable with a name
     /// 
     /// # Parameters
@@ -350,16 +576,52 @@ impl IRGenerator {
         let value = self.store.search_for_var(name);
         match value {
             Ok(tag) => tag,
-            Err(e) => panic!("{:?}", e)
+            Err(e) => panic!("{:?}\nVariables in scope:\n{}", e, self.dump_locals())
         }
     }
 
+    /// Searches the store table for what is known about a variable with a name
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of a variable in the current scope or an outer scope.
+    ///
+    /// # Returns
+    ///
+    /// Returns the variable's `StoreEntry`, or None if no variable with the name is in scope.
+    pub fn search_store_entry(&self, name: &str) -> Option<&StoreEntry> {
+        self.store.search_for_entry(name.to_string()).ok()
+    }
+
+    /// Describes the variables in scope, one per line, innermost scope first and each scope in declaration order,
+    /// as in `x: Integer, mutable, declared at bytes 4..5`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the description, which is empty if no variables are in scope.
+    pub fn dump_locals(&self) -> String {
+        self.store.locals().into_iter()
+            .map(|(name, entry)| {
+                let data_type = entry.data_type.map_or("unknown type".to_string(), |data_type| data_type.to_string());
+                let mutability = if entry.mutable { "mutable" } else { "const" };
+                let declared = entry.span.map_or("declared at an unknown position".to_string(), |span| {
+                    format!("declared at bytes {}..{}", span.offset, span.end())
+                });
+                format!("{}: {}, {}, {}\n", name, data_type, mutability, declared)
+            })
+            .collect()
+    }
+
     /// adds an allocation tag to the current store table
     /// 
     /// # Parameters
     ///
     /// - `name`: A `String` name of a variable to set an allocation tag for.
     /// - `tag`: A `ValueTag` allocation tag of the variable to store in the table
+    /// - `data_type`: The type the variable was declared with, or None if the declaration did not name one.
+    /// - `mutable`: Whether the variable may be assigned after it is initialized.
+    /// - `volatile`: Whether the variable was declared `volatile`, so its loads and stores are marked volatile.
+    /// - `span`: The source of the variable's name in its declaration, or None if it is not known.
     ///
     /// # Examples
     ///
@@ -370,11 +632,12 @@ impl IRGenerator {
     /// these allocation tags later. Use */
     /// //let var_name: String = /* some variable name that we want to create*/
     /// //let alloca_tag = /* allocation tag of the variable we've created with IR generation */
-    /// //self.add_tag_to_store_table(var_name, alloca_tag);
+    /// //self.add_tag_to_store_table(var_name, alloca_tag, Some(DataType::Integer), true, false, name_span);
     /// /* to do this. */
     /// ```
-    pub fn add_tag_to_store_table(&mut self, name: String, tag: ValueTag) {
-        self.store.add_tag_to_top_table(name, tag).expect("Failed to add tag to table");
+    pub fn add_tag_to_store_table(&mut self, name: String, tag: ValueTag, data_type: Option<DataType>, mutable: bool, volatile: bool, span: Option<Span>) {
+        let entry = StoreEntry::new(tag, data_type, mutable, volatile, span);
+        self.store.add_tag_to_top_table(name, entry).expect("Failed to add tag to table");
     }
 
     /// Retrieves the current basic block the builder is pointing into.
@@ -505,8 +768,30 @@ impl IRGenerator {
     ///
     /// Returns a `ModuleTag` containing the constructed module when IR generation is complete.
     ///
-    pub fn generate_ir(&mut self, mut input: Module) -> ModuleTag {
+    pub fn generate_ir(&mut self, input: Module) -> ModuleTag {
+        let mut diagnostics = DiagnosticSink::new();
+        match self.generate_ir_reporting(input, &mut diagnostics) {
+            Some(module) => module,
+            None => panic!("Failed on root: {:?}", diagnostics.errors()),
+        }
+    }
+
+    /// Generates LLVM IR from a given module by processing its AST, reporting the error of each element that fails
+    /// instead of panicking. Elements after a failed one are still generated, so every failure is reported, along
+    /// with the warnings found while generating.
+    ///
+    /// # Parameters
+    ///
+    /// - `input`: A `Module` Containing an AST and an STS.
+    /// - `diagnostics`: The sink errors and warnings are reported to.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `ModuleTag` of the constructed module, or `None` if any element failed.
+    ///
+    pub fn generate_ir_reporting(&mut self, mut input: Module, diagnostics: &mut DiagnosticSink) -> Option<ModuleTag> {
         let module: &mut Vec<ModElement> = input.get_mut_children();
+        let mut failed = false;
 
         while let Some(mod_element) = module.pop() {
             let sts_stack: SymbolTableStack = mod_element.get_sym_table_stack();
@@ -515,10 +800,82 @@ impl IRGenerator {
 
             let ast: AST = mod_element.get_ast();
             let root = ast.get_root();
-            self.ir_router(&root).expect("Failed on root");
+            if let Err(error) = self.ir_router(&root, &mut CodegenCtx::new()) {
+                diagnostics.error(error);
+                failed = true;
+            }
         }
+        diagnostics.warnings_from(std::mem::take(&mut self.warnings));
+
+        if failed {
+            return None;
+        }
+        Some(self.get_module())
+    }
+
+    /// Generates the LLVM type of a data type. Integers of every width and signedness other than `char` are `i64`,
+    /// `float` and `double` are the IEEE single and double precision types of the C calling convention.
+    ///
+    /// # Parameters
+    ///
+    /// - `data_type`: The `DataType` to generate a type for.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<TypeTag, ErrorType>` containing the type.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an error if the data type is not supported.
+    ///
+    pub fn generate_type_ir(&mut self, data_type: DataType) -> Result<TypeTag, ErrorType> {
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in type!");
+        match data_type {
+            DataType::Integer | DataType::Sign | DataType::Unsign | DataType::Long | DataType::UnsignedLong
+            | DataType::LongLong | DataType::UnsignedLongLong => resource_pools.int_type(self.get_context(), 64)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer type".to_string() }),
+            DataType::Float => resource_pools.float_type(self.get_context())
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create float type".to_string() }),
+            DataType::Double => resource_pools.double_type(self.get_context())
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create double type".to_string() }),
+            DataType::Boolean => resource_pools.boolean_type(self.get_context())
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create boolean type".to_string() }),
+            DataType::Char | DataType::SignedChar | DataType::UnsignedChar => resource_pools.int_type(self.get_context(), 8)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create char type".to_string() }),
+            DataType::Void => resource_pools.void_type(self.get_context())
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create void type".to_string() }),
+            _ => Err(ErrorType::DevError { message: format!("Unsupported data type: {:?}", data_type) })
+        }
+    }
 
-        self.get_module()
+    /// Generates the LLVM type a variable of a declared type is stored as, and so loaded as: its own type for numbers,
+    /// booleans and characters, a pointer for strings and function pointers, and `i64` for any other type or a
+    /// declaration without one.
+    ///
+    /// # Parameters
+    ///
+    /// - `data_type`: The `DataType` the variable was declared with, if its declaration named one.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<TypeTag, ErrorType>` containing the type.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an error if the type could not be created.
+    ///
+    pub fn storage_type_ir(&mut self, data_type: Option<DataType>) -> Result<TypeTag, ErrorType> {
+        match data_type {
+            Some(DataType::String | DataType::Function) => {
+                let resource_pools = self.get_resource_pools();
+                let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in type!");
+                resource_pools.pointer_type(self.get_context())
+                    .ok_or_else(|| ErrorType::DevError { message: "Failed to create pointer type".to_string() })
+            },
+            Some(DataType::Void | DataType::None | DataType::Struct | DataType::Enum) | None => self.generate_type_ir(DataType::Integer),
+            Some(data_type) => self.generate_type_ir(data_type),
+        }
     }
 
     /// Routes the generation of LLVM IR based on the type of AST node encountered.
@@ -526,6 +883,7 @@ impl IRGenerator {
     /// # Parameters
     ///
     /// - `node`: A reference to an `ASTNode` to generate IR for.
+    /// - `ctx`: The context of the function the node is in, or of the top level outside any function.
     /// 
     /// # Returns
     ///
@@ -536,149 +894,58 @@ impl IRGenerator {
     ///
     /// - Returns an error if there was a problem during IR generation such as a malformed node.
     ///
-    pub fn ir_router(&mut self, node: &ASTNode) -> Result<Option<Tag>, ErrorType> {
+    pub fn ir_router(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
         match node.get_node_type() {
             NodeType::TopLevelExpression => {
                 // Process all children of TopLevelExpression
                 for child in node.get_children() {
-                    self.ir_router(&child)?;
+                    self.ir_router(&child, ctx)?;
                 }
                 Ok(None)
             },
-            NodeType::FunctionDeclaration => self.generate_fn_declaration_ir(node),
-            NodeType::BlockExpression => self.generate_block_exp(node),
-            NodeType::Assignment => self.generate_assignment_ir(node),
-            NodeType::Initialization => {
-                // For initialization, we need to ensure proper ordering of operations
-                let children = node.get_children();
-                if children.len() < 2 || children.len() > 3 {
-                    return Err(ErrorType::DevError { 
-                        message: "Invalid variable initialization node: expected 2 or 3 children".to_string() 
-                    });
-                }
-
-                // If initializing with a variable, handle it specially
-                if let Some(init_value_node) = children.get(1) {
-                    if let NodeType::Variable = init_value_node.get_node_type() {
-                        // Get the source variable name
-                        let src_var_name = match init_value_node.get_children()[0].get_node_type() {
-                            NodeType::Identifier(ref n) => n.clone(),
-                            _ => return Err(ErrorType::DevError { message: "Expected identifier in variable node".to_string() })
-                        };
-
-                        // Get the target variable name
-                        let var_name = match children[0].get_node_type() {
-                            NodeType::Identifier(name) => name.clone(),
-                            NodeType::Variable => {
-                                let id_child = &children[0].get_children()[0];
-                                match id_child.get_node_type() {
-                                    NodeType::Identifier(name) => name.clone(),
-                                    _ => return Err(ErrorType::DevError { message: "Expected identifier in variable node".to_string() })
-                                }
-                            },
-                            _ => return Err(ErrorType::DevError { message: "Expected identifier or variable node".to_string() })
-                        };
-
-                        // First load the value from source variable
-                        let src_alloca = self.search_store_table(src_var_name);
-                        let resource_pools = self.get_resource_pools();
-                        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in variable recall!");
-                        let type_tag = resource_pools.int_type(self.get_context(), 64)
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to create i64 type".to_string() })?;
-                        let loaded = resource_pools.get_var(self.get_builder(), type_tag, src_alloca, "vrecallID1")
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to load variable".to_string() })?;
-                        drop(resource_pools);
-
-                        // Then create the new variable
-                        let resource_pools = self.get_resource_pools();
-                        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in initialization!");
-                        let alloca = resource_pools.init_var(self.get_builder(), &var_name, type_tag, None)
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to initialize variable".to_string() })?;
-                        drop(resource_pools);
-                        self.add_tag_to_store_table(var_name.clone(), alloca);
-
-                        // Finally store the loaded value
-                        let resource_pools = self.get_resource_pools();
-                        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in initialization store!");
-                        resource_pools.reassign_var(self.get_builder(), alloca, loaded)
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to store initial value".to_string() })?;
-                        return Ok(None);
-                    }
-                }
-                self.generate_initialization_ir(node)
-            },
-            NodeType::IfStatement => self.generate_if_ir(node),
-            NodeType::WhileLoop => self.generate_while_ir(node),
-            NodeType::DoWhileLoop => self.generate_do_while_ir(node),
-            NodeType::ForLoop => self.generate_for_ir(node),
-            NodeType::Return => self.generate_return_ir(node),
+            NodeType::FunctionDeclaration => self.generate_fn_declaration_ir(node, ctx),
+            NodeType::FunctionPrototype => self.generate_fn_prototype_ir(node, ctx),
+            NodeType::StructDeclaration => self.generate_struct_declaration_ir(node, ctx),
+            NodeType::EnumDeclaration => self.generate_enum_declaration_ir(node),
+            NodeType::BlockExpression => self.generate_block_exp(node, ctx),
+            NodeType::Assignment => self.generate_assignment_ir(node, ctx),
+            NodeType::CompoundAssignment => self.generate_compound_assignment_ir(node, ctx),
+            NodeType::Initialization => self.generate_initialization_ir(node, ctx),
+            NodeType::IfStatement => self.generate_if_ir(node, ctx),
+            NodeType::WhileLoop => self.generate_while_ir(node, ctx),
+            NodeType::DoWhileLoop => self.generate_do_while_ir(node, ctx),
+            NodeType::ForLoop => self.generate_for_ir(node, ctx),
+            NodeType::Return => self.generate_return_ir(node, ctx),
             NodeType::Variable => self.generate_variable_ir(node),
-            NodeType::Break => self.generate_break_ir(node),
-            NodeType::Continue => self.generate_continue_ir(node),
+            NodeType::FunctionCall => self.generate_function_call_ir(node, ctx),
+            NodeType::UnaryExpression => self.generate_unary_expression_ir(node, ctx),
+            NodeType::PostfixExpression => self.generate_increment_ir(node, ctx),
+            NodeType::BinaryExpression => self.generate_binary_expression_ir(node, ctx),
+            NodeType::SwitchStatement => self.generate_switch_ir(node, ctx),
+            NodeType::Break => self.generate_break_ir(node, ctx),
+            NodeType::Continue => self.generate_continue_ir(node, ctx),
             NodeType::Literal(_) => self.generate_literal_ir(node),
-            NodeType::Type(data_type) => {
-                let resource_pools = self.get_resource_pools();
-                let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in type!");
-                match data_type {
-                    DataType::Integer => {
-                        let int_type = resource_pools.int_type(self.get_context(), 64)
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer type".to_string() })?;
-                        Ok(Some(Tag::Type(int_type)))
-                    },
-                    DataType::Float => {
-                        let float_type = resource_pools.float_type(self.get_context())
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to create float type".to_string() })?;
-                        Ok(Some(Tag::Type(float_type)))
-                    },
-                    DataType::Boolean => {
-                        let bool_type = resource_pools.boolean_type(self.get_context())
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to create boolean type".to_string() })?;
-                        Ok(Some(Tag::Type(bool_type)))
-                    },
-                    DataType::Void => {
-                        let void_type = resource_pools.void_type(self.get_context())
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to create void type".to_string() })?;
-                        Ok(Some(Tag::Type(void_type)))
-                    },
-                    _ => Err(ErrorType::DevError { message: format!("Unsupported data type: {:?}", data_type) })
-                }
-            },
-            NodeType::Condition => {
-                // Condition nodes typically have one child that is the actual condition expression
-                if let Some(child) = node.get_children().first() {
-                    let result = self.ir_router(child)?;
-                    if let Some(Tag::Value(value)) = result {
-                        // Convert the value to a boolean type if it's not already
-                        let resource_pools = self.get_resource_pools();
-                        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in condition!");
-                        let bool_type = resource_pools.boolean_type(self.get_context())
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to create boolean type".to_string() })?;
-                        let zero = resource_pools.create_integer(self.get_context(), 0)
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer constant".to_string() })?;
-                        let eq = resource_pools.build_icmp_eq(self.get_builder(), value, zero, "cmptmp")
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to create comparison".to_string() })?;
-                        let bool_value = resource_pools.build_logical_not(self.get_builder(), self.get_context(), eq, "nottmp")
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to create logical not".to_string() })?;
-                        Ok(Some(Tag::Value(bool_value)))
-                    } else {
-                        Err(ErrorType::DevError { message: "Expected value tag".to_string() })
-                    }
-                } else {
-                    Err(ErrorType::DevError { message: "Condition node has no children".to_string() })
-                }
-            },
+            NodeType::Type(data_type) => Ok(Some(Tag::Type(self.generate_type_ir(data_type)?))),
+            NodeType::Condition => self.generate_condition_ir(node, ctx),
             NodeType::AssignedValue => {
                 // AssignedValue nodes wrap the actual value being assigned
                 if let Some(child) = node.get_children().first() {
-                    self.ir_router(child)
+                    self.ir_router(child, ctx)
                 } else {
                     Err(ErrorType::DevError { message: "AssignedValue node has no children".to_string() })
                 }
             },
+            NodeType::ExpressionStatement => {
+                // The expression is evaluated for its effects and its value discarded
+                if let Some(child) = node.get_children().first() {
+                    self.ir_router(child, ctx)?;
+                }
+                Ok(None)
+            },
             NodeType::ElseStatement => {
                 // ElseStatement nodes contain a block expression as their child
                 if let Some(child) = node.get_children().first() {
-                    self.ir_router(child)
+                    self.ir_router(child, ctx)
                 } else {
                     Ok(None) // Empty else statement
                 }
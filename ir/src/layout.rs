@@ -0,0 +1,156 @@
+//! This file computes the memory layout of structs as C lays them out on a 64-bit target: each field is placed at
+//! the next multiple of its alignment, and the struct is padded to a multiple of its own alignment, which is that of
+//! its most aligned field. The `@packed` and `@align(N)` attributes change this as they do in GCC and Clang.
+//!
+//! The fields of anonymous struct and union members are laid out with the struct containing them, so each one has an
+//! offset from the start of that struct, as it is accessed as one of its fields.
+
+use common::ast::data_type::DataType;
+
+/// A member of a struct, as laid out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Member {
+    /// A field, with its name and type.
+    Field(String, DataType),
+    /// An anonymous struct, with its members.
+    Struct(Vec<Member>),
+    /// An anonymous union, with its members, which all start at its beginning.
+    Union(Vec<Member>),
+}
+
+impl Member {
+    /// Returns the size and alignment of the member in bytes, or None if it has a field whose size is not known.
+    pub fn size_and_align(&self) -> Option<(u64, u64)> {
+        place(self, 0, &mut Vec::new())
+    }
+}
+
+/// A field of a struct, including one of an anonymous member, as laid out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldLayout {
+    /// The name of the field.
+    pub name: String,
+    /// The type of the field.
+    pub data_type: DataType,
+    /// The offset of the field from the start of the struct in bytes.
+    pub offset: u64,
+}
+
+/// The layout of a struct in memory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructLayout {
+    /// The size of the struct in bytes, including the padding after its last member.
+    pub size: u64,
+    /// The alignment of the struct in bytes.
+    pub align: u64,
+    /// Every field of the struct, including those of its anonymous members, in declaration order.
+    pub fields: Vec<FieldLayout>,
+    /// The number of padding bytes after the last member.
+    pub trailing_padding: u64,
+    /// Whether the struct is packed, so that its members are placed without padding between them.
+    pub packed: bool,
+}
+
+impl StructLayout {
+    /// Computes the layout of a struct from its members.
+    ///
+    /// # Parameters
+    ///
+    /// - `members`: The struct's members, in declaration order.
+    /// - `packed`: Whether the struct is `@packed`, which places every member directly after the previous one and
+    /// makes the struct's own alignment 1. Anonymous members keep their own layout, as in GCC.
+    /// - `min_align`: The alignment given by `@align(N)`, which raises the struct's alignment to N but never lowers
+    /// it, as in C.
+    ///
+    /// # Returns
+    ///
+    /// Returns the layout, or None if a field has a type whose size is not known.
+    pub fn new(members: &[Member], packed: bool, min_align: Option<u64>) -> Option<Self> {
+        let mut fields = Vec::new();
+        let (end, align) = place_sequence(members, packed, 0, &mut fields)?;
+        let align = align.max(min_align.unwrap_or(1));
+        let size = end.next_multiple_of(align);
+        Some(Self { size, align, fields, trailing_padding: size - end, packed })
+    }
+
+    /// Looks up a field of the struct, which may be a field of one of its anonymous members.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the field.
+    ///
+    /// # Returns
+    ///
+    /// Returns the field's layout, or None if the struct has no such field.
+    pub fn field(&self, name: &str) -> Option<&FieldLayout> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+/// Places members one after another starting at `base`, adding their fields to `fields`.
+///
+/// # Returns
+///
+/// Returns the offset just after the last member, without trailing padding, and the alignment of the most aligned
+/// member.
+fn place_sequence(members: &[Member], packed: bool, base: u64, fields: &mut Vec<FieldLayout>) -> Option<(u64, u64)> {
+    let mut end = base;
+    let mut align: u64 = 1;
+    for member in members {
+        let (_, member_align) = member.size_and_align()?;
+        let member_align = if packed { 1 } else { member_align };
+        let offset = end.next_multiple_of(member_align);
+        let (member_size, _) = place(member, offset, fields)?;
+        end = offset + member_size;
+        align = align.max(member_align);
+    }
+    Some((end, align))
+}
+
+/// Places one member at `offset`, adding its fields to `fields`.
+///
+/// # Returns
+///
+/// Returns the member's size, padded to a multiple of its alignment, and its alignment.
+fn place(member: &Member, offset: u64, fields: &mut Vec<FieldLayout>) -> Option<(u64, u64)> {
+    match member {
+        Member::Field(name, data_type) => {
+            let (size, align) = scalar_size_and_align(data_type)?;
+            fields.push(FieldLayout { name: name.clone(), data_type: *data_type, offset });
+            Some((size, align))
+        },
+        Member::Struct(members) => {
+            let (end, align) = place_sequence(members, false, offset, fields)?;
+            Some(((end - offset).next_multiple_of(align), align))
+        },
+        Member::Union(members) => {
+            let mut size: u64 = 0;
+            let mut align: u64 = 1;
+            for member in members {
+                let (member_size, member_align) = place(member, offset, fields)?;
+                size = size.max(member_size);
+                align = align.max(member_align);
+            }
+            Some((size.next_multiple_of(align), align))
+        },
+    }
+}
+
+/// Returns the size and alignment in bytes of the LLVM type a field of the given type is generated as.
+///
+/// # Parameters
+///
+/// - `data_type`: The type of the field.
+///
+/// # Returns
+///
+/// Returns the size and alignment, or None if fields of the type are not supported.
+fn scalar_size_and_align(data_type: &DataType) -> Option<(u64, u64)> {
+    match data_type {
+        DataType::Boolean | DataType::Char | DataType::SignedChar | DataType::UnsignedChar => Some((1, 1)),
+        DataType::Float => Some((4, 4)),
+        DataType::Integer | DataType::Sign | DataType::Unsign | DataType::Long | DataType::UnsignedLong
+        | DataType::LongLong | DataType::UnsignedLongLong | DataType::Double | DataType::Function => Some((8, 8)),
+        _ => None,
+    }
+}
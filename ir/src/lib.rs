@@ -8,15 +8,21 @@
 //! The `ir` module is organized into submodules that handle different aspects of LLVM IR generation:
 //!
 //! - `core`: Drives the generation process.
+//! - `context`: Holds the state of generating one function, which is passed down the generation of its body.
 //! - `block`: Handles generation for block-containing elements like loops and functions.
 //! - `statement`: Handles generation for statements and operations within blocks.
 //! - `primitive`: Handles generation for primitive data types and operations.
 //! - `store`: Handles keeping track of the tags for creating and storing variables
+//! - `layout`: Computes the memory layout of structs, including `@packed` and `@align(N)` ones
+//! - `stats`: Measures the instructions, basic blocks and allocas of each generated function
 
 
 /// Core of the LLVM IR generation process.
 pub mod core;
 
+/// Per-function state of LLVM IR generation.
+pub mod context;
+
 /// LLVM IR generation for expressions containing blocks like loops, functions, enums, etc.
 mod block;
 
@@ -27,4 +33,10 @@ mod statement;
 mod primitive;
 
 /// Allocation store for managing variable allocations across scopes. 
-mod store;
\ No newline at end of file
+mod store;
+
+/// Memory layout of structs.
+pub mod layout;
+
+/// Size statistics of generated functions.
+pub mod stats;
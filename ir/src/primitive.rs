@@ -1,9 +1,9 @@
 //! This file hosts all of the functions necessary for generating LLVM IR
 //! for primitives such as basic data types and literal values.
 
-use common::{ast::{core::ASTNode, data_type::DataType}, error::ErrorType};
+use common::{ast::{core::ASTNode, data_type::DataType}, error::ErrorType, literal::string_bytes};
 use common::ast::node_type::NodeType;
-use safe_llvm::ir::core::Tag;
+use safe_llvm::ir::core::{Tag, TypeTag};
 use crate::core::IRGenerator;
 
 impl IRGenerator {
@@ -32,8 +32,59 @@ impl IRGenerator {
     /// the Tag and use this for other functions. */
     /// ```
     pub fn generate_data_type_ir(&mut self, data_type: &DataType) -> Result<Option<Tag>, ErrorType> {
-        let _ = data_type;
-        unimplemented!();
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in data type!");
+        let type_tag = match data_type {
+            DataType::Integer => resource_pools.int_type(self.get_context(), 64)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer type".to_string() })?,
+            DataType::Float => resource_pools.float_type(self.get_context())
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create float type".to_string() })?,
+            DataType::Boolean => resource_pools.boolean_type(self.get_context())
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create boolean type".to_string() })?,
+            DataType::Void => resource_pools.void_type(self.get_context())
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create void type".to_string() })?,
+            // Function values are always handled through pointers
+            DataType::Function => resource_pools.pointer_type(self.get_context())
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create pointer type".to_string() })?,
+            _ => return Err(ErrorType::DevError { message: format!("Unsupported data type: {:?}", data_type) })
+        };
+        Ok(Some(Tag::Type(type_tag)))
+    }
+
+    /// Generates an LLVM function type from a `Type(Function)` node, whose first child is the return type
+    /// and whose remaining children are the parameter types.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to the `Type(Function)` `ASTNode`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<TypeTag, ErrorType>` containing the function type, or an Error if one of the
+    /// types could not be generated.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if the node is not a function type or generation of a type failed.
+    pub fn generate_function_type_ir(&mut self, node: &ASTNode) -> Result<TypeTag, ErrorType> {
+        let mut types = Vec::new();
+        for child in node.get_children() {
+            if let NodeType::Type(data_type) = child.get_node_type() {
+                match self.generate_data_type_ir(&data_type)? {
+                    Some(Tag::Type(type_tag)) => types.push(type_tag),
+                    _ => return Err(ErrorType::DevError { message: "Expected type tag".to_string() })
+                }
+            }
+        }
+        if types.is_empty() {
+            return Err(ErrorType::DevError { message: "Function type is missing its return type".to_string() });
+        }
+        let return_type = types.remove(0);
+
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in function type!");
+        resource_pools.create_function(Some(return_type), &types, false, self.get_context())
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to create function type".to_string() })
     }
 
     /// Generates LLVM IR for a literal.
@@ -64,6 +115,32 @@ impl IRGenerator {
             let resource_pools = self.get_resource_pools();
             let mut resource_pools = resource_pools.lock().expect("Failed to lock mutex in literal IR!");
 
+            // Character literals are stored by the parser as `'c'`, with escapes already decoded by the lexer
+            if let Some(body) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+                let mut chars = body.chars();
+                let c = match (chars.next(), chars.next()) {
+                    (Some(c), None) => c,
+                    _ => return Err(ErrorType::DevError { message: format!("Malformed character literal: {}", value) }),
+                };
+                // A C `char` is a single byte, so anything past 0xFF cannot be represented
+                if c as u32 > 0xFF {
+                    return Err(ErrorType::DevError {
+                        message: format!("Character literal {} does not fit in a char", value)
+                    });
+                }
+                let constant = resource_pools.create_integer(self.get_context(), c as i64)
+                    .ok_or_else(|| ErrorType::DevError { message: "Failed to create character constant".to_string() })?;
+                return Ok(Some(Tag::Value(constant)));
+            }
+
+            // String literals are stored as `"..."` with their contents escaped, so the constant is built from the
+            // bytes the body stands for, which need not be valid UTF-8
+            if let Some(body) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+                let constant = resource_pools.create_byte_string(self.get_context(), &string_bytes(body))
+                    .ok_or_else(|| ErrorType::DevError { message: "Failed to create string constant".to_string() })?;
+                return Ok(Some(Tag::Value(constant)));
+            }
+
             // Check if it's a boolean literal
             if value == "true" || value == "false" {
                 let bool_val = value == "true";
@@ -71,26 +148,108 @@ impl IRGenerator {
                     .ok_or_else(|| ErrorType::DevError { message: "Failed to create boolean constant".to_string() })?;
                 Ok(Some(Tag::Value(constant)))
             } else {
-                // Try parsing as integer first
-                if let Ok(int_value) = value.parse::<i64>() {
+                // Floating-point literals have a fractional part or an exponent, and may end in an `f` suffix
+                if let Some(float_value) = float_literal_value(&value) {
+                    let constant = resource_pools.create_float(self.get_context(), float_value)
+                        .ok_or_else(|| ErrorType::DevError { message: "Failed to create float constant".to_string() })?;
+                    Ok(Some(Tag::Value(constant)))
+                } else if let Ok(int_value) = value.parse::<i64>() {
                     let constant = resource_pools.create_integer(self.get_context(), int_value)
                         .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer constant".to_string() })?;
                     Ok(Some(Tag::Value(constant)))
                 } else {
-                    // Try parsing as float
-                    if let Ok(float_value) = value.parse::<f64>() {
-                        let constant = resource_pools.create_float(self.get_context(), float_value)
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to create float constant".to_string() })?;
-                        Ok(Some(Tag::Value(constant)))
-                    } else {
-                        Err(ErrorType::DevError { 
-                            message: format!("Failed to parse literal value: {}", value)
-                        })
-                    }
+                    Err(ErrorType::DevError { 
+                        message: format!("Failed to parse literal value: {}", value)
+                    })
                 }
             }
         } else {
             Err(ErrorType::DevError { message: "Expected literal node".to_string() })
         }
     }
-}
\ No newline at end of file
+}
+/// Parses a floating-point literal as spelled by the lexer, such as `3.14`, `1e-9`, or `2.5f`.
+///
+/// # Parameters
+///
+/// - `value`: The literal's spelling.
+///
+/// # Returns
+///
+/// Returns the literal's value, or None if it is not a floating-point literal.
+pub(crate) fn float_literal_value(value: &str) -> Option<f64> {
+    if !value.contains(['.', 'e', 'E']) {
+        return None;
+    }
+    value.trim_end_matches(['f', 'F']).parse::<f64>().ok()
+}
+
+/// Evaluates an expression made only of integer, character and boolean literals, as the condition of an `if` or
+/// `while` whose outcome is known before the program runs. `&&` and `||` short-circuit as they do at runtime, so
+/// `0 && f()` is constant even though `f()` is not.
+///
+/// # Parameters
+///
+/// - `node`: The expression, or the `Condition` or `AssignedValue` wrapping it.
+///
+/// # Returns
+///
+/// Returns the value of the expression, with `true` as 1 and `false` as 0, or None if it is not constant or
+/// would overflow or divide by zero.
+pub(crate) fn constant_integer_value(node: &ASTNode) -> Option<i64> {
+    let children = node.get_children();
+    match node.get_node_type() {
+        NodeType::Condition | NodeType::AssignedValue => constant_integer_value(children.first()?),
+        NodeType::Literal(value) => match value.as_str() {
+            "true" => Some(1),
+            "false" => Some(0),
+            _ => match value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+                Some(body) => {
+                    let mut chars = body.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) if c as u32 <= 0xFF => Some(c as i64),
+                        _ => None,
+                    }
+                },
+                _ => value.parse::<i64>().ok(),
+            },
+        },
+        NodeType::UnaryExpression => {
+            let operand = constant_integer_value(children.get(1)?)?;
+            match children.first()?.get_node_type() {
+                NodeType::Operator(op) if op == "-" => operand.checked_neg(),
+                NodeType::Operator(op) if op == "!" => Some((operand == 0) as i64),
+                _ => None,
+            }
+        },
+        NodeType::BinaryExpression if children.len() == 3 => {
+            let op = match children[1].get_node_type() {
+                NodeType::Operator(op) => op,
+                _ => return None,
+            };
+            let left = constant_integer_value(&children[0])?;
+            match op.as_str() {
+                "&&" if left == 0 => return Some(0),
+                "||" if left != 0 => return Some(1),
+                _ => {}
+            }
+            let right = constant_integer_value(&children[2])?;
+            match op.as_str() {
+                "+" => left.checked_add(right),
+                "-" => left.checked_sub(right),
+                "*" => left.checked_mul(right),
+                "/" => left.checked_div(right),
+                "%" => left.checked_rem(right),
+                "<" => Some((left < right) as i64),
+                ">" => Some((left > right) as i64),
+                "<=" => Some((left <= right) as i64),
+                ">=" => Some((left >= right) as i64),
+                "==" => Some((left == right) as i64),
+                "!=" => Some((left != right) as i64),
+                "&&" | "||" => Some((right != 0) as i64),
+                _ => None,
+            }
+        },
+        _ => None,
+    }
+}
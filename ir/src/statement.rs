@@ -4,11 +4,16 @@
 use common::{
     ast::{
         core::ASTNode, data_type::DataType, node_type::NodeType
-    }, error::ErrorType,
+    }, config::{Environment, OverflowMode}, constants::{
+        DEFAULT_LOGIC_END_LABEL, DEFAULT_LOGIC_RHS_LABEL, DEFAULT_OVERFLOW_CONTINUE_LABEL, DEFAULT_OVERFLOW_TRAP_LABEL
+    },
+    error::ErrorType, location::Span,
 };
 
-use crate::core::IRGenerator;
-use safe_llvm::ir::core::Tag;
+use crate::{context::CodegenCtx, core::IRGenerator, layout::{Member, StructLayout}};
+use crate::primitive::float_literal_value;
+use sts::dataflow::has_side_effects;
+use safe_llvm::ir::core::{ContextTag, IRManager, Tag, TypeTag, ValueTag};
 
 impl IRGenerator {
     /// Generates LLVM IR for a statement.
@@ -16,6 +21,7 @@ impl IRGenerator {
     /// # Parameters
     ///
     /// - `node`: A reference to an `ASTNode` to generate IR for a statement.
+    /// - `ctx`: The context of the function being generated.
     ///
     /// # Returns
     ///
@@ -30,24 +36,26 @@ impl IRGenerator {
     /// 
     /// ```
     /// //let a_node: ASTNode = /* Some ASTNode we want to generate a statement from */
-    /// //let result = self.generate_statement_ir(&a_node);
+    /// //let result = self.generate_statement_ir(&a_node, &mut ctx);
     /// /* check if type_result was Ok or Err, if Ok, it will contain the Tag that houses the 
     /// statement's ValueTag. */
     /// ```
-    pub fn generate_statement_ir(&mut self, node: &ASTNode) -> Result<Option<Tag>, ErrorType> {
+    pub fn generate_statement_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
         let child_node = &node.get_children()[0];
-        self.ir_router(child_node)
+        self.ir_router(child_node, ctx)
     }
 
-    /// Generates LLVM IR for an assignment.
+    /// Generates LLVM IR for an assignment. An assignment is an expression whose value is the value stored, so in
+    /// `a = b = 0` the inner assignment's value is stored again into `a`.
     /// 
     /// # Parameters
     ///
     /// - `node`: A reference to an `ASTNode` to generate IR for an assignment.
+    /// - `ctx`: The context of the function being generated.
     ///
     /// # Returns
     ///
-    /// Returns a `Result<Option<Tag>, ErrorType>` containing None
+    /// Returns a `Result<Option<Tag>, ErrorType>` containing the Tag of the value stored
     /// if generation went smoothly or an Error if there was a problem generating the assignment.
     ///
     /// # Errors
@@ -58,10 +66,10 @@ impl IRGenerator {
     /// 
     /// ```
     /// //let a_node: ASTNode = /* Some ASTNode we want to generate an assignment from */
-    /// //let result = self.generate_assignment_ir(&a_node);
-    /// /* check if type_result was Ok or Err, if Ok, it will contain None. */
+    /// //let result = self.generate_assignment_ir(&a_node, &mut ctx);
+    /// /* check if type_result was Ok or Err, if Ok, it will contain the Tag of the value stored. */
     /// ```
-    pub fn generate_assignment_ir(&mut self, node: &ASTNode) -> Result<Option<Tag>, ErrorType> {
+    pub fn generate_assignment_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
         let children = node.get_children();
         // Accept either an Identifier or a Variable node as the assignee
         let assignee_name = match children[0].get_node_type() {
@@ -77,11 +85,20 @@ impl IRGenerator {
         };
 
         // Process value first
-        let llvm_value = self.ir_router(&children[1])?.expect("Missing value in assignment");
-        let llvm_value = match llvm_value {
+        let llvm_value = self.ir_router(&children[1], ctx)?.expect("Missing value in assignment");
+        let mut llvm_value = match llvm_value {
             Tag::Value(value) => value,
             _ => return Err(ErrorType::DevError { message: "Expected value tag".to_string() })
         };
+        // A `bool` holds an `i1`, so any other value is stored as whether it is nonzero, and other values are
+        // converted to the type the variable is stored as
+        match self.search_declared_type(&assignee_name) {
+            Some(DataType::Boolean) => llvm_value = self.lower_condition_value(&children[1], llvm_value)?,
+            Some(to) => if let Some(from) = self.value_data_type(&children[1]) {
+                llvm_value = self.convert_value_ir(llvm_value, from, to)?;
+            },
+            None => {},
+        }
         
         // Get allocation with proper mutex handling
         let llvm_alloca = self.search_store_table(assignee_name.clone());
@@ -89,10 +106,50 @@ impl IRGenerator {
         let resource_pools = self.get_resource_pools();
         let mut resource_pools = resource_pools.lock().expect("Failed to lock mutex in assignment!");
         
-        resource_pools.reassign_var(self.get_builder(), llvm_alloca, llvm_value)
+        let store = resource_pools.reassign_var(self.get_builder(), llvm_alloca, llvm_value)
             .ok_or_else(|| ErrorType::DevError { message: "Failed to reassign variable".to_string() })?;
+        drop(resource_pools);
+        self.apply_volatile(&assignee_name, store)?;
 
-        Ok(None)
+        Ok(Some(Tag::Value(llvm_value)))
+    }
+
+    /// Generates LLVM IR for a compound assignment such as `x += 5`, which is generated as the assignment
+    /// `x = x + 5`.
+    /// 
+    /// # Parameters
+    ///
+    /// - `node`: A reference to a `CompoundAssignment` `ASTNode`, whose children are the target, the operator, and
+    /// the value.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Option<Tag>, ErrorType>` containing the Tag of the value stored
+    /// if generation went smoothly or an Error if there was a problem generating the compound assignment.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if the node is malformed, or if generation of the operation or the store failed.
+    pub fn generate_compound_assignment_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
+        let children = node.get_children();
+        let (target, operator, value) = match children.as_slice() {
+            [target, operator, value] => (target, operator, value),
+            _ => return Err(ErrorType::DevError { message: "Invalid compound assignment node".to_string() }),
+        };
+        let operator = match operator.get_node_type() {
+            NodeType::Operator(op) if op.len() > 1 && op.ends_with('=') => op[..op.len() - 1].to_string(),
+            _ => return Err(ErrorType::DevError { message: "Expected compound assignment operator".to_string() }),
+        };
+
+        let mut operation = ASTNode::new(NodeType::BinaryExpression);
+        operation.add_child(target.clone());
+        operation.add_child(ASTNode::new(NodeType::Operator(operator)));
+        operation.add_child(value.clone());
+        let mut assignment = ASTNode::new(NodeType::Assignment);
+        assignment.add_child(target.clone());
+        assignment.add_child(operation);
+        self.generate_assignment_ir(&assignment, ctx)
     }
 
     /// Generates LLVM IR for a variable initialization.
@@ -100,6 +157,7 @@ impl IRGenerator {
     /// # Parameters
     ///
     /// - `node`: A reference to an `ASTNode` to generate IR for a variable initialization.
+    /// - `ctx`: The context of the function being generated.
     ///
     /// # Returns
     ///
@@ -114,10 +172,10 @@ impl IRGenerator {
     /// 
     /// ```
     /// //let a_node: ASTNode = /* Some ASTNode we want to generate an initialization from */
-    /// //let result = self.generate_initialization_ir(&a_node);
+    /// //let result = self.generate_initialization_ir(&a_node, &mut ctx);
     /// /* check if type_result was Ok or Err, if Ok, it will contain None. */
     /// ```
-    pub fn generate_initialization_ir(&mut self, node: &ASTNode) -> Result<Option<Tag>, ErrorType> {
+    pub fn generate_initialization_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
         let children = node.get_children();
         if children.len() < 2 || children.len() > 3 {
             return Err(ErrorType::DevError { 
@@ -141,67 +199,34 @@ impl IRGenerator {
                 message: "Expected identifier or variable node".to_string() 
             })
         };
+        let mutable = !Self::declares_const(&children[0]);
+        let volatile = Self::declares_volatile(&children[0]);
+        let span = Self::declared_name_span(&children[0]);
 
-        // Process type node or infer type from initial value
-        let (type_tag, init_value_node_opt) = if children.len() == 3 {
-            // 3 children: [var, type, value]
-            let type_node = &children[1];
-            let init_value_node = &children[2];
-            let type_tag = match type_node.get_node_type() {
-                NodeType::Type(data_type) => {
-                    let resource_pools = self.get_resource_pools();
-                    let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in type processing!");
-                    match data_type {
-                        DataType::Integer => resource_pools.int_type(self.get_context(), 64)
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer type".to_string() })?,
-                        DataType::Float => resource_pools.float_type(self.get_context())
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to create float type".to_string() })?,
-                        DataType::Boolean => resource_pools.boolean_type(self.get_context())
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to create boolean type".to_string() })?,
-                        DataType::Void => resource_pools.void_type(self.get_context())
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to create void type".to_string() })?,
-                        _ => return Err(ErrorType::DevError { message: format!("Unsupported data type: {:?}", data_type) })
-                    }
-                },
-                _ => {
-                    let resource_pools = self.get_resource_pools();
-                    let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in type processing!");
-                    resource_pools.int_type(self.get_context(), 64)
-                        .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer type".to_string() })?
-                }
-            };
-            (type_tag, Some(init_value_node))
-        } else if children.len() == 2 {
-            // 2 children: [var, value] or [var, type]
-            match children[1].get_node_type() {
-                NodeType::Type(data_type) => {
-                    let resource_pools = self.get_resource_pools();
-                    let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in type processing!");
-                    let type_tag = match data_type {
-                        DataType::Integer => resource_pools.int_type(self.get_context(), 64)
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer type".to_string() })?,
-                        DataType::Float => resource_pools.float_type(self.get_context())
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to create float type".to_string() })?,
-                        DataType::Boolean => resource_pools.boolean_type(self.get_context())
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to create boolean type".to_string() })?,
-                        DataType::Void => resource_pools.void_type(self.get_context())
-                            .ok_or_else(|| ErrorType::DevError { message: "Failed to create void type".to_string() })?,
-                        _ => return Err(ErrorType::DevError { message: format!("Unsupported data type: {:?}", data_type) })
-                    };
-                    (type_tag, None)
-                },
-                _ => {
-                    // If not a type, treat as initial value and default to i64
-                    let resource_pools = self.get_resource_pools();
-                    let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in type processing!");
-                    let type_tag = resource_pools.int_type(self.get_context(), 64)
-                        .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer type".to_string() })?;
-                    (type_tag, Some(&children[1]))
-                }
-            }
-        } else {
-            return Err(ErrorType::DevError { message: "Invalid variable initialization node: expected 2 or 3 children".to_string() });
-        };
+        // Struct variables are allocated with their struct type, which later loads and by-value arguments need
+        if let Some(struct_name) = Self::struct_type_name(&children[0]) {
+            return self.generate_struct_initialization_ir(var_name, &children[0], &struct_name, ctx);
+        }
+
+        // Function pointers carry their function type, which later calls through the variable need
+        if let Some(fn_type_node) = Self::function_pointer_type_node(&children[0]) {
+            let init_value_node = children.iter().skip(1).find(|c| !matches!(c.get_node_type(), NodeType::Type(_)));
+            return self.generate_function_pointer_initialization_ir(var_name, &fn_type_node, init_value_node, &children[0], ctx);
+        }
+
+        // The initial value is the child after the variable that is not its type
+        let init_value_node_opt = children.iter().skip(1).find(|c| !matches!(c.get_node_type(), NodeType::Type(_)));
+        let data_type = children.iter().skip(1)
+            .find_map(|child| match child.get_node_type() {
+                NodeType::Type(data_type) => Some(data_type),
+                _ => None,
+            })
+            .or_else(|| Self::declared_data_type(&children[0]));
+
+        // Variables are stored as their declared type, which loads of them find in their store entry. A `bool` is an
+        // `i1`, whose initial value is converted by comparing it against zero.
+        let type_tag = self.storage_type_ir(data_type)?;
+        let declares_boolean = data_type == Some(DataType::Boolean);
 
         // Helper: recursively check for AssignedValue -> Variable
         fn get_variable_node_from_assigned_value(node: &ASTNode) -> Option<ASTNode> {
@@ -232,28 +257,42 @@ impl IRGenerator {
                     NodeType::Identifier(ref n) => n.clone(),
                     _ => return Err(ErrorType::DevError { message: "Expected identifier in variable node".to_string() })
                 };
-                let src_alloca = self.search_store_table(src_var_name);
+                let src_alloca = self.search_store_table(src_var_name.clone());
+                let src_type = self.search_declared_type(&src_var_name);
+                let load_type = self.storage_type_ir(src_type)?;
                 let resource_pools = self.get_resource_pools();
                 let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in variable recall!");
-                let type_tag = resource_pools.int_type(self.get_context(), 64)
-                    .ok_or_else(|| ErrorType::DevError { message: "Failed to create i64 type".to_string() })?;
-                let loaded = resource_pools.get_var(self.get_builder(), type_tag, src_alloca, "vrecallID1")
+                let mut loaded = resource_pools.get_var(self.get_builder(), load_type, src_alloca, "vrecallID1")
                     .ok_or_else(|| ErrorType::DevError { message: "Failed to load variable".to_string() })?;
                 drop(resource_pools);
+                self.apply_volatile(&src_var_name, loaded.clone())?;
+
+                // A variable without a declared type takes the type of the variable it is initialized from
+                let (data_type, alloca_type) = match data_type {
+                    Some(_) => (data_type, type_tag),
+                    None => (src_type, load_type),
+                };
+                if declares_boolean {
+                    loaded = self.lower_condition_value(&var_node, loaded)?;
+                } else if let (Some(from), Some(to)) = (src_type, data_type) {
+                    loaded = self.convert_value_ir(loaded, from, to)?;
+                }
 
                 // 2. THEN alloca for the new variable
                 let resource_pools = self.get_resource_pools();
                 let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in initialization!");
-                let alloca = resource_pools.init_var(self.get_builder(), &var_name, type_tag, None)
+                let alloca = resource_pools.init_var(self.get_alloca_builder(ctx), &var_name, alloca_type, None)
                     .ok_or_else(|| ErrorType::DevError { message: "Failed to initialize variable".to_string() })?;
                 drop(resource_pools);
-                self.add_tag_to_store_table(var_name.clone(), alloca);
+                self.add_tag_to_store_table(var_name.clone(), alloca, data_type, mutable, volatile, span);
 
                 // 3. FINALLY store the loaded value
                 let resource_pools = self.get_resource_pools();
                 let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in initialization store!");
-                resource_pools.reassign_var(self.get_builder(), alloca, loaded)
+                let store = resource_pools.reassign_var(self.get_builder(), alloca, loaded)
                     .ok_or_else(|| ErrorType::DevError { message: "Failed to store initial value".to_string() })?;
+                drop(resource_pools);
+                self.apply_volatile(&var_name, store)?;
                 return Ok(None);
             }
         }
@@ -261,23 +300,30 @@ impl IRGenerator {
         // Default case: alloca, then store (if any)
         let resource_pools = self.get_resource_pools();
         let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in initialization!");
-        let alloca = resource_pools.init_var(self.get_builder(), &var_name, type_tag, None)
+        let alloca = resource_pools.init_var(self.get_alloca_builder(ctx), &var_name, type_tag, None)
             .ok_or_else(|| ErrorType::DevError { message: "Failed to initialize variable".to_string() })?;
         drop(resource_pools);
-        self.add_tag_to_store_table(var_name.clone(), alloca);
+        self.add_tag_to_store_table(var_name.clone(), alloca, data_type, mutable, volatile, span);
 
         // If there is an initial value, emit a store
         if let Some(init_value_node) = init_value_node_opt {
-            let llvm_value = self.ir_router(init_value_node)?
+            let llvm_value = self.ir_router(init_value_node, ctx)?
                 .ok_or_else(|| ErrorType::DevError { message: "Failed to generate initial value".to_string() })?;
-            let store_value = match llvm_value {
+            let mut store_value = match llvm_value {
                 Tag::Value(value_tag) => value_tag,
                 _ => return Err(ErrorType::DevError { message: "Expected value tag from initial value node".to_string() })
             };
+            if declares_boolean {
+                store_value = self.lower_condition_value(init_value_node, store_value)?;
+            } else if let (Some(from), Some(to)) = (self.value_data_type(init_value_node), data_type) {
+                store_value = self.convert_value_ir(store_value, from, to)?;
+            }
             let resource_pools = self.get_resource_pools();
             let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in initialization store!");
-            resource_pools.reassign_var(self.get_builder(), alloca, store_value)
+            let store = resource_pools.reassign_var(self.get_builder(), alloca, store_value)
                 .ok_or_else(|| ErrorType::DevError { message: "Failed to store initial value".to_string() })?;
+            drop(resource_pools);
+            self.apply_volatile(&var_name, store)?;
         }
 
         Ok(None)
@@ -288,6 +334,7 @@ impl IRGenerator {
     /// # Parameters
     ///
     /// - `node`: A reference to an `ASTNode` to generate IR for a break statement.
+    /// - `ctx`: The context of the function being generated.
     ///
     /// # Returns
     ///
@@ -302,11 +349,11 @@ impl IRGenerator {
     /// 
     /// ```
     /// //let a_node: ASTNode = /* Some ASTNode we want to generate a break from */
-    /// //let result = self.generate_break_ir(&a_node);
+    /// //let result = self.generate_break_ir(&a_node, &mut ctx);
     /// /* check if type_result was Ok or Err, if Ok, it will contain None. */
     /// ```
-    pub fn generate_break_ir(&mut self, _node: &ASTNode) -> Result<Option<Tag>, ErrorType> {
-        let targets = self.get_break_continue_target()
+    pub fn generate_break_ir(&mut self, _node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
+        let targets = ctx.get_break_continue_target()
             .ok_or_else(|| ErrorType::DevError { message: "No break/continue targets available".to_string() })?;
         
         let break_target = targets.get(0)
@@ -327,6 +374,7 @@ impl IRGenerator {
     /// # Parameters
     ///
     /// - `node`: A reference to an `ASTNode` to generate IR for a continue statement.
+    /// - `ctx`: The context of the function being generated.
     ///
     /// # Returns
     ///
@@ -341,16 +389,13 @@ impl IRGenerator {
     /// 
     /// ```
     /// //let a_node: ASTNode = /* Some ASTNode we want to generate a continue from */
-    /// //let result = self.generate_continue_ir(&a_node);
+    /// //let result = self.generate_continue_ir(&a_node, &mut ctx);
     /// /* check if type_result was Ok or Err, if Ok, it will contain None. */
     /// ```
-    pub fn generate_continue_ir(&mut self, _node: &ASTNode) -> Result<Option<Tag>, ErrorType> {
-        let targets = self.get_break_continue_target()
-            .ok_or_else(|| ErrorType::DevError { message: "No break/continue targets available".to_string() })?;
-        
-        let continue_target = targets.get(1)
-            .ok_or_else(|| ErrorType::DevError { message: "No continue target available".to_string() })?
-            .clone();
+    pub fn generate_continue_ir(&mut self, _node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
+        // A switch only takes break, so a continue inside one goes to the loop around it
+        let continue_target = ctx.get_continue_target()
+            .ok_or_else(|| ErrorType::DevError { message: "No continue target available".to_string() })?;
         
         let resource_pools = self.get_resource_pools();
         let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in continue!");
@@ -366,6 +411,7 @@ impl IRGenerator {
     /// # Parameters
     ///
     /// - `node`: A reference to an `ASTNode` to generate IR for a return statement.
+    /// - `ctx`: The context of the function being generated.
     ///
     /// # Returns
     ///
@@ -375,7 +421,7 @@ impl IRGenerator {
     /// # Errors
     ///
     /// - Returns an ErrorType if generation failed.
-    pub fn generate_return_ir(&mut self, node: &ASTNode) -> Result<Option<Tag>, ErrorType> {
+    pub fn generate_return_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
         let resource_pools = self.get_resource_pools();
         let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in return!");
 
@@ -384,11 +430,12 @@ impl IRGenerator {
             // Release lock before calling self methods
             drop(resource_pools);
             
-            let value_ptr = self.ir_router(&children[0])?;
-            let value_ptr = value_ptr.expect("Missing return value");
-            let llvm_value = match value_ptr {
-                Tag::Value(value) => value,
-                _ => return Err(ErrorType::DevError { message: "Expected value tag".to_string() })
+            let llvm_value = self.generate_operand_ir(&children[0], ctx)?;
+
+            // The returned value takes the declared return type of the function
+            let llvm_value = match (self.value_data_type(&children[0]), ctx.get_function_return_type()) {
+                (Some(from), Some(to)) => self.convert_value_ir(llvm_value, from, to)?,
+                _ => llvm_value,
             };
             
             // Re-acquire lock
@@ -432,20 +479,1342 @@ impl IRGenerator {
             _ => return Err(ErrorType::DevError { message: "Expected variable node".to_string() })
         };
 
+        // An enum constant is promoted to an integer wherever it is used, as in C
+        if let Some((value, _)) = self.search_enum_constant(&name) {
+            let resource_pools = self.get_resource_pools();
+            let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in variable recall!");
+            let constant = resource_pools.create_integer(self.get_context(), value)
+                .ok_or_else(|| ErrorType::DevError { message: format!("Failed to create enum constant {}", name) })?;
+            return Ok(Some(Tag::Value(constant)));
+        }
+
         let llvm_alloca = self.search_store_table(name.clone());
-        
+
+        // Variables are loaded as the type they are stored as, which follows from the type their scoped store entry
+        // was declared with, or the struct type of a struct variable
+        let type_tag = match self.search_struct_variable_type(&name) {
+            Some(struct_type) => struct_type,
+            None => {
+                let data_type = self.search_declared_type(&name);
+                self.storage_type_ir(data_type)?
+            },
+        };
+        self.load_variable_ir(&name, llvm_alloca, type_tag)
+    }
+
+    /// Loads the value of a variable from its allocation.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the variable.
+    /// - `llvm_alloca`: The `ValueTag` of the variable's allocation.
+    /// - `type_tag`: The `TypeTag` of the value to load.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Option<Tag>, ErrorType>` containing the Tag of the loaded value.
+    fn load_variable_ir(&mut self, name: &str, llvm_alloca: ValueTag, type_tag: TypeTag) -> Result<Option<Tag>, ErrorType> {
         let resource_pools = self.get_resource_pools();
         let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in variable recall!");
 
-        // For now, assume i64 type for variables
-        // TODO: Get actual type from symbol table or node metadata
-        let type_tag = resource_pools.int_type(self.get_context(), 64)
-            .ok_or_else(|| ErrorType::DevError { message: "Failed to create i64 type".to_string() })?;
-
         // Load the value from the variable
         let load = resource_pools.get_var(self.get_builder(), type_tag, llvm_alloca, "vrecallID1")
             .ok_or_else(|| ErrorType::DevError { message: "Failed to load variable".to_string() })?;
+        drop(resource_pools);
+        self.apply_volatile(name, load.clone())?;
 
         Ok(Some(Tag::Value(load)))
     }
-}
\ No newline at end of file
+
+    /// Generates LLVM IR for a condition, lowering the value of its expression to an `i1`.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to a `Condition` `ASTNode` to generate IR for.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Option<Tag>, ErrorType>` containing the Tag of the `i1` condition value
+    /// if generation went smoothly or an Error if there was a problem generating the condition.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if generation failed.
+    pub fn generate_condition_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
+        let child = node.get_children().first().cloned()
+            .ok_or_else(|| ErrorType::DevError { message: "Condition node has no children".to_string() })?;
+
+        let value = match self.ir_router(&child, ctx)? {
+            Some(Tag::Value(value)) => value,
+            _ => return Err(ErrorType::DevError { message: "Expected value tag".to_string() })
+        };
+
+        let bool_value = self.lower_condition_value(&child, value)?;
+        Ok(Some(Tag::Value(bool_value)))
+    }
+
+    /// Lowers an already generated value to an `i1` truth value according to the type of the expression
+    /// it came from: integers are compared against a zero of their own width, floats use `fcmp one 0.0`, strings
+    /// and function pointers are checked against null and values that are already `i1` are used directly.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to the expression `ASTNode` that produced `value`.
+    /// - `value`: The `ValueTag` of the generated expression.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<ValueTag, ErrorType>` containing the `i1` value or an Error if the comparison
+    /// could not be built.
+    pub fn lower_condition_value(&mut self, node: &ASTNode, value: ValueTag) -> Result<ValueTag, ErrorType> {
+        let operand_type = self.get_operand_type(node);
+
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in condition!");
+
+        match operand_type {
+            DataType::Boolean => Ok(value),
+            DataType::Float | DataType::Double => {
+                let zero = resource_pools.create_float(self.get_context(), 0.0)
+                    .ok_or_else(|| ErrorType::DevError { message: "Failed to create float constant".to_string() })?;
+                drop(resource_pools);
+                // Floating-point constants are doubles, so a float is compared against a float zero
+                let zero = self.convert_value_ir(zero, DataType::Double, operand_type)?;
+                let resource_pools = self.get_resource_pools();
+                let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in condition!");
+                resource_pools.build_fcmp_one(self.get_builder(), value, zero, "fcmptmp")
+                    .ok_or_else(|| ErrorType::DevError { message: "Failed to create float comparison".to_string() })
+            },
+            DataType::String | DataType::Function => {
+                resource_pools.build_is_not_null(self.get_builder(), value, "notnulltmp")
+                    .ok_or_else(|| ErrorType::DevError { message: "Failed to create null check".to_string() })
+            },
+            _ => {
+                let zero = resource_pools.create_integer(self.get_context(), 0)
+                    .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer constant".to_string() })?;
+                drop(resource_pools);
+                // Integer constants are `i64`, so a narrower integer is compared against a zero truncated to its width
+                let zero = self.convert_value_ir(zero, DataType::Integer, operand_type)?;
+                let resource_pools = self.get_resource_pools();
+                let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in condition!");
+                let eq = resource_pools.build_icmp_eq(self.get_builder(), value, zero, "cmptmp")
+                    .ok_or_else(|| ErrorType::DevError { message: "Failed to create comparison".to_string() })?;
+                resource_pools.build_logical_not(self.get_builder(), self.get_context(), eq, "nottmp")
+                    .ok_or_else(|| ErrorType::DevError { message: "Failed to create logical not".to_string() })
+            }
+        }
+    }
+
+    /// Determines the type of the value an expression node generates, as far as condition lowering is concerned.
+    /// Comparisons, logical operators and nested conditions yield `Boolean` (an `i1`), while boolean literals are
+    /// `i64` constants and therefore reported as `Integer`. A variable has the type its scoped store entry was
+    /// declared with, and a function named as a value is a `Function` pointer.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to the expression `ASTNode`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `DataType` of the generated value, defaulting to `Integer` when it cannot be determined.
+    fn get_operand_type(&self, node: &ASTNode) -> DataType {
+        match node.get_node_type() {
+            NodeType::Condition => DataType::Boolean,
+            NodeType::Literal(value) => {
+                if value.starts_with('"') {
+                    DataType::String
+                } else if float_literal_value(&value).is_some() {
+                    // Floating-point literals are generated as double constants
+                    DataType::Double
+                } else {
+                    DataType::Integer
+                }
+            },
+            NodeType::UnaryExpression => {
+                let children = node.get_children();
+                match children.first().map(|c| c.get_node_type()) {
+                    Some(NodeType::Operator(op)) if op == "!" => DataType::Boolean,
+                    _ => children.get(1).map_or(DataType::Integer, |operand| self.get_operand_type(operand)),
+                }
+            },
+            NodeType::BinaryExpression => {
+                let children = node.get_children();
+                match children.get(1).map(|c| c.get_node_type()) {
+                    Some(NodeType::Operator(op)) if matches!(op.as_str(), "==" | "!=" | "<" | ">" | "<=" | ">=" | "&&" | "||") => DataType::Boolean,
+                    _ => children.first().map_or(DataType::Integer, |left| self.get_operand_type(left)),
+                }
+            },
+            NodeType::AssignedValue => node.get_children().first().map_or(DataType::Integer, |value| self.get_operand_type(value)),
+            // An assignment has the value, and so the type, of its target after the store
+            NodeType::Assignment | NodeType::CompoundAssignment => node.get_children().first()
+                .map_or(DataType::Integer, |target| self.get_operand_type(target)),
+            NodeType::Identifier(_) | NodeType::Variable => {
+                let name = match node.get_node_type() {
+                    NodeType::Identifier(name) => name,
+                    _ => match node.get_children().first().map(|c| c.get_node_type()) {
+                        Some(NodeType::Identifier(name)) => name,
+                        _ => return DataType::Integer,
+                    },
+                };
+                // Variables are stored as the type they were declared with, which shadows any function of the name
+                if let Some(entry) = self.search_store_entry(&name) {
+                    return entry.data_type.unwrap_or(DataType::Integer);
+                }
+                if self.search_enum_constant(&name).is_some() {
+                    return DataType::Integer;
+                }
+                if self.search_function_table(&name).is_some() {
+                    return DataType::Function;
+                }
+                match self.lookup_symbol_type(&name) {
+                    Some(data_type @ (DataType::Float | DataType::Double | DataType::String)) => data_type,
+                    _ => DataType::Integer,
+                }
+            },
+            _ => DataType::Integer,
+        }
+    }
+
+    /// Looks up the data type of a symbol, searching from the current scope of the symbol table stack outwards.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the symbol.
+    ///
+    /// # Returns
+    ///
+    /// Returns the symbol's `DataType`, or None if there is no symbol table stack or the symbol is not found.
+    pub fn lookup_symbol_type(&self, name: &str) -> Option<DataType> {
+        let sts = self.get_stack()?;
+        let top = self.get_stack_pointer().min(sts.size().checked_sub(1)?);
+        (0..=top).rev()
+            .filter_map(|index| sts.get_element(index).ok())
+            .find_map(|table| table.get(name).map(|info| info.get_data_type()))
+    }
+
+    /// Checks whether a declared variable node carries a `volatile` qualifier on its type.
+    ///
+    /// # Parameters
+    ///
+    /// - `var_node`: A reference to the `Variable` `ASTNode` of a declaration.
+    ///
+    /// # Returns
+    ///
+    /// Returns true if the variable's `Type` node has a `Qualifier("volatile")` child.
+    pub fn declares_volatile(var_node: &ASTNode) -> bool {
+        var_node.get_children().iter()
+            .filter(|child| matches!(child.get_node_type(), NodeType::Type(_)))
+            .flat_map(|type_node| type_node.get_children())
+            .any(|qualifier| qualifier.get_node_type() == NodeType::Qualifier("volatile".to_string()))
+    }
+
+    /// Checks whether a declared variable node carries a `const` qualifier on its type.
+    ///
+    /// # Parameters
+    ///
+    /// - `var_node`: A reference to the `Variable` `ASTNode` of a declaration.
+    ///
+    /// # Returns
+    ///
+    /// Returns true if the variable's `Type` node has a `Qualifier("const")` child.
+    pub fn declares_const(var_node: &ASTNode) -> bool {
+        var_node.get_children().iter()
+            .filter(|child| matches!(child.get_node_type(), NodeType::Type(_)))
+            .flat_map(|type_node| type_node.get_children())
+            .any(|qualifier| qualifier.get_node_type() == NodeType::Qualifier("const".to_string()))
+    }
+
+    /// Marks a load or store instruction volatile if the variable it accesses was declared `volatile`.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the variable being accessed.
+    /// - `instruction`: The `ValueTag` of the load or store instruction.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if the instruction could not be marked volatile.
+    pub fn apply_volatile(&mut self, name: &str, instruction: ValueTag) -> Result<(), ErrorType> {
+        if !self.is_volatile_var(name) {
+            return Ok(());
+        }
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in volatile!");
+        resource_pools.set_volatile(instruction, true)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to mark instruction volatile".to_string() })?;
+        Ok(())
+    }
+
+    /// Finds the `Type(Function)` node of a declared variable, if the variable is a function pointer.
+    ///
+    /// # Parameters
+    ///
+    /// - `var_node`: A reference to the `Variable` `ASTNode` of a declaration.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `Type(Function)` node, or None if the variable is not a function pointer.
+    fn function_pointer_type_node(var_node: &ASTNode) -> Option<ASTNode> {
+        var_node.get_children().into_iter()
+            .find(|child| child.get_node_type() == NodeType::Type(DataType::Function))
+    }
+
+    /// Generates LLVM IR for the initialization of a function pointer variable, allocating a pointer and
+    /// storing the address of the initial function in it.
+    ///
+    /// # Parameters
+    ///
+    /// - `var_name`: The name of the function pointer variable.
+    /// - `fn_type_node`: A reference to the variable's `Type(Function)` `ASTNode`.
+    /// - `init_value_node`: The `AssignedValue` node holding the initial function, if there is one.
+    /// - `var_node`: A reference to the declared `Variable` `ASTNode`, whose qualifiers and name are recorded in the store.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Option<Tag>, ErrorType>` containing None if generation went smoothly or an Error
+    /// if there was a problem generating the initialization.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if generation failed.
+    fn generate_function_pointer_initialization_ir(&mut self, var_name: String, fn_type_node: &ASTNode, init_value_node: Option<&ASTNode>, var_node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
+        let fn_type = self.generate_function_type_ir(fn_type_node)?;
+        // The first type is the return type, and the rest are the parameter types
+        let param_types = fn_type_node.get_children().into_iter().skip(1)
+            .filter_map(|child| match child.get_node_type() {
+                NodeType::Type(data_type) => Some(data_type),
+                _ => None,
+            })
+            .collect();
+
+        let init_value = match init_value_node {
+            Some(init_value_node) => Some(self.generate_function_value_ir(init_value_node, ctx)?),
+            None => None,
+        };
+
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in function pointer initialization!");
+        let ptr_type = resource_pools.pointer_type(self.get_context())
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to create pointer type".to_string() })?;
+        let alloca = resource_pools.init_var(self.get_alloca_builder(ctx), &var_name, ptr_type, None)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to initialize variable".to_string() })?;
+        drop(resource_pools);
+        self.add_tag_to_store_table(var_name.clone(), alloca.clone(), Some(DataType::Function), !Self::declares_const(var_node), Self::declares_volatile(var_node), Self::declared_name_span(var_node));
+        self.add_function_pointer_type(var_name.clone(), fn_type, param_types);
+
+        if let Some(init_value) = init_value {
+            let resource_pools = self.get_resource_pools();
+            let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in function pointer store!");
+            let store = resource_pools.reassign_var(self.get_builder(), alloca, init_value)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to store initial value".to_string() })?;
+            drop(resource_pools);
+            self.apply_volatile(&var_name, store)?;
+        }
+
+        Ok(None)
+    }
+
+    /// Finds the name of a variable's struct type, from a `Variable` node whose children are its identifier and
+    /// a `Type(Struct)` node naming the struct.
+    ///
+    /// # Parameters
+    ///
+    /// - `var_node`: A reference to the `Variable` `ASTNode`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the struct's name, or None if the variable is not a struct.
+    fn struct_type_name(var_node: &ASTNode) -> Option<String> {
+        let type_node = var_node.get_children().into_iter()
+            .find(|child| child.get_node_type() == NodeType::Type(DataType::Struct))?;
+        match type_node.get_children().first().map(|c| c.get_node_type()) {
+            Some(NodeType::Identifier(name)) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Finds the declared data type of a variable from the `Type` child of its `Variable` node.
+    ///
+    /// # Parameters
+    ///
+    /// - `var_node`: A reference to the `Variable` `ASTNode`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the declared `DataType`, or None if the node does not carry a type.
+    pub(crate) fn declared_data_type(var_node: &ASTNode) -> Option<DataType> {
+        var_node.get_children().into_iter().find_map(|child| match child.get_node_type() {
+            NodeType::Type(data_type) => Some(data_type),
+            _ => None,
+        })
+    }
+
+    /// Finds the source of the declared name from the `Identifier` child of a `Variable` or `Parameter` node, or from
+    /// the `Identifier` node itself.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to the declared `Variable`, `Parameter`, or `Identifier` `ASTNode`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the span of the name, or None if the node was not parsed with source ranges.
+    pub(crate) fn declared_name_span(node: &ASTNode) -> Option<Span> {
+        match node.get_node_type() {
+            NodeType::Identifier(_) => node.get_span(),
+            _ => node.get_children().first().and_then(|name| name.get_span()),
+        }
+    }
+
+    /// Generates LLVM IR for the declaration of a struct variable, allocating storage of its struct type.
+    ///
+    /// # Parameters
+    ///
+    /// - `var_name`: The name of the struct variable.
+    /// - `var_node`: A reference to the declared `Variable` `ASTNode`, whose qualifiers and name are recorded in the store.
+    /// - `struct_name`: The name of the variable's struct type.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Option<Tag>, ErrorType>` containing None if generation went smoothly or an Error
+    /// if there was a problem generating the declaration.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if the struct was not declared or generation failed.
+    fn generate_struct_initialization_ir(&mut self, var_name: String, var_node: &ASTNode, struct_name: &str, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
+        let struct_type = self.search_struct_type(struct_name)
+            .ok_or_else(|| ErrorType::DevError { message: format!("Struct not found: {}", struct_name) })?;
+
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in struct initialization!");
+        let alloca = resource_pools.init_var(self.get_alloca_builder(ctx), &var_name, struct_type, None)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to initialize variable".to_string() })?;
+        drop(resource_pools);
+        self.add_tag_to_store_table(var_name.clone(), alloca, Some(DataType::Struct), !Self::declares_const(var_node), Self::declares_volatile(var_node), Self::declared_name_span(var_node));
+        self.add_struct_variable_type(var_name, struct_type);
+
+        Ok(None)
+    }
+
+    /// Generates LLVM IR for a struct declaration, creating a named struct type with one element per member and
+    /// recording its layout, in which the fields of anonymous members are found as fields of the struct. A `@packed`
+    /// struct is generated as an unnamed packed struct type, `<{ ... }>`, whose fields have no padding between them.
+    /// LLVM struct types have no alignment of their own, so a struct whose alignment is raised by `@align(N)` ends in
+    /// `i8` padding up to a multiple of N bytes, giving it the size it has in C.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to a `StructDeclaration` `ASTNode`, whose children are the struct's identifier, its
+    /// `Field` nodes, its anonymous `StructDeclaration` and `UnionDeclaration` members and its `Attribute` nodes.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Option<Tag>, ErrorType>` containing the Tag of the struct type.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if a field has an unsupported type, `@align` is not given a power of two, or
+    /// generation failed.
+    pub fn generate_struct_declaration_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
+        let children = node.get_children();
+        let name = match children.first().map(|c| c.get_node_type()) {
+            Some(NodeType::Identifier(name)) => name,
+            _ => return Err(ErrorType::DevError { message: "Expected identifier in struct declaration".to_string() })
+        };
+
+        let mut packed = false;
+        let mut min_align = None;
+        for attribute in children.iter() {
+            match attribute.get_node_type() {
+                NodeType::Attribute(attribute_name) if attribute_name == "packed" => packed = true,
+                NodeType::Attribute(attribute_name) if attribute_name == "align" => {
+                    let align = match attribute.get_children().first().map(|c| c.get_node_type()) {
+                        Some(NodeType::Literal(value)) => value.parse::<u64>().ok().filter(|align| align.is_power_of_two()),
+                        _ => None,
+                    };
+                    min_align = Some(align.ok_or_else(|| ErrorType::DevError {
+                        message: format!("`@align` of struct {} must be given a power of two", name)
+                    })?);
+                },
+                _ => {}
+            }
+        }
+
+        let (mut field_types, members) = self.generate_member_types_ir(node, ctx)?;
+
+        let layout = StructLayout::new(&members, packed, min_align)
+            .ok_or_else(|| ErrorType::DevError { message: format!("Cannot lay out the fields of struct {}", name) })?;
+        let natural_size = StructLayout::new(&members, packed, None).map_or(layout.size, |natural| natural.size);
+
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in struct declaration!");
+        if layout.size > natural_size {
+            let byte_type = resource_pools.int_type(self.get_context(), 8)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to create padding type".to_string() })?;
+            field_types.extend((natural_size..layout.size).map(|_| byte_type.clone()));
+        }
+        let struct_type = if packed {
+            resource_pools.struct_type(self.get_context(), &field_types, true)
+        } else {
+            resource_pools.create_struct_type(self.get_context(), &name, &field_types)
+        }.ok_or_else(|| ErrorType::DevError { message: "Failed to create struct type".to_string() })?;
+        drop(resource_pools);
+        self.add_struct_type(name.clone(), struct_type.clone());
+        self.add_struct_layout(name, layout);
+
+        Ok(Some(Tag::Type(struct_type)))
+    }
+
+    /// Records the constants of an enum declaration, numbered from 0 in declaration order. An enum has no LLVM type
+    /// of its own: variables and fields of the enum have its underlying type, which the parser gives them.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to an `EnumDeclaration` `ASTNode`, whose children are the enum's identifier, an optional
+    /// `Type` node with its underlying type and its `Variant` nodes.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Option<Tag>, ErrorType>` containing None, as the declaration generates no IR.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if a variant has no name.
+    pub fn generate_enum_declaration_ir(&mut self, node: &ASTNode) -> Result<Option<Tag>, ErrorType> {
+        let children = node.get_children();
+        let underlying = children.iter().find_map(|child| match child.get_node_type() {
+            NodeType::Type(data_type) => Some(data_type),
+            _ => None,
+        }).unwrap_or(DataType::Integer);
+        let variants = children.iter().filter(|child| child.get_node_type() == NodeType::Variant);
+        for (value, variant) in variants.enumerate() {
+            match variant.get_children().first().map(|c| c.get_node_type()) {
+                Some(NodeType::Identifier(name)) => self.add_enum_constant(name, value as i64, underlying),
+                _ => return Err(ErrorType::DevError { message: "Expected identifier in enum variant".to_string() })
+            }
+        }
+        Ok(None)
+    }
+
+    /// Generates the LLVM types of the members of a struct or union. An anonymous struct member is generated as an
+    /// unnamed struct type. LLVM has no union types, so an anonymous union member is generated as an unnamed struct
+    /// holding its most aligned member followed by `i8` padding up to the size of the union; its fields are accessed
+    /// through their offsets in the layout.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to the `StructDeclaration` or `UnionDeclaration` `ASTNode` whose members to generate.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `TypeTag` of each member and the `Member` each one is laid out as.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if a field has an unsupported type or generation failed.
+    fn generate_member_types_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<(Vec<TypeTag>, Vec<Member>), ErrorType> {
+        let mut types = Vec::new();
+        let mut members = Vec::new();
+        for child in node.get_children().iter() {
+            match child.get_node_type() {
+                NodeType::Field => {
+                    let field_name = match child.get_children().first().map(|c| c.get_node_type()) {
+                        Some(NodeType::Literal(field_name)) => field_name,
+                        _ => return Err(ErrorType::DevError { message: "Field node has no name".to_string() })
+                    };
+                    let type_node = child.get_children().get(1).cloned()
+                        .ok_or_else(|| ErrorType::DevError { message: "Field node has no type".to_string() })?;
+                    if let NodeType::Type(data_type) = type_node.get_node_type() {
+                        members.push(Member::Field(field_name, data_type));
+                    }
+                    match self.ir_router(&type_node, ctx)? {
+                        Some(Tag::Type(field_type)) => types.push(field_type),
+                        _ => return Err(ErrorType::DevError { message: "Expected type tag for field".to_string() })
+                    }
+                },
+                NodeType::StructDeclaration => {
+                    let (nested_types, nested_members) = self.generate_member_types_ir(child, ctx)?;
+                    let resource_pools = self.get_resource_pools();
+                    let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in struct declaration!");
+                    let nested_type = resource_pools.struct_type(self.get_context(), &nested_types, false)
+                        .ok_or_else(|| ErrorType::DevError { message: "Failed to create struct type".to_string() })?;
+                    types.push(nested_type);
+                    members.push(Member::Struct(nested_members));
+                },
+                NodeType::UnionDeclaration => {
+                    let (nested_types, nested_members) = self.generate_member_types_ir(child, ctx)?;
+                    let union = Member::Union(nested_members.clone());
+                    let (size, _) = union.size_and_align()
+                        .ok_or_else(|| ErrorType::DevError { message: "Cannot lay out the fields of a union".to_string() })?;
+                    let mut storage = None;
+                    for (nested_type, nested_member) in nested_types.into_iter().zip(nested_members.iter()) {
+                        if let Some((member_size, member_align)) = nested_member.size_and_align() {
+                            if storage.as_ref().is_none_or(|(_, _, align)| member_align > *align) {
+                                storage = Some((nested_type, member_size, member_align));
+                            }
+                        }
+                    }
+                    let (storage_type, storage_size, _) = storage
+                        .ok_or_else(|| ErrorType::DevError { message: "Union has no members".to_string() })?;
+
+                    let resource_pools = self.get_resource_pools();
+                    let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in struct declaration!");
+                    let byte_type = resource_pools.int_type(self.get_context(), 8)
+                        .ok_or_else(|| ErrorType::DevError { message: "Failed to create padding type".to_string() })?;
+                    let mut union_types = vec![storage_type];
+                    union_types.extend((storage_size..size).map(|_| byte_type.clone()));
+                    let union_type = resource_pools.struct_type(self.get_context(), &union_types, false)
+                        .ok_or_else(|| ErrorType::DevError { message: "Failed to create union type".to_string() })?;
+                    types.push(union_type);
+                    members.push(union);
+                },
+                _ => {}
+            }
+        }
+        Ok((types, members))
+    }
+
+    /// Generates the value of an expression used as a function, such as `&add`, `add`, `*fp`, or a function pointer
+    /// variable. A bare function name evaluates to the function's address, and dereferencing a function pointer gives
+    /// back the same function, as in C. A variable in scope shadows a function of the same name.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to the expression `ASTNode`, possibly wrapped in an `AssignedValue`.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<ValueTag, ErrorType>` containing the function pointer value.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if the expression does not produce a value.
+    fn generate_function_value_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<ValueTag, ErrorType> {
+        match node.get_node_type() {
+            NodeType::AssignedValue => {
+                let child = node.get_children().first().cloned()
+                    .ok_or_else(|| ErrorType::DevError { message: "AssignedValue node has no children".to_string() })?;
+                self.generate_function_value_ir(&child, ctx)
+            },
+            NodeType::Identifier(name) if self.names_function(&name) => {
+                self.search_function_table(&name).map(|(function, _)| function)
+                    .ok_or_else(|| ErrorType::DevError { message: format!("Function not found: {}", name) })
+            },
+            NodeType::Variable if matches!(node.get_children().first().map(|c| c.get_node_type()),
+                Some(NodeType::Identifier(name)) if self.names_function(&name)) => {
+                let identifier = node.get_children()[0].clone();
+                self.generate_function_value_ir(&identifier, ctx)
+            },
+            NodeType::Identifier(name) => {
+                let mut var_node = ASTNode::new(NodeType::Variable);
+                var_node.add_child(ASTNode::new(NodeType::Identifier(name)));
+                self.generate_function_value_ir(&var_node, ctx)
+            },
+            NodeType::UnaryExpression if Self::is_function_dereference(node) => {
+                let operand = node.get_children()[1].clone();
+                self.generate_function_value_ir(&operand, ctx)
+            },
+            _ => match self.ir_router(node, ctx)? {
+                Some(Tag::Value(value)) => Ok(value),
+                _ => Err(ErrorType::DevError { message: "Expected value tag".to_string() })
+            }
+        }
+    }
+
+    /// Generates LLVM IR for a unary expression. Currently only `&` (address-of) applied to a function, `!` on any
+    /// value that can be used as a condition, and the prefix increment and decrement of a variable, are supported.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to a `UnaryExpression` `ASTNode`.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Option<Tag>, ErrorType>` containing the Tag of the resulting value.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if the operator is unsupported or the operand is not a declared function.
+    pub fn generate_unary_expression_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
+        let children = node.get_children();
+        match (children.first().map(|c| c.get_node_type()), children.get(1).map(|c| c.get_node_type())) {
+            (Some(NodeType::Operator(op)), _) if op == "++" || op == "--" => self.generate_increment_ir(node, ctx),
+            (Some(NodeType::Operator(op)), Some(NodeType::Identifier(name))) if op == "&" => {
+                let (function, _) = self.search_function_table(&name)
+                    .ok_or_else(|| ErrorType::DevError { message: format!("Cannot take the address of {}", name) })?;
+                Ok(Some(Tag::Value(function)))
+            },
+            (Some(NodeType::Operator(op)), Some(_)) if op == "!" => {
+                let value = self.generate_operand_ir(&children[1], ctx)?;
+                let truth = self.lower_condition_value(&children[1], value)?;
+                let resource_pools = self.get_resource_pools();
+                let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in unary expression!");
+                let not = resource_pools.build_logical_not(self.get_builder(), self.get_context(), truth, "nottmp")
+                    .ok_or_else(|| ErrorType::DevError { message: "Failed to create logical not".to_string() })?;
+                Ok(Some(Tag::Value(not)))
+            },
+            (Some(NodeType::Operator(op)), _) => Err(ErrorType::DevError { message: format!("Unsupported unary operator: {}", op) }),
+            _ => Err(ErrorType::DevError { message: "Invalid unary expression node".to_string() })
+        }
+    }
+
+    /// Generates LLVM IR for the increment or decrement of an integer variable, as in `++i`, `i++`, `--i` or `i--`.
+    /// The variable is loaded once and stored once wherever the expression is generated, so an increment in a loop
+    /// condition, as in `while (i++ != n)`, happens exactly once each time the condition is checked.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to a `UnaryExpression` `ASTNode` with the operator `++` or `--` before the variable, or a
+    /// `PostfixExpression` `ASTNode` with the operator after it.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Option<Tag>, ErrorType>` containing the Tag of the value after the change for a prefix
+    /// operator, and of the value before it for a postfix one.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if the operand is not a variable, or generation failed.
+    pub fn generate_increment_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
+        let children = node.get_children();
+        let postfix = node.get_node_type() == NodeType::PostfixExpression;
+        let (operator, operand) = if postfix { (children.get(1), children.first()) } else { (children.first(), children.get(1)) };
+
+        let op = match operator.map(|c| c.get_node_type()) {
+            Some(NodeType::Operator(op)) if op == "++" => "+",
+            Some(NodeType::Operator(op)) if op == "--" => "-",
+            _ => return Err(ErrorType::DevError { message: "Expected `++` or `--` in increment".to_string() })
+        };
+        let (operand, name) = match operand.map(|c| (c, c.get_node_type())) {
+            Some((operand, NodeType::Identifier(name))) => (operand, name),
+            _ => return Err(ErrorType::DevError { message: "Only a variable can be incremented or decremented".to_string() })
+        };
+
+        let old_value = self.generate_operand_ir(operand, ctx)?;
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in increment!");
+        let one = resource_pools.create_integer(self.get_context(), 1)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer constant".to_string() })?;
+        drop(resource_pools);
+
+        let overflow = self.get_codegen_config().overflow;
+        let new_value = if overflow == OverflowMode::Trap {
+            self.generate_checked_arithmetic_ir(op, old_value.clone(), one, ctx)?
+        } else {
+            let resource_pools = self.get_resource_pools();
+            let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in increment!");
+            let builder = self.get_builder();
+            match (op, overflow == OverflowMode::Undefined) {
+                ("+", true) => resource_pools.build_nsw_add(builder, old_value.clone(), one, "inctmp"),
+                ("+", false) => resource_pools.build_add(builder, old_value.clone(), one, "inctmp"),
+                (_, true) => resource_pools.build_nsw_sub(builder, old_value.clone(), one, "dectmp"),
+                (_, false) => resource_pools.build_sub(builder, old_value.clone(), one, "dectmp"),
+            }.ok_or_else(|| ErrorType::DevError { message: format!("Failed to build {}{}", op, op) })?
+        };
+
+        let llvm_alloca = self.search_store_table(name.clone());
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in increment!");
+        let store = resource_pools.reassign_var(self.get_builder(), llvm_alloca, new_value.clone())
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to store incremented value".to_string() })?;
+        drop(resource_pools);
+        self.apply_volatile(&name, store)?;
+
+        Ok(Some(Tag::Value(if postfix { old_value } else { new_value })))
+    }
+
+    /// Generates LLVM IR for a function call. Calls to declared functions are direct, while calls through a
+    /// function pointer load the pointer and call it indirectly with the pointer's function type. The callee may be
+    /// any expression whose function type is known from its form, such as `fp`, `(*fp)` or `(&add)`. The arguments
+    /// are evaluated from left to right, so their side effects happen in the order they are written.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to a `FunctionCall` `ASTNode`, whose first child is the callee and whose remaining
+    /// children are the arguments.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Option<Tag>, ErrorType>` containing the Tag of the call's result.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if the callee is not a function or function pointer, or generation failed.
+    pub fn generate_function_call_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
+        let children = node.get_children();
+        let callee = children.first()
+            .ok_or_else(|| ErrorType::DevError { message: "Function call node has no callee".to_string() })?;
+
+        let (fn_type, param_data_types) = self.callee_function_type(callee)
+            .ok_or_else(|| ErrorType::DevError { message: "Only functions and function pointers can be called".to_string() })?;
+        let callee_value = self.generate_function_value_ir(callee, ctx)?;
+
+        // Arguments are converted to the declared parameter types, so a float parameter never receives an i64
+
+        let mut args = Vec::new();
+        let mut byval_args = Vec::new();
+        for (index, arg) in children[1..].iter().enumerate() {
+            // A struct variable is passed by value as a pointer to its storage, which the callee copies. If a later
+            // argument may change the struct, it is copied now so the callee sees its value at this point.
+            if let Some((alloca, struct_type)) = self.struct_argument(arg) {
+                let alloca = if children[index + 2..].iter().any(has_side_effects) {
+                    self.copy_struct_argument_ir(alloca, struct_type, ctx)?
+                } else {
+                    alloca
+                };
+                args.push(alloca);
+                byval_args.push((index as u32, struct_type));
+                continue;
+            }
+            let value = self.generate_operand_ir(arg, ctx)?;
+            let value = match (self.value_data_type(arg), param_data_types.get(index)) {
+                (Some(from), Some(&to)) => self.convert_value_ir(value, from, to)?,
+                _ => value,
+            };
+            args.push(value);
+        }
+
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in function call!");
+        let call = resource_pools.build_call(self.get_builder(), fn_type, callee_value, &args, "calltmp")
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to build call".to_string() })?;
+        for (index, struct_type) in byval_args {
+            resource_pools.add_call_byval_attribute(call, index, struct_type)
+                .ok_or_else(|| ErrorType::DevError { message: "Failed to add byval attribute".to_string() })?;
+        }
+
+        Ok(Some(Tag::Value(call)))
+    }
+
+    /// Finds the function type of an expression used as a callee from its form: a function or function pointer
+    /// variable named by an identifier, possibly in a `Variable` node, or `*` or `&` applied to such an expression.
+    /// A variable in scope shadows a function of the same name, so a variable that is not a function pointer has no
+    /// function type.
+    ///
+    /// # Parameters
+    ///
+    /// - `callee`: A reference to the callee `ASTNode` of a `FunctionCall`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the callee's function `TypeTag` with the declared types of its parameters, or None if the callee is
+    /// not a function or function pointer.
+    fn callee_function_type(&self, callee: &ASTNode) -> Option<(TypeTag, Vec<DataType>)> {
+        match callee.get_node_type() {
+            NodeType::Identifier(name) if self.names_function(&name) => self.search_function_table(&name)
+                .map(|(_, fn_type)| (fn_type, self.search_function_parameter_types(&name).unwrap_or_default())),
+            NodeType::Identifier(name) => self.search_function_pointer_type(&name),
+            NodeType::Variable => self.callee_function_type(callee.get_children().first()?),
+            NodeType::UnaryExpression if Self::is_function_dereference(callee) || Self::is_address_of(callee) => {
+                self.callee_function_type(callee.get_children().get(1)?)
+            },
+            _ => None,
+        }
+    }
+
+    /// Checks whether a name refers to a function declared in the module, which it does unless a variable of the same
+    /// name is in scope.
+    fn names_function(&self, name: &str) -> bool {
+        self.search_store_entry(name).is_none() && self.search_function_table(name).is_some()
+    }
+
+    /// Checks whether a node is a `UnaryExpression` applying `*`, which dereferencing a function pointer or function
+    /// leaves as the same function.
+    fn is_function_dereference(node: &ASTNode) -> bool {
+        node.get_node_type() == NodeType::UnaryExpression
+            && node.get_children().first().map(|c| c.get_node_type()) == Some(NodeType::Operator("*".to_string()))
+    }
+
+    /// Checks whether a node is a `UnaryExpression` applying `&`.
+    fn is_address_of(node: &ASTNode) -> bool {
+        node.get_node_type() == NodeType::UnaryExpression
+            && node.get_children().first().map(|c| c.get_node_type()) == Some(NodeType::Operator("&".to_string()))
+    }
+
+    /// Generates LLVM IR for a binary expression on integers. The arithmetic operators `+`, `-`, `*`, `/` and `%`
+    /// produce an `i64`, while `==`, `!=`, `&&` and `||` produce an `i1`. The arithmetic is unsigned when an operand is
+    /// an `unsigned int` or wider, so it wraps instead of following the overflow mode, and `/` and `%` use `udiv` and
+    /// `urem`. The left operand is evaluated before the right one, which `&&` and `||` skip when the left one decides
+    /// the result.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to a `BinaryExpression` `ASTNode`, whose children are the left operand, the operator
+    /// and the right operand.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<Option<Tag>, ErrorType>` containing the Tag of the resulting value.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if the operator is unsupported, including the relational operators `<`, `>`, `<=` and
+    /// `>=`, which are typed as `i1` but not yet lowered, or generation failed.
+    pub fn generate_binary_expression_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<Option<Tag>, ErrorType> {
+        let children = node.get_children();
+        if children.len() != 3 {
+            return Err(ErrorType::DevError { message: "Invalid binary expression node".to_string() });
+        }
+        let op = match children[1].get_node_type() {
+            NodeType::Operator(op) => op,
+            _ => return Err(ErrorType::DevError { message: "Expected operator in binary expression".to_string() })
+        };
+        if matches!(op.as_str(), "&&" | "||") {
+            return Ok(Some(Tag::Value(self.generate_logical_ir(&op, &children[0], &children[2], ctx)?)));
+        }
+
+        let left = self.generate_operand_ir(&children[0], ctx)?;
+        let right = self.generate_operand_ir(&children[2], ctx)?;
+
+        // Strings are compared by their contents rather than by their addresses
+        if matches!(op.as_str(), "==" | "!=")
+            && self.get_operand_type(&children[0]) == DataType::String
+            && self.get_operand_type(&children[2]) == DataType::String {
+            return Ok(Some(Tag::Value(self.generate_string_comparison_ir(&op, left, right)?)));
+        }
+
+        // Unsigned arithmetic wraps in every overflow mode, and divides and takes remainders without a sign
+        let unsigned = Self::is_unsigned_arithmetic(self.get_operand_type(&children[0]), self.get_operand_type(&children[2]));
+        let overflow = self.get_codegen_config().overflow;
+        if overflow == OverflowMode::Trap && !unsigned && matches!(op.as_str(), "+" | "-" | "*") {
+            return Ok(Some(Tag::Value(self.generate_checked_arithmetic_ir(&op, left, right, ctx)?)));
+        }
+        let no_signed_wrap = overflow == OverflowMode::Undefined && !unsigned;
+
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in binary expression!");
+        let builder = self.get_builder();
+        let value = match op.as_str() {
+            "+" if no_signed_wrap => resource_pools.build_nsw_add(builder, left, right, "addtmp"),
+            "-" if no_signed_wrap => resource_pools.build_nsw_sub(builder, left, right, "subtmp"),
+            "*" if no_signed_wrap => resource_pools.build_nsw_mul(builder, left, right, "multmp"),
+            "+" => resource_pools.build_add(builder, left, right, "addtmp"),
+            "-" => resource_pools.build_sub(builder, left, right, "subtmp"),
+            "*" => resource_pools.build_mul(builder, left, right, "multmp"),
+            "/" if unsigned => resource_pools.build_udiv(builder, left, right, "divtmp"),
+            "%" if unsigned => resource_pools.build_urem(builder, left, right, "remtmp"),
+            "/" => resource_pools.build_sdiv(builder, left, right, "divtmp"),
+            "%" => resource_pools.build_srem(builder, left, right, "remtmp"),
+            "==" => resource_pools.build_icmp_eq(builder, left, right, "cmptmp"),
+            "!=" => resource_pools.build_icmp_eq(builder, left, right, "cmptmp")
+                .and_then(|eq| resource_pools.build_logical_not(builder, self.get_context(), eq, "nottmp")),
+            "<" | ">" | "<=" | ">=" => return Err(ErrorType::DevError {
+                message: format!("Relational operator {} is parsed and type-checked but not yet lowered", op)
+            }),
+            _ => return Err(ErrorType::DevError { message: format!("Unsupported binary operator: {}", op) })
+        }.ok_or_else(|| ErrorType::DevError { message: format!("Failed to build binary operator {}", op) })?;
+
+        Ok(Some(Tag::Value(value)))
+    }
+
+    /// Generates a short-circuiting `&&` or `||`. The left operand is lowered to an `i1` and stored in a temporary,
+    /// and only when it does not already decide the result is the right operand generated, in its own block, and
+    /// stored over it. Both paths then meet in an end block that loads the result, so a right operand with side
+    /// effects, such as a call or an increment, runs only when C says it does.
+    ///
+    /// # Parameters
+    ///
+    /// - `op`: The operator, `&&` or `||`.
+    /// - `left`: A reference to the left operand `ASTNode`.
+    /// - `right`: A reference to the right operand `ASTNode`.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<ValueTag, ErrorType>` containing the `i1` result of the expression.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if the expression is outside a function or generation failed.
+    fn generate_logical_ir(&mut self, op: &str, left: &ASTNode, right: &ASTNode, ctx: &mut CodegenCtx) -> Result<ValueTag, ErrorType> {
+        let function = ctx.get_function()
+            .ok_or_else(|| ErrorType::DevError { message: format!("{} outside of a function", op) })?;
+
+        let left_value = self.generate_operand_ir(left, ctx)?;
+        let left_value = self.lower_condition_value(left, left_value)?;
+
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in logical expression!");
+        let next_id = self.get_next_label_id();
+        let rhs_label = format!("{}{}", DEFAULT_LOGIC_RHS_LABEL, next_id);
+        let end_label = format!("{}{}", DEFAULT_LOGIC_END_LABEL, next_id);
+        let bool_type = resource_pools.boolean_type(self.get_context())
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to get boolean type".to_string() })?;
+        let result = resource_pools.init_var(self.get_alloca_builder(ctx), "logictmp", bool_type.clone(), None)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to allocate logical result".to_string() })?;
+        resource_pools.reassign_var(self.get_builder(), result.clone(), left_value.clone())
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to store logical result".to_string() })?;
+
+        let current_block = resource_pools.get_current_block(self.get_builder())
+            .ok_or_else(|| ErrorType::DevError { message: "No current block for logical expression".to_string() })?;
+        let rhs_block = resource_pools.create_basic_block_after(self.get_context(), function.clone(), current_block, &rhs_label)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to create logical rhs block".to_string() })?;
+        let end_block = resource_pools.create_basic_block_after(self.get_context(), function, rhs_block.clone(), &end_label)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to create logical end block".to_string() })?;
+        // `&&` only needs its right operand when the left is true, `||` only when it is false
+        let (on_true, on_false) = if op == "&&" { (rhs_block.clone(), end_block.clone()) } else { (end_block.clone(), rhs_block.clone()) };
+        resource_pools.create_cond_br(self.get_builder(), left_value, on_true, on_false)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to create logical branch".to_string() })?;
+        resource_pools.position_builder_at_end(self.get_builder(), rhs_block)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
+        drop(resource_pools);
+
+        let right_value = self.generate_operand_ir(right, ctx)?;
+        let right_value = self.lower_condition_value(right, right_value)?;
+
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in logical expression!");
+        resource_pools.reassign_var(self.get_builder(), result.clone(), right_value)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to store logical result".to_string() })?;
+        resource_pools.create_br(self.get_builder(), end_block.clone())
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to branch to logical end".to_string() })?;
+        resource_pools.position_builder_at_end(self.get_builder(), end_block)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
+        resource_pools.get_var(self.get_builder(), bool_type, result, "logictmp")
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to load logical result".to_string() })
+    }
+
+    /// Generates `==` or `!=` between two strings as a call to `strcmp`, whose result is compared against zero. A
+    /// freestanding program has no `strcmp` to call, so its string comparisons are rejected.
+    ///
+    /// # Parameters
+    ///
+    /// - `op`: The operator, `==` or `!=`.
+    /// - `left`: The `ValueTag` of the left string.
+    /// - `right`: The `ValueTag` of the right string.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<ValueTag, ErrorType>` containing the `i1` result of the comparison.
+    fn generate_string_comparison_ir(&mut self, op: &str, left: ValueTag, right: ValueTag) -> Result<ValueTag, ErrorType> {
+        if self.get_codegen_config().environment == Environment::Freestanding {
+            return Err(ErrorType::FreestandingStringComparison);
+        }
+        let (strcmp, strcmp_type) = self.declare_function_ir("strcmp", |resource_pools, context| {
+            let int_type = resource_pools.int_type(context.clone(), 32)?;
+            let pointer_type = resource_pools.pointer_type(context.clone())?;
+            resource_pools.create_function(Some(int_type), &[pointer_type.clone(), pointer_type], false, context)
+        })?;
+        let long_type = self.generate_type_ir(DataType::Integer)?;
+
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in string comparison!");
+        let order = resource_pools.build_call(self.get_builder(), strcmp_type, strcmp, &[left, right], "strcmptmp")
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to call strcmp".to_string() })?;
+        // `strcmp` returns a C `int`, which is widened to compare it against an integer zero
+        let order = resource_pools.build_sext(self.get_builder(), order, long_type, "sexttmp")
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to widen strcmp result".to_string() })?;
+        let zero = resource_pools.create_integer(self.get_context(), 0)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to create integer constant".to_string() })?;
+        let equal = resource_pools.build_icmp_eq(self.get_builder(), order, zero, "cmptmp")
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to create comparison".to_string() })?;
+        if op == "==" {
+            return Ok(equal);
+        }
+        resource_pools.build_logical_not(self.get_builder(), self.get_context(), equal, "nottmp")
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to create logical not".to_string() })
+    }
+
+    /// Generates a signed `+`, `-`, or `*` that traps at runtime on overflow. The operation is performed with the
+    /// matching `llvm.s*.with.overflow.i64` intrinsic, and its overflow flag branches to a block that calls
+    /// `llvm.trap`, while generation continues in a new block after the check.
+    ///
+    /// # Parameters
+    ///
+    /// - `op`: The operator, one of `+`, `-`, or `*`.
+    /// - `left`: The `ValueTag` of the left operand.
+    /// - `right`: The `ValueTag` of the right operand.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<ValueTag, ErrorType>` containing the result of the operation.
+    fn generate_checked_arithmetic_ir(&mut self, op: &str, left: ValueTag, right: ValueTag, ctx: &mut CodegenCtx) -> Result<ValueTag, ErrorType> {
+        let intrinsic_name = match op {
+            "+" => "llvm.sadd.with.overflow.i64",
+            "-" => "llvm.ssub.with.overflow.i64",
+            _ => "llvm.smul.with.overflow.i64",
+        };
+        let (intrinsic, intrinsic_type) = self.declare_function_ir(intrinsic_name, |resource_pools, context| {
+            let int_type = resource_pools.int_type(context.clone(), 64)?;
+            let bool_type = resource_pools.boolean_type(context.clone())?;
+            let result_type = resource_pools.struct_type(context.clone(), &[int_type.clone(), bool_type], false)?;
+            resource_pools.create_function(Some(result_type), &[int_type.clone(), int_type], false, context)
+        })?;
+        let (trap, trap_type) = self.declare_function_ir("llvm.trap", |resource_pools, context| {
+            let void_type = resource_pools.void_type(context.clone())?;
+            resource_pools.create_function(Some(void_type), &[], false, context)
+        })?;
+
+        let function = ctx.get_function()
+            .ok_or_else(|| ErrorType::DevError { message: "Checked arithmetic outside of a function".to_string() })?;
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in checked arithmetic!");
+        let next_id = self.get_next_label_id();
+        let trap_label = format!("{}{}", DEFAULT_OVERFLOW_TRAP_LABEL, next_id);
+        let continue_label = format!("{}{}", DEFAULT_OVERFLOW_CONTINUE_LABEL, next_id);
+        let current_block = resource_pools.get_current_block(self.get_builder())
+            .ok_or_else(|| ErrorType::DevError { message: "No current block for checked arithmetic".to_string() })?;
+        let trap_block = resource_pools.create_basic_block_after(self.get_context(), function.clone(), current_block, &trap_label)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to create overflow trap block".to_string() })?;
+        let continue_block = resource_pools.create_basic_block_after(self.get_context(), function, trap_block.clone(), &continue_label)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to create overflow continue block".to_string() })?;
+
+        let checked = resource_pools.build_call(self.get_builder(), intrinsic_type, intrinsic, &[left, right], "checktmp")
+            .ok_or_else(|| ErrorType::DevError { message: format!("Failed to call {}", intrinsic_name) })?;
+        let value = resource_pools.build_extract_value(self.get_builder(), checked.clone(), 0, "resulttmp")
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to extract checked arithmetic result".to_string() })?;
+        let overflowed = resource_pools.build_extract_value(self.get_builder(), checked, 1, "overflowtmp")
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to extract overflow flag".to_string() })?;
+        resource_pools.create_cond_br(self.get_builder(), overflowed, trap_block.clone(), continue_block.clone())
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to create overflow branch".to_string() })?;
+
+        resource_pools.position_builder_at_end(self.get_builder(), trap_block)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
+        resource_pools.build_call(self.get_builder(), trap_type, trap, &[], "")
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to call llvm.trap".to_string() })?;
+        resource_pools.build_unreachable(self.get_builder())
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to build unreachable".to_string() })?;
+
+        resource_pools.position_builder_at_end(self.get_builder(), continue_block)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to position builder".to_string() })?;
+        Ok(value)
+    }
+
+    /// Declares a function defined outside the module, such as an LLVM intrinsic or a C library function, the first
+    /// time it is used, and records it in the function table so later uses call the same declaration.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the function, such as `llvm.trap` or `strcmp`.
+    /// - `function_type`: Builds the function's type.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the function's `ValueTag` and function `TypeTag`.
+    fn declare_function_ir<F>(&mut self, name: &str, function_type: F) -> Result<(ValueTag, TypeTag), ErrorType>
+    where
+        F: FnOnce(&mut IRManager, ContextTag) -> Option<TypeTag>,
+    {
+        if let Some(declared) = self.search_function_table(name) {
+            return Ok(declared);
+        }
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex while declaring a function!");
+        let fn_type = function_type(&mut resource_pools, self.get_context())
+            .ok_or_else(|| ErrorType::DevError { message: format!("Failed to create the type of {}", name) })?;
+        let function = resource_pools.add_function_to_module(self.get_module(), name, fn_type.clone())
+            .ok_or_else(|| ErrorType::DevError { message: format!("Failed to declare {}", name) })?;
+        drop(resource_pools);
+        self.add_function_to_table(name.to_string(), function.clone(), fn_type.clone());
+        Ok((function, fn_type))
+    }
+
+    /// Generates the value of an operand. A bare identifier is recalled as a variable, since the router treats
+    /// identifiers as names rather than values.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to the operand `ASTNode`.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<ValueTag, ErrorType>` containing the operand's value.
+    pub fn generate_operand_ir(&mut self, node: &ASTNode, ctx: &mut CodegenCtx) -> Result<ValueTag, ErrorType> {
+        let result = match node.get_node_type() {
+            NodeType::Identifier(name) => {
+                let mut var_node = ASTNode::new(NodeType::Variable);
+                var_node.add_child(ASTNode::new(NodeType::Identifier(name)));
+                self.generate_variable_ir(&var_node)?
+            },
+            NodeType::AssignedValue => match node.get_children().first() {
+                Some(child) => return self.generate_operand_ir(child, ctx),
+                None => return Err(ErrorType::DevError { message: "AssignedValue node has no children".to_string() })
+            },
+            _ => self.ir_router(node, ctx)?
+        };
+        match result {
+            Some(Tag::Value(value)) => Ok(value),
+            _ => Err(ErrorType::DevError { message: "Expected value tag for operand".to_string() })
+        }
+    }
+
+    /// Determines the data type of the value an argument, return expression, initializer or switch scrutinee
+    /// generates, so it can be converted to the declared type. Literals with a fractional part generate a `double`,
+    /// variables carry the type their store entry was declared with, and a binary expression has the type
+    /// `get_operand_type` finds for it.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to the expression `ASTNode`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `DataType` of the value, or None if it is unknown and the value should be passed unchanged.
+    pub(crate) fn value_data_type(&self, node: &ASTNode) -> Option<DataType> {
+        match node.get_node_type() {
+            NodeType::AssignedValue => node.get_children().first().and_then(|child| self.value_data_type(child)),
+            NodeType::Literal(value) if value.starts_with('"') => None,
+            NodeType::Literal(value) if float_literal_value(&value).is_some() => Some(DataType::Double),
+            NodeType::Literal(_) => Some(DataType::Integer),
+            NodeType::BinaryExpression => Some(self.get_operand_type(node)),
+            NodeType::Identifier(name) => self.search_declared_type(&name),
+            NodeType::Variable => match node.get_children().first().map(|c| c.get_node_type()) {
+                Some(NodeType::Identifier(name)) => self.search_declared_type(&name),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Converts a value between integer and floating-point types as the C calling convention requires: integers
+    /// become floating-point with `sitofp`, or `uitofp` if they are unsigned, floating-point values become integers
+    /// with `fptosi`, or `fptoui` for an unsigned target, and `float` and `double` are widened or narrowed into each
+    /// other. Integers of different widths are truncated or extended, a `bool` and any unsigned integer with zeros and
+    /// any other integer with its sign. Any other pair of types is left unchanged.
+    ///
+    /// # Parameters
+    ///
+    /// - `value`: The `ValueTag` to convert.
+    /// - `from`: The `DataType` of the value.
+    /// - `to`: The `DataType` the value is converted to.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<ValueTag, ErrorType>` containing the converted value.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if the conversion could not be built.
+    pub fn convert_value_ir(&mut self, value: ValueTag, from: DataType, to: DataType) -> Result<ValueTag, ErrorType> {
+        let is_floating = |data_type: DataType| matches!(data_type, DataType::Float | DataType::Double);
+        let (from_width, to_width) = (Self::integer_width(from), Self::integer_width(to));
+        let converts = match (from_width, to_width) {
+            (Some(from_width), Some(to_width)) => from_width != to_width,
+            // A `bool` is only made from a floating-point value by comparing it against zero
+            (Some(1), None) | (None, Some(1)) => false,
+            (Some(_), None) => is_floating(to),
+            (None, Some(_)) => is_floating(from),
+            (None, None) => from != to && is_floating(from) && is_floating(to),
+        };
+        if !converts {
+            return Ok(value);
+        }
+        let target = self.generate_type_ir(to)?;
+
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in conversion!");
+        let builder = self.get_builder();
+        match (from_width, to_width) {
+            (Some(from_width), Some(to_width)) if from_width > to_width => resource_pools.build_trunc(builder, value, target, "trunctmp"),
+            (Some(_), Some(_)) if from == DataType::Boolean || Self::is_unsigned(from) => resource_pools.build_zext(builder, value, target, "zexttmp"),
+            (Some(_), Some(_)) => resource_pools.build_sext(builder, value, target, "sexttmp"),
+            (Some(_), None) if Self::is_unsigned(from) => resource_pools.build_ui_to_fp(builder, value, target, "uitofptmp"),
+            (Some(_), None) => resource_pools.build_si_to_fp(builder, value, target, "sitofptmp"),
+            (None, Some(_)) if Self::is_unsigned(to) => resource_pools.build_fp_to_ui(builder, value, target, "fptouitmp"),
+            (None, Some(_)) => resource_pools.build_fp_to_si(builder, value, target, "fptositmp"),
+            _ if to == DataType::Double => resource_pools.build_fp_ext(builder, value, target, "fpexttmp"),
+            _ => resource_pools.build_fp_trunc(builder, value, target, "fptrunctmp"),
+        }.ok_or_else(|| ErrorType::DevError { message: format!("Failed to convert {:?} to {:?}", from, to) })
+    }
+
+    /// Returns the width in bits of the LLVM integer type a data type is generated as, or None if it is not an
+    /// integer type.
+    ///
+    /// # Parameters
+    ///
+    /// - `data_type`: The `DataType` to measure.
+    pub(crate) fn integer_width(data_type: DataType) -> Option<u32> {
+        match data_type {
+            DataType::Boolean => Some(1),
+            DataType::Char | DataType::SignedChar | DataType::UnsignedChar => Some(8),
+            DataType::Integer | DataType::Sign | DataType::Unsign | DataType::Long | DataType::UnsignedLong
+            | DataType::LongLong | DataType::UnsignedLongLong => Some(64),
+            _ => None,
+        }
+    }
+
+    /// Checks whether a data type is an unsigned integer type, whose values are extended with zeros and converted to
+    /// and from floating-point as unsigned.
+    ///
+    /// # Parameters
+    ///
+    /// - `data_type`: The `DataType` to check.
+    pub(crate) fn is_unsigned(data_type: DataType) -> bool {
+        matches!(data_type, DataType::UnsignedChar | DataType::Unsign | DataType::UnsignedLong | DataType::UnsignedLongLong)
+    }
+
+    /// Checks whether a binary operator on operands of the given types computes with unsigned arithmetic. As in C,
+    /// an `unsigned char` is promoted to a signed `int` first, while an `unsigned int` or wider makes the other
+    /// operand unsigned too, since every such type is as wide as an `int` here.
+    ///
+    /// # Parameters
+    ///
+    /// - `left`: The `DataType` of the left operand.
+    /// - `right`: The `DataType` of the right operand.
+    fn is_unsigned_arithmetic(left: DataType, right: DataType) -> bool {
+        [left, right].into_iter().any(|data_type| data_type != DataType::UnsignedChar && Self::is_unsigned(data_type))
+    }
+
+    /// Copies a struct argument into a temporary, so that it keeps its current value while later arguments are
+    /// evaluated.
+    ///
+    /// # Parameters
+    ///
+    /// - `alloca`: The storage of the struct variable.
+    /// - `struct_type`: The struct's type.
+    /// - `ctx`: The context of the function being generated.
+    ///
+    /// # Returns
+    ///
+    /// Returns the temporary's allocation.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an ErrorType if the copy could not be generated.
+    fn copy_struct_argument_ir(&mut self, alloca: ValueTag, struct_type: TypeTag, ctx: &CodegenCtx) -> Result<ValueTag, ErrorType> {
+        let resource_pools = self.get_resource_pools();
+        let mut resource_pools = resource_pools.try_lock().expect("Failed to lock mutex in struct argument copy!");
+        let value = resource_pools.get_var(self.get_builder(), struct_type, alloca, "argcopy")
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to load struct argument".to_string() })?;
+        let copy = resource_pools.init_var(self.get_alloca_builder(ctx), "argcopy", struct_type, None)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to allocate struct argument copy".to_string() })?;
+        resource_pools.reassign_var(self.get_builder(), copy, value)
+            .ok_or_else(|| ErrorType::DevError { message: "Failed to copy struct argument".to_string() })?;
+        Ok(copy)
+    }
+
+    /// Finds the storage and struct type of an argument that names a struct variable.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to the argument `ASTNode`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the variable's allocation and struct type, or None if the argument is not a struct variable.
+    fn struct_argument(&self, node: &ASTNode) -> Option<(ValueTag, TypeTag)> {
+        let name = match node.get_node_type() {
+            NodeType::Identifier(name) => name,
+            NodeType::Variable => match node.get_children().first().map(|c| c.get_node_type()) {
+                Some(NodeType::Identifier(name)) => name,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let struct_type = self.search_struct_variable_type(&name)?;
+        Some((self.search_store_table(name), struct_type))
+    }
+}
@@ -0,0 +1,84 @@
+//! This file measures the size of generated code, counting the instructions, basic blocks and stack allocations of
+//! each function defined in a module's printed IR, so that the effect of codegen changes and optimization passes
+//! can be compared from one build to the next.
+
+use std::fmt;
+
+/// The size of one function of generated IR.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionStats {
+    /// The name of the function.
+    pub name: String,
+    /// The number of instructions in the function's body, including its terminators.
+    pub instructions: usize,
+    /// The number of basic blocks in the function's body.
+    pub basic_blocks: usize,
+    /// The number of `alloca` instructions, one per variable or temporary kept on the stack.
+    pub allocas: usize,
+}
+
+/// Provides a display implementation for `FunctionStats`, as in `main: 12 instructions, 3 basic blocks, 2 allocas`.
+///
+/// # Parameters
+/// * `f` - The formatter.
+///
+/// # Returns
+/// * `fmt::Result` - The result of the formatting operation.
+impl fmt::Display for FunctionStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} instructions, {} basic blocks, {} allocas",
+            self.name, self.instructions, self.basic_blocks, self.allocas,
+        )
+    }
+}
+
+/// Measures every function defined in a module. Functions that are only declared have no body and are left out.
+///
+/// # Parameters
+///
+/// - `ir`: The printed IR of the module, which may be annotated with comments.
+///
+/// # Returns
+///
+/// Returns the size of each defined function, in the order they are defined.
+pub fn function_stats(ir: &str) -> Vec<FunctionStats> {
+    let mut stats = Vec::new();
+    let mut lines = ir.lines();
+    while let Some(line) = lines.next() {
+        let Some(name) = line.strip_prefix("define ").and_then(function_name) else {
+            continue;
+        };
+        let mut function = FunctionStats { name, instructions: 0, basic_blocks: 0, allocas: 0 };
+        for line in lines.by_ref().take_while(|line| *line != "}") {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with(';') {
+                continue;
+            }
+            if line.starts_with(char::is_whitespace) {
+                // The entry block is only labeled when a branch targets it
+                if function.basic_blocks == 0 {
+                    function.basic_blocks = 1;
+                }
+                function.instructions += 1;
+                if trimmed.contains("= alloca ") {
+                    function.allocas += 1;
+                }
+            } else {
+                function.basic_blocks += 1;
+            }
+        }
+        stats.push(function);
+    }
+    stats
+}
+
+/// Finds the name of the function a `define` line defines, such as `main` in `i64 @main() {`.
+fn function_name(signature: &str) -> Option<String> {
+    let name = &signature[signature.find('@')? + 1..];
+    match name.strip_prefix('"') {
+        Some(quoted) => Some(quoted[..quoted.find('"')?].to_string()),
+        None => Some(name[..name.find('(')?].to_string()),
+    }
+}
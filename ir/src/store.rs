@@ -5,12 +5,50 @@
 //! please do not attempt to access it directly.
 
 use std::collections::HashMap;
-use common::error::ErrorType;
-use safe_llvm::ir::core::ValueTag;
+use common::{ast::data_type::DataType, error::ErrorType, location::Span};
+use safe_llvm::ir::core::{TypeTag, ValueTag};
+
+/// What the store knows about a variable: where it is allocated and how it was declared.
+#[derive(Clone)]
+pub struct StoreEntry {
+    /// The allocation tag of the variable.
+    pub tag: ValueTag,
+    /// The type the variable was declared with, or None if the declaration did not name one.
+    pub data_type: Option<DataType>,
+    /// Whether the variable may be assigned after it is initialized, which a `const` variable may not.
+    pub mutable: bool,
+    /// The source of the variable's name in its declaration, or None if it is not known.
+    pub span: Option<Span>,
+    /// Whether the variable was declared `volatile`, so its loads and stores must not be optimized away.
+    pub volatile: bool,
+    /// The function type a function pointer variable points to, with the declared types of its parameters, or None
+    /// if the variable is not a function pointer.
+    pub function_type: Option<(TypeTag, Vec<DataType>)>,
+    /// The struct type of a struct variable, or None if the variable is not a struct.
+    pub struct_type: Option<TypeTag>,
+    /// How many variables were added to the store before this one.
+    order: usize,
+}
+
+impl StoreEntry {
+    /// Creates a new `StoreEntry`, whose place in declaration order is set when it is added to the store.
+    ///
+    /// # Parameters
+    ///
+    /// - `tag`: The allocation tag of the variable.
+    /// - `data_type`: The type the variable was declared with, if the declaration named one.
+    /// - `mutable`: Whether the variable may be assigned after it is initialized.
+    /// - `volatile`: Whether the variable was declared `volatile`.
+    /// - `span`: The source of the variable's name in its declaration, if it is known.
+    pub fn new(tag: ValueTag, data_type: Option<DataType>, mutable: bool, volatile: bool, span: Option<Span>) -> Self {
+        Self { tag, data_type, mutable, span, volatile, function_type: None, struct_type: None, order: 0 }
+    }
+}
 
 pub struct Store {
     current_table_id: Option<usize>,
-    table_stack: Vec<HashMap<String, ValueTag>>
+    table_stack: Vec<HashMap<String, StoreEntry>>,
+    added: usize,
 }
 
 impl Store {
@@ -18,7 +56,8 @@ impl Store {
     pub fn new() -> Self {
         Self {
             current_table_id: None,
-            table_stack: Vec::new()
+            table_stack: Vec::new(),
+            added: 0,
         }
     }
 
@@ -67,22 +106,24 @@ impl Store {
         None
     }
 
-    /// Initializes a tag in the current store table, Error if name is already defined
-    pub fn add_tag_to_top_table(&mut self, var_name: String, alloca_tag: ValueTag) -> Result<(), ErrorType> {
+    /// Initializes an entry in the current store table, Error if name is already defined in it. A name defined in an
+    /// outer table is shadowed.
+    pub fn add_tag_to_top_table(&mut self, var_name: String, mut entry: StoreEntry) -> Result<(), ErrorType> {
         let id_of_found: Option<usize> = self.search_tables_for_var(var_name.clone());
         let table_id = self.get_current_id();
         if table_id.is_none() {
             return Err(ErrorType::DevError { message: "No tables to set tag in!".to_string() })
         }
 
-        if id_of_found.is_some() {
+        if id_of_found.is_some() && id_of_found == table_id {
             return Err(ErrorType::DevError { message: "Allocation tag already exists in table!".to_string() })
         }
 
         if !self.table_stack.is_empty() {
-            let cur_table: &mut HashMap<String, ValueTag> = self.table_stack.get_mut(table_id.unwrap()).unwrap();
-            cur_table.insert(var_name ,alloca_tag);
-            
+            entry.order = self.added;
+            self.added += 1;
+            let cur_table: &mut HashMap<String, StoreEntry> = self.table_stack.get_mut(table_id.unwrap()).unwrap();
+            cur_table.insert(var_name, entry);
         }
         else{
             return Err(ErrorType::DevError { message: "No tables in the store to modify!".to_string() })
@@ -93,18 +134,41 @@ impl Store {
 
     /// Searches for a variable in the table and outer tables, Error if it's not found
     pub fn search_for_var(&self, var_name: String) -> Result<ValueTag, ErrorType> {
+        self.search_for_entry(var_name).map(|entry| entry.tag.clone())
+    }
+
+    /// Searches for the entry of a variable in the table and outer tables, Error if it's not found
+    pub fn search_for_entry(&self, var_name: String) -> Result<&StoreEntry, ErrorType> {
         let id_of_found: Option<usize> = self.search_tables_for_var(var_name.clone());
 
         if id_of_found.is_none() {
-            return Err(ErrorType::DevError { message: "Variable not found in table!".to_string() })
+            return Err(ErrorType::DevError { message: format!("Variable `{}` not found in table!", var_name) })
         }
 
         if !self.table_stack.is_empty() {
-            let cur_table: &HashMap<String, ValueTag> = self.table_stack.get(id_of_found.unwrap()).unwrap();
-            return Ok(cur_table.get(&var_name).unwrap().clone())
+            let cur_table: &HashMap<String, StoreEntry> = self.table_stack.get(id_of_found.unwrap()).unwrap();
+            return Ok(cur_table.get(&var_name).unwrap())
         }
         else{
             return Err(ErrorType::DevError { message: "No tables in the store to modify!".to_string() })
         }
     }
-}
\ No newline at end of file
+
+    /// Searches for the entry of a variable in the table and outer tables to change it, Error if it's not found
+    pub fn search_for_entry_mut(&mut self, var_name: String) -> Result<&mut StoreEntry, ErrorType> {
+        let id_of_found = self.search_tables_for_var(var_name.clone())
+            .ok_or_else(|| ErrorType::DevError { message: format!("Variable `{}` not found in table!", var_name) })?;
+        Ok(self.table_stack[id_of_found].get_mut(&var_name).unwrap())
+    }
+
+    /// Lists the variables in scope, innermost table first and each table in the order its variables were added.
+    pub fn locals(&self) -> Vec<(&String, &StoreEntry)> {
+        let mut locals = Vec::new();
+        for table in self.table_stack.iter().rev() {
+            let mut entries: Vec<(&String, &StoreEntry)> = table.iter().collect();
+            entries.sort_by_key(|(_, entry)| entry.order);
+            locals.extend(entries);
+        }
+        locals
+    }
+}
@@ -25,7 +25,10 @@ fn test_function_declaration() {
     source_filename = "dummy_module"
 
     define i64 @testFunction() {
-    entryID0:
+    allocasID0:
+      br label %entryID0
+
+    entryID0:                                         ; preds = %allocasID0
     }
     */ 
     
@@ -70,7 +73,7 @@ fn test_function_declaration() {
         Ok(str) => str,
         Err(e) => panic!("{}", e)
     };
-    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunction() {\nentryID0:\n}\n";
+    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunction() {\nallocasID0:\n  br label %entryID0\n\nentryID0:                                         ; preds = %allocasID0\n}\n";
 
     assert_eq!(test_str, expected_str)
 }
@@ -90,18 +93,19 @@ fn test_function_with_if_else() {
     source_filename = "dummy_module"
 
     define i64 @testFunction() {
-    entryID0:
+    allocasID0:
+      br label %entryID0
+
+    entryID0:                                         ; preds = %allocasID0
       br i1 true, label %thenID1, label %elseID1
 
     thenID1:                                          ; preds = %entryID0
       ret i64 1
-      br label %mergeID1
 
     elseID1:                                          ; preds = %entryID0
       ret i64 1
-      br label %mergeID1
 
-    mergeID1:                                         ; preds = %elseID1, %thenID1
+    mergeID1:                                         ; No predecessors!
     }
 
     */ 
@@ -189,7 +193,7 @@ fn test_function_with_if_else() {
         Ok(str) => str,
         Err(e) => panic!("{}", e)
     };
-    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunction() {\nentryID0:\n  br i1 true, label %thenID1, label %elseID1\n\nthenID1:                                          ; preds = %entryID0\n  ret i64 1\n  br label %mergeID1\n\nelseID1:                                          ; preds = %entryID0\n  ret i64 1\n  br label %mergeID1\n\nmergeID1:                                         ; preds = %elseID1, %thenID1\n}\n";
+    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunction() {\nallocasID0:\n  br label %entryID0\n\nentryID0:                                         ; preds = %allocasID0\n  br i1 true, label %thenID1, label %elseID1\n\nthenID1:                                          ; preds = %entryID0\n  ret i64 1\n\nelseID1:                                          ; preds = %entryID0\n  ret i64 1\n\nmergeID1:                                         ; No predecessors!\n}\n";
 
     assert_eq!(test_str, expected_str)
 }
@@ -207,15 +211,17 @@ fn test_function_with_while_loop() {
     source_filename = "dummy_module"
 
     define i64 @testFunctionWithWhileLoop() {
-    entryID0:
+    allocasID0:
+      br label %entryID0
+
+    entryID0:                                         ; preds = %allocasID0
       br label %while_condID1
 
-    while_condID1:                                    ; preds = %while_bodyID1, %entryID0
+    while_condID1:                                    ; preds = %entryID0
       br i1 true, label %while_bodyID1, label %while_endID1
 
     while_bodyID1:                                    ; preds = %while_condID1
       ret i64 42
-      br label %while_condID1
 
     while_endID1:                                     ; preds = %while_condID1
     }
@@ -282,7 +288,7 @@ fn test_function_with_while_loop() {
         Ok(str) => str,
         Err(e) => panic!("{}", e)
     };
-    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunctionWithWhileLoop() {\nentryID0:\n  br label %while_condID1\n\nwhile_condID1:                                    ; preds = %while_bodyID1, %entryID0\n  br i1 true, label %while_bodyID1, label %while_endID1\n\nwhile_bodyID1:                                    ; preds = %while_condID1\n  ret i64 42\n  br label %while_condID1\n\nwhile_endID1:                                     ; preds = %while_condID1\n}\n";
+    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunctionWithWhileLoop() {\nallocasID0:\n  br label %entryID0\n\nentryID0:                                         ; preds = %allocasID0\n  br label %while_condID1\n\nwhile_condID1:                                    ; preds = %entryID0\n  br i1 true, label %while_bodyID1, label %while_endID1\n\nwhile_bodyID1:                                    ; preds = %while_condID1\n  ret i64 42\n\nwhile_endID1:                                     ; preds = %while_condID1\n}\n";
 
     assert_eq!(test_str, expected_str)
 }
@@ -297,7 +303,10 @@ fn test_function_with_while_no_body() {
     source_filename = "dummy_module"
 
     define i64 @testFunctionWithWhileNoBody() {
-    entryID0:
+    allocasID0:
+      br label %entryID0
+
+    entryID0:                                         ; preds = %allocasID0
       br label %while_condID1
 
     while_condID1:                                    ; preds = %while_bodyID1, %entryID0
@@ -361,7 +370,7 @@ fn test_function_with_while_no_body() {
         Ok(str) => str,
         Err(e) => panic!("{}", e)
     };
-    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunctionWithWhileNoBody() {\nentryID0:\n  br label %while_condID1\n\nwhile_condID1:                                    ; preds = %while_bodyID1, %entryID0\n  br i1 true, label %while_bodyID1, label %while_endID1\n\nwhile_bodyID1:                                    ; preds = %while_condID1\n  br label %while_condID1\n\nwhile_endID1:                                     ; preds = %while_condID1\n}\n";
+    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunctionWithWhileNoBody() {\nallocasID0:\n  br label %entryID0\n\nentryID0:                                         ; preds = %allocasID0\n  br label %while_condID1\n\nwhile_condID1:                                    ; preds = %while_bodyID1, %entryID0\n  br i1 true, label %while_bodyID1, label %while_endID1\n\nwhile_bodyID1:                                    ; preds = %while_condID1\n  br label %while_condID1\n\nwhile_endID1:                                     ; preds = %while_condID1\n}\n";
 
     assert_eq!(test_str, expected_str)
 }
@@ -380,14 +389,16 @@ fn test_function_with_do_while_loop() {
     source_filename = "dummy_module"
 
     define i64 @testFunctionWithDoWhileLoop() {
-    entryID0:
+    allocasID0:
+      br label %entryID0
+
+    entryID0:                                         ; preds = %allocasID0
       br label %do_bodyID1
 
     do_bodyID1:                                       ; preds = %do_condID1, %entryID0
       ret i64 24
-      br label %do_condID1
 
-    do_condID1:                                       ; preds = %do_bodyID1
+    do_condID1:                                       ; No predecessors!
       br i1 true, label %do_bodyID1, label %do_endID1
 
     do_endID1:                                        ; preds = %do_condID1
@@ -454,7 +465,7 @@ fn test_function_with_do_while_loop() {
         Ok(str) => str,
         Err(e) => panic!("{}", e)
     };
-    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunctionWithDoWhileLoop() {\nentryID0:\n  br label %do_bodyID1\n\ndo_bodyID1:                                       ; preds = %do_condID1, %entryID0\n  ret i64 24\n  br label %do_condID1\n\ndo_condID1:                                       ; preds = %do_bodyID1\n  br i1 true, label %do_bodyID1, label %do_endID1\n\ndo_endID1:                                        ; preds = %do_condID1\n}\n";
+    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunctionWithDoWhileLoop() {\nallocasID0:\n  br label %entryID0\n\nentryID0:                                         ; preds = %allocasID0\n  br label %do_bodyID1\n\ndo_bodyID1:                                       ; preds = %do_condID1, %entryID0\n  ret i64 24\n\ndo_condID1:                                       ; No predecessors!\n  br i1 true, label %do_bodyID1, label %do_endID1\n\ndo_endID1:                                        ; preds = %do_condID1\n}\n";
 
     assert_eq!(test_str, expected_str)
 }
@@ -471,10 +482,13 @@ fn test_function_with_assign() {
     source_filename = "dummy_module"
 
     define i64 @testFunctionWithAssign() {
-    entryID0:
+    allocasID0:
       %test_var = alloca i64, align 8
-      store i64 0, ptr %test_var, align 4
       %test_var_2 = alloca i64, align 8
+      br label %entryID0
+
+    entryID0:                                         ; preds = %allocasID0
+      store i64 0, ptr %test_var, align 4
     }
 
     */
@@ -548,7 +562,7 @@ fn test_function_with_assign() {
         Ok(str) => str,
         Err(e) => panic!("{}", e)
     };
-    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunctionWithAssign() {\nentryID0:\n  %test_var = alloca i64, align 8\n  store i64 0, ptr %test_var, align 4\n  %test_var_2 = alloca i64, align 8\n}\n";
+    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunctionWithAssign() {\nallocasID0:\n  %test_var = alloca i64, align 8\n  %test_var_2 = alloca i64, align 8\n  br label %entryID0\n\nentryID0:                                         ; preds = %allocasID0\n  store i64 0, ptr %test_var, align 4\n}\n";
 
     assert_eq!(test_str, expected_str)
 
@@ -566,11 +580,14 @@ fn test_function_with_variable_retrieval() {
     source_filename = "dummy_module"
 
     define i64 @testFunctionWithRetrieve() {
-    entryID0:
+    allocasID0:
       %test_var = alloca i64, align 8
+      %test_var_2 = alloca i64, align 8
+      br label %entryID0
+
+    entryID0:                                         ; preds = %allocasID0
       store i64 0, ptr %test_var, align 4
       %vrecallID1 = load i64, ptr %test_var, align 4
-      %test_var_2 = alloca i64, align 8
       store i64 %vrecallID1, ptr %test_var_2, align 4
     }
 
@@ -649,7 +666,7 @@ fn test_function_with_variable_retrieval() {
         Ok(str) => str,
         Err(e) => panic!("{}", e)
     };
-    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunctionWithRetrieve() {\nentryID0:\n  %test_var = alloca i64, align 8\n  store i64 0, ptr %test_var, align 4\n  %vrecallID1 = load i64, ptr %test_var, align 4\n  %test_var_2 = alloca i64, align 8\n  store i64 %vrecallID1, ptr %test_var_2, align 4\n}\n";
+    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunctionWithRetrieve() {\nallocasID0:\n  %test_var = alloca i64, align 8\n  %test_var_2 = alloca i64, align 8\n  br label %entryID0\n\nentryID0:                                         ; preds = %allocasID0\n  store i64 0, ptr %test_var, align 4\n  %vrecallID1 = load i64, ptr %test_var, align 4\n  store i64 %vrecallID1, ptr %test_var_2, align 4\n}\n";
 
     assert_eq!(test_str, expected_str)
 
@@ -667,8 +684,11 @@ fn test_function_with_reassign() {
     source_filename = "dummy_module"
 
     define i64 @testFunctionWithReassign() {
-    entryID0:
+    allocasID0:
       %test_var = alloca i64, align 8
+      br label %entryID0
+
+    entryID0:                                         ; preds = %allocasID0
       store i64 0, ptr %test_var, align 4
       store i64 42, ptr %test_var, align 4
 }
@@ -742,7 +762,7 @@ fn test_function_with_reassign() {
         Ok(str) => str,
         Err(e) => panic!("{}", e)
     };
-    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunctionWithReassign() {\nentryID0:\n  %test_var = alloca i64, align 8\n  store i64 0, ptr %test_var, align 4\n  store i64 42, ptr %test_var, align 4\n}\n";
+    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunctionWithReassign() {\nallocasID0:\n  %test_var = alloca i64, align 8\n  br label %entryID0\n\nentryID0:                                         ; preds = %allocasID0\n  store i64 0, ptr %test_var, align 4\n  store i64 42, ptr %test_var, align 4\n}\n";
 
     assert_eq!(test_str, expected_str)
 
@@ -763,8 +783,11 @@ fn test_function_with_for_loop() {
     source_filename = "dummy_module"
 
     define i64 @testForLoop() {
-    entryID0:
+    allocasID0:
       %test_var = alloca i64, align 8
+      br label %entryID0
+
+    entryID0:                                         ; preds = %allocasID0
       store i64 0, ptr %test_var, align 4
       br label %for_condID1
 
@@ -773,7 +796,6 @@ fn test_function_with_for_loop() {
 
     for_bodyID1:                                      ; preds = %for_condID1
       br label %for_incID1
-      br label %for_incID1
 
     for_incID1:                                       ; preds = %for_bodyID1
       store i64 42, ptr %test_var, align 4
@@ -881,7 +903,7 @@ fn test_function_with_for_loop() {
         Ok(str) => str,
         Err(e) => panic!("{}", e)
     };
-    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testForLoop() {\nentryID0:\n  %test_var = alloca i64, align 8\n  store i64 0, ptr %test_var, align 4\n  br label %for_condID1\n\nfor_condID1:                                      ; preds = %for_incID1, %entryID0\n  br i1 true, label %for_bodyID1, label %for_endID1\n\nfor_bodyID1:                                      ; preds = %for_condID1\n  br label %for_incID1\n  br label %for_incID1\n\nfor_incID1:                                       ; preds = %for_bodyID1, %for_bodyID1\n  store i64 42, ptr %test_var, align 4\n  br label %for_condID1\n\nfor_endID1:                                       ; preds = %for_condID1\n}\n";
+    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testForLoop() {\nallocasID0:\n  %test_var = alloca i64, align 8\n  br label %entryID0\n\nentryID0:                                         ; preds = %allocasID0\n  store i64 0, ptr %test_var, align 4\n  br label %for_condID1\n\nfor_condID1:                                      ; preds = %for_incID1, %entryID0\n  br i1 true, label %for_bodyID1, label %for_endID1\n\nfor_bodyID1:                                      ; preds = %for_condID1\n  br label %for_incID1\n\nfor_incID1:                                       ; preds = %for_bodyID1\n  store i64 42, ptr %test_var, align 4\n  br label %for_condID1\n\nfor_endID1:                                       ; preds = %for_condID1\n}\n";
 
     assert_eq!(test_str, expected_str)
 
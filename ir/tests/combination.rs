@@ -5,7 +5,10 @@ use std::sync::{Arc, Mutex};
 use ir::core::IRGenerator;
 use common::{
     ast::{core::{ASTNode, AST}, data_type::DataType, node_type::NodeType},
-    constants::DEFAULT_PRIORITY_MODELEMENT};
+    config::{CodegenConfig, OptLevel, OverflowMode},
+    constants::DEFAULT_PRIORITY_MODELEMENT,
+    diagnostic::DiagnosticSink,
+    warning::WarningType};
 use safe_llvm::{common::io, ir::core::IRManager};
 use sts::core::{SymbolInfo, SymbolTable, SymbolTableStack, SymbolValue};
 use integration::module::{
@@ -29,15 +32,24 @@ fn test_triple_function_declaration() {
     source_filename = "dummy_module"
 
     define i64 @testFunction() {
-    entryID0:
+    allocasID0:
+      br label %entryID0
+
+    entryID0:                                         ; preds = %allocasID0
     }
 
     define i64 @testFunction2() {
-    entryID1:
+    allocasID1:
+      br label %entryID1
+
+    entryID1:                                         ; preds = %allocasID1
     }
 
     define i64 @testFunction3() {
-    entryID2:
+    allocasID2:
+      br label %entryID2
+
+    entryID2:                                         ; preds = %allocasID2
     }
     */ 
 
@@ -117,7 +129,7 @@ fn test_triple_function_declaration() {
         Ok(str) => str,
         Err(e) => panic!("{}", e)
     };
-    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunction() {\nentryID0:\n}\n\ndefine i64 @testFunction2() {\nentryID1:\n}\n\ndefine i64 @testFunction3() {\nentryID2:\n}\n";
+    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunction() {\nallocasID0:\n  br label %entryID0\n\nentryID0:                                         ; preds = %allocasID0\n}\n\ndefine i64 @testFunction2() {\nallocasID1:\n  br label %entryID1\n\nentryID1:                                         ; preds = %allocasID1\n}\n\ndefine i64 @testFunction3() {\nallocasID2:\n  br label %entryID2\n\nentryID2:                                         ; preds = %allocasID2\n}\n";
 
     assert_eq!(test_str, expected_str)
 }
@@ -139,7 +151,10 @@ fn test_function_with_while_if_else() {
     source_filename = "dummy_module"
 
     define i64 @testFunction() {
-    entryID0:
+    allocasID0:
+      br label %entryID0
+
+    entryID0:                                         ; preds = %allocasID0
       br label %while_condID1
 
     while_condID1:                                    ; preds = %mergeID2, %entryID0
@@ -150,13 +165,11 @@ fn test_function_with_while_if_else() {
 
     thenID2:                                          ; preds = %while_bodyID1
       ret i64 2
-      br label %mergeID2
 
     elseID2:                                          ; preds = %while_bodyID1
       ret i64 1
-      br label %mergeID2
 
-    mergeID2:                                         ; preds = %elseID2, %thenID2
+    mergeID2:                                         ; No predecessors!
       br label %while_condID1
 
     while_endID1:                                     ; preds = %while_condID1
@@ -259,7 +272,7 @@ fn test_function_with_while_if_else() {
         Ok(str) => str,
         Err(e) => panic!("{}", e)
     };
-    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunction() {\nentryID0:\n  br label %while_condID1\n\nwhile_condID1:                                    ; preds = %mergeID2, %entryID0\n  br i1 true, label %while_bodyID1, label %while_endID1\n\nwhile_bodyID1:                                    ; preds = %while_condID1\n  br i1 true, label %thenID2, label %elseID2\n\nthenID2:                                          ; preds = %while_bodyID1\n  ret i64 2\n  br label %mergeID2\n\nelseID2:                                          ; preds = %while_bodyID1\n  ret i64 1\n  br label %mergeID2\n\nmergeID2:                                         ; preds = %elseID2, %thenID2\n  br label %while_condID1\n\nwhile_endID1:                                     ; preds = %while_condID1\n}\n";
+    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunction() {\nallocasID0:\n  br label %entryID0\n\nentryID0:                                         ; preds = %allocasID0\n  br label %while_condID1\n\nwhile_condID1:                                    ; preds = %mergeID2, %entryID0\n  br i1 true, label %while_bodyID1, label %while_endID1\n\nwhile_bodyID1:                                    ; preds = %while_condID1\n  br i1 true, label %thenID2, label %elseID2\n\nthenID2:                                          ; preds = %while_bodyID1\n  ret i64 2\n\nelseID2:                                          ; preds = %while_bodyID1\n  ret i64 1\n\nmergeID2:                                         ; No predecessors!\n  br label %while_condID1\n\nwhile_endID1:                                     ; preds = %while_condID1\n}\n";
 
     assert_eq!(test_str, expected_str)
 }
@@ -279,8 +292,12 @@ fn test_nested_for_loop() {
     source_filename = "dummy_module"
 
     define i64 @testForLoopNested() {
-    entryID0:
+    allocasID0:
       %test_var_outer = alloca i64, align 8
+      %test_var = alloca i64, align 8
+      br label %entryID0
+
+    entryID0:                                         ; preds = %allocasID0
       store i64 0, ptr %test_var_outer, align 4
       br label %for_condID1
 
@@ -288,7 +305,6 @@ fn test_nested_for_loop() {
       br i1 true, label %for_bodyID1, label %for_endID1
 
     for_bodyID1:                                      ; preds = %for_condID1
-      %test_var = alloca i64, align 8
       store i64 0, ptr %test_var, align 4
       br label %for_condID2
 
@@ -297,7 +313,6 @@ fn test_nested_for_loop() {
 
     for_bodyID2:                                      ; preds = %for_condID2
       br label %for_incID2
-      br label %for_incID2
 
     for_incID2:                                       ; preds = %for_bodyID2
       store i64 42, ptr %test_var, align 4
@@ -466,7 +481,7 @@ fn test_nested_for_loop() {
         Ok(str) => str,
         Err(e) => panic!("{}", e)
     };
-    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testForLoopNested() {\nentryID0:\n  %test_var_outer = alloca i64, align 8\n  store i64 0, ptr %test_var_outer, align 4\n  br label %for_condID1\n\nfor_condID1:                                      ; preds = %for_incID1, %entryID0\n  br i1 true, label %for_bodyID1, label %for_endID1\n\nfor_bodyID1:                                      ; preds = %for_condID1\n  %test_var = alloca i64, align 8\n  store i64 0, ptr %test_var, align 4\n  br label %for_condID2\n\nfor_condID2:                                      ; preds = %for_incID2, %for_bodyID1\n  br i1 true, label %for_bodyID2, label %for_endID2\n\nfor_bodyID2:                                      ; preds = %for_condID2\n  br label %for_incID2\n  br label %for_incID2\n\nfor_incID2:                                       ; preds = %for_bodyID2, %for_bodyID2\n  store i64 42, ptr %test_var, align 4\n  br label %for_condID2\n\nfor_endID2:                                       ; preds = %for_condID2\n  br label %for_incID1\n\nfor_incID1:                                       ; preds = %for_endID2\n  store i64 42, ptr %test_var_outer, align 4\n  br label %for_condID1\n\nfor_endID1:                                       ; preds = %for_condID1\n}\n";
+    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testForLoopNested() {\nallocasID0:\n  %test_var_outer = alloca i64, align 8\n  %test_var = alloca i64, align 8\n  br label %entryID0\n\nentryID0:                                         ; preds = %allocasID0\n  store i64 0, ptr %test_var_outer, align 4\n  br label %for_condID1\n\nfor_condID1:                                      ; preds = %for_incID1, %entryID0\n  br i1 true, label %for_bodyID1, label %for_endID1\n\nfor_bodyID1:                                      ; preds = %for_condID1\n  store i64 0, ptr %test_var, align 4\n  br label %for_condID2\n\nfor_condID2:                                      ; preds = %for_incID2, %for_bodyID1\n  br i1 true, label %for_bodyID2, label %for_endID2\n\nfor_bodyID2:                                      ; preds = %for_condID2\n  br label %for_incID2\n\nfor_incID2:                                       ; preds = %for_bodyID2\n  store i64 42, ptr %test_var, align 4\n  br label %for_condID2\n\nfor_endID2:                                       ; preds = %for_condID2\n  br label %for_incID1\n\nfor_incID1:                                       ; preds = %for_endID2\n  store i64 42, ptr %test_var_outer, align 4\n  br label %for_condID1\n\nfor_endID1:                                       ; preds = %for_condID1\n}\n";
 
     assert_eq!(test_str, expected_str)
 }
@@ -483,8 +498,11 @@ fn test_return_var() {
     source_filename = "dummy_module"
 
     define i64 @testFunctionWithRetrieveReturn() {
-    entryID0:
+    allocasID0:
       %i = alloca i64, align 8
+      br label %entryID0
+
+    entryID0:                                         ; preds = %allocasID0
       store i64 42, ptr %i, align 4
       %vrecallID1 = load i64, ptr %i, align 4
       ret i64 %vrecallID1
@@ -557,7 +575,2723 @@ fn test_return_var() {
         Ok(str) => str,
         Err(e) => panic!("{}", e)
     };
-    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunctionWithRetrieveReturn() {\nentryID0:\n  %i = alloca i64, align 8\n  store i64 42, ptr %i, align 4\n  %vrecallID1 = load i64, ptr %i, align 4\n  ret i64 %vrecallID1\n}\n";
+    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunctionWithRetrieveReturn() {\nallocasID0:\n  %i = alloca i64, align 8\n  br label %entryID0\n\nentryID0:                                         ; preds = %allocasID0\n  store i64 42, ptr %i, align 4\n  %vrecallID1 = load i64, ptr %i, align 4\n  ret i64 %vrecallID1\n}\n";
 
     assert_eq!(test_str, expected_str)
-}
\ No newline at end of file
+}
+#[test]
+fn test_switch_on_expression() {
+    /*
+    int testSwitchOnExpression() {
+        int x = 7;
+        switch (x % 3) {
+            case 1: return 1;
+            default: return 0;
+        }
+    }
+    */
+    let mut init_node = ASTNode::new(NodeType::Initialization);
+    let mut var_node = ASTNode::new(NodeType::Variable);
+    var_node.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    var_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    let mut value_node = ASTNode::new(NodeType::AssignedValue);
+    value_node.add_child(ASTNode::new(NodeType::Literal("7".to_string())));
+    init_node.add_child(var_node);
+    init_node.add_child(value_node);
+
+    let mut scrutinee = ASTNode::new(NodeType::BinaryExpression);
+    scrutinee.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    scrutinee.add_child(ASTNode::new(NodeType::Operator("%".to_string())));
+    scrutinee.add_child(ASTNode::new(NodeType::Literal("3".to_string())));
+
+    let mut case_return = ASTNode::new(NodeType::Return);
+    case_return.add_child(ASTNode::new(NodeType::Literal("1".to_string())));
+    let mut case_block = ASTNode::new(NodeType::BlockExpression);
+    case_block.add_child(case_return);
+    let mut case_node = ASTNode::new(NodeType::Case);
+    case_node.add_child(ASTNode::new(NodeType::Literal("1".to_string())));
+    case_node.add_child(case_block);
+
+    let mut default_return = ASTNode::new(NodeType::Return);
+    default_return.add_child(ASTNode::new(NodeType::Literal("0".to_string())));
+    let mut default_block = ASTNode::new(NodeType::BlockExpression);
+    default_block.add_child(default_return);
+    let mut default_node = ASTNode::new(NodeType::Default);
+    default_node.add_child(default_block);
+
+    let mut arms = ASTNode::new(NodeType::BlockExpression);
+    arms.add_child(case_node);
+    arms.add_child(default_node);
+
+    let mut switch_node = ASTNode::new(NodeType::SwitchStatement);
+    switch_node.add_child(scrutinee);
+    switch_node.add_child(arms);
+
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    fn_block.add_child(init_node);
+    fn_block.add_child(switch_node);
+
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier("testSwitchOnExpression".to_string())));
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    fn_declaration_node.add_child(fn_block);
+
+    let ast = wrap_in_tle(fn_declaration_node);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    let fn_value = SymbolValue::FunctionValue{
+        parameters: Vec::new(),
+    };
+    let fn_info = SymbolInfo::new(DataType::Integer, fn_value);
+    sts_global.add("testSwitchOnExpression".to_string(), fn_info);
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    let test_str = match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    };
+
+    // The whole scrutinee is computed and compared, not just `x`
+    assert!(test_str.contains("%remtmp = srem i64 %vrecallID1, 3"), "{}", test_str);
+    assert!(test_str.contains("icmp eq i64 %remtmp, 1"), "{}", test_str);
+    assert!(test_str.contains("switch_caseID"), "{}", test_str);
+    assert!(test_str.contains("switch_defaultID"), "{}", test_str);
+}
+
+#[test]
+fn test_continue_inside_switch() {
+    /*
+    int testContinueInsideSwitch() {
+        while (true) {
+            switch (1) {
+                case 1: continue;
+                default: return 0;
+            }
+        }
+    }
+    */
+    let mut case_block = ASTNode::new(NodeType::BlockExpression);
+    case_block.add_child(ASTNode::new(NodeType::Continue));
+    let mut case_node = ASTNode::new(NodeType::Case);
+    case_node.add_child(ASTNode::new(NodeType::Literal("1".to_string())));
+    case_node.add_child(case_block);
+
+    let mut default_return = ASTNode::new(NodeType::Return);
+    default_return.add_child(ASTNode::new(NodeType::Literal("0".to_string())));
+    let mut default_block = ASTNode::new(NodeType::BlockExpression);
+    default_block.add_child(default_return);
+    let mut default_node = ASTNode::new(NodeType::Default);
+    default_node.add_child(default_block);
+
+    let mut arms = ASTNode::new(NodeType::BlockExpression);
+    arms.add_child(case_node);
+    arms.add_child(default_node);
+
+    let mut switch_node = ASTNode::new(NodeType::SwitchStatement);
+    switch_node.add_child(ASTNode::new(NodeType::Literal("1".to_string())));
+    switch_node.add_child(arms);
+
+    let mut while_condition = ASTNode::new(NodeType::Condition);
+    while_condition.add_child(ASTNode::new(NodeType::Literal("true".to_string())));
+    let mut while_body = ASTNode::new(NodeType::BlockExpression);
+    while_body.add_child(switch_node);
+    let mut while_statement = ASTNode::new(NodeType::WhileLoop);
+    while_statement.add_child(while_condition);
+    while_statement.add_child(while_body);
+
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    fn_block.add_child(while_statement);
+
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier("testContinueInsideSwitch".to_string())));
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    fn_declaration_node.add_child(fn_block);
+
+    let ast = wrap_in_tle(fn_declaration_node);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    let fn_value = SymbolValue::FunctionValue{
+        parameters: Vec::new(),
+    };
+    let fn_info = SymbolInfo::new(DataType::Integer, fn_value);
+    sts_global.add("testContinueInsideSwitch".to_string(), fn_info);
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    let test_str = match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    };
+
+    // The switch only takes break, so the continue goes back to the loop's condition
+    let case_start = test_str.find("switch_caseID").expect("Missing case block");
+    let case_body = &test_str[case_start..];
+    assert!(case_body.contains("br label %while_condID"), "{}", test_str);
+}
+
+#[test]
+fn test_declarations_in_separate_cases() {
+    /*
+    int testDeclarationsInCases() {
+        switch (1) {
+            case 1: int x = 1; break;
+            default: int x = 2; break;
+        }
+        return 0;
+    }
+    */
+    fn declare_x(value: &str) -> ASTNode {
+        let mut variable = ASTNode::new(NodeType::Variable);
+        variable.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+        variable.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+        let mut assigned_value = ASTNode::new(NodeType::AssignedValue);
+        assigned_value.add_child(ASTNode::new(NodeType::Literal(value.to_string())));
+        let mut initialization = ASTNode::new(NodeType::Initialization);
+        initialization.add_child(variable);
+        initialization.add_child(assigned_value);
+        initialization
+    }
+
+    let mut case_block = ASTNode::new(NodeType::BlockExpression);
+    case_block.add_child(declare_x("1"));
+    case_block.add_child(ASTNode::new(NodeType::Break));
+    let mut case_node = ASTNode::new(NodeType::Case);
+    case_node.add_child(ASTNode::new(NodeType::Literal("1".to_string())));
+    case_node.add_child(case_block);
+
+    let mut default_block = ASTNode::new(NodeType::BlockExpression);
+    default_block.add_child(declare_x("2"));
+    default_block.add_child(ASTNode::new(NodeType::Break));
+    let mut default_node = ASTNode::new(NodeType::Default);
+    default_node.add_child(default_block);
+
+    let mut arms = ASTNode::new(NodeType::BlockExpression);
+    arms.add_child(case_node);
+    arms.add_child(default_node);
+
+    let mut switch_node = ASTNode::new(NodeType::SwitchStatement);
+    switch_node.add_child(ASTNode::new(NodeType::Literal("1".to_string())));
+    switch_node.add_child(arms);
+
+    let mut fn_return = ASTNode::new(NodeType::Return);
+    fn_return.add_child(ASTNode::new(NodeType::Literal("0".to_string())));
+
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    fn_block.add_child(switch_node);
+    fn_block.add_child(fn_return);
+
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier("testDeclarationsInCases".to_string())));
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    fn_declaration_node.add_child(fn_block);
+
+    let ast = wrap_in_tle(fn_declaration_node);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    let fn_value = SymbolValue::FunctionValue{
+        parameters: Vec::new(),
+    };
+    let fn_info = SymbolInfo::new(DataType::Integer, fn_value);
+    sts_global.add("testDeclarationsInCases".to_string(), fn_info);
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    let test_str = match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    };
+
+    // Each case declares its own x in its own scope
+    assert_eq!(test_str.matches("= alloca i64").count(), 2, "{}", test_str);
+}
+
+#[test]
+fn test_struct_passed_by_value() {
+    /*
+    struct P [ x: int, y: int ]
+    void show(struct P p) {}
+    int main() {
+        struct P q;
+        show(q);
+    }
+    */
+    let struct_type = || {
+        let mut type_node = ASTNode::new(NodeType::Type(DataType::Struct));
+        type_node.add_child(ASTNode::new(NodeType::Identifier("P".to_string())));
+        type_node
+    };
+
+    let mut struct_node = ASTNode::new(NodeType::StructDeclaration);
+    struct_node.add_child(ASTNode::new(NodeType::Identifier("P".to_string())));
+    for field_name in ["x", "y"] {
+        let mut field = ASTNode::new(NodeType::Field);
+        field.add_child(ASTNode::new(NodeType::Literal(field_name.to_string())));
+        field.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+        struct_node.add_child(field);
+    }
+
+    let mut param_node = ASTNode::new(NodeType::Parameter);
+    param_node.add_child(ASTNode::new(NodeType::Identifier("p".to_string())));
+    param_node.add_child(struct_type());
+
+    let mut show_node = ASTNode::new(NodeType::FunctionDeclaration);
+    show_node.add_child(ASTNode::new(NodeType::Identifier("show".to_string())));
+    show_node.add_child(param_node);
+    show_node.add_child(ASTNode::new(NodeType::Type(DataType::Void)));
+    show_node.add_child(ASTNode::new(NodeType::BlockExpression));
+
+    let mut var_node = ASTNode::new(NodeType::Variable);
+    var_node.add_child(ASTNode::new(NodeType::Identifier("q".to_string())));
+    var_node.add_child(struct_type());
+    let mut init_node = ASTNode::new(NodeType::Initialization);
+    init_node.add_child(var_node);
+
+    let mut call_node = ASTNode::new(NodeType::FunctionCall);
+    call_node.add_child(ASTNode::new(NodeType::Identifier("show".to_string())));
+    call_node.add_child(ASTNode::new(NodeType::Identifier("q".to_string())));
+
+    let mut main_block = ASTNode::new(NodeType::BlockExpression);
+    main_block.add_child(init_node);
+    main_block.add_child(call_node);
+
+    let mut main_node = ASTNode::new(NodeType::FunctionDeclaration);
+    main_node.add_child(ASTNode::new(NodeType::Identifier("main".to_string())));
+    main_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    main_node.add_child(main_block);
+
+    let mut tle = ASTNode::new(NodeType::TopLevelExpression);
+    tle.add_child(struct_node);
+    tle.add_child(show_node);
+    tle.add_child(main_node);
+    let ast = AST::new(tle);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    sts_global.add("show".to_string(), SymbolInfo::new(DataType::Void, SymbolValue::FunctionValue {
+        parameters: vec![("p".to_string(), DataType::Struct)],
+    }));
+    sts_global.add("main".to_string(), SymbolInfo::new(DataType::Integer, SymbolValue::FunctionValue {
+        parameters: Vec::new(),
+    }));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    let test_str = match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    };
+
+    // The callee takes a pointer to its own copy, and the caller passes its struct by value
+    assert!(test_str.contains("define void @show(ptr byval(%P)"), "{}", test_str);
+    assert!(test_str.contains("load %P, ptr"), "{}", test_str);
+    assert!(test_str.contains("call void @show(ptr byval(%P)"), "{}", test_str);
+}
+
+#[test]
+fn test_packed_and_aligned_structs() {
+    /*
+    @packed struct Packed [ flag: bool, value: int ]
+    @align(16) struct Wide [ x: int ]
+    int main() {
+        struct Packed p;
+        struct Wide w;
+    }
+    */
+    let struct_declaration = |name: &str, fields: &[(&str, DataType)], attribute: ASTNode| {
+        let mut struct_node = ASTNode::new(NodeType::StructDeclaration);
+        struct_node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+        for (field_name, field_type) in fields {
+            let mut field = ASTNode::new(NodeType::Field);
+            field.add_child(ASTNode::new(NodeType::Literal(field_name.to_string())));
+            field.add_child(ASTNode::new(NodeType::Type(*field_type)));
+            struct_node.add_child(field);
+        }
+        struct_node.add_child(attribute);
+        struct_node
+    };
+    let packed_node = struct_declaration(
+        "Packed", &[("flag", DataType::Boolean), ("value", DataType::Integer)],
+        ASTNode::new(NodeType::Attribute("packed".to_string())),
+    );
+    let mut align_attribute = ASTNode::new(NodeType::Attribute("align".to_string()));
+    align_attribute.add_child(ASTNode::new(NodeType::Literal("16".to_string())));
+    let wide_node = struct_declaration("Wide", &[("x", DataType::Integer)], align_attribute);
+
+    let mut main_block = ASTNode::new(NodeType::BlockExpression);
+    for (var_name, struct_name) in [("p", "Packed"), ("w", "Wide")] {
+        let mut type_node = ASTNode::new(NodeType::Type(DataType::Struct));
+        type_node.add_child(ASTNode::new(NodeType::Identifier(struct_name.to_string())));
+        let mut var_node = ASTNode::new(NodeType::Variable);
+        var_node.add_child(ASTNode::new(NodeType::Identifier(var_name.to_string())));
+        var_node.add_child(type_node);
+        let mut init_node = ASTNode::new(NodeType::Initialization);
+        init_node.add_child(var_node);
+        main_block.add_child(init_node);
+    }
+
+    let mut main_node = ASTNode::new(NodeType::FunctionDeclaration);
+    main_node.add_child(ASTNode::new(NodeType::Identifier("main".to_string())));
+    main_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    main_node.add_child(main_block);
+
+    let mut tle = ASTNode::new(NodeType::TopLevelExpression);
+    tle.add_child(packed_node);
+    tle.add_child(wide_node);
+    tle.add_child(main_node);
+    let ast = AST::new(tle);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    sts_global.add("main".to_string(), SymbolInfo::new(DataType::Integer, SymbolValue::FunctionValue {
+        parameters: Vec::new(),
+    }));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    ir_generator.generate_ir(mod_ast);
+
+    // The packed struct has no padding at all, and the aligned one is padded to a multiple of its alignment
+    let packed = ir_generator.search_struct_layout("Packed").expect("No layout for Packed");
+    assert_eq!((packed.size, packed.align, packed.fields.iter().map(|field| field.offset).collect::<Vec<_>>()), (9, 1, vec![0, 1]));
+    let wide = ir_generator.search_struct_layout("Wide").expect("No layout for Wide");
+    assert_eq!((wide.size, wide.align, wide.trailing_padding), (16, 16, 8));
+
+    let test_str = ir_generator.function_ir_text("main");
+    assert!(test_str.contains("%p = alloca <{ i1, i64 }>"), "{}", test_str);
+    assert!(test_str.contains("%w = alloca %Wide"), "{}", test_str);
+}
+
+#[test]
+fn test_anonymous_struct_and_union_members() {
+    /*
+    struct Value [ tag: bool, union [ i: int, struct [ x: float, y: float ] ] ]
+    */
+    let field = |name: &str, data_type: DataType| {
+        let mut field = ASTNode::new(NodeType::Field);
+        field.add_child(ASTNode::new(NodeType::Literal(name.to_string())));
+        field.add_child(ASTNode::new(NodeType::Type(data_type)));
+        field
+    };
+    let mut inner_struct = ASTNode::new(NodeType::StructDeclaration);
+    inner_struct.add_child(field("x", DataType::Float));
+    inner_struct.add_child(field("y", DataType::Float));
+    let mut union_node = ASTNode::new(NodeType::UnionDeclaration);
+    union_node.add_child(field("i", DataType::Integer));
+    union_node.add_child(inner_struct);
+
+    let mut struct_node = ASTNode::new(NodeType::StructDeclaration);
+    struct_node.add_child(ASTNode::new(NodeType::Identifier("Value".to_string())));
+    struct_node.add_child(field("tag", DataType::Boolean));
+    struct_node.add_child(union_node);
+
+    let mut tle = ASTNode::new(NodeType::TopLevelExpression);
+    tle.add_child(struct_node);
+    let ast = AST::new(tle);
+
+    let mut sts_stack = SymbolTableStack::new();
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    // The fields of the union all start where it does, after the padding that aligns it
+    let value = ir_generator.search_struct_layout("Value").expect("No layout for Value");
+    let offsets: Vec<(&str, u64)> = value.fields.iter().map(|field| (field.name.as_str(), field.offset)).collect();
+    assert_eq!(offsets, vec![("tag", 0), ("i", 8), ("x", 8), ("y", 12)]);
+    assert_eq!((value.size, value.align), (16, 8));
+    assert_eq!(value.field("y").map(|field| field.data_type), Some(DataType::Float));
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    let test_str = match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    };
+
+    // The union is stored as its most aligned member
+    assert!(test_str.contains("%Value = type { i1, { i64 } }"), "{}", test_str);
+}
+
+#[test]
+fn test_floating_point_parameters_and_returns() {
+    /*
+    double half(float x) {
+        return x;
+    }
+    int main() {
+        half(3);
+    }
+    */
+    let mut param_node = ASTNode::new(NodeType::Parameter);
+    param_node.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    param_node.add_child(ASTNode::new(NodeType::Type(DataType::Float)));
+
+    let mut return_node = ASTNode::new(NodeType::Return);
+    return_node.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    let mut half_block = ASTNode::new(NodeType::BlockExpression);
+    half_block.add_child(return_node);
+
+    let mut half_node = ASTNode::new(NodeType::FunctionDeclaration);
+    half_node.add_child(ASTNode::new(NodeType::Identifier("half".to_string())));
+    half_node.add_child(param_node);
+    half_node.add_child(ASTNode::new(NodeType::Type(DataType::Double)));
+    half_node.add_child(half_block);
+
+    let mut call_node = ASTNode::new(NodeType::FunctionCall);
+    call_node.add_child(ASTNode::new(NodeType::Identifier("half".to_string())));
+    call_node.add_child(ASTNode::new(NodeType::Literal("3".to_string())));
+
+    let mut main_block = ASTNode::new(NodeType::BlockExpression);
+    main_block.add_child(call_node);
+
+    let mut main_node = ASTNode::new(NodeType::FunctionDeclaration);
+    main_node.add_child(ASTNode::new(NodeType::Identifier("main".to_string())));
+    main_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    main_node.add_child(main_block);
+
+    let mut tle = ASTNode::new(NodeType::TopLevelExpression);
+    tle.add_child(half_node);
+    tle.add_child(main_node);
+    let ast = AST::new(tle);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    sts_global.add("half".to_string(), SymbolInfo::new(DataType::Double, SymbolValue::FunctionValue {
+        parameters: vec![("x".to_string(), DataType::Float)],
+    }));
+    sts_global.add("main".to_string(), SymbolInfo::new(DataType::Integer, SymbolValue::FunctionValue {
+        parameters: Vec::new(),
+    }));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    let test_str = match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    };
+
+    // The signature keeps its floating-point types, the float is widened on return and the int converted at the call
+    assert!(test_str.contains("define double @half(float"), "{}", test_str);
+    assert!(test_str.contains("fpext float"), "{}", test_str);
+    assert!(test_str.contains("sitofp i64 3 to float"), "{}", test_str);
+}
+
+#[test]
+fn test_long_and_signedness_specified_integers() {
+    /*
+    long widths(unsigned long a, long long b, unsigned long long c, signed d, unsigned e) {
+        unsigned long long total = b;
+        return a;
+    }
+    */
+    let parameters = [
+        ("a", DataType::UnsignedLong),
+        ("b", DataType::LongLong),
+        ("c", DataType::UnsignedLongLong),
+        ("d", DataType::Sign),
+        ("e", DataType::Unsign),
+    ];
+
+    let mut variable_node = ASTNode::new(NodeType::Variable);
+    variable_node.add_child(ASTNode::new(NodeType::Identifier("total".to_string())));
+    variable_node.add_child(ASTNode::new(NodeType::Type(DataType::UnsignedLongLong)));
+    let mut value_node = ASTNode::new(NodeType::AssignedValue);
+    value_node.add_child(ASTNode::new(NodeType::Identifier("b".to_string())));
+    let mut initialization_node = ASTNode::new(NodeType::Initialization);
+    initialization_node.add_child(variable_node);
+    initialization_node.add_child(value_node);
+
+    let mut return_node = ASTNode::new(NodeType::Return);
+    return_node.add_child(ASTNode::new(NodeType::Identifier("a".to_string())));
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    fn_block.add_child(initialization_node);
+    fn_block.add_child(return_node);
+
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier("widths".to_string())));
+    for (name, data_type) in parameters {
+        let mut param_node = ASTNode::new(NodeType::Parameter);
+        param_node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+        param_node.add_child(ASTNode::new(NodeType::Type(data_type)));
+        fn_declaration_node.add_child(param_node);
+    }
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Long)));
+    fn_declaration_node.add_child(fn_block);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    sts_global.add("widths".to_string(), SymbolInfo::new(DataType::Long, SymbolValue::FunctionValue {
+        parameters: parameters.iter().map(|(name, data_type)| (name.to_string(), *data_type)).collect(),
+    }));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(wrap_in_tle(fn_declaration_node), sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    ir_generator.generate_ir(mod_ast);
+    let test_str = ir_generator.function_ir_text("widths");
+
+    // Every width and signedness of integer but `char` is an `i64`, in the signature and in locals alike
+    let signature = test_str.lines().find(|line| line.starts_with("define ")).expect("The function should be defined");
+    assert!(signature.starts_with("define i64 @widths("), "{}", test_str);
+    assert_eq!(signature.matches("i64").count(), 6, "{}", test_str);
+    assert!(test_str.contains("alloca i64"), "{}", test_str);
+}
+
+/// Generates IR for `int testOverflow() { int x = 7; return x * 3; }` with the given overflow mode.
+fn generate_multiplication_ir(overflow: OverflowMode) -> String {
+    generate_arithmetic_ir(DataType::Integer, "*", overflow)
+}
+
+/// Generates IR for `int testOverflow() { T x = 7; return x <op> 3; }`, where `x` has type `data_type`, with the
+/// given overflow mode.
+fn generate_arithmetic_ir(data_type: DataType, op: &str, overflow: OverflowMode) -> String {
+    let mut init_node = ASTNode::new(NodeType::Initialization);
+    let mut var_node = ASTNode::new(NodeType::Variable);
+    var_node.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    var_node.add_child(ASTNode::new(NodeType::Type(data_type)));
+    let mut value_node = ASTNode::new(NodeType::AssignedValue);
+    value_node.add_child(ASTNode::new(NodeType::Literal("7".to_string())));
+    init_node.add_child(var_node);
+    init_node.add_child(value_node);
+
+    let mut product = ASTNode::new(NodeType::BinaryExpression);
+    product.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    product.add_child(ASTNode::new(NodeType::Operator(op.to_string())));
+    product.add_child(ASTNode::new(NodeType::Literal("3".to_string())));
+    let mut return_node = ASTNode::new(NodeType::Return);
+    return_node.add_child(product);
+
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    fn_block.add_child(init_node);
+    fn_block.add_child(return_node);
+
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier("testOverflow".to_string())));
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    fn_declaration_node.add_child(fn_block);
+
+    let ast = wrap_in_tle(fn_declaration_node);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    let fn_value = SymbolValue::FunctionValue{
+        parameters: Vec::new(),
+    };
+    sts_global.add("testOverflow".to_string(), SymbolInfo::new(DataType::Integer, fn_value));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::with_config(CodegenConfig { overflow, ..CodegenConfig::default() });
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    }
+}
+
+#[test]
+fn test_signed_overflow_modes() {
+    let wrapping = generate_multiplication_ir(OverflowMode::Wrap);
+    assert!(wrapping.contains("%multmp = mul i64 %vrecallID1, 3"), "{}", wrapping);
+
+    let undefined = generate_multiplication_ir(OverflowMode::Undefined);
+    assert!(undefined.contains("%multmp = mul nsw i64 %vrecallID1, 3"), "{}", undefined);
+
+    // Overflow is detected by the intrinsic and branches to a block that traps
+    let trapping = generate_multiplication_ir(OverflowMode::Trap);
+    assert!(trapping.contains("call { i64, i1 } @llvm.smul.with.overflow.i64(i64 %vrecallID1, i64 3)"), "{}", trapping);
+    assert!(trapping.contains("overflow_trapID"), "{}", trapping);
+    assert!(trapping.contains("call void @llvm.trap()"), "{}", trapping);
+    assert!(trapping.contains("unreachable"), "{}", trapping);
+    assert!(!trapping.contains("mul i64"), "{}", trapping);
+}
+
+#[test]
+fn test_unsigned_arithmetic() {
+    // `unsigned long` divides and takes remainders without a sign
+    let division = generate_arithmetic_ir(DataType::UnsignedLong, "/", OverflowMode::Wrap);
+    assert!(division.contains("%divtmp = udiv i64 %vrecallID1, 3"), "{}", division);
+    let remainder = generate_arithmetic_ir(DataType::Unsign, "%", OverflowMode::Wrap);
+    assert!(remainder.contains("%remtmp = urem i64 %vrecallID1, 3"), "{}", remainder);
+    let signed_division = generate_arithmetic_ir(DataType::Long, "/", OverflowMode::Wrap);
+    assert!(signed_division.contains("%divtmp = sdiv i64 %vrecallID1, 3"), "{}", signed_division);
+
+    // Unsigned overflow wraps, so neither `nsw` nor the signed overflow intrinsics apply to it
+    let undefined = generate_arithmetic_ir(DataType::UnsignedLongLong, "*", OverflowMode::Undefined);
+    assert!(undefined.contains("%multmp = mul i64 %vrecallID1, 3"), "{}", undefined);
+    let trapping = generate_arithmetic_ir(DataType::UnsignedLong, "*", OverflowMode::Trap);
+    assert!(trapping.contains("%multmp = mul i64 %vrecallID1, 3"), "{}", trapping);
+    assert!(!trapping.contains("llvm.smul.with.overflow"), "{}", trapping);
+}
+
+#[test]
+fn test_integer_to_boolean_conversion() {
+    /*
+    int testIntegerToBoolean() {
+        int x = 7;
+        bool b = x * 2;
+        b = x;
+        return x;
+    }
+    */
+    let mut x_init = ASTNode::new(NodeType::Initialization);
+    let mut x_var = ASTNode::new(NodeType::Variable);
+    x_var.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    x_var.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    let mut x_value = ASTNode::new(NodeType::AssignedValue);
+    x_value.add_child(ASTNode::new(NodeType::Literal("7".to_string())));
+    x_init.add_child(x_var);
+    x_init.add_child(x_value);
+
+    let mut product = ASTNode::new(NodeType::BinaryExpression);
+    product.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    product.add_child(ASTNode::new(NodeType::Operator("*".to_string())));
+    product.add_child(ASTNode::new(NodeType::Literal("2".to_string())));
+    let mut b_init = ASTNode::new(NodeType::Initialization);
+    let mut b_var = ASTNode::new(NodeType::Variable);
+    b_var.add_child(ASTNode::new(NodeType::Identifier("b".to_string())));
+    b_var.add_child(ASTNode::new(NodeType::Type(DataType::Boolean)));
+    let mut b_value = ASTNode::new(NodeType::AssignedValue);
+    b_value.add_child(product);
+    b_init.add_child(b_var);
+    b_init.add_child(b_value);
+
+    let mut x_recall = ASTNode::new(NodeType::Variable);
+    x_recall.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    let mut reassignment_node = ASTNode::new(NodeType::Assignment);
+    reassignment_node.add_child(ASTNode::new(NodeType::Identifier("b".to_string())));
+    let mut reassigned_value = ASTNode::new(NodeType::AssignedValue);
+    reassigned_value.add_child(x_recall);
+    reassignment_node.add_child(reassigned_value);
+
+    let mut return_node = ASTNode::new(NodeType::Return);
+    return_node.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    fn_block.add_child(x_init);
+    fn_block.add_child(b_init);
+    fn_block.add_child(reassignment_node);
+    fn_block.add_child(return_node);
+
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier("testIntegerToBoolean".to_string())));
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    fn_declaration_node.add_child(fn_block);
+
+    let ast = wrap_in_tle(fn_declaration_node);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    let fn_value = SymbolValue::FunctionValue{
+        parameters: Vec::new(),
+    };
+    sts_global.add("testIntegerToBoolean".to_string(), SymbolInfo::new(DataType::Integer, fn_value));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    let test_str = match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    };
+
+    // The bool is an i1, and both the initializer and the assigned value are compared against zero
+    assert!(test_str.contains("%b = alloca i1"), "{}", test_str);
+    assert!(test_str.contains("icmp eq i64 %multmp, 0"), "{}", test_str);
+    assert_eq!(test_str.matches("icmp eq i64").count(), 2, "{}", test_str);
+    assert_eq!(test_str.matches("store i1").count(), 2, "{}", test_str);
+    assert!(!test_str.contains("store i64 %multmp"), "{}", test_str);
+}
+
+#[test]
+fn test_string_equality_calls_strcmp() {
+    /*
+    int testStringEquality() {
+        if ("main" != "test") {
+            return 1;
+        }
+        return 0;
+    }
+    */
+    let mut comparison = ASTNode::new(NodeType::BinaryExpression);
+    comparison.add_child(ASTNode::new(NodeType::Literal("\"main\"".to_string())));
+    comparison.add_child(ASTNode::new(NodeType::Operator("!=".to_string())));
+    comparison.add_child(ASTNode::new(NodeType::Literal("\"test\"".to_string())));
+    let mut if_condition = ASTNode::new(NodeType::Condition);
+    if_condition.add_child(comparison);
+
+    let mut then_return = ASTNode::new(NodeType::Return);
+    then_return.add_child(ASTNode::new(NodeType::Literal("1".to_string())));
+    let mut then_branch = ASTNode::new(NodeType::BlockExpression);
+    then_branch.add_child(then_return);
+
+    let mut if_statement = ASTNode::new(NodeType::IfStatement);
+    if_statement.add_child(if_condition);
+    if_statement.add_child(then_branch);
+
+    let mut final_return = ASTNode::new(NodeType::Return);
+    final_return.add_child(ASTNode::new(NodeType::Literal("0".to_string())));
+
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    fn_block.add_child(if_statement);
+    fn_block.add_child(final_return);
+
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier("testStringEquality".to_string())));
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    fn_declaration_node.add_child(fn_block);
+
+    let ast = wrap_in_tle(fn_declaration_node);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    let fn_value = SymbolValue::FunctionValue{
+        parameters: Vec::new(),
+    };
+    sts_global.add("testStringEquality".to_string(), SymbolInfo::new(DataType::Integer, fn_value));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    let test_str = match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    };
+
+    // The contents are compared, so the strings' addresses never are
+    assert!(test_str.contains("declare i32 @strcmp(ptr, ptr)"), "{}", test_str);
+    assert!(test_str.contains("%strcmptmp = call i32 @strcmp("), "{}", test_str);
+    assert!(test_str.contains("%sexttmp = sext i32 %strcmptmp to i64"), "{}", test_str);
+    assert!(test_str.contains("icmp eq i64 %sexttmp, 0"), "{}", test_str);
+    assert!(!test_str.contains("icmp eq ptr"), "{}", test_str);
+}
+
+/// Generates the IR of a function comparing the string literal `escaped`, written as the parser stores it, to `"é"`.
+fn generate_string_comparison_ir(escaped: &str) -> String {
+    /*
+    int testStringBytes() {
+        if (<escaped> != "é") {
+            return 1;
+        }
+        return 0;
+    }
+    */
+    let mut comparison = ASTNode::new(NodeType::BinaryExpression);
+    comparison.add_child(ASTNode::new(NodeType::Literal(escaped.to_string())));
+    comparison.add_child(ASTNode::new(NodeType::Operator("!=".to_string())));
+    comparison.add_child(ASTNode::new(NodeType::Literal("\"é\"".to_string())));
+    let mut if_condition = ASTNode::new(NodeType::Condition);
+    if_condition.add_child(comparison);
+
+    let mut then_return = ASTNode::new(NodeType::Return);
+    then_return.add_child(ASTNode::new(NodeType::Literal("1".to_string())));
+    let mut then_branch = ASTNode::new(NodeType::BlockExpression);
+    then_branch.add_child(then_return);
+
+    let mut if_statement = ASTNode::new(NodeType::IfStatement);
+    if_statement.add_child(if_condition);
+    if_statement.add_child(then_branch);
+
+    let mut final_return = ASTNode::new(NodeType::Return);
+    final_return.add_child(ASTNode::new(NodeType::Literal("0".to_string())));
+
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    fn_block.add_child(if_statement);
+    fn_block.add_child(final_return);
+
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier("testStringBytes".to_string())));
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    fn_declaration_node.add_child(fn_block);
+
+    let ast = wrap_in_tle(fn_declaration_node);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    let fn_value = SymbolValue::FunctionValue{
+        parameters: Vec::new(),
+    };
+    sts_global.add("testStringBytes".to_string(), SymbolInfo::new(DataType::Integer, fn_value));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    }
+}
+
+#[test]
+fn test_string_byte_escapes_are_single_bytes() {
+    let test_str = generate_string_comparison_ir("\"\\303\\251\"");
+
+    // Each escape is one byte, so both strings are the two bytes of `é` rather than the escaped string being the
+    // UTF-8 encoding of U+00C3 and U+00A9
+    assert_eq!(test_str.matches("c\"\\C3\\A9").count(), 2, "{}", test_str);
+    assert!(!test_str.contains("\\C3\\83"), "{}", test_str);
+}
+
+#[test]
+fn test_string_bytes_need_not_be_utf8() {
+    // `"\xFF"` is not valid UTF-8, and `"\u{ef80}"` is a character whose UTF-8 encoding is kept as it is
+    let test_str = generate_string_comparison_ir("\"\\377\u{ef80}\"");
+    assert!(test_str.contains("c\"\\FF\\EE\\BE\\80\\00\""), "{}", test_str);
+}
+
+/// Generates IR for `int testDenseSwitch() { int x = 2; switch (x) { case 0: ... case 3: return 3; default: return
+/// -1; } }` with the given switch table threshold.
+fn generate_dense_switch_ir(switch_table_threshold: usize) -> String {
+    let mut init_node = ASTNode::new(NodeType::Initialization);
+    let mut var_node = ASTNode::new(NodeType::Variable);
+    var_node.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    var_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    let mut value_node = ASTNode::new(NodeType::AssignedValue);
+    value_node.add_child(ASTNode::new(NodeType::Literal("2".to_string())));
+    init_node.add_child(var_node);
+    init_node.add_child(value_node);
+
+    let mut arms = ASTNode::new(NodeType::BlockExpression);
+    for value in ["0", "1", "2", "3"] {
+        let mut case_return = ASTNode::new(NodeType::Return);
+        case_return.add_child(ASTNode::new(NodeType::Literal(value.to_string())));
+        let mut case_block = ASTNode::new(NodeType::BlockExpression);
+        case_block.add_child(case_return);
+        let mut case_node = ASTNode::new(NodeType::Case);
+        case_node.add_child(ASTNode::new(NodeType::Literal(value.to_string())));
+        case_node.add_child(case_block);
+        arms.add_child(case_node);
+    }
+    let mut default_return = ASTNode::new(NodeType::Return);
+    default_return.add_child(ASTNode::new(NodeType::Literal("-1".to_string())));
+    let mut default_block = ASTNode::new(NodeType::BlockExpression);
+    default_block.add_child(default_return);
+    let mut default_node = ASTNode::new(NodeType::Default);
+    default_node.add_child(default_block);
+    arms.add_child(default_node);
+
+    let mut switch_node = ASTNode::new(NodeType::SwitchStatement);
+    switch_node.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    switch_node.add_child(arms);
+
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    fn_block.add_child(init_node);
+    fn_block.add_child(switch_node);
+
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier("testDenseSwitch".to_string())));
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    fn_declaration_node.add_child(fn_block);
+
+    let ast = wrap_in_tle(fn_declaration_node);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    let fn_value = SymbolValue::FunctionValue{
+        parameters: Vec::new(),
+    };
+    sts_global.add("testDenseSwitch".to_string(), SymbolInfo::new(DataType::Integer, fn_value));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::with_config(CodegenConfig { switch_table_threshold, ..CodegenConfig::default() });
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    }
+}
+
+#[test]
+fn test_dense_switch_lowering() {
+    // Four dense cases reach the default threshold and become a single `switch`
+    let table = generate_dense_switch_ir(4);
+    assert!(table.contains("switch i64 %vrecallID1, label %switch_defaultID"), "{}", table);
+    assert!(table.contains("i64 3, label %switch_caseID"), "{}", table);
+    assert!(!table.contains("casecmp"), "{}", table);
+
+    // Below the threshold, each case is compared in turn
+    let chain = generate_dense_switch_ir(5);
+    assert!(chain.contains("icmp eq i64 %vrecallID1, 0"), "{}", chain);
+    assert!(!chain.contains("switch i64"), "{}", chain);
+}
+
+/// Generates `int testCounting() { int i = 0; int n = 3; while (i++ != n) { } return i; }`, or the same loop written
+/// as `do { } while (i++ != n);`.
+fn generate_counting_loop_ir(do_while: bool) -> String {
+    fn declare(name: &str, value: &str) -> ASTNode {
+        let mut var_node = ASTNode::new(NodeType::Variable);
+        var_node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+        var_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+        let mut value_node = ASTNode::new(NodeType::AssignedValue);
+        value_node.add_child(ASTNode::new(NodeType::Literal(value.to_string())));
+        let mut init_node = ASTNode::new(NodeType::Initialization);
+        init_node.add_child(var_node);
+        init_node.add_child(value_node);
+        init_node
+    }
+
+    let mut increment = ASTNode::new(NodeType::PostfixExpression);
+    increment.add_child(ASTNode::new(NodeType::Identifier("i".to_string())));
+    increment.add_child(ASTNode::new(NodeType::Operator("++".to_string())));
+    let mut comparison = ASTNode::new(NodeType::BinaryExpression);
+    comparison.add_child(increment);
+    comparison.add_child(ASTNode::new(NodeType::Operator("!=".to_string())));
+    comparison.add_child(ASTNode::new(NodeType::Identifier("n".to_string())));
+    let mut condition = ASTNode::new(NodeType::Condition);
+    condition.add_child(comparison);
+
+    let mut loop_node = ASTNode::new(if do_while { NodeType::DoWhileLoop } else { NodeType::WhileLoop });
+    if do_while {
+        loop_node.add_child(ASTNode::new(NodeType::BlockExpression));
+        loop_node.add_child(condition);
+    } else {
+        loop_node.add_child(condition);
+        loop_node.add_child(ASTNode::new(NodeType::BlockExpression));
+    }
+
+    let mut return_node = ASTNode::new(NodeType::Return);
+    return_node.add_child(ASTNode::new(NodeType::Identifier("i".to_string())));
+
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    fn_block.add_child(declare("i", "0"));
+    fn_block.add_child(declare("n", "3"));
+    fn_block.add_child(loop_node);
+    fn_block.add_child(return_node);
+
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier("testCounting".to_string())));
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    fn_declaration_node.add_child(fn_block);
+
+    let ast = wrap_in_tle(fn_declaration_node);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    let fn_value = SymbolValue::FunctionValue{
+        parameters: Vec::new(),
+    };
+    sts_global.add("testCounting".to_string(), SymbolInfo::new(DataType::Integer, fn_value));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    }
+}
+
+#[test]
+fn test_increment_in_loop_condition() {
+    // The increment is generated once, in the condition block, and the old value of i is compared with n
+    let while_ir = generate_counting_loop_ir(false);
+    assert_eq!(while_ir.matches("%inctmp = add i64").count(), 1, "{}", while_ir);
+    let condition = &while_ir[while_ir.find("\nwhile_condID").expect("Missing condition block")..];
+    let condition = &condition[..condition.find("\nwhile_bodyID").expect("Missing body block")];
+    assert!(condition.contains("%inctmp = add i64 %vrecallID1, 1"), "{}", while_ir);
+    assert!(condition.contains("store i64 %inctmp, ptr %i"), "{}", while_ir);
+    assert!(condition.contains("icmp eq i64 %vrecallID1, %vrecallID"), "{}", while_ir);
+
+    // A do while loop checks its condition, and so increments i, only after the body
+    let do_while_ir = generate_counting_loop_ir(true);
+    assert_eq!(do_while_ir.matches("%inctmp = add i64").count(), 1, "{}", do_while_ir);
+    let condition_start = do_while_ir.find("\ndo_condID").expect("Missing condition block");
+    assert!(do_while_ir[condition_start..].contains("%inctmp = add i64 %vrecallID1, 1"), "{}", do_while_ir);
+    let body = &do_while_ir[..condition_start];
+    assert!(!body.contains("inctmp"), "{}", do_while_ir);
+}
+
+#[test]
+fn test_expression_branch_reaches_merge() {
+    /*
+    int testExpressionBranch() {
+        if (true) 7; else return 1;
+        return 0;
+    }
+    */
+    let mut if_condition = ASTNode::new(NodeType::Condition);
+    if_condition.add_child(ASTNode::new(NodeType::Literal("true".to_string())));
+
+    let mut else_return = ASTNode::new(NodeType::Return);
+    else_return.add_child(ASTNode::new(NodeType::Literal("1".to_string())));
+    let mut else_branch = ASTNode::new(NodeType::ElseStatement);
+    else_branch.add_child(else_return);
+
+    let mut if_statement = ASTNode::new(NodeType::IfStatement);
+    if_statement.add_child(if_condition);
+    if_statement.add_child(ASTNode::new(NodeType::Literal("7".to_string())));
+    if_statement.add_child(else_branch);
+
+    let mut fn_return = ASTNode::new(NodeType::Return);
+    fn_return.add_child(ASTNode::new(NodeType::Literal("0".to_string())));
+
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    fn_block.add_child(if_statement);
+    fn_block.add_child(fn_return);
+
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier("testExpressionBranch".to_string())));
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    fn_declaration_node.add_child(fn_block);
+
+    let ast = wrap_in_tle(fn_declaration_node);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    let fn_value = SymbolValue::FunctionValue{
+        parameters: Vec::new(),
+    };
+    let fn_info = SymbolInfo::new(DataType::Integer, fn_value);
+    sts_global.add("testExpressionBranch".to_string(), fn_info);
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    let test_str = match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    };
+
+    // The expression statement still continues to the merge block, while the return does not branch after itself
+    let then_block = test_str.split("\nthenID1:").nth(1).and_then(|rest| rest.split("\n\n").next()).expect("No then block");
+    assert!(then_block.contains("br label %mergeID1"), "{}", test_str);
+    let else_block = test_str.split("\nelseID1:").nth(1).and_then(|rest| rest.split("\n\n").next()).expect("No else block");
+    assert!(else_block.contains("ret i64 1"), "{}", test_str);
+    assert!(!else_block.contains("br label"), "{}", test_str);
+}
+
+#[test]
+fn test_if_without_else() {
+    /*
+    int testIfWithoutElse() {
+        if (true) {
+            return 1;
+        }
+        return 0;
+    }
+    */
+    let mut if_condition = ASTNode::new(NodeType::Condition);
+    if_condition.add_child(ASTNode::new(NodeType::Literal("true".to_string())));
+
+    let mut then_return = ASTNode::new(NodeType::Return);
+    then_return.add_child(ASTNode::new(NodeType::Literal("1".to_string())));
+    let mut then_branch = ASTNode::new(NodeType::BlockExpression);
+    then_branch.add_child(then_return);
+
+    let mut if_statement = ASTNode::new(NodeType::IfStatement);
+    if_statement.add_child(if_condition);
+    if_statement.add_child(then_branch);
+
+    let mut fn_return = ASTNode::new(NodeType::Return);
+    fn_return.add_child(ASTNode::new(NodeType::Literal("0".to_string())));
+
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    fn_block.add_child(if_statement);
+    fn_block.add_child(fn_return);
+
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier("testIfWithoutElse".to_string())));
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    fn_declaration_node.add_child(fn_block);
+
+    let ast = wrap_in_tle(fn_declaration_node);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    let fn_value = SymbolValue::FunctionValue{
+        parameters: Vec::new(),
+    };
+    sts_global.add("testIfWithoutElse".to_string(), SymbolInfo::new(DataType::Integer, fn_value));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    let test_str = match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    };
+
+    // The false edge goes straight to the merge block, so there is no else block at all
+    assert!(test_str.contains("br i1 true, label %thenID1, label %mergeID1"), "{}", test_str);
+    assert!(!test_str.contains("elseID"), "{}", test_str);
+    assert!(test_str.contains("mergeID1:                                         ; preds = %entryID0"), "{}", test_str);
+}
+
+#[test]
+fn test_else_if_chain() {
+    /*
+    int testElseIf(int a, int b) {
+        if (a) {
+            return 1;
+        } else if (b) {
+            return 2;
+        } else if (false) {
+            return 3;
+        } else {
+            return 4;
+        }
+        return 0;
+    }
+    */
+    let condition = |value: ASTNode| {
+        let mut condition = ASTNode::new(NodeType::Condition);
+        condition.add_child(value);
+        condition
+    };
+    let returning = |value: &str| {
+        let mut return_node = ASTNode::new(NodeType::Return);
+        return_node.add_child(ASTNode::new(NodeType::Literal(value.to_string())));
+        let mut block = ASTNode::new(NodeType::BlockExpression);
+        block.add_child(return_node);
+        block
+    };
+    let else_if = |value: ASTNode, result: &str| {
+        let mut else_if = ASTNode::new(NodeType::ElseIf);
+        else_if.add_child(condition(value));
+        else_if.add_child(returning(result));
+        else_if
+    };
+    let parameter = |name: &str| {
+        let mut param_node = ASTNode::new(NodeType::Parameter);
+        param_node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+        param_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+        param_node
+    };
+
+    let mut if_statement = ASTNode::new(NodeType::IfStatement);
+    if_statement.add_child(condition(ASTNode::new(NodeType::Identifier("a".to_string()))));
+    if_statement.add_child(returning("1"));
+    if_statement.add_child(else_if(ASTNode::new(NodeType::Identifier("b".to_string())), "2"));
+    if_statement.add_child(else_if(ASTNode::new(NodeType::Literal("false".to_string())), "3"));
+    if_statement.add_child(returning("4"));
+
+    let mut fn_return = ASTNode::new(NodeType::Return);
+    fn_return.add_child(ASTNode::new(NodeType::Literal("0".to_string())));
+
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    fn_block.add_child(if_statement);
+    fn_block.add_child(fn_return);
+
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier("testElseIf".to_string())));
+    fn_declaration_node.add_child(parameter("a"));
+    fn_declaration_node.add_child(parameter("b"));
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    fn_declaration_node.add_child(fn_block);
+
+    let ast = wrap_in_tle(fn_declaration_node);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    let fn_value = SymbolValue::FunctionValue{
+        parameters: vec![("a".to_string(), DataType::Integer), ("b".to_string(), DataType::Integer)],
+    };
+    sts_global.add("testElseIf".to_string(), SymbolInfo::new(DataType::Integer, fn_value));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::with_config(CodegenConfig { opt_level: OptLevel::O1, ..CodegenConfig::default() });
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    let test_str = match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    };
+
+    // Each branch tests its condition in the else block of the one before, the constantly false branch is dropped,
+    // and every branch shares one merge block
+    assert!(test_str.contains("label %thenID1, label %elseID1"), "{}", test_str);
+    assert!(test_str.contains("label %thenID2, label %elseID2"), "{}", test_str);
+    assert!(!test_str.contains("ret i64 3"), "{}", test_str);
+    assert!(test_str.contains("ret i64 4"), "{}", test_str);
+    assert_eq!(test_str.matches("mergeID").count(), 1, "{}", test_str);
+}
+
+#[test]
+fn test_logical_operators_on_booleans() {
+    /*
+    int testLogicalOperators() {
+        bool flag1 = true;
+        bool flag2 = false;
+        if (flag1 && !flag2) {
+            return 1;
+        }
+        return 0;
+    }
+    */
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    for (name, value) in [("flag1", "true"), ("flag2", "false")] {
+        let mut var_node = ASTNode::new(NodeType::Variable);
+        var_node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+        var_node.add_child(ASTNode::new(NodeType::Type(DataType::Boolean)));
+        let mut value_node = ASTNode::new(NodeType::AssignedValue);
+        value_node.add_child(ASTNode::new(NodeType::Literal(value.to_string())));
+        let mut init_node = ASTNode::new(NodeType::Initialization);
+        init_node.add_child(var_node);
+        init_node.add_child(value_node);
+        fn_block.add_child(init_node);
+    }
+
+    let mut not_flag2 = ASTNode::new(NodeType::UnaryExpression);
+    not_flag2.add_child(ASTNode::new(NodeType::Operator("!".to_string())));
+    not_flag2.add_child(ASTNode::new(NodeType::Identifier("flag2".to_string())));
+    let mut both = ASTNode::new(NodeType::BinaryExpression);
+    both.add_child(ASTNode::new(NodeType::Identifier("flag1".to_string())));
+    both.add_child(ASTNode::new(NodeType::Operator("&&".to_string())));
+    both.add_child(not_flag2);
+    let mut if_condition = ASTNode::new(NodeType::Condition);
+    if_condition.add_child(both);
+
+    let mut then_return = ASTNode::new(NodeType::Return);
+    then_return.add_child(ASTNode::new(NodeType::Literal("1".to_string())));
+    let mut then_branch = ASTNode::new(NodeType::BlockExpression);
+    then_branch.add_child(then_return);
+
+    let mut if_statement = ASTNode::new(NodeType::IfStatement);
+    if_statement.add_child(if_condition);
+    if_statement.add_child(then_branch);
+
+    let mut fn_return = ASTNode::new(NodeType::Return);
+    fn_return.add_child(ASTNode::new(NodeType::Literal("0".to_string())));
+
+    fn_block.add_child(if_statement);
+    fn_block.add_child(fn_return);
+
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier("testLogicalOperators".to_string())));
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    fn_declaration_node.add_child(fn_block);
+
+    let ast = wrap_in_tle(fn_declaration_node);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    let fn_value = SymbolValue::FunctionValue{
+        parameters: Vec::new(),
+    };
+    sts_global.add("testLogicalOperators".to_string(), SymbolInfo::new(DataType::Integer, fn_value));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    let test_str = match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    };
+
+    // The bools are used as i1 directly, `flag1` short-circuits past `!flag2`, and the result feeds the if
+    assert!(test_str.contains("%logictmp = alloca i1"), "{}", test_str);
+    assert!(test_str.contains("load i1, ptr %flag1"), "{}", test_str);
+    assert!(test_str.contains("br i1 %vrecallID1, label %logic_rhsID"), "{}", test_str);
+    assert!(test_str.contains("load i1, ptr %flag2"), "{}", test_str);
+    assert!(test_str.contains("xor i1 %vrecallID"), "{}", test_str);
+    assert!(test_str.contains("load i1, ptr %logictmp"), "{}", test_str);
+    assert!(!test_str.contains("icmp"), "{}", test_str);
+}
+
+#[test]
+fn test_compound_assignment() {
+    /*
+    int testCompoundAssignment() {
+        int x = 2;
+        x *= 3;
+        return x;
+    }
+    */
+    let mut var_node = ASTNode::new(NodeType::Variable);
+    var_node.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    var_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    let mut value_node = ASTNode::new(NodeType::AssignedValue);
+    value_node.add_child(ASTNode::new(NodeType::Literal("2".to_string())));
+    let mut init_node = ASTNode::new(NodeType::Initialization);
+    init_node.add_child(var_node);
+    init_node.add_child(value_node);
+
+    let mut update = ASTNode::new(NodeType::CompoundAssignment);
+    update.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    update.add_child(ASTNode::new(NodeType::Operator("*=".to_string())));
+    update.add_child(ASTNode::new(NodeType::Literal("3".to_string())));
+
+    let mut return_node = ASTNode::new(NodeType::Return);
+    return_node.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    fn_block.add_child(init_node);
+    fn_block.add_child(update);
+    fn_block.add_child(return_node);
+
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier("testCompoundAssignment".to_string())));
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    fn_declaration_node.add_child(fn_block);
+
+    let ast = wrap_in_tle(fn_declaration_node);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    let fn_value = SymbolValue::FunctionValue{
+        parameters: Vec::new(),
+    };
+    sts_global.add("testCompoundAssignment".to_string(), SymbolInfo::new(DataType::Integer, fn_value));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    let test_str = match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    };
+
+    // x *= 3 loads x, multiplies, and stores the product back into x
+    assert!(test_str.contains("load i64, ptr %x"), "{}", test_str);
+    assert!(test_str.contains("mul i64 %vrecallID"), "{}", test_str);
+    assert!(test_str.contains(", 3\n"), "{}", test_str);
+    assert_eq!(test_str.matches("store i64").count(), 2, "{}", test_str);
+}
+
+#[test]
+fn test_chained_assignment() {
+    /*
+    int testChainedAssignment() {
+        int a = 0;
+        int b = 0;
+        a = b = 7;
+        return a;
+    }
+    */
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    for name in ["a", "b"] {
+        let mut var_node = ASTNode::new(NodeType::Variable);
+        var_node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+        var_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+        let mut value_node = ASTNode::new(NodeType::AssignedValue);
+        value_node.add_child(ASTNode::new(NodeType::Literal("0".to_string())));
+        let mut init_node = ASTNode::new(NodeType::Initialization);
+        init_node.add_child(var_node);
+        init_node.add_child(value_node);
+        fn_block.add_child(init_node);
+    }
+
+    let mut inner = ASTNode::new(NodeType::Assignment);
+    inner.add_child(ASTNode::new(NodeType::Identifier("b".to_string())));
+    inner.add_child(ASTNode::new(NodeType::Literal("7".to_string())));
+    let mut outer = ASTNode::new(NodeType::Assignment);
+    outer.add_child(ASTNode::new(NodeType::Identifier("a".to_string())));
+    outer.add_child(inner);
+    fn_block.add_child(outer);
+
+    let mut return_node = ASTNode::new(NodeType::Return);
+    return_node.add_child(ASTNode::new(NodeType::Identifier("a".to_string())));
+    fn_block.add_child(return_node);
+
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier("testChainedAssignment".to_string())));
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    fn_declaration_node.add_child(fn_block);
+
+    let ast = wrap_in_tle(fn_declaration_node);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    let fn_value = SymbolValue::FunctionValue{
+        parameters: Vec::new(),
+    };
+    sts_global.add("testChainedAssignment".to_string(), SymbolInfo::new(DataType::Integer, fn_value));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    ir_generator.generate_ir(mod_ast);
+    let test_str = ir_generator.function_ir_text("testChainedAssignment");
+
+    // The value stored into b is the value of the inner assignment, which is stored again into a
+    assert!(test_str.contains("store i64 7, ptr %b"), "{}", test_str);
+    assert!(test_str.contains("store i64 7, ptr %a"), "{}", test_str);
+    assert_eq!(test_str.matches("store i64").count(), 4, "{}", test_str);
+}
+
+/// Generates IR at `-O1` for a function `int name()` with the given body, returning the IR and the warnings reported.
+fn generate_optimized(name: &str, fn_block: ASTNode) -> (String, Vec<WarningType>) {
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    fn_declaration_node.add_child(fn_block);
+
+    let ast = wrap_in_tle(fn_declaration_node);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    let fn_value = SymbolValue::FunctionValue{
+        parameters: Vec::new(),
+    };
+    sts_global.add(name.to_string(), SymbolInfo::new(DataType::Integer, fn_value));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::with_config(CodegenConfig { opt_level: OptLevel::O1, ..CodegenConfig::default() });
+    let mut diagnostics = DiagnosticSink::new();
+    let module_tag = ir_generator.generate_ir_reporting(mod_ast, &mut diagnostics).expect("IR generation failed");
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    let test_str = match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    };
+    (test_str, diagnostics.warnings().to_vec())
+}
+
+#[test]
+fn test_constant_if_generates_only_the_taken_branch() {
+    /*
+    int testConstantIf() {
+        if (1 > 2) {
+            return 1;
+        }
+        return 0;
+    }
+    */
+    let mut comparison = ASTNode::new(NodeType::BinaryExpression);
+    comparison.add_child(ASTNode::new(NodeType::Literal("1".to_string())));
+    comparison.add_child(ASTNode::new(NodeType::Operator(">".to_string())));
+    comparison.add_child(ASTNode::new(NodeType::Literal("2".to_string())));
+    let mut if_condition = ASTNode::new(NodeType::Condition);
+    if_condition.add_child(comparison);
+
+    let mut then_return = ASTNode::new(NodeType::Return);
+    then_return.add_child(ASTNode::new(NodeType::Literal("1".to_string())));
+    let mut then_branch = ASTNode::new(NodeType::BlockExpression);
+    then_branch.add_child(then_return);
+
+    let mut if_statement = ASTNode::new(NodeType::IfStatement);
+    if_statement.add_child(if_condition);
+    if_statement.add_child(then_branch);
+
+    let mut fn_return = ASTNode::new(NodeType::Return);
+    fn_return.add_child(ASTNode::new(NodeType::Literal("0".to_string())));
+
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    fn_block.add_child(if_statement);
+    fn_block.add_child(fn_return);
+
+    let (test_str, warnings) = generate_optimized("testConstantIf", fn_block);
+
+    // Neither the comparison nor the never-taken branch is generated
+    assert!(!test_str.contains("thenID"), "{}", test_str);
+    assert!(!test_str.contains("br i1"), "{}", test_str);
+    assert!(!test_str.contains("ret i64 1"), "{}", test_str);
+    assert!(test_str.contains("ret i64 0"), "{}", test_str);
+    assert_eq!(warnings, vec![WarningType::ConstantCondition { expression: "1 > 2".to_string(), value: false }]);
+}
+
+#[test]
+fn test_constant_while_is_an_endless_loop() {
+    /*
+    int testConstantWhile() {
+        while (1) {
+            break;
+        }
+        return 0;
+    }
+    */
+    let mut while_condition = ASTNode::new(NodeType::Condition);
+    while_condition.add_child(ASTNode::new(NodeType::Literal("1".to_string())));
+    let mut while_body = ASTNode::new(NodeType::BlockExpression);
+    while_body.add_child(ASTNode::new(NodeType::Break));
+    let mut while_statement = ASTNode::new(NodeType::WhileLoop);
+    while_statement.add_child(while_condition);
+    while_statement.add_child(while_body);
+
+    let mut fn_return = ASTNode::new(NodeType::Return);
+    fn_return.add_child(ASTNode::new(NodeType::Literal("0".to_string())));
+
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    fn_block.add_child(while_statement);
+    fn_block.add_child(fn_return);
+
+    let (test_str, warnings) = generate_optimized("testConstantWhile", fn_block);
+
+    // The loop has no condition block, and only the `break` reaches its end
+    assert!(!test_str.contains("while_condID"), "{}", test_str);
+    assert!(!test_str.contains("br i1"), "{}", test_str);
+    assert!(test_str.contains("br label %while_bodyID1"), "{}", test_str);
+    assert!(test_str.contains("while_endID1:                                     ; preds = %while_bodyID1"), "{}", test_str);
+    assert_eq!(warnings, vec![WarningType::ConstantCondition { expression: "1".to_string(), value: true }]);
+}
+
+#[test]
+fn test_function_ir_text() {
+    /*
+    int testFunction() {};
+    int testFunction2() {};
+    */
+    let mut top_level = ASTNode::new(NodeType::TopLevelExpression);
+    let mut sts_global = SymbolTable::new();
+    for name in ["testFunction", "testFunction2"] {
+        let mut function_ast = ASTNode::new(NodeType::FunctionDeclaration);
+        function_ast.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+        function_ast.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+        function_ast.add_child(ASTNode::new(NodeType::BlockExpression));
+        top_level.add_child(function_ast);
+
+        let fn_value = SymbolValue::FunctionValue{
+            parameters: Vec::new(),
+        };
+        sts_global.add(name.to_string(), SymbolInfo::new(DataType::Integer, fn_value));
+    }
+    let mut sts_stack = SymbolTableStack::new();
+    sts_stack.push(sts_global);
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(AST::new(top_level), sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    ir_generator.generate_ir(mod_ast);
+
+    // Only the named function is returned, even when another function's name is a prefix of it
+    let expected_str = "define i64 @testFunction2() {\nallocasID1:\n  br label %entryID1\n\nentryID1:                                         ; preds = %allocasID1\n}\n";
+    assert_eq!(ir_generator.function_ir_text("testFunction2"), expected_str);
+    assert!(ir_generator.function_ir_text("testFunction").starts_with("define i64 @testFunction() {"));
+    assert_eq!(ir_generator.function_ir_text("missing"), "");
+}
+
+#[test]
+fn test_enum_with_char_underlying_type() {
+    /*
+    enum Color : char { Red, Green, Blue };
+    struct Pixel [ color: Color, count: int ]
+    int testEnum() {
+        return Blue;
+    }
+    */
+    let mut enum_node = ASTNode::new(NodeType::EnumDeclaration);
+    enum_node.add_child(ASTNode::new(NodeType::Identifier("Color".to_string())));
+    enum_node.add_child(ASTNode::new(NodeType::Type(DataType::Char)));
+    for name in ["Red", "Green", "Blue"] {
+        let mut variant = ASTNode::new(NodeType::Variant);
+        variant.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+        enum_node.add_child(variant);
+    }
+
+    // The parser gives a field of the enum its underlying type
+    let field = |name: &str, data_type: DataType| {
+        let mut field = ASTNode::new(NodeType::Field);
+        field.add_child(ASTNode::new(NodeType::Literal(name.to_string())));
+        field.add_child(ASTNode::new(NodeType::Type(data_type)));
+        field
+    };
+    let mut struct_node = ASTNode::new(NodeType::StructDeclaration);
+    struct_node.add_child(ASTNode::new(NodeType::Identifier("Pixel".to_string())));
+    struct_node.add_child(field("color", DataType::Char));
+    struct_node.add_child(field("count", DataType::Integer));
+
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    let mut return_node = ASTNode::new(NodeType::Return);
+    return_node.add_child(ASTNode::new(NodeType::Identifier("Blue".to_string())));
+    fn_block.add_child(return_node);
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier("testEnum".to_string())));
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    fn_declaration_node.add_child(fn_block);
+
+    let mut tle = ASTNode::new(NodeType::TopLevelExpression);
+    tle.add_child(enum_node);
+    tle.add_child(struct_node);
+    tle.add_child(fn_declaration_node);
+    let ast = AST::new(tle);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    let fn_value = SymbolValue::FunctionValue{
+        parameters: Vec::new(),
+    };
+    sts_global.add("testEnum".to_string(), SymbolInfo::new(DataType::Integer, fn_value));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    assert_eq!(ir_generator.search_enum_constant("Blue"), Some((2, DataType::Char)));
+    let pixel = ir_generator.search_struct_layout("Pixel").expect("No layout for Pixel");
+    assert_eq!(pixel.field("count").map(|field| field.offset), Some(8));
+    assert_eq!((pixel.size, pixel.align), (16, 8));
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    let test_str = match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    };
+
+    // The field is stored as a char, and the constant is promoted to an integer where it is used
+    assert!(test_str.contains("%Pixel = type { i8, i64 }"), "{}", test_str);
+    assert!(test_str.contains("ret i64 2"), "{}", test_str);
+}
+
+#[test]
+fn test_function_prototype() {
+    /*
+    int twice(int x);
+    int main() {
+        return twice(3);
+    }
+    int twice(int x) {
+        return x;
+    }
+    */
+    let parameter = || {
+        let mut param_node = ASTNode::new(NodeType::Parameter);
+        param_node.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+        param_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+        param_node
+    };
+
+    let mut prototype_node = ASTNode::new(NodeType::FunctionPrototype);
+    prototype_node.add_child(ASTNode::new(NodeType::Identifier("twice".to_string())));
+    prototype_node.add_child(parameter());
+    prototype_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+
+    let mut call_node = ASTNode::new(NodeType::FunctionCall);
+    call_node.add_child(ASTNode::new(NodeType::Identifier("twice".to_string())));
+    call_node.add_child(ASTNode::new(NodeType::Literal("3".to_string())));
+    let mut main_return = ASTNode::new(NodeType::Return);
+    main_return.add_child(call_node);
+    let mut main_block = ASTNode::new(NodeType::BlockExpression);
+    main_block.add_child(main_return);
+    let mut main_node = ASTNode::new(NodeType::FunctionDeclaration);
+    main_node.add_child(ASTNode::new(NodeType::Identifier("main".to_string())));
+    main_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    main_node.add_child(main_block);
+
+    let mut twice_return = ASTNode::new(NodeType::Return);
+    twice_return.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    let mut twice_block = ASTNode::new(NodeType::BlockExpression);
+    twice_block.add_child(twice_return);
+    let mut twice_node = ASTNode::new(NodeType::FunctionDeclaration);
+    twice_node.add_child(ASTNode::new(NodeType::Identifier("twice".to_string())));
+    twice_node.add_child(parameter());
+    twice_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    twice_node.add_child(twice_block);
+
+    let mut tle = ASTNode::new(NodeType::TopLevelExpression);
+    tle.add_child(prototype_node);
+    tle.add_child(main_node);
+    tle.add_child(twice_node);
+    let ast = AST::new(tle);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    sts_global.add("twice".to_string(), SymbolInfo::new(DataType::Integer, SymbolValue::FunctionValue {
+        parameters: vec![("x".to_string(), DataType::Integer)],
+    }));
+    sts_global.add("main".to_string(), SymbolInfo::new(DataType::Integer, SymbolValue::FunctionValue {
+        parameters: Vec::new(),
+    }));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    let test_str = match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    };
+
+    // The definition gives the function declared by the prototype its body, rather than declaring another
+    assert!(test_str.contains("call i64 @twice(i64 3)"), "{}", test_str);
+    assert_eq!(test_str.matches("define i64 @twice").count(), 1, "{}", test_str);
+    assert!(!test_str.contains("twice.1"), "{}", test_str);
+}
+
+#[test]
+fn test_variadic_function_prototype() {
+    /*
+    int sum(int count, ...);
+    int main() {
+        return sum(2, 3, 4);
+    }
+    */
+    let mut param_node = ASTNode::new(NodeType::Parameter);
+    param_node.add_child(ASTNode::new(NodeType::Identifier("count".to_string())));
+    param_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+
+    let mut prototype_node = ASTNode::new(NodeType::FunctionPrototype);
+    prototype_node.add_child(ASTNode::new(NodeType::Identifier("sum".to_string())));
+    prototype_node.add_child(param_node);
+    prototype_node.add_child(ASTNode::new(NodeType::Variadic));
+    prototype_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+
+    let mut call_node = ASTNode::new(NodeType::FunctionCall);
+    call_node.add_child(ASTNode::new(NodeType::Identifier("sum".to_string())));
+    for argument in ["2", "3", "4"] {
+        call_node.add_child(ASTNode::new(NodeType::Literal(argument.to_string())));
+    }
+    let mut main_return = ASTNode::new(NodeType::Return);
+    main_return.add_child(call_node);
+    let mut main_block = ASTNode::new(NodeType::BlockExpression);
+    main_block.add_child(main_return);
+    let mut main_node = ASTNode::new(NodeType::FunctionDeclaration);
+    main_node.add_child(ASTNode::new(NodeType::Identifier("main".to_string())));
+    main_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    main_node.add_child(main_block);
+
+    let mut tle = ASTNode::new(NodeType::TopLevelExpression);
+    tle.add_child(prototype_node);
+    tle.add_child(main_node);
+    let ast = AST::new(tle);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    sts_global.add("sum".to_string(), SymbolInfo::new(DataType::Integer, SymbolValue::FunctionValue {
+        parameters: vec![("count".to_string(), DataType::Integer)],
+    }));
+    sts_global.add("main".to_string(), SymbolInfo::new(DataType::Integer, SymbolValue::FunctionValue {
+        parameters: Vec::new(),
+    }));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    let test_str = match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    };
+
+    // The arguments after the parameters are passed to the variadic function as they are
+    assert!(test_str.contains("declare i64 @sum(i64, ...)"), "{}", test_str);
+    assert!(test_str.contains("call i64 (i64, ...) @sum(i64 2, i64 3, i64 4)"), "{}", test_str);
+}
+
+#[test]
+fn test_switch_on_char() {
+    /*
+    enum Color { Red, Green, Blue };
+    int classify(unsigned char c) {
+        switch (c) {
+            case 'a': return 1;
+            case 200: return 2;
+            case Blue: return 3;
+        }
+        return 0;
+    }
+    */
+    let mut enum_node = ASTNode::new(NodeType::EnumDeclaration);
+    enum_node.add_child(ASTNode::new(NodeType::Identifier("Color".to_string())));
+    for name in ["Red", "Green", "Blue"] {
+        let mut variant = ASTNode::new(NodeType::Variant);
+        variant.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+        enum_node.add_child(variant);
+    }
+
+    let mut arms = ASTNode::new(NodeType::BlockExpression);
+    for (label, result) in [("'a'", "1"), ("200", "2"), ("Blue", "3")] {
+        let mut case_return = ASTNode::new(NodeType::Return);
+        case_return.add_child(ASTNode::new(NodeType::Literal(result.to_string())));
+        let mut case_block = ASTNode::new(NodeType::BlockExpression);
+        case_block.add_child(case_return);
+        let mut case_node = ASTNode::new(NodeType::Case);
+        if label == "Blue" {
+            case_node.add_child(ASTNode::new(NodeType::Identifier(label.to_string())));
+        } else {
+            case_node.add_child(ASTNode::new(NodeType::Literal(label.to_string())));
+        }
+        case_node.add_child(case_block);
+        arms.add_child(case_node);
+    }
+    let mut switch_node = ASTNode::new(NodeType::SwitchStatement);
+    switch_node.add_child(ASTNode::new(NodeType::Identifier("c".to_string())));
+    switch_node.add_child(arms);
+
+    let mut return_node = ASTNode::new(NodeType::Return);
+    return_node.add_child(ASTNode::new(NodeType::Literal("0".to_string())));
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    fn_block.add_child(switch_node);
+    fn_block.add_child(return_node);
+
+    let mut param_node = ASTNode::new(NodeType::Parameter);
+    param_node.add_child(ASTNode::new(NodeType::Identifier("c".to_string())));
+    param_node.add_child(ASTNode::new(NodeType::Type(DataType::UnsignedChar)));
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier("classify".to_string())));
+    fn_declaration_node.add_child(param_node);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    fn_declaration_node.add_child(fn_block);
+
+    let mut tle = ASTNode::new(NodeType::TopLevelExpression);
+    tle.add_child(enum_node);
+    tle.add_child(fn_declaration_node);
+    let ast = AST::new(tle);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    let fn_value = SymbolValue::FunctionValue{
+        parameters: Vec::new(),
+    };
+    sts_global.add("classify".to_string(), SymbolInfo::new(DataType::Integer, fn_value));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    ir_generator.generate_ir(mod_ast);
+    let test_str = ir_generator.function_ir_text("classify");
+
+    // The char is sign extended, and 200 is truncated to the char it is compared with
+    assert!(test_str.contains("sext i8 %vrecallID1 to i64"), "{}", test_str);
+    assert!(test_str.contains("icmp eq i64 %switchext, 97"), "{}", test_str);
+    assert!(test_str.contains("icmp eq i64 %switchext, -56"), "{}", test_str);
+    assert!(test_str.contains("icmp eq i64 %switchext, 2"), "{}", test_str);
+}
+
+#[test]
+fn test_evaluation_order() {
+    /*
+    int g();
+    int h();
+    int pair(int a, int b);
+    int main() {
+        return pair(g(), h()) - h();
+    }
+    */
+    let prototype = |name: &str, parameters: &[&str]| {
+        let mut prototype_node = ASTNode::new(NodeType::FunctionPrototype);
+        prototype_node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+        for parameter in parameters {
+            let mut param_node = ASTNode::new(NodeType::Parameter);
+            param_node.add_child(ASTNode::new(NodeType::Identifier(parameter.to_string())));
+            param_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+            prototype_node.add_child(param_node);
+        }
+        prototype_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+        prototype_node
+    };
+    let call = |name: &str, arguments: Vec<ASTNode>| {
+        let mut call_node = ASTNode::new(NodeType::FunctionCall);
+        call_node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+        for argument in arguments {
+            call_node.add_child(argument);
+        }
+        call_node
+    };
+
+    let mut difference = ASTNode::new(NodeType::BinaryExpression);
+    difference.add_child(call("pair", vec![call("g", Vec::new()), call("h", Vec::new())]));
+    difference.add_child(ASTNode::new(NodeType::Operator("-".to_string())));
+    difference.add_child(call("h", Vec::new()));
+    let mut main_return = ASTNode::new(NodeType::Return);
+    main_return.add_child(difference);
+    let mut main_block = ASTNode::new(NodeType::BlockExpression);
+    main_block.add_child(main_return);
+    let mut main_node = ASTNode::new(NodeType::FunctionDeclaration);
+    main_node.add_child(ASTNode::new(NodeType::Identifier("main".to_string())));
+    main_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    main_node.add_child(main_block);
+
+    let mut tle = ASTNode::new(NodeType::TopLevelExpression);
+    tle.add_child(prototype("g", &[]));
+    tle.add_child(prototype("h", &[]));
+    tle.add_child(prototype("pair", &["a", "b"]));
+    tle.add_child(main_node);
+    let ast = AST::new(tle);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    for name in ["g", "h", "main"] {
+        sts_global.add(name.to_string(), SymbolInfo::new(DataType::Integer, SymbolValue::FunctionValue {
+            parameters: Vec::new(),
+        }));
+    }
+    sts_global.add("pair".to_string(), SymbolInfo::new(DataType::Integer, SymbolValue::FunctionValue {
+        parameters: vec![("a".to_string(), DataType::Integer), ("b".to_string(), DataType::Integer)],
+    }));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    let test_str = match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    };
+
+    // Arguments and operands are evaluated from left to right, so the calls appear in the order they are written
+    let g_call = test_str.find("call i64 @g()").expect(&test_str);
+    let first_h_call = test_str.find("call i64 @h()").expect(&test_str);
+    let pair_call = test_str.find("call i64 @pair(").expect(&test_str);
+    let last_h_call = test_str.rfind("call i64 @h()").expect(&test_str);
+    assert!(g_call < first_h_call && first_h_call < pair_call && pair_call < last_h_call, "{}", test_str);
+}
+
+#[test]
+fn test_struct_argument_copied_before_later_side_effects() {
+    /*
+    struct P [ x: int, y: int ]
+    int reset();
+    void show(struct P p, int n) {}
+    int main() {
+        struct P q;
+        show(q, reset());
+    }
+    */
+    let struct_type = || {
+        let mut type_node = ASTNode::new(NodeType::Type(DataType::Struct));
+        type_node.add_child(ASTNode::new(NodeType::Identifier("P".to_string())));
+        type_node
+    };
+
+    let mut struct_node = ASTNode::new(NodeType::StructDeclaration);
+    struct_node.add_child(ASTNode::new(NodeType::Identifier("P".to_string())));
+    for field_name in ["x", "y"] {
+        let mut field = ASTNode::new(NodeType::Field);
+        field.add_child(ASTNode::new(NodeType::Literal(field_name.to_string())));
+        field.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+        struct_node.add_child(field);
+    }
+
+    let mut reset_node = ASTNode::new(NodeType::FunctionPrototype);
+    reset_node.add_child(ASTNode::new(NodeType::Identifier("reset".to_string())));
+    reset_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+
+    let mut struct_param = ASTNode::new(NodeType::Parameter);
+    struct_param.add_child(ASTNode::new(NodeType::Identifier("p".to_string())));
+    struct_param.add_child(struct_type());
+    let mut int_param = ASTNode::new(NodeType::Parameter);
+    int_param.add_child(ASTNode::new(NodeType::Identifier("n".to_string())));
+    int_param.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+
+    let mut show_node = ASTNode::new(NodeType::FunctionDeclaration);
+    show_node.add_child(ASTNode::new(NodeType::Identifier("show".to_string())));
+    show_node.add_child(struct_param);
+    show_node.add_child(int_param);
+    show_node.add_child(ASTNode::new(NodeType::Type(DataType::Void)));
+    show_node.add_child(ASTNode::new(NodeType::BlockExpression));
+
+    let mut var_node = ASTNode::new(NodeType::Variable);
+    var_node.add_child(ASTNode::new(NodeType::Identifier("q".to_string())));
+    var_node.add_child(struct_type());
+    let mut init_node = ASTNode::new(NodeType::Initialization);
+    init_node.add_child(var_node);
+
+    let mut reset_call = ASTNode::new(NodeType::FunctionCall);
+    reset_call.add_child(ASTNode::new(NodeType::Identifier("reset".to_string())));
+    let mut call_node = ASTNode::new(NodeType::FunctionCall);
+    call_node.add_child(ASTNode::new(NodeType::Identifier("show".to_string())));
+    call_node.add_child(ASTNode::new(NodeType::Identifier("q".to_string())));
+    call_node.add_child(reset_call);
+
+    let mut main_block = ASTNode::new(NodeType::BlockExpression);
+    main_block.add_child(init_node);
+    main_block.add_child(call_node);
+
+    let mut main_node = ASTNode::new(NodeType::FunctionDeclaration);
+    main_node.add_child(ASTNode::new(NodeType::Identifier("main".to_string())));
+    main_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    main_node.add_child(main_block);
+
+    let mut tle = ASTNode::new(NodeType::TopLevelExpression);
+    tle.add_child(struct_node);
+    tle.add_child(reset_node);
+    tle.add_child(show_node);
+    tle.add_child(main_node);
+    let ast = AST::new(tle);
+
+    let mut sts_stack = SymbolTableStack::new();
+    let mut sts_global = SymbolTable::new();
+    sts_global.add("reset".to_string(), SymbolInfo::new(DataType::Integer, SymbolValue::FunctionValue {
+        parameters: Vec::new(),
+    }));
+    sts_global.add("show".to_string(), SymbolInfo::new(DataType::Void, SymbolValue::FunctionValue {
+        parameters: vec![("p".to_string(), DataType::Struct), ("n".to_string(), DataType::Integer)],
+    }));
+    sts_global.add("main".to_string(), SymbolInfo::new(DataType::Integer, SymbolValue::FunctionValue {
+        parameters: Vec::new(),
+    }));
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(ast, sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    let module_tag = ir_generator.generate_ir(mod_ast);
+
+    let pools = ir_generator.get_resource_pools();
+    let module = pools.lock().expect("coouldn't unlock pools mutex").get_module(module_tag).expect("No module found!");
+    let test_str = match io::write_to_string(module) {
+        Ok(str) => str,
+        Err(e) => panic!("{}", e)
+    };
+
+    // The struct is copied before reset() runs, and the copy is what the callee receives
+    let copy = test_str.find("load %P, ptr").expect(&test_str);
+    let reset = test_str.find("call i64 @reset()").expect(&test_str);
+    assert!(copy < reset, "{}", test_str);
+    assert!(test_str.contains("call void @show(ptr byval(%P) %argcopy"), "{}", test_str);
+}
+
+/// Builds `int name(int a, int b) { return returned; }`, with its symbol in `sts_global`.
+fn two_parameter_function(name: &str, returned: &str, sts_global: &mut SymbolTable) -> ASTNode {
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+    for param_name in ["a", "b"] {
+        let mut param_node = ASTNode::new(NodeType::Parameter);
+        param_node.add_child(ASTNode::new(NodeType::Identifier(param_name.to_string())));
+        param_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+        fn_declaration_node.add_child(param_node);
+    }
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+
+    let mut return_node = ASTNode::new(NodeType::Return);
+    return_node.add_child(ASTNode::new(NodeType::Identifier(returned.to_string())));
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    fn_block.add_child(return_node);
+    fn_declaration_node.add_child(fn_block);
+
+    sts_global.add(name.to_string(), SymbolInfo::new(DataType::Integer, SymbolValue::FunctionValue {
+        parameters: vec![("a".to_string(), DataType::Integer), ("b".to_string(), DataType::Integer)],
+    }));
+    fn_declaration_node
+}
+
+/// Builds `int (*name)(int, int) = &target;`.
+fn function_pointer_initialization(name: &str, target: &str) -> ASTNode {
+    let mut function_type_node = ASTNode::new(NodeType::Type(DataType::Function));
+    for _ in 0..3 {
+        function_type_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    }
+    let mut var_node = ASTNode::new(NodeType::Variable);
+    var_node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+    var_node.add_child(function_type_node);
+
+    let mut address_of_node = ASTNode::new(NodeType::UnaryExpression);
+    address_of_node.add_child(ASTNode::new(NodeType::Operator("&".to_string())));
+    address_of_node.add_child(ASTNode::new(NodeType::Identifier(target.to_string())));
+    let mut value_node = ASTNode::new(NodeType::AssignedValue);
+    value_node.add_child(address_of_node);
+
+    let mut initialization_node = ASTNode::new(NodeType::Initialization);
+    initialization_node.add_child(var_node);
+    initialization_node.add_child(value_node);
+    initialization_node
+}
+
+/// Builds a call of `callee` with the integer arguments `args`.
+fn call_with_integers(callee: ASTNode, args: &[&str]) -> ASTNode {
+    let mut call_node = ASTNode::new(NodeType::FunctionCall);
+    call_node.add_child(callee);
+    for arg in args {
+        call_node.add_child(ASTNode::new(NodeType::Literal(arg.to_string())));
+    }
+    call_node
+}
+
+/// Builds a `UnaryExpression` applying `operator` to the function or variable `name`.
+fn unary_on_name(operator: &str, name: &str) -> ASTNode {
+    let mut unary_node = ASTNode::new(NodeType::UnaryExpression);
+    unary_node.add_child(ASTNode::new(NodeType::Operator(operator.to_string())));
+    unary_node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+    unary_node
+}
+
+/// Builds `int name() { statements }`, with its symbol in `sts_global`.
+fn function_without_parameters(name: &str, statements: Vec<ASTNode>, sts_global: &mut SymbolTable) -> ASTNode {
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    for statement in statements {
+        fn_block.add_child(statement);
+    }
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    fn_declaration_node.add_child(fn_block);
+
+    sts_global.add(name.to_string(), SymbolInfo::new(DataType::Integer, SymbolValue::FunctionValue {
+        parameters: Vec::new(),
+    }));
+    fn_declaration_node
+}
+
+/// Generates IR for the functions in `functions`, whose symbols are in `sts_global`.
+fn generate_functions_ir(functions: Vec<ASTNode>, sts_global: SymbolTable) -> IRGenerator {
+    let mut tle = ASTNode::new(NodeType::TopLevelExpression);
+    for function in functions {
+        tle.add_child(function);
+    }
+    let mut sts_stack = SymbolTableStack::new();
+    sts_stack.push(sts_global);
+    sts_stack.push(SymbolTable::new());
+
+    let mod_ast: Module = ast_stitch(vec![ModElement::new(AST::new(tle), sts_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+
+    let mut ir_generator = IRGenerator::new();
+    ir_generator.generate_ir(mod_ast);
+    ir_generator
+}
+
+#[test]
+fn test_function_pointer_scoped_to_its_function() {
+    /*
+    int add(int a, int b) { return a; }
+    int first() {
+        int (*op)(int, int) = &add;
+        return (*op)(1, 2);
+    }
+    int second() {
+        int op = 3;
+        return op;
+    }
+    */
+    let mut sts_global = SymbolTable::new();
+    let add = two_parameter_function("add", "a", &mut sts_global);
+
+    let mut first_return = ASTNode::new(NodeType::Return);
+    first_return.add_child(call_with_integers(unary_on_name("*", "op"), &["1", "2"]));
+    let first = function_without_parameters("first", vec![function_pointer_initialization("op", "add"), first_return], &mut sts_global);
+
+    let mut op_var = ASTNode::new(NodeType::Variable);
+    op_var.add_child(ASTNode::new(NodeType::Identifier("op".to_string())));
+    op_var.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    let mut op_value = ASTNode::new(NodeType::AssignedValue);
+    op_value.add_child(ASTNode::new(NodeType::Literal("3".to_string())));
+    let mut op_initialization = ASTNode::new(NodeType::Initialization);
+    op_initialization.add_child(op_var);
+    op_initialization.add_child(op_value);
+    let mut second_return = ASTNode::new(NodeType::Return);
+    second_return.add_child(ASTNode::new(NodeType::Identifier("op".to_string())));
+    let second = function_without_parameters("second", vec![op_initialization, second_return], &mut sts_global);
+
+    let mut ir_generator = generate_functions_ir(vec![add, first, second], sts_global);
+
+    // `(*op)` calls through the pointer, and the `op` of `second` is an integer, not the pointer of `first`
+    let first_ir = ir_generator.function_ir_text("first");
+    assert!(first_ir.contains("load ptr, ptr %op"), "{}", first_ir);
+    assert!(first_ir.contains("call i64 %"), "{}", first_ir);
+    assert!(!first_ir.contains("call i64 @add"), "{}", first_ir);
+    let second_ir = ir_generator.function_ir_text("second");
+    assert!(second_ir.contains("load i64, ptr %op"), "{}", second_ir);
+    assert!(!second_ir.contains("load ptr"), "{}", second_ir);
+}
+
+#[test]
+fn test_local_function_pointer_shadows_function() {
+    /*
+    int add(int a, int b) { return a; }
+    int sub(int a, int b) { return b; }
+    int pick() {
+        int (*add)(int, int) = &sub;
+        return add(1, 2) + (&sub)(3, 4);
+    }
+    */
+    let mut sts_global = SymbolTable::new();
+    let add = two_parameter_function("add", "a", &mut sts_global);
+    let sub = two_parameter_function("sub", "b", &mut sts_global);
+
+    let mut sum = ASTNode::new(NodeType::BinaryExpression);
+    sum.add_child(call_with_integers(ASTNode::new(NodeType::Identifier("add".to_string())), &["1", "2"]));
+    sum.add_child(ASTNode::new(NodeType::Operator("+".to_string())));
+    sum.add_child(call_with_integers(unary_on_name("&", "sub"), &["3", "4"]));
+    let mut pick_return = ASTNode::new(NodeType::Return);
+    pick_return.add_child(sum);
+    let pick = function_without_parameters("pick", vec![function_pointer_initialization("add", "sub"), pick_return], &mut sts_global);
+
+    let mut ir_generator = generate_functions_ir(vec![add, sub, pick], sts_global);
+
+    // `add` names the local pointer, so it is called indirectly, while `(&sub)` is a direct call
+    let pick_ir = ir_generator.function_ir_text("pick");
+    assert!(pick_ir.contains("store ptr @sub, ptr %add"), "{}", pick_ir);
+    assert!(pick_ir.contains("call i64 %"), "{}", pick_ir);
+    assert!(!pick_ir.contains("call i64 @add"), "{}", pick_ir);
+    assert!(pick_ir.contains("call i64 @sub(i64 3, i64 4)"), "{}", pick_ir);
+}
+
+/// Builds `int name = value;`, with a `volatile` qualifier on its type if `volatile` is set.
+fn integer_initialization(name: &str, value: &str, volatile: bool) -> ASTNode {
+    let mut type_node = ASTNode::new(NodeType::Type(DataType::Integer));
+    if volatile {
+        type_node.add_child(ASTNode::new(NodeType::Qualifier("volatile".to_string())));
+    }
+    let mut var_node = ASTNode::new(NodeType::Variable);
+    var_node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+    var_node.add_child(type_node);
+    let mut value_node = ASTNode::new(NodeType::AssignedValue);
+    value_node.add_child(ASTNode::new(NodeType::Literal(value.to_string())));
+
+    let mut initialization_node = ASTNode::new(NodeType::Initialization);
+    initialization_node.add_child(var_node);
+    initialization_node.add_child(value_node);
+    initialization_node
+}
+
+#[test]
+fn test_volatile_scoped_to_its_declaration() {
+    /*
+    int first() {
+        volatile int x = 1;
+        return x;
+    }
+    int second() {
+        int x = 2;
+        return x;
+    }
+    */
+    let mut sts_global = SymbolTable::new();
+    let mut functions = Vec::new();
+    for (name, value, volatile) in [("first", "1", true), ("second", "2", false)] {
+        let mut return_node = ASTNode::new(NodeType::Return);
+        return_node.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+        let statements = vec![integer_initialization("x", value, volatile), return_node];
+        functions.push(function_without_parameters(name, statements, &mut sts_global));
+    }
+
+    let mut ir_generator = generate_functions_ir(functions, sts_global);
+
+    // Only the `x` declared volatile has volatile accesses, not the later `x` of the same name
+    let first_ir = ir_generator.function_ir_text("first");
+    assert!(first_ir.contains("store volatile i64 1, ptr %x"), "{}", first_ir);
+    assert!(first_ir.contains("load volatile i64, ptr %x"), "{}", first_ir);
+    let second_ir = ir_generator.function_ir_text("second");
+    assert!(second_ir.contains("store i64 2, ptr %x"), "{}", second_ir);
+    assert!(!second_ir.contains("volatile"), "{}", second_ir);
+}
+
+#[test]
+fn test_conditions_lowered_by_operand_type() {
+    /*
+    int testConditions(float f, double d, bool b, int n) {
+        if (f) { return 1; }
+        if (d) { return 2; }
+        if (b) { return 3; }
+        if (n) { return 4; }
+        if (n != 4) { return 5; }
+        return 0;
+    }
+    */
+    let params = [("f", DataType::Float), ("d", DataType::Double), ("b", DataType::Boolean), ("n", DataType::Integer)];
+    let mut fn_declaration_node = ASTNode::new(NodeType::FunctionDeclaration);
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Identifier("testConditions".to_string())));
+    for (name, data_type) in params {
+        let mut param_node = ASTNode::new(NodeType::Parameter);
+        param_node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+        param_node.add_child(ASTNode::new(NodeType::Type(data_type)));
+        fn_declaration_node.add_child(param_node);
+    }
+    fn_declaration_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+
+    let variable = |name: &str| {
+        let mut var_node = ASTNode::new(NodeType::Variable);
+        var_node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+        var_node
+    };
+    let mut n_not_four = ASTNode::new(NodeType::BinaryExpression);
+    n_not_four.add_child(variable("n"));
+    n_not_four.add_child(ASTNode::new(NodeType::Operator("!=".to_string())));
+    n_not_four.add_child(ASTNode::new(NodeType::Literal("4".to_string())));
+
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    let conditions = [variable("f"), variable("d"), variable("b"), variable("n"), n_not_four];
+    for (index, condition) in conditions.into_iter().enumerate() {
+        let mut condition_node = ASTNode::new(NodeType::Condition);
+        condition_node.add_child(condition);
+        let mut then_return = ASTNode::new(NodeType::Return);
+        then_return.add_child(ASTNode::new(NodeType::Literal((index + 1).to_string())));
+        let mut then_branch = ASTNode::new(NodeType::BlockExpression);
+        then_branch.add_child(then_return);
+
+        let mut if_statement = ASTNode::new(NodeType::IfStatement);
+        if_statement.add_child(condition_node);
+        if_statement.add_child(then_branch);
+        fn_block.add_child(if_statement);
+    }
+    let mut final_return = ASTNode::new(NodeType::Return);
+    final_return.add_child(ASTNode::new(NodeType::Literal("0".to_string())));
+    fn_block.add_child(final_return);
+    fn_declaration_node.add_child(fn_block);
+
+    let mut sts_global = SymbolTable::new();
+    sts_global.add("testConditions".to_string(), SymbolInfo::new(DataType::Integer, SymbolValue::FunctionValue {
+        parameters: params.iter().map(|(name, data_type)| (name.to_string(), *data_type)).collect(),
+    }));
+    let mut ir_generator = generate_functions_ir(vec![fn_declaration_node], sts_global);
+    let test_str = ir_generator.function_ir_text("testConditions");
+
+    // Floating-point values are compared against a zero of their own type, booleans are used as they are, and
+    // integers are compared against zero, except for a comparison, which is already an `i1`
+    assert!(test_str.contains("fcmp one float"), "{}", test_str);
+    assert!(test_str.contains("fcmp one double"), "{}", test_str);
+    assert!(test_str.contains("load i1, ptr %b"), "{}", test_str);
+    assert!(!test_str.contains("icmp eq i1"), "{}", test_str);
+    assert_eq!(test_str.matches("icmp eq i64").count(), 2, "{}", test_str);
+    assert!(!test_str.contains("icmp eq float") && !test_str.contains("icmp eq double"), "{}", test_str);
+}
+
+#[test]
+fn test_boolean_scoped_to_its_declaration() {
+    /*
+    int first() {
+        bool b = 1;
+        return 0;
+    }
+    int second() {
+        int b = 2;
+        int c = b;
+        b = 3;
+        return c;
+    }
+    */
+    let mut sts_global = SymbolTable::new();
+    let mut b_var = ASTNode::new(NodeType::Variable);
+    b_var.add_child(ASTNode::new(NodeType::Identifier("b".to_string())));
+    b_var.add_child(ASTNode::new(NodeType::Type(DataType::Boolean)));
+    let mut b_value = ASTNode::new(NodeType::AssignedValue);
+    b_value.add_child(ASTNode::new(NodeType::Literal("1".to_string())));
+    let mut b_initialization = ASTNode::new(NodeType::Initialization);
+    b_initialization.add_child(b_var);
+    b_initialization.add_child(b_value);
+    let mut first_return = ASTNode::new(NodeType::Return);
+    first_return.add_child(ASTNode::new(NodeType::Literal("0".to_string())));
+    let first = function_without_parameters("first", vec![b_initialization, first_return], &mut sts_global);
+
+    let mut c_var = ASTNode::new(NodeType::Variable);
+    c_var.add_child(ASTNode::new(NodeType::Identifier("c".to_string())));
+    c_var.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    let mut b_read = ASTNode::new(NodeType::Variable);
+    b_read.add_child(ASTNode::new(NodeType::Identifier("b".to_string())));
+    let mut c_value = ASTNode::new(NodeType::AssignedValue);
+    c_value.add_child(b_read);
+    let mut c_initialization = ASTNode::new(NodeType::Initialization);
+    c_initialization.add_child(c_var);
+    c_initialization.add_child(c_value);
+    let mut b_assignment = ASTNode::new(NodeType::Assignment);
+    b_assignment.add_child(ASTNode::new(NodeType::Identifier("b".to_string())));
+    b_assignment.add_child(ASTNode::new(NodeType::Literal("3".to_string())));
+    let mut second_return = ASTNode::new(NodeType::Return);
+    second_return.add_child(ASTNode::new(NodeType::Identifier("c".to_string())));
+    let statements = vec![integer_initialization("b", "2", false), c_initialization, b_assignment, second_return];
+    let second = function_without_parameters("second", statements, &mut sts_global);
+
+    let mut ir_generator = generate_functions_ir(vec![first, second], sts_global);
+
+    // Only the `b` declared `bool` is an `i1`, so the `int b` of `second` is read and assigned as an `i64`
+    let first_ir = ir_generator.function_ir_text("first");
+    assert!(first_ir.contains("%b = alloca i1"), "{}", first_ir);
+    let second_ir = ir_generator.function_ir_text("second");
+    assert!(second_ir.contains("load i64, ptr %b"), "{}", second_ir);
+    assert!(second_ir.contains("store i64 3, ptr %b"), "{}", second_ir);
+    assert!(!second_ir.contains(" i1"), "{}", second_ir);
+}
+
+#[test]
+fn test_parameter_type_scoped_to_its_function() {
+    /*
+    float f(float x) { return x; }
+    int main() {
+        int x = 3;
+        return x;
+    }
+    */
+    let mut sts_global = SymbolTable::new();
+    let mut param_node = ASTNode::new(NodeType::Parameter);
+    param_node.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    param_node.add_child(ASTNode::new(NodeType::Type(DataType::Float)));
+    let mut f_return = ASTNode::new(NodeType::Return);
+    f_return.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    let mut f_block = ASTNode::new(NodeType::BlockExpression);
+    f_block.add_child(f_return);
+    let mut f = ASTNode::new(NodeType::FunctionDeclaration);
+    f.add_child(ASTNode::new(NodeType::Identifier("f".to_string())));
+    f.add_child(param_node);
+    f.add_child(ASTNode::new(NodeType::Type(DataType::Float)));
+    f.add_child(f_block);
+    sts_global.add("f".to_string(), SymbolInfo::new(DataType::Float, SymbolValue::FunctionValue {
+        parameters: vec![("x".to_string(), DataType::Float)],
+    }));
+
+    let mut main_return = ASTNode::new(NodeType::Return);
+    main_return.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    let main = function_without_parameters("main", vec![integer_initialization("x", "3", false), main_return], &mut sts_global);
+
+    let mut ir_generator = generate_functions_ir(vec![f, main], sts_global);
+
+    // The parameter `x` of `f` is a float only within `f`, so the local `x` of `main` is loaded as an integer
+    let f_ir = ir_generator.function_ir_text("f");
+    assert!(f_ir.contains("load float, ptr %x"), "{}", f_ir);
+    let main_ir = ir_generator.function_ir_text("main");
+    assert!(main_ir.contains("load i64, ptr %x"), "{}", main_ir);
+    assert!(!main_ir.contains("float"), "{}", main_ir);
+}
+
+#[test]
+fn test_struct_variable_type_scoped_to_its_function() {
+    /*
+    struct P [ x: int, y: int ]
+    int first() {
+        struct P p;
+        return 0;
+    }
+    int second() {
+        int p = 1;
+        return p;
+    }
+    */
+    let mut struct_node = ASTNode::new(NodeType::StructDeclaration);
+    struct_node.add_child(ASTNode::new(NodeType::Identifier("P".to_string())));
+    for field_name in ["x", "y"] {
+        let mut field = ASTNode::new(NodeType::Field);
+        field.add_child(ASTNode::new(NodeType::Literal(field_name.to_string())));
+        field.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+        struct_node.add_child(field);
+    }
+
+    let mut sts_global = SymbolTable::new();
+    let mut struct_type = ASTNode::new(NodeType::Type(DataType::Struct));
+    struct_type.add_child(ASTNode::new(NodeType::Identifier("P".to_string())));
+    let mut var_node = ASTNode::new(NodeType::Variable);
+    var_node.add_child(ASTNode::new(NodeType::Identifier("p".to_string())));
+    var_node.add_child(struct_type);
+    let mut p_initialization = ASTNode::new(NodeType::Initialization);
+    p_initialization.add_child(var_node);
+    let mut first_return = ASTNode::new(NodeType::Return);
+    first_return.add_child(ASTNode::new(NodeType::Literal("0".to_string())));
+    let first = function_without_parameters("first", vec![p_initialization, first_return], &mut sts_global);
+
+    let mut second_return = ASTNode::new(NodeType::Return);
+    second_return.add_child(ASTNode::new(NodeType::Identifier("p".to_string())));
+    let second = function_without_parameters("second", vec![integer_initialization("p", "1", false), second_return], &mut sts_global);
+
+    let mut ir_generator = generate_functions_ir(vec![struct_node, first, second], sts_global);
+
+    // Only the `p` of `first` is a struct, so the `p` of `second` is loaded as an integer
+    let first_ir = ir_generator.function_ir_text("first");
+    assert!(first_ir.contains("%p = alloca %P"), "{}", first_ir);
+    let second_ir = ir_generator.function_ir_text("second");
+    assert!(second_ir.contains("load i64, ptr %p"), "{}", second_ir);
+    assert!(!second_ir.contains("%P"), "{}", second_ir);
+}
+
+#[test]
+fn test_same_name_declared_with_different_types() {
+    /*
+    int first() {
+        double v = 1.5;
+        return 0;
+    }
+    int second() {
+        char v = 'a';
+        return v;
+    }
+    */
+    let initialization = |data_type: DataType, value: &str| {
+        let mut var_node = ASTNode::new(NodeType::Variable);
+        var_node.add_child(ASTNode::new(NodeType::Identifier("v".to_string())));
+        var_node.add_child(ASTNode::new(NodeType::Type(data_type)));
+        let mut value_node = ASTNode::new(NodeType::AssignedValue);
+        value_node.add_child(ASTNode::new(NodeType::Literal(value.to_string())));
+        let mut initialization_node = ASTNode::new(NodeType::Initialization);
+        initialization_node.add_child(var_node);
+        initialization_node.add_child(value_node);
+        initialization_node
+    };
+    let mut sts_global = SymbolTable::new();
+    let mut first_return = ASTNode::new(NodeType::Return);
+    first_return.add_child(ASTNode::new(NodeType::Literal("0".to_string())));
+    let first = function_without_parameters("first", vec![initialization(DataType::Double, "1.5"), first_return], &mut sts_global);
+    let mut second_return = ASTNode::new(NodeType::Return);
+    second_return.add_child(ASTNode::new(NodeType::Identifier("v".to_string())));
+    let second = function_without_parameters("second", vec![initialization(DataType::Char, "'a'"), second_return], &mut sts_global);
+
+    let mut ir_generator = generate_functions_ir(vec![first, second], sts_global);
+
+    // Each `v` is stored as the type it was declared with in its own function, and nothing of the `double` is
+    // carried into `second`
+    let first_ir = ir_generator.function_ir_text("first");
+    assert!(first_ir.contains("%v = alloca double"), "{}", first_ir);
+    assert!(first_ir.contains("store double 1.500000e+00, ptr %v"), "{}", first_ir);
+    let second_ir = ir_generator.function_ir_text("second");
+    assert!(second_ir.contains("%v = alloca i8"), "{}", second_ir);
+    assert!(second_ir.contains("store i8 97, ptr %v"), "{}", second_ir);
+    assert!(second_ir.contains("load i8, ptr %v"), "{}", second_ir);
+    assert!(!second_ir.contains("double"), "{}", second_ir);
+}
+
+#[test]
+fn test_conditions_on_chars_and_pointers() {
+    /*
+    int add(int a, int b) { return a; }
+    int check(char c) {
+        int (*op)(int, int) = &add;
+        if (c) { return 1; }
+        if (op) { return 2; }
+        return 0;
+    }
+    */
+    let mut sts_global = SymbolTable::new();
+    let add = two_parameter_function("add", "a", &mut sts_global);
+
+    let mut param_node = ASTNode::new(NodeType::Parameter);
+    param_node.add_child(ASTNode::new(NodeType::Identifier("c".to_string())));
+    param_node.add_child(ASTNode::new(NodeType::Type(DataType::Char)));
+    let mut fn_block = ASTNode::new(NodeType::BlockExpression);
+    fn_block.add_child(function_pointer_initialization("op", "add"));
+    for (index, name) in ["c", "op"].into_iter().enumerate() {
+        let mut condition_node = ASTNode::new(NodeType::Condition);
+        condition_node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+        let mut then_return = ASTNode::new(NodeType::Return);
+        then_return.add_child(ASTNode::new(NodeType::Literal((index + 1).to_string())));
+        let mut then_branch = ASTNode::new(NodeType::BlockExpression);
+        then_branch.add_child(then_return);
+        let mut if_statement = ASTNode::new(NodeType::IfStatement);
+        if_statement.add_child(condition_node);
+        if_statement.add_child(then_branch);
+        fn_block.add_child(if_statement);
+    }
+    let mut final_return = ASTNode::new(NodeType::Return);
+    final_return.add_child(ASTNode::new(NodeType::Literal("0".to_string())));
+    fn_block.add_child(final_return);
+    let mut check = ASTNode::new(NodeType::FunctionDeclaration);
+    check.add_child(ASTNode::new(NodeType::Identifier("check".to_string())));
+    check.add_child(param_node);
+    check.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    check.add_child(fn_block);
+    sts_global.add("check".to_string(), SymbolInfo::new(DataType::Integer, SymbolValue::FunctionValue {
+        parameters: vec![("c".to_string(), DataType::Char)],
+    }));
+
+    let mut ir_generator = generate_functions_ir(vec![add, check], sts_global);
+    let test_str = ir_generator.function_ir_text("check");
+
+    // The `char` is compared against an `i8` zero and the function pointer against null
+    assert!(test_str.contains("load i8, ptr %c"), "{}", test_str);
+    assert!(test_str.contains("icmp eq i8"), "{}", test_str);
+    assert!(test_str.contains("icmp ne ptr"), "{}", test_str);
+    assert!(!test_str.contains("icmp eq i64"), "{}", test_str);
+}
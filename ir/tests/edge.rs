@@ -41,8 +41,12 @@ fn test_deeply_nested_loops1() {
     source_filename = "dummy_module"
 
     define i64 @testDeeplyNestedLoops() {
-    entryID0:
+    allocasID0:
       %i = alloca i64, align 8
+      %j = alloca i64, align 8
+      br label %entryID0
+
+    entryID0:                                         ; preds = %allocasID0
       store i64 5, ptr %i, align 4
       br label %for_condID1
 
@@ -59,7 +63,6 @@ fn test_deeply_nested_loops1() {
       br label %do_bodyID3
 
     do_bodyID3:                                       ; preds = %do_condID3, %while_bodyID2
-      %j = alloca i64, align 8
       store i64 6, ptr %j, align 4
       br label %for_condID4
 
@@ -77,9 +80,8 @@ fn test_deeply_nested_loops1() {
 
     do_bodyID6:                                       ; preds = %do_condID6, %while_bodyID5
       ret i64 0
-      br label %do_condID6
 
-    do_condID6:                                       ; preds = %do_bodyID6
+    do_condID6:                                       ; No predecessors!
       br i1 true, label %do_bodyID6, label %do_endID6
 
     do_endID6:                                        ; preds = %do_condID6
@@ -230,7 +232,7 @@ fn test_deeply_nested_loops1() {
         Ok(str) => str,
         Err(e) => panic!("{}", e)
     };
-    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testDeeplyNestedLoops() {\nentryID0:\n  %i = alloca i64, align 8\n  store i64 5, ptr %i, align 4\n  br label %for_condID1\n\nfor_condID1:                                      ; preds = %for_incID1, %entryID0\n  br i1 true, label %for_bodyID1, label %for_endID1\n\nfor_bodyID1:                                      ; preds = %for_condID1\n  br label %while_condID2\n\nwhile_condID2:                                    ; preds = %do_endID3, %for_bodyID1\n  br i1 true, label %while_bodyID2, label %while_endID2\n\nwhile_bodyID2:                                    ; preds = %while_condID2\n  br label %do_bodyID3\n\ndo_bodyID3:                                       ; preds = %do_condID3, %while_bodyID2\n  %j = alloca i64, align 8\n  store i64 6, ptr %j, align 4\n  br label %for_condID4\n\nfor_condID4:                                      ; preds = %for_incID4, %do_bodyID3\n  br i1 true, label %for_bodyID4, label %for_endID4\n\nfor_bodyID4:                                      ; preds = %for_condID4\n  br label %while_condID5\n\nwhile_condID5:                                    ; preds = %do_endID6, %for_bodyID4\n  br i1 true, label %while_bodyID5, label %while_endID5\n\nwhile_bodyID5:                                    ; preds = %while_condID5\n  br label %do_bodyID6\n\ndo_bodyID6:                                       ; preds = %do_condID6, %while_bodyID5\n  ret i64 0\n  br label %do_condID6\n\ndo_condID6:                                       ; preds = %do_bodyID6\n  br i1 true, label %do_bodyID6, label %do_endID6\n\ndo_endID6:                                        ; preds = %do_condID6\n  br label %while_condID5\n\nwhile_endID5:                                     ; preds = %while_condID5\n  br label %for_incID4\n\nfor_incID4:                                       ; preds = %while_endID5\n  br label %for_condID4\n\nfor_endID4:                                       ; preds = %for_condID4\n  br label %do_condID3\n\ndo_condID3:                                       ; preds = %for_endID4\n  br i1 true, label %do_bodyID3, label %do_endID3\n\ndo_endID3:                                        ; preds = %do_condID3\n  br label %while_condID2\n\nwhile_endID2:                                     ; preds = %while_condID2\n  br label %for_incID1\n\nfor_incID1:                                       ; preds = %while_endID2\n  br label %for_condID1\n\nfor_endID1:                                       ; preds = %for_condID1\n}\n";
+    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testDeeplyNestedLoops() {\nallocasID0:\n  %i = alloca i64, align 8\n  %j = alloca i64, align 8\n  br label %entryID0\n\nentryID0:                                         ; preds = %allocasID0\n  store i64 5, ptr %i, align 4\n  br label %for_condID1\n\nfor_condID1:                                      ; preds = %for_incID1, %entryID0\n  br i1 true, label %for_bodyID1, label %for_endID1\n\nfor_bodyID1:                                      ; preds = %for_condID1\n  br label %while_condID2\n\nwhile_condID2:                                    ; preds = %do_endID3, %for_bodyID1\n  br i1 true, label %while_bodyID2, label %while_endID2\n\nwhile_bodyID2:                                    ; preds = %while_condID2\n  br label %do_bodyID3\n\ndo_bodyID3:                                       ; preds = %do_condID3, %while_bodyID2\n  store i64 6, ptr %j, align 4\n  br label %for_condID4\n\nfor_condID4:                                      ; preds = %for_incID4, %do_bodyID3\n  br i1 true, label %for_bodyID4, label %for_endID4\n\nfor_bodyID4:                                      ; preds = %for_condID4\n  br label %while_condID5\n\nwhile_condID5:                                    ; preds = %do_endID6, %for_bodyID4\n  br i1 true, label %while_bodyID5, label %while_endID5\n\nwhile_bodyID5:                                    ; preds = %while_condID5\n  br label %do_bodyID6\n\ndo_bodyID6:                                       ; preds = %do_condID6, %while_bodyID5\n  ret i64 0\n\ndo_condID6:                                       ; No predecessors!\n  br i1 true, label %do_bodyID6, label %do_endID6\n\ndo_endID6:                                        ; preds = %do_condID6\n  br label %while_condID5\n\nwhile_endID5:                                     ; preds = %while_condID5\n  br label %for_incID4\n\nfor_incID4:                                       ; preds = %while_endID5\n  br label %for_condID4\n\nfor_endID4:                                       ; preds = %for_condID4\n  br label %do_condID3\n\ndo_condID3:                                       ; preds = %for_endID4\n  br i1 true, label %do_bodyID3, label %do_endID3\n\ndo_endID3:                                        ; preds = %do_condID3\n  br label %while_condID2\n\nwhile_endID2:                                     ; preds = %while_condID2\n  br label %for_incID1\n\nfor_incID1:                                       ; preds = %while_endID2\n  br label %for_condID1\n\nfor_endID1:                                       ; preds = %for_condID1\n}\n";
 
     assert_eq!(test_str, expected_str)
 }
@@ -257,7 +259,10 @@ fn test_deeply_nested_loops2() {
     source_filename = "dummy_module"
 
     define i64 @testSwappedWhileForLoops() {
-    entryID0:
+    allocasID0:
+      br label %entryID0
+
+    entryID0:                                         ; preds = %allocasID0
       br label %while_condID1
 
     while_condID1:                                    ; preds = %for_endID2, %entryID0
@@ -289,9 +294,8 @@ fn test_deeply_nested_loops2() {
 
     do_bodyID6:                                       ; preds = %do_condID6, %for_bodyID5
       ret i64 42
-      br label %do_condID6
 
-    do_condID6:                                       ; preds = %do_bodyID6
+    do_condID6:                                       ; No predecessors!
       br i1 true, label %do_bodyID6, label %do_endID6
 
     do_endID6:                                        ; preds = %do_condID6
@@ -413,7 +417,7 @@ fn test_deeply_nested_loops2() {
         Ok(str) => str,
         Err(e) => panic!("{}", e)
     };
-    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testSwappedWhileForLoops() {\nentryID0:\n  br label %while_condID1\n\nwhile_condID1:                                    ; preds = %for_endID2, %entryID0\n  br i1 true, label %while_bodyID1, label %while_endID1\n\nwhile_bodyID1:                                    ; preds = %while_condID1\n  br label %for_condID2\n\nfor_condID2:                                      ; preds = %for_incID2, %while_bodyID1\n  br i1 true, label %for_bodyID2, label %for_endID2\n\nfor_bodyID2:                                      ; preds = %for_condID2\n  br label %do_bodyID3\n\ndo_bodyID3:                                       ; preds = %do_condID3, %for_bodyID2\n  br label %while_condID4\n\nwhile_condID4:                                    ; preds = %for_endID5, %do_bodyID3\n  br i1 true, label %while_bodyID4, label %while_endID4\n\nwhile_bodyID4:                                    ; preds = %while_condID4\n  br label %for_condID5\n\nfor_condID5:                                      ; preds = %for_incID5, %while_bodyID4\n  br i1 true, label %for_bodyID5, label %for_endID5\n\nfor_bodyID5:                                      ; preds = %for_condID5\n  br label %do_bodyID6\n\ndo_bodyID6:                                       ; preds = %do_condID6, %for_bodyID5\n  ret i64 42\n  br label %do_condID6\n\ndo_condID6:                                       ; preds = %do_bodyID6\n  br i1 true, label %do_bodyID6, label %do_endID6\n\ndo_endID6:                                        ; preds = %do_condID6\n  br label %for_incID5\n\nfor_incID5:                                       ; preds = %do_endID6\n  br label %for_condID5\n\nfor_endID5:                                       ; preds = %for_condID5\n  br label %while_condID4\n\nwhile_endID4:                                     ; preds = %while_condID4\n  br label %do_condID3\n\ndo_condID3:                                       ; preds = %while_endID4\n  br i1 true, label %do_bodyID3, label %do_endID3\n\ndo_endID3:                                        ; preds = %do_condID3\n  br label %for_incID2\n\nfor_incID2:                                       ; preds = %do_endID3\n  br label %for_condID2\n\nfor_endID2:                                       ; preds = %for_condID2\n  br label %while_condID1\n\nwhile_endID1:                                     ; preds = %while_condID1\n}\n";
+    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testSwappedWhileForLoops() {\nallocasID0:\n  br label %entryID0\n\nentryID0:                                         ; preds = %allocasID0\n  br label %while_condID1\n\nwhile_condID1:                                    ; preds = %for_endID2, %entryID0\n  br i1 true, label %while_bodyID1, label %while_endID1\n\nwhile_bodyID1:                                    ; preds = %while_condID1\n  br label %for_condID2\n\nfor_condID2:                                      ; preds = %for_incID2, %while_bodyID1\n  br i1 true, label %for_bodyID2, label %for_endID2\n\nfor_bodyID2:                                      ; preds = %for_condID2\n  br label %do_bodyID3\n\ndo_bodyID3:                                       ; preds = %do_condID3, %for_bodyID2\n  br label %while_condID4\n\nwhile_condID4:                                    ; preds = %for_endID5, %do_bodyID3\n  br i1 true, label %while_bodyID4, label %while_endID4\n\nwhile_bodyID4:                                    ; preds = %while_condID4\n  br label %for_condID5\n\nfor_condID5:                                      ; preds = %for_incID5, %while_bodyID4\n  br i1 true, label %for_bodyID5, label %for_endID5\n\nfor_bodyID5:                                      ; preds = %for_condID5\n  br label %do_bodyID6\n\ndo_bodyID6:                                       ; preds = %do_condID6, %for_bodyID5\n  ret i64 42\n\ndo_condID6:                                       ; No predecessors!\n  br i1 true, label %do_bodyID6, label %do_endID6\n\ndo_endID6:                                        ; preds = %do_condID6\n  br label %for_incID5\n\nfor_incID5:                                       ; preds = %do_endID6\n  br label %for_condID5\n\nfor_endID5:                                       ; preds = %for_condID5\n  br label %while_condID4\n\nwhile_endID4:                                     ; preds = %while_condID4\n  br label %do_condID3\n\ndo_condID3:                                       ; preds = %while_endID4\n  br i1 true, label %do_bodyID3, label %do_endID3\n\ndo_endID3:                                        ; preds = %do_condID3\n  br label %for_incID2\n\nfor_incID2:                                       ; preds = %do_endID3\n  br label %for_condID2\n\nfor_endID2:                                       ; preds = %for_condID2\n  br label %while_condID1\n\nwhile_endID1:                                     ; preds = %while_condID1\n}\n";
 
     assert_eq!(test_str, expected_str)
 }
@@ -438,14 +442,17 @@ fn test_deply_nested_loops3() {
     source_filename = "dummy_module"
 
     define i64 @testMultipleDoWhileLoops3() {
-    entryID0:
+    allocasID0:
+      %i = alloca i64, align 8
+      br label %entryID0
+
+    entryID0:                                         ; preds = %allocasID0
       br label %do_bodyID1
 
     do_bodyID1:                                       ; preds = %do_condID1, %entryID0
       br label %do_bodyID2
 
     do_bodyID2:                                       ; preds = %do_condID2, %do_bodyID1
-      %i = alloca i64, align 8
       store i64 42, ptr %i, align 4
       br label %for_condID3
 
@@ -463,9 +470,8 @@ fn test_deply_nested_loops3() {
 
     do_bodyID5:                                       ; preds = %do_condID5, %while_bodyID4
       ret i64 42
-      br label %do_condID5
 
-    do_condID5:                                       ; preds = %do_bodyID5
+    do_condID5:                                       ; No predecessors!
       br i1 true, label %do_bodyID5, label %do_endID5
 
     do_endID5:                                        ; preds = %do_condID5
@@ -588,7 +594,7 @@ fn test_deply_nested_loops3() {
         Ok(str) => str,
         Err(e) => panic!("{}", e)
     };
-    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testMultipleDoWhileLoops3() {\nentryID0:\n  br label %do_bodyID1\n\ndo_bodyID1:                                       ; preds = %do_condID1, %entryID0\n  br label %do_bodyID2\n\ndo_bodyID2:                                       ; preds = %do_condID2, %do_bodyID1\n  %i = alloca i64, align 8\n  store i64 42, ptr %i, align 4\n  br label %for_condID3\n\nfor_condID3:                                      ; preds = %for_incID3, %do_bodyID2\n  br i1 true, label %for_bodyID3, label %for_endID3\n\nfor_bodyID3:                                      ; preds = %for_condID3\n  br label %while_condID4\n\nwhile_condID4:                                    ; preds = %do_endID5, %for_bodyID3\n  br i1 true, label %while_bodyID4, label %while_endID4\n\nwhile_bodyID4:                                    ; preds = %while_condID4\n  br label %do_bodyID5\n\ndo_bodyID5:                                       ; preds = %do_condID5, %while_bodyID4\n  ret i64 42\n  br label %do_condID5\n\ndo_condID5:                                       ; preds = %do_bodyID5\n  br i1 true, label %do_bodyID5, label %do_endID5\n\ndo_endID5:                                        ; preds = %do_condID5\n  br label %while_condID4\n\nwhile_endID4:                                     ; preds = %while_condID4\n  br label %for_incID3\n\nfor_incID3:                                       ; preds = %while_endID4\n  br label %for_condID3\n\nfor_endID3:                                       ; preds = %for_condID3\n  br label %do_condID2\n\ndo_condID2:                                       ; preds = %for_endID3\n  br i1 true, label %do_bodyID2, label %do_endID2\n\ndo_endID2:                                        ; preds = %do_condID2\n  br label %do_condID1\n\ndo_condID1:                                       ; preds = %do_endID2\n  br i1 true, label %do_bodyID1, label %do_endID1\n\ndo_endID1:                                        ; preds = %do_condID1\n}\n";
+    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testMultipleDoWhileLoops3() {\nallocasID0:\n  %i = alloca i64, align 8\n  br label %entryID0\n\nentryID0:                                         ; preds = %allocasID0\n  br label %do_bodyID1\n\ndo_bodyID1:                                       ; preds = %do_condID1, %entryID0\n  br label %do_bodyID2\n\ndo_bodyID2:                                       ; preds = %do_condID2, %do_bodyID1\n  store i64 42, ptr %i, align 4\n  br label %for_condID3\n\nfor_condID3:                                      ; preds = %for_incID3, %do_bodyID2\n  br i1 true, label %for_bodyID3, label %for_endID3\n\nfor_bodyID3:                                      ; preds = %for_condID3\n  br label %while_condID4\n\nwhile_condID4:                                    ; preds = %do_endID5, %for_bodyID3\n  br i1 true, label %while_bodyID4, label %while_endID4\n\nwhile_bodyID4:                                    ; preds = %while_condID4\n  br label %do_bodyID5\n\ndo_bodyID5:                                       ; preds = %do_condID5, %while_bodyID4\n  ret i64 42\n\ndo_condID5:                                       ; No predecessors!\n  br i1 true, label %do_bodyID5, label %do_endID5\n\ndo_endID5:                                        ; preds = %do_condID5\n  br label %while_condID4\n\nwhile_endID4:                                     ; preds = %while_condID4\n  br label %for_incID3\n\nfor_incID3:                                       ; preds = %while_endID4\n  br label %for_condID3\n\nfor_endID3:                                       ; preds = %for_condID3\n  br label %do_condID2\n\ndo_condID2:                                       ; preds = %for_endID3\n  br i1 true, label %do_bodyID2, label %do_endID2\n\ndo_endID2:                                        ; preds = %do_condID2\n  br label %do_condID1\n\ndo_condID1:                                       ; preds = %do_endID2\n  br i1 true, label %do_bodyID1, label %do_endID1\n\ndo_endID1:                                        ; preds = %do_condID1\n}\n";
 
     assert_eq!(test_str, expected_str)
 }
@@ -610,7 +616,10 @@ fn test_function_with_while_if_else_break_continue() {
     source_filename = "dummy_module"
 
     define i64 @testFunction() {
-    entryID0:
+    allocasID0:
+      br label %entryID0
+
+    entryID0:                                         ; preds = %allocasID0
       br label %while_condID1
 
     while_condID1:                                    ; preds = %mergeID2, %elseID2, %entryID0
@@ -621,13 +630,11 @@ fn test_function_with_while_if_else_break_continue() {
 
     thenID2:                                          ; preds = %while_bodyID1
       br label %while_endID1
-      br label %mergeID2
 
     elseID2:                                          ; preds = %while_bodyID1
       br label %while_condID1
-      br label %mergeID2
 
-    mergeID2:                                         ; preds = %elseID2, %thenID2
+    mergeID2:                                         ; No predecessors!
       br label %while_condID1
 
     while_endID1:                                     ; preds = %thenID2, %while_condID1
@@ -716,7 +723,7 @@ fn test_function_with_while_if_else_break_continue() {
         Ok(str) => str,
         Err(e) => panic!("{}", e)
     };
-    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunction() {\nentryID0:\n  br label %while_condID1\n\nwhile_condID1:                                    ; preds = %mergeID2, %elseID2, %entryID0\n  br i1 true, label %while_bodyID1, label %while_endID1\n\nwhile_bodyID1:                                    ; preds = %while_condID1\n  br i1 true, label %thenID2, label %elseID2\n\nthenID2:                                          ; preds = %while_bodyID1\n  br label %while_endID1\n  br label %mergeID2\n\nelseID2:                                          ; preds = %while_bodyID1\n  br label %while_condID1\n  br label %mergeID2\n\nmergeID2:                                         ; preds = %elseID2, %thenID2\n  br label %while_condID1\n\nwhile_endID1:                                     ; preds = %thenID2, %while_condID1\n}\n";
+    let expected_str = "; ModuleID = 'dummy_module'\nsource_filename = \"dummy_module\"\n\ndefine i64 @testFunction() {\nallocasID0:\n  br label %entryID0\n\nentryID0:                                         ; preds = %allocasID0\n  br label %while_condID1\n\nwhile_condID1:                                    ; preds = %mergeID2, %elseID2, %entryID0\n  br i1 true, label %while_bodyID1, label %while_endID1\n\nwhile_bodyID1:                                    ; preds = %while_condID1\n  br i1 true, label %thenID2, label %elseID2\n\nthenID2:                                          ; preds = %while_bodyID1\n  br label %while_endID1\n\nelseID2:                                          ; preds = %while_bodyID1\n  br label %while_condID1\n\nmergeID2:                                         ; No predecessors!\n  br label %while_condID1\n\nwhile_endID1:                                     ; preds = %thenID2, %while_condID1\n}\n";
 
     assert_eq!(test_str, expected_str)
 }
\ No newline at end of file
@@ -0,0 +1,55 @@
+//! This file provides constructors for building token vectors by hand, as parser tests and tools do, without
+//! spelling out each payload.
+//!
+//! The `tokens!` macro builds a vector from the names of token variants and the constructors of this module:
+//!
+//! ```
+//! use lexer::{token::Token, tokens};
+//! use common::symbol::Symbol;
+//!
+//! let tokens = tokens![TINTEGER, ident("x"), EQUAL, num(5), SEMICOLON, EOF];
+//! assert_eq!(tokens[1], Token::IDENTIFIER(Symbol::intern("x")));
+//! ```
+
+use std::fmt;
+
+use common::{literal::ByteString, symbol::Symbol};
+
+pub use crate::token::Token::*;
+use crate::token::Token;
+
+/// Builds an `IDENTIFIER` token.
+///
+/// # Parameters
+///
+/// * `name` - The name of the identifier.
+pub fn ident(name: &str) -> Token {
+    Token::IDENTIFIER(Symbol::intern(name))
+}
+
+/// Builds a `NUMBER` token spelled as the given value prints, as in `num(5)` or `num("2.5f")`.
+///
+/// # Parameters
+///
+/// * `value` - The number, or its spelling in source.
+pub fn num(value: impl fmt::Display) -> Token {
+    Token::NUMBER(Symbol::intern(&value.to_string()))
+}
+
+/// Builds a `STRINGLITERAL` token.
+///
+/// # Parameters
+///
+/// * `contents` - The decoded contents of the string literal, without quotes.
+pub fn string(contents: &str) -> Token {
+    Token::STRINGLITERAL(ByteString::from(contents))
+}
+
+/// Builds a vector of tokens from a comma-separated list of token variants, such as `TINTEGER` or `CHAR('a')`, and
+/// calls to the constructors of `lexer::build`, such as `ident("x")`.
+#[macro_export]
+macro_rules! tokens {
+    ($($name:ident $(($($arg:expr),*))?),* $(,)?) => {
+        vec![$($crate::build::$name$(($($arg),*))?),*]
+    };
+}
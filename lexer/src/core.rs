@@ -1,32 +1,75 @@
 //! This file drives the lexing process, which takes an input string and breaks it up into lexemes (tokens).
 
-use crate::token::Token;
-use common::error::ErrorType;
+use crate::{detokenize::TriviaToken, token::Token};
+use common::{
+    config::LanguageConfig,
+    error::ErrorType,
+    literal::ByteString,
+    location::{SourceLocation, Span},
+    source::{is_line_break, LineIndex, BOM},
+    symbol::Symbol,
+};
+
+/// Tokens or errors, each paired with where it is in the source, such as its `SourceLocation` or `Span`.
+pub type Marked<T, M> = Vec<(T, M)>;
+
+/// The result of lexing with every token or every error marked with where it is in the source.
+pub type MarkedResult<M> = Result<Marked<Token, M>, Marked<ErrorType, M>>;
+
+/// What an escape sequence denotes. An octal or hex escape denotes a single byte, and any other escape a character.
+enum Escape {
+    Byte(u8),
+    Char(char),
+}
 
 /// The `Lexer` struct models the process of lexical analysis.
 /// 
 /// At initialization, it takes a string input, a starting position, and the current character.
 ///
+/// The lexer borrows the source rather than copying its text into tokens: identifiers and numbers are interned
+/// straight from slices of the source, so lexing allocates per file rather than per token. Only string literals are
+/// copied, into the bytes they stand for.
+///
 /// # Fields
+/// * `source` - The source code being lexed.
 /// * `input` - A vector of characters representing the source code to be lexed.
+/// * `byte_offsets` - The byte offset in `source` of each character of `input`, followed by the length of `source`.
 /// * `position` - The current position within the input vector.
 /// * `current` - The current character being analyzed by the lexer.
-pub struct Lexer {
+/// * `token_start` - The position of the first character of the token being lexed.
+/// * `lines` - The lines of the source and the `#line` directives seen so far, which locations are computed from.
+/// * `config` - The optional language features that are enabled.
+pub struct Lexer<'a> {
+    source: &'a str,
     input: Vec<char>,
+    byte_offsets: Vec<usize>,
     position: usize,
     current: char,
+    token_start: usize,
+    lines: LineIndex<'a>,
+    config: LanguageConfig,
 }
 
-impl Lexer {
+impl<'a> Lexer<'a> {
     /// Initializes the lexer. 
     /// 
     /// # Parameters
-    /// * `input` - A vector of characters that represents the source code to be lexed. 
-    fn new(input: Vec<char>) -> Self {
+    /// * `source` - A string slice that represents the source code to be lexed. 
+    /// * `config` - The optional language features that are enabled.
+    fn new(source: &'a str, config: LanguageConfig) -> Self {
+        let (byte_offsets, input): (Vec<usize>, Vec<char>) = source.char_indices().unzip();
+        let byte_offsets = byte_offsets.into_iter().chain(std::iter::once(source.len())).collect();
+        // A byte order mark is skipped, as it is not part of the first line
+        let start = usize::from(input.first() == Some(&BOM));
         Self {
+            source,
+            current: input.get(start).copied().unwrap_or('@'), // '@' is the EOF token
             input,
-            position: 0,
-            current: '@', // EOF token
+            byte_offsets,
+            position: start,
+            token_start: start,
+            lines: LineIndex::new(source),
+            config,
         }
     }
 
@@ -76,31 +119,294 @@ impl Lexer {
     // }
 
     pub fn lex(input: &str) -> Result<Vec<Token>, Vec<ErrorType>> {
-        let mut lexer: Lexer = Lexer::new(input.chars().collect());
-        let mut errors: Vec<ErrorType> = Vec::new();
-        let mut tokens: Vec<Token> = Vec::new();
-        lexer.current = lexer.input[0];
+        Lexer::lex_with_config(input, LanguageConfig::default())
+    }
+
+    /// Lexically analyzes the given input string like `lex`, accepting the optional language features enabled in
+    /// `config`. With `typedef` enabled, `typedef` is a keyword rather than an identifier; with `attributes` enabled,
+    /// `@` is lexed as the start of an attribute. Keywords of the sets disabled in `config.lexer` are lexed as
+    /// identifiers.
+    ///
+    /// # Parameters
+    /// * `input` - A string slice representing the source code to be lexed.
+    /// * `config` - The optional language features that are enabled.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Token>)` - A vector of tokens if the input is successfully lexed without errors.
+    /// * `Err(Vec<ErrorType>)` - A vector of error types if any issues occur during lexing, such as unrecognized tokens.
+    pub fn lex_with_config(input: &str, config: LanguageConfig) -> Result<Vec<Token>, Vec<ErrorType>> {
+        match Lexer::lex_with_locations_and_config(input, config) {
+            Ok(tokens) => Ok(tokens.into_iter().map(|(token, _)| token).collect()),
+            Err(errors) => Err(errors.into_iter().map(|(error, _)| error).collect()),
+        }
+    }
+
+    /// Lexically analyzes the given input string like `lex`, pairing every token and error with the location it
+    /// starts at. `#line N "file"` directives, and the `# N "file"` form emitted by preprocessors, renumber the
+    /// lines after them so locations in generated sources point at the original source.
+    ///
+    /// # Parameters
+    /// * `input` - A string slice representing the source code to be lexed.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<(Token, SourceLocation)>)` - The tokens and their locations if the input is lexed without errors.
+    /// * `Err(Vec<(ErrorType, SourceLocation)>)` - The errors and their locations if any issues occur during lexing.
+    ///
+    /// # Errors
+    /// This function may return errors if it encounters characters that do not conform the expected token or character types,
+    /// or a malformed `#line` directive.
+    pub fn lex_with_locations(input: &str) -> MarkedResult<SourceLocation> {
+        Lexer::lex_with_locations_and_config(input, LanguageConfig::default())
+    }
+
+    /// Lexically analyzes the given input string like `lex_with_locations`, accepting the optional language features
+    /// enabled in `config`.
+    ///
+    /// # Parameters
+    /// * `input` - A string slice representing the source code to be lexed.
+    /// * `config` - The optional language features that are enabled.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<(Token, SourceLocation)>)` - The tokens and their locations if the input is lexed without errors.
+    /// * `Err(Vec<(ErrorType, SourceLocation)>)` - The errors and their locations if any issues occur during lexing.
+    pub fn lex_with_locations_and_config(input: &str, config: LanguageConfig) -> MarkedResult<SourceLocation> {
+        Lexer::lex_marked(input, config, |lexer| lexer.location_of(lexer.token_start))
+    }
+
+    /// Lexically analyzes the given input string like `lex`, pairing every token and error with the range of bytes
+    /// it covers in `input`. The ranges borrow nothing, so the text of a token can be resolved lazily with
+    /// `Span::text` for as long as the source is kept.
+    ///
+    /// # Parameters
+    /// * `input` - A string slice representing the source code to be lexed.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<(Token, Span)>)` - The tokens and their byte ranges if the input is lexed without errors.
+    /// * `Err(Vec<(ErrorType, Span)>)` - The errors and their byte ranges if any issues occur during lexing.
+    pub fn lex_with_spans(input: &str) -> MarkedResult<Span> {
+        Lexer::lex_with_spans_and_config(input, LanguageConfig::default())
+    }
+
+    /// Lexically analyzes the given input string like `lex_with_spans`, accepting the optional language features
+    /// enabled in `config`.
+    ///
+    /// # Parameters
+    /// * `input` - A string slice representing the source code to be lexed.
+    /// * `config` - The optional language features that are enabled.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<(Token, Span)>)` - The tokens and their byte ranges if the input is lexed without errors.
+    /// * `Err(Vec<(ErrorType, Span)>)` - The errors and their byte ranges if any issues occur during lexing.
+    pub fn lex_with_spans_and_config(input: &str, config: LanguageConfig) -> MarkedResult<Span> {
+        Lexer::lex_marked(input, config, |lexer| lexer.span_of_token())
+    }
+
+    /// Lexically analyzes the given input string like `lex_with_spans`, pairing every token with its text and the
+    /// trivia before it, the whitespace, comments, and `#line` directives it was separated from the previous token
+    /// by. `EOF` carries the trivia at the end of the input, so `detokenize_with_trivia` gives back `input` exactly.
+    ///
+    /// # Parameters
+    /// * `input` - A string slice representing the source code to be lexed.
+    /// * `config` - The optional language features that are enabled.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<TriviaToken>)` - The tokens with their text and trivia if the input is lexed without errors.
+    /// * `Err(Vec<(ErrorType, Span)>)` - The errors and their byte ranges if any issues occur during lexing.
+    pub fn lex_with_trivia(input: &str, config: LanguageConfig) -> Result<Vec<TriviaToken>, Vec<(ErrorType, Span)>> {
+        let mut previous_end = 0;
+        let tokens = Lexer::lex_with_spans_and_config(input, config)?.into_iter().map(|(token, span)| {
+            let (start, end) = (span.offset, span.offset + span.len);
+            let leading = input[previous_end..start].to_string();
+            let text = if token == Token::EOF { String::new() } else { input[start..end].to_string() };
+            previous_end = end;
+            TriviaToken { token, leading, text }
+        }).collect();
+        Ok(tokens)
+    }
+
+    /// Lexically analyzes input that may not be valid UTF-8 or may not be valid source, as editors and fuzzers give,
+    /// reading on past every error. Every byte sequence is lexed without panicking: bytes that are not valid UTF-8
+    /// are reported as `InvalidUtf8` and read as spaces, so they separate the tokens around them, and the lexer
+    /// resumes after every other error.
+    ///
+    /// # Parameters
+    /// * `input` - The bytes of the source code to be lexed.
+    /// * `config` - The optional language features that are enabled.
+    ///
+    /// # Returns
+    /// The tokens that were read, ending with `EOF`, and the errors found, each with the range of bytes of `input`
+    /// it covers. The errors are in input order.
+    pub fn lex_lossy(input: &[u8], config: LanguageConfig) -> (Marked<Token, Span>, Marked<ErrorType, Span>) {
+        let mut text = String::with_capacity(input.len());
+        let mut invalid = Vec::new();
+        for chunk in input.utf8_chunks() {
+            text.push_str(chunk.valid());
+            if !chunk.invalid().is_empty() {
+                let bytes = chunk.invalid().iter().map(|byte| format!("\\x{:02x}", byte)).collect();
+                invalid.push((ErrorType::InvalidUtf8 { bytes }, Span::new(text.len(), chunk.invalid().len())));
+                // One space per byte keeps every offset in `text` the offset of the same byte in `input`
+                text.extend(std::iter::repeat_n(' ', chunk.invalid().len()));
+            }
+        }
+        let (tokens, mut errors) = Lexer::lex_recovering(&text, config, |lexer| lexer.span_of_token());
+        errors.extend(invalid);
+        errors.sort_by_key(|(_, span)| span.offset);
+        (tokens, errors)
+    }
+
+    /// Lexes the input, pairing every token and error with the mark `mark` computes from the lexer right after it
+    /// is read.
+    fn lex_marked<M>(input: &str, config: LanguageConfig, mark: impl Fn(&Lexer) -> M) -> MarkedResult<M> {
+        let (tokens, errors) = Lexer::lex_recovering(input, config, mark);
+        if errors.is_empty() {
+            return Ok(tokens);
+        }
+        Err(errors)
+    }
+
+    /// Indexes the lines of the given input along with the `#line` directives in it, so the locations of byte
+    /// offsets, such as the spans of tokens and of the AST nodes parsed from them, point at the original source as
+    /// those of `lex_with_locations` do. The input is lexed to find the directives, reading on past any error.
+    ///
+    /// # Parameters
+    /// * `input` - A string slice representing the source code to be indexed.
+    /// * `config` - The optional language features that are enabled.
+    pub fn line_index(input: &str, config: LanguageConfig) -> LineIndex<'_> {
+        let mut lexer = Lexer::new(input, config);
+        lexer.read_all(|_| ());
+        lexer.lines
+    }
+
+    /// Lexes the input like `lex_marked`, skipping the character each error is found at and reading on, and returns
+    /// the tokens read along with the errors.
+    fn lex_recovering<M>(input: &str, config: LanguageConfig, mark: impl Fn(&Lexer) -> M) -> (Marked<Token, M>, Marked<ErrorType, M>) {
+        Lexer::new(input, config).read_all(mark)
+    }
+
+    /// Reads every token up to the end of the input, skipping the character each error is found at, and returns the
+    /// tokens and errors marked by `mark`.
+    fn read_all<M>(&mut self, mark: impl Fn(&Lexer) -> M) -> (Marked<Token, M>, Marked<ErrorType, M>) {
+        let mut errors: Marked<ErrorType, M> = Vec::new();
+        let mut tokens: Marked<Token, M> = Vec::new();
 
         loop {
-            let token: Result<Token, ErrorType> = lexer.next_token();
+            let token: Result<Token, ErrorType> = self.next_token();
             match token {
                 Ok(token) => {
+                    let marker = mark(self);
                     if token == Token::EOF {
-                        tokens.push(token);
+                        tokens.push((token, marker));
                         break;
                     }
-                    tokens.push(token);
+                    tokens.push((token, marker));
                 }
                 Err(error) => {
-                    errors.push(error);
-                    lexer.read_char();
+                    errors.push((error, mark(self)));
+                    self.read_char();
                 }
             }
         }
-        if errors.is_empty() {
-            return Ok(tokens);
+        (tokens, errors)
+    }
+
+    /// Returns the source text between two character positions of the input, which may be past its end.
+    fn slice(&self, start: usize, end: usize) -> &'a str {
+        let last = self.input.len();
+        &self.source[self.byte_offsets[start.min(last)]..self.byte_offsets[end.min(last)]]
+    }
+
+    /// Returns the byte offset of a character position of the input. Positions past its end are a byte apart.
+    fn byte_offset(&self, position: usize) -> usize {
+        let last = self.input.len();
+        self.byte_offsets[position.min(last)] + position.saturating_sub(last)
+    }
+
+    /// Computes the byte range of the token just read, from its first character up to the lexer's position.
+    fn span_of_token(&self) -> Span {
+        let last = self.input.len();
+        let start = self.byte_offsets[self.token_start.min(last)];
+        let end = self.byte_offsets[self.position.min(last)];
+        Span::new(start, end.saturating_sub(start))
+    }
+
+    /// Computes the source location of a position in the input, applying any `#line` directive before it.
+    fn location_of(&self, position: usize) -> SourceLocation {
+        self.lines.location(self.byte_offset(position))
+    }
+
+    /// Checks whether the current character is the first on its line, ignoring spaces and tabs.
+    fn at_line_start(&self) -> bool {
+        let offset = self.byte_offset(self.position);
+        let line_start = self.lines.line_start(self.lines.line(offset)).unwrap_or(0);
+        self.source[line_start..offset].chars().all(|c| c == ' ' || c == '\t')
+    }
+
+    /// Returns the text after the current `#` up to the end of its line, without leading spaces and tabs.
+    fn directive_text(&self) -> &'a str {
+        let end = self.input[self.position..].iter()
+            .position(|c| is_line_break(*c))
+            .map_or(self.input.len(), |length| self.position + length);
+        self.slice(self.position + 1, end).trim_start_matches([' ', '\t'])
+    }
+
+    /// Checks whether the current `#` starts a line directive: it must be the first character on its line and be
+    /// followed by `line` or a line number.
+    fn at_line_directive(&self) -> bool {
+        let rest = self.directive_text();
+        self.at_line_start() && (rest.starts_with("line") || rest.starts_with(|c: char| c.is_ascii_digit()))
+    }
+
+    /// Checks whether the current `#` starts a preprocessing directive such as `#include`: it must be the first
+    /// character on its line and be followed by a name.
+    fn at_preprocessing_directive(&self) -> bool {
+        self.at_line_start() && self.directive_text().starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+    }
+
+    /// Handles a preprocessing directive other than `#line`, which is left to the preprocessor as a single token
+    /// holding the rest of its line. Leaves the lexer on the newline ending the directive.
+    fn preprocessing_directive(&mut self) -> Result<Token, ErrorType> {
+        let text = self.directive_text().trim_end();
+        while !self.at_end() && !is_line_break(self.current) {
+            self.read_char();
         }
-        Err(errors)
+        Ok(Token::DIRECTIVE(Symbol::intern(text)))
+    }
+
+    /// Handles a `#line N "file"` directive, or the `# N "file" flags...` form emitted by preprocessors, by recording
+    /// the line number and file for the lines that follow it. The file name is optional and kept from the previous
+    /// directive when omitted. Leaves the lexer on the newline ending the directive.
+    fn line_directive(&mut self) -> Result<(), ErrorType> {
+        let physical_line = self.lines.line(self.byte_offset(self.position));
+        let mut text = String::new();
+        self.read_char(); // Skip '#'
+        while !self.at_end() && !is_line_break(self.current) {
+            text.push(self.current);
+            self.read_char();
+        }
+
+        let malformed = || ErrorType::SyntaxError { message: format!("Malformed #line directive: #{}", text) };
+        let body = text.trim_start();
+        let body = match body.strip_prefix("line") {
+            Some(rest) if rest.starts_with(char::is_whitespace) => rest.trim_start(),
+            Some(_) => return Err(malformed()),
+            None => body,
+        };
+
+        let digits_end = body.find(|c: char| !c.is_ascii_digit()).unwrap_or(body.len());
+        let line: usize = body[..digits_end].parse().map_err(|_| malformed())?;
+        let rest = body[digits_end..].trim_start();
+
+        let file = if rest.is_empty() {
+            None
+        } else if let Some(quoted) = rest.strip_prefix('"') {
+            let end = quoted.find('"').ok_or_else(malformed)?;
+            Some(quoted[..end].to_string())
+        } else {
+            return Err(malformed());
+        };
+
+        self.lines.add_line_directive(physical_line, line, file);
+        Ok(())
     }
 
 
@@ -149,10 +455,10 @@ impl Lexer {
         }
     }
 
-    /// Helper function to create unrecognized token error
+    /// Helper function to create unrecognized token error. Control characters are written as escapes, such as
+    /// `\u{1}`, so that messages show them.
     fn make_unrecognized_error(&self, c: char) -> ErrorType {
-        let mut err_token = String::new();
-        err_token.push(c);
+        let err_token = if c.is_control() { c.escape_default().to_string() } else { c.to_string() };
         ErrorType::UnrecognizedToken { token: err_token }
     }
 
@@ -181,6 +487,15 @@ impl Lexer {
                     self.read_char();
                     Ok(Token::LESSTHANEQUAL)
                 }
+                '<' => {
+                    self.read_char();
+                    if self.peek_char() == '=' {
+                        self.read_char();
+                        Ok(Token::LSHIFTEQUAL)
+                    } else {
+                        Ok(Token::LSHIFT)
+                    }
+                }
                 _ => Ok(Token::LESSTHAN),
             },
             
@@ -189,6 +504,15 @@ impl Lexer {
                     self.read_char();
                     Ok(Token::GREATERTHANEQUAL)
                 }
+                '>' => {
+                    self.read_char();
+                    if self.peek_char() == '=' {
+                        self.read_char();
+                        Ok(Token::RSHIFTEQUAL)
+                    } else {
+                        Ok(Token::RSHIFT)
+                    }
+                }
                 _ => Ok(Token::GREATERTHAN),
             },
             
@@ -201,6 +525,7 @@ impl Lexer {
         let keyword_map = [
             ("struct", Token::STRUCT),
             ("enum", Token::ENUM),
+            ("union", Token::UNION),
             ("if", Token::IF),
             ("else", Token::ELSE),
             ("return", Token::RETURN),
@@ -211,6 +536,7 @@ impl Lexer {
             ("continue", Token::CONTINUE),
             ("switch", Token::SWITCH),
             ("case", Token::CASE),
+            ("default", Token::DEFAULT),
             ("int", Token::TINTEGER),
             ("bool", Token::TBOOLEAN),
             ("double", Token::TDOUBLE),
@@ -221,104 +547,307 @@ impl Lexer {
             ("unsigned", Token::TUSIGN),
             ("long", Token::TLONG),
             ("const", Token::CONST),
+            ("volatile", Token::VOLATILE),
+            ("static", Token::STATIC),
+            ("extern", Token::EXTERN),
+            ("inline", Token::INLINE),
             ("true", Token::CTRUE),
         ];
 
-        // First, collect the entire identifier to check against keywords
-        let mut id = vec![self.current];
-        loop {
-            match self.peek_char() {
-                'a'..='z' | 'A'..='Z' | '0'..='9' | '_' => {
-                    id.push(self.peek_char());
-                    self.read_char();
-                }
-                _ => break,
-            }
+        // First, find the end of the entire identifier to check it against keywords
+        while matches!(self.peek_char(), 'a'..='z' | 'A'..='Z' | '0'..='9' | '_') {
+            self.read_char();
         }
+        let identifier = self.slice(self.token_start, self.position + 1);
         
-        // Convert the collected characters to a string for keyword matching
-        let identifier: String = id.iter().collect();
-        
-        // Check if the entire identifier matches a keyword
+        // `typedef` is only a keyword when type aliases are enabled
+        if identifier == "typedef" && self.config.typedef {
+            return Ok(Token::TYPEDEF);
+        }
+
+        // Check if the entire identifier matches a keyword whose set is enabled
         for (keyword, token) in keyword_map.iter() {
-            if &identifier == keyword {
+            if identifier == *keyword && self.config.lexer.is_keyword(keyword) {
                 return Ok(token.clone());
             }
         }
 
         // If no keyword matches, treat as identifier
-        Ok(Token::IDENTIFIER(id))
+        Ok(Token::IDENTIFIER(Symbol::intern(identifier)))
     }
 
-    // Handles numbers
+    // Handles numbers, including floating-point numbers with a fractional part, an exponent, or an `f` suffix,
+    // such as `3.14`, `1e-9`, and `2.5f`
     fn numbers(&mut self) -> Result<Token, ErrorType> {
         if !('0'..='9').contains(&self.current) {
             return Err(self.make_unrecognized_error(self.current));
         }
 
-        let mut num = vec![self.current];
-        loop {
-            match self.peek_char() {
-                '0'..='9' => {
-                    num.push(self.peek_char());
+        self.read_digits();
+        let mut is_float = false;
+
+        if self.peek_char() == '.' {
+            is_float = true;
+            self.read_char();
+            self.read_digits();
+        }
+
+        if matches!(self.peek_char(), 'e' | 'E') {
+            is_float = true;
+            self.read_char();
+            if matches!(self.peek_char(), '+' | '-') {
+                self.read_char();
+            }
+            if !self.peek_char().is_ascii_digit() {
+                return Err(ErrorType::InvalidLiteral {
+                    literal: self.slice(self.token_start, self.position + 1).to_string(),
+                    reason: "exponent has no digits".to_string(),
+                });
+            }
+            self.read_digits();
+        }
+
+        // Only floating-point numbers take the `f` suffix
+        if is_float && matches!(self.peek_char(), 'f' | 'F') {
+            self.read_char();
+        }
+        let literal = self.slice(self.token_start, self.position + 1);
+
+        // Integers are 64 bits wide at most, so a larger literal would otherwise lose its value in code generation
+        if !is_float && literal.parse::<i64>().is_err() {
+            return Err(ErrorType::InvalidLiteral {
+                literal: literal.to_string(),
+                reason: format!("integer literal is larger than the largest integer, {}", i64::MAX),
+            });
+        }
+        Ok(Token::NUMBER(Symbol::intern(literal)))
+    }
+
+    /// Skips the digits that follow the current character, leaving the lexer on the last one
+    fn read_digits(&mut self) {
+        while self.peek_char().is_ascii_digit() {
+            self.read_char();
+        }
+    }
+
+    /// Checks whether the lexer has run past the last character of the input
+    fn at_end(&self) -> bool {
+        self.position >= self.input.len()
+    }
+
+    /// Reads the escape sequence starting at the current backslash and returns what it denotes. Leaves the lexer on
+    /// the last character of the escape sequence.
+    fn read_escape(&mut self) -> Result<Escape, ErrorType> {
+        let mut sequence = String::from("\\");
+        self.read_char();
+        if self.at_end() {
+            return Err(ErrorType::InvalidEscapeSequence { sequence });
+        }
+        sequence.push(self.current);
+
+        match self.current {
+            'n' => Ok(Escape::Char('\n')),
+            't' => Ok(Escape::Char('\t')),
+            'r' => Ok(Escape::Char('\r')),
+            'a' => Ok(Escape::Char('\x07')),
+            'b' => Ok(Escape::Char('\x08')),
+            'f' => Ok(Escape::Char('\x0C')),
+            'v' => Ok(Escape::Char('\x0B')),
+            '\\' | '\'' | '"' | '?' => Ok(Escape::Char(self.current)),
+            // Octal escape: `\0` or `\101`, at most three digits and one byte
+            '0'..='7' => {
+                let mut digits = String::from(self.current);
+                while digits.len() < 3 && ('0'..='7').contains(&self.peek_char()) {
                     self.read_char();
+                    digits.push(self.current);
                 }
-                _ => {
-                    break;
+                sequence = format!("\\{}", digits);
+                match u32::from_str_radix(&digits, 8) {
+                    Ok(value) if value <= 0xFF => Ok(Escape::Byte(value as u8)),
+                    _ => Err(ErrorType::InvalidEscapeSequence { sequence }),
                 }
             }
+            // Hex escape: `\x41`, at most one byte
+            'x' => {
+                let mut digits = String::new();
+                while self.peek_char().is_ascii_hexdigit() {
+                    self.read_char();
+                    digits.push(self.current);
+                }
+                sequence.push_str(&digits);
+                match u32::from_str_radix(&digits, 16) {
+                    Ok(value) if value <= 0xFF => Ok(Escape::Byte(value as u8)),
+                    _ => Err(ErrorType::InvalidEscapeSequence { sequence }),
+                }
+            }
+            // Unicode escape: `\u{1F600}`, one to six hex digits naming a scalar value
+            'u' => {
+                if self.peek_char() != '{' {
+                    return Err(ErrorType::InvalidEscapeSequence { sequence });
+                }
+                self.read_char();
+                sequence.push('{');
+                let mut digits = String::new();
+                while self.peek_char().is_ascii_hexdigit() {
+                    self.read_char();
+                    digits.push(self.current);
+                }
+                sequence.push_str(&digits);
+                if self.peek_char() != '}' || digits.is_empty() || digits.len() > 6 {
+                    return Err(ErrorType::InvalidEscapeSequence { sequence });
+                }
+                self.read_char();
+                sequence.push('}');
+                u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .map(Escape::Char)
+                    .ok_or(ErrorType::InvalidEscapeSequence { sequence })
+            }
+            _ => Err(ErrorType::InvalidEscapeSequence { sequence }),
         }
-        Ok(Token::NUMBER(num))
     }
-    
-    /// Handles single-line and block comments
-    fn handle_comments(&mut self) -> Option<Result<Token, ErrorType>> {
+
+    /// Handles string literals delimited by double quotes, decoding escape sequences into the bytes they denote. An
+    /// octal or hex escape denotes a single byte, which need not be part of valid UTF-8, and other characters denote
+    /// their UTF-8 encoding.
+    fn string_literal(&mut self) -> Result<Token, ErrorType> {
+        let contents_start = self.position + 1;
+        let mut bytes: Vec<u8> = Vec::new();
+        let mut first_error: Option<ErrorType> = None;
+        loop {
+            self.read_char();
+            if self.at_end() || is_line_break(self.current) {
+                return Err(ErrorType::InvalidLiteral {
+                    literal: format!("\"{}", self.slice(contents_start, self.position)),
+                    reason: "unterminated string literal".to_string(),
+                });
+            }
+            match self.current {
+                '"' => break,
+                '\\' => match self.read_escape() {
+                    Ok(Escape::Byte(byte)) => bytes.push(byte),
+                    Ok(Escape::Char(c)) => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+                    // Keep scanning to the closing quote so the rest of the literal is not lexed as code
+                    Err(error) => {
+                        first_error.get_or_insert(error);
+                    }
+                },
+                c => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+            }
+        }
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(Token::STRINGLITERAL(ByteString::new(bytes))),
+        }
+    }
+
+    /// Checks whether the lexer is at the `r` starting a raw string literal, an `r` followed by any number of `#` and
+    /// a double quote.
+    fn at_raw_string_literal(&self) -> bool {
+        let after_hashes = self.input[self.position + 1..].iter().position(|c| *c != '#');
+        self.current == 'r' && after_hashes.is_some_and(|len| self.input[self.position + 1 + len] == '"')
+    }
+
+    /// Handles raw string literals, `r"..."`, whose contents are taken as written, without decoding escape sequences.
+    /// The quotes can be fenced by the same number of `#` on both sides, as in `r#"say "hi""#`, for contents with
+    /// double quotes. Like other string literals, a raw one ends on the line it starts on.
+    fn raw_string_literal(&mut self) -> Result<Token, ErrorType> {
+        let literal_start = self.position;
+        self.read_char();
+        let mut hashes = 0;
+        while self.current == '#' {
+            hashes += 1;
+            self.read_char();
+        }
+        let contents_start = self.position + 1;
+        loop {
+            self.read_char();
+            if self.at_end() || is_line_break(self.current) {
+                return Err(ErrorType::InvalidLiteral {
+                    literal: self.slice(literal_start, self.position).to_string(),
+                    reason: "unterminated raw string literal".to_string(),
+                });
+            }
+            let closes = self.current == '"'
+                && (1..=hashes).all(|offset| self.input.get(self.position + offset) == Some(&'#'));
+            if closes {
+                let contents = self.slice(contents_start, self.position);
+                self.read_chars(hashes);
+                return Ok(Token::STRINGLITERAL(ByteString::from(contents)));
+            }
+        }
+    }
+
+    /// Handles character literals delimited by single quotes, decoding escape sequences
+    fn char_literal(&mut self) -> Result<Token, ErrorType> {
+        let mut literal = vec![];
+        let mut first_error: Option<ErrorType> = None;
+        loop {
+            self.read_char();
+            if self.at_end() || is_line_break(self.current) {
+                return Err(ErrorType::InvalidLiteral {
+                    literal: format!("'{}", literal.iter().collect::<String>()),
+                    reason: "unterminated character literal".to_string(),
+                });
+            }
+            match self.current {
+                '\'' => break,
+                '\\' => match self.read_escape() {
+                    // A `char` is a single byte, so a byte escape denotes the character with that value
+                    Ok(Escape::Byte(byte)) => literal.push(byte as char),
+                    Ok(Escape::Char(c)) => literal.push(c),
+                    Err(error) => {
+                        first_error.get_or_insert(error);
+                    }
+                },
+                c => literal.push(c),
+            }
+        }
+        if let Some(error) = first_error {
+            return Err(error);
+        }
+        match literal.as_slice() {
+            [c] => Ok(Token::CHAR(*c)),
+            _ => Err(ErrorType::InvalidLiteral {
+                literal: format!("'{}'", literal.iter().collect::<String>()),
+                reason: "character literal must contain exactly one character".to_string(),
+            }),
+        }
+    }
+
+    /// Handles single-line and block comments, skipping the comment at the current character if there is one.
+    /// Returns None if there is no comment there, or the error of a block comment that is never closed.
+    fn handle_comments(&mut self) -> Option<Result<(), ErrorType>> {
         if self.current == '/' {
             match self.peek_char() {
                 '/' => {
-                    // Skip single-line comment
-                    while self.current != '\n' && self.current != '@' {
+                    // Skip single-line comment, up to the newline or the end of input; an `@` inside the comment
+                    // is ordinary text rather than the end-of-input marker
+                    while !self.at_end() && !is_line_break(self.current) {
                         self.read_char();
                     }
-                    return Some(self.next_token());
+                    return Some(Ok(()));
                 }
                 '*' => {
-                    // Process block comment
-                    self.read_char(); // Skip '*'
-                    
-                    // Keep track of nesting level to handle nested comments
-                    let mut level = 1;
-                    
+                    // Process block comment. As in C, block comments do not nest, so the first `*/` ends the
+                    // comment even if another `/*` was opened inside it
+                    let start = self.position;
+                    self.read_chars(2); // Skip '/*'
+
                     loop {
-                        // Check for the end of a block comment
-                        if self.current == '*' && self.peek_char() == '/' {
-                            level -= 1;
-                            self.read_char(); // Skip '*'
-                            self.read_char(); // Skip '/'
-                            
-                            if level == 0 {
-                                // We've found the matching end comment
-                                break;
-                            }
-                        } 
-                        // Check for a nested block comment
-                        else if self.current == '/' && self.peek_char() == '*' {
-                            level += 1;
-                            self.read_char(); // Skip '/'
-                            self.read_char(); // Skip '*'
-                        }
-                        // Check for EOF (end of file)
-                        else if self.current == '@' {
-                            // Instead of returning an error for an unclosed comment,
-                            // we'll treat it as a sequence of tokens to match the test
-                            // (Though in a real compiler this would be an error)
-                            return None;
+                        if self.at_end() {
+                            // The error is reported at the start of the comment
+                            self.token_start = start;
+                            return Some(Err(ErrorType::UnterminatedBlockComment));
                         }
-                        else {
-                            self.read_char();
+                        if self.current == '*' && self.peek_char() == '/' {
+                            self.read_chars(2); // Skip '*/'
+                            break;
                         }
+                        self.read_char();
                     }
-                    return Some(self.next_token());
+                    return Some(Ok(()));
                 }
                 _ => {}
             }
@@ -326,9 +855,13 @@ impl Lexer {
         None
     }
 
-    /// Handles plus sign and increment operator
+    /// Handles plus sign, increment operator, and addition assignment
     fn handle_plus(&mut self) -> Result<Token, ErrorType> {
         match self.peek_char() {
+            '=' => {
+                self.read_char();
+                Ok(Token::PLUSEQUAL)
+            }
             '+' => {
                 self.read_char();
                 Ok(Token::PLUSPLUS)
@@ -337,9 +870,13 @@ impl Lexer {
         }
     }
 
-    /// Handles minus sign, decrement operator, and pointer
+    /// Handles minus sign, decrement operator, subtraction assignment, and pointer
     fn handle_minus(&mut self) -> Result<Token, ErrorType> {
         match self.peek_char() {
+            '=' => {
+                self.read_char();
+                Ok(Token::DASHEQUAL)
+            }
             '>' => {
                 self.read_char();
                 Ok(Token::POINTER)
@@ -352,35 +889,87 @@ impl Lexer {
         }
     }
 
-    /// Handles ampersand and logical AND
+    /// Handles ampersand, logical AND, and bitwise and assignment
     fn handle_ampersand(&mut self) -> Result<Token, ErrorType> {
         match self.peek_char() {
             '&' => {
                 self.read_char(); // Advance to the second &
                 Ok(Token::ANDAND)
             }
+            '=' => {
+                self.read_char(); // Advance to the =
+                Ok(Token::AMPERSANDEQUAL)
+            }
             _ => Ok(Token::AMPERSAND),
         }
     }
 
-    /// Handles pipe and logical OR
+    /// Handles pipe, logical OR, and bitwise or assignment
     fn handle_pipe(&mut self) -> Result<Token, ErrorType> {
         match self.peek_char() {
             '|' => {
                 self.read_char(); // Advance to the second |
                 Ok(Token::BARBAR)
             }
+            '=' => {
+                self.read_char(); // Advance to the =
+                Ok(Token::BAREQUAL)
+            }
             _ => Ok(Token::BAR),
         }
     }
 
+    /// Handles period and ellipsis
+    fn handle_dot(&mut self) -> Result<Token, ErrorType> {
+        if self.peek_chars(3) == "..." {
+            self.read_chars(2); // Advance to the third .
+            Ok(Token::ELLIPSIS)
+        } else {
+            Ok(Token::DOT)
+        }
+    }
+
+    /// Handles colon and scope resolution
+    fn handle_colon(&mut self) -> Result<Token, ErrorType> {
+        match self.peek_char() {
+            ':' => {
+                self.read_char(); // Advance to the second :
+                Ok(Token::COLONCOLON)
+            }
+            _ => Ok(Token::COLON),
+        }
+    }
+
+    /// Handles multiplication, division and modulo, and their assignment operators
+    fn handle_arithmetic(&mut self) -> Result<Token, ErrorType> {
+        let (operator, assignment) = match self.current {
+            '*' => (Token::ASTERISK, Token::ASTERISKEQUAL),
+            '/' => (Token::FSLASH, Token::FSLASHEQUAL),
+            _ => (Token::PERCENT, Token::PERCENTEQUAL),
+        };
+        if self.peek_char() == '=' {
+            self.read_char(); // Advance to the =
+            Ok(assignment)
+        } else {
+            Ok(operator)
+        }
+    }
+
+    /// Handles caret and bitwise xor assignment
+    fn handle_caret(&mut self) -> Result<Token, ErrorType> {
+        match self.peek_char() {
+            '=' => {
+                self.read_char(); // Advance to the =
+                Ok(Token::CARETEQUAL)
+            }
+            _ => Ok(Token::CARET),
+        }
+    }
+
     /// Handles special-character tokens and single-character tokens
     fn handle_single_char_token(&self, c: char) -> Result<Token, ErrorType> {
         match c {
             '@' => Ok(Token::EOF),
-            '*' => Ok(Token::ASTERISK),
-            '/' => Ok(Token::FSLASH),
-            '%' => Ok(Token::PERCENT),
             '{' => Ok(Token::LBRACKET),
             '}' => Ok(Token::RBRACKET),
             '(' => Ok(Token::LPAREN),
@@ -388,10 +977,7 @@ impl Lexer {
             '[' => Ok(Token::LBRACE),
             ']' => Ok(Token::RBRACE),
             ';' => Ok(Token::SEMICOLON),
-            ':' => Ok(Token::COLON),
             ',' => Ok(Token::COMMA),
-            '.' => Ok(Token::DOT),
-            '^' => Ok(Token::CARET),
             '~' => Ok(Token::TILDE),
             '?' => Ok(Token::CTRUE),
             _ => Err(self.make_unrecognized_error(c)),
@@ -400,11 +986,26 @@ impl Lexer {
 
     /// Returns the current token type and advances to the next token
     fn next_token(&mut self) -> Result<Token, ErrorType> {
-        self.skip_whitespace();
-    
-        // Handle comments
-        if let Some(comment_result) = self.handle_comments() {
-            return comment_result;
+        // Comments and `#line` directives are skipped in a loop rather than by recursion, so that any number of them
+        // in a row is lexed without growing the stack
+        loop {
+            self.skip_whitespace();
+            if let Some(comment_result) = self.handle_comments() {
+                comment_result?;
+                continue;
+            }
+
+            self.token_start = self.position;
+
+            // Handle `#line` and other preprocessing directives, which must start their line
+            if self.current == '#' && self.at_line_directive() {
+                self.line_directive()?;
+                continue;
+            }
+            break;
+        }
+        if self.current == '#' && self.at_preprocessing_directive() {
+            return self.preprocessing_directive();
         }
     
         // Try boolean comparison operators but only for the ones that are actually comparison operators
@@ -424,12 +1025,18 @@ impl Lexer {
                 if self.position >= self.input.len() {
                     // This is the EOF marker
                     Ok(Token::EOF)
+                } else if self.config.attributes {
+                    // This `@` starts an attribute
+                    Ok(Token::AT)
                 } else {
                     // This is an actual '@' in the input
                     Err(self.make_unrecognized_error('@'))
                 }
             },
             '0'..='9' => self.numbers(),
+            '"' => self.string_literal(),
+            '\'' => self.char_literal(),
+            'r' if self.at_raw_string_literal() => self.raw_string_literal(),
             'a'..='z' | 'A'..='Z' | '_' => self.handle_keywords_and_identifiers(),
             '+' => self.handle_plus(),
             '-' => self.handle_minus(),
@@ -440,7 +1047,7 @@ impl Lexer {
                         // For single &, advance the cursor here
                         self.read_char();
                     } else {
-                        // For &&, handle_ampersand already advanced once, and we need to advance one more time
+                        // For && and &=, handle_ampersand already advanced once, and we need to advance one more time
                         self.read_char();
                     }
                 }
@@ -453,13 +1060,17 @@ impl Lexer {
                         // For single |, advance the cursor here
                         self.read_char();
                     } else {
-                        // For ||, handle_pipe already advanced once, and we need to advance one more time
+                        // For || and |=, handle_pipe already advanced once, and we need to advance one more time
                         self.read_char();
                     }
                 }
                 return result;
             },
-            '*' | '/' | '%' | '{' | '}' | '(' | ')' | '[' | ']' | ';' | ':' | ',' | '.' | '^' | '~' | '?' => 
+            '^' => self.handle_caret(),
+            '*' | '/' | '%' => self.handle_arithmetic(),
+            '.' => self.handle_dot(),
+            ':' => self.handle_colon(),
+            '{' | '}' | '(' | ')' | '[' | ']' | ';' | ',' | '~' | '?' => 
                 self.handle_single_char_token(self.current),
             _ => Err(self.make_unrecognized_error(self.current)),
         };
@@ -0,0 +1,142 @@
+//! Reconstructs source text from tokens.
+//!
+//! Tokens lexed with their trivia, the whitespace, comments, and `#line` directives between them, are written back
+//! exactly as they were read, so a tool can change the text of some tokens and keep the rest of the file as it was.
+//! Bare tokens are written in a canonical layout instead, separated by single spaces where they need to be, which
+//! lexes back to the same tokens.
+
+use common::location::SourceLocation;
+
+use crate::token::Token;
+
+/// The most blank lines `detokenize_with_locations` writes to move down to the line of the next token, beyond which
+/// it writes a `#line` directive instead.
+const MAX_BLANK_LINES: usize = 8;
+
+/// A token together with its text in the source and the trivia before it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriviaToken {
+    /// The token.
+    pub token: Token,
+    /// The whitespace, comments, and `#line` directives between the previous token and this one.
+    pub leading: String,
+    /// The text of the token as written in the source, which is empty for `EOF`.
+    pub text: String,
+}
+
+/// Writes tokens lexed with `Lexer::lex_with_trivia` back as source text. Each token is written as its `text`, so
+/// unchanged tokens give back the source they were lexed from byte for byte.
+///
+/// # Parameters
+/// * `tokens` - The tokens and their trivia.
+///
+/// # Returns
+/// The source text.
+pub fn detokenize_with_trivia(tokens: &[TriviaToken]) -> String {
+    let mut source = String::new();
+    for token in tokens {
+        source.push_str(&token.leading);
+        source.push_str(&token.text);
+    }
+    source
+}
+
+/// Writes tokens as source text in a canonical layout: tokens are separated by a single space, except where no
+/// space is needed, as inside parentheses and brackets, before `;` and `,`, between a function name and its
+/// arguments, and around `.`, `->`, and `::` between names. Preprocessing directives are written on their own lines.
+/// The text stops at the first `EOF`.
+///
+/// # Parameters
+/// * `tokens` - The tokens to write.
+///
+/// # Returns
+/// The source text, which lexes back to `tokens`.
+pub fn detokenize(tokens: &[Token]) -> String {
+    let mut source = String::new();
+    let mut previous: Option<&Token> = None;
+    for token in tokens.iter().take_while(|token| **token != Token::EOF) {
+        if let Token::DIRECTIVE(_) = token {
+            if !source.is_empty() && !source.ends_with('\n') {
+                source.push('\n');
+            }
+        } else if let Some(previous) = previous {
+            if !source.ends_with('\n') && needs_space(previous, token) {
+                source.push(' ');
+            }
+        }
+        source.push_str(&token.spelling());
+        if let Token::DIRECTIVE(_) = token {
+            source.push('\n');
+        }
+        previous = Some(token);
+    }
+    source
+}
+
+/// Writes preprocessed tokens as source text at the locations they were read from, so that lexing the text with its
+/// locations gives every token its file and line, and its column where the tokens before it on the line leave room.
+/// Tokens on the same line are spaced as in `detokenize`. Where the next token is in another file, on an earlier line,
+/// or too many lines further down, a `#line` directive moves to its line. The text stops at the first `EOF`.
+///
+/// # Parameters
+/// * `tokens` - The tokens to write and their locations, which hold no preprocessing directives.
+///
+/// # Returns
+/// The source text, which lexes back to the tokens.
+pub fn detokenize_with_locations(tokens: &[(Token, SourceLocation)]) -> String {
+    let mut source = String::new();
+    // The file and line of the line being written, and the number of characters already written on it
+    let mut current: Option<(&Option<String>, usize)> = None;
+    let mut column = 0;
+    let mut previous: Option<&Token> = None;
+    for (token, location) in tokens.iter().take_while(|(token, _)| *token != Token::EOF) {
+        match current {
+            Some((file, line)) if *file == location.file && (line..=line + MAX_BLANK_LINES).contains(&location.line) => {
+                if location.line > line {
+                    source.push_str(&"\n".repeat(location.line - line));
+                    column = 0;
+                }
+            }
+            _ => {
+                if !source.is_empty() {
+                    source.push('\n');
+                }
+                match &location.file {
+                    Some(file) => source.push_str(&format!("#line {} \"{}\"\n", location.line, file)),
+                    None => source.push_str(&format!("#line {}\n", location.line)),
+                }
+                column = 0;
+            }
+        }
+        current = Some((&location.file, location.line));
+
+        let start = location.column.saturating_sub(1);
+        if column < start {
+            source.push_str(&" ".repeat(start - column));
+            column = start;
+        } else if column > 0 && previous.is_some_and(|previous| needs_space(previous, token)) {
+            source.push(' ');
+            column += 1;
+        }
+        let spelling = token.spelling();
+        column += spelling.chars().count();
+        source.push_str(&spelling);
+        previous = Some(token);
+    }
+    source
+}
+
+/// Checks whether two adjacent tokens are separated by a space in the canonical layout. Tokens are only written
+/// together where their spellings cannot run into a different token, as `-` and `>` would run into `->`.
+fn needs_space(previous: &Token, next: &Token) -> bool {
+    !matches!(
+        (previous, next),
+        (Token::LPAREN | Token::LBRACE, _)
+            | (_, Token::RPAREN | Token::RBRACE | Token::SEMICOLON | Token::COMMA)
+            | (Token::IDENTIFIER(_), Token::LPAREN)
+            | (Token::IDENTIFIER(_) | Token::RPAREN | Token::RBRACE, Token::DOT | Token::POINTER)
+            | (Token::DOT | Token::POINTER, Token::IDENTIFIER(_))
+            | (Token::IDENTIFIER(_), Token::COLONCOLON)
+            | (Token::COLONCOLON, Token::IDENTIFIER(_))
+    )
+}
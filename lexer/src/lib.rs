@@ -9,9 +9,25 @@
 //!
 //! - `core`: Core takes source code as input and outputs tokens and ignores comments and whitespace. 
 //! - 'token': Tokens are the base unit of our compiler. This module contains accepted tokens.
+//! - `build`: Constructors and the `tokens!` macro for building token vectors by hand, as in parser tests.
+//! - `preprocess`: Expands preprocessing directives such as `#include` in the token stream.
+//! - `detokenize`: Writes tokens back as source text, exactly with their trivia or in a canonical layout.
+//! - `normalize`: Strips byte order marks and treats `\n`, `\r\n`, and `\r` alike as line breaks.
 
 /// Core of the Lexer
 pub mod core;
 
 /// Lists accepted tokens
 pub mod token;
+
+/// Constructors for building token vectors by hand
+pub mod build;
+
+/// Expands preprocessing directives
+pub mod preprocess;
+
+/// Reconstructs source text from tokens
+pub mod detokenize;
+
+/// Byte order marks and line endings
+pub mod normalize;
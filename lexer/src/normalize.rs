@@ -0,0 +1,37 @@
+//! Normalizes the encoding details of source files that do not change their meaning: a leading UTF-8 byte order
+//! mark, and Windows (`\r\n`) or classic Mac (`\r`) line endings.
+//!
+//! The lexer applies the same rules in place, skipping the byte order mark and counting lines with
+//! `common::source::LineIndex`, which treats each of `\n`, `\r\n`, and a lone `\r` as one line break, so spans still
+//! index the text as it was read. `normalize` rewrites the text itself, for tools that compare or store sources and
+//! want each file written the same way.
+
+use std::borrow::Cow;
+
+pub use common::source::{is_line_break, BOM};
+
+/// Removes the byte order mark from the start of a source, if it has one.
+///
+/// # Parameters
+/// * `source` - The source text.
+///
+/// # Returns
+/// The source without its byte order mark.
+pub fn strip_bom(source: &str) -> &str {
+    source.strip_prefix(BOM).unwrap_or(source)
+}
+
+/// Rewrites a source with its byte order mark removed and every line break written as `\n`.
+///
+/// # Parameters
+/// * `source` - The source text.
+///
+/// # Returns
+/// The normalized source, borrowed when the source was already normalized.
+pub fn normalize(source: &str) -> Cow<'_, str> {
+    let source = strip_bom(source);
+    if !source.contains('\r') {
+        return Cow::Borrowed(source);
+    }
+    Cow::Owned(source.replace("\r\n", "\n").replace('\r', "\n"))
+}
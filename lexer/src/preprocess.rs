@@ -0,0 +1,394 @@
+//! This file implements the preprocessing stage, which expands the directives in a stream of tokens before it is
+//! parsed.
+//!
+//! `#include "file"` is replaced by the tokens of the named file, which is preprocessed in turn. A quoted file is
+//! looked for next to the file including it and then in each include path in order, while `#include <file>` only
+//! searches the include paths. A file that includes itself, directly or through other files, is reported instead of
+//! being expanded forever; a file included more than once without a cycle is spliced in each time.
+//!
+//! `#define NAME replacement` defines an object-like macro: each identifier `NAME` after the directive, including in
+//! files included later, is replaced by the tokens of the replacement, which are expanded in turn. A macro is not
+//! expanded inside its own replacement, so `#define X X + 1` does not loop. Defining a macro again with the same
+//! replacement has no effect, while a different replacement is reported and the first one kept. Macros can also be
+//! defined before the source is read, through the `defines` of the configuration.
+//!
+//! `#ifdef NAME` and `#ifndef NAME` keep the lines up to the matching `#else` or `#endif` only if the macro is or is
+//! not defined, and `#else` keeps the lines up to `#endif` otherwise. Directives in lines that are not kept are not
+//! expanded, though conditional ones are still matched up. Each file must close the conditionals it opens.
+//!
+//! Every token keeps the location it was read at, in the file it was read from, and the tokens a macro is replaced by
+//! take the location of the macro's name, so the expanded tokens can be written back as source text that points at
+//! the original files.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use common::{config::PreprocessorConfig, diagnostic::DiagnosticSink, error::ErrorType, location::SourceLocation};
+
+use crate::{core::Lexer, token::Token};
+
+/// A conditional opened by `#ifdef` or `#ifndef`, until its `#endif`.
+///
+/// # Fields
+/// * `directive` - The directive that opened it, for diagnostics.
+/// * `taken` - Whether the lines of its current branch are kept, ignoring the conditionals it is nested in.
+/// * `seen_else` - Whether its `#else` was reached.
+struct Conditional {
+    directive: String,
+    taken: bool,
+    seen_else: bool,
+}
+
+/// An object-like macro defined by `#define`.
+///
+/// # Fields
+/// * `text` - The replacement as written, for diagnostics.
+/// * `tokens` - The tokens the macro is replaced by.
+struct Macro {
+    text: String,
+    tokens: Vec<Token>,
+}
+
+/// The `Preprocessor` struct models the process of preprocessing.
+///
+/// # Fields
+/// * `config` - The include paths and language features to preprocess with.
+/// * `including` - The files being included, from the outermost one in, used to detect include cycles.
+/// * `macros` - The macros defined so far, by name.
+/// * `expanding` - The macros being expanded, from the outermost one in, which are not expanded again.
+/// * `diagnostics` - The errors found so far, which are reported once preprocessing finishes.
+pub struct Preprocessor {
+    config: PreprocessorConfig,
+    including: Vec<PathBuf>,
+    macros: HashMap<String, Macro>,
+    expanding: Vec<String>,
+    diagnostics: DiagnosticSink,
+}
+
+impl Preprocessor {
+    /// Initializes the preprocessor, with the macros of the configuration defined.
+    ///
+    /// # Parameters
+    /// * `config` - The include paths and language features to preprocess with.
+    fn new(config: &PreprocessorConfig) -> Self {
+        let mut preprocessor = Self {
+            config: config.clone(),
+            including: Vec::new(),
+            macros: HashMap::new(),
+            expanding: Vec::new(),
+            diagnostics: DiagnosticSink::new(),
+        };
+        for (name, replacement) in &config.defines {
+            preprocessor.define(&format!("{} {}", name, replacement));
+        }
+        preprocessor
+    }
+
+    /// Lexes and preprocesses a source that was not read from a file, so quoted includes are only looked for in the
+    /// include paths.
+    ///
+    /// # Parameters
+    /// * `source` - The source code to preprocess.
+    /// * `config` - The include paths and language features to preprocess with.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Token>)` - The tokens of the source with every directive expanded, ending with `EOF`.
+    /// * `Err(Vec<ErrorType>)` - The errors found lexing the source or any included file, or expanding a directive.
+    pub fn preprocess(source: &str, config: &PreprocessorConfig) -> Result<Vec<Token>, Vec<ErrorType>> {
+        let mut preprocessor = Preprocessor::new(config);
+        let mut output = Vec::new();
+        preprocessor.expand_source(source, None, None, &mut output);
+        preprocessor.finish(output).map(without_locations)
+    }
+
+    /// Reads, lexes, and preprocesses a file.
+    ///
+    /// # Parameters
+    /// * `path` - The path of the file to preprocess.
+    /// * `config` - The include paths and language features to preprocess with.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Token>)` - The tokens of the file with every directive expanded, ending with `EOF`.
+    /// * `Err(Vec<ErrorType>)` - The errors found reading or lexing the file or any included file, or expanding a
+    ///   directive.
+    pub fn preprocess_file(path: &Path, config: &PreprocessorConfig) -> Result<Vec<Token>, Vec<ErrorType>> {
+        Preprocessor::preprocess_file_with_locations(path, config).map(without_locations)
+    }
+
+    /// Reads, lexes, and preprocesses a file like `preprocess_file`, pairing every token with the location it was
+    /// read at. The location names the file the token was read from as it was found: the given path for the file
+    /// itself, and the include path or directory joined with the included name for an included file. Tokens a macro
+    /// is replaced by are at the location of the macro's name.
+    ///
+    /// # Parameters
+    /// * `path` - The path of the file to preprocess.
+    /// * `config` - The include paths and language features to preprocess with.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<(Token, SourceLocation)>)` - The tokens of the file with every directive expanded and their
+    ///   locations, ending with `EOF`.
+    /// * `Err(Vec<ErrorType>)` - The errors found reading or lexing the file or any included file, or expanding a
+    ///   directive.
+    pub fn preprocess_file_with_locations(path: &Path, config: &PreprocessorConfig) -> Result<Vec<(Token, SourceLocation)>, Vec<ErrorType>> {
+        let mut preprocessor = Preprocessor::new(config);
+        let mut output = Vec::new();
+        preprocessor.expand_file(&path.display().to_string(), path, &mut output);
+        preprocessor.finish(output)
+    }
+
+    /// Ends the token stream at the location of its last token, or reports the errors found.
+    fn finish(self, mut output: Vec<(Token, SourceLocation)>) -> Result<Vec<(Token, SourceLocation)>, Vec<ErrorType>> {
+        let end = output.last().map_or_else(|| SourceLocation::new(None, 1, 1), |(_, location)| location.clone());
+        output.push((Token::EOF, end));
+        self.diagnostics.finish(output)
+    }
+
+    /// Reads and expands a file into `output`, unless it is already being included.
+    ///
+    /// # Parameters
+    /// * `name` - The file as named by the directive including it, for diagnostics.
+    /// * `path` - The path of the file.
+    /// * `output` - The tokens expanded so far.
+    fn expand_file(&mut self, name: &str, path: &Path, output: &mut Vec<(Token, SourceLocation)>) {
+        let file = path.display().to_string();
+        let invalid = |reason: String| ErrorType::InvalidInclude { file: name.to_string(), reason };
+        let path = match fs::canonicalize(path) {
+            Ok(path) => path,
+            Err(error) => return self.diagnostics.error(invalid(format!("could not be read: {}", error))),
+        };
+        if let Some(start) = self.including.iter().position(|file| *file == path) {
+            let cycle: Vec<String> = self.including[start..].iter()
+                .chain(std::iter::once(&path))
+                .map(|file| file.display().to_string())
+                .collect();
+            return self.diagnostics.error(invalid(format!("it includes itself through {}", cycle.join(" -> "))));
+        }
+        let source = match fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(error) => return self.diagnostics.error(invalid(format!("could not be read: {}", error))),
+        };
+
+        self.including.push(path.clone());
+        self.expand_source(&source, Some(&file), path.parent(), output);
+        self.including.pop();
+    }
+
+    /// Lexes a source and expands it into `output`.
+    ///
+    /// # Parameters
+    /// * `source` - The source code to expand.
+    /// * `file` - The file the source was read from, as its tokens' locations name it, if any.
+    /// * `directory` - The directory of the file the source was read from, if any.
+    /// * `output` - The tokens expanded so far.
+    fn expand_source(&mut self, source: &str, file: Option<&str>, directory: Option<&Path>, output: &mut Vec<(Token, SourceLocation)>) {
+        // The lexer expects at least one character
+        if source.is_empty() {
+            return;
+        }
+        let tokens = match Lexer::lex_with_locations_and_config(source, self.config.language) {
+            Ok(tokens) => tokens,
+            Err(errors) => return self.diagnostics.errors_from(errors.into_iter().map(|(error, _)| error)),
+        };
+        let mut conditionals = Vec::new();
+        for (token, mut location) in tokens {
+            // A `#line` directive in the file names the file its lines come from instead
+            if location.file.is_none() {
+                location.file = file.map(str::to_string);
+            }
+            let kept = conditionals.iter().all(|conditional: &Conditional| conditional.taken);
+            match token {
+                Token::EOF => break,
+                Token::DIRECTIVE(text) => {
+                    let is_conditional = self.conditional(text.as_str(), &mut conditionals);
+                    if !is_conditional && kept {
+                        self.directive(text.as_str(), directory, output);
+                    }
+                }
+                token if kept => self.expand_token(token, &location, output),
+                _ => {}
+            }
+        }
+        if let Some(conditional) = conditionals.first() {
+            self.diagnostics.error(ErrorType::SyntaxError {
+                message: format!("Unterminated #{}: expected #endif before the end of the file", conditional.directive),
+            });
+        }
+    }
+
+    /// Applies a directive if it is `#ifdef`, `#ifndef`, `#else`, or `#endif`.
+    ///
+    /// # Parameters
+    /// * `text` - The text of the directive after the `#`.
+    /// * `conditionals` - The conditionals open in the source, from the outermost one in.
+    ///
+    /// # Returns
+    /// Whether the directive is a conditional one.
+    fn conditional(&mut self, text: &str, conditionals: &mut Vec<Conditional>) -> bool {
+        let (name, operand) = split_directive(text);
+        let unmatched = |message: String| ErrorType::SyntaxError { message };
+        match name {
+            "ifdef" | "ifndef" => {
+                let is_name = !operand.is_empty()
+                    && !operand.starts_with(|c: char| c.is_ascii_digit())
+                    && operand.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+                if !is_name {
+                    self.diagnostics.error(unmatched(format!("Malformed #{} directive: #{}", name, text.trim_end())));
+                }
+                let defined = self.macros.contains_key(operand);
+                conditionals.push(Conditional {
+                    directive: format!("{} {}", name, operand),
+                    taken: is_name && defined == (name == "ifdef"),
+                    seen_else: false,
+                });
+            }
+            "else" => match conditionals.last_mut() {
+                Some(conditional) if conditional.seen_else => {
+                    self.diagnostics.error(unmatched(format!("#else after #else for #{}", conditional.directive)));
+                }
+                Some(conditional) => {
+                    conditional.taken = !conditional.taken;
+                    conditional.seen_else = true;
+                }
+                None => self.diagnostics.error(unmatched("#else without #ifdef or #ifndef".to_string())),
+            },
+            "endif" => {
+                if conditionals.pop().is_none() {
+                    self.diagnostics.error(unmatched("#endif without #ifdef or #ifndef".to_string()));
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Expands a token into `output`, replacing it if it names a macro that is not already being expanded.
+    ///
+    /// # Parameters
+    /// * `token` - The token to expand.
+    /// * `location` - Where the token, or the macro use it was expanded from, was read.
+    /// * `output` - The tokens expanded so far.
+    fn expand_token(&mut self, token: Token, location: &SourceLocation, output: &mut Vec<(Token, SourceLocation)>) {
+        let name = match &token {
+            Token::IDENTIFIER(name) if !self.expanding.iter().any(|expanding| expanding == name.as_str()) => name.as_str(),
+            _ => return output.push((token, location.clone())),
+        };
+        let replacement = match self.macros.get(name) {
+            Some(definition) => definition.tokens.clone(),
+            None => return output.push((token, location.clone())),
+        };
+        self.expanding.push(name.to_string());
+        for token in replacement {
+            self.expand_token(token, location, output);
+        }
+        self.expanding.pop();
+    }
+
+    /// Expands a directive into `output`.
+    ///
+    /// # Parameters
+    /// * `text` - The text of the directive after the `#`.
+    /// * `directory` - The directory of the file the directive is in, if any.
+    /// * `output` - The tokens expanded so far.
+    fn directive(&mut self, text: &str, directory: Option<&Path>, output: &mut Vec<(Token, SourceLocation)>) {
+        let (name, rest) = split_directive(text);
+        match name {
+            "include" => self.include(rest, directory, output),
+            "define" => self.define(rest),
+            _ => self.diagnostics.error(ErrorType::SyntaxError {
+                message: format!("Unknown preprocessing directive: #{}", name),
+            }),
+        }
+    }
+
+    /// Expands `#include "file"` or `#include <file>` into the tokens of the file.
+    ///
+    /// # Parameters
+    /// * `operand` - The text of the directive after `include`.
+    /// * `directory` - The directory of the file the directive is in, if any.
+    /// * `output` - The tokens expanded so far.
+    fn include(&mut self, operand: &str, directory: Option<&Path>, output: &mut Vec<(Token, SourceLocation)>) {
+        let (name, quoted) = match operand.chars().next() {
+            Some('"') => (operand[1..].split_once('"').map(|(name, _)| name), true),
+            Some('<') => (operand[1..].split_once('>').map(|(name, _)| name), false),
+            _ => (None, false),
+        };
+        let name = match name {
+            Some(name) if !name.is_empty() => name,
+            _ => {
+                return self.diagnostics.error(ErrorType::SyntaxError {
+                    message: format!("Malformed #include directive: #include {}", operand),
+                });
+            }
+        };
+
+        let next_to_includer = directory.filter(|_| quoted).map(|directory| directory.join(name));
+        let found = next_to_includer.into_iter()
+            .chain(self.config.include_paths.iter().map(|include_path| include_path.join(name)))
+            .find(|candidate| candidate.is_file());
+        match found {
+            Some(path) => self.expand_file(name, &path, output),
+            None => self.diagnostics.error(ErrorType::InvalidInclude {
+                file: name.to_string(),
+                reason: if quoted {
+                    "not found next to the including file or in the include paths".to_string()
+                } else {
+                    "not found in the include paths".to_string()
+                },
+            }),
+        }
+    }
+
+    /// Defines the macro of `#define NAME replacement`, unless it is already defined with another replacement.
+    ///
+    /// # Parameters
+    /// * `operand` - The text of the directive after `define`.
+    fn define(&mut self, operand: &str) {
+        let name_end = operand.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(operand.len());
+        let (name, text) = operand.split_at(name_end);
+        let malformed = |message: &str| ErrorType::SyntaxError { message: format!("{}: #define {}", message, operand) };
+        if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+            return self.diagnostics.error(malformed("Malformed #define directive"));
+        }
+        if text.starts_with('(') {
+            return self.diagnostics.error(malformed("Function-like macros are not supported"));
+        }
+
+        let tokens = if text.trim().is_empty() {
+            Vec::new()
+        } else {
+            match Lexer::lex_with_config(text.trim(), self.config.language) {
+                Ok(mut tokens) => {
+                    tokens.pop();
+                    tokens
+                }
+                Err(errors) => return self.diagnostics.errors_from(errors),
+            }
+        };
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        match self.macros.get(name) {
+            Some(previous) if previous.tokens != tokens => self.diagnostics.error(ErrorType::MacroRedefinition {
+                name: name.to_string(),
+                previous: previous.text.clone(),
+                replacement: text,
+            }),
+            Some(_) => {}
+            None => {
+                self.macros.insert(name.to_string(), Macro { text, tokens });
+            }
+        }
+    }
+}
+
+/// Drops the locations of preprocessed tokens.
+fn without_locations(tokens: Vec<(Token, SourceLocation)>) -> Vec<Token> {
+    tokens.into_iter().map(|(token, _)| token).collect()
+}
+
+/// Splits the text of a directive after the `#` into its name and its operand.
+fn split_directive(text: &str) -> (&str, &str) {
+    let name_end = text.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(text.len());
+    (&text[..name_end], text[name_end..].trim())
+}
@@ -1,9 +1,14 @@
 use std::fmt;
 
+use common::{
+    literal::{escape_char, ByteString},
+    symbol::Symbol,
+};
+
 /// Represents all possible tokens that can be recognized by the lexer.
 #[derive(PartialEq, Debug, Clone, Default)]
 pub enum Token {
-    /// The default token state.
+    /// The default token state, which is also the `default` label of a switch statement.
     #[default]
     DEFAULT,
     
@@ -12,19 +17,41 @@ pub enum Token {
   
     // ---- Multi-Character Tokens ----
     /// Number.
-    NUMBER(Vec<char>),
+    NUMBER(Symbol),
     /// Identifier.
-    IDENTIFIER(Vec<char>),
-    /// Represents a string literal like "hello world".
-    STRINGLITERAL(Vec<char>),
+    IDENTIFIER(Symbol),
+    /// Represents a string literal like "hello world", holding the bytes its decoded contents stand for.
+    STRINGLITERAL(ByteString),
     /// Character literal like 'a'.
     CHAR(char),
+    /// A preprocessing directive such as `#include "file"`, holding the text of its line after the `#`.
+    DIRECTIVE(Symbol),
 
     // ----- Assignment Operators -----
     /// Increment operator `++`.
     PLUSPLUS,
     /// Decrement operator `--`.
     MINUSMINUS,
+    /// Bitwise and assignment operator `&=`.
+    AMPERSANDEQUAL,
+    /// Bitwise or assignment operator `|=`.
+    BAREQUAL,
+    /// Bitwise xor assignment operator `^=`.
+    CARETEQUAL,
+    /// Addition assignment operator `+=`.
+    PLUSEQUAL,
+    /// Subtraction assignment operator `-=`.
+    DASHEQUAL,
+    /// Multiplication assignment operator `*=`.
+    ASTERISKEQUAL,
+    /// Division assignment operator `/=`.
+    FSLASHEQUAL,
+    /// Modulo assignment operator `%=`.
+    PERCENTEQUAL,
+    /// Left shift assignment operator `<<=`.
+    LSHIFTEQUAL,
+    /// Right shift assignment operator `>>=`.
+    RSHIFTEQUAL,
 
     // ----- Binary Operators -----
     /// Division operator `/`.
@@ -45,6 +72,10 @@ pub enum Token {
     STRUCT,
     /// An "enum" definition.
     ENUM,
+    /// A "union" definition, whose fields share storage.
+    UNION,
+    /// A "typedef" type alias definition.
+    TYPEDEF,
     /// If conditional.
     IF,
     /// Else branch.
@@ -85,8 +116,14 @@ pub enum Token {
     COMMA,
     /// Colon `:`.
     COLON,
+    /// Scope resolution `::`, which qualifies a name with the enum or module it belongs to, as in `Color::Red`.
+    COLONCOLON,
     /// Period `.`.
     DOT,
+    /// Ellipsis `...`, which stands for the variable arguments of a function.
+    ELLIPSIS,
+    /// At sign `@`, which starts an attribute.
+    AT,
 
     // ----- Boolean and Comparison Operators -----
     /// Logical and "&&".
@@ -137,16 +174,134 @@ pub enum Token {
     CARET,
     /// Bitwise not "~".
     TILDE,
+    /// Left shift "<<".
+    LSHIFT,
+    /// Right shift ">>".
+    RSHIFT,
 
     // ----- Miscellaneous -----
     /// Pointer to member operator `->`.
     POINTER,
     /// Constant declaration.
     CONST,
+    /// Volatile qualifier.
+    VOLATILE,
+    /// Static storage class.
+    STATIC,
+    /// External linkage storage class.
+    EXTERN,
+    /// Inline function specifier.
+    INLINE,
     /// Conditional true `?`.
     CTRUE,
 }
 
+impl Token {
+    /// Returns the source spelling of a keyword token, or None if the token is not a keyword.
+    pub fn keyword_spelling(&self) -> Option<&'static str> {
+        let spelling = match self {
+            Token::STRUCT => "struct",
+            Token::ENUM => "enum",
+            Token::UNION => "union",
+            Token::TYPEDEF => "typedef",
+            Token::IF => "if",
+            Token::ELSE => "else",
+            Token::RETURN => "return",
+            Token::FOR => "for",
+            Token::WHILE => "while",
+            Token::DO => "do",
+            Token::BREAK => "break",
+            Token::CONTINUE => "continue",
+            Token::SWITCH => "switch",
+            Token::CASE => "case",
+            Token::DEFAULT => "default",
+            Token::TINTEGER => "int",
+            Token::TBOOLEAN => "bool",
+            Token::TDOUBLE => "double",
+            Token::TFLOAT => "float",
+            Token::TCHAR => "char",
+            Token::TVOID => "void",
+            Token::TSIGNINT => "signed",
+            Token::TUSIGN => "unsigned",
+            Token::TLONG => "long",
+            Token::CONST => "const",
+            Token::VOLATILE => "volatile",
+            Token::STATIC => "static",
+            Token::EXTERN => "extern",
+            Token::INLINE => "inline",
+            Token::CTRUE => "true",
+            _ => return None,
+        };
+        Some(spelling)
+    }
+
+    /// Returns the canonical source spelling of the token, which lexes back to the same token. String and character
+    /// literals are quoted with their special characters escaped, and the end of the file is spelled as nothing.
+    pub fn spelling(&self) -> String {
+        if let Some(keyword) = self.keyword_spelling() {
+            return keyword.to_string();
+        }
+        let spelling = match self {
+            Token::EOF => "",
+            Token::NUMBER(number) => return number.to_string(),
+            Token::IDENTIFIER(name) => return name.to_string(),
+            Token::STRINGLITERAL(contents) => return format!("\"{}\"", contents.escaped()),
+            Token::CHAR(c) => return format!("'{}'", escape_char(*c, '\'')),
+            Token::DIRECTIVE(text) => return format!("#{}", text),
+            Token::PLUSPLUS => "++",
+            Token::MINUSMINUS => "--",
+            Token::AMPERSANDEQUAL => "&=",
+            Token::BAREQUAL => "|=",
+            Token::CARETEQUAL => "^=",
+            Token::PLUSEQUAL => "+=",
+            Token::DASHEQUAL => "-=",
+            Token::ASTERISKEQUAL => "*=",
+            Token::FSLASHEQUAL => "/=",
+            Token::PERCENTEQUAL => "%=",
+            Token::LSHIFTEQUAL => "<<=",
+            Token::RSHIFTEQUAL => ">>=",
+            Token::FSLASH => "/",
+            Token::DASH => "-",
+            Token::PLUS => "+",
+            Token::EQUAL => "=",
+            Token::PERCENT => "%",
+            Token::ASTERISK => "*",
+            Token::RBRACKET => "}",
+            Token::LBRACKET => "{",
+            Token::LPAREN => "(",
+            Token::RPAREN => ")",
+            Token::LBRACE => "[",
+            Token::RBRACE => "]",
+            Token::SEMICOLON => ";",
+            Token::COMMA => ",",
+            Token::COLON => ":",
+            Token::COLONCOLON => "::",
+            Token::DOT => ".",
+            Token::ELLIPSIS => "...",
+            Token::AT => "@",
+            Token::ANDAND => "&&",
+            Token::BARBAR => "||",
+            Token::EXCLAMATIONPOINT => "!",
+            Token::LESSTHAN => "<",
+            Token::GREATERTHAN => ">",
+            Token::NOTEQUAL => "!=",
+            Token::EQUALEQUAL => "==",
+            Token::LESSTHANEQUAL => "<=",
+            Token::GREATERTHANEQUAL => ">=",
+            Token::AMPERSAND => "&",
+            Token::BAR => "|",
+            Token::CARET => "^",
+            Token::TILDE => "~",
+            Token::LSHIFT => "<<",
+            Token::RSHIFT => ">>",
+            Token::POINTER => "->",
+            // Keywords were spelled above
+            _ => "",
+        };
+        spelling.to_string()
+    }
+}
+
 impl fmt::Display for Token {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:?}", self)
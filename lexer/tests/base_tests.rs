@@ -1,3 +1,9 @@
+use common::{
+    config::{KeywordSet, LanguageConfig, LexerConfig},
+    literal::ByteString,
+    location::Span,
+    symbol::Symbol,
+};
 use lexer::{
     core::Lexer,
     token::Token,
@@ -21,7 +27,22 @@ fn test_num() {
     let input = "2";
     let result = Lexer::lex(input);
     let expected = vec![
-    	Token::NUMBER(vec!['2']), Token::EOF,
+    	Token::NUMBER(Symbol::intern("2")), Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_float_numbers() {
+    let input = "3.14 1e-9 2.5f 6.02E+23 1.";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::NUMBER(Symbol::intern("3.14")),
+        Token::NUMBER(Symbol::intern("1e-9")),
+        Token::NUMBER(Symbol::intern("2.5f")),
+        Token::NUMBER(Symbol::intern("6.02E+23")),
+        Token::NUMBER(Symbol::intern("1.")),
+        Token::EOF,
     ];
     assert_eq!(result, Ok(expected));
 }
@@ -196,6 +217,16 @@ fn test_enum() {
     assert_eq!(result, Ok(expected));
 }
 
+#[test]
+fn test_union() {
+    let input = "union";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::UNION, Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
 #[test]
 fn test_if() {
     let input = "if";
@@ -416,6 +447,27 @@ fn test_geq() {
     assert_eq!(result, Ok(expected));
 }
 
+#[test]
+fn test_shifts() {
+    let input = "<< >> <<=";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::LSHIFT, Token::RSHIFT, Token::LSHIFTEQUAL, Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_compound_assignments() {
+    let input = "+= -= *= /= %= <<= >>= &= |= ^=";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::PLUSEQUAL, Token::DASHEQUAL, Token::ASTERISKEQUAL, Token::FSLASHEQUAL, Token::PERCENTEQUAL,
+        Token::LSHIFTEQUAL, Token::RSHIFTEQUAL, Token::AMPERSANDEQUAL, Token::BAREQUAL, Token::CARETEQUAL, Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
 #[test]
 fn test_int() {
     let input = "int";
@@ -556,6 +608,92 @@ fn test_const() {
     assert_eq!(result, Ok(expected));
 }
 
+#[test]
+fn test_volatile() {
+    let input = "volatile";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::VOLATILE, Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_storage_classes() {
+    let input = "static extern inline statically";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::STATIC, Token::EXTERN, Token::INLINE, Token::IDENTIFIER(Symbol::intern("statically")), Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_scope_resolution() {
+    let input = "Color::Red a ? b : c x:::y";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::IDENTIFIER(Symbol::intern("Color")), Token::COLONCOLON, Token::IDENTIFIER(Symbol::intern("Red")),
+        Token::IDENTIFIER(Symbol::intern("a")), Token::CTRUE, Token::IDENTIFIER(Symbol::intern("b")), Token::COLON,
+        Token::IDENTIFIER(Symbol::intern("c")),
+        Token::IDENTIFIER(Symbol::intern("x")), Token::COLONCOLON, Token::COLON, Token::IDENTIFIER(Symbol::intern("y")),
+        Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_typedef() {
+    let input = "typedef";
+    let result = Lexer::lex_with_config(input, LanguageConfig::extended());
+    let expected = vec![
+        Token::TYPEDEF, Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+
+    // Without the feature, `typedef` is an ordinary identifier
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::IDENTIFIER(Symbol::intern("typedef")), Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_disabled_keyword_sets() {
+    let input = "switch case default if static";
+    let config = LanguageConfig {
+        lexer: LexerConfig::default().disable(KeywordSet::Switch).disable(KeywordSet::StorageClasses),
+        ..LanguageConfig::strict()
+    };
+    let result = Lexer::lex_with_config(input, config);
+    let expected = vec![
+        Token::IDENTIFIER(Symbol::intern("switch")), Token::IDENTIFIER(Symbol::intern("case")),
+        Token::IDENTIFIER(Symbol::intern("default")), Token::IF, Token::IDENTIFIER(Symbol::intern("static")), Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+
+    // Re-enabling a set makes its words keywords again
+    let config = LanguageConfig { lexer: LexerConfig::none().enable(KeywordSet::Switch), ..LanguageConfig::strict() };
+    let result = Lexer::lex_with_config(input, config);
+    let expected = vec![
+        Token::SWITCH, Token::CASE, Token::DEFAULT, Token::IDENTIFIER(Symbol::intern("if")),
+        Token::IDENTIFIER(Symbol::intern("static")), Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_attribute() {
+    let input = "@packed";
+    let result = Lexer::lex_with_config(input, LanguageConfig::extended());
+    let expected = vec![
+        Token::AT, Token::IDENTIFIER(Symbol::intern("packed")), Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+    assert!(Lexer::lex(input).is_err());
+}
+
 #[test]
 fn test_true() {
     let input = "?";
@@ -565,3 +703,140 @@ fn test_true() {
     ];
     assert_eq!(result, Ok(expected));
 }
+
+#[test]
+fn test_string_literal() {
+    let input = "\"hi\"";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::STRINGLITERAL(ByteString::from("hi")), Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_raw_string_literal() {
+    let input = "r\"C:\\new\\d+\" r#\"say \"hi\"\"# r\"\" r x";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::STRINGLITERAL(ByteString::from("C:\\new\\d+")),
+        Token::STRINGLITERAL(ByteString::from("say \"hi\"")),
+        Token::STRINGLITERAL(ByteString::from("")),
+        Token::IDENTIFIER(Symbol::intern("r")),
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_char_literal() {
+    let input = "'a'";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::CHAR('a'), Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_hex_escape() {
+    let input = "'\\x41' \"\\x48\\x69\"";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::CHAR('A'), Token::STRINGLITERAL(ByteString::from("Hi")), Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_simple_escapes() {
+    let input = "'\\n' '\\'' '\\\\' '\\t' '\\0' \"\\\"\\a\\?\"";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::CHAR('\n'), Token::CHAR('\''), Token::CHAR('\\'), Token::CHAR('\t'), Token::CHAR('\0'),
+        Token::STRINGLITERAL(ByteString::from("\"\x07?")), Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_high_byte_escapes() {
+    // In a string, an escape from 0x80 up is one byte rather than a character, unlike a `\u{..}` escape
+    let input = "\"\\xFF\\303\\u{ff}\" '\\xFF'";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::STRINGLITERAL(ByteString::new(vec![0xFF, 0xC3, 0xC3, 0xBF])), Token::CHAR('\u{ff}'), Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+
+    // Characters in the private use area are kept as their UTF-8 bytes
+    let result = Lexer::lex("\"\\u{ef80}\u{efff}\"");
+    let expected = vec![Token::STRINGLITERAL(ByteString::new(vec![0xEE, 0xBE, 0x80, 0xEE, 0xBF, 0xBF])), Token::EOF];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_octal_escape() {
+    let input = "'\\101' \"\\1234\"";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::CHAR('A'), Token::STRINGLITERAL(ByteString::from("S4")), Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_unicode_escape() {
+    let input = "\"\\u{1F600}!\" '\\u{e9}'";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::STRINGLITERAL(ByteString::from("\u{1F600}!")), Token::CHAR('\u{e9}'), Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_line_directive_is_skipped() {
+    let input = "#line 10 \"gen.c\"\nx";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::IDENTIFIER(Symbol::intern("x")), Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_locations_without_line_directive() {
+    let input = "int x;\n  y";
+    let result = Lexer::lex_with_locations(input).expect("Failed to lex");
+    let locations: Vec<(usize, usize)> = result.iter().map(|(_, loc)| (loc.line, loc.column)).collect();
+    assert_eq!(locations, vec![(1, 1), (1, 5), (1, 6), (2, 3), (2, 4)]);
+}
+
+#[test]
+fn test_locations_with_line_directive() {
+    let input = "a\n#line 40 \"orig.c\"\nb\nc\n# 7 \"other.c\" 2\nd";
+    let result = Lexer::lex_with_locations(input).expect("Failed to lex");
+    let locations: Vec<String> = result.iter().map(|(_, loc)| loc.to_string()).collect();
+    assert_eq!(locations, vec!["1:1", "orig.c:40:1", "orig.c:41:1", "other.c:7:1", "other.c:7:2"]);
+}
+
+#[test]
+fn test_line_index_with_line_directive() {
+    let input = "a\n#line 40 \"orig.c\"\nb\nc\n# 7 \"other.c\" 2\nd";
+    let index = Lexer::line_index(input, LanguageConfig::default());
+    let spans = Lexer::lex_with_spans(input).expect("Failed to lex");
+    let locations: Vec<String> = spans.iter().map(|(_, span)| index.location(span.offset).to_string()).collect();
+    assert_eq!(locations, vec!["1:1", "orig.c:40:1", "orig.c:41:1", "other.c:7:1", "other.c:7:2"]);
+}
+
+#[test]
+fn test_spans_cover_token_text() {
+    let input = "int y = \"hé\\n\";\n  x1 += 2.5f;";
+    let result = Lexer::lex_with_spans(input).expect("Failed to lex");
+    let texts: Vec<&str> = result.iter().map(|(_, span)| span.text(input).unwrap()).collect();
+    assert_eq!(texts, vec!["int", "y", "=", "\"hé\\n\"", ";", "x1", "+=", "2.5f", ";", ""]);
+    assert_eq!(result[3].0, Token::STRINGLITERAL(ByteString::from("hé\n")));
+    assert_eq!(result.last().unwrap().1, Span::new(input.len(), 0));
+}
@@ -1,4 +1,4 @@
-use common::error::ErrorType;
+use common::{error::ErrorType, symbol::Symbol};
 use lexer::{
     core::Lexer,
     token::Token,
@@ -12,15 +12,15 @@ fn test_complex_expressions() {
     let input: &str = "x = 5 + 10 / 5 % 3;";
     let result: Result<Vec<Token>, Vec<ErrorType>> = Lexer::lex(input);
     let expected: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::EQUAL,
-        Token::NUMBER(vec!['5']),
+        Token::NUMBER(Symbol::intern("5")),
         Token::PLUS,
-        Token::NUMBER(vec!['1', '0']),
+        Token::NUMBER(Symbol::intern("10")),
         Token::FSLASH,
-        Token::NUMBER(vec!['5']),
+        Token::NUMBER(Symbol::intern("5")),
         Token::PERCENT,
-        Token::NUMBER(vec!['3']),
+        Token::NUMBER(Symbol::intern("3")),
         Token::SEMICOLON,
         Token::EOF,
     ];
@@ -32,9 +32,9 @@ fn test_whitespace_handling() {
     let input = "  x   = 5  ;  ";
     let result = Lexer::lex(input);
     let expected = vec![
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::EQUAL,
-        Token::NUMBER(vec!['5']),
+        Token::NUMBER(Symbol::intern("5")),
         Token::SEMICOLON,
         Token::EOF,
     ];
@@ -46,11 +46,11 @@ fn test_assignment() {
     let input = "x: int = 5;";
     let result = Lexer::lex(input);
     let expected = vec![
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::COLON,
         Token::TINTEGER, 
         Token::EQUAL,
-        Token::NUMBER(vec!['5']),
+        Token::NUMBER(Symbol::intern("5")),
         Token::SEMICOLON,
         Token::EOF,
     ];
@@ -62,13 +62,13 @@ fn test_function_declarations() {
     let input: &str = "add(a: int, b: int) -> int { return a + b; }";
     let result: Result<Vec<Token>, Vec<ErrorType>> = Lexer::lex(input);
     let expected: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['a', 'd', 'd']),
+        Token::IDENTIFIER(Symbol::intern("add")),
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['a']),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::COLON,
         Token::TINTEGER,
         Token::COMMA,
-        Token::IDENTIFIER(vec!['b']),
+        Token::IDENTIFIER(Symbol::intern("b")),
         Token::COLON,
         Token::TINTEGER,
         Token::RPAREN,
@@ -76,9 +76,9 @@ fn test_function_declarations() {
         Token::TINTEGER,
         Token::LBRACKET,
         Token::RETURN,
-        Token::IDENTIFIER(vec!['a']),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::PLUS,
-        Token::IDENTIFIER(vec!['b']),
+        Token::IDENTIFIER(Symbol::intern("b")),
         Token::SEMICOLON,
         Token::RBRACKET,
         Token::EOF,
@@ -91,26 +91,26 @@ fn test_if_else_in_function() {
     let input: &str = "check(x: int) { if x > 0 { return 1; } else { return 0; } }";
     let result: Result<Vec<Token>, Vec<ErrorType>> = Lexer::lex(input);
     let expected: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['c', 'h', 'e', 'c', 'k']),
+        Token::IDENTIFIER(Symbol::intern("check")),
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::COLON,
         Token::TINTEGER,
         Token::RPAREN,
         Token::LBRACKET,
         Token::IF,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::GREATERTHAN, 
-        Token::NUMBER(vec!['0']),
+        Token::NUMBER(Symbol::intern("0")),
         Token::LBRACKET,
         Token::RETURN,
-        Token::NUMBER(vec!['1']),
+        Token::NUMBER(Symbol::intern("1")),
         Token::SEMICOLON,
         Token::RBRACKET,
         Token::ELSE,
         Token::LBRACKET,
         Token::RETURN,
-        Token::NUMBER(vec!['0']),
+        Token::NUMBER(Symbol::intern("0")),
         Token::SEMICOLON,
         Token::RBRACKET,
         Token::RBRACKET,
@@ -124,18 +124,18 @@ fn test_logical_operators_and_parentheses() {
     let input: &str = "result = (5 > 3) && (2 < 4);";
     let result: Result<Vec<Token>, Vec<ErrorType>> = Lexer::lex(input);
     let expected: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['r', 'e', 's', 'u', 'l', 't']),
+        Token::IDENTIFIER(Symbol::intern("result")),
         Token::EQUAL,
         Token::LPAREN,
-        Token::NUMBER(vec!['5']),
+        Token::NUMBER(Symbol::intern("5")),
         Token::GREATERTHAN, 
-        Token::NUMBER(vec!['3']),
+        Token::NUMBER(Symbol::intern("3")),
         Token::RPAREN,
         Token::ANDAND,
         Token::LPAREN,
-        Token::NUMBER(vec!['2']),
+        Token::NUMBER(Symbol::intern("2")),
         Token::LESSTHAN, 
-        Token::NUMBER(vec!['4']),
+        Token::NUMBER(Symbol::intern("4")),
         Token::RPAREN,
         Token::SEMICOLON,
         Token::EOF,
@@ -148,18 +148,18 @@ fn test_nested_function_calls() {
     let input: &str = "val = add(multiply(2, 3), 4);";
     let result: Result<Vec<Token>, Vec<ErrorType>> = Lexer::lex(input);
     let expected: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['v', 'a', 'l']),
+        Token::IDENTIFIER(Symbol::intern("val")),
         Token::EQUAL,
-        Token::IDENTIFIER(vec!['a', 'd', 'd']),
+        Token::IDENTIFIER(Symbol::intern("add")),
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['m', 'u', 'l', 't', 'i', 'p', 'l', 'y']),
+        Token::IDENTIFIER(Symbol::intern("multiply")),
         Token::LPAREN,
-        Token::NUMBER(vec!['2']),
+        Token::NUMBER(Symbol::intern("2")),
         Token::COMMA,
-        Token::NUMBER(vec!['3']),
+        Token::NUMBER(Symbol::intern("3")),
         Token::RPAREN,
         Token::COMMA,
-        Token::NUMBER(vec!['4']),
+        Token::NUMBER(Symbol::intern("4")),
         Token::RPAREN,
         Token::SEMICOLON,
         Token::EOF,
@@ -172,7 +172,7 @@ fn test_class() {
     let input = "foo {}";
     let result = Lexer::lex(input);
     let expected = vec![
-        Token::IDENTIFIER(vec!['f', 'o', 'o']),
+        Token::IDENTIFIER(Symbol::intern("foo")),
         Token::LBRACKET, Token::RBRACKET, Token::EOF
     ];
     assert_eq!(result, Ok(expected));
@@ -193,8 +193,8 @@ fn test_identifiers() {
     let input = "variable another_var";
     let result = Lexer::lex(input);
     let expected = vec![
-        Token::IDENTIFIER(vec!['v', 'a', 'r', 'i', 'a', 'b', 'l', 'e']),
-        Token::IDENTIFIER(vec!['a', 'n', 'o', 't', 'h', 'e', 'r', '_', 'v', 'a', 'r']),
+        Token::IDENTIFIER(Symbol::intern("variable")),
+        Token::IDENTIFIER(Symbol::intern("another_var")),
         Token::EOF,
     ];
     assert_eq!(result, Ok(expected));
@@ -205,8 +205,8 @@ fn test_int_literals() {
     let input = "123 456";
     let result = Lexer::lex(input);
     let expected = vec![
-        Token::NUMBER(vec!['1', '2', '3']),
-        Token::NUMBER(vec!['4', '5', '6']),
+        Token::NUMBER(Symbol::intern("123")),
+        Token::NUMBER(Symbol::intern("456")),
         Token::EOF,
     ];
     assert_eq!(result, Ok(expected));
@@ -248,6 +248,35 @@ fn test_arithmetic_operators() {
     assert_eq!(result, Ok(expected));
 }
 
+#[test]
+fn test_bitwise_assignment_operators() {
+    let input = "flags &= mask; flags |= 4; flags ^= x&y|z^w;";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::IDENTIFIER(Symbol::intern("flags")), Token::AMPERSANDEQUAL,
+        Token::IDENTIFIER(Symbol::intern("mask")), Token::SEMICOLON,
+        Token::IDENTIFIER(Symbol::intern("flags")), Token::BAREQUAL, Token::NUMBER(Symbol::intern("4")), Token::SEMICOLON,
+        Token::IDENTIFIER(Symbol::intern("flags")), Token::CARETEQUAL,
+        Token::IDENTIFIER(Symbol::intern("x")), Token::AMPERSAND, Token::IDENTIFIER(Symbol::intern("y")), Token::BAR,
+        Token::IDENTIFIER(Symbol::intern("z")), Token::CARET, Token::IDENTIFIER(Symbol::intern("w")), Token::SEMICOLON,
+        Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_variadic_declaration() {
+    let input = "int printf(char fmt, ...); p.x;";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::TINTEGER, Token::IDENTIFIER(Symbol::intern("printf")), Token::LPAREN,
+        Token::TCHAR, Token::IDENTIFIER(Symbol::intern("fmt")), Token::COMMA, Token::ELLIPSIS, Token::RPAREN,
+        Token::SEMICOLON, Token::IDENTIFIER(Symbol::intern("p")), Token::DOT, Token::IDENTIFIER(Symbol::intern("x")), Token::SEMICOLON,
+        Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
 #[test]
 fn test_logical_operators() {
     let input = "&& || !";
@@ -311,14 +340,14 @@ fn test_dot_and_coloncolon_operators() {
     let input = ". ::";
     let result = Lexer::lex(input);
     let expected = vec![
-        Token::DOT, Token::COLON, Token::COLON, Token::EOF,
+        Token::DOT, Token::COLONCOLON, Token::EOF,
     ];
     assert_eq!(result, Ok(expected));
 }
 
 #[test]
 fn test_block_comments() {
-    let input = "* / /* */ * /* * / /*";
+    let input = "* / /* */ * /* * / */";
     let result = Lexer::lex(input);
     let expected = vec![
         Token::ASTERISK, Token::FSLASH,
@@ -336,3 +365,15 @@ fn test_single_line_comment() {
     ];
     assert_eq!(result, Ok(expected));
 }
+
+#[test]
+fn test_line_comments() {
+    let input = "// whole-line comment\nint x = 1; // end of statement\n// mail me@home\nx = 2;";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::TINTEGER, Token::IDENTIFIER(Symbol::intern("x")), Token::EQUAL, Token::NUMBER(Symbol::intern("1")), Token::SEMICOLON,
+        Token::IDENTIFIER(Symbol::intern("x")), Token::EQUAL, Token::NUMBER(Symbol::intern("2")), Token::SEMICOLON,
+        Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
@@ -0,0 +1,106 @@
+//! cargo test --test detokenize_tests
+//! Tests writing tokens back as source text, exactly with their trivia or in the canonical layout.
+
+use common::{config::LanguageConfig, location::SourceLocation};
+use lexer::{
+    core::Lexer,
+    detokenize::{detokenize, detokenize_with_locations, detokenize_with_trivia},
+    token::Token,
+};
+
+const SOURCE: &str = "#include \"io.h\"\n\
+    /* Sums the odd numbers. */\n\
+    int   sum(int n) {\n\
+    \tint total = 0; // running total\n\
+    #line 40 \"gen.c\"\n\
+    \tfor (int i = 0; i < n; i++) { if (i % 2 != 0) { total += i; } }\n\
+    \tchar c = '\\t'; p->x = s.y; c = Color::Red; f(-1, --k, a - -b);\n\
+    \tputs(\"tab\\tquote\\\" bell\\a1\");\n\
+    \treturn total;\n\
+    }\n  ";
+
+#[test]
+fn test_trivia_round_trip() {
+    let tokens = Lexer::lex_with_trivia(SOURCE, LanguageConfig::default()).expect("Failed to lex");
+    assert_eq!(detokenize_with_trivia(&tokens), SOURCE);
+    assert_eq!(tokens.iter().map(|t| t.token.clone()).collect::<Vec<_>>(), Lexer::lex(SOURCE).unwrap());
+
+    let first_int = tokens.iter().find(|t| t.token == Token::TINTEGER).unwrap();
+    assert_eq!(first_int.leading, "\n/* Sums the odd numbers. */\n");
+    assert_eq!(tokens.last().unwrap().leading, "\n  ");
+}
+
+#[test]
+fn test_trivia_rename() {
+    let source = "int x = 1; // x\nx = x + 1;";
+    let mut tokens = Lexer::lex_with_trivia(source, LanguageConfig::default()).expect("Failed to lex");
+    for token in tokens.iter_mut().filter(|t| t.text == "x") {
+        token.text = "count".to_string();
+    }
+    assert_eq!(detokenize_with_trivia(&tokens), "int count = 1; // x\ncount = count + 1;");
+}
+
+#[test]
+fn test_canonical_round_trip() {
+    let tokens = Lexer::lex(SOURCE).expect("Failed to lex");
+    let canonical = detokenize(&tokens);
+    assert_eq!(Lexer::lex(&canonical), Ok(tokens));
+    assert_eq!(canonical.lines().next(), Some("#include \"io.h\""));
+    assert!(canonical.contains("int sum(int n) {"));
+    assert!(canonical.contains("p->x = s.y; c = Color::Red; f(- 1, -- k, a - - b);"));
+    assert!(canonical.contains("puts(\"tab\\tquote\\\" bell\\0071\");"));
+}
+
+#[test]
+fn test_switch_labels_round_trip() {
+    let tokens = Lexer::lex("switch (x) { case 1: break; default: break; }").expect("Failed to lex");
+    assert!(tokens.contains(&Token::DEFAULT));
+    let canonical = detokenize(&tokens);
+    assert_eq!(canonical, "switch (x) { case 1 : break; default : break; }");
+    assert_eq!(Lexer::lex(&canonical), Ok(tokens));
+}
+
+#[test]
+fn test_high_byte_escape_round_trip() {
+    // Bytes that form UTF-8 are spelled as their characters, and the rest as octal escapes
+    let tokens = Lexer::lex("puts(\"\\xC3\\xA9 \\u{e9} \\xFF\\xC3\");").expect("Failed to lex");
+    let canonical = detokenize(&tokens);
+    assert_eq!(canonical, "puts(\"é é \\377\\303\");");
+    assert_eq!(Lexer::lex(&canonical), Ok(tokens));
+}
+
+#[test]
+fn test_located_round_trip() {
+    let source = &SOURCE[SOURCE.find('\n').unwrap() + 1..];
+    let tokens = Lexer::lex_with_locations(source).expect("Failed to lex");
+    let written = detokenize_with_locations(&tokens);
+    let relexed = Lexer::lex_with_locations(&written).expect("Failed to lex the written text");
+
+    let lines = |tokens: &[(Token, SourceLocation)]| tokens.iter()
+        .filter(|(token, _)| *token != Token::EOF)
+        .map(|(token, location)| (token.clone(), location.file.clone(), location.line))
+        .collect::<Vec<_>>();
+    assert_eq!(lines(&relexed), lines(&tokens));
+    let sum = |tokens: &[(Token, SourceLocation)]| tokens.iter()
+        .find(|(token, _)| *token == Token::IDENTIFIER("sum".into()))
+        .map(|(_, location)| location.clone());
+    assert_eq!(sum(&relexed), sum(&tokens));
+}
+
+#[test]
+fn test_located_line_directives() {
+    let at = |file: &str, line: usize, column: usize| SourceLocation::new(Some(file.to_string()), line, column);
+    let tokens = vec![
+        (Token::TINTEGER, at("main.c", 1, 1)),
+        (Token::IDENTIFIER("y".into()), at("main.c", 1, 5)),
+        (Token::IDENTIFIER("y".into()), at("main.c", 3, 3)),
+        (Token::IDENTIFIER("z".into()), at("defs.h", 1, 1)),
+        (Token::SEMICOLON, at("main.c", 2, 1)),
+        (Token::IDENTIFIER("w".into()), at("main.c", 20, 1)),
+        (Token::EOF, at("main.c", 20, 2)),
+    ];
+    assert_eq!(
+        detokenize_with_locations(&tokens),
+        "#line 1 \"main.c\"\nint y\n\n  y\n#line 1 \"defs.h\"\nz\n#line 2 \"main.c\"\n;\n#line 20 \"main.c\"\nw",
+    );
+}
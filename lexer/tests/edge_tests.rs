@@ -1,4 +1,4 @@
-use common::error::ErrorType;
+use common::{error::ErrorType, symbol::Symbol};
 
 use lexer::{
     core::Lexer,
@@ -24,8 +24,8 @@ fn test_identifiers() {
     let input = "variable another_var";
     let result = Lexer::lex(input);
     let expected = vec![
-        Token::IDENTIFIER(vec!['v', 'a', 'r', 'i', 'a', 'b', 'l', 'e']),
-        Token::IDENTIFIER(vec!['a', 'n', 'o', 't', 'h', 'e', 'r', '_', 'v', 'a', 'r']),
+        Token::IDENTIFIER(Symbol::intern("variable")),
+        Token::IDENTIFIER(Symbol::intern("another_var")),
         Token::EOF,
     ];
     assert_eq!(result, Ok(expected));
@@ -36,8 +36,8 @@ fn test_int_literals() {
     let input = "123 456";
     let result = Lexer::lex(input);
     let expected = vec![
-        Token::NUMBER(vec!['1', '2', '3']),
-        Token::NUMBER(vec!['4', '5', '6']),
+        Token::NUMBER(Symbol::intern("123")),
+        Token::NUMBER(Symbol::intern("456")),
         Token::EOF,
     ];
     assert_eq!(result, Ok(expected));
@@ -151,14 +151,14 @@ fn test_dot_and_coloncolon_operators() {
     let input = ". ::";
     let result = Lexer::lex(input);
     let expected = vec![
-        Token::DOT, Token::COLON, Token::COLON, Token::EOF,
+        Token::DOT, Token::COLONCOLON, Token::EOF,
     ];
     assert_eq!(result, Ok(expected));
 }
 
 #[test]
 fn test_block_comments() {
-    let input = "* / /* */ * /* * / /*";
+    let input = "* / /* */ * /* * / */";
     let result = Lexer::lex(input);
     let expected = vec![
         Token::ASTERISK, Token::FSLASH,
@@ -176,3 +176,23 @@ fn test_single_line_comment() {
     ];
     assert_eq!(result, Ok(expected));
 }
+
+#[test]
+fn test_block_comments_do_not_nest() {
+    let input = "/* a /* b */ c */";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::IDENTIFIER(Symbol::intern("c")), Token::ASTERISK, Token::FSLASH, Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
+
+#[test]
+fn test_block_comment_closed_by_shared_star() {
+    let input = "/*/ x */ y";
+    let result = Lexer::lex(input);
+    let expected = vec![
+        Token::IDENTIFIER(Symbol::intern("y")), Token::EOF,
+    ];
+    assert_eq!(result, Ok(expected));
+}
@@ -48,3 +48,107 @@ fn test_at_in_statement() {
     let expected = Err(vec![expected_error]);
     assert_eq!(result, expected);
 }
+
+#[test]
+fn test_hex_escape_out_of_range() {
+    let input = "'\\x141'";
+    let result = Lexer::lex(input);
+    let expected_error = ErrorType::InvalidEscapeSequence{sequence: "\\x141".to_string()};
+    let expected = Err(vec![expected_error]);
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn test_octal_escape_out_of_range() {
+    let input = "'\\777'";
+    let result = Lexer::lex(input);
+    let expected_error = ErrorType::InvalidEscapeSequence{sequence: "\\777".to_string()};
+    let expected = Err(vec![expected_error]);
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn test_unicode_escape_not_a_scalar() {
+    let input = "\"a\\u{D800}b\" x";
+    let result = Lexer::lex(input);
+    let expected_error = ErrorType::InvalidEscapeSequence{sequence: "\\u{D800}".to_string()};
+    let expected = Err(vec![expected_error]);
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn test_unicode_escape_missing_brace() {
+    let input = "'\\u41'";
+    let result = Lexer::lex(input);
+    assert!(matches!(result, Err(ref errors) if errors[0] == ErrorType::InvalidEscapeSequence{sequence: "\\u".to_string()}));
+}
+
+#[test]
+fn test_unterminated_string() {
+    let input = "\"abc";
+    let result = Lexer::lex(input);
+    let expected_error = ErrorType::InvalidLiteral{
+        literal: "\"abc".to_string(),
+        reason: "unterminated string literal".to_string(),
+    };
+    let expected = Err(vec![expected_error]);
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn test_unterminated_raw_string() {
+    let input = "r#\"abc\"\nx";
+    let result = Lexer::lex(input);
+    let expected_error = ErrorType::InvalidLiteral{
+        literal: "r#\"abc\"".to_string(),
+        reason: "unterminated raw string literal".to_string(),
+    };
+    assert_eq!(result, Err(vec![expected_error]));
+}
+
+#[test]
+fn test_malformed_line_directive() {
+    let input = "#line abc\nx";
+    let result = Lexer::lex_with_locations(input);
+    assert!(matches!(result, Err(ref errors) if matches!(errors[0].0, ErrorType::SyntaxError { .. }) && errors[0].1.line == 1));
+}
+
+#[test]
+fn test_unterminated_block_comment() {
+    let input = "int x;\n  /* never closed\nint y;";
+    let result = Lexer::lex_with_locations(input);
+    let errors = result.expect_err("Unterminated comment should be an error");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, ErrorType::UnterminatedBlockComment);
+    assert_eq!((errors[0].1.line, errors[0].1.column), (2, 3));
+}
+
+#[test]
+fn test_exponent_without_digits() {
+    let input = "double d = 1e+;";
+    let result = Lexer::lex(input);
+    assert!(matches!(result, Err(ref errors) if errors[0] == ErrorType::InvalidLiteral{
+        literal: "1e+".to_string(),
+        reason: "exponent has no digits".to_string(),
+    }));
+}
+
+#[test]
+fn test_integer_literal_too_large() {
+    let input = "long x = 9223372036854775808;";
+    let result = Lexer::lex(input);
+    assert_eq!(result, Err(vec![ErrorType::InvalidLiteral {
+        literal: "9223372036854775808".to_string(),
+        reason: "integer literal is larger than the largest integer, 9223372036854775807".to_string(),
+    }]));
+
+    let tokens = Lexer::lex("9223372036854775807 1e400 99999999999999999999.0").expect("Failed to lex");
+    assert_eq!(tokens.len(), 4);
+}
+
+#[test]
+fn test_error_span_covers_offending_text() {
+    let input = "int x = 1e+;";
+    let errors = Lexer::lex_with_spans(input).expect_err("Exponent without digits should be an error");
+    assert_eq!(errors[0].1.text(input), Some("1e+"));
+}
@@ -0,0 +1,76 @@
+//! cargo test --test lossy_tests
+//! Tests lexing arbitrary bytes with `lex_lossy`, which reports invalid UTF-8 and every other error without
+//! panicking, and inputs that used to exhaust the stack or overflow line numbers.
+
+use common::{config::LanguageConfig, error::ErrorType, literal::ByteString, location::Span, symbol::Symbol};
+use lexer::{core::Lexer, token::Token};
+
+/// Bytes that start or end tokens, literals, comments, and directives, or that are not valid UTF-8 on their own.
+const ALPHABET: &[u8] = b"ab r1e.x_ \t\n\r\"'\\/*#@:&|<=>+-(){}[];,?~$\x00\x01\x7f\xc3\xa9\xe2\x82\xef\xbb\xbf\xff\xfe";
+
+/// Generates a pseudo-random input of up to 64 bytes from `ALPHABET`, the same for every run.
+fn fuzz_input(seed: &mut u64) -> Vec<u8> {
+    let mut next = || {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 7;
+        *seed ^= *seed << 17;
+        *seed
+    };
+    let len = next() % 64;
+    (0..len).map(|_| ALPHABET[(next() % ALPHABET.len() as u64) as usize]).collect()
+}
+
+#[test]
+fn test_lex_lossy_never_panics() {
+    let mut seed = 0x2545f4914f6cdd1d;
+    let attributes = LanguageConfig { attributes: true, typedef: true, ..LanguageConfig::default() };
+    for _ in 0..20_000 {
+        let input = fuzz_input(&mut seed);
+        for config in [LanguageConfig::default(), attributes] {
+            let (tokens, errors) = Lexer::lex_lossy(&input, config);
+            assert_eq!(tokens.last().map(|(token, _)| token), Some(&Token::EOF), "input {:?}", input);
+            let spans = tokens.iter().map(|(_, span)| span).chain(errors.iter().map(|(_, span)| span));
+            for span in spans {
+                assert!(span.offset + span.len <= input.len(), "span {:?} of input {:?}", span, input);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_invalid_utf8_is_reported() {
+    let (tokens, errors) = Lexer::lex_lossy(b"int\xffx = \"\xc3\";\x01", LanguageConfig::default());
+    assert_eq!(errors, vec![
+        (ErrorType::InvalidUtf8 { bytes: "\\xff".to_string() }, Span::new(3, 1)),
+        (ErrorType::InvalidUtf8 { bytes: "\\xc3".to_string() }, Span::new(9, 1)),
+        (ErrorType::UnrecognizedToken { token: "\\u{1}".to_string() }, Span::new(12, 1)),
+    ]);
+    let tokens: Vec<Token> = tokens.into_iter().map(|(token, _)| token).collect();
+    assert_eq!(tokens, vec![
+        Token::TINTEGER,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::EQUAL,
+        Token::STRINGLITERAL(ByteString::from(" ")),
+        Token::SEMICOLON,
+        Token::EOF,
+    ]);
+
+    let (tokens, errors) = Lexer::lex_lossy("int x = 1; // é".as_bytes(), LanguageConfig::default());
+    assert!(errors.is_empty());
+    assert_eq!(tokens.len(), 6);
+}
+
+#[test]
+fn test_many_comments_and_directives_in_a_row() {
+    let input = "// comment\n/* block */\n#line 1\n".repeat(100_000) + "x";
+    let tokens = Lexer::lex_with_locations(&input).expect("Failed to lex");
+    assert_eq!(tokens[0].0, Token::IDENTIFIER(Symbol::intern("x")));
+    assert_eq!(tokens[0].1.to_string(), "1:1");
+}
+
+#[test]
+fn test_line_number_overflow() {
+    let input = format!("#line {}\n\n\nx", usize::MAX);
+    let tokens = Lexer::lex_with_locations(&input).expect("Failed to lex");
+    assert_eq!(tokens[0].1.to_string(), format!("{}:1", usize::MAX));
+}
@@ -0,0 +1,58 @@
+//! cargo test --test normalize_tests
+//! Tests lexing sources with a byte order mark and with `\n`, `\r\n`, or `\r` line endings.
+
+use common::{config::LanguageConfig, literal::ByteString, symbol::Symbol};
+use lexer::{
+    core::Lexer,
+    detokenize::detokenize_with_trivia,
+    normalize::{normalize, strip_bom},
+    token::Token,
+};
+
+const SOURCE: &str = "#include \"io.h\"\n\
+    int main() { // entry\n\
+    #line 10 \"gen.c\"\n\
+    \tchar *s = \"a\\tb\";\n\
+    \treturn 0;\n\
+    }\n";
+
+/// Writes `SOURCE` with the given line ending, optionally starting with a byte order mark.
+fn with_line_endings(line_ending: &str, bom: bool) -> String {
+    let source = SOURCE.replace('\n', line_ending);
+    if bom { format!("\u{feff}{}", source) } else { source }
+}
+
+#[test]
+fn test_line_endings_lex_alike() {
+    let expected = Lexer::lex(SOURCE).expect("Failed to lex");
+    let locations = Lexer::lex_with_locations(SOURCE).expect("Failed to lex");
+    for (line_ending, bom) in [("\r\n", false), ("\r", false), ("\n", true), ("\r\n", true), ("\r", true)] {
+        let source = with_line_endings(line_ending, bom);
+        assert_eq!(Lexer::lex(&source), Ok(expected.clone()), "line ending {:?}, BOM {}", line_ending, bom);
+        assert_eq!(Lexer::lex_with_locations(&source), Ok(locations.clone()), "line ending {:?}, BOM {}", line_ending, bom);
+    }
+    assert!(expected.contains(&Token::DIRECTIVE(Symbol::intern("include \"io.h\""))));
+    assert!(expected.contains(&Token::STRINGLITERAL(ByteString::from("a\tb"))));
+}
+
+#[test]
+fn test_spans_index_original_text() {
+    let source = with_line_endings("\r\n", true);
+    let tokens = Lexer::lex_with_trivia(&source, LanguageConfig::default()).expect("Failed to lex");
+    assert_eq!(detokenize_with_trivia(&tokens), source);
+    assert_eq!(tokens[0].leading, "\u{feff}");
+    assert!(tokens.iter().all(|t| !t.text.contains('\r')));
+
+    let spans = Lexer::lex_with_spans(&source).expect("Failed to lex");
+    let (_, main) = spans.iter().find(|(t, _)| *t == Token::IDENTIFIER(Symbol::intern("main"))).unwrap();
+    assert_eq!(&source[main.offset..main.offset + main.len], "main");
+}
+
+#[test]
+fn test_normalize() {
+    assert_eq!(normalize(&with_line_endings("\r\n", true)), SOURCE);
+    assert_eq!(normalize(&with_line_endings("\r", false)), SOURCE);
+    assert_eq!(normalize("a\r\r\nb"), "a\n\nb");
+    assert_eq!(strip_bom("\u{feff}int"), "int");
+    assert_eq!(strip_bom("int"), "int");
+}
@@ -0,0 +1,203 @@
+//! cargo test --test preprocess_tests
+//! Tests expanding preprocessing directives.
+
+use std::{fs, path::PathBuf};
+
+use common::{config::PreprocessorConfig, error::ErrorType, location::SourceLocation};
+use lexer::{core::Lexer, preprocess::Preprocessor, token::Token, tokens};
+
+/// Creates an empty directory for one test, holding the given files.
+fn directory(test: &str, files: &[(&str, &str)]) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("sicc-preprocess-{}-{}", std::process::id(), test));
+    let _ = fs::remove_dir_all(&directory);
+    for (name, contents) in files {
+        let path = directory.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+    directory
+}
+
+#[test]
+fn test_lex_directive() {
+    let result = Lexer::lex("#include \"a.h\"\nint x;").expect("Failed to lex");
+    assert_eq!(result, vec![
+        Token::DIRECTIVE("include \"a.h\"".into()), Token::TINTEGER, Token::IDENTIFIER("x".into()), Token::SEMICOLON,
+        Token::EOF,
+    ]);
+}
+
+#[test]
+fn test_include_next_to_file() {
+    let directory = directory("next_to_file", &[
+        ("main.c", "#include \"lib/consts.h\"\nint y = X;"),
+        ("lib/consts.h", "int X = 1;\n#include \"more.h\"\n"),
+        ("lib/more.h", "int Z;"),
+    ]);
+    let result = Preprocessor::preprocess_file(&directory.join("main.c"), &PreprocessorConfig::default());
+    assert_eq!(result, Ok(tokens![
+        TINTEGER, ident("X"), EQUAL, num(1), SEMICOLON,
+        TINTEGER, ident("Z"), SEMICOLON,
+        TINTEGER, ident("y"), EQUAL, ident("X"), SEMICOLON, EOF,
+    ]));
+}
+
+#[test]
+fn test_include_locations() {
+    let directory = directory("locations", &[
+        ("main.c", "#define ONE 1\n#include \"defs.h\"\nint y = ONE;"),
+        ("defs.h", "\n  int x;"),
+    ]);
+    let main = directory.join("main.c");
+    let result = Preprocessor::preprocess_file_with_locations(&main, &PreprocessorConfig::default());
+    let at = |file: &str, line: usize, column: usize| SourceLocation::new(Some(file.to_string()), line, column);
+    let (main, defs) = (main.display().to_string(), directory.join("defs.h").display().to_string());
+    assert_eq!(result, Ok(vec![
+        (Token::TINTEGER, at(&defs, 2, 3)),
+        (Token::IDENTIFIER("x".into()), at(&defs, 2, 7)),
+        (Token::SEMICOLON, at(&defs, 2, 8)),
+        (Token::TINTEGER, at(&main, 3, 1)),
+        (Token::IDENTIFIER("y".into()), at(&main, 3, 5)),
+        (Token::EQUAL, at(&main, 3, 7)),
+        (Token::NUMBER("1".into()), at(&main, 3, 9)),
+        (Token::SEMICOLON, at(&main, 3, 12)),
+        (Token::EOF, at(&main, 3, 12)),
+    ]));
+}
+
+#[test]
+fn test_include_paths() {
+    let directory = directory("include_paths", &[
+        ("first/a.h", "int first;"),
+        ("second/a.h", "int second;"),
+        ("second/b.h", "int b;"),
+    ]);
+    let config = PreprocessorConfig {
+        include_paths: vec![directory.join("first"), directory.join("second")],
+        ..Default::default()
+    };
+    let result = Preprocessor::preprocess("#include <a.h>\n#include \"b.h\"\n", &config);
+    assert_eq!(result, Ok(tokens![TINTEGER, ident("first"), SEMICOLON, TINTEGER, ident("b"), SEMICOLON, EOF]));
+}
+
+#[test]
+fn test_include_twice_without_cycle() {
+    let directory = directory("twice", &[
+        ("main.c", "#include \"a.h\"\n#include \"a.h\"\n"),
+        ("a.h", "x;"),
+    ]);
+    let result = Preprocessor::preprocess_file(&directory.join("main.c"), &PreprocessorConfig::default());
+    assert_eq!(result, Ok(tokens![ident("x"), SEMICOLON, ident("x"), SEMICOLON, EOF]));
+}
+
+#[test]
+fn test_include_cycle() {
+    let directory = directory("cycle", &[
+        ("main.c", "#include \"a.h\"\n"),
+        ("a.h", "#include \"b.h\"\n"),
+        ("b.h", "#include \"a.h\"\n"),
+    ]);
+    let errors = Preprocessor::preprocess_file(&directory.join("main.c"), &PreprocessorConfig::default())
+        .expect_err("The cycle should be reported");
+    let directory = fs::canonicalize(directory).unwrap();
+    let (a, b) = (directory.join("a.h").display().to_string(), directory.join("b.h").display().to_string());
+    assert_eq!(errors, vec![ErrorType::InvalidInclude {
+        file: "a.h".to_string(),
+        reason: format!("it includes itself through {} -> {} -> {}", a, b, a),
+    }]);
+}
+
+#[test]
+fn test_include_not_found() {
+    let result = Preprocessor::preprocess("#include <missing.h>\n", &PreprocessorConfig::default());
+    assert_eq!(result, Err(vec![ErrorType::InvalidInclude {
+        file: "missing.h".to_string(),
+        reason: "not found in the include paths".to_string(),
+    }]));
+}
+
+#[test]
+fn test_malformed_and_unknown_directives() {
+    let result = Preprocessor::preprocess("#include missing.h\n#pragma once\n", &PreprocessorConfig::default());
+    assert_eq!(result, Err(vec![
+        ErrorType::SyntaxError { message: "Malformed #include directive: #include missing.h".to_string() },
+        ErrorType::SyntaxError { message: "Unknown preprocessing directive: #pragma".to_string() },
+    ]));
+}
+
+#[test]
+fn test_define_substitution() {
+    let directory = directory("define", &[
+        ("main.c", "#include \"limits.h\"\nint a[MAX];\nint y = LIMIT;"),
+        ("limits.h", "#define MAX 100\n#define LIMIT MAX * 2\n"),
+    ]);
+    let result = Preprocessor::preprocess_file(&directory.join("main.c"), &PreprocessorConfig::default());
+    assert_eq!(result, Ok(tokens![
+        TINTEGER, ident("a"), LBRACE, num(100), RBRACE, SEMICOLON,
+        TINTEGER, ident("y"), EQUAL, num(100), ASTERISK, num(2), SEMICOLON, EOF,
+    ]));
+}
+
+#[test]
+fn test_define_not_expanded_in_itself() {
+    let result = Preprocessor::preprocess("int X = 0;\n#define X X + 1\n#define EMPTY\nX EMPTY;", &PreprocessorConfig::default());
+    assert_eq!(result, Ok(tokens![
+        TINTEGER, ident("X"), EQUAL, num(0), SEMICOLON, ident("X"), PLUS, num(1), SEMICOLON, EOF,
+    ]));
+}
+
+#[test]
+fn test_define_redefinition() {
+    let source = "#define MAX 100\n#define MAX  100\n#define MAX 200\n#define (x)\n#define F(x) x\nMAX;";
+    let result = Preprocessor::preprocess(source, &PreprocessorConfig::default());
+    assert_eq!(result, Err(vec![
+        ErrorType::MacroRedefinition {
+            name: "MAX".to_string(),
+            previous: "100".to_string(),
+            replacement: "200".to_string(),
+        },
+        ErrorType::SyntaxError { message: "Malformed #define directive: #define (x)".to_string() },
+        ErrorType::SyntaxError { message: "Function-like macros are not supported: #define F(x) x".to_string() },
+    ]));
+}
+
+#[test]
+fn test_conditionals() {
+    let source = "#ifdef DEBUG\nint debug;\n#ifndef VERBOSE\nint quiet;\n#else\nint verbose;\n#endif\n#else\nint release;\n#endif\n#ifdef NEVER\n#include <missing.h>\n#pragma once\n#endif\n#ifndef DEBUG\nint off;\n#endif\n";
+    let result = Preprocessor::preprocess(source, &PreprocessorConfig::default());
+    assert_eq!(result, Ok(tokens![TINTEGER, ident("release"), SEMICOLON, TINTEGER, ident("off"), SEMICOLON, EOF]));
+
+    let config = PreprocessorConfig::default().define("DEBUG").define("LEVEL=2");
+    let result = Preprocessor::preprocess(&format!("{}int level = LEVEL;", source), &config);
+    assert_eq!(result, Ok(tokens![
+        TINTEGER, ident("debug"), SEMICOLON, TINTEGER, ident("quiet"), SEMICOLON,
+        TINTEGER, ident("level"), EQUAL, num(2), SEMICOLON, EOF,
+    ]));
+}
+
+#[test]
+fn test_conditionals_defined_in_source() {
+    let directory = directory("conditionals", &[
+        ("main.c", "#include \"guarded.h\"\n#include \"guarded.h\"\nint y = X;"),
+        ("guarded.h", "#ifndef GUARDED_H\n#define GUARDED_H\nint X = 1;\n#endif\n"),
+    ]);
+    let result = Preprocessor::preprocess_file(&directory.join("main.c"), &PreprocessorConfig::default());
+    assert_eq!(result, Ok(tokens![
+        TINTEGER, ident("X"), EQUAL, num(1), SEMICOLON, TINTEGER, ident("y"), EQUAL, ident("X"), SEMICOLON, EOF,
+    ]));
+}
+
+#[test]
+fn test_unmatched_conditionals() {
+    let source = "#endif\n#ifdef 1X\n#else\n#else\n#endif\n#else\n#ifndef OPEN\n";
+    let result = Preprocessor::preprocess(source, &PreprocessorConfig::default());
+    assert_eq!(result, Err(vec![
+        ErrorType::SyntaxError { message: "#endif without #ifdef or #ifndef".to_string() },
+        ErrorType::SyntaxError { message: "Malformed #ifdef directive: #ifdef 1X".to_string() },
+        ErrorType::SyntaxError { message: "#else after #else for #ifdef 1X".to_string() },
+        ErrorType::SyntaxError { message: "#else without #ifdef or #ifndef".to_string() },
+        ErrorType::SyntaxError {
+            message: "Unterminated #ifndef OPEN: expected #endif before the end of the file".to_string(),
+        },
+    ]));
+}
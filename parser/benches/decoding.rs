@@ -0,0 +1,34 @@
+//! This benchmark compares decoding a cached AST with lexing and parsing its source again, which is what makes the
+//! binary encoding worth caching. Run it with `cargo bench -p parser`.
+
+use common::ast::encode;
+use lexer::core::Lexer;
+use parser::core::Parser;
+use std::time::{Duration, Instant};
+
+/// How many times each side is run; the reported times are per run.
+const RUNS: u32 = 50;
+
+/// Times `run` over `RUNS` runs and returns the time of one run.
+fn time(mut run: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        run();
+    }
+    start.elapsed() / RUNS
+}
+
+fn main() {
+    let source: String = (0..200).map(|i| format!(
+        "int f{}(int a, int b) {{\n    int c = a;\n    if (a) {{\n        c = c - b;\n    }}\n    while (b) {{\n        b = b - 1;\n    }}\n    return c;\n}}\n",
+        i,
+    )).collect();
+    let ast = Parser::parse(Lexer::lex(&source).expect("the source should lex")).expect("the source should parse");
+    let bytes = encode::encode(&ast);
+
+    let reparsing = time(|| assert!(Parser::parse(Lexer::lex(&source).unwrap()).is_ok()));
+    let decoding = time(|| assert!(encode::decode(&bytes).is_some()));
+
+    println!("source: {} bytes, encoding: {} bytes", source.len(), bytes.len());
+    println!("reparsing: {:?}, decoding: {:?} ({:.1}x)", reparsing, decoding, reparsing.as_secs_f64() / decoding.as_secs_f64());
+}
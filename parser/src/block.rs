@@ -2,7 +2,7 @@
 
 use common::{ 
     error::ErrorType,
-    ast::core::ASTNode,
+    ast::{core::ASTNode, data_type::DataType},
 };
 use crate::core::Parser;
 use lexer::token::Token;
@@ -41,9 +41,9 @@ impl Parser {
                     self.consume(Token::SEMICOLON, "Unexpected `;` in block")?;
                 }
     
-                // For any other token, try parsing a nested construct
+                // For any other token, try parsing a statement
                 _ => {
-                    if let Some(node) = self.parse_router()? {
+                    if let Some(node) = self.parse_block_statement()? {
                         children.push(node);
                     } else {
                         // Nothing recognized here, just advance
@@ -58,6 +58,89 @@ impl Parser {
             message: "Unclosed block".into(),
         }])
     }
+
+    /// Parses one statement of a block. Expressions are parsed as expression statements, and everything else is
+    /// routed like any other construct.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(ASTNode))` - The parsed statement if successful.
+    /// * `Ok(None)` - If there was nothing to parse at the current token.
+    /// * `Err(Vec<ErrorType>)` - A list of errors if parsing fails.
+    pub fn parse_block_statement(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
+        if self.at_expression_statement() {
+            self.parse_expression_statement()
+        } else {
+            self.parse_router()
+        }
+    }
+
+    /// Parses an expression statement: an expression evaluated for its effects, followed by `;`. The expression is
+    /// wrapped in an `ExpressionStatement` node, except for assignments and compound assignments, which already form
+    /// a statement on their own and may leave out the `;`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(ASTNode))` - The parsed statement if successful.
+    /// * `Err(Vec<ErrorType>)` - A list of errors if parsing fails.
+    ///
+    /// # Errors
+    ///
+    /// * Will return an error if the expression is malformed or is not followed by `;`.
+    pub fn parse_expression_statement(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
+        let expression = self.parse_router()?.ok_or_else(|| vec![ErrorType::SyntaxError {
+            message: "Expected expression".into(),
+        }])?;
+        if matches!(
+            expression.get_node_type(),
+            common::ast::node_type::NodeType::Assignment | common::ast::node_type::NodeType::CompoundAssignment
+        ) {
+            if let Some(Token::SEMICOLON) = self.get_current_token() {
+                self.consume(Token::SEMICOLON, "Expected ';' after assignment")?;
+            }
+            return Ok(Some(expression));
+        }
+        self.consume(Token::SEMICOLON, "Expected ';' after expression")?;
+
+        let mut statement = ASTNode::new(common::ast::node_type::NodeType::ExpressionStatement);
+        statement.add_child(expression);
+        Ok(Some(statement))
+    }
+
+    /// Parses the body of an `if`, `else`, or loop. The body is a block, or, when braceless bodies are enabled, a
+    /// single statement, which is wrapped in a `BlockExpression` so both forms produce the same tree.
+    ///
+    /// # Parameters
+    ///
+    /// * `message`: The error message used when no body is found.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(ASTNode))` - The parsed body as a block expression node if successful.
+    /// * `Err(Vec<ErrorType>)` - A list of errors if parsing fails.
+    ///
+    /// # Errors
+    ///
+    /// * Will return an error if the body is missing, or is not a block while braceless bodies are disabled.
+    pub fn parse_body(&mut self, message: &str) -> Result<Option<ASTNode>, Vec<ErrorType>> {
+        if let Some(Token::LBRACKET) = self.get_current_token() {
+            return self.parse_block();
+        }
+        if !self.config.braceless_bodies {
+            return Err(vec![ErrorType::SyntaxError { message: message.to_string() }]);
+        }
+
+        let statement = self.parse_block_statement()?.ok_or_else(|| vec![ErrorType::SyntaxError {
+            message: message.to_string(),
+        }])?;
+        if let Some(Token::SEMICOLON) = self.get_current_token() {
+            self.consume(Token::SEMICOLON, "Expected ';' after statement")?;
+        }
+
+        let mut block_node = ASTNode::new(common::ast::node_type::NodeType::BlockExpression);
+        block_node.add_child(statement);
+        Ok(Some(block_node))
+    }
     
 
     /// Parses the initialization of a variable or function. 
@@ -72,16 +155,46 @@ impl Parser {
     ///
     /// * Will return an error if a token is missing or if parsing fails at any point.
     pub fn parse_initialization(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
+        // Leading `const` and `volatile`, in either order, are recorded as qualifiers on the variable's type
+        let mut qualifiers = Vec::new();
+        while let Some(token @ (Token::CONST | Token::VOLATILE)) = self.get_current_token() {
+            let qualifier = if *token == Token::CONST { "const" } else { "volatile" };
+            if !qualifiers.contains(&qualifier) {
+                qualifiers.push(qualifier);
+            }
+            self.advance();
+        }
+
         // Parse the data type
-        let type_result = self.parse_type().map_err(|e| vec![e])?;
-        let type_node = ASTNode::new(common::ast::node_type::NodeType::Type(type_result));
-        
-        // Parse the identifier
-        let identifier_name = self.parse_variable_name()?;
-        let identifier_node = ASTNode::new(common::ast::node_type::NodeType::Identifier(identifier_name.clone()));
+        let type_node = if self.at_struct_type() {
+            self.parse_struct_type()?
+        } else {
+            let type_result = self.parse_type().map_err(|e| vec![e])?;
+            ASTNode::new(common::ast::node_type::NodeType::Type(type_result))
+        };
+
+        // `type (*name)(params)` declares a function pointer returning the parsed type
+        let (identifier_node, mut type_node, is_function_pointer) = if let Some(Token::LPAREN) = self.get_current_token() {
+            let (identifier_node, function_type_node) = self.parse_function_pointer_declarator(type_node)?;
+            (identifier_node, function_type_node, true)
+        } else {
+            let start = self.position();
+            let identifier_name = self.parse_variable_name()?;
+            let mut identifier_node = ASTNode::new(common::ast::node_type::NodeType::Identifier(identifier_name));
+            self.mark_span(&mut identifier_node, start);
+            (identifier_node, type_node, false)
+        };
+        for qualifier in &qualifiers {
+            type_node.add_child(ASTNode::new(common::ast::node_type::NodeType::Qualifier(qualifier.to_string())));
+        }
         
         // Check if this is a function declaration (has parentheses after the identifier)
-        if let Some(Token::LPAREN) = self.get_current_token() {
+        if let (Some(Token::LPAREN), false) = (self.get_current_token(), is_function_pointer) {
+            if let Some(qualifier) = qualifiers.first() {
+                return Err(vec![ErrorType::SyntaxError {
+                    message: format!("`{}` is only allowed on variable declarations", qualifier),
+                }]);
+            }
             return self.parse_function_declaration(identifier_node, type_node);
         }
         
@@ -97,20 +210,14 @@ impl Parser {
         if let Some(Token::EQUAL) = self.get_current_token() {
             self.consume(Token::EQUAL, "Expected '=' for variable initialization")?;
             
-            // Parse the assigned value
-            let assigned_value = match self.get_current_token() {
-                Some(Token::NUMBER(_)) => self.parse_primitive()?,
-                Some(Token::IDENTIFIER(_)) => self.parse_identifier()?,
-                Some(Token::DASH) | Some(Token::EXCLAMATIONPOINT) => self.parse_unary_expression()?,
-                _ => {
-                    return Err(vec![ErrorType::SyntaxError {
-                        message: "Expected expression for assigned value".into(),
-                    }]);
-                }
-            }.ok_or_else(|| vec![ErrorType::SyntaxError {
+            // Parse the assigned value, which may be any expression, such as `a + 1` or `a ? b : c`
+            let assigned_value = self.parse_binary_expression()?.ok_or_else(|| vec![ErrorType::SyntaxError {
                 message: "Expected expression for assigned value".into(),
             }])?;
-            
+
+            // Initializers must be constants for globals, so `-5` is folded into a single literal here
+            let assigned_value = Parser::fold_negative_literal(assigned_value);
+
             // Create an AssignedValue node
             let mut assigned_value_node = ASTNode::new(common::ast::node_type::NodeType::AssignedValue);
             assigned_value_node.add_child(assigned_value);
@@ -126,7 +233,63 @@ impl Parser {
         Ok(Some(initialization_node))
     }
 
+    /// Parses a function pointer declarator of the form `(*name)(type, ...)`, which follows the return type
+    /// of a function pointer declaration. Parameter names are optional and ignored.
+    ///
+    /// # Parameters
+    ///
+    /// * `return_type_node`: The `Type` node of the function's return type, parsed before the declarator.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((ASTNode, ASTNode))` - The `Identifier` node of the declared name and a `Type(Function)` node whose
+    ///   children are the return type followed by the parameter types.
+    /// * `Err(Vec<ErrorType>)` - A list of errors if parsing fails.
+    ///
+    /// # Errors
+    ///
+    /// * Will return an error if a token is missing or a parameter type cannot be parsed.
+    pub fn parse_function_pointer_declarator(&mut self, return_type_node: ASTNode) -> Result<(ASTNode, ASTNode), Vec<ErrorType>> {
+        self.consume(Token::LPAREN, "Expected '(' before function pointer name")?;
+        self.consume(Token::ASTERISK, "Expected '*' in function pointer declaration")?;
+        let start = self.position();
+        let name = self.parse_variable_name()?;
+        let mut identifier_node = ASTNode::new(common::ast::node_type::NodeType::Identifier(name));
+        self.mark_span(&mut identifier_node, start);
+        self.consume(Token::RPAREN, "Expected ')' after function pointer name")?;
+
+        let mut function_type_node = ASTNode::new(common::ast::node_type::NodeType::Type(common::ast::data_type::DataType::Function));
+        function_type_node.add_child(return_type_node);
+
+        self.consume(Token::LPAREN, "Expected '(' before function pointer parameter types")?;
+        if let Some(Token::RPAREN) = self.get_current_token() {
+            self.consume(Token::RPAREN, "Expected ')' after function pointer parameter types")?;
+        } else {
+            loop {
+                let param_type = self.parse_type().map_err(|e| vec![e])?;
+                function_type_node.add_child(ASTNode::new(common::ast::node_type::NodeType::Type(param_type)));
+
+                // Parameter names are allowed but carry no meaning in a pointer type
+                if let Some(Token::IDENTIFIER(_)) = self.get_current_token() {
+                    self.advance();
+                }
+
+                match self.get_current_token() {
+                    Some(Token::COMMA) => self.consume(Token::COMMA, "Expected ',' between parameter types")?,
+                    _ => {
+                        self.consume(Token::RPAREN, "Expected ')' after function pointer parameter types")?;
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok((identifier_node, function_type_node))
+    }
+
     /// Parses an if statement. Such a statement is characterized by a leading 'Token::IF', with a subsequent condition expression and body. 
+    /// Each `else if` that follows is added to the statement as an `ElseIf` node with its own condition and body,
+    /// and a final `else` as a block, so a chain of any length is one flat `IfStatement`.
     ///
     /// # Returns
     ///
@@ -146,7 +309,7 @@ impl Parser {
         }])?;
         
         // Parse the 'then' block
-        let then_block = self.parse_block()?.ok_or_else(|| vec![ErrorType::SyntaxError {
+        let then_block = self.parse_body("Expected block after if condition")?.ok_or_else(|| vec![ErrorType::SyntaxError {
             message: "Expected block after if condition".into(),
         }])?;
         
@@ -155,30 +318,37 @@ impl Parser {
         if_statement.add_child(condition);
         if_statement.add_child(then_block);
         
-        // Check for an 'else' clause
-        if let Some(Token::ELSE) = self.get_current_token() {
+        // Check for 'else if' clauses, and a final 'else' clause
+        while let Some(Token::ELSE) = self.get_current_token() {
             self.consume(Token::ELSE, "Expected 'else' token")?;
-            
-            // Parse the 'else' block or 'else if' statement
-            let else_block = match self.get_current_token() {
-                Some(Token::IF) => self.parse_if_statement()?.ok_or_else(|| vec![ErrorType::SyntaxError {
-                    message: "Expected if statement after 'else'".into(),
-                }])?,
-                Some(Token::LBRACKET) => self.parse_block()?.ok_or_else(|| vec![ErrorType::SyntaxError {
-                    message: "Expected block after 'else'".into(),
-                }])?,
-                _ => return Err(vec![ErrorType::SyntaxError {
-                    message: "Expected block or if statement after 'else'".into(),
-                }]),
-            };
-            
+
+            if let Some(Token::IF) = self.get_current_token() {
+                self.consume(Token::IF, "Expected 'if' for else if")?;
+                let condition = self.parse_condition()?.ok_or_else(|| vec![ErrorType::SyntaxError {
+                    message: "Expected condition after 'else if'".into(),
+                }])?;
+                let body = self.parse_body("Expected block after else if condition")?.ok_or_else(|| vec![ErrorType::SyntaxError {
+                    message: "Expected block after else if condition".into(),
+                }])?;
+                let mut else_if = ASTNode::new(common::ast::node_type::NodeType::ElseIf);
+                else_if.add_child(condition);
+                else_if.add_child(body);
+                if_statement.add_child(else_if);
+                continue;
+            }
+
+            let else_block = self.parse_body("Expected block or if statement after 'else'")?.ok_or_else(|| vec![ErrorType::SyntaxError {
+                message: "Expected block after 'else'".into(),
+            }])?;
             if_statement.add_child(else_block);
+            break;
         }
         
         Ok(Some(if_statement))
     }
 
     /// Parses a for loop. Looks for a initialization, condition, and increment expressions, as well as a loop body.
+    /// Any of the three header clauses may be empty, as in `for (;;)`, in which case its node has no children.
     ///
     /// # Returns
     ///
@@ -196,191 +366,64 @@ impl Parser {
         self.consume(Token::LPAREN, "Expected '(' after 'for'")?;
         
         // ----- INITIALIZER -----
+        // Either a declaration such as `int i = 0`, an expression such as `i = 0`, or nothing
         let mut initializer_node = ASTNode::new(common::ast::node_type::NodeType::LoopInitializer);
-        
-        // Check for optional type
-        let type_node = match self.get_current_token() {
+        let clause_start = self.position();
+        let initializer = match self.get_current_token() {
+            Some(Token::SEMICOLON) => None,
+            Some(Token::CONST) | Some(Token::VOLATILE) |
             Some(Token::TINTEGER) | Some(Token::TBOOLEAN) | Some(Token::TDOUBLE) |
             Some(Token::TFLOAT) | Some(Token::TCHAR) | Some(Token::TVOID) |
-            Some(Token::TSIGNINT) | Some(Token::TUSIGN) | Some(Token::TLONG) => {
-                let type_result = self.parse_type().map_err(|e| vec![e])?;
-                Some(ASTNode::new(common::ast::node_type::NodeType::Type(type_result)))
-            }
-            _ => None,
+            Some(Token::TSIGNINT) | Some(Token::TUSIGN) | Some(Token::TLONG) => self.parse_initialization()?,
+            _ => self.parse_binary_expression()?,
         };
-        
-        // Parse identifier
-        let identifier_name = self.parse_variable_name()?;
-        let identifier_name_str = identifier_name;
-        let identifier_node = ASTNode::new(common::ast::node_type::NodeType::Identifier(identifier_name_str.clone()));
-        
-        let mut variable_node = ASTNode::new(common::ast::node_type::NodeType::Variable);
-        variable_node.add_child(identifier_node.clone());
-        if let Some(type_node) = type_node {
-            variable_node.add_child(type_node);
+        if let Some(initializer) = initializer {
+            initializer_node.add_child(initializer);
         }
-        
-        // Parse equals sign
-        self.consume(Token::EQUAL, "Expected '=' in for loop initializer")?;
-        
-        // Parse number
-        let number = match self.get_current_token() {
-            Some(Token::NUMBER(num)) => {
-                let num_str = String::from_iter(num.clone());
-                let node = ASTNode::new(common::ast::node_type::NodeType::Literal(num_str));
-                self.advance();
-                node
-            }
-            _ => {
-                return Err(vec![ErrorType::SyntaxError {
-                    message: "Expected number in for loop initializer".into(),
-                }]);
-            }
-        };
-        
-        // Create assignment node
-        let mut assignment = ASTNode::new(common::ast::node_type::NodeType::Assignment);
-        assignment.add_child(identifier_node);
-        assignment.add_child(number);
-        
-        // Add assignment directly to initializer node (no variable node in the expected AST)
-        initializer_node.add_child(assignment);
         for_loop.add_child(initializer_node);
-        
-        // Consume semicolon
-        self.consume(Token::SEMICOLON, "Expected ';' after for loop initializer")?;
+        self.consume_for_clause_separator(clause_start, "Expected ';' after for loop initializer")?;
         
         // ----- CONDITION -----
+        // An empty condition, as in `for (;;)`, is always true
         let mut condition_node = ASTNode::new(common::ast::node_type::NodeType::Condition);
-        
-        // Manually parse identifier
-        let left_id = match self.get_current_token() {
-            Some(Token::IDENTIFIER(name)) => {
-                let name_str = String::from_iter(name.clone());
-                let node = ASTNode::new(common::ast::node_type::NodeType::Identifier(name_str));
-                self.advance();
-                node
-            }
-            _ => {
-                return Err(vec![ErrorType::SyntaxError {
-                    message: "Expected identifier in for loop condition".into(),
-                }]);
-            }
-        };
-        
-        // Parse operator
-        let operator = match self.get_current_token() {
-            Some(Token::LESSTHAN) => {
-                self.advance();
-                ASTNode::new(common::ast::node_type::NodeType::Operator("<".to_string()))
-            }
-            _ => {
-                return Err(vec![ErrorType::SyntaxError {
-                    message: "Expected comparison operator".into(),
-                }]);
-            }
-        };
-        
-        // Manually parse number
-        let right_operand = match self.get_current_token() {
-            Some(Token::NUMBER(num)) => {
-                let num_str = String::from_iter(num.clone());
-                let node = ASTNode::new(common::ast::node_type::NodeType::Literal(num_str));
-                self.advance();
-                node
-            }
-            _ => {
-                return Err(vec![ErrorType::SyntaxError {
-                    message: "Expected number in for loop condition".into(),
-                }]);
-            }
-        };
-        
-        // Create binary expression
-        let mut binary_expr = ASTNode::new(common::ast::node_type::NodeType::BinaryExpression);
-        binary_expr.add_child(left_id);
-        binary_expr.add_child(operator);
-        binary_expr.add_child(right_operand);
-        
-        condition_node.add_child(binary_expr);
+        let clause_start = self.position();
+        if !matches!(self.get_current_token(), Some(Token::SEMICOLON)) {
+            let condition = self.parse_binary_expression()?.ok_or_else(|| vec![ErrorType::SyntaxError {
+                message: "Expected for loop condition".into(),
+            }])?;
+            condition_node.add_child(condition);
+        }
         for_loop.add_child(condition_node);
-        
-        // Consume semicolon
-        self.consume(Token::SEMICOLON, "Expected ';' after for loop condition")?;
+        self.consume_for_clause_separator(clause_start, "Expected ';' after for loop condition")?;
         
         // ----- INCREMENT -----
         let mut increment_node = ASTNode::new(common::ast::node_type::NodeType::LoopIncrement);
-        
-        // Parse identifier
-        let inc_id = match self.get_current_token() {
-            Some(Token::IDENTIFIER(name)) => {
-                let name_str = String::from_iter(name.clone());
-                let node = ASTNode::new(common::ast::node_type::NodeType::Identifier(name_str));
-                self.advance();
-                node
-            }
-            _ => {
-                return Err(vec![ErrorType::SyntaxError {
-                    message: "Expected identifier in for loop increment".into(),
-                }]);
-            }
-        };
-        
-        // Parse equals sign
-        self.consume(Token::EQUAL, "Expected '=' in for loop increment")?;
-        
-        // Parse right-hand identifier
-        let right_id = match self.get_current_token() {
-            Some(Token::IDENTIFIER(name)) => {
-                let name_str = String::from_iter(name.clone());
-                let node = ASTNode::new(common::ast::node_type::NodeType::Identifier(name_str));
-                self.advance();
-                node
-            }
-            _ => {
-                return Err(vec![ErrorType::SyntaxError {
-                    message: "Expected identifier on right side of assignment".into(),
-                }]);
-            }
-        };
-        
-        // Parse plus sign
-        self.consume(Token::PLUS, "Expected '+' in for loop increment")?;
-        
-        // Parse number
-        let inc_num = match self.get_current_token() {
-            Some(Token::NUMBER(num)) => {
-                let num_str = String::from_iter(num.clone());
-                let node = ASTNode::new(common::ast::node_type::NodeType::Literal(num_str));
+        let postfix_step = matches!(self.peek_next_token(), Some(Token::PLUSPLUS) | Some(Token::MINUSMINUS));
+        let increment = match self.get_current_token() {
+            Some(Token::RPAREN) => None,
+            Some(Token::PLUSPLUS) | Some(Token::MINUSMINUS) => self.parse_unary_expression()?,
+            // `i++` and `i--` are recorded like `++i` and `--i`, since the value of the increment is unused
+            Some(Token::IDENTIFIER(_)) if postfix_step => {
+                let operand = ASTNode::new(common::ast::node_type::NodeType::Identifier(self.parse_variable_name()?));
+                let operator = if matches!(self.get_current_token(), Some(Token::PLUSPLUS)) { "++" } else { "--" };
                 self.advance();
-                node
-            }
-            _ => {
-                return Err(vec![ErrorType::SyntaxError {
-                    message: "Expected number after '+' in increment".into(),
-                }]);
+                let mut unary_expr = ASTNode::new(common::ast::node_type::NodeType::UnaryExpression);
+                unary_expr.add_child(ASTNode::new(common::ast::node_type::NodeType::Operator(operator.to_string())));
+                unary_expr.add_child(operand);
+                Some(unary_expr)
             }
+            _ => self.parse_binary_expression()?,
         };
-        
-        // Create binary expression for x + 1
-        let mut inc_binary = ASTNode::new(common::ast::node_type::NodeType::BinaryExpression);
-        inc_binary.add_child(right_id);
-        inc_binary.add_child(ASTNode::new(common::ast::node_type::NodeType::Operator("+".to_string())));
-        inc_binary.add_child(inc_num);
-        
-        // Create assignment node
-        let mut inc_assignment = ASTNode::new(common::ast::node_type::NodeType::Assignment);
-        inc_assignment.add_child(inc_id);
-        inc_assignment.add_child(inc_binary);
-        
-        increment_node.add_child(inc_assignment);
+        if let Some(increment) = increment {
+            increment_node.add_child(increment);
+        }
         for_loop.add_child(increment_node);
         
         // Consume closing parenthesis
         self.consume(Token::RPAREN, "Expected ')' after for loop increment")?;
         
         // ----- BODY -----
-        let body = self.parse_block()?.ok_or_else(|| vec![ErrorType::SyntaxError {
+        let body = self.parse_body("Expected block for for loop body")?.ok_or_else(|| vec![ErrorType::SyntaxError {
             message: "Expected block for for loop body".into(),
         }])?;
         
@@ -389,6 +432,25 @@ impl Parser {
         Ok(Some(for_loop))
     }
 
+    /// Consumes the `;` ending the initializer or condition of a for loop header. Declarations consume their own
+    /// trailing `;`, so the separator is only required when the clause did not end with one.
+    ///
+    /// # Parameters
+    ///
+    /// * `clause_start`: The position of the first token of the clause.
+    /// * `message`: The error message used if the separator is missing.
+    ///
+    /// # Errors
+    ///
+    /// * Will return an error if the clause is not followed by a `;`.
+    fn consume_for_clause_separator(&mut self, clause_start: usize, message: &str) -> Result<(), Vec<ErrorType>> {
+        let clause_ended = self.position() > clause_start && matches!(self.previous_token(), Some(Token::SEMICOLON));
+        if !clause_ended {
+            self.consume(Token::SEMICOLON, message)?;
+        }
+        Ok(())
+    }
+
 
 
     /// Parses a while loop. Looks for a condition expression, and a loop body.
@@ -411,7 +473,7 @@ impl Parser {
         }])?;
         
         // Parse the loop body
-        let body = self.parse_block()?.ok_or_else(|| vec![ErrorType::SyntaxError {
+        let body = self.parse_body("Expected block for while loop body")?.ok_or_else(|| vec![ErrorType::SyntaxError {
             message: "Expected block for while loop body".into(),
         }])?;
         
@@ -438,7 +500,7 @@ impl Parser {
         self.consume(Token::DO, "Expected 'do' for do-while loop")?;
         
         // Parse the loop body
-        let body = self.parse_block()?.ok_or_else(|| vec![ErrorType::SyntaxError {
+        let body = self.parse_body("Expected block for do-while loop body")?.ok_or_else(|| vec![ErrorType::SyntaxError {
             message: "Expected block for do-while loop body".into(),
         }])?;
         
@@ -461,7 +523,7 @@ impl Parser {
         Ok(Some(do_while_loop))
     }
 
-    /// Parses a switch statement. Looks for an identifier to switch on, and cases.
+    /// Parses a switch statement. Looks for an expression to switch on, and cases.
     ///
     /// # Returns
     ///
@@ -475,26 +537,20 @@ impl Parser {
         // Consume the switch token
         self.consume(Token::SWITCH, "Expected 'switch' for switch statement")?;
         
-        // Parse the condition
-        let condition = self.parse_condition()?.ok_or_else(|| vec![ErrorType::SyntaxError {
-            message: "Expected condition after 'switch'".into(),
+        // Parse the scrutinee, which may be any expression
+        self.consume(Token::LPAREN, "Expected '(' after 'switch'")?;
+        let scrutinee = self.parse_binary_expression()?.ok_or_else(|| vec![ErrorType::SyntaxError {
+            message: "Expected expression after 'switch'".into(),
         }])?;
-        
+        self.consume(Token::RPAREN, "Expected ')' after switch expression")?;
+
         // Consume the opening brace
         self.consume(Token::LBRACKET, "Expected '{' after switch condition")?;
-        
-        // Create the switch statement node
+
+        // Create the switch statement node, with the scrutinee as its first child
         let mut switch_statement = ASTNode::new(common::ast::node_type::NodeType::SwitchStatement);
-        
-        // Extract the identifier from the condition and add it directly (as expected by the tests)
-        let mut condition_children = condition.get_children();
-        if let Some(identifier) = condition_children.pop() {
-            switch_statement.add_child(identifier);
-        } else {
-            // Fallback - use the whole condition if we can't extract the identifier
-            switch_statement.add_child(condition);
-        }
-        
+        switch_statement.add_child(scrutinee);
+
         // Create a block to hold the case/default
         let mut block = ASTNode::new(common::ast::node_type::NodeType::BlockExpression);
         
@@ -549,7 +605,7 @@ impl Parser {
         
         // Parse the case value
         let case_value = match self.get_current_token() {
-            Some(Token::NUMBER(_)) => self.parse_primitive()?,
+            Some(Token::NUMBER(_)) | Some(Token::STRINGLITERAL(_)) | Some(Token::CHAR(_)) => self.parse_primitive()?,
             Some(Token::IDENTIFIER(_)) => self.parse_identifier()?,
             _ => {
                 return Err(vec![ErrorType::SyntaxError {
@@ -586,7 +642,7 @@ impl Parser {
                 },
                 _ => {
                     // Parse a statement within the case
-                    if let Some(stmt) = self.parse_router()? {
+                    if let Some(stmt) = self.parse_block_statement()? {
                         // Add the statement to the block expression
                         block_expr.add_child(stmt);
                     } else {
@@ -640,7 +696,7 @@ impl Parser {
                 },
                 _ => {
                     // Parse a statement within the default
-                    if let Some(stmt) = self.parse_router()? {
+                    if let Some(stmt) = self.parse_block_statement()? {
                         // Add the statement to the block expression
                         block_expr.add_child(stmt);
                     } else {
@@ -659,7 +715,10 @@ impl Parser {
 
     /// Parses a function declaration. This method expects tokens for the function's name (identifier),
     /// return type, parameters, and function body. The resulting AST will include a `FunctionDeclaration`
-    /// node containing the function's identifier, parameters, return type, and body.
+    /// node containing the function's identifier, parameters, return type, and body. A declaration ending in `;`
+    /// instead of a body, as in `int foo(int a);`, is a prototype, and results in a `FunctionPrototype` node with
+    /// the same children but the body. A trailing `...` after the parameters, as in `int printf(char *format, ...)`,
+    /// makes the function variadic, and adds a `Variadic` node after the parameters.
     ///
     /// # Parameters
     ///
@@ -670,10 +729,27 @@ impl Parser {
     ///
     /// Returns an `Option<ASTNode>` containing the parsed function declaration node, or an error `Vec<ErrorType>` if parsing fails.
     ///
+    /// A function defined inside the body of another function is not supported. It is reported and skipped, leaving
+    /// the closing `}` of its body as the current token so the enclosing block can continue after it, and `None` is
+    /// returned.
+    ///
     /// # Errors
     ///
     /// * Returns an error if there is a failure in token consumption or block parsing.
     pub fn parse_function_declaration(&mut self, identifier_node: ASTNode, return_type_node: ASTNode) -> Result<Option<ASTNode>, Vec<ErrorType>> {
+        if self.in_function_body && self.at_function_body_after_parameters() {
+            let name = match identifier_node.get_node_type() {
+                common::ast::node_type::NodeType::Identifier(name) => name,
+                _ => String::new(),
+            };
+            self.recover_from(ErrorType::SyntaxError {
+                message: format!("nested function definitions are not supported: `{}` is defined inside another function", name),
+            });
+            self.skip_balanced(Token::LPAREN, Token::RPAREN, true);
+            self.skip_balanced(Token::LBRACKET, Token::RBRACKET, false);
+            return Ok(None);
+        }
+
         // Consume the opening parenthesis for parameters
         self.consume(Token::LPAREN, "Expected '(' after function name")?;
         
@@ -688,22 +764,43 @@ impl Parser {
         } else {
             // Parse parameters
             loop {
+                // A parameter starts with a type keyword, a struct type, or a `typedef` alias
+                let starts_type = self.at_type_alias() || self.at_struct_type() || matches!(self.get_current_token(),
+                    Some(Token::TINTEGER) | Some(Token::TBOOLEAN) | Some(Token::TDOUBLE) | 
+                    Some(Token::TFLOAT) | Some(Token::TCHAR) | Some(Token::TVOID) | 
+                    Some(Token::TSIGNINT) | Some(Token::TUSIGN) | Some(Token::TLONG));
                 match self.get_current_token() {
                     Some(Token::RPAREN) => {
                         // End of parameters
                         self.consume(Token::RPAREN, "Expected ')' after parameters")?;
                         break;
                     },
-                    Some(Token::TINTEGER) | Some(Token::TBOOLEAN) | Some(Token::TDOUBLE) | 
-                    Some(Token::TFLOAT) | Some(Token::TCHAR) | Some(Token::TVOID) | 
-                    Some(Token::TSIGNINT) | Some(Token::TUSIGN) | Some(Token::TLONG) => {
-                        // Parse parameter (type + identifier)
-                        let type_result = self.parse_type().map_err(|e| vec![e])?;
-                        let type_node = ASTNode::new(common::ast::node_type::NodeType::Type(type_result));
+                    Some(Token::ELLIPSIS) => {
+                        // A trailing `...` makes the function variadic, after at least one named parameter
+                        if function_declaration.get_children().len() < 2 {
+                            return Err(vec![ErrorType::SyntaxError {
+                                message: "Expected a named parameter before '...'".into(),
+                            }]);
+                        }
+                        self.consume(Token::ELLIPSIS, "Expected '...' in parameters")?;
+                        function_declaration.add_child(ASTNode::new(common::ast::node_type::NodeType::Variadic));
+                        self.consume(Token::RPAREN, "Expected ')' after '...', which must be the last parameter")?;
+                        break;
+                    },
+                    Some(_) if starts_type => {
+                        // Parse parameter (type + identifier); struct parameters are passed by value
+                        let type_node = if self.at_struct_type() {
+                            self.parse_struct_type()?
+                        } else {
+                            let type_result = self.parse_type().map_err(|e| vec![e])?;
+                            ASTNode::new(common::ast::node_type::NodeType::Type(type_result))
+                        };
                         
                         // Parse the parameter name
+                        let start = self.position();
                         let param_name = self.parse_variable_name()?;
-                        let name_node = ASTNode::new(common::ast::node_type::NodeType::Identifier(param_name));
+                        let mut name_node = ASTNode::new(common::ast::node_type::NodeType::Identifier(param_name));
+                        self.mark_span(&mut name_node, start);
                         
                         // Create parameter node
                         let mut param_node = ASTNode::new(common::ast::node_type::NodeType::Parameter);
@@ -729,9 +826,20 @@ impl Parser {
         
         // Add return type after parameters
         function_declaration.add_child(return_type_node);
+
+        // A `;` instead of a body makes the declaration a prototype
+        if let Some(Token::SEMICOLON) = self.get_current_token() {
+            self.consume(Token::SEMICOLON, "Expected ';' after function prototype")?;
+            let mut prototype = ASTNode::new(common::ast::node_type::NodeType::FunctionPrototype);
+            prototype.set_children(function_declaration.get_children());
+            return Ok(Some(prototype));
+        }
         
         // Parse the function body
-        let body = self.parse_block()?.ok_or_else(|| vec![ErrorType::SyntaxError {
+        let enclosing = std::mem::replace(&mut self.in_function_body, true);
+        let body = self.parse_block();
+        self.in_function_body = enclosing;
+        let body = body?.ok_or_else(|| vec![ErrorType::SyntaxError {
             message: "Expected function body".into(),
         }])?;
         
@@ -740,9 +848,11 @@ impl Parser {
         Ok(Some(function_declaration))
     }
     
-    /// Parses an enum declaration. This method expects tokens for the enum name and its variants,
-    /// enclosed in braces. The resulting AST will include an `EnumDeclaration` node containing the
-    /// enum's name and its variants as `Variant` nodes.
+    /// Parses an enum declaration. This method expects tokens for the enum name, an optional underlying type
+    /// written `: type`, and its variants, enclosed in braces. The resulting AST will include an `EnumDeclaration`
+    /// node containing the enum's name, the `Type` of its underlying type if one is given, and its variants as
+    /// `Variant` nodes. The enum's name can then be used as a type, which stands for its underlying type, `int`
+    /// unless another is chosen, as in `enum Color : char { Red, Green }`.
     ///
     /// # Returns
     ///
@@ -751,20 +861,40 @@ impl Parser {
     /// # Errors
     ///
     /// * Returns an error if there is a failure in token consumption or if the expected tokens are not found.
+    /// * Returns an error if the underlying type is not an integer type.
     pub fn parse_enum_declaration(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
         // Consume the 'enum' token
         self.consume(Token::ENUM, "Expected 'enum' for enum declaration")?;
         
         // Parse the enum name
         let enum_name = self.parse_variable_name()?;
-        let name_node = ASTNode::new(common::ast::node_type::NodeType::Identifier(enum_name));
-        
-        // Consume the opening brace
-        self.consume(Token::LBRACE, "Expected '{' after enum name")?;
+        let name_node = ASTNode::new(common::ast::node_type::NodeType::Identifier(enum_name.clone()));
         
         // Create the enum declaration node
         let mut enum_declaration = ASTNode::new(common::ast::node_type::NodeType::EnumDeclaration);
         enum_declaration.add_child(name_node);
+
+        // Parse the underlying type, which holds the enum's values
+        let mut underlying = DataType::Integer;
+        if let Some(Token::COLON) = self.get_current_token() {
+            self.consume(Token::COLON, "Expected ':' before the underlying type of the enum")?;
+            underlying = self.parse_type().map_err(|e| vec![e])?;
+            if !matches!(
+                underlying,
+                DataType::Integer | DataType::Boolean | DataType::Char | DataType::SignedChar | DataType::UnsignedChar
+                | DataType::Sign | DataType::Unsign | DataType::Long | DataType::UnsignedLong | DataType::LongLong
+                | DataType::UnsignedLongLong
+            ) {
+                return Err(vec![ErrorType::SyntaxError {
+                    message: format!("The underlying type of enum `{}` must be an integer type", enum_name),
+                }]);
+            }
+            enum_declaration.add_child(ASTNode::new(common::ast::node_type::NodeType::Type(underlying)));
+        }
+        self.type_aliases.insert(enum_name, underlying);
+        
+        // Consume the opening brace
+        self.consume(Token::LBRACE, "Expected '{' after enum name")?;
         
         // Parse variants
         loop {
@@ -806,7 +936,8 @@ impl Parser {
     
     /// Parses a struct declaration. This method expects tokens for the struct name and its fields,
     /// including field names and types, enclosed in braces. The resulting AST will include a
-    /// `StructDeclaration` node containing the struct's name and its fields as `Field` nodes.
+    /// `StructDeclaration` node containing the struct's name and its fields as `Field` nodes, along with any
+    /// anonymous struct or union members.
     ///
     /// # Returns
     ///
@@ -833,31 +964,26 @@ impl Parser {
         // Parse fields
         loop {
             match self.get_current_token() {
-                Some(Token::IDENTIFIER(_)) => {
-                    // Parse field name first
-                    let field_name = self.parse_variable_name()?;
-                    // Use Literal node instead of Identifier node for field names as expected by the tests
-                    let name_node = ASTNode::new(common::ast::node_type::NodeType::Literal(field_name));
-                    
-                    // Consume the colon
-                    self.consume(Token::COLON, "Expected ':' after field name")?;
-                    
-                    // Parse field type
-                    let type_result = self.parse_type().map_err(|e| vec![e])?;
-                    let type_node = ASTNode::new(common::ast::node_type::NodeType::Type(type_result));
-                    
-                    // Create field node
-                    let mut field_node = ASTNode::new(common::ast::node_type::NodeType::Field);
-                    field_node.add_child(name_node);
-                    field_node.add_child(type_node);
-                    
-                    // Optionally consume a comma if present
-                    if let Some(Token::COMMA) = self.get_current_token() {
-                        self.consume(Token::COMMA, "Expected ',' between fields")?;
+                // A leading type starts a method, which is a function declaration inside the struct
+                Some(Token::TINTEGER) | Some(Token::TBOOLEAN) | Some(Token::TDOUBLE) |
+                Some(Token::TFLOAT) | Some(Token::TCHAR) | Some(Token::TVOID) |
+                Some(Token::TSIGNINT) | Some(Token::TUSIGN) | Some(Token::TLONG) => {
+                    if !self.config.struct_methods {
+                        return Err(vec![ErrorType::SyntaxError {
+                            message: "Struct methods are not enabled".into(),
+                        }]);
                     }
-                    
+                    let method = self.parse_struct_method()?;
+                    struct_declaration.add_child(method);
+                },
+                Some(Token::IDENTIFIER(_)) => {
+                    let field_node = self.parse_field()?;
                     struct_declaration.add_child(field_node);
                 },
+                Some(Token::STRUCT) | Some(Token::UNION) => {
+                    let member = self.parse_anonymous_member()?;
+                    struct_declaration.add_child(member);
+                },
                 Some(Token::RBRACE) => {
                     // End of struct declaration
                     self.consume(Token::RBRACE, "Expected '}' to close struct declaration")?;
@@ -879,4 +1005,209 @@ impl Parser {
         Ok(Some(struct_declaration))
     }
 
+    /// Parses a field of a struct or union, written `name: type` and optionally followed by a comma.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ASTNode)` - A `Field` node whose children are the field's name as a `Literal` and its `Type`.
+    /// * `Err(Vec<ErrorType>)` - A list of errors if parsing fails.
+    fn parse_field(&mut self) -> Result<ASTNode, Vec<ErrorType>> {
+        // Parse field name first
+        let field_name = self.parse_variable_name()?;
+        // Use Literal node instead of Identifier node for field names as expected by the tests
+        let name_node = ASTNode::new(common::ast::node_type::NodeType::Literal(field_name));
+        
+        // Consume the colon
+        self.consume(Token::COLON, "Expected ':' after field name")?;
+        
+        // Parse field type
+        let type_result = self.parse_type().map_err(|e| vec![e])?;
+        let type_node = ASTNode::new(common::ast::node_type::NodeType::Type(type_result));
+        
+        // Create field node
+        let mut field_node = ASTNode::new(common::ast::node_type::NodeType::Field);
+        field_node.add_child(name_node);
+        field_node.add_child(type_node);
+        
+        // Optionally consume a comma if present
+        if let Some(Token::COMMA) = self.get_current_token() {
+            self.consume(Token::COMMA, "Expected ',' between fields")?;
+        }
+        Ok(field_node)
+    }
+
+    /// Parses an anonymous struct or union declared inside a struct, as in `union { i: int, f: float }`, whose
+    /// fields are accessed as if they were fields of the struct containing it. Anonymous members may contain
+    /// further anonymous members, and may be followed by a comma like a field.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ASTNode)` - A `StructDeclaration` or `UnionDeclaration` node without a name, whose children are its
+    ///   `Field` nodes and anonymous members.
+    /// * `Err(Vec<ErrorType>)` - A list of errors if parsing fails.
+    fn parse_anonymous_member(&mut self) -> Result<ASTNode, Vec<ErrorType>> {
+        let mut member = if let Some(Token::UNION) = self.get_current_token() {
+            self.consume(Token::UNION, "Expected 'union' for anonymous union")?;
+            ASTNode::new(common::ast::node_type::NodeType::UnionDeclaration)
+        } else {
+            self.consume(Token::STRUCT, "Expected 'struct' for anonymous struct")?;
+            ASTNode::new(common::ast::node_type::NodeType::StructDeclaration)
+        };
+        self.consume(Token::LBRACE, "Expected '{' after 'struct' or 'union' of an anonymous member")?;
+
+        loop {
+            match self.get_current_token() {
+                Some(Token::IDENTIFIER(_)) => member.add_child(self.parse_field()?),
+                Some(Token::STRUCT) | Some(Token::UNION) => member.add_child(self.parse_anonymous_member()?),
+                Some(Token::RBRACE) => {
+                    self.consume(Token::RBRACE, "Expected '}' to close anonymous member")?;
+                    break;
+                },
+                _ => {
+                    return Err(vec![ErrorType::SyntaxError {
+                        message: "Expected field name or closing brace".into(),
+                    }]);
+                }
+            }
+        }
+
+        if let Some(Token::COMMA) = self.get_current_token() {
+            self.consume(Token::COMMA, "Expected ',' between fields")?;
+        }
+        Ok(member)
+    }
+
+    /// Parses a struct type, as in `struct Name`, used to declare variables and parameters of that struct.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ASTNode)` - A `Type(Struct)` node whose child is the `Identifier` of the struct.
+    /// * `Err(Vec<ErrorType>)` - A list of errors if parsing fails.
+    pub fn parse_struct_type(&mut self) -> Result<ASTNode, Vec<ErrorType>> {
+        self.consume(Token::STRUCT, "Expected 'struct' for struct type")?;
+        let struct_name = self.parse_variable_name()?;
+
+        let mut type_node = ASTNode::new(common::ast::node_type::NodeType::Type(common::ast::data_type::DataType::Struct));
+        type_node.add_child(ASTNode::new(common::ast::node_type::NodeType::Identifier(struct_name)));
+        Ok(type_node)
+    }
+
+    /// Parses a method declared inside a struct, which is written like a function declaration.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ASTNode)` - The parsed `FunctionDeclaration` node if successful.
+    /// * `Err(Vec<ErrorType>)` - A list of errors if parsing fails.
+    ///
+    /// # Errors
+    ///
+    /// * Will return an error if the return type, name, parameters, or body cannot be parsed.
+    fn parse_struct_method(&mut self) -> Result<ASTNode, Vec<ErrorType>> {
+        let return_type = self.parse_type().map_err(|e| vec![e])?;
+        let return_type_node = ASTNode::new(common::ast::node_type::NodeType::Type(return_type));
+        let method_name = self.parse_variable_name()?;
+        let identifier_node = ASTNode::new(common::ast::node_type::NodeType::Identifier(method_name));
+
+        self.parse_function_declaration(identifier_node, return_type_node)?.ok_or_else(|| vec![ErrorType::SyntaxError {
+            message: "Expected method declaration".into(),
+        }])
+    }
+
+    /// Parses a type alias declaration of the form `typedef type name;`. The alias is recorded so later
+    /// declarations can use `name` as a type, and it is resolved to the aliased type while parsing.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(ASTNode))` - A `TypeDefinition` node whose children are the alias `Identifier` and the aliased `Type`.
+    /// * `Err(Vec<ErrorType>)` - A list of errors if parsing fails.
+    ///
+    /// # Errors
+    ///
+    /// * Will return an error if `typedef` is not enabled, or if the type or alias name cannot be parsed.
+    pub fn parse_typedef(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
+        if !self.config.typedef {
+            return Err(vec![ErrorType::SyntaxError {
+                message: "`typedef` is not enabled".into(),
+            }]);
+        }
+        self.consume(Token::TYPEDEF, "Expected 'typedef' for type alias declaration")?;
+
+        let aliased = self.parse_type().map_err(|e| vec![e])?;
+        let alias = self.parse_variable_name()?;
+        self.consume(Token::SEMICOLON, "Expected ';' after typedef")?;
+        self.type_aliases.insert(alias.clone(), aliased);
+
+        let mut typedef_node = ASTNode::new(common::ast::node_type::NodeType::TypeDefinition);
+        typedef_node.add_child(ASTNode::new(common::ast::node_type::NodeType::Identifier(alias)));
+        typedef_node.add_child(ASTNode::new(common::ast::node_type::NodeType::Type(aliased)));
+        Ok(Some(typedef_node))
+    }
+
+    /// Parses one or more attributes, each written `@name` or `@name(arg, ...)`, followed by the declaration
+    /// they apply to. The attributes are appended to the declaration's children as `Attribute` nodes, whose
+    /// children are the attribute's arguments as `Literal` nodes.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(ASTNode))` - The parsed declaration node with its attributes if successful.
+    /// * `Err(Vec<ErrorType>)` - A list of errors if parsing fails.
+    ///
+    /// # Errors
+    ///
+    /// * Will return an error if attributes are not enabled, an attribute is malformed, or no declaration follows.
+    pub fn parse_attributed_declaration(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
+        if !self.config.attributes {
+            return Err(vec![ErrorType::SyntaxError {
+                message: "Attributes are not enabled".into(),
+            }]);
+        }
+
+        let mut attributes = Vec::new();
+        while let Some(Token::AT) = self.get_current_token() {
+            self.consume(Token::AT, "Expected '@' to start attribute")?;
+            let name = self.parse_variable_name()?;
+            let mut attribute_node = ASTNode::new(common::ast::node_type::NodeType::Attribute(name));
+
+            if let Some(Token::LPAREN) = self.get_current_token() {
+                self.consume(Token::LPAREN, "Expected '(' after attribute name")?;
+                loop {
+                    let argument = match self.get_current_token() {
+                        Some(Token::RPAREN) => break,
+                        Some(Token::NUMBER(value)) | Some(Token::IDENTIFIER(value)) => value.as_str().to_string(),
+                        _ => return Err(vec![ErrorType::SyntaxError {
+                            message: "Expected attribute argument".into(),
+                        }]),
+                    };
+                    self.advance();
+                    attribute_node.add_child(ASTNode::new(common::ast::node_type::NodeType::Literal(argument)));
+
+                    if let Some(Token::COMMA) = self.get_current_token() {
+                        self.consume(Token::COMMA, "Expected ',' between attribute arguments")?;
+                    }
+                }
+                self.consume(Token::RPAREN, "Expected ')' after attribute arguments")?;
+            }
+            attributes.push(attribute_node);
+        }
+
+        let at_type_alias = self.at_type_alias();
+        let mut declaration = match self.get_current_token() {
+            Some(Token::STRUCT) => self.parse_struct_declaration()?,
+            Some(Token::ENUM) => self.parse_enum_declaration()?,
+            Some(Token::CONST) | Some(Token::VOLATILE) |
+            Some(Token::TINTEGER) | Some(Token::TBOOLEAN) | Some(Token::TDOUBLE) |
+            Some(Token::TFLOAT) | Some(Token::TCHAR) | Some(Token::TVOID) |
+            Some(Token::TSIGNINT) | Some(Token::TUSIGN) | Some(Token::TLONG) => self.parse_initialization()?,
+            Some(Token::IDENTIFIER(_)) if at_type_alias => self.parse_initialization()?,
+            _ => None,
+        }.ok_or_else(|| vec![ErrorType::SyntaxError {
+            message: "Expected declaration after attribute".into(),
+        }])?;
+
+        for attribute in attributes {
+            declaration.add_child(attribute);
+        }
+        Ok(Some(declaration))
+    }
+
 }
\ No newline at end of file
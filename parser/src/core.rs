@@ -1,11 +1,16 @@
 //! The driver for the parsing process, uses the method of recursive descent to systematically iterate through 
 //! tokens and routes to appropriate helper methods in the parser to construct an abstract syntax tree.
                                  
+use std::collections::HashMap;
+
 use common::{ 
-    ast::core::{ASTNode, AST}, 
-    error::ErrorType
+    ast::{core::{ASTNode, AST}, data_type::DataType}, 
+    config::LanguageConfig,
+    constants::DEFAULT_MAX_NESTING_DEPTH,
+    error::ErrorType,
+    location::Span
 };
-use lexer::token::Token;
+use lexer::{core::Lexer, token::Token};
 
 /// The `Parser` struct models the process of parsing.
 /// 
@@ -14,9 +19,24 @@ use lexer::token::Token;
 /// # Fields
 /// * `input` - A vector of tokens from the output of the lexer representing the source code to be parsed.
 /// * `current` - The current token being considered by the parser.
+/// * `depth` - The current nesting depth of blocks and expressions.
+/// * `max_depth` - The nesting depth at which parsing is aborted.
+/// * `config` - The optional language features that are enabled.
+/// * `type_aliases` - The types named by the `typedef` and enum declarations seen so far.
+/// * `in_function_body` - Whether the parser is inside the body of a function.
+/// * `errors` - The errors recovered from so far, which are reported once parsing finishes, each with the source range
+///   of the token it was found at, if the tokens were given with their ranges.
+/// * `spans` - The source range of each token in `input`, or nothing if the tokens were given without them.
 pub struct Parser {
     input: Vec<Token>,
+    spans: Vec<Span>,
     current: usize,
+    depth: usize,
+    max_depth: usize,
+    pub(crate) config: LanguageConfig,
+    pub(crate) type_aliases: HashMap<String, DataType>,
+    pub(crate) in_function_body: bool,
+    pub(crate) errors: Vec<(ErrorType, Option<Span>)>,
 }
 
 impl Parser {
@@ -28,14 +48,23 @@ impl Parser {
     /// # Parameters
     ///
     /// * `input`: A vector of `Token` representing the sequence of tokens to be parsed.
+    /// * `max_depth`: The maximum nesting depth of blocks and expressions.
+    /// * `config`: The optional language features that are enabled.
     ///
     /// # Returns
     ///
     /// Returns a new `Parser` instance ready to parse the provided tokens.
-    fn new(input: Vec<Token>) -> Self {
+    fn new(input: Vec<Token>, max_depth: usize, config: LanguageConfig) -> Self {
         Self {
             input,
+            spans: Vec::new(),
             current: 0,
+            depth: 0,
+            max_depth,
+            config,
+            type_aliases: HashMap::new(),
+            in_function_body: false,
+            errors: Vec::new(),
         }
     }
 
@@ -63,6 +92,55 @@ impl Parser {
         }
     }
 
+    /// Returns the index of the current token, which identifies a position to compare later positions with.
+    pub(crate) fn position(&self) -> usize {
+        self.current
+    }
+
+    /// Returns the token before the current one, which was the last token consumed.
+    pub(crate) fn previous_token(&self) -> Option<&Token> {
+        self.current.checked_sub(1).and_then(|index| self.input.get(index))
+    }
+
+    /// Returns the source range from the token at position `start` to the last token consumed, or None if the
+    /// tokens have no source ranges or no token was consumed since `start`.
+    pub(crate) fn span_since(&self, start: usize) -> Option<Span> {
+        let last = self.current.checked_sub(1).filter(|last| *last >= start)?;
+        Some(self.spans.get(start)?.join(self.spans.get(last)?))
+    }
+
+    /// Gives `node` the source range of the tokens consumed since position `start`, unless it already has one, as
+    /// a parenthesized expression keeps the range of the expression inside the parentheses.
+    pub(crate) fn mark_span(&self, node: &mut ASTNode, start: usize) {
+        if node.get_span().is_none() {
+            if let Some(span) = self.span_since(start) {
+                node.set_span(span);
+            }
+        }
+    }
+
+    /// Gives `node` the source range from the start of `first` to the last token consumed, for a node built on an
+    /// already parsed node, such as a call on its callee, whose range is `first`.
+    pub(crate) fn mark_span_from(&self, node: &mut ASTNode, first: Option<Span>) {
+        let last = self.current.checked_sub(1).and_then(|last| self.spans.get(last));
+        if let (Some(first), Some(last)) = (first, last) {
+            node.set_span(first.join(last));
+        }
+    }
+
+    /// Returns the source range of the current token, or of the last token once every token is consumed, or None if
+    /// the tokens have no source ranges.
+    fn current_span(&self) -> Option<Span> {
+        self.spans.get(self.current).or(self.spans.last()).copied()
+    }
+
+    /// Records an error found at the current token, which is reported once parsing finishes, so that parsing can
+    /// recover from it and go on.
+    pub(crate) fn recover_from(&mut self, error: ErrorType) {
+        let span = self.current_span();
+        self.errors.push((error, span));
+    }
+
     // Consume the current token if it equals `expected`, advancing past it.
     /// Otherwise return a single‐element Vec<ErrorType> with your `message`.
     pub(crate) fn consume(&mut self, expected: Token, message: &str) -> Result<(), Vec<ErrorType>> {
@@ -77,6 +155,104 @@ impl Parser {
         }
     }
 
+    /// Skips a group of tokens opened by the current token `open`, up to the `close` token that matches it. The
+    /// matching `close` token is consumed when `consume_close` is true, and otherwise left as the current token.
+    /// Does nothing if the current token is not `open`.
+    pub(crate) fn skip_balanced(&mut self, open: Token, close: Token, consume_close: bool) {
+        if self.input.get(self.current) != Some(&open) {
+            return;
+        }
+        let mut depth = 0;
+        while let Some(token) = self.input.get(self.current) {
+            if *token == open {
+                depth += 1;
+            } else if *token == close {
+                depth -= 1;
+                if depth == 0 {
+                    if consume_close {
+                        self.advance();
+                    }
+                    return;
+                }
+            }
+            self.advance();
+        }
+    }
+
+    /// Checks whether the current token opens a parameter list that is followed by a function body, as in a
+    /// function definition rather than a prototype.
+    pub(crate) fn at_function_body_after_parameters(&self) -> bool {
+        if self.input.get(self.current) != Some(&Token::LPAREN) {
+            return false;
+        }
+        let mut depth = 0;
+        for (offset, token) in self.input[self.current..].iter().enumerate() {
+            match token {
+                Token::LPAREN => depth += 1,
+                Token::RPAREN => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return self.input.get(self.current + offset + 1) == Some(&Token::LBRACKET);
+                    }
+                }
+                _ => {}
+            }
+        }
+        false
+    }
+
+    /// Checks whether the current token is an identifier naming a type declared by an earlier `typedef`.
+    pub(crate) fn at_type_alias(&self) -> bool {
+        match self.input.get(self.current) {
+            Some(Token::IDENTIFIER(name)) => self.type_aliases.contains_key(name.as_str()),
+            _ => false,
+        }
+    }
+
+    /// Checks whether the current tokens name a struct type, as in `struct Name`.
+    pub(crate) fn at_struct_type(&self) -> bool {
+        matches!(
+            (self.input.get(self.current), self.input.get(self.current + 1)),
+            (Some(Token::STRUCT), Some(Token::IDENTIFIER(_)))
+        )
+    }
+
+    /// Checks whether the current token starts an expression statement, such as `foo(x);` or `x + 1;`, rather than
+    /// a declaration or control flow.
+    pub(crate) fn at_expression_statement(&self) -> bool {
+        match self.input.get(self.current) {
+            Some(Token::IDENTIFIER(_)) => {
+                !(self.at_type_alias() && matches!(self.input.get(self.current + 1), Some(Token::IDENTIFIER(_))))
+            }
+            Some(Token::NUMBER(_)) | Some(Token::STRINGLITERAL(_)) | Some(Token::CHAR(_)) => true,
+            Some(Token::DASH) | Some(Token::EXCLAMATIONPOINT) | Some(Token::TILDE) | Some(Token::LPAREN) => true,
+            Some(Token::PLUSPLUS) | Some(Token::MINUSMINUS) => true,
+            _ => false,
+        }
+    }
+
+    /// Enters one level of nesting, failing once the maximum nesting depth is exceeded so that deeply nested
+    /// input produces a diagnostic instead of overflowing the stack. Every successful call must be paired
+    /// with a call to `exit_nesting`.
+    ///
+    /// # Errors
+    ///
+    /// * Returns a `SyntaxError` if the maximum nesting depth is exceeded.
+    pub(crate) fn enter_nesting(&mut self) -> Result<(), Vec<ErrorType>> {
+        if self.depth >= self.max_depth {
+            return Err(vec![ErrorType::SyntaxError {
+                message: format!("program too deeply nested (maximum nesting depth is {})", self.max_depth),
+            }]);
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Leaves one level of nesting entered with `enter_nesting`.
+    pub(crate) fn exit_nesting(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+
     /// Parses an input of tokens into an AST using recursive descent parsing.
     /// Iterates through tokens and routes to appropriate helper methods to construct an AST.
     ///
@@ -102,26 +278,241 @@ impl Parser {
     /// let ast = Parser::parse(tokens);
     /// ```
     pub fn parse(input: Vec<Token>) -> Result<AST, Vec<ErrorType>> {
-        let mut parser = Parser::new(input);
+        Parser::parse_with_max_depth(input, DEFAULT_MAX_NESTING_DEPTH)
+    }
+
+    /// Parses an input of tokens into an AST like `parse`, but with a custom limit on how deeply blocks and
+    /// expressions may be nested.
+    ///
+    /// # Parameters
+    ///
+    /// * `input`: A vector of `Token` representing the input to be parsed.
+    /// * `max_depth`: The maximum nesting depth of blocks and expressions.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<AST, Vec<ErrorType>>` containing the constructed AST if successful, 
+    /// or a vector of `ErrorType` if there are parsing errors.
+    ///
+    /// # Errors
+    ///
+    /// * Returns a vector of errors if there are issues during parsing, including nesting deeper than `max_depth`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lexer::token::Token;
+    /// use parser::core::Parser;
+    /// let tokens: Vec<Token> = vec![/* tokens */];
+    /// let ast = Parser::parse_with_max_depth(tokens, 64);
+    /// ```
+    pub fn parse_with_max_depth(input: Vec<Token>, max_depth: usize) -> Result<AST, Vec<ErrorType>> {
+        Parser::parse_tokens(input, Vec::new(), max_depth, LanguageConfig::default()).map_err(without_spans)
+    }
+
+    /// Parses an input of tokens into an AST like `parse`, accepting the optional language features enabled in
+    /// `config`. The tokens should come from a lexer using the same configuration.
+    ///
+    /// # Parameters
+    ///
+    /// * `input`: A vector of `Token` representing the input to be parsed.
+    /// * `config`: The optional language features that are enabled.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<AST, Vec<ErrorType>>` containing the constructed AST if successful, 
+    /// or a vector of `ErrorType` if there are parsing errors.
+    ///
+    /// # Errors
+    ///
+    /// * Returns a vector of errors if there are issues during parsing, including use of a disabled feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use common::config::LanguageConfig;
+    /// use lexer::token::Token;
+    /// use parser::core::Parser;
+    /// let tokens: Vec<Token> = vec![/* tokens */];
+    /// let ast = Parser::parse_with_config(tokens, LanguageConfig::extended());
+    /// ```
+    pub fn parse_with_config(input: Vec<Token>, config: LanguageConfig) -> Result<AST, Vec<ErrorType>> {
+        Parser::parse_tokens(input, Vec::new(), DEFAULT_MAX_NESTING_DEPTH, config).map_err(without_spans)
+    }
+
+    /// Parses tokens paired with their source ranges into an AST like `parse_with_config`, giving every statement,
+    /// declaration and expression node the range of the tokens it was parsed from. The tokens should come from
+    /// `Lexer::lex_with_spans_and_config` with the same configuration.
+    ///
+    /// # Parameters
+    ///
+    /// * `input`: The tokens to be parsed, each with the range of the source it covers.
+    /// * `config`: The optional language features that are enabled.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<AST, Vec<(ErrorType, Option<Span>)>>` containing the constructed AST if successful,
+    /// or the parsing errors, each with the range of the token it was found at, if there are any tokens.
+    ///
+    /// # Errors
+    ///
+    /// * Returns a vector of errors if there are issues during parsing, such as unexpected tokens.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use common::config::LanguageConfig;
+    /// use lexer::core::Lexer;
+    /// use parser::core::Parser;
+    /// let tokens = Lexer::lex_with_spans("int main() { return 0; }").unwrap();
+    /// let ast = Parser::parse_with_spans(tokens, LanguageConfig::default()).unwrap();
+    /// let main = &ast.get_root().get_children()[0];
+    /// assert_eq!(main.get_span().map(|span| span.offset), Some(0));
+    /// ```
+    pub fn parse_with_spans(
+        input: Vec<(Token, Span)>,
+        config: LanguageConfig,
+    ) -> Result<AST, Vec<(ErrorType, Option<Span>)>> {
+        let (tokens, spans) = input.into_iter().unzip();
+        Parser::parse_tokens(tokens, spans, DEFAULT_MAX_NESTING_DEPTH, config)
+    }
+
+    /// Runs the parser over `input`, whose tokens cover the source ranges in `spans` if it is not empty, with the
+    /// given nesting limit and language features.
+    fn parse_tokens(
+        input: Vec<Token>,
+        spans: Vec<Span>,
+        max_depth: usize,
+        config: LanguageConfig,
+    ) -> Result<AST, Vec<(ErrorType, Option<Span>)>> {
+        let mut parser = Parser::new(input, max_depth, config);
+        parser.spans = spans;
         let mut children = vec![];
         
         while let Some(token) = parser.get_current_token() {
             match token {
                 Token::EOF => break,
                 _ => {
-                    match parser.parse_router()? {
-                        Some(node) => children.push(node),
-                        None => parser.advance(),
+                    match parser.parse_router() {
+                        Ok(Some(node)) => children.push(node),
+                        Ok(None) => parser.advance(),
+                        Err(errors) => {
+                            for error in errors {
+                                parser.recover_from(error);
+                            }
+                            return Err(parser.errors);
+                        }
                     }
                 }
             }
         }
+        if !parser.errors.is_empty() {
+            return Err(parser.errors);
+        }
 
         let mut root = ASTNode::new(common::ast::node_type::NodeType::TopLevelExpression);
         root.set_children(children);
         Ok(AST::new(root))
     }
 
+    /// Parses tokens that hold a single expression, such as `a + f(1)`, without a trailing `;`.
+    ///
+    /// # Parameters
+    ///
+    /// * `input`: The tokens of the expression, optionally ending with `EOF`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the expression's node, or the errors found.
+    ///
+    /// # Errors
+    ///
+    /// * Returns an error if the tokens do not start with an expression or continue past its end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lexer::tokens;
+    /// use parser::core::Parser;
+    /// let expression = Parser::parse_expression(tokens![ident("a"), PLUS, num(1)]);
+    /// ```
+    pub fn parse_expression(input: Vec<Token>) -> Result<ASTNode, Vec<ErrorType>> {
+        Parser::parse_fragment(input, |parser| {
+            parser.parse_binary_expression()?.ok_or_else(|| vec![ErrorType::SyntaxError {
+                message: "Expected expression".into(),
+            }])
+        })
+    }
+
+    /// Parses tokens that hold a single statement as it would appear in a block, such as `x = 1;` or a loop.
+    ///
+    /// # Parameters
+    ///
+    /// * `input`: The tokens of the statement, optionally ending with `EOF`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the statement's node, or the errors found.
+    ///
+    /// # Errors
+    ///
+    /// * Returns an error if the tokens do not start with a statement or continue past its end.
+    pub fn parse_statement(input: Vec<Token>) -> Result<ASTNode, Vec<ErrorType>> {
+        Parser::parse_fragment(input, |parser| {
+            parser.parse_block_statement()?.ok_or_else(|| vec![ErrorType::SyntaxError {
+                message: "Expected statement".into(),
+            }])
+        })
+    }
+
+    /// Lexes and parses the spelling of a type, such as `int`, `unsigned long`, or `struct Point`.
+    ///
+    /// # Parameters
+    ///
+    /// * `text`: The source text of the type.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `Type` node of the type, or the errors found.
+    ///
+    /// # Errors
+    ///
+    /// * Returns an error if the text does not lex, does not name a type, or continues past the type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use parser::core::Parser;
+    /// let unsigned_long = Parser::parse_type_str("unsigned long");
+    /// ```
+    pub fn parse_type_str(text: &str) -> Result<ASTNode, Vec<ErrorType>> {
+        Parser::parse_fragment(Lexer::lex(text)?, |parser| match parser.get_current_token() {
+            Some(Token::STRUCT) => parser.parse_struct_type(),
+            _ => {
+                let data_type = parser.parse_type().map_err(|e| vec![e])?;
+                Ok(ASTNode::new(common::ast::node_type::NodeType::Type(data_type)))
+            }
+        })
+    }
+
+    /// Runs `parse` over `input`, then checks that it consumed every token but a final `EOF`.
+    fn parse_fragment<T>(
+        input: Vec<Token>,
+        parse: impl FnOnce(&mut Parser) -> Result<T, Vec<ErrorType>>,
+    ) -> Result<T, Vec<ErrorType>> {
+        let mut parser = Parser::new(input, DEFAULT_MAX_NESTING_DEPTH, LanguageConfig::default());
+        let fragment = parse(&mut parser)?;
+        if let Some(token) = parser.get_current_token().filter(|token| **token != Token::EOF).cloned() {
+            parser.recover_from(ErrorType::SyntaxError {
+                message: format!("Unexpected token {:?} after the end of the fragment", token),
+            });
+        }
+        if !parser.errors.is_empty() {
+            return Err(without_spans(parser.errors));
+        }
+        Ok(fragment)
+    }
+
    
 
     /// Entry point to the main parsing logic. Routes the current token to the appropriate parsing method based on token type.
@@ -134,9 +525,30 @@ impl Parser {
     ///
     /// * Returns a vector of errors if there are issues during parsing, such as unexpected tokens or parsing failures.
     pub fn parse_router(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
+        self.enter_nesting()?;
+        let start = self.position();
+        let mut result = self.route_current_token();
+        if let Ok(Some(node)) = &mut result {
+            self.mark_span(node, start);
+        }
+        self.exit_nesting();
+        result
+    }
+
+    /// Routes the current token to the parsing method for the construct it starts. Called through
+    /// `parse_router`, which tracks the nesting depth.
+    fn route_current_token(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
         if self.current >= self.input.len() {
             return Ok(None);
         }
+
+        // `alias name` declares a variable or function whose type is a `typedef` alias
+        let starts_alias_declaration = self.at_type_alias()
+            && matches!(self.input.get(self.current + 1), Some(Token::IDENTIFIER(_)));
+
+        // `struct Name name` declares a variable or function of a struct type rather than a struct
+        let starts_struct_declaration = self.at_struct_type()
+            && matches!(self.input.get(self.current + 2), Some(Token::IDENTIFIER(_)));
     
         match self.get_current_token() {
             // End‐of‐input marker
@@ -160,6 +572,9 @@ impl Parser {
             Some(Token::STRINGLITERAL(_)) => self.parse_primitive(), 
             Some(Token::CHAR(_)) => self.parse_primitive(), 
     
+            // A declaration whose type is a `typedef` alias
+            Some(Token::IDENTIFIER(_)) if starts_alias_declaration => self.parse_initialization(),
+
             // Identifiers, assignments, or start of binary/unary expressions
             Some(Token::IDENTIFIER(_)) => {
                 // Always try to parse as a binary expression first
@@ -169,7 +584,7 @@ impl Parser {
             // Expressions starting with unary operators should still be parsed as full expressions to
             // correctly capture cases like `-5 - 3`. The precedence-climbing logic internally calls
             // `parse_unary_expression` for the left-hand side.
-            Some(Token::DASH) | Some(Token::EXCLAMATIONPOINT) => self.parse_binary_expression(),
+            Some(Token::DASH) | Some(Token::EXCLAMATIONPOINT) | Some(Token::TILDE) => self.parse_binary_expression(),
             
             // Control flow statements
             Some(Token::IF) => self.parse_if_statement(),
@@ -181,8 +596,11 @@ impl Parser {
             Some(Token::DEFAULT) => self.parse_default(), 
             
             // Declarations
+            Some(Token::STRUCT) if starts_struct_declaration => self.parse_initialization(),
             Some(Token::STRUCT) => self.parse_struct_declaration(),
             Some(Token::ENUM) => self.parse_enum_declaration(),
+            Some(Token::TYPEDEF) => self.parse_typedef(),
+            Some(Token::AT) => self.parse_attributed_declaration(),
             
             // break / continue / return
             Some(Token::BREAK) | Some(Token::CONTINUE) | Some(Token::RETURN) => {
@@ -190,7 +608,9 @@ impl Parser {
             },
     
             // Leading‐type → var‐ or func‐decl
-            Some(Token::TINTEGER)
+            Some(Token::CONST)
+            | Some(Token::VOLATILE)
+            | Some(Token::TINTEGER)
             | Some(Token::TBOOLEAN)
             | Some(Token::TDOUBLE)
             | Some(Token::TFLOAT)
@@ -203,6 +623,7 @@ impl Parser {
             // Binary operators
             Some(Token::PLUS) | Some(Token::ASTERISK) | Some(Token::FSLASH) |
             Some(Token::LESSTHAN) | Some(Token::GREATERTHAN) |
+            Some(Token::LESSTHANEQUAL) | Some(Token::GREATERTHANEQUAL) |
             Some(Token::EQUALEQUAL) | Some(Token::NOTEQUAL) => self.parse_binary_expression(),
             
             // Assignment operators
@@ -234,4 +655,8 @@ impl Parser {
     }
 
     
-}
\ No newline at end of file
+}
+/// Drops the source ranges of parsing errors, for the entry points whose tokens have none.
+fn without_spans(errors: Vec<(ErrorType, Option<Span>)>) -> Vec<ErrorType> {
+    errors.into_iter().map(|(error, _)| error).collect()
+}
@@ -0,0 +1,180 @@
+//! Reparses a program after an edit to its source by re-lexing and re-parsing only the top-level items the edit
+//! touches, which keeps editors responsive on large files.
+//!
+//! The previous AST need not record where its nodes came from, so the old source is lexed to find the byte range of each
+//! top-level item. Items are split at `;` outside of any brackets, and after the `}` closing a function or control
+//! flow body. The source between the unaffected items before and after the edit is then lexed and parsed on its own,
+//! and its items replace the ones it held in the previous AST. Whenever the items of the old source cannot be
+//! matched with the children of the previous AST, or the edited items do not parse on their own, the whole new
+//! source is lexed and parsed instead, so the result is always what a full parse would give.
+
+use common::{
+    ast::{core::{ASTNode, AST}, node_type::NodeType},
+    error::ErrorType,
+    location::Span,
+};
+use lexer::{core::Lexer, token::Token};
+
+use crate::core::Parser;
+
+/// An edit to a source, replacing a range of its bytes with new text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    /// The range of bytes of the old source that is replaced.
+    pub range: Span,
+    /// The text that replaces the range.
+    pub replacement: String,
+}
+
+impl TextEdit {
+    /// Creates a new `TextEdit`.
+    ///
+    /// # Parameters
+    ///
+    /// * `range`: The range of bytes of the old source that is replaced.
+    /// * `replacement`: The text that replaces the range.
+    pub fn new(range: Span, replacement: impl Into<String>) -> Self {
+        Self { range, replacement: replacement.into() }
+    }
+
+    /// Applies the edit to the source it was made against.
+    ///
+    /// # Parameters
+    ///
+    /// * `text`: The old source.
+    ///
+    /// # Returns
+    ///
+    /// Returns the new source, or None if the range of the edit is not within `text` or does not fall on character
+    /// boundaries.
+    pub fn apply(&self, text: &str) -> Option<String> {
+        self.range.text(text)?;
+        Some(format!("{}{}{}", &text[..self.range.offset], self.replacement, &text[self.range.end()..]))
+    }
+}
+
+/// Reparses a program after an edit, reusing the items of the previous AST the edit does not touch.
+///
+/// # Parameters
+///
+/// * `previous`: The AST parsed from `old_text`.
+/// * `old_text`: The source before the edit.
+/// * `edit`: The edit made to `old_text`.
+///
+/// # Returns
+///
+/// Returns the AST of the edited source, equal to the one `Parser::parse` gives for it, or the errors found in it.
+///
+/// # Errors
+///
+/// * Returns a `SyntaxError` if the range of the edit is not within `old_text`.
+/// * Returns the errors of lexing or parsing the edited source.
+///
+/// # Examples
+///
+/// ```
+/// use common::location::Span;
+/// use lexer::core::Lexer;
+/// use parser::{core::Parser, incremental::{reparse, TextEdit}};
+/// let old_text = "int x = 1;\nint y = 2;";
+/// let previous = Parser::parse(Lexer::lex(old_text).unwrap()).unwrap();
+/// let ast = reparse(&previous, old_text, &TextEdit::new(Span::new(19, 1), "3"));
+/// ```
+pub fn reparse(previous: &AST, old_text: &str, edit: &TextEdit) -> Result<AST, Vec<ErrorType>> {
+    let new_text = edit.apply(old_text).ok_or_else(|| vec![ErrorType::SyntaxError {
+        message: format!("Edit range {}..{} is outside of the source", edit.range.offset, edit.range.end()),
+    }])?;
+
+    let old_children = previous.get_root().get_children();
+    let items = match Lexer::lex_with_spans(old_text) {
+        Ok(tokens) => top_level_items(&tokens),
+        Err(_) => None,
+    };
+    let items = match items {
+        Some(items) if items.len() == old_children.len() => items,
+        _ => return parse_text(&new_text),
+    };
+
+    // The items before `first` and from `last` on are reused, and the source between them is reparsed
+    let first = items.iter().take_while(|item| item.end() < edit.range.offset).count();
+    let last = first + items[first..].iter().take_while(|item| item.offset <= edit.range.end()).count();
+    let region_start = if first == 0 { 0 } else { items[first - 1].end() };
+    let region_end = items.get(last).map_or(old_text.len(), |item| item.offset);
+
+    let growth = edit.replacement.len() as isize - edit.range.len as isize;
+    let new_region_end = (region_end as isize + growth) as usize;
+    let region = match parse_text(&new_text[region_start..new_region_end]) {
+        Ok(region) => region,
+        Err(_) => return parse_text(&new_text),
+    };
+
+    let mut children: Vec<ASTNode> = old_children[..first].to_vec();
+    children.extend(region.get_root().get_children());
+    children.extend_from_slice(&old_children[last..]);
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.set_children(children);
+    Ok(AST::new(root))
+}
+
+/// Lexes and parses a source, which may be empty.
+fn parse_text(text: &str) -> Result<AST, Vec<ErrorType>> {
+    if text.is_empty() {
+        return Ok(AST::new(ASTNode::new(NodeType::TopLevelExpression)));
+    }
+    Parser::parse(Lexer::lex(text)?)
+}
+
+/// Splits the tokens of a source into its top-level items, giving the byte range of each.
+///
+/// # Returns
+///
+/// Returns the range of each item, or None if the source has a stray `;` or `}`, which the parser skips without
+/// producing a node.
+fn top_level_items(tokens: &[(Token, Span)]) -> Option<Vec<Span>> {
+    let mut items = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut depth = 0usize;
+    // Whether the outermost `{` of the current item opens a function or control flow body, which ends the item
+    let mut body = false;
+
+    for (index, (token, span)) in tokens.iter().enumerate() {
+        if *token == Token::EOF {
+            break;
+        }
+        let first = *start.get_or_insert(index);
+        let previous = index.checked_sub(1).map(|index| &tokens[index].0);
+        match token {
+            Token::LBRACKET if depth == 0 => {
+                body = index == first || matches!(previous, Some(Token::RPAREN) | Some(Token::ELSE));
+                depth += 1;
+            }
+            Token::LPAREN | Token::LBRACKET | Token::LBRACE => depth += 1,
+            Token::RPAREN | Token::RBRACKET | Token::RBRACE if depth == 0 => return None,
+            Token::RPAREN | Token::RBRACKET | Token::RBRACE => depth -= 1,
+            _ => {}
+        }
+
+        if *token == Token::SEMICOLON && depth == 0 && index == first {
+            return None;
+        }
+        let next = tokens.get(index + 1).map(|(token, _)| token);
+        let ends_item = depth == 0 && next != Some(&Token::ELSE) && match token {
+            Token::SEMICOLON => true,
+            Token::RBRACKET => body,
+            _ => false,
+        };
+        if ends_item {
+            let offset = tokens[first].1.offset;
+            items.push(Span::new(offset, span.end() - offset));
+            start = None;
+            body = false;
+        }
+    }
+    // A final item without a closing `;` or `}` still produces a node
+    if let Some(first) = start {
+        let offset = tokens[first].1.offset;
+        let end = tokens.iter().rev().find(|(token, _)| *token != Token::EOF).map_or(offset, |(_, span)| span.end());
+        items.push(Span::new(offset, end - offset));
+    }
+    Some(items)
+}
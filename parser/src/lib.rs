@@ -11,6 +11,7 @@
 //! * `statement`: Handles generation for statements and operations within blocks.
 //! * `block`: Handles generation for code blocks.
 //! * `primitive`: Handles generation for primitive data types and operations.
+//! * `incremental`: Reparses only the top-level items an edit to the source touches.
 
 /// Core of the parsing process.
 pub mod core;
@@ -24,3 +25,5 @@ mod block;
 /// Parses primitive data types and operations.
 mod primitive;
 
+/// Reparses the top-level items touched by an edit.
+pub mod incremental;
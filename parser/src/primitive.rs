@@ -19,16 +19,15 @@ impl Parser {
     ///
     /// * Returns an error if the current token is not a `NUMBER` or if there is a failure in token consumption.
     pub fn parse_primitive(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
-        match self.get_current_token() {
-            Some(Token::NUMBER(chars)) => {
-                let lit_str: String = chars.iter().collect();
-                let node = ASTNode::new(common::ast::node_type::NodeType::Literal(lit_str));
+        let start = self.position();
+        let mut result = match self.get_current_token() {
+            Some(Token::NUMBER(number)) => {
+                let node = ASTNode::new(common::ast::node_type::NodeType::Literal(number.as_str().to_string()));
                 self.advance();
                 Ok(Some(node))
             },
-            Some(Token::STRINGLITERAL(chars)) => {
-                let lit_str: String = chars.iter().collect();
-                let node = ASTNode::new(common::ast::node_type::NodeType::Literal("\"".to_string() + &lit_str + "\""));
+            Some(Token::STRINGLITERAL(contents)) => {
+                let node = ASTNode::new(common::ast::node_type::NodeType::Literal(format!("\"{}\"", contents.escaped())));
                 self.advance();
                 Ok(Some(node))
             },
@@ -42,34 +41,40 @@ impl Parser {
                     message: "Expected a literal (number, string, or char)".into(),
                 }])
             }
+        };
+        if let Ok(Some(node)) = &mut result {
+            self.mark_span(node, start);
         }
+        result
     }
 
-    /// Parses an identifier token into an AST node or an assignment if an equal sign follows the identifier.
-    /// This method expects a token of type `IDENTIFIER`.
+    /// Parses an identifier token into an AST node, along with any call, member access, or postfix `++`/`--` that
+    /// follows it. This method expects a token of type `IDENTIFIER`. An assignment to the identifier is parsed by
+    /// `Parser::parse_binary_expression`, since assignment is itself an expression.
     ///
     /// # Returns
     ///
-    /// Returns an `Option<ASTNode>` containing either the identifier or the assignment node, or an error `Vec<ErrorType>` if parsing fails.
+    /// Returns an `Option<ASTNode>` containing the identifier or the postfix expression built on it, or an error `Vec<ErrorType>` if parsing fails.
     ///
     /// # Errors
     ///
-    /// * Returns an error if the current token is not an `IDENTIFIER` or if there is a failure in token consumption or assignment parsing.
-    /// Parses an identifier token into an AST node or an assignment if an equal sign follows.
+    /// * Returns an error if the current token is not an `IDENTIFIER` or if there is a failure in token consumption or postfix parsing.
     pub fn parse_identifier(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
         // Extract the variable name
+        let start = self.position();
         let name = self.parse_variable_name()?;
+        let mut base = ASTNode::new(common::ast::node_type::NodeType::Identifier(name));
+        self.mark_span(&mut base, start);
 
         // Check what follows the identifier
         match self.get_current_token() {
-            // If next token is '=', parse assignment
-            Some(Token::EQUAL) => {
-                // Use the assignment handler with the name we already parsed
-                let name_chars: Vec<char> = name.chars().collect();
-                self.parse_assignment(name_chars)
+            // If next token is '(', '.', '->', '++' or '--', the identifier is called, has a member accessed, or is
+            // incremented or decremented after its value is taken
+            Some(Token::LPAREN) | Some(Token::DOT) | Some(Token::POINTER) | Some(Token::PLUSPLUS) | Some(Token::MINUSMINUS) => {
+                self.parse_postfix_expression(base).map(Some)
             },
             // Otherwise, it's just a bare identifier (or the start of an expression to be handled by a higher-level parser function)
-            _ => Ok(Some(ASTNode::new(common::ast::node_type::NodeType::Identifier(name))))
+            _ => Ok(Some(base))
         }
     
     }
@@ -84,11 +89,14 @@ impl Parser {
     /// # Errors
     ///
     /// * Returns an error if the current token is not an `IDENTIFIER` or if there is a failure in token consumption.
+    /// * Returns a `ReservedKeyword` error if the current token is a keyword, as in `int if = 3;`.
     pub fn parse_variable_name(&mut self) -> Result<String, Vec<ErrorType>> {
-        if let Some(Token::IDENTIFIER(chars)) = self.get_current_token() {
-            let name: String = chars.iter().collect();
+        if let Some(Token::IDENTIFIER(name)) = self.get_current_token() {
+            let name = name.as_str().to_string();
             self.advance();
             Ok(name)
+        } else if let Some(keyword) = self.get_current_token().and_then(Token::keyword_spelling) {
+            Err(vec![ErrorType::ReservedKeyword { keyword: keyword.to_string() }])
         } else {
             Err(vec![ErrorType::SyntaxError {
                 message: "Expected identifier".into(),
@@ -134,7 +142,7 @@ impl Parser {
                         let expr = match self.get_current_token() {
                             Some(Token::NUMBER(_)) => self.parse_primitive()?,
                             Some(Token::IDENTIFIER(_)) => self.parse_identifier()?,
-                            Some(Token::DASH) | Some(Token::EXCLAMATIONPOINT) => self.parse_unary_expression()?,
+                            Some(Token::DASH) | Some(Token::EXCLAMATIONPOINT) | Some(Token::TILDE) => self.parse_unary_expression()?,
                             Some(Token::LPAREN) => self.parse_parenthesized_expression()?,
                             _ => {
                                 return Err(vec![ErrorType::SyntaxError {
@@ -169,6 +177,9 @@ impl Parser {
 
     /// Consumes a type token and returns the corresponding `DataType` enum value. Supported types include
     /// `TINTEGER`, `TBOOLEAN`, `TDOUBLE`, `TFLOAT`, `TCHAR`, `TVOID`, `TSIGN`, `TUSIGN`, `TSIGNINT`, and `TLONG`.
+    /// `TSIGNINT`, `TUSIGN`, and `TLONG` start a composed specifier such as `unsigned long`, which is combined into one type,
+    /// as do `TINTEGER` and `TCHAR` when one of them follows, as in `int long` or `char unsigned`.
+    /// An identifier declared by an earlier `typedef` is replaced by the type it names.
     ///
     /// # Returns
     ///
@@ -178,6 +189,12 @@ impl Parser {
     ///
     /// * Returns an error if the current token is not a recognized type token or if there is a failure in token consumption.
     pub fn parse_type(&mut self) -> Result<DataType, ErrorType> {
+        let composed_after_first = matches!(self.peek_next_token(), Some(Token::TSIGNINT | Token::TUSIGN | Token::TLONG))
+            && matches!(self.get_current_token(), Some(Token::TINTEGER | Token::TCHAR));
+        if composed_after_first {
+            return self.parse_composed_type();
+        }
+
         // Peek at the current token to decide which DataType it represents
         let dt = match self.get_current_token() {
             Some(Token::TINTEGER)  => DataType::Integer,
@@ -186,9 +203,16 @@ impl Parser {
             Some(Token::TFLOAT)    => DataType::Float,
             Some(Token::TCHAR)     => DataType::Char,
             Some(Token::TVOID)     => DataType::Void,
-            Some(Token::TSIGNINT)  => DataType::Sign,
-            Some(Token::TUSIGN)    => DataType::Unsign,
-            Some(Token::TLONG)     => DataType::Long,
+            Some(Token::TSIGNINT) | Some(Token::TUSIGN) | Some(Token::TLONG) => return self.parse_composed_type(),
+            Some(Token::IDENTIFIER(name)) => {
+                let name = name.as_str();
+                match self.type_aliases.get(name) {
+                    Some(aliased) => *aliased,
+                    None => return Err(ErrorType::SyntaxError {
+                        message: format!("Unknown type name `{}`", name),
+                    }),
+                }
+            },
             _ => {
                 return Err(ErrorType::SyntaxError {
                     message: "Expected a type keyword (`int`, `boolean`, etc.)".into(),
@@ -200,4 +224,69 @@ impl Parser {
         self.advance();
         Ok(dt)
     }
+
+    /// Consumes a composed type specifier that starts with `signed`, `unsigned`, or `long`, such as `unsigned long`,
+    /// `signed char`, or `long long int`, and combines its keywords into a single `DataType`. As in C, the keywords
+    /// may appear in any order.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `DataType` with the width and signedness described by the specifier, or an error `ErrorType`
+    /// if the keywords cannot be combined.
+    ///
+    /// # Errors
+    ///
+    /// * Returns an error if both `signed` and `unsigned` are given, a keyword is repeated (other than `long`
+    ///   appearing twice), `char` is combined with `long`, or a non-integer type keyword is part of the specifier.
+    fn parse_composed_type(&mut self) -> Result<DataType, ErrorType> {
+        let mut signed_count = 0;
+        let mut unsigned_count = 0;
+        let mut long_count = 0;
+        let mut int_count = 0;
+        let mut char_count = 0;
+        let mut keywords: Vec<&str> = Vec::new();
+
+        loop {
+            let keyword = match self.get_current_token() {
+                Some(Token::TSIGNINT) => { signed_count += 1; "signed" },
+                Some(Token::TUSIGN) => { unsigned_count += 1; "unsigned" },
+                Some(Token::TLONG) => { long_count += 1; "long" },
+                Some(Token::TINTEGER) => { int_count += 1; "int" },
+                Some(Token::TCHAR) => { char_count += 1; "char" },
+                Some(Token::TBOOLEAN) => "boolean",
+                Some(Token::TDOUBLE) => "double",
+                Some(Token::TFLOAT) => "float",
+                Some(Token::TVOID) => "void",
+                _ => break,
+            };
+            keywords.push(keyword);
+            self.advance();
+        }
+
+        let is_integer_specifier = keywords.iter().all(|k| matches!(*k, "signed" | "unsigned" | "long" | "int" | "char"));
+        if !is_integer_specifier
+            || signed_count + unsigned_count > 1
+            || long_count > 2
+            || int_count > 1
+            || char_count > 1
+            || (char_count == 1 && (long_count > 0 || int_count > 0))
+        {
+            return Err(ErrorType::SyntaxError {
+                message: format!("Invalid combination of type specifiers `{}`", keywords.join(" ")),
+            });
+        }
+
+        let unsigned = unsigned_count == 1;
+        let dt = match (char_count, long_count) {
+            (1, _) if unsigned => DataType::UnsignedChar,
+            (1, _) => DataType::SignedChar,
+            (_, 2) if unsigned => DataType::UnsignedLongLong,
+            (_, 2) => DataType::LongLong,
+            (_, 1) if unsigned => DataType::UnsignedLong,
+            (_, 1) => DataType::Long,
+            _ if unsigned => DataType::Unsign,
+            _ => DataType::Sign,
+        };
+        Ok(dt)
+    }
 }
\ No newline at end of file
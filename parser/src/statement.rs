@@ -9,8 +9,9 @@ use lexer::token::Token;
 
 impl Parser {
     /// Parses a unary expression. 
-    /// Specifically handles DASH and EXCLAMATIONPOINT tokens, as returns corresponding AST with a top-level 
-    /// 'NodeType::UnaryExpression' ASTNode.
+    /// Specifically handles DASH, EXCLAMATIONPOINT, TILDE (bitwise not), AMPERSAND (address-of), ASTERISK (dereference),
+    /// PLUSPLUS and MINUSMINUS tokens, and returns corresponding AST
+    /// with a top-level 'NodeType::UnaryExpression' ASTNode.
     ///
     /// # Returns
     ///
@@ -21,7 +22,8 @@ impl Parser {
     ///
     /// * Returns an error if parsing of the unary expression fails.
     pub fn parse_unary_expression(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
-        // Check if the current token is a unary operator (- or !)
+        let start = self.position();
+        // Check if the current token is a unary operator (-, !, ~, &, *, ++ or --)
         let operator = match self.get_current_token() {
             Some(Token::DASH) => {
                 self.advance();
@@ -31,24 +33,35 @@ impl Parser {
                 self.advance();
                 "!".to_string()
             },
+            Some(Token::TILDE) => {
+                self.advance();
+                "~".to_string()
+            },
+            Some(Token::AMPERSAND) => {
+                self.advance();
+                "&".to_string()
+            },
+            Some(Token::ASTERISK) => {
+                self.advance();
+                "*".to_string()
+            },
+            Some(Token::PLUSPLUS) => {
+                self.advance();
+                "++".to_string()
+            },
+            Some(Token::MINUSMINUS) => {
+                self.advance();
+                "--".to_string()
+            },
             _ => {
                 return Err(vec![ErrorType::SyntaxError {
-                    message: "Expected unary operator (- or !)".into(),
+                    message: "Expected unary operator (-, !, ~, &, *, ++ or --)".into(),
                 }]);
             }
         };
 
         // Parse the operand (can be a primitive, another expression, or parenthesized expression)
-        let operand = match self.get_current_token() {
-            Some(Token::NUMBER(_)) => self.parse_primitive()?,
-            Some(Token::IDENTIFIER(_)) => self.parse_identifier()?,
-            Some(Token::LPAREN) => self.parse_parenthesized_expression()?,
-            _ => {
-                return Err(vec![ErrorType::SyntaxError {
-                    message: "Expected expression after unary operator".into(),
-                }]);
-            }
-        }.ok_or_else(|| vec![ErrorType::SyntaxError {
+        let operand = self.parse_unary_operand()?.ok_or_else(|| vec![ErrorType::SyntaxError {
             message: "Expected expression after unary operator".into(),
         }])?;
 
@@ -56,19 +69,63 @@ impl Parser {
         let mut unary_expr = ASTNode::new(NodeType::UnaryExpression);
         unary_expr.add_child(ASTNode::new(NodeType::Operator(operator)));
         unary_expr.add_child(operand);
+        self.mark_span(&mut unary_expr, start);
         
         // Simply return the unary expression. Any following binary operators will be handled by
         // `parse_expression_with_precedence`, which ensures correct operator precedence.
         Ok(Some(unary_expr))
     }
 
-    /// Parses a variable reassignment. Handles assignment to literals, expressions, and other identifiers.
-    /// Creates a top level 'NodeType::Assignment' ASTNode, with children representing the identifier and
-    /// its new AssignedValue. Is called by 'Parser::parse_identifier', which fullfills the `name_chars` parameter.
+    /// Parses the operand of a unary expression, counting it as one level of nesting, since a parenthesized operand
+    /// such as the one in `-(-(1))` may hold another unary expression.
+    fn parse_unary_operand(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
+        self.enter_nesting()?;
+        let result = match self.get_current_token() {
+            Some(Token::NUMBER(_)) => self.parse_primitive(),
+            Some(Token::IDENTIFIER(_)) => self.parse_identifier(),
+            Some(Token::LPAREN) => self.parse_parenthesized_callee(),
+            _ => Err(vec![ErrorType::SyntaxError {
+                message: "Expected expression after unary operator".into(),
+            }]),
+        };
+        self.exit_nesting();
+        result
+    }
+
+    /// Folds a negated numeric literal, such as the `UnaryExpression` produced for `-5`, into a single
+    /// negative `Literal` node. Any other node is returned unchanged.
+    ///
+    /// # Parameters
+    ///
+    /// * `node`: The expression node to fold.
+    ///
+    /// # Returns
+    ///
+    /// Returns the folded `Literal` node, or the original node if it is not a negated numeric literal.
+    pub(crate) fn fold_negative_literal(node: ASTNode) -> ASTNode {
+        if node.get_node_type() != NodeType::UnaryExpression {
+            return node;
+        }
+
+        let children = node.get_children();
+        match (children.first().map(|c| c.get_node_type()), children.get(1).map(|c| c.get_node_type())) {
+            (Some(NodeType::Operator(op)), Some(NodeType::Literal(value)))
+                if op == "-" && children.len() == 2 && value.starts_with(|c: char| c.is_ascii_digit()) =>
+            {
+                ASTNode::new(NodeType::Literal(format!("-{}", value)))
+            }
+            _ => node,
+        }
+    }
+
+    /// Parses the rest of an assignment `target = value`, starting at its `=`. The value is parsed with
+    /// `Parser::parse_binary_expression`, so it may be any expression, including another assignment, which makes
+    /// `a = b = 0` assign `0` to `b` and then to `a`. Creates a 'NodeType::Assignment' ASTNode, with children
+    /// representing the identifier and its new value.
     ///
     /// # Parameters
     ///
-    /// * `name_chars`: A vector of characters representing the name of the variable to be reassigned.
+    /// * `target`: The expression before the `=`, which must be a variable.
     ///
     /// # Returns
     ///
@@ -77,120 +134,203 @@ impl Parser {
     ///
     /// # Errors
     ///
-    /// * Returns an error if parsing of the assignment fails.
-    pub fn parse_assignment(&mut self, name_chars: Vec<char>) -> Result<Option<ASTNode>, Vec<ErrorType>> {
-        // Create an identifier from the name
-        let name: String = name_chars.iter().collect();
-        
-        // Consume the equal sign
+    /// * Returns an error if the target is not a variable, or the value is missing or malformed.
+    pub fn parse_assignment(&mut self, target: ASTNode) -> Result<Option<ASTNode>, Vec<ErrorType>> {
+        let target = Parser::assignment_target(target, "=")?;
         self.consume(Token::EQUAL, "Expected '=' for assignment")?;
-        
-        // Parse the expression on the right side of the equals sign
-        // This can be a simple value or a complex expression
-        let mut assigned_value = match self.get_current_token() {
-            Some(Token::NUMBER(_)) => self.parse_primitive()?,
-            Some(Token::IDENTIFIER(_)) => self.parse_identifier()?,
-            Some(Token::DASH) | Some(Token::EXCLAMATIONPOINT) => self.parse_unary_expression()?,
-            Some(Token::LPAREN) => self.parse_parenthesized_expression()?,
-            _ => {
-                return Err(vec![ErrorType::SyntaxError {
-                    message: "Expected expression after '='".into(),
-                }]);
-            }
-        }.ok_or_else(|| vec![ErrorType::SyntaxError {
+
+        let value = self.parse_assigned_value()?.ok_or_else(|| vec![ErrorType::SyntaxError {
             message: "Expected expression after '='".into(),
         }])?;
 
-        // Check if there's a binary operator after the initial value (e.g., "x = 10 * 4")
-        // Handle complex expressions like "x = a * (b + c)"
-        loop {
-            match self.get_current_token() {
-                Some(Token::PLUS) | Some(Token::DASH) | Some(Token::ASTERISK) | Some(Token::FSLASH) => {
-                    // Parse the operator
-                    let operator = match self.get_current_token() {
-                        Some(Token::PLUS) => {
-                            self.advance();
-                            "+".to_string()
-                        },
-                        Some(Token::DASH) => {
-                            self.advance();
-                            "-".to_string()
-                        },
-                        Some(Token::ASTERISK) => {
-                            self.advance();
-                            "*".to_string()
-                        },
-                        Some(Token::FSLASH) => {
-                            self.advance();
-                            "/".to_string()
-                        },
-                        _ => unreachable!(),
-                    };
-                    
-                    // Parse the right side of the binary expression
-                    let right = match self.get_current_token() {
-                        Some(Token::NUMBER(_)) => self.parse_primitive()?,
-                        Some(Token::IDENTIFIER(_)) => self.parse_identifier()?,
-                        Some(Token::DASH) | Some(Token::EXCLAMATIONPOINT) => self.parse_unary_expression()?,
-                        Some(Token::LPAREN) => self.parse_parenthesized_expression()?,
-                        _ => {
-                            return Err(vec![ErrorType::SyntaxError {
-                                message: "Expected expression after operator".into(),
-                            }]);
-                        }
-                    }.ok_or_else(|| vec![ErrorType::SyntaxError {
-                        message: "Expected expression after operator".into(),
-                    }])?;
-                    
-                    // Create a binary expression with proper precedence
-                    // If we have a * (b + c), ensure it has the right structure
-                    let mut binary_expr = ASTNode::new(NodeType::BinaryExpression);
-                    binary_expr.add_child(assigned_value);
-                    binary_expr.add_child(ASTNode::new(NodeType::Operator(operator)));
-                    binary_expr.add_child(right);
-                    assigned_value = binary_expr;
-                },
-                _ => break, // Exit the loop if no more operators
-            }
+        let target_span = target.get_span();
+        let mut assignment_node = ASTNode::new(NodeType::Assignment);
+        assignment_node.add_child(target);
+        assignment_node.add_child(value);
+        self.mark_span_from(&mut assignment_node, target_span);
+        Ok(Some(assignment_node))
+    }
+
+    /// Parses the rest of a compound assignment such as `x += 5` or `x -= y * 2`, starting at its operator, into a
+    /// `NodeType::CompoundAssignment` ASTNode whose children are the identifier, the operator, and the value, which
+    /// may be any expression, including another assignment.
+    ///
+    /// # Parameters
+    ///
+    /// * `target`: The expression before the operator, which must be a variable.
+    ///
+    /// # Returns
+    ///
+    /// Returns an `Option<ASTNode>` representing the parsed compound assignment, or an error
+    /// `Vec<ErrorType>` if parsing fails.
+    ///
+    /// # Errors
+    ///
+    /// * Returns an error if the current token is not a compound assignment operator, the target is not a
+    ///   variable, or the value is missing.
+    pub fn parse_compound_assignment(&mut self, target: ASTNode) -> Result<Option<ASTNode>, Vec<ErrorType>> {
+        let operator = self.get_current_token().and_then(Parser::compound_assignment_operator)
+            .ok_or_else(|| vec![ErrorType::SyntaxError {
+                message: "Expected compound assignment operator".into(),
+            }])?;
+        let target = Parser::assignment_target(target, operator)?;
+        self.advance();
+
+        let value = self.parse_assigned_value()?.ok_or_else(|| vec![ErrorType::SyntaxError {
+            message: format!("Expected expression after '{}'", operator),
+        }])?;
+
+        let target_span = target.get_span();
+        let mut assignment_node = ASTNode::new(NodeType::CompoundAssignment);
+        assignment_node.add_child(target);
+        assignment_node.add_child(ASTNode::new(NodeType::Operator(operator.to_string())));
+        assignment_node.add_child(value);
+        self.mark_span_from(&mut assignment_node, target_span);
+        Ok(Some(assignment_node))
+    }
+
+    /// Parses the value of an assignment or compound assignment, counting it as one level of nesting, since
+    /// assignment is right-associative and `a = b = c` parses the value `b = c` recursively.
+    fn parse_assigned_value(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
+        self.enter_nesting()?;
+        let result = self.parse_binary_expression();
+        self.exit_nesting();
+        result
+    }
+
+    /// Returns the spelling of a compound assignment operator such as `+=`, or None for any other token.
+    fn compound_assignment_operator(token: &Token) -> Option<&'static str> {
+        match token {
+            Token::PLUSEQUAL => Some("+="),
+            Token::DASHEQUAL => Some("-="),
+            Token::ASTERISKEQUAL => Some("*="),
+            Token::FSLASHEQUAL => Some("/="),
+            Token::PERCENTEQUAL => Some("%="),
+            Token::LSHIFTEQUAL => Some("<<="),
+            Token::RSHIFTEQUAL => Some(">>="),
+            Token::AMPERSANDEQUAL => Some("&="),
+            Token::BAREQUAL => Some("|="),
+            Token::CARETEQUAL => Some("^="),
+            _ => None,
         }
+    }
 
-        // Create the assignment node
-        let mut assignment_node = ASTNode::new(NodeType::Assignment);
-        assignment_node.add_child(ASTNode::new(NodeType::Identifier(name)));
-        assignment_node.add_child(assigned_value);
-        
-        // Consume semicolon if present
-        if let Some(Token::SEMICOLON) = self.get_current_token() {
-            self.consume(Token::SEMICOLON, "Expected ';' after assignment")?;
+    /// Checks that the left side of an assignment is a variable, the only target assignments support, so that
+    /// `a + b = c` is rejected rather than parsed as `a + (b = c)`. Struct members are not assignable yet, so
+    /// `p.x = 3` is rejected with an error saying so.
+    ///
+    /// # Parameters
+    ///
+    /// * `target`: The expression before the assignment operator.
+    /// * `operator`: The assignment operator, used in the error message.
+    ///
+    /// # Errors
+    ///
+    /// * Returns a `SyntaxError` if the target is not an identifier.
+    fn assignment_target(target: ASTNode, operator: &str) -> Result<ASTNode, Vec<ErrorType>> {
+        match target.get_node_type() {
+            NodeType::Identifier(_) => Ok(target),
+            NodeType::MemberAccess => Err(vec![ErrorType::SyntaxError {
+                message: format!("Assignment to a struct member with '{}' is not supported", operator),
+            }]),
+            _ => Err(vec![ErrorType::SyntaxError {
+                message: format!("Expected a variable on the left of '{}'", operator),
+            }]),
         }
-        
-        Ok(Some(assignment_node))
     }
 
-    /// Entry point for the parsing of a binary expression.
+    /// Entry point for the parsing of an expression: a binary expression, a conditional expression `a ? b : c`
+    /// built from them, which binds looser than every binary operator, or an assignment, which binds loosest of all
+    /// and groups to the right, so `a = b = c ? d : e` is parsed as `a = (b = (c ? d : e))`.
     ///
     /// # Returns
     /// 
-    /// * `Ok(Some(ASTNode))` - if the binary expression was successfully parsed.
-    /// * `Ok(None)` - if there was no binary expression to parse.
+    /// * `Ok(Some(ASTNode))` - if the expression was successfully parsed.
+    /// * `Ok(None)` - if there was no expression to parse.
     /// * `Err(Vec<ErrorType>)` - if there were errors encountered during parsing.
     ///
     /// # Errors
     ///
-    /// * Returns an error if parsing of the assignment fails.
+    /// * Returns an error if parsing of the expression or an assignment in it fails.
     pub fn parse_binary_expression(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
-        self.parse_expression_with_precedence(0)
+        let expression = match self.parse_expression_with_precedence(0)? {
+            Some(condition) if matches!(self.get_current_token(), Some(Token::CTRUE)) => {
+                self.parse_ternary_expression(condition)?
+            }
+            Some(expression) => expression,
+            None => return Ok(None),
+        };
+        match self.get_current_token() {
+            Some(Token::EQUAL) => self.parse_assignment(expression),
+            Some(token) if Parser::compound_assignment_operator(token).is_some() => {
+                self.parse_compound_assignment(expression)
+            }
+            _ => Ok(Some(expression)),
+        }
+    }
+
+    /// Parses the rest of a conditional expression `condition ? then : else`, starting at its `?`. Either branch may
+    /// be a conditional expression itself, and they group to the right, so `a ? b : c ? d : e` is parsed as
+    /// `a ? b : (c ? d : e)`.
+    ///
+    /// # Parameters
+    ///
+    /// * `condition`: The already parsed condition before the `?`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `TernaryExpression` node, or an error `Vec<ErrorType>` if parsing fails.
+    ///
+    /// # Errors
+    ///
+    /// * Returns an error if a branch is missing, or the `:` between them is.
+    pub(crate) fn parse_ternary_expression(&mut self, condition: ASTNode) -> Result<ASTNode, Vec<ErrorType>> {
+        self.enter_nesting()?;
+        let result = self.parse_ternary_branches(condition);
+        self.exit_nesting();
+        result
+    }
+
+    /// Parses the branches for `parse_ternary_expression`, which tracks the nesting depth.
+    fn parse_ternary_branches(&mut self, condition: ASTNode) -> Result<ASTNode, Vec<ErrorType>> {
+        self.consume(Token::CTRUE, "Expected '?' in conditional expression")?;
+        let then_branch = self.parse_binary_expression()?.ok_or_else(|| vec![ErrorType::SyntaxError {
+            message: "Expected expression after '?'".into(),
+        }])?;
+        self.consume(Token::COLON, "Expected ':' in conditional expression")?;
+        let else_branch = self.parse_binary_expression()?.ok_or_else(|| vec![ErrorType::SyntaxError {
+            message: "Expected expression after ':'".into(),
+        }])?;
+
+        let condition_span = condition.get_span();
+        let mut ternary_expr = ASTNode::new(NodeType::TernaryExpression);
+        ternary_expr.add_child(condition);
+        ternary_expr.add_child(then_branch);
+        ternary_expr.add_child(else_branch);
+        self.mark_span_from(&mut ternary_expr, condition_span);
+        Ok(ternary_expr)
     }
 
     /// Helper function to parse expressions with operator precedence.
     /// Uses the precedence climbing method to correctly handle operator precedence.
     fn parse_expression_with_precedence(&mut self, min_precedence: i32) -> Result<Option<ASTNode>, Vec<ErrorType>> {
+        self.enter_nesting()?;
+        let result = self.parse_precedence_climbing(min_precedence);
+        self.exit_nesting();
+        result
+    }
+
+    /// Performs the precedence climbing for `parse_expression_with_precedence`, which tracks the nesting depth.
+    fn parse_precedence_climbing(&mut self, min_precedence: i32) -> Result<Option<ASTNode>, Vec<ErrorType>> {
         // Parse the left-hand side
-        let mut left = match self.get_current_token() {
-            Some(Token::NUMBER(_)) => self.parse_primitive()?,
+        let start = self.position();
+        let left = match self.get_current_token() {
+            Some(Token::NUMBER(_)) | Some(Token::STRINGLITERAL(_)) | Some(Token::CHAR(_)) => self.parse_primitive()?,
             Some(Token::IDENTIFIER(_)) => self.parse_identifier()?,
-            Some(Token::DASH) | Some(Token::EXCLAMATIONPOINT) => self.parse_unary_expression()?,
-            Some(Token::LPAREN) => self.parse_parenthesized_expression()?,
+            Some(Token::DASH) | Some(Token::EXCLAMATIONPOINT) | Some(Token::TILDE) | Some(Token::AMPERSAND) => self.parse_unary_expression()?,
+            // A leading `*` dereferences its operand, as in the callee of `(*fp)(1, 2)`
+            Some(Token::ASTERISK) => self.parse_unary_expression()?,
+            Some(Token::LPAREN) => self.parse_parenthesized_callee()?,
             _ => {
                 return Err(vec![ErrorType::SyntaxError {
                     message: "Expected expression".into(),
@@ -200,13 +340,26 @@ impl Parser {
             message: "Expected left-hand side expression".into(),
         }])?;
 
+        self.parse_binary_operators(left, start, min_precedence)
+    }
+
+    /// Parses the binary operators following `left` for `parse_precedence_climbing`, for as long as their precedence
+    /// is at least `min_precedence`. Kept apart from parsing the left-hand side so that the locals used here do not
+    /// add to the stack taken by each level of a nested left-hand side, such as `((((1))))`.
+    fn parse_binary_operators(&mut self, mut left: ASTNode, start: usize, min_precedence: i32) -> Result<Option<ASTNode>, Vec<ErrorType>> {
         // Define operator precedence
         let get_precedence = |op: &str| -> i32 {
             match op {
-                "*" | "/" | "%" => 3,
-                "+" | "-" => 2,
-                "<" | ">" | "<=" | ">=" => 1,
-                "==" | "!=" => 0,
+                "*" | "/" | "%" => 9,
+                "+" | "-" => 8,
+                "<<" | ">>" => 7,
+                "<" | ">" | "<=" | ">=" => 6,
+                "==" | "!=" => 5,
+                "&" => 4,
+                "^" => 3,
+                "|" => 2,
+                "&&" => 1,
+                "||" => 0,
                 _ => -1,
             }
         };
@@ -222,8 +375,17 @@ impl Parser {
                 Token::PERCENT       => "%".to_string(),
                 Token::LESSTHAN      => "<".to_string(),
                 Token::GREATERTHAN   => ">".to_string(),
+                Token::LESSTHANEQUAL => "<=".to_string(),
+                Token::GREATERTHANEQUAL => ">=".to_string(),
+                Token::LSHIFT        => "<<".to_string(),
+                Token::RSHIFT        => ">>".to_string(),
                 Token::EQUALEQUAL    => "==".to_string(),
                 Token::NOTEQUAL      => "!=".to_string(),
+                Token::AMPERSAND     => "&".to_string(),
+                Token::CARET         => "^".to_string(),
+                Token::BAR           => "|".to_string(),
+                Token::ANDAND        => "&&".to_string(),
+                Token::BARBAR        => "||".to_string(),
                 _ => break,
             };
 
@@ -247,6 +409,7 @@ impl Parser {
             binary_expr.add_child(left);
             binary_expr.add_child(ASTNode::new(NodeType::Operator(operator)));
             binary_expr.add_child(right);
+            self.mark_span(&mut binary_expr, start);
 
             left = binary_expr;
         }
@@ -260,7 +423,7 @@ impl Parser {
     ///
     /// # Returns
     ///
-    /// Returns an `Option<ASTNode>` representing the parsed expression, or an error
+    /// Returns an `Option<ASTNode>` representing the parsed expression, marked as parenthesized, or an error
     /// `Vec<ErrorType>` if parsing fails.
     ///
     /// # Errors
@@ -272,9 +435,11 @@ impl Parser {
 
         // Parse the full expression inside the parentheses using normal binary-expression parsing
         let expr = self.parse_binary_expression()?;
-        let expr = expr.ok_or_else(|| vec![ErrorType::SyntaxError {
+        let mut expr = expr.ok_or_else(|| vec![ErrorType::SyntaxError {
             message: "Expected expression within parentheses".into(),
         }])?;
+        // Record the parentheses so that printing and precedence lints can tell `(a & b) == c` from `a & b == c`
+        expr.set_parenthesized(true);
 
         // Consume the closing parenthesis
         self.consume(Token::RPAREN, "Expected ')' to close parenthesized expression")?;
@@ -283,6 +448,116 @@ impl Parser {
         // surrounding `parse_expression_with_precedence` call.
         Ok(Some(expr))
     }
+
+    /// Parses a parenthesized expression that may be called or have its members accessed, such as
+    /// `(pick(x))(1, 2)` or `(*p).x`.
+    ///
+    /// # Returns
+    ///
+    /// Returns an `Option<ASTNode>` representing the expression, wrapped in `FunctionCall`, `MemberAccess` and
+    /// `PostfixExpression` nodes for every argument list, member access, increment and decrement that follows it, or
+    /// an error `Vec<ErrorType>` if parsing fails.
+    ///
+    /// # Errors
+    ///
+    /// * Returns an error if parsing of the expression or one of its argument lists fails.
+    pub fn parse_parenthesized_callee(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
+        let expr = self.parse_parenthesized_expression()?.ok_or_else(|| vec![ErrorType::SyntaxError {
+            message: "Expected expression within parentheses".into(),
+        }])?;
+        self.parse_postfix_expression(expr).map(Some)
+    }
+
+    /// Parses any argument lists, member accesses, increments and decrements following an expression. Creates a
+    /// 'NodeType::FunctionCall' ASTNode per argument list, whose first child is the callee and whose remaining children
+    /// are the arguments, so `f(1)(2)` calls the result of `f(1)`. Creates a 'NodeType::MemberAccess' ASTNode per `.`
+    /// or `->`, whose children are the base expression, the operator, and the field name, so `a.b.c` accesses `c` of
+    /// `a.b`. Creates a 'NodeType::PostfixExpression' ASTNode per `++` or `--`, whose children are the operand and the
+    /// operator, so `p.n++` increments `p.n`.
+    ///
+    /// # Parameters
+    ///
+    /// * `base`: The expression the postfix operators apply to, such as an identifier or a parenthesized expression.
+    ///
+    /// # Returns
+    ///
+    /// Returns the base unchanged if no `(`, `.`, `->`, `++` or `--` follows it, otherwise the outermost
+    /// `FunctionCall`, `MemberAccess` or `PostfixExpression` node, or an error `Vec<ErrorType>` if parsing fails.
+    ///
+    /// # Errors
+    ///
+    /// * Returns an error if an argument cannot be parsed, the argument list is not closed, or a `.` or `->` is not
+    ///   followed by a field name.
+    pub fn parse_postfix_expression(&mut self, base: ASTNode) -> Result<ASTNode, Vec<ErrorType>> {
+        let mut expr = base;
+
+        loop {
+            match self.get_current_token() {
+                Some(Token::LPAREN) => {
+                    let callee_span = expr.get_span();
+                    let mut call_node = self.parse_call_arguments(expr)?;
+                    self.mark_span_from(&mut call_node, callee_span);
+                    expr = call_node;
+                }
+                Some(Token::DOT) | Some(Token::POINTER) => {
+                    let operator = if self.get_current_token() == Some(&Token::DOT) { "." } else { "->" };
+                    self.advance();
+                    let field = self.parse_variable_name().map_err(|_| vec![ErrorType::SyntaxError {
+                        message: format!("Expected field name after '{}'", operator),
+                    }])?;
+
+                    let base_span = expr.get_span();
+                    let mut member_access = ASTNode::new(NodeType::MemberAccess);
+                    member_access.add_child(expr);
+                    member_access.add_child(ASTNode::new(NodeType::Operator(operator.to_string())));
+                    member_access.add_child(ASTNode::new(NodeType::Identifier(field)));
+                    self.mark_span_from(&mut member_access, base_span);
+                    expr = member_access;
+                }
+                Some(Token::PLUSPLUS) | Some(Token::MINUSMINUS) => {
+                    let operator = if self.get_current_token() == Some(&Token::PLUSPLUS) { "++" } else { "--" };
+                    self.advance();
+
+                    let operand_span = expr.get_span();
+                    let mut postfix = ASTNode::new(NodeType::PostfixExpression);
+                    postfix.add_child(expr);
+                    postfix.add_child(ASTNode::new(NodeType::Operator(operator.to_string())));
+                    self.mark_span_from(&mut postfix, operand_span);
+                    expr = postfix;
+                }
+                _ => return Ok(expr),
+            }
+        }
+    }
+
+    /// Parses the argument list of a call to `callee`, which the current `(` starts.
+    fn parse_call_arguments(&mut self, callee: ASTNode) -> Result<ASTNode, Vec<ErrorType>> {
+        self.consume(Token::LPAREN, "Expected '(' for function call")?;
+
+        let mut call_node = ASTNode::new(NodeType::FunctionCall);
+        call_node.add_child(callee);
+
+        if let Some(Token::RPAREN) = self.get_current_token() {
+            self.consume(Token::RPAREN, "Expected ')' after arguments")?;
+        } else {
+            loop {
+                let argument = self.parse_binary_expression()?.ok_or_else(|| vec![ErrorType::SyntaxError {
+                    message: "Expected argument expression".into(),
+                }])?;
+                call_node.add_child(argument);
+
+                match self.get_current_token() {
+                    Some(Token::COMMA) => self.consume(Token::COMMA, "Expected ',' between arguments")?,
+                    _ => {
+                        self.consume(Token::RPAREN, "Expected ')' after arguments")?;
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(call_node)
+    }
     
     /// Parses a condition expression, which is often part of control flow statements.
     ///
@@ -297,15 +572,8 @@ impl Parser {
     pub fn parse_condition(&mut self) -> Result<Option<ASTNode>, Vec<ErrorType>> {
         self.consume(Token::LPAREN, "Expected '(' after control flow keyword")?;
         
-        // Parse the condition expression
-        let condition_expr = match self.get_current_token() {
-            Some(Token::NUMBER(_)) => self.parse_primitive()?,
-            Some(Token::IDENTIFIER(_)) => self.parse_identifier()?,
-            Some(Token::DASH) | Some(Token::EXCLAMATIONPOINT) => self.parse_unary_expression()?,
-            _ => {
-                self.parse_binary_expression()?
-            }
-        }.ok_or_else(|| vec![ErrorType::SyntaxError {
+        // Parse the condition expression, so that a condition such as `i++ != n` is read in full
+        let condition_expr = self.parse_binary_expression()?.ok_or_else(|| vec![ErrorType::SyntaxError {
             message: "Expected condition expression".into(),
         }])?;
         
@@ -3,6 +3,7 @@
 use common::ast::{
     core::{ASTNode, AST}, data_type::DataType, node_type::NodeType
 };
+use common::symbol::Symbol;
 use lexer::token::Token;
 use parser::core::Parser;
 
@@ -28,7 +29,7 @@ fn test_eof() {
 #[test]
 fn test_number_syntax_element() {
     let tokens: Vec<Token> = vec![
-        Token::NUMBER(vec!['2', '3']),
+        Token::NUMBER(Symbol::intern("23")),
     ];
 
     let result = Parser::parse(tokens);
@@ -47,7 +48,7 @@ fn test_number_syntax_element() {
 #[test]
 fn test_floating_point_number() {
     let tokens: Vec<Token> = vec![
-        Token::NUMBER(vec!['2', '.', '3']),
+        Token::NUMBER(Symbol::intern("2.3")),
     ];
 
     let result = Parser::parse(tokens);
@@ -66,7 +67,7 @@ fn test_floating_point_number() {
 #[test]
 fn test_identifier_syntax_element() {
     let tokens: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['f', 'o', 'o']),
+        Token::IDENTIFIER(Symbol::intern("foo")),
     ];
 
     let result = Parser::parse(tokens);
@@ -138,9 +139,9 @@ fn test_semicolon_syntax_element() {
 #[test]
 fn test_operator_syntax_element() {
     let tokens: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::LESSTHAN,
-        Token::IDENTIFIER(vec!['y']),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::SEMICOLON,
     ];
 
@@ -171,7 +172,7 @@ fn test_operator_syntax_element() {
 fn test_data_type_syntax_element() {
     let tokens: Vec<Token> = vec![
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['y']),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::SEMICOLON,
     ];
 
@@ -203,11 +204,11 @@ fn test_if_statement_syntax_element() {
     let tokens: Vec<Token> = vec![
         Token::IF,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('x')),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::RETURN,
-        Token::IDENTIFIER(vec!('y')),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::SEMICOLON,
         Token::RBRACKET,
         Token::EOF,
@@ -227,19 +228,19 @@ fn test_for_loop_syntax_element() {
     let tokens: Vec<Token> = vec![
         Token::FOR,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('x')), 
+        Token::IDENTIFIER(Symbol::intern("x")), 
         Token::EQUAL,
-        Token::NUMBER(vec!('0')),
+        Token::NUMBER(Symbol::intern("0")),
         Token::SEMICOLON,
-        Token::IDENTIFIER(vec!('x')), 
+        Token::IDENTIFIER(Symbol::intern("x")), 
         Token::LESSTHAN,
-        Token::NUMBER(vec!('1')),
+        Token::NUMBER(Symbol::intern("1")),
         Token::SEMICOLON,
-        Token::IDENTIFIER(vec!('x')), 
+        Token::IDENTIFIER(Symbol::intern("x")), 
         Token::EQUAL,
-        Token::IDENTIFIER(vec!('x')), 
+        Token::IDENTIFIER(Symbol::intern("x")), 
         Token::PLUS,
-        Token::NUMBER(vec!('1')),
+        Token::NUMBER(Symbol::intern("1")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::BREAK,
@@ -261,7 +262,7 @@ fn test_while_loop_syntax_element() {
     let tokens: Vec<Token> = vec![
         Token::WHILE,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('x')),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::BREAK,
@@ -289,7 +290,7 @@ fn test_do_while_loop_syntax_element() {
         Token::RBRACKET,
         Token::WHILE,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('x')),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::RPAREN,
         Token::SEMICOLON,
         Token::EOF,
@@ -307,7 +308,7 @@ fn test_do_while_loop_syntax_element() {
 fn test_return_syntax_element() {
     let tokens: Vec<Token> = vec![
         Token::RETURN,
-        Token::IDENTIFIER(vec!('x')),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::SEMICOLON,
     ];
 
@@ -325,7 +326,7 @@ fn test_switch_statement_syntax_element() {
     let tokens: Vec<Token> = vec![
         Token::SWITCH,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['y']),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::RPAREN,
         Token::LBRACKET,        
         Token::RBRACKET,
@@ -346,16 +347,16 @@ fn test_case_syntax_element() {
     let tokens: Vec<Token> = vec![
         Token::SWITCH,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['y']),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::RPAREN,
         Token::LBRACKET, 
         Token::CASE,
-        Token::NUMBER(vec!['1']),
+        Token::NUMBER(Symbol::intern("1")),
         Token::COLON,
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::EQUAL,
-        Token::NUMBER(vec!['6']),
+        Token::NUMBER(Symbol::intern("6")),
         Token::SEMICOLON,       
         Token::RBRACKET,
         Token::EOF,
@@ -375,15 +376,15 @@ fn test_default_syntax_element() {
     let tokens: Vec<Token> = vec![
         Token::SWITCH,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['y']),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::RPAREN,
         Token::LBRACKET, 
         Token::DEFAULT,
         Token::COLON,
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::EQUAL,
-        Token::NUMBER(vec!['8']),
+        Token::NUMBER(Symbol::intern("8")),
         Token::SEMICOLON,     
         Token::RBRACKET,
         Token::EOF,
@@ -401,9 +402,9 @@ fn test_default_syntax_element() {
 #[test]
 fn test_assignment_syntax_element() {
     let tokens: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['y']),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::EQUAL,
-        Token::NUMBER(vec!['8']),
+        Token::NUMBER(Symbol::intern("8")),
     ];
 
     let ast: AST = Parser::parse(tokens).expect("Failed to parse");
@@ -419,9 +420,9 @@ fn test_assignment_syntax_element() {
 fn test_initialization_syntax_element() {
     let tokens: Vec<Token> = vec![
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['y']),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::EQUAL,
-        Token::NUMBER(vec!['8']),
+        Token::NUMBER(Symbol::intern("8")),
     ];
 
     let ast: AST = Parser::parse(tokens).expect("Failed to parse");
@@ -437,7 +438,7 @@ fn test_initialization_syntax_element() {
 fn test_function_declaration_syntax_element() {
     let tokens: Vec<Token> = vec![
         Token::TVOID,
-        Token::IDENTIFIER(vec!['m', 'y', '_', 'f', 'u', 'n', 'c']),
+        Token::IDENTIFIER(Symbol::intern("my_func")),
         Token::LPAREN,
         Token::RPAREN,
         Token::LBRACKET,
@@ -459,7 +460,7 @@ fn test_function_declaration_syntax_element() {
 fn test_struct_declaration_syntax_element() {
     let tokens: Vec<Token> = vec![
         Token::STRUCT,
-        Token::IDENTIFIER(vec!['M', 'y', 'S', 't', 'r', 'u', 'c', 't']),
+        Token::IDENTIFIER(Symbol::intern("MyStruct")),
         Token::LBRACE,
         Token::RBRACE,
         Token::SEMICOLON,
@@ -478,7 +479,7 @@ fn test_struct_declaration_syntax_element() {
 fn test_enum_declaration_syntax_element() {
     let tokens: Vec<Token> = vec![
         Token::ENUM,
-        Token::IDENTIFIER(vec!['M', 'y', 'E', 'n', 'u', 'm']),
+        Token::IDENTIFIER(Symbol::intern("MyEnum")),
         Token::LBRACE,
         Token::RBRACE,
         Token::SEMICOLON,
@@ -514,11 +515,11 @@ fn test_condition_syntax_element() {
     let tokens: Vec<Token> = vec![
         Token::IF,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('x')),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::RETURN,
-        Token::IDENTIFIER(vec!('y')),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::SEMICOLON,
         Token::RBRACKET,
         Token::EOF,
@@ -536,13 +537,13 @@ fn test_condition_syntax_element() {
 fn test_variant_syntax_element() {
     let tokens: Vec<Token> = vec![
         Token::ENUM,
-        Token::IDENTIFIER(vec!['C', 'o', 'l', 'o', 'r']),
+        Token::IDENTIFIER(Symbol::intern("Color")),
         Token::LBRACE,
-        Token::IDENTIFIER(vec!['R', 'e', 'd']),
+        Token::IDENTIFIER(Symbol::intern("Red")),
         Token::COMMA,
-        Token::IDENTIFIER(vec!['G', 'r', 'e', 'e', 'n']),
+        Token::IDENTIFIER(Symbol::intern("Green")),
         Token::COMMA,
-        Token::IDENTIFIER(vec!['B', 'l', 'u', 'e']),
+        Token::IDENTIFIER(Symbol::intern("Blue")),
         Token::RBRACE,
         Token::SEMICOLON,
     ];
@@ -560,9 +561,9 @@ fn test_variant_syntax_element() {
 fn test_assigned_value_syntax_element() {
     let tokens: Vec<Token> = vec![
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['y']),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::EQUAL,
-        Token::NUMBER(vec!['8']),
+        Token::NUMBER(Symbol::intern("8")),
     ];
 
     let ast: AST = Parser::parse(tokens).expect("Failed to parse");
@@ -578,13 +579,13 @@ fn test_assigned_value_syntax_element() {
 fn test_field_syntax_element() {
     let tokens: Vec<Token> = vec![
         Token::STRUCT,
-        Token::IDENTIFIER(vec!['M', 'y', 'S', 't', 'r', 'u', 'c', 't']),
+        Token::IDENTIFIER(Symbol::intern("MyStruct")),
         Token::LBRACE,
-        Token::IDENTIFIER(vec!['a']),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::COLON,
         Token::TINTEGER,
         Token::COMMA,
-        Token::IDENTIFIER(vec!['b']),
+        Token::IDENTIFIER(Symbol::intern("b")),
         Token::COLON,
         Token::TBOOLEAN,
         Token::RBRACE,
@@ -604,10 +605,10 @@ fn test_field_syntax_element() {
 fn test_parameter_syntax_element() {
     let tokens: Vec<Token> = vec![
         Token::TBOOLEAN,
-        Token::IDENTIFIER(vec!['c', 'a', 'l', 'c', 'u', 'l', 'a', 't', 'e']),
+        Token::IDENTIFIER(Symbol::intern("calculate")),
         Token::LPAREN,
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::RBRACKET,
@@ -627,9 +628,9 @@ fn test_parameter_syntax_element() {
 fn test_variable_syntax_element() {
     let tokens: Vec<Token> = vec![
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['y']),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::EQUAL,
-        Token::NUMBER(vec!['8']),
+        Token::NUMBER(Symbol::intern("8")),
     ];
 
     let ast: AST = Parser::parse(tokens).expect("Failed to parse");
@@ -644,9 +645,9 @@ fn test_variable_syntax_element() {
 #[test]
 fn test_bin_exp_syntax_element() {
     let tokens: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::LESSTHAN,
-        Token::IDENTIFIER(vec!['y']),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::SEMICOLON,
     ];
     
@@ -663,7 +664,7 @@ fn test_bin_exp_syntax_element() {
 fn test_unary_exp_syntax_element() {
     let tokens: Vec<Token> = vec![
         Token::DASH,
-        Token::IDENTIFIER(vec!['y']),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::SEMICOLON,
     ];
     
@@ -681,19 +682,19 @@ fn test_for_loop_elements() {
     let tokens: Vec<Token> = vec![
         Token::FOR,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('x')), 
+        Token::IDENTIFIER(Symbol::intern("x")), 
         Token::EQUAL,
-        Token::NUMBER(vec!('0')),
+        Token::NUMBER(Symbol::intern("0")),
         Token::SEMICOLON,
-        Token::IDENTIFIER(vec!('x')), 
+        Token::IDENTIFIER(Symbol::intern("x")), 
         Token::LESSTHAN,
-        Token::NUMBER(vec!('1')),
+        Token::NUMBER(Symbol::intern("1")),
         Token::SEMICOLON,
-        Token::IDENTIFIER(vec!('x')), 
+        Token::IDENTIFIER(Symbol::intern("x")), 
         Token::EQUAL,
-        Token::IDENTIFIER(vec!('x')), 
+        Token::IDENTIFIER(Symbol::intern("x")), 
         Token::PLUS,
-        Token::NUMBER(vec!('1')),
+        Token::NUMBER(Symbol::intern("1")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::BREAK,
@@ -1,10 +1,16 @@
 //! This file contains combination tests for the parser, which tests sequences of tokens that represent common programming concepts, such as statements, loops, expressions, etc.
 
-use common::ast::{
-    core::{ASTNode, AST}, data_type::DataType, node_type::NodeType
+use common::{
+    ast::{
+        build, core::{ASTNode, AST}, data_type::DataType, node_type::NodeType
+    },
+    config::LanguageConfig,
+    literal::ByteString,
+    location::Span,
+    symbol::Symbol,
 };
-use lexer::token::Token;
-use parser::core::Parser;
+use lexer::{core::Lexer, token::Token, tokens};
+use parser::{core::Parser, incremental::{reparse, TextEdit}};
 
 /// ---- Expression Section ---- 
 
@@ -24,9 +30,9 @@ use parser::core::Parser;
 fn test_basic_binary_expr() {
     
     let tokens_2: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['a']),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::PLUS,
-        Token::NUMBER(vec!['5']),
+        Token::NUMBER(Symbol::intern("5")),
         Token::EOF
         
     ];
@@ -51,9 +57,9 @@ fn test_basic_binary_expr() {
     assert_eq!(ast_2, expected_ast_2);
 
     let tokens_3: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['a']),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::DASH,
-        Token::NUMBER(vec!['5']),
+        Token::NUMBER(Symbol::intern("5")),
         Token::EOF
         
     ];
@@ -79,9 +85,9 @@ fn test_basic_binary_expr() {
     assert_eq!(ast_3, expected_ast_3);
 
     let tokens_4: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['a']),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::FSLASH,
-        Token::NUMBER(vec!['5']),
+        Token::NUMBER(Symbol::intern("5")),
         Token::EOF
         
     ];
@@ -123,11 +129,11 @@ fn test_basic_binary_expr() {
 fn test_compound_binary_expr() {
     // A * B + C
     let tokens: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['a']),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::ASTERISK,
-        Token::IDENTIFIER(vec!['b']),
+        Token::IDENTIFIER(Symbol::intern("b")),
         Token::PLUS,
-        Token::IDENTIFIER(vec!['c']),
+        Token::IDENTIFIER(Symbol::intern("c")),
         Token::EOF
         
     ];
@@ -155,13 +161,13 @@ fn test_compound_binary_expr() {
 
     // A + B * C + D
     let tokens: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['a']),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::PLUS,
-        Token::IDENTIFIER(vec!['b']),
+        Token::IDENTIFIER(Symbol::intern("b")),
         Token::ASTERISK,
-        Token::IDENTIFIER(vec!['c']),
+        Token::IDENTIFIER(Symbol::intern("c")),
         Token::PLUS,
-        Token::IDENTIFIER(vec!['d']),
+        Token::IDENTIFIER(Symbol::intern("d")),
         Token::EOF
         
     ];
@@ -199,17 +205,17 @@ fn test_compound_binary_expr() {
 
     // A * B + C / D % E - F
     let tokens: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['a']),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::ASTERISK,
-        Token::IDENTIFIER(vec!['b']),
+        Token::IDENTIFIER(Symbol::intern("b")),
         Token::PLUS,
-        Token::IDENTIFIER(vec!['c']),
+        Token::IDENTIFIER(Symbol::intern("c")),
         Token::FSLASH,
-        Token::IDENTIFIER(vec!['d']),
+        Token::IDENTIFIER(Symbol::intern("d")),
         Token::PERCENT,
-        Token::IDENTIFIER(vec!['e']),
+        Token::IDENTIFIER(Symbol::intern("e")),
         Token::DASH,
-        Token::IDENTIFIER(vec!['f']),
+        Token::IDENTIFIER(Symbol::intern("f")),
         Token::EOF
         
     ];
@@ -264,19 +270,19 @@ fn test_compound_binary_expr() {
 
         Token::LPAREN,
         Token::DASH,
-        Token::IDENTIFIER(vec!['a']),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::ASTERISK,
-        Token::IDENTIFIER(vec!['b']),
+        Token::IDENTIFIER(Symbol::intern("b")),
         Token::RPAREN,
         Token::PLUS,
-        Token::IDENTIFIER(vec!['c']),
+        Token::IDENTIFIER(Symbol::intern("c")),
         Token::FSLASH,
-        Token::IDENTIFIER(vec!['d']),
+        Token::IDENTIFIER(Symbol::intern("d")),
         Token::PERCENT,
         Token::DASH,
-        Token::IDENTIFIER(vec!['e']),
+        Token::IDENTIFIER(Symbol::intern("e")),
         Token::DASH,
-        Token::IDENTIFIER(vec!['f']),
+        Token::IDENTIFIER(Symbol::intern("f")),
         Token::EOF
         
     ];
@@ -344,7 +350,7 @@ fn test_compound_binary_expr() {
 fn test_unary_expression() {
     let tokens: Vec<Token> = vec![
         Token::DASH,
-        Token::IDENTIFIER(vec!['A']),
+        Token::IDENTIFIER(Symbol::intern("A")),
         Token::EOF
     ];
 
@@ -371,9 +377,9 @@ fn test_unary_expression() {
 fn test_binary_expr_with_unary_negation() {
     let tokens: Vec<Token> = vec![
         Token::DASH,
-        Token::NUMBER(vec!['5']),
+        Token::NUMBER(Symbol::intern("5")),
         Token::DASH, 
-        Token::NUMBER(vec!['3']),
+        Token::NUMBER(Symbol::intern("3")),
         Token::EOF,
     ];
 
@@ -409,9 +415,9 @@ fn test_binary_expr_with_unary_negation() {
 #[test]
 fn test_assignment_to_number() {
     let tokens: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::EQUAL, 
-        Token::NUMBER(vec!['3']),
+        Token::NUMBER(Symbol::intern("3")),
         Token::SEMICOLON,
     ];
 
@@ -438,11 +444,11 @@ fn test_assignment_to_number() {
 #[test]
 fn test_assignment_to_addition_expression() {
     let tokens: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::EQUAL,
-        Token::NUMBER(vec!['3']),
+        Token::NUMBER(Symbol::intern("3")),
         Token::PLUS,
-        Token::NUMBER(vec!['4']),
+        Token::NUMBER(Symbol::intern("4")),
         Token::SEMICOLON,
     ];
 
@@ -475,11 +481,11 @@ fn test_assignment_to_addition_expression() {
 #[test]
 fn test_assignment_to_multiplication_expression() {
     let tokens: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['y']),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::EQUAL,
-        Token::NUMBER(vec!['5']),
+        Token::NUMBER(Symbol::intern("5")),
         Token::ASTERISK,
-        Token::NUMBER(vec!['6']),
+        Token::NUMBER(Symbol::intern("6")),
         Token::SEMICOLON,
     ];
 
@@ -512,11 +518,11 @@ fn test_assignment_to_multiplication_expression() {
 #[test]
 fn test_assignment_to_division_expression() {
     let tokens: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['z']),
+        Token::IDENTIFIER(Symbol::intern("z")),
         Token::EQUAL,
-        Token::NUMBER(vec!['8']),
+        Token::NUMBER(Symbol::intern("8")),
         Token::FSLASH,
-        Token::NUMBER(vec!['2']),
+        Token::NUMBER(Symbol::intern("2")),
         Token::SEMICOLON,
     ];
 
@@ -549,11 +555,11 @@ fn test_assignment_to_division_expression() {
 #[test]
 fn test_assignment_to_subtraction_expression() {
     let tokens: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['w']),
+        Token::IDENTIFIER(Symbol::intern("w")),
         Token::EQUAL,
-        Token::NUMBER(vec!['1','0']),
+        Token::NUMBER(Symbol::intern("10")),
         Token::DASH,
-        Token::NUMBER(vec!['4']),
+        Token::NUMBER(Symbol::intern("4")),
         Token::SEMICOLON,
     ];
 
@@ -587,15 +593,15 @@ fn test_assignment_to_subtraction_expression() {
 #[test]
 fn test_assignment_to_parenthesized_addition_expression() {
     let tokens: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['a']),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::EQUAL,
         Token::LPAREN,
-        Token::NUMBER(vec!['3']),
+        Token::NUMBER(Symbol::intern("3")),
         Token::PLUS,
-        Token::NUMBER(vec!['4']),
+        Token::NUMBER(Symbol::intern("4")),
         Token::RPAREN,
         Token::ASTERISK,
-        Token::NUMBER(vec!['2']),
+        Token::NUMBER(Symbol::intern("2")),
         Token::SEMICOLON,
     ];
 
@@ -635,18 +641,18 @@ fn test_assignment_to_parenthesized_addition_expression() {
 #[test]
 fn test_assignment_to_complex_expression() {
     let tokens: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['b']),
+        Token::IDENTIFIER(Symbol::intern("b")),
         Token::EQUAL,
         Token::LPAREN,
-        Token::NUMBER(vec!['1']),
+        Token::NUMBER(Symbol::intern("1")),
         Token::PLUS,
-        Token::NUMBER(vec!['2']),
+        Token::NUMBER(Symbol::intern("2")),
         Token::RPAREN,
         Token::ASTERISK,
         Token::LPAREN,
-        Token::NUMBER(vec!['3']),
+        Token::NUMBER(Symbol::intern("3")),
         Token::DASH,
-        Token::NUMBER(vec!['4']),
+        Token::NUMBER(Symbol::intern("4")),
         Token::RPAREN,
         Token::SEMICOLON,
     ];
@@ -692,19 +698,19 @@ fn test_assignment_to_complex_expression() {
 #[test]
 fn test_assignment_to_nested_parentheses_expression() {
     let tokens: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['c']),
+        Token::IDENTIFIER(Symbol::intern("c")),
         Token::EQUAL,
         Token::LPAREN,
         Token::LPAREN,
-        Token::NUMBER(vec!['7']),
+        Token::NUMBER(Symbol::intern("7")),
         Token::PLUS,
-        Token::NUMBER(vec!['8']),
+        Token::NUMBER(Symbol::intern("8")),
         Token::RPAREN,
         Token::ASTERISK,
-        Token::NUMBER(vec!['2']),
+        Token::NUMBER(Symbol::intern("2")),
         Token::RPAREN,
         Token::FSLASH,
-        Token::NUMBER(vec!['3']),
+        Token::NUMBER(Symbol::intern("3")),
         Token::SEMICOLON,
     ];
 
@@ -741,6 +747,40 @@ fn test_assignment_to_nested_parentheses_expression() {
     assert_eq!(ast, expected_ast, "The parsed AST does not match the expected AST.");
 }
 
+/// Tests the parsing of bitwise and logical operators, and that parenthesized expressions are marked.
+/// This test checks if the parser handles `a & b == c || (a && b)` with the precedence of C, so that
+/// `b == c` groups before `&` and `&` before `||`, and that only the parenthesized `a && b` is marked.
+#[test]
+fn test_bitwise_and_logical_expression() {
+    let expression = Parser::parse_expression(Lexer::lex("a & b == c || (a && b)").unwrap()).expect("Failed to parse");
+
+    let comparison = build::binary(build::identifier("b"), "==", build::identifier("c"));
+    let bitwise = build::binary(build::identifier("a"), "&", comparison);
+    let logical = build::binary(build::identifier("a"), "&&", build::identifier("b"));
+    assert_eq!(expression, build::binary(bitwise, "||", logical), "The parsed AST does not match the expected AST.");
+
+    let operands = expression.get_children();
+    assert!(!expression.is_parenthesized());
+    assert!(!operands[0].is_parenthesized());
+    assert!(operands[2].is_parenthesized());
+}
+
+/// Tests that `<=` and `>=` share the precedence of `<` and `>`, binding looser than `+` and tighter than `==`, so
+/// `a + 1 <= b == c >= d` groups as `((a + 1) <= b) == (c >= d)`, and that they parse in a `while` condition.
+#[test]
+fn test_less_equal_and_greater_equal() {
+    let expression = Parser::parse_expression(Lexer::lex("a + 1 <= b == c >= d").unwrap()).expect("Failed to parse");
+
+    let sum = build::binary(build::identifier("a"), "+", build::literal("1"));
+    let at_most = build::binary(sum, "<=", build::identifier("b"));
+    let at_least = build::binary(build::identifier("c"), ">=", build::identifier("d"));
+    assert_eq!(expression, build::binary(at_most, "==", at_least), "The parsed AST does not match the expected AST.");
+
+    let ast = Parser::parse(Lexer::lex("while (i <= n) { }").unwrap()).expect("Failed to parse");
+    let condition = build::binary(build::identifier("i"), "<=", build::identifier("n"));
+    assert_eq!(ast.get_root().get_children(), vec![build::while_loop(condition, vec![])]);
+}
+
 /// ---- Initialization Section ---- 
 
 /// This test ensures that the parser correctly handles the initialization of a boolean variable without an assigned value.
@@ -749,7 +789,7 @@ fn test_assignment_to_nested_parentheses_expression() {
 fn test_initialization_parsing_no_value() {
     let tokens = vec![
         Token::TBOOLEAN,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::SEMICOLON,
         Token::EOF,
     ];
@@ -780,9 +820,9 @@ fn test_initialization_parsing_no_value() {
 fn test_initialization_parsing_int() {
     let tokens = vec![
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::EQUAL,
-        Token::NUMBER(vec!['1']),
+        Token::NUMBER(Symbol::intern("1")),
         Token::SEMICOLON,
         Token::EOF,
     ];
@@ -818,9 +858,9 @@ fn test_initialization_parsing_int() {
 fn test_initialization_parsing_long() {
     let tokens = vec![
         Token::TLONG,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::EQUAL,
-        Token::NUMBER(vec!['1']),
+        Token::NUMBER(Symbol::intern("1")),
         Token::SEMICOLON,
         Token::EOF,
     ];
@@ -850,6 +890,196 @@ fn test_initialization_parsing_long() {
     assert_eq!(ast, expected_ast);
 }
 
+/// This test ensures that the parser combines multi-keyword type specifiers into a single data type.
+/// The input is `unsigned long long x = 1;`, and the expected AST holds a single `UnsignedLongLong` type node.
+#[test]
+fn test_initialization_parsing_unsigned_long_long() {
+    let tokens = vec![
+        Token::TUSIGN,
+        Token::TLONG,
+        Token::TLONG,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::EQUAL,
+        Token::NUMBER(Symbol::intern("1")),
+        Token::SEMICOLON,
+        Token::EOF,
+    ];
+    let ast: AST = Parser::parse(tokens).expect("Failed to parse");
+
+    let mut initialization_node: ASTNode = ASTNode::new(NodeType::Initialization);
+
+    let var_id_node: ASTNode = ASTNode::new(NodeType::Identifier("x".to_string()));
+    let type_node: ASTNode = ASTNode::new(NodeType::Type(DataType::UnsignedLongLong));
+
+    let mut variable_node: ASTNode = ASTNode::new(NodeType::Variable);
+    variable_node.add_child(var_id_node);
+    variable_node.add_child(type_node);
+
+    let value_node: ASTNode = ASTNode::new(NodeType::Literal("1".to_string()));
+    let mut assigned_value_node: ASTNode = ASTNode::new(NodeType::AssignedValue);
+    assigned_value_node.add_child(value_node);
+
+    initialization_node.add_child(variable_node);
+    initialization_node.add_child(assigned_value_node);
+
+    let mut top_level_expr: ASTNode = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(initialization_node);
+
+    let expected_ast: AST = AST::new(top_level_expr);
+
+    assert_eq!(ast, expected_ast);
+}
+
+/// This test ensures that the parser handles `signed char` as a single type in an initialization.
+/// The input is `signed char c = 1;`, and the expected AST holds a single `SignedChar` type node.
+#[test]
+fn test_initialization_parsing_signed_char() {
+    let tokens = vec![
+        Token::TSIGNINT,
+        Token::TCHAR,
+        Token::IDENTIFIER(Symbol::intern("c")),
+        Token::EQUAL,
+        Token::NUMBER(Symbol::intern("1")),
+        Token::SEMICOLON,
+        Token::EOF,
+    ];
+    let ast: AST = Parser::parse(tokens).expect("Failed to parse");
+
+    let mut initialization_node: ASTNode = ASTNode::new(NodeType::Initialization);
+
+    let var_id_node: ASTNode = ASTNode::new(NodeType::Identifier("c".to_string()));
+    let type_node: ASTNode = ASTNode::new(NodeType::Type(DataType::SignedChar));
+
+    let mut variable_node: ASTNode = ASTNode::new(NodeType::Variable);
+    variable_node.add_child(var_id_node);
+    variable_node.add_child(type_node);
+
+    let value_node: ASTNode = ASTNode::new(NodeType::Literal("1".to_string()));
+    let mut assigned_value_node: ASTNode = ASTNode::new(NodeType::AssignedValue);
+    assigned_value_node.add_child(value_node);
+
+    initialization_node.add_child(variable_node);
+    initialization_node.add_child(assigned_value_node);
+
+    let mut top_level_expr: ASTNode = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(initialization_node);
+
+    let expected_ast: AST = AST::new(top_level_expr);
+
+    assert_eq!(ast, expected_ast);
+}
+
+/// This test ensures that the parser records a `const` qualifier on the type of the declared variable, alongside
+/// `volatile` in either order. The inputs are `const int x = 5;` and `volatile const int x = 5;`, while
+/// `const int f() {}` is rejected since only variables are `const`.
+#[test]
+fn test_initialization_parsing_const() {
+    let expected = |qualifiers: &[&str]| {
+        let mut type_node = ASTNode::new(NodeType::Type(DataType::Integer));
+        for qualifier in qualifiers {
+            type_node.add_child(ASTNode::new(NodeType::Qualifier(qualifier.to_string())));
+        }
+        let mut variable_node = ASTNode::new(NodeType::Variable);
+        variable_node.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+        variable_node.add_child(type_node);
+        let mut assigned_value_node = ASTNode::new(NodeType::AssignedValue);
+        assigned_value_node.add_child(ASTNode::new(NodeType::Literal("5".to_string())));
+        let mut initialization_node = ASTNode::new(NodeType::Initialization);
+        initialization_node.add_child(variable_node);
+        initialization_node.add_child(assigned_value_node);
+        let mut top_level_expr = ASTNode::new(NodeType::TopLevelExpression);
+        top_level_expr.add_child(initialization_node);
+        AST::new(top_level_expr)
+    };
+
+    let ast = Parser::parse(Lexer::lex("const int x = 5;").unwrap()).expect("Failed to parse");
+    assert_eq!(ast, expected(&["const"]));
+
+    let ast = Parser::parse(Lexer::lex("volatile const int x = 5;").unwrap()).expect("Failed to parse");
+    assert_eq!(ast, expected(&["volatile", "const"]));
+
+    assert!(Parser::parse(Lexer::lex("const int f() {}").unwrap()).is_err());
+}
+
+/// This test ensures that the parser records a `volatile` qualifier on the type of the declared variable.
+/// The input is `volatile int x = 1;`, and the expected AST holds a `Qualifier(volatile)` child under the type node.
+#[test]
+fn test_initialization_parsing_volatile() {
+    let tokens = vec![
+        Token::VOLATILE,
+        Token::TINTEGER,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::EQUAL,
+        Token::NUMBER(Symbol::intern("1")),
+        Token::SEMICOLON,
+        Token::EOF,
+    ];
+    let ast: AST = Parser::parse(tokens).expect("Failed to parse");
+
+    let mut initialization_node: ASTNode = ASTNode::new(NodeType::Initialization);
+
+    let var_id_node: ASTNode = ASTNode::new(NodeType::Identifier("x".to_string()));
+    let mut type_node: ASTNode = ASTNode::new(NodeType::Type(DataType::Integer));
+    type_node.add_child(ASTNode::new(NodeType::Qualifier("volatile".to_string())));
+
+    let mut variable_node: ASTNode = ASTNode::new(NodeType::Variable);
+    variable_node.add_child(var_id_node);
+    variable_node.add_child(type_node);
+
+    let value_node: ASTNode = ASTNode::new(NodeType::Literal("1".to_string()));
+    let mut assigned_value_node: ASTNode = ASTNode::new(NodeType::AssignedValue);
+    assigned_value_node.add_child(value_node);
+
+    initialization_node.add_child(variable_node);
+    initialization_node.add_child(assigned_value_node);
+
+    let mut top_level_expr: ASTNode = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(initialization_node);
+
+    let expected_ast: AST = AST::new(top_level_expr);
+
+    assert_eq!(ast, expected_ast);
+}
+
+/// This test ensures that a negated numeric initializer is folded into a single negative literal.
+/// The input is `int x = -5;`, and the expected AST holds `Literal(-5)` rather than a unary expression.
+#[test]
+fn test_initialization_parsing_negative_literal() {
+    let tokens = vec![
+        Token::TINTEGER,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::EQUAL,
+        Token::DASH,
+        Token::NUMBER(Symbol::intern("5")),
+        Token::SEMICOLON,
+        Token::EOF,
+    ];
+    let ast: AST = Parser::parse(tokens).expect("Failed to parse");
+
+    let mut initialization_node: ASTNode = ASTNode::new(NodeType::Initialization);
+
+    let var_id_node: ASTNode = ASTNode::new(NodeType::Identifier("x".to_string()));
+    let type_node: ASTNode = ASTNode::new(NodeType::Type(DataType::Integer));
+
+    let mut variable_node: ASTNode = ASTNode::new(NodeType::Variable);
+    variable_node.add_child(var_id_node);
+    variable_node.add_child(type_node);
+
+    let value_node: ASTNode = ASTNode::new(NodeType::Literal("-5".to_string()));
+    let mut assigned_value_node: ASTNode = ASTNode::new(NodeType::AssignedValue);
+    assigned_value_node.add_child(value_node);
+
+    initialization_node.add_child(variable_node);
+    initialization_node.add_child(assigned_value_node);
+
+    let mut top_level_expr: ASTNode = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(initialization_node);
+
+    let expected_ast: AST = AST::new(top_level_expr);
+
+    assert_eq!(ast, expected_ast);
+}
+
 /// ---- Struct Section ----
 
 /// This test ensures that the parser correctly handles the declaration of a struct without any fields.
@@ -858,7 +1088,7 @@ fn test_initialization_parsing_long() {
 fn test_struct_declaration_empty() {
     let tokens: Vec<Token> = vec![
         Token::STRUCT,
-        Token::IDENTIFIER(vec!['M', 'y', 'S', 't', 'r', 'u', 'c', 't']),
+        Token::IDENTIFIER(Symbol::intern("MyStruct")),
         Token::LBRACE,
         Token::RBRACE,
         Token::SEMICOLON,
@@ -885,13 +1115,13 @@ fn test_struct_declaration_empty() {
 fn test_struct_declaration_with_int_fields() {
     let tokens: Vec<Token> = vec![
         Token::STRUCT,
-        Token::IDENTIFIER(vec!['M', 'y', 'S', 't', 'r', 'u', 'c', 't']),
+        Token::IDENTIFIER(Symbol::intern("MyStruct")),
         Token::LBRACE,
-        Token::IDENTIFIER(vec!['a']),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::COLON,
         Token::TINTEGER,
         Token::COMMA,
-        Token::IDENTIFIER(vec!['b']),
+        Token::IDENTIFIER(Symbol::intern("b")),
         Token::COLON,
         Token::TINTEGER,
         Token::RBRACE,
@@ -930,13 +1160,13 @@ fn test_struct_declaration_with_int_fields() {
 fn test_struct_declaration_with_char_fields() {
     let tokens: Vec<Token> = vec![
         Token::STRUCT,
-        Token::IDENTIFIER(vec!['M', 'y', 'S', 't', 'r', 'u', 'c', 't']),
+        Token::IDENTIFIER(Symbol::intern("MyStruct")),
         Token::LBRACE,
-        Token::IDENTIFIER(vec!['a']),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::COLON,
         Token::TCHAR,
         Token::COMMA,
-        Token::IDENTIFIER(vec!['b']),
+        Token::IDENTIFIER(Symbol::intern("b")),
         Token::COLON,
         Token::TCHAR,
         Token::RBRACE,
@@ -969,6 +1199,72 @@ fn test_struct_declaration_with_char_fields() {
     assert_eq!(ast, expected_ast, "The parsed AST does not match the expected AST.");
 }
 
+/// This test ensures that the parser correctly handles anonymous struct and union members, which may be nested.
+/// The input is `struct Value { tag: int, union { i: int, struct { x: float, y: float } } };`, and the expected AST has
+/// an unnamed `UnionDeclaration` holding a field and an unnamed `StructDeclaration`.
+#[test]
+fn test_struct_declaration_with_anonymous_members() {
+    let tokens: Vec<Token> = vec![
+        Token::STRUCT,
+        Token::IDENTIFIER(Symbol::intern("Value")),
+        Token::LBRACE,
+        Token::IDENTIFIER(Symbol::intern("tag")),
+        Token::COLON,
+        Token::TINTEGER,
+        Token::COMMA,
+        Token::UNION,
+        Token::LBRACE,
+        Token::IDENTIFIER(Symbol::intern("i")),
+        Token::COLON,
+        Token::TINTEGER,
+        Token::COMMA,
+        Token::STRUCT,
+        Token::LBRACE,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::COLON,
+        Token::TFLOAT,
+        Token::COMMA,
+        Token::IDENTIFIER(Symbol::intern("y")),
+        Token::COLON,
+        Token::TFLOAT,
+        Token::RBRACE,
+        Token::RBRACE,
+        Token::RBRACE,
+        Token::SEMICOLON,
+    ];
+
+    let result = Parser::parse(tokens);
+    assert!(result.is_ok(), "Parser should successfully parse the struct declaration without errors.");
+    let ast = result.expect("Failed to parse");
+
+    let field = |name: &str, data_type: DataType| {
+        let mut field = ASTNode::new(NodeType::Field);
+        field.add_child(ASTNode::new(NodeType::Literal(name.to_string())));
+        field.add_child(ASTNode::new(NodeType::Type(data_type)));
+        field
+    };
+
+    let mut inner_struct = ASTNode::new(NodeType::StructDeclaration);
+    inner_struct.add_child(field("x", DataType::Float));
+    inner_struct.add_child(field("y", DataType::Float));
+
+    let mut union_node = ASTNode::new(NodeType::UnionDeclaration);
+    union_node.add_child(field("i", DataType::Integer));
+    union_node.add_child(inner_struct);
+
+    let mut struct_node = ASTNode::new(NodeType::StructDeclaration);
+    struct_node.add_child(ASTNode::new(NodeType::Identifier("Value".to_string())));
+    struct_node.add_child(field("tag", DataType::Integer));
+    struct_node.add_child(union_node);
+
+    let mut top_level_expr = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(struct_node);
+
+    let expected_ast: AST = AST::new(top_level_expr);
+
+    assert_eq!(ast, expected_ast, "The parsed AST does not match the expected AST.");
+}
+
 /// ---- Enum Section ----
 
 /// This test checks the parser's ability to correctly parse an empty enum declaration.
@@ -978,7 +1274,7 @@ fn test_struct_declaration_with_char_fields() {
 fn test_enum_declaration_empty() {
     let tokens: Vec<Token> = vec![
         Token::ENUM,
-        Token::IDENTIFIER(vec!['M', 'y', 'E', 'n', 'u', 'm']),
+        Token::IDENTIFIER(Symbol::intern("MyEnum")),
         Token::LBRACE,
         Token::RBRACE,
         Token::SEMICOLON,
@@ -1006,13 +1302,13 @@ fn test_enum_declaration_empty() {
 fn test_enum_declaration_with_variants() {
     let tokens: Vec<Token> = vec![
         Token::ENUM,
-        Token::IDENTIFIER(vec!['C', 'o', 'l', 'o', 'r']),
+        Token::IDENTIFIER(Symbol::intern("Color")),
         Token::LBRACE,
-        Token::IDENTIFIER(vec!['R', 'e', 'd']),
+        Token::IDENTIFIER(Symbol::intern("Red")),
         Token::COMMA,
-        Token::IDENTIFIER(vec!['G', 'r', 'e', 'e', 'n']),
+        Token::IDENTIFIER(Symbol::intern("Green")),
         Token::COMMA,
-        Token::IDENTIFIER(vec!['B', 'l', 'u', 'e']),
+        Token::IDENTIFIER(Symbol::intern("Blue")),
         Token::RBRACE,
         Token::SEMICOLON,
     ];
@@ -1045,6 +1341,53 @@ fn test_enum_declaration_with_variants() {
     assert_eq!(ast, expected_ast, "The parsed AST does not match the expected AST.");
 }
 
+/// This test checks that an enum declaration can choose its underlying type.
+/// The input tokens represent `enum Color : char { Red, Green };`, whose AST holds the type after the enum's name,
+/// and `enum Color : float { Red };`, which is rejected since the underlying type must be an integer type.
+#[test]
+fn test_enum_declaration_with_underlying_type() {
+    let tokens: Vec<Token> = vec![
+        Token::ENUM,
+        Token::IDENTIFIER(Symbol::intern("Color")),
+        Token::COLON,
+        Token::TCHAR,
+        Token::LBRACE,
+        Token::IDENTIFIER(Symbol::intern("Red")),
+        Token::COMMA,
+        Token::IDENTIFIER(Symbol::intern("Green")),
+        Token::RBRACE,
+        Token::SEMICOLON,
+    ];
+
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+
+    let mut enum_node = ASTNode::new(NodeType::EnumDeclaration);
+    enum_node.add_child(ASTNode::new(NodeType::Identifier("Color".to_string())));
+    enum_node.add_child(ASTNode::new(NodeType::Type(DataType::Char)));
+    for name in ["Red", "Green"] {
+        let mut variant = ASTNode::new(NodeType::Variant);
+        variant.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+        enum_node.add_child(variant);
+    }
+
+    let mut top_level_expr = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(enum_node);
+    assert_eq!(ast, AST::new(top_level_expr), "The parsed AST does not match the expected AST.");
+
+    let tokens: Vec<Token> = vec![
+        Token::ENUM,
+        Token::IDENTIFIER(Symbol::intern("Color")),
+        Token::COLON,
+        Token::TFLOAT,
+        Token::LBRACE,
+        Token::IDENTIFIER(Symbol::intern("Red")),
+        Token::RBRACE,
+        Token::SEMICOLON,
+    ];
+
+    assert!(Parser::parse(tokens).is_err(), "A float cannot be the underlying type of an enum.");
+}
+
 /// ---- Function Section ----
 
 /// This test checks the parser's ability to correctly parse a simple function declaration without parameters.
@@ -1053,7 +1396,7 @@ fn test_enum_declaration_with_variants() {
 fn test_single_function_declaration() {
     let tokens: Vec<Token> = vec![
         Token::TVOID,
-        Token::IDENTIFIER(vec!['m', 'y', '_', 'f', 'u', 'n', 'c']),
+        Token::IDENTIFIER(Symbol::intern("my_func")),
         Token::LPAREN,
         Token::RPAREN,
         Token::LBRACKET,
@@ -1090,13 +1433,13 @@ fn test_single_function_declaration() {
 fn test_function_with_parameters_and_return_type() {
     let tokens: Vec<Token> = vec![
         Token::TBOOLEAN,
-        Token::IDENTIFIER(vec!['c', 'a', 'l', 'c', 'u', 'l', 'a', 't', 'e']),
+        Token::IDENTIFIER(Symbol::intern("calculate")),
         Token::LPAREN,
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::COMMA,
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['y']),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::RBRACKET,
@@ -1134,6 +1477,79 @@ fn test_function_with_parameters_and_return_type() {
     assert_eq!(ast, expected_ast);
 }
 
+/// This test checks that a function declared without a body, as in `int square(int x);`, is parsed as a prototype
+/// with the function's name, parameters, and return type.
+#[test]
+fn test_function_prototype() {
+    let tokens: Vec<Token> = vec![
+        Token::TINTEGER,
+        Token::IDENTIFIER(Symbol::intern("square")),
+        Token::LPAREN,
+        Token::TINTEGER,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::RPAREN,
+        Token::SEMICOLON,
+        Token::EOF,
+    ];
+    let ast: AST = Parser::parse(tokens).expect("Failed to parse");
+
+    let mut prototype_node = ASTNode::new(NodeType::FunctionPrototype);
+    prototype_node.add_child(ASTNode::new(NodeType::Identifier("square".to_string())));
+    let mut parameter_node = ASTNode::new(NodeType::Parameter);
+    parameter_node.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    parameter_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    prototype_node.add_child(parameter_node);
+    prototype_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+
+    let mut top_level_expr = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(prototype_node);
+
+    assert_eq!(ast, AST::new(top_level_expr));
+}
+
+/// This test checks that a trailing `...`, as in `int log(int level, ...);`, marks a function as variadic after its
+/// parameters, and that `...` needs a named parameter before it.
+#[test]
+fn test_variadic_function_prototype() {
+    let tokens: Vec<Token> = vec![
+        Token::TINTEGER,
+        Token::IDENTIFIER(Symbol::intern("log")),
+        Token::LPAREN,
+        Token::TINTEGER,
+        Token::IDENTIFIER(Symbol::intern("level")),
+        Token::COMMA,
+        Token::ELLIPSIS,
+        Token::RPAREN,
+        Token::SEMICOLON,
+        Token::EOF,
+    ];
+    let ast: AST = Parser::parse(tokens).expect("Failed to parse");
+
+    let mut prototype_node = ASTNode::new(NodeType::FunctionPrototype);
+    prototype_node.add_child(ASTNode::new(NodeType::Identifier("log".to_string())));
+    let mut parameter_node = ASTNode::new(NodeType::Parameter);
+    parameter_node.add_child(ASTNode::new(NodeType::Identifier("level".to_string())));
+    parameter_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    prototype_node.add_child(parameter_node);
+    prototype_node.add_child(ASTNode::new(NodeType::Variadic));
+    prototype_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+
+    let mut top_level_expr = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(prototype_node);
+    assert_eq!(ast, AST::new(top_level_expr));
+
+    let tokens: Vec<Token> = vec![
+        Token::TINTEGER,
+        Token::IDENTIFIER(Symbol::intern("log")),
+        Token::LPAREN,
+        Token::ELLIPSIS,
+        Token::RPAREN,
+        Token::SEMICOLON,
+        Token::EOF,
+    ];
+    assert!(Parser::parse(tokens).is_err());
+}
+
 /// This test checks the parser's ability to correctly parse a function with a body containing variable initialization.
 /// The input tokens represent `void test() { int x = 1; }` and the expected AST should reflect this structure with the
 /// initialization of variable `x` inside the function body.
@@ -1141,15 +1557,15 @@ fn test_function_with_parameters_and_return_type() {
 fn test_function_with_body() {
     let tokens: Vec<Token> = vec![
         Token::TVOID,
-        Token::IDENTIFIER(vec!['t', 'e', 's', 't']),
+        Token::IDENTIFIER(Symbol::intern("test")),
         Token::LPAREN,
         Token::RPAREN,
         Token::LBRACKET,
         
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::EQUAL,
-        Token::NUMBER(vec!['1']),
+        Token::NUMBER(Symbol::intern("1")),
         Token::SEMICOLON,
         
         Token::RBRACKET,
@@ -1201,28 +1617,28 @@ fn test_function_with_body() {
 fn test_function_with_if_else_statement() {
     let tokens: Vec<Token> = vec![
         Token::TBOOLEAN,
-        Token::IDENTIFIER(vec!['f', 'o', 'o']),
+        Token::IDENTIFIER(Symbol::intern("foo")),
         Token::LPAREN,
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['a']),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::COMMA,
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['b']),
+        Token::IDENTIFIER(Symbol::intern("b")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::IF,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::RETURN,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::SEMICOLON,
         Token::RBRACKET,
         Token::ELSE,
         Token::LBRACKET,
         Token::RETURN,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::SEMICOLON,
         Token::RBRACKET,
         Token::RBRACKET,
@@ -1288,31 +1704,182 @@ fn test_function_with_if_else_statement() {
     assert_eq!(ast, expected_ast, "The parsed AST does not match the expected AST.");
 }
 
-/// ---- Control Flow Section ----
-
-
-/// This test checks the parser's ability to correctly parse a for-loop statement.
-/// The input tokens represent `for (x = 0; x < 1; x = x + 1) { break; }` and the expected AST should
-/// reflect this structure including the initialization, condition, increment, and body of the loop.
+/// This test ensures that the parser handles a function pointer declaration initialized with the address of a function.
+/// The input is `int (*fp)(int, int) = &add;`, and the expected AST holds a `Function` type whose children are the
+/// return type followed by the parameter types.
 #[test]
-fn test_for_loop_parsing() {
-
-    let tokens: Vec<Token> = vec![
+fn test_function_pointer_initialization() {
+    let tokens = vec![
+        Token::TINTEGER,
+        Token::LPAREN,
+        Token::ASTERISK,
+        Token::IDENTIFIER(Symbol::intern("fp")),
+        Token::RPAREN,
+        Token::LPAREN,
+        Token::TINTEGER,
+        Token::COMMA,
+        Token::TINTEGER,
+        Token::RPAREN,
+        Token::EQUAL,
+        Token::AMPERSAND,
+        Token::IDENTIFIER(Symbol::intern("add")),
+        Token::SEMICOLON,
+        Token::EOF,
+    ];
+    let ast: AST = Parser::parse(tokens).expect("Failed to parse");
+
+    let mut function_type_node: ASTNode = ASTNode::new(NodeType::Type(DataType::Function));
+    function_type_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    function_type_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    function_type_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+
+    let mut variable_node: ASTNode = ASTNode::new(NodeType::Variable);
+    variable_node.add_child(ASTNode::new(NodeType::Identifier("fp".to_string())));
+    variable_node.add_child(function_type_node);
+
+    let mut address_of_node: ASTNode = ASTNode::new(NodeType::UnaryExpression);
+    address_of_node.add_child(ASTNode::new(NodeType::Operator("&".to_string())));
+    address_of_node.add_child(ASTNode::new(NodeType::Identifier("add".to_string())));
+
+    let mut assigned_value_node: ASTNode = ASTNode::new(NodeType::AssignedValue);
+    assigned_value_node.add_child(address_of_node);
+
+    let mut initialization_node: ASTNode = ASTNode::new(NodeType::Initialization);
+    initialization_node.add_child(variable_node);
+    initialization_node.add_child(assigned_value_node);
+
+    let mut top_level_expr: ASTNode = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(initialization_node);
+
+    let expected_ast: AST = AST::new(top_level_expr);
+
+    assert_eq!(ast, expected_ast);
+}
+
+/// This test ensures that the parser handles calls inside expressions, including calling the result of a parenthesized expression.
+/// The input is `fp(1, 2) + (fp)(3)`, and the expected AST holds a binary expression of two `FunctionCall` nodes.
+#[test]
+fn test_function_call_expression() {
+    let tokens = vec![
+        Token::IDENTIFIER(Symbol::intern("fp")),
+        Token::LPAREN,
+        Token::NUMBER(Symbol::intern("1")),
+        Token::COMMA,
+        Token::NUMBER(Symbol::intern("2")),
+        Token::RPAREN,
+        Token::PLUS,
+        Token::LPAREN,
+        Token::IDENTIFIER(Symbol::intern("fp")),
+        Token::RPAREN,
+        Token::LPAREN,
+        Token::NUMBER(Symbol::intern("3")),
+        Token::RPAREN,
+        Token::SEMICOLON,
+        Token::EOF,
+    ];
+    let ast: AST = Parser::parse(tokens).expect("Failed to parse");
+
+    let mut left_call: ASTNode = ASTNode::new(NodeType::FunctionCall);
+    left_call.add_child(ASTNode::new(NodeType::Identifier("fp".to_string())));
+    left_call.add_child(ASTNode::new(NodeType::Literal("1".to_string())));
+    left_call.add_child(ASTNode::new(NodeType::Literal("2".to_string())));
+
+    let mut right_call: ASTNode = ASTNode::new(NodeType::FunctionCall);
+    right_call.add_child(ASTNode::new(NodeType::Identifier("fp".to_string())));
+    right_call.add_child(ASTNode::new(NodeType::Literal("3".to_string())));
+
+    let mut binary_expr: ASTNode = ASTNode::new(NodeType::BinaryExpression);
+    binary_expr.add_child(left_call);
+    binary_expr.add_child(ASTNode::new(NodeType::Operator("+".to_string())));
+    binary_expr.add_child(right_call);
+
+    let mut top_level_expr: ASTNode = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(binary_expr);
+
+    let expected_ast: AST = AST::new(top_level_expr);
+
+    assert_eq!(ast, expected_ast);
+}
+
+/// This test ensures that a call through a dereferenced function pointer parses wherever a value is expected.
+/// The input declares `int r = (*fp)(1, 2);` and assigns `x = (*fp)(1, 2);`, and both values are expected to be a
+/// `FunctionCall` whose callee is a `*` `UnaryExpression` on `fp`.
+#[test]
+fn test_dereferenced_function_pointer_calls() {
+    let source = "int add(int a, int b) { return a; }\nint main() {\n    int (*fp)(int, int) = &add;\n    \
+                  int r = (*fp)(1, 2);\n    int x = 0;\n    x = (*fp)(1, 2);\n    return r;\n}\n";
+    let ast: AST = Parser::parse(Lexer::lex(source).unwrap()).expect("Failed to parse");
+
+    let mut expected_call: ASTNode = ASTNode::new(NodeType::FunctionCall);
+    expected_call.add_child(build::unary("*", build::identifier("fp")));
+    expected_call.add_child(build::literal("1"));
+    expected_call.add_child(build::literal("2"));
+
+    let mut calls = Vec::new();
+    let mut stack = vec![ast.get_root()];
+    while let Some(node) = stack.pop() {
+        match node.get_node_type() {
+            NodeType::AssignedValue | NodeType::Assignment => calls.push(node.get_children().last().cloned()),
+            _ => {}
+        }
+        stack.extend(node.get_children());
+    }
+    let calls: Vec<ASTNode> = calls.into_iter().flatten().filter(|value| value.get_node_type() == NodeType::FunctionCall).collect();
+    assert_eq!(calls, vec![expected_call.clone(), expected_call]);
+}
+
+/// This test ensures that string and character literals are accepted as call arguments.
+/// The input is `printf("%c", 'a');`, and the expected AST holds a `FunctionCall` with two literal arguments.
+#[test]
+fn test_function_call_with_literal_arguments() {
+    let tokens = vec![
+        Token::IDENTIFIER(Symbol::intern("printf")),
+        Token::LPAREN,
+        Token::STRINGLITERAL(ByteString::from("%c")),
+        Token::COMMA,
+        Token::CHAR('a'),
+        Token::RPAREN,
+        Token::SEMICOLON,
+        Token::EOF,
+    ];
+    let ast: AST = Parser::parse(tokens).expect("Failed to parse");
+
+    let mut call: ASTNode = ASTNode::new(NodeType::FunctionCall);
+    call.add_child(ASTNode::new(NodeType::Identifier("printf".to_string())));
+    call.add_child(ASTNode::new(NodeType::Literal("\"%c\"".to_string())));
+    call.add_child(ASTNode::new(NodeType::Literal("'a'".to_string())));
+
+    let mut top_level_expr: ASTNode = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(call);
+
+    assert_eq!(ast, AST::new(top_level_expr));
+}
+
+/// ---- Control Flow Section ----
+
+
+/// This test checks the parser's ability to correctly parse a for-loop statement.
+/// The input tokens represent `for (x = 0; x < 1; x = x + 1) { break; }` and the expected AST should
+/// reflect this structure including the initialization, condition, increment, and body of the loop.
+#[test]
+fn test_for_loop_parsing() {
+
+    let tokens: Vec<Token> = vec![
         Token::FOR,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('x')), 
+        Token::IDENTIFIER(Symbol::intern("x")), 
         Token::EQUAL,
-        Token::NUMBER(vec!('0')),
+        Token::NUMBER(Symbol::intern("0")),
         Token::SEMICOLON,
-        Token::IDENTIFIER(vec!('x')), 
+        Token::IDENTIFIER(Symbol::intern("x")), 
         Token::LESSTHAN,
-        Token::NUMBER(vec!('1')),
+        Token::NUMBER(Symbol::intern("1")),
         Token::SEMICOLON,
-        Token::IDENTIFIER(vec!('x')), 
+        Token::IDENTIFIER(Symbol::intern("x")), 
         Token::EQUAL,
-        Token::IDENTIFIER(vec!('x')), 
+        Token::IDENTIFIER(Symbol::intern("x")), 
         Token::PLUS,
-        Token::NUMBER(vec!('1')),
+        Token::NUMBER(Symbol::intern("1")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::BREAK,
@@ -1367,6 +1934,64 @@ fn test_for_loop_parsing() {
     assert_eq!(ast, expected_ast);
 }
 
+/// Builds the `ForLoop` node of a loop with the given header clauses, any of which may be empty, and an empty body.
+fn for_loop(initializer: Option<ASTNode>, condition: Option<ASTNode>, increment: Option<ASTNode>) -> ASTNode {
+    let mut for_loop_node = ASTNode::new(NodeType::ForLoop);
+    for (kind, clause) in [(NodeType::LoopInitializer, initializer), (NodeType::Condition, condition), (NodeType::LoopIncrement, increment)] {
+        let mut clause_node = ASTNode::new(kind);
+        clause_node.add_children(clause.into_iter().collect());
+        for_loop_node.add_child(clause_node);
+    }
+    for_loop_node.add_child(ASTNode::new(NodeType::BlockExpression));
+    for_loop_node
+}
+
+/// This test checks that the header of a for loop accepts a declaration, any comparison, and `++`/`--` in
+/// either position, as in `for (int i = 0; i <= n; i++) {}`.
+#[test]
+fn test_for_loop_general_header() {
+    let ast = Parser::parse(Lexer::lex("for (int i = 0; i <= n; i++) {}").unwrap()).expect("Failed to parse");
+    let expected = for_loop(
+        Some(build::declare("i", DataType::Integer, Some(build::literal("0")))),
+        Some(build::binary(build::identifier("i"), "<=", build::identifier("n"))),
+        Some(build::unary("++", build::identifier("i"))),
+    );
+    assert_eq!(ast.get_root().get_children(), vec![expected]);
+
+    let ast = Parser::parse(Lexer::lex("for (i = n; i > 0 && f(i) != 0; --i) {}").unwrap()).expect("Failed to parse");
+    let condition = build::binary(
+        build::binary(build::identifier("i"), ">", build::literal("0")),
+        "&&",
+        build::binary(build::call("f", vec![build::identifier("i")]), "!=", build::literal("0")),
+    );
+    let expected = for_loop(
+        Some(build::assign("i", build::identifier("n"))),
+        Some(condition),
+        Some(build::unary("--", build::identifier("i"))),
+    );
+    assert_eq!(ast.get_root().get_children(), vec![expected]);
+
+    let ast = Parser::parse(Lexer::lex("for (i = 0; i < n; i = i * 2) {}").unwrap()).expect("Failed to parse");
+    let increment = build::assign("i", build::binary(build::identifier("i"), "*", build::literal("2")));
+    assert_eq!(ast.get_root().get_children()[0].get_children()[2].get_children(), vec![increment]);
+}
+
+/// This test checks that each clause of a for loop header may be left empty, as in `for (;;) {}`.
+#[test]
+fn test_for_loop_empty_clauses() {
+    let ast = Parser::parse(Lexer::lex("for (;;) {}").unwrap()).expect("Failed to parse");
+    assert_eq!(ast.get_root().get_children(), vec![for_loop(None, None, None)]);
+
+    let ast = Parser::parse(Lexer::lex("for (i = 0;;) {}").unwrap()).expect("Failed to parse");
+    assert_eq!(ast.get_root().get_children(), vec![for_loop(Some(build::assign("i", build::literal("0"))), None, None)]);
+
+    let ast = Parser::parse(Lexer::lex("for (; x;) {}").unwrap()).expect("Failed to parse");
+    assert_eq!(ast.get_root().get_children(), vec![for_loop(None, Some(build::identifier("x")), None)]);
+
+    assert!(Parser::parse(Lexer::lex("for (;) {}").unwrap()).is_err());
+    assert!(Parser::parse(Lexer::lex("for (i = 0; i < n) {}").unwrap()).is_err());
+}
+
 /// This test checks the parser's ability to correctly parse a while-loop statement.
 /// The input tokens represent `while (x) { break; }` and the expected AST should reflect this structure,
 /// including the loop condition and body.
@@ -1375,7 +2000,7 @@ fn test_while_loop_parsing() {
     let tokens: Vec<Token> = vec![
         Token::WHILE,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('x')),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::BREAK,
@@ -1420,7 +2045,7 @@ fn test_do_while_loop_parsing() {
         Token::RBRACKET,
         Token::WHILE,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('x')),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::RPAREN,
         Token::SEMICOLON,
         Token::EOF,
@@ -1456,11 +2081,11 @@ fn test_if_statement_parsing() {
     let tokens: Vec<Token> = vec![
         Token::IF,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('x')),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::RETURN,
-        Token::IDENTIFIER(vec!('x')),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::SEMICOLON,
         Token::RBRACKET,
         Token::EOF,
@@ -1504,17 +2129,17 @@ fn test_if_statement_parsing_with_else() {
     let tokens: Vec<Token> = vec![
         Token::IF,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('x')),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::RETURN,
-        Token::IDENTIFIER(vec!('x')),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::SEMICOLON,
         Token::RBRACKET,
         Token::ELSE,
         Token::LBRACKET,
         Token::RETURN,
-        Token::IDENTIFIER(vec!('y')),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::SEMICOLON,
         Token::RBRACKET,
         Token::EOF,
@@ -1575,31 +2200,31 @@ fn test_switch_statement_parsing() {
     let tokens: Vec<Token> = vec![
         Token::SWITCH,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['y']),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::RPAREN,
         Token::LBRACKET,
         
         Token::CASE,
-        Token::NUMBER(vec!['1']),
+        Token::NUMBER(Symbol::intern("1")),
         Token::COLON,
         
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::EQUAL,
-        Token::NUMBER(vec!['6']),
+        Token::NUMBER(Symbol::intern("6")),
         Token::SEMICOLON,
         
         Token::BREAK,
         Token::SEMICOLON,
         
         Token::CASE,
-        Token::NUMBER(vec!['2']),
+        Token::NUMBER(Symbol::intern("2")),
         Token::COLON,
         
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::EQUAL,
-        Token::NUMBER(vec!['7']),
+        Token::NUMBER(Symbol::intern("7")),
         Token::SEMICOLON,
         
         Token::BREAK,
@@ -1609,9 +2234,9 @@ fn test_switch_statement_parsing() {
         Token::COLON,
         
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::EQUAL,
-        Token::NUMBER(vec!['8']),
+        Token::NUMBER(Symbol::intern("8")),
         Token::SEMICOLON,
         
         Token::BREAK,
@@ -1715,28 +2340,28 @@ fn test_switch_statement_parsing_without_break_statements() {
     let tokens: Vec<Token> = vec![
         Token::SWITCH,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['y']),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::RPAREN,
         Token::LBRACKET,
         
         Token::CASE,
-        Token::NUMBER(vec!['1']),
+        Token::NUMBER(Symbol::intern("1")),
         Token::COLON,
         
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::EQUAL,
-        Token::NUMBER(vec!['1']),
+        Token::NUMBER(Symbol::intern("1")),
         Token::SEMICOLON,
         
         Token::CASE,
-        Token::NUMBER(vec!['2']),
+        Token::NUMBER(Symbol::intern("2")),
         Token::COLON,
         
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::EQUAL,
-        Token::NUMBER(vec!['2']),
+        Token::NUMBER(Symbol::intern("2")),
         Token::SEMICOLON,
         
         Token::RBRACKET,
@@ -1805,13 +2430,62 @@ fn test_switch_statement_parsing_without_break_statements() {
     assert_eq!(ast, expected_ast, "The parsed AST does not match the expected AST.");
 }
 
+/// Test that the whole scrutinee expression of a switch statement is kept, not just its first operand.
+/// Tokens represent 'switch (x % 3) { case 1: break; }'.
+#[test]
+fn test_switch_statement_with_expression_scrutinee() {
+    let tokens: Vec<Token> = vec![
+        Token::SWITCH,
+        Token::LPAREN,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::PERCENT,
+        Token::NUMBER(Symbol::intern("3")),
+        Token::RPAREN,
+        Token::LBRACKET,
+        Token::CASE,
+        Token::NUMBER(Symbol::intern("1")),
+        Token::COLON,
+        Token::BREAK,
+        Token::SEMICOLON,
+        Token::RBRACKET,
+        Token::EOF,
+    ];
+
+    let ast: AST = Parser::parse(tokens).expect("Failed to parse");
+
+    let mut scrutinee = ASTNode::new(NodeType::BinaryExpression);
+    scrutinee.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    scrutinee.add_child(ASTNode::new(NodeType::Operator("%".to_string())));
+    scrutinee.add_child(ASTNode::new(NodeType::Literal("3".to_string())));
+
+    let mut case_block = ASTNode::new(NodeType::BlockExpression);
+    case_block.add_child(ASTNode::new(NodeType::Break));
+
+    let mut case_node = ASTNode::new(NodeType::Case);
+    case_node.add_child(ASTNode::new(NodeType::Literal("1".to_string())));
+    case_node.add_child(case_block);
+
+    let mut cases_block_node = ASTNode::new(NodeType::BlockExpression);
+    cases_block_node.add_child(case_node);
+
+    let mut switch_statement_node = ASTNode::new(NodeType::SwitchStatement);
+    switch_statement_node.add_child(scrutinee);
+    switch_statement_node.add_child(cases_block_node);
+
+    let mut top_level_expr = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(switch_statement_node);
+
+    assert_eq!(ast, AST::new(top_level_expr), "The switch scrutinee should be the full expression.");
+}
+
 /// Tests the abillity for an expression to be a child of a block expression. 
-/// Tokens represent a block expression containing an identifier.
+/// Tokens represent a block expression containing the expression statement 'A;'.
 #[test]
 fn test_block_with_expression() {
     let tokens: Vec<Token> = vec![
         Token::LBRACKET,
-        Token::IDENTIFIER(vec!['A']),
+        Token::IDENTIFIER(Symbol::intern("A")),
+        Token::SEMICOLON,
         Token::RBRACKET
     ];
 
@@ -1822,7 +2496,9 @@ fn test_block_with_expression() {
 
     let mut block_exp_node = ASTNode::new(NodeType::BlockExpression);
 
-    block_exp_node.add_child(ASTNode::new(NodeType::Identifier("A".to_string())));
+    let mut statement_node = ASTNode::new(NodeType::ExpressionStatement);
+    statement_node.add_child(ASTNode::new(NodeType::Identifier("A".to_string())));
+    block_exp_node.add_child(statement_node);
 
     let mut top_level_expr = ASTNode::new(NodeType::TopLevelExpression);
 
@@ -1839,7 +2515,7 @@ fn test_block_with_expression() {
 fn test_return_expression() {
     let tokens: Vec<Token> = vec![
         Token::RETURN,
-        Token::IDENTIFIER(vec!['A']),
+        Token::IDENTIFIER(Symbol::intern("A")),
         Token::SEMICOLON,
     ];
 
@@ -1863,4 +2539,791 @@ fn test_return_expression() {
     let expected_ast: AST = AST::new(top_level_expr);
 
     assert_eq!(ast, expected_ast, "The parsed AST does not match the expected AST.");
-}
\ No newline at end of file
+}
+
+/// Test that a struct variable can be declared and passed to a function taking the struct by value.
+/// Tokens represent 'void show(struct P p) { } struct P q; show(q);'.
+#[test]
+fn test_struct_value_parameter() {
+    let tokens: Vec<Token> = vec![
+        Token::TVOID,
+        Token::IDENTIFIER(Symbol::intern("show")),
+        Token::LPAREN,
+        Token::STRUCT,
+        Token::IDENTIFIER(Symbol::intern("P")),
+        Token::IDENTIFIER(Symbol::intern("p")),
+        Token::RPAREN,
+        Token::LBRACKET,
+        Token::RBRACKET,
+        Token::STRUCT,
+        Token::IDENTIFIER(Symbol::intern("P")),
+        Token::IDENTIFIER(Symbol::intern("q")),
+        Token::SEMICOLON,
+        Token::IDENTIFIER(Symbol::intern("show")),
+        Token::LPAREN,
+        Token::IDENTIFIER(Symbol::intern("q")),
+        Token::RPAREN,
+        Token::SEMICOLON,
+        Token::EOF,
+    ];
+
+    let ast: AST = Parser::parse(tokens).expect("Failed to parse");
+
+    let struct_type = || {
+        let mut type_node = ASTNode::new(NodeType::Type(DataType::Struct));
+        type_node.add_child(ASTNode::new(NodeType::Identifier("P".to_string())));
+        type_node
+    };
+
+    let mut param_node = ASTNode::new(NodeType::Parameter);
+    param_node.add_child(ASTNode::new(NodeType::Identifier("p".to_string())));
+    param_node.add_child(struct_type());
+
+    let mut function_node = ASTNode::new(NodeType::FunctionDeclaration);
+    function_node.add_child(ASTNode::new(NodeType::Identifier("show".to_string())));
+    function_node.add_child(param_node);
+    function_node.add_child(ASTNode::new(NodeType::Type(DataType::Void)));
+    function_node.add_child(ASTNode::new(NodeType::BlockExpression));
+
+    let mut var_node = ASTNode::new(NodeType::Variable);
+    var_node.add_child(ASTNode::new(NodeType::Identifier("q".to_string())));
+    var_node.add_child(struct_type());
+
+    let mut init_node = ASTNode::new(NodeType::Initialization);
+    init_node.add_child(var_node);
+
+    let mut call_node = ASTNode::new(NodeType::FunctionCall);
+    call_node.add_child(ASTNode::new(NodeType::Identifier("show".to_string())));
+    call_node.add_child(ASTNode::new(NodeType::Identifier("q".to_string())));
+
+    let mut top_level_expr = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(function_node);
+    top_level_expr.add_child(init_node);
+    top_level_expr.add_child(call_node);
+
+    assert_eq!(ast, AST::new(top_level_expr));
+}
+
+/// ---- Language Feature Section ----
+
+/// Test that an `if` body without braces is parsed into the same tree as the braced body when braceless bodies are enabled.
+/// Tokens represent the statement 'if (x) return x;'.
+#[test]
+fn test_braceless_if_body() {
+    let braceless: Vec<Token> = vec![
+        Token::IF,
+        Token::LPAREN,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::RPAREN,
+        Token::RETURN,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::SEMICOLON,
+        Token::EOF,
+    ];
+    let braced: Vec<Token> = vec![
+        Token::IF,
+        Token::LPAREN,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::RPAREN,
+        Token::LBRACKET,
+        Token::RETURN,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::SEMICOLON,
+        Token::RBRACKET,
+        Token::EOF,
+    ];
+
+    let ast = Parser::parse_with_config(braceless, LanguageConfig::extended()).expect("Failed to parse");
+    let expected_ast = Parser::parse(braced).expect("Failed to parse");
+
+    assert_eq!(ast, expected_ast);
+}
+
+/// Test that a `typedef` declares an alias which later declarations can use as a type.
+/// Tokens represent 'typedef int count; count x = 1;'.
+#[test]
+fn test_typedef_alias() {
+    let tokens: Vec<Token> = vec![
+        Token::TYPEDEF,
+        Token::TINTEGER,
+        Token::IDENTIFIER(Symbol::intern("count")),
+        Token::SEMICOLON,
+        Token::IDENTIFIER(Symbol::intern("count")),
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::EQUAL,
+        Token::NUMBER(Symbol::intern("1")),
+        Token::SEMICOLON,
+        Token::EOF,
+    ];
+    let ast = Parser::parse_with_config(tokens, LanguageConfig::extended()).expect("Failed to parse");
+
+    let mut typedef_node = ASTNode::new(NodeType::TypeDefinition);
+    typedef_node.add_child(ASTNode::new(NodeType::Identifier("count".to_string())));
+    typedef_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+
+    let mut variable_node = ASTNode::new(NodeType::Variable);
+    variable_node.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    variable_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+
+    let mut assigned_value_node = ASTNode::new(NodeType::AssignedValue);
+    assigned_value_node.add_child(ASTNode::new(NodeType::Literal("1".to_string())));
+
+    let mut initialization_node = ASTNode::new(NodeType::Initialization);
+    initialization_node.add_child(variable_node);
+    initialization_node.add_child(assigned_value_node);
+
+    let mut top_level_expr = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(typedef_node);
+    top_level_expr.add_child(initialization_node);
+
+    assert_eq!(ast, AST::new(top_level_expr));
+}
+
+/// Test that attributes are attached to the declaration that follows them.
+/// Tokens represent '@packed @align(8) struct S [ ]'.
+#[test]
+fn test_attributes_on_struct() {
+    let tokens: Vec<Token> = vec![
+        Token::AT,
+        Token::IDENTIFIER(Symbol::intern("packed")),
+        Token::AT,
+        Token::IDENTIFIER(Symbol::intern("align")),
+        Token::LPAREN,
+        Token::NUMBER(Symbol::intern("8")),
+        Token::RPAREN,
+        Token::STRUCT,
+        Token::IDENTIFIER(Symbol::intern("S")),
+        Token::LBRACE,
+        Token::RBRACE,
+        Token::EOF,
+    ];
+    let ast = Parser::parse_with_config(tokens, LanguageConfig::extended()).expect("Failed to parse");
+
+    let mut align_node = ASTNode::new(NodeType::Attribute("align".to_string()));
+    align_node.add_child(ASTNode::new(NodeType::Literal("8".to_string())));
+
+    let mut struct_node = ASTNode::new(NodeType::StructDeclaration);
+    struct_node.add_child(ASTNode::new(NodeType::Identifier("S".to_string())));
+    struct_node.add_child(ASTNode::new(NodeType::Attribute("packed".to_string())));
+    struct_node.add_child(align_node);
+
+    let mut top_level_expr = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(struct_node);
+
+    assert_eq!(ast, AST::new(top_level_expr));
+}
+
+/// Test that a function declared inside a struct is parsed as a method of the struct when struct methods are enabled.
+/// Tokens represent 'struct S [ int get() { return 1; } ]'.
+#[test]
+fn test_struct_method() {
+    let method_tokens: Vec<Token> = vec![
+        Token::TINTEGER,
+        Token::IDENTIFIER(Symbol::intern("get")),
+        Token::LPAREN,
+        Token::RPAREN,
+        Token::LBRACKET,
+        Token::RETURN,
+        Token::NUMBER(Symbol::intern("1")),
+        Token::SEMICOLON,
+        Token::RBRACKET,
+    ];
+    let mut tokens: Vec<Token> = vec![
+        Token::STRUCT,
+        Token::IDENTIFIER(Symbol::intern("S")),
+        Token::LBRACE,
+    ];
+    tokens.extend(method_tokens.clone());
+    tokens.extend(vec![Token::RBRACE, Token::EOF]);
+
+    let ast = Parser::parse_with_config(tokens, LanguageConfig::extended()).expect("Failed to parse");
+    let method_node = Parser::parse(method_tokens).expect("Failed to parse").get_root().get_children()[0].clone();
+
+    let mut struct_node = ASTNode::new(NodeType::StructDeclaration);
+    struct_node.add_child(ASTNode::new(NodeType::Identifier("S".to_string())));
+    struct_node.add_child(method_node);
+
+    let mut top_level_expr = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(struct_node);
+
+    assert_eq!(ast, AST::new(top_level_expr));
+}
+
+/// Test that calls and other expressions followed by ';' are parsed as expression statements inside a block.
+/// Tokens represent '{ foo(x); x + 1; }'.
+#[test]
+fn test_expression_statements() {
+    let tokens: Vec<Token> = vec![
+        Token::LBRACKET,
+        Token::IDENTIFIER(Symbol::intern("foo")),
+        Token::LPAREN,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::RPAREN,
+        Token::SEMICOLON,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::PLUS,
+        Token::NUMBER(Symbol::intern("1")),
+        Token::SEMICOLON,
+        Token::RBRACKET,
+        Token::EOF,
+    ];
+
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+
+    let mut call_node = ASTNode::new(NodeType::FunctionCall);
+    call_node.add_child(ASTNode::new(NodeType::Identifier("foo".to_string())));
+    call_node.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    let mut call_statement = ASTNode::new(NodeType::ExpressionStatement);
+    call_statement.add_child(call_node);
+
+    let mut sum_node = ASTNode::new(NodeType::BinaryExpression);
+    sum_node.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    sum_node.add_child(ASTNode::new(NodeType::Operator("+".to_string())));
+    sum_node.add_child(ASTNode::new(NodeType::Literal("1".to_string())));
+    let mut sum_statement = ASTNode::new(NodeType::ExpressionStatement);
+    sum_statement.add_child(sum_node);
+
+    let mut block_node = ASTNode::new(NodeType::BlockExpression);
+    block_node.add_child(call_statement);
+    block_node.add_child(sum_statement);
+
+    let mut top_level_expr = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(block_node);
+
+    assert_eq!(ast, AST::new(top_level_expr));
+}
+
+/// Test that a string literal can initialize a variable, leaving its type to be checked by semantic analysis.
+/// Tokens represent 'int x = "str";'.
+#[test]
+fn test_string_initializer() {
+    let tokens: Vec<Token> = vec![
+        Token::TINTEGER,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::EQUAL,
+        Token::STRINGLITERAL(ByteString::from("str")),
+        Token::SEMICOLON,
+        Token::EOF,
+    ];
+
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+
+    let mut variable_node = ASTNode::new(NodeType::Variable);
+    variable_node.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    variable_node.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    let mut assigned_value_node = ASTNode::new(NodeType::AssignedValue);
+    assigned_value_node.add_child(ASTNode::new(NodeType::Literal("\"str\"".to_string())));
+    let mut initialization_node = ASTNode::new(NodeType::Initialization);
+    initialization_node.add_child(variable_node);
+    initialization_node.add_child(assigned_value_node);
+
+    let mut top_level_expr = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(initialization_node);
+
+    assert_eq!(ast, AST::new(top_level_expr));
+}
+
+/// Test that string and character literals can label the cases of a switch.
+/// Tokens represent 'switch (s) { case "go": break; case 'q': break; }'.
+#[test]
+fn test_switch_on_string_labels() {
+    let tokens = tokens![
+        SWITCH, LPAREN, ident("s"), RPAREN, LBRACKET,
+        CASE, string("go"), COLON, BREAK, SEMICOLON,
+        CASE, CHAR('q'), COLON, BREAK, SEMICOLON,
+        RBRACKET, EOF,
+    ];
+
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+
+    let mut cases_block_node = ASTNode::new(NodeType::BlockExpression);
+    for label in ["\"go\"", "'q'"] {
+        let mut case_block = ASTNode::new(NodeType::BlockExpression);
+        case_block.add_child(ASTNode::new(NodeType::Break));
+        let mut case_node = ASTNode::new(NodeType::Case);
+        case_node.add_child(ASTNode::new(NodeType::Literal(label.to_string())));
+        case_node.add_child(case_block);
+        cases_block_node.add_child(case_node);
+    }
+
+    let mut switch_statement_node = ASTNode::new(NodeType::SwitchStatement);
+    switch_statement_node.add_child(ASTNode::new(NodeType::Identifier("s".to_string())));
+    switch_statement_node.add_child(cases_block_node);
+
+    let mut top_level_expr = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(switch_statement_node);
+
+    assert_eq!(ast, AST::new(top_level_expr));
+}
+
+/// Test that a function built with `common::ast::build` is the same as the parsed function.
+/// Tokens represent 'bool pick(int a, int b) { if (a) { return a; } else { return b; } }'.
+#[test]
+fn test_built_function_matches_parsed() {
+    let tokens = tokens![
+        TBOOLEAN, ident("pick"), LPAREN, TINTEGER, ident("a"), COMMA, TINTEGER, ident("b"), RPAREN, LBRACKET,
+        IF, LPAREN, ident("a"), RPAREN, LBRACKET, RETURN, ident("a"), SEMICOLON, RBRACKET,
+        ELSE, LBRACKET, RETURN, ident("b"), SEMICOLON, RBRACKET,
+        RBRACKET, EOF,
+    ];
+
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+
+    let pick = build::function("pick", DataType::Boolean)
+        .param("a", DataType::Integer)
+        .param("b", DataType::Integer)
+        .body(vec![build::if_else(
+            build::identifier("a"),
+            vec![build::ret(Some(build::identifier("a")))],
+            Some(vec![build::ret(Some(build::identifier("b")))]),
+        )])
+        .build()
+        .expect("Failed to build");
+
+    assert_eq!(ast, build::program(vec![pick]).expect("Failed to build"));
+}
+
+/// Test that an `else if` chain is one flat `IfStatement`, with an `ElseIf` for each `else if` and the final `else`.
+/// Tokens represent 'if (a) { x = 1; } else if (b) { x = 2; } else if (c) { x = 3; } else { x = 4; }'.
+#[test]
+fn test_else_if_chain() {
+    let tokens = tokens![
+        IF, LPAREN, ident("a"), RPAREN, LBRACKET, ident("x"), EQUAL, num(1), SEMICOLON, RBRACKET,
+        ELSE, IF, LPAREN, ident("b"), RPAREN, LBRACKET, ident("x"), EQUAL, num(2), SEMICOLON, RBRACKET,
+        ELSE, IF, LPAREN, ident("c"), RPAREN, LBRACKET, ident("x"), EQUAL, num(3), SEMICOLON, RBRACKET,
+        ELSE, LBRACKET, ident("x"), EQUAL, num(4), SEMICOLON, RBRACKET,
+        EOF,
+    ];
+
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+
+    let assign = |value: &str| build::block(vec![build::assign("x", build::literal(value))]);
+    let else_if = |condition: &str, value: &str| {
+        let if_statement = build::if_else(build::identifier(condition), Vec::new(), None);
+        let mut else_if = ASTNode::new(NodeType::ElseIf);
+        else_if.set_children(vec![if_statement.get_children()[0].clone(), assign(value)]);
+        else_if
+    };
+    let mut outer = build::if_else(build::identifier("a"), Vec::new(), None);
+    outer.set_children(vec![
+        outer.get_children()[0].clone(),
+        assign("1"),
+        else_if("b", "2"),
+        else_if("c", "3"),
+        assign("4"),
+    ]);
+    assert_eq!(ast, build::program(vec![outer]).expect("The chain should be well-formed"));
+}
+
+/// Test that a call can be passed as an argument to another call.
+/// Tokens represent 'f(g(1), x);'.
+#[test]
+fn test_nested_call_arguments() {
+    let tokens = tokens![ident("f"), LPAREN, ident("g"), LPAREN, num(1), RPAREN, COMMA, ident("x"), RPAREN, SEMICOLON, EOF];
+
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+
+    let call = build::call("f", vec![build::call("g", vec![build::literal("1")]), build::identifier("x")]);
+    let mut top_level_expr = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(call);
+    assert_eq!(ast, AST::new(top_level_expr));
+}
+
+/// Test that a negated identifier can be the condition of a while loop.
+/// Tokens represent 'while (!done) { done = 1; }'.
+#[test]
+fn test_negated_while_condition() {
+    let tokens = tokens![
+        WHILE, LPAREN, EXCLAMATIONPOINT, ident("done"), RPAREN,
+        LBRACKET, ident("done"), EQUAL, num(1), SEMICOLON, RBRACKET,
+        EOF,
+    ];
+
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+
+    let while_loop = build::while_loop(build::unary("!", build::identifier("done")), vec![
+        build::assign("done", build::literal("1")),
+    ]);
+    let mut top_level_expr = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(while_loop);
+    assert_eq!(ast, AST::new(top_level_expr));
+}
+
+/// Test that the product of two variables can be assigned.
+/// Tokens represent 'x = a * b;'.
+#[test]
+fn test_assignment_to_variable_product() {
+    let tokens = tokens![ident("x"), EQUAL, ident("a"), ASTERISK, ident("b"), SEMICOLON, EOF];
+
+    let ast = Parser::parse(tokens).expect("Failed to parse");
+
+    let mut top_level_expr = ASTNode::new(NodeType::TopLevelExpression);
+    top_level_expr.add_child(build::assign("x", build::binary(build::identifier("a"), "*", build::identifier("b"))));
+    assert_eq!(ast, AST::new(top_level_expr));
+}
+
+/// Test that a lone expression parses to its node, and that tokens after it are an error.
+#[test]
+fn test_parse_expression_fragment() {
+    let expression = Parser::parse_expression(tokens![ident("a"), PLUS, ident("f"), LPAREN, num(1), RPAREN, EOF])
+        .expect("Failed to parse");
+    assert_eq!(expression, build::binary(build::identifier("a"), "+", build::call("f", vec![build::literal("1")])));
+
+    assert!(Parser::parse_expression(tokens![ident("a"), ident("b")]).is_err());
+}
+
+/// Test that member accesses bind tighter than binary and unary operators, and that chains of them and of calls
+/// apply left to right.
+#[test]
+fn test_member_access_expressions() {
+    let expression = Parser::parse_expression(tokens![
+        ident("point"), DOT, ident("x"), PLUS, ident("ptr"), POINTER, ident("y"), ASTERISK, num(2), EOF,
+    ]).expect("Failed to parse");
+    assert_eq!(expression, build::binary(
+        build::member(build::identifier("point"), ".", "x"),
+        "+",
+        build::binary(build::member(build::identifier("ptr"), "->", "y"), "*", build::literal("2")),
+    ));
+
+    let expression = Parser::parse_expression(tokens![ident("a"), DOT, ident("b"), DOT, ident("c"), EOF])
+        .expect("Failed to parse");
+    assert_eq!(expression, build::member(build::member(build::identifier("a"), ".", "b"), ".", "c"));
+
+    let expression = Parser::parse_expression(tokens![DASH, ident("f"), LPAREN, RPAREN, POINTER, ident("x"), EOF])
+        .expect("Failed to parse");
+    assert_eq!(expression, build::unary("-", build::member(build::call("f", Vec::new()), "->", "x")));
+
+    let expression = Parser::parse_expression(tokens![LPAREN, ident("p"), RPAREN, DOT, ident("x"), EOF])
+        .expect("Failed to parse");
+    assert_eq!(expression.get_node_type(), NodeType::MemberAccess);
+    assert!(build::validate(&expression).is_empty());
+
+    assert!(Parser::parse_expression(tokens![ident("p"), DOT, num(1), EOF]).is_err());
+}
+
+/// Test that the bitwise operators parse with C precedence: `&` above `^` above `|`, all below equality.
+#[test]
+fn test_bitwise_expressions() {
+    let expression = Parser::parse_expression(tokens![
+        ident("a"), AMPERSAND, TILDE, ident("b"), BAR, ident("c"), CARET, ident("d"), EQUALEQUAL, num(0), EOF,
+    ]).expect("Failed to parse");
+    assert_eq!(expression, build::binary(
+        build::binary(build::identifier("a"), "&", build::unary("~", build::identifier("b"))),
+        "|",
+        build::binary(build::identifier("c"), "^", build::binary(build::identifier("d"), "==", build::literal("0"))),
+    ));
+
+    let statement = Parser::parse_statement(tokens![ident("x"), EQUAL, TILDE, ident("mask"), SEMICOLON, EOF])
+        .expect("Failed to parse");
+    assert_eq!(statement, build::assign("x", build::unary("~", build::identifier("mask"))));
+}
+
+/// Test that shifts bind looser than additive operators and tighter than relational ones.
+#[test]
+fn test_shift_expressions() {
+    let expression = Parser::parse_expression(tokens![
+        num(1), LSHIFT, ident("n"), PLUS, num(1), LESSTHAN, ident("x"), RSHIFT, num(2), EOF,
+    ]).expect("Failed to parse");
+    assert_eq!(expression, build::binary(
+        build::binary(build::literal("1"), "<<", build::binary(build::identifier("n"), "+", build::literal("1"))),
+        "<",
+        build::binary(build::identifier("x"), ">>", build::literal("2")),
+    ));
+    assert!(build::validate(&expression).is_empty());
+
+    let expression = Parser::parse_expression(tokens![ident("a"), RSHIFT, num(1), LSHIFT, num(2), EOF])
+        .expect("Failed to parse");
+    assert_eq!(expression, build::binary(
+        build::binary(build::identifier("a"), ">>", build::literal("1")),
+        "<<",
+        build::literal("2"),
+    ));
+}
+
+/// Test that conditional expressions bind looser than binary operators and group to the right.
+#[test]
+fn test_ternary_expressions() {
+    let expression = Parser::parse_expression(tokens![
+        ident("a"), LESSTHAN, ident("b"), CTRUE, ident("a"), PLUS, num(1), COLON, ident("c"), CTRUE, num(2), COLON, num(3), EOF,
+    ]).expect("Failed to parse");
+    assert_eq!(expression, build::ternary(
+        build::binary(build::identifier("a"), "<", build::identifier("b")),
+        build::binary(build::identifier("a"), "+", build::literal("1")),
+        build::ternary(build::identifier("c"), build::literal("2"), build::literal("3")),
+    ));
+    assert!(build::validate(&expression).is_empty());
+
+    let statement = Parser::parse_statement(tokens![
+        ident("x"), EQUAL, ident("flag"), CTRUE, ident("y"), COLON, num(0), SEMICOLON, EOF,
+    ]).expect("Failed to parse");
+    assert_eq!(statement, build::assign("x", build::ternary(build::identifier("flag"), build::identifier("y"), build::literal("0"))));
+
+    let missing_colon = Parser::parse_expression(tokens![ident("a"), CTRUE, num(1), EOF]);
+    assert!(missing_colon.is_err());
+}
+
+/// Test that the value of a declaration may be a conditional or binary expression, not only a single operand.
+#[test]
+fn test_ternary_declaration_initializers() {
+    let statement = Parser::parse_statement(tokens![
+        TINTEGER, ident("x"), EQUAL, ident("a"), CTRUE, ident("b"), COLON, ident("c"), SEMICOLON, EOF,
+    ]).expect("Failed to parse");
+    assert_eq!(statement, build::declare(
+        "x",
+        DataType::Integer,
+        Some(build::ternary(build::identifier("a"), build::identifier("b"), build::identifier("c"))),
+    ));
+    assert!(build::validate(&statement).is_empty());
+
+    let statement = Parser::parse_statement(tokens![
+        TINTEGER, ident("y"), EQUAL, ident("a"), PLUS, num(1), LESSTHAN, ident("b"), CTRUE, num(1), COLON, DASH, num(1), SEMICOLON, EOF,
+    ]).expect("Failed to parse");
+    assert_eq!(statement, build::declare(
+        "y",
+        DataType::Integer,
+        Some(build::ternary(
+            build::binary(build::binary(build::identifier("a"), "+", build::literal("1")), "<", build::identifier("b")),
+            build::literal("1"),
+            build::unary("-", build::literal("1")),
+        )),
+    ));
+
+    let source = "int main() {\n    int a = 1;\n    int x = a ? 2 : 3;\n    return x;\n}\n";
+    let ast = Parser::parse(Lexer::lex(source).unwrap()).expect("Failed to parse");
+    let mut ternaries = 0;
+    let mut stack = vec![ast.get_root()];
+    while let Some(node) = stack.pop() {
+        if node.get_node_type() == NodeType::TernaryExpression {
+            ternaries += 1;
+        }
+        stack.extend(node.get_children());
+    }
+    assert_eq!(ternaries, 1);
+}
+
+/// Test that compound assignments parse to `CompoundAssignment` nodes whose value is a full expression.
+#[test]
+fn test_compound_assignments() {
+    let statement = Parser::parse_statement(tokens![ident("x"), DASHEQUAL, ident("y"), ASTERISK, num(2), SEMICOLON, EOF])
+        .expect("Failed to parse");
+    assert_eq!(statement, build::compound_assign("x", "-=", build::binary(build::identifier("y"), "*", build::literal("2"))));
+    assert!(build::validate(&statement).is_empty());
+
+    let statement = Parser::parse_statement(tokens![
+        WHILE, LPAREN, ident("n"), RPAREN, LBRACKET,
+        ident("total"), PLUSEQUAL, num(5), SEMICOLON,
+        ident("mask"), LSHIFTEQUAL, num(1), SEMICOLON,
+        RBRACKET, EOF,
+    ]).expect("Failed to parse");
+    assert_eq!(statement, build::while_loop(build::identifier("n"), vec![
+        build::compound_assign("total", "+=", build::literal("5")),
+        build::compound_assign("mask", "<<=", build::literal("1")),
+    ]));
+}
+
+/// Test that assignment is an expression that groups to the right and binds loosest of all, so assignments chain,
+/// nest inside larger expressions, and take a full expression as their value, while only variables are assigned to.
+#[test]
+fn test_assignment_expressions() {
+    let statement = Parser::parse_statement(tokens![
+        ident("a"), EQUAL, ident("b"), PLUSEQUAL, ident("c"), EQUAL, num(0), SEMICOLON, EOF,
+    ]).expect("Failed to parse");
+    assert_eq!(statement, build::assign("a", build::compound_assign("b", "+=", build::assign("c", build::literal("0")))));
+    assert!(build::validate(&statement).is_empty());
+
+    let statement = Parser::parse_statement(tokens![
+        ident("x"), EQUAL, ident("a"), PLUS, ident("b"), ASTERISK, ident("c"), SEMICOLON, EOF,
+    ]).expect("Failed to parse");
+    let product = build::binary(build::identifier("b"), "*", build::identifier("c"));
+    assert_eq!(statement, build::assign("x", build::binary(build::identifier("a"), "+", product)));
+
+    let expression = Parser::parse_expression(tokens![
+        LPAREN, ident("n"), EQUAL, ident("f"), LPAREN, RPAREN, RPAREN, NOTEQUAL, num(0), EOF,
+    ]).expect("Failed to parse");
+    let call = build::assign("n", build::call("f", Vec::new()));
+    assert_eq!(expression, build::binary(call, "!=", build::literal("0")));
+    assert!(expression.get_children()[0].is_parenthesized());
+
+    assert!(Parser::parse_expression(tokens![ident("a"), PLUS, ident("b"), EQUAL, num(1), EOF]).is_err());
+    assert!(Parser::parse_expression(tokens![num(1), PLUSEQUAL, num(1), EOF]).is_err());
+}
+
+/// Test that `++` and `--` after an operand parse to postfix expressions, which bind tighter than prefix operators.
+#[test]
+fn test_postfix_increments() {
+    let expression = Parser::parse_expression(tokens![ident("i"), PLUSPLUS, NOTEQUAL, ident("n"), EOF])
+        .expect("Failed to parse");
+    assert_eq!(expression, build::binary(build::postfix(build::identifier("i"), "++"), "!=", build::identifier("n")));
+
+    let expression = Parser::parse_expression(tokens![DASH, ident("p"), DOT, ident("n"), MINUSMINUS, EOF])
+        .expect("Failed to parse");
+    assert_eq!(expression, build::unary("-", build::postfix(build::member(build::identifier("p"), ".", "n"), "--")));
+    assert!(build::validate(&expression).is_empty());
+
+    let statement = Parser::parse_statement(tokens![
+        WHILE, LPAREN, ident("i"), PLUSPLUS, NOTEQUAL, ident("n"), RPAREN, LBRACKET, RBRACKET, EOF,
+    ]).expect("Failed to parse");
+    assert_eq!(statement, build::while_loop(
+        build::binary(build::postfix(build::identifier("i"), "++"), "!=", build::identifier("n")),
+        Vec::new(),
+    ));
+}
+
+/// Test that a lone statement parses as it would inside a block.
+#[test]
+fn test_parse_statement_fragment() {
+    let statement = Parser::parse_statement(tokens![ident("f"), LPAREN, RPAREN, SEMICOLON]).expect("Failed to parse");
+    assert_eq!(statement, build::expression_statement(build::call("f", Vec::new())));
+
+    let statement = Parser::parse_statement(tokens![
+        WHILE, LPAREN, ident("x"), RPAREN, LBRACKET, ident("x"), EQUAL, num(0), SEMICOLON, RBRACKET, EOF,
+    ]).expect("Failed to parse");
+    assert_eq!(statement, build::while_loop(build::identifier("x"), vec![build::assign("x", build::literal("0"))]));
+}
+
+/// Test that the spelling of a type parses to its type node.
+#[test]
+fn test_parse_type_str() {
+    assert_eq!(Parser::parse_type_str("int"), Ok(ASTNode::new(NodeType::Type(DataType::Integer))));
+    assert_eq!(Parser::parse_type_str("unsigned long"), Ok(ASTNode::new(NodeType::Type(DataType::UnsignedLong))));
+
+    let mut struct_type = ASTNode::new(NodeType::Type(DataType::Struct));
+    struct_type.add_child(build::identifier("Point"));
+    assert_eq!(Parser::parse_type_str("struct Point"), Ok(struct_type));
+
+    assert!(Parser::parse_type_str("int x").is_err());
+    assert!(Parser::parse_type_str("x").is_err());
+}
+
+/// Test that the keywords of a composed type specifier combine into the same type in any order, including after `int`
+/// or `char`, and that invalid combinations are still rejected whichever keyword comes first.
+#[test]
+fn test_parse_composed_type_any_order() {
+    let expected = [
+        ("int long", DataType::Long),
+        ("long int", DataType::Long),
+        ("int unsigned long", DataType::UnsignedLong),
+        ("int long long", DataType::LongLong),
+        ("long int long unsigned", DataType::UnsignedLongLong),
+        ("int signed", DataType::Sign),
+        ("char unsigned", DataType::UnsignedChar),
+        ("char signed", DataType::SignedChar),
+        ("unsigned char", DataType::UnsignedChar),
+    ];
+    for (text, data_type) in expected {
+        assert_eq!(Parser::parse_type_str(text), Ok(ASTNode::new(NodeType::Type(data_type))), "{}", text);
+    }
+
+    for text in ["char long", "int long char", "int unsigned signed", "int int long", "int long long long"] {
+        assert!(Parser::parse_type_str(text).is_err(), "{}", text);
+    }
+}
+
+/// ---- Incremental Reparsing Section ----
+
+/// Applies `edit` to `old_text` both incrementally and by parsing the new text from scratch, checking both agree.
+fn assert_reparse_matches_full_parse(old_text: &str, edit: TextEdit) {
+    let previous = Parser::parse(Lexer::lex(old_text).unwrap()).unwrap();
+    let new_text = edit.apply(old_text).unwrap();
+    assert_eq!(reparse(&previous, old_text, &edit), Parser::parse(Lexer::lex(&new_text).unwrap()), "{}", new_text);
+}
+
+const INCREMENTAL_SOURCE: &str = "int x = 1;\nint f(int a) {\n    if (a) {\n        return 1;\n    } else {\n        return 0;\n    }\n}\n// helper\nint g() {\n    return 2;\n}\n";
+
+#[test]
+fn test_reparse_edit_inside_function_body() {
+    let offset = INCREMENTAL_SOURCE.find("return 0").unwrap() + "return ".len();
+    assert_reparse_matches_full_parse(INCREMENTAL_SOURCE, TextEdit::new(Span::new(offset, 1), "5"));
+}
+
+#[test]
+fn test_reparse_insert_item_between_items() {
+    let offset = INCREMENTAL_SOURCE.find("// helper").unwrap();
+    assert_reparse_matches_full_parse(INCREMENTAL_SOURCE, TextEdit::new(Span::new(offset, 0), "int y = 3;\n"));
+}
+
+#[test]
+fn test_reparse_delete_item() {
+    let end = INCREMENTAL_SOURCE.find("int f").unwrap();
+    assert_reparse_matches_full_parse(INCREMENTAL_SOURCE, TextEdit::new(Span::new(0, end), ""));
+}
+
+#[test]
+fn test_reparse_edit_spanning_items() {
+    let start = INCREMENTAL_SOURCE.find("return 0").unwrap();
+    let end = INCREMENTAL_SOURCE.find("return 2").unwrap();
+    assert_reparse_matches_full_parse(INCREMENTAL_SOURCE, TextEdit::new(Span::new(start, end - start), "return "));
+}
+
+#[test]
+fn test_reparse_reports_syntax_errors() {
+    let previous = Parser::parse(Lexer::lex(INCREMENTAL_SOURCE).unwrap()).unwrap();
+    let offset = INCREMENTAL_SOURCE.find("return 2;").unwrap() + "return 2".len();
+    let edit = TextEdit::new(Span::new(offset, 1), "");
+    assert!(reparse(&previous, INCREMENTAL_SOURCE, &edit).is_err());
+}
+
+#[test]
+fn test_reparse_edit_outside_source() {
+    let previous = Parser::parse(Lexer::lex("int x = 1;").unwrap()).unwrap();
+    let edit = TextEdit::new(Span::new(20, 1), "2");
+    assert!(reparse(&previous, "int x = 1;", &edit).is_err());
+}
+
+/// ---- Source Span Section ----
+
+/// Collects the node type and source text of every node of a tree that has a source range, parent first.
+fn spanned_texts(node: &ASTNode, source: &str, texts: &mut Vec<(NodeType, String)>) {
+    if let Some(span) = node.get_span() {
+        texts.push((node.get_node_type(), span.text(source).unwrap().to_string()));
+    }
+    for child in node.get_children() {
+        spanned_texts(&child, source, texts);
+    }
+}
+
+/// This test checks that parsing tokens with their source ranges gives every statement and expression the range it
+/// was parsed from, and that the ranges do not change what the tree compares equal to.
+#[test]
+fn test_source_spans() {
+    let source = "int main() {\n    int x = 0;\n    x = f(1) + y * 2;\n    x += -x;\n    return x;\n}\n";
+    let ast = Parser::parse_with_spans(Lexer::lex_with_spans(source).unwrap(), LanguageConfig::default()).unwrap();
+    let mut texts = Vec::new();
+    spanned_texts(&ast.get_root(), source, &mut texts);
+    let text_of = |node_type: NodeType| texts.iter()
+        .filter(|(spanned_type, _)| *spanned_type == node_type)
+        .map(|(_, text)| text.as_str())
+        .collect::<Vec<_>>();
+
+    assert_eq!(text_of(NodeType::FunctionDeclaration), vec![source.trim_end()]);
+    assert_eq!(text_of(NodeType::BinaryExpression), vec!["f(1) + y * 2", "y * 2"]);
+    assert_eq!(text_of(NodeType::FunctionCall), vec!["f(1)"]);
+    assert_eq!(text_of(NodeType::Identifier("y".to_string())), vec!["y"]);
+    assert_eq!(text_of(NodeType::Literal("1".to_string())), vec!["1"]);
+    assert_eq!(text_of(NodeType::Initialization), vec!["int x = 0;"]);
+    assert_eq!(text_of(NodeType::Assignment), vec!["x = f(1) + y * 2"]);
+    assert_eq!(text_of(NodeType::UnaryExpression), vec!["-x"]);
+    assert_eq!(text_of(NodeType::Return), vec!["return x;"]);
+
+    assert_eq!(ast, Parser::parse(Lexer::lex(source).unwrap()).unwrap());
+    assert!(Parser::parse(Lexer::lex(source).unwrap()).unwrap().get_root().get_children()[0].get_span().is_none());
+}
+
+/// This test checks that the name in each declaration of a parameter, a variable, and a function pointer has the
+/// range of the name alone, which is where the variable is reported to be declared.
+#[test]
+fn test_declaration_name_spans() {
+    let source = "int f(int a, int b) {\n    int x = a;\n    int (*g)(int, int) = f;\n    return x;\n}\n";
+    let ast = Parser::parse_with_spans(Lexer::lex_with_spans(source).unwrap(), LanguageConfig::default()).unwrap();
+    let mut texts = Vec::new();
+    spanned_texts(&ast.get_root(), source, &mut texts);
+    let spans_of = |name: &str| texts.iter()
+        .filter(|(spanned_type, _)| *spanned_type == NodeType::Identifier(name.to_string()))
+        .map(|(_, text)| text.as_str())
+        .collect::<Vec<_>>();
+
+    assert_eq!(spans_of("a"), vec!["a", "a"]);
+    assert_eq!(spans_of("b"), vec!["b"]);
+    assert_eq!(spans_of("x"), vec!["x", "x"]);
+    assert_eq!(spans_of("g"), vec!["g"]);
+}
@@ -1,6 +1,7 @@
 use common::ast::{
     core::{ASTNode, AST}, data_type::DataType, node_type::NodeType
 };
+use common::symbol::Symbol;
 use lexer::token::Token;
 use parser::core::Parser;
 
@@ -13,50 +14,50 @@ fn test_nested_switch_statements() {
     let tokens: Vec<Token> = vec![
         Token::SWITCH,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['y']),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::RPAREN,
         Token::LBRACKET,
         
         Token::CASE,
-        Token::NUMBER(vec!['1']),
+        Token::NUMBER(Symbol::intern("1")),
         Token::COLON,
         
         Token::SWITCH,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::RPAREN,
         Token::LBRACKET,
         
         Token::CASE,
-        Token::NUMBER(vec!['1']),
+        Token::NUMBER(Symbol::intern("1")),
         Token::COLON,
         
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['z']),
+        Token::IDENTIFIER(Symbol::intern("z")),
         Token::EQUAL,
-        Token::NUMBER(vec!['1']),
+        Token::NUMBER(Symbol::intern("1")),
         Token::SEMICOLON,
         
         Token::CASE,
-        Token::NUMBER(vec!['2']),
+        Token::NUMBER(Symbol::intern("2")),
         Token::COLON,
         
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['z']),
+        Token::IDENTIFIER(Symbol::intern("z")),
         Token::EQUAL,
-        Token::NUMBER(vec!['2']),
+        Token::NUMBER(Symbol::intern("2")),
         Token::SEMICOLON,
         
         Token::RBRACKET,
         
         Token::CASE,
-        Token::NUMBER(vec!['2']),
+        Token::NUMBER(Symbol::intern("2")),
         Token::COLON,
         
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['z']),
+        Token::IDENTIFIER(Symbol::intern("z")),
         Token::EQUAL,
-        Token::NUMBER(vec!['3']),
+        Token::NUMBER(Symbol::intern("3")),
         Token::SEMICOLON,
         
         Token::RBRACKET,
@@ -163,12 +164,12 @@ fn test_nested_switch_statements() {
 #[test]
 fn test_nested_binary_expression() {
     let tokens: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['a']),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::PLUS,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['b']),
+        Token::IDENTIFIER(Symbol::intern("b")),
         Token::ASTERISK,
-        Token::IDENTIFIER(vec!['c']),
+        Token::IDENTIFIER(Symbol::intern("c")),
         Token::RPAREN,
         Token::SEMICOLON,
     ];
@@ -203,16 +204,16 @@ fn test_nested_if_statements() {
     let tokens: Vec<Token> = vec![
         Token::IF,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('x')),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::IF,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('y')),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::RETURN,
-        Token::IDENTIFIER(vec!('z')),
+        Token::IDENTIFIER(Symbol::intern("z")),
         Token::SEMICOLON,
         Token::RBRACKET,
         Token::RBRACKET,
@@ -268,16 +269,16 @@ fn test_if_inside_loop() {
     let tokens: Vec<Token> = vec![
         Token::WHILE,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('x')),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::IF,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('y')),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::RETURN,
-        Token::IDENTIFIER(vec!('z')),
+        Token::IDENTIFIER(Symbol::intern("z")),
         Token::SEMICOLON,
         Token::RBRACKET,
         Token::RBRACKET,
@@ -333,22 +334,22 @@ fn test_nested_if_else_statements() {
     let tokens: Vec<Token> = vec![
         Token::IF,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('a')),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::IF,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('b')),
+        Token::IDENTIFIER(Symbol::intern("b")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::RETURN,
-        Token::IDENTIFIER(vec!('c')),
+        Token::IDENTIFIER(Symbol::intern("c")),
         Token::SEMICOLON,
         Token::RBRACKET,
         Token::ELSE,
         Token::LBRACKET,
         Token::RETURN,
-        Token::IDENTIFIER(vec!('d')),
+        Token::IDENTIFIER(Symbol::intern("d")),
         Token::SEMICOLON,
         Token::RBRACKET,
         Token::RBRACKET,
@@ -414,22 +415,22 @@ fn test_nested_if_else_statements() {
 fn test_function_with_nested_if() {
     let tokens: Vec<Token> = vec![
         Token::TVOID,
-        Token::IDENTIFIER(vec!('f', 'o', 'o')),
+        Token::IDENTIFIER(Symbol::intern("foo")),
         Token::LPAREN,
         Token::RPAREN,
         Token::LBRACKET,
         Token::IF,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('a')),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::IF,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('b')),
+        Token::IDENTIFIER(Symbol::intern("b")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::RETURN,
-        Token::IDENTIFIER(vec!('c')),
+        Token::IDENTIFIER(Symbol::intern("c")),
         Token::SEMICOLON,
         Token::RBRACKET,
         Token::RBRACKET,
@@ -497,34 +498,34 @@ fn test_function_with_nested_if() {
 fn test_function_with_loops() {
     let tokens: Vec<Token> = vec![
         Token::TVOID,
-        Token::IDENTIFIER(vec!('f', 'o', 'o')),
+        Token::IDENTIFIER(Symbol::intern("foo")),
         Token::LPAREN,
         Token::RPAREN,
         Token::LBRACKET,
         Token::FOR,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('i')),
+        Token::IDENTIFIER(Symbol::intern("i")),
         Token::EQUAL,
-        Token::NUMBER(vec!('0')),
+        Token::NUMBER(Symbol::intern("0")),
         Token::SEMICOLON,
-        Token::IDENTIFIER(vec!('i')),
+        Token::IDENTIFIER(Symbol::intern("i")),
         Token::LESSTHAN,
-        Token::NUMBER(vec!('1')),
+        Token::NUMBER(Symbol::intern("1")),
         Token::SEMICOLON,
-        Token::IDENTIFIER(vec!('i')),
+        Token::IDENTIFIER(Symbol::intern("i")),
         Token::EQUAL,
-        Token::IDENTIFIER(vec!('i')),
+        Token::IDENTIFIER(Symbol::intern("i")),
         Token::PLUS,
-        Token::NUMBER(vec!('1')),
+        Token::NUMBER(Symbol::intern("1")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::WHILE,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('j')),
+        Token::IDENTIFIER(Symbol::intern("j")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::RETURN,
-        Token::IDENTIFIER(vec!('k')),
+        Token::IDENTIFIER(Symbol::intern("k")),
         Token::SEMICOLON,
         Token::RBRACKET,
         Token::RBRACKET,
@@ -727,17 +728,17 @@ fn test_nested_block_expressions_with_statements_parsing() {
         
         Token::LBRACKET,
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::EQUAL,
-        Token::NUMBER(vec!['1']),
+        Token::NUMBER(Symbol::intern("1")),
         Token::SEMICOLON,
         Token::RBRACKET,
         
         Token::LBRACKET,
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['y']),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::EQUAL,
-        Token::NUMBER(vec!['2']),
+        Token::NUMBER(Symbol::intern("2")),
         Token::SEMICOLON,
         Token::RBRACKET,
         
@@ -798,21 +799,21 @@ fn test_switch_with_nested_if_statement_parsing() {
     let tokens: Vec<Token> = vec![
         Token::SWITCH,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['z']),
+        Token::IDENTIFIER(Symbol::intern("z")),
         Token::RPAREN,
         Token::LBRACKET,
         
         Token::CASE,
-        Token::NUMBER(vec!['1']),
+        Token::NUMBER(Symbol::intern("1")),
         Token::COLON,
         
         Token::IF,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::RETURN,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::SEMICOLON,
         Token::RBRACKET,
         
@@ -878,28 +879,28 @@ fn test_switch_with_nested_if_else_statement_parsing() {
     let tokens: Vec<Token> = vec![
         Token::SWITCH,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['a']),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::RPAREN,
         Token::LBRACKET,
         
         Token::CASE,
-        Token::NUMBER(vec!['1']),
+        Token::NUMBER(Symbol::intern("1")),
         Token::COLON,
         
         Token::IF,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['b']),
+        Token::IDENTIFIER(Symbol::intern("b")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::RETURN,
-        Token::NUMBER(vec!['1']),
+        Token::NUMBER(Symbol::intern("1")),
         Token::SEMICOLON,
         Token::RBRACKET,
         
         Token::ELSE,
         Token::LBRACKET,
         Token::RETURN,
-        Token::NUMBER(vec!['0']),
+        Token::NUMBER(Symbol::intern("0")),
         Token::SEMICOLON,
         Token::RBRACKET,
         
@@ -974,18 +975,18 @@ fn test_if_with_nested_switch_statement_parsing() {
     let tokens: Vec<Token> = vec![
         Token::IF,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::RPAREN,
         Token::LBRACKET,
         
         Token::SWITCH,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['y']),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::RPAREN,
         Token::LBRACKET,
         
         Token::CASE,
-        Token::NUMBER(vec!['1']),
+        Token::NUMBER(Symbol::intern("1")),
         Token::COLON,
         Token::BREAK,
         Token::SEMICOLON,
@@ -1055,24 +1056,24 @@ fn test_if_with_nested_switch_with_multiple_cases_parsing() {
     let tokens: Vec<Token> = vec![
         Token::IF,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['a']),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::RPAREN,
         Token::LBRACKET,
         
         Token::SWITCH,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['b']),
+        Token::IDENTIFIER(Symbol::intern("b")),
         Token::RPAREN,
         Token::LBRACKET,
         
         Token::CASE,
-        Token::NUMBER(vec!['1']),
+        Token::NUMBER(Symbol::intern("1")),
         Token::COLON,
         Token::BREAK,
         Token::SEMICOLON,
         
         Token::CASE,
-        Token::NUMBER(vec!['2']),
+        Token::NUMBER(Symbol::intern("2")),
         Token::COLON,
         Token::BREAK,
         Token::SEMICOLON,
@@ -1138,32 +1139,32 @@ fn test_mixed_statements_and_nested_blocks_parsing() {
         Token::LBRACKET,
         
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['a']),
+        Token::IDENTIFIER(Symbol::intern("a")),
         Token::EQUAL,
-        Token::NUMBER(vec!['0']),
+        Token::NUMBER(Symbol::intern("0")),
         Token::SEMICOLON,
         
         Token::LBRACKET,
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['b']),
+        Token::IDENTIFIER(Symbol::intern("b")),
         Token::EQUAL,
-        Token::NUMBER(vec!['1']),
+        Token::NUMBER(Symbol::intern("1")),
         Token::SEMICOLON,
         
         Token::LBRACKET,
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['c']),
+        Token::IDENTIFIER(Symbol::intern("c")),
         Token::EQUAL,
-        Token::NUMBER(vec!['2']),
+        Token::NUMBER(Symbol::intern("2")),
         Token::SEMICOLON,
         Token::RBRACKET,
         
         Token::RBRACKET,
         
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['d']),
+        Token::IDENTIFIER(Symbol::intern("d")),
         Token::EQUAL,
-        Token::NUMBER(vec!['3']),
+        Token::NUMBER(Symbol::intern("3")),
         Token::SEMICOLON,
         
         Token::RBRACKET,
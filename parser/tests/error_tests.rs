@@ -1,6 +1,6 @@
 //! This file contains tests for error handling, making sure that the parser returns the correct errors when given invalid token streams.
 
-use common::error::ErrorType;
+use common::{config::LanguageConfig, constants::DEFAULT_MAX_NESTING_DEPTH, error::ErrorType, localization::Language, symbol::Symbol};
 use lexer::token::Token;
 use parser::core::Parser;
 
@@ -20,7 +20,7 @@ fn test_unclosed_block() {
 fn test_malformed_initialization() {
     let tokens: Vec<Token> = vec![
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::EQUAL,
         Token::SEMICOLON,
     ];
@@ -35,11 +35,11 @@ fn test_malformed_if_statement() {
     let tokens: Vec<Token> = vec![
         Token::IF,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::RPAREN,
         Token::LBRACKET,
         Token::RETURN,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::SEMICOLON,
         Token::RBRACKET,
         Token::ELSE,
@@ -58,19 +58,19 @@ fn test_malformed_for_loop() {
     let tokens: Vec<Token> = vec![
         Token::FOR,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('x')), 
+        Token::IDENTIFIER(Symbol::intern("x")), 
         Token::EQUAL,
-        Token::NUMBER(vec!('0')),
+        Token::NUMBER(Symbol::intern("0")),
         Token::SEMICOLON,
-        Token::IDENTIFIER(vec!('x')), 
+        Token::IDENTIFIER(Symbol::intern("x")), 
         Token::LESSTHAN,
-        Token::NUMBER(vec!('1')),
+        Token::NUMBER(Symbol::intern("1")),
         Token::SEMICOLON,
-        Token::IDENTIFIER(vec!('x')), 
+        Token::IDENTIFIER(Symbol::intern("x")), 
         Token::EQUAL,
-        Token::IDENTIFIER(vec!('x')), 
+        Token::IDENTIFIER(Symbol::intern("x")), 
         Token::PLUS,
-        Token::NUMBER(vec!('1')),
+        Token::NUMBER(Symbol::intern("1")),
         Token::RPAREN,
         Token::BREAK,
         Token::SEMICOLON,
@@ -127,7 +127,7 @@ fn test_malformed_do_while_loop() {
         Token::DO,
         Token::WHILE,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!('x')),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::RPAREN,
         Token::SEMICOLON,
         Token::EOF,
@@ -144,13 +144,13 @@ fn test_malformed_switch_statement() {
     let tokens: Vec<Token> = vec![
         Token::SWITCH,
         Token::LPAREN,
-        Token::IDENTIFIER(vec!['y']),
+        Token::IDENTIFIER(Symbol::intern("y")),
         Token::RPAREN,
         
         Token::TINTEGER,
-        Token::IDENTIFIER(vec!['x']),
+        Token::IDENTIFIER(Symbol::intern("x")),
         Token::EQUAL,
-        Token::NUMBER(vec!['8']),
+        Token::NUMBER(Symbol::intern("8")),
         Token::SEMICOLON,
         
         Token::BREAK,
@@ -168,7 +168,7 @@ fn test_malformed_switch_statement() {
 fn test_malformed_function_declaration() {
     let tokens: Vec<Token> = vec![
         Token::TVOID,
-        Token::IDENTIFIER(vec!['m', 'y', '_', 'f', 'u', 'n', 'c']),
+        Token::IDENTIFIER(Symbol::intern("my_func")),
         Token::LPAREN,
         Token::RPAREN,
         Token::EOF,
@@ -184,7 +184,7 @@ fn test_malformed_function_declaration() {
 fn test_malformed_enum_declaration() {
     let tokens: Vec<Token> = vec![
         Token::ENUM,
-        Token::IDENTIFIER(vec!['C', 'o', 'l', 'o', 'r']),
+        Token::IDENTIFIER(Symbol::intern("Color")),
         Token::SEMICOLON,
     ];
 
@@ -198,7 +198,7 @@ fn test_malformed_enum_declaration() {
 fn test_malformed_struct_declaration() {
     let tokens: Vec<Token> = vec![
         Token::STRUCT,
-        Token::IDENTIFIER(vec!['C', 'o', 'l', 'o', 'r']),
+        Token::IDENTIFIER(Symbol::intern("Color")),
         Token::SEMICOLON,
     ];
 
@@ -211,7 +211,7 @@ fn test_malformed_struct_declaration() {
 #[test]
 fn test_malformed_assignment() {
     let tokens: Vec<Token> = vec![
-        Token::IDENTIFIER(vec!['C', 'o', 'l', 'o', 'r']),
+        Token::IDENTIFIER(Symbol::intern("Color")),
         Token::EQUAL,
     ];
 
@@ -219,3 +219,340 @@ fn test_malformed_assignment() {
 
     assert!(matches!(e[0], ErrorType::SyntaxError { .. }));
 }
+
+/// This test checks that the parser returns a SyntaxError when a type specifier combines `signed` and `unsigned`.
+#[test]
+fn test_conflicting_type_specifiers() {
+    let tokens: Vec<Token> = vec![
+        Token::TSIGNINT,
+        Token::TUSIGN,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::EQUAL,
+        Token::NUMBER(Symbol::intern("1")),
+        Token::SEMICOLON,
+        Token::EOF,
+    ];
+
+    let e = Parser::parse(tokens).unwrap_err();
+    assert!(matches!(e[0], ErrorType::SyntaxError { .. }));
+}
+
+/// This test checks that the parser returns a SyntaxError when `volatile` is used on a function declaration.
+#[test]
+fn test_volatile_function() {
+    let tokens: Vec<Token> = vec![
+        Token::VOLATILE,
+        Token::TINTEGER,
+        Token::IDENTIFIER(Symbol::intern("f")),
+        Token::LPAREN,
+        Token::RPAREN,
+        Token::LBRACKET,
+        Token::RBRACKET,
+        Token::EOF,
+    ];
+
+    let e = Parser::parse(tokens).unwrap_err();
+    assert!(matches!(e[0], ErrorType::SyntaxError { .. }));
+}
+
+/// This test checks that thousands of nested parentheses produce a SyntaxError instead of overflowing the stack.
+#[test]
+fn test_deeply_nested_parentheses() {
+    let mut tokens: Vec<Token> = vec![Token::LPAREN; 100_000];
+    tokens.push(Token::NUMBER(Symbol::intern("1")));
+    tokens.extend(vec![Token::RPAREN; 100_000]);
+    tokens.push(Token::EOF);
+
+    let e = Parser::parse(tokens).unwrap_err();
+    assert!(matches!(&e[0], ErrorType::SyntaxError { message } if message.contains("too deeply nested")));
+}
+
+/// This test checks that a long chain of assignments such as `a = a = ... = 1;` produces a SyntaxError instead of
+/// overflowing the stack, as does a chain of compound assignments.
+#[test]
+fn test_deeply_nested_assignments() {
+    for operator in [Token::EQUAL, Token::PLUSEQUAL] {
+        let mut tokens: Vec<Token> = Vec::new();
+        for _ in 0..100_000 {
+            tokens.push(Token::IDENTIFIER(Symbol::intern("a")));
+            tokens.push(operator.clone());
+        }
+        tokens.extend([Token::NUMBER(Symbol::intern("1")), Token::SEMICOLON, Token::EOF]);
+
+        let e = Parser::parse(tokens).unwrap_err();
+        assert!(matches!(&e[0], ErrorType::SyntaxError { message } if message.contains("too deeply nested")));
+    }
+}
+
+/// Checks that parsing the tokens fails with the nesting limit error rather than overflowing the stack.
+fn assert_too_deeply_nested(tokens: Vec<Token>) {
+    let e = Parser::parse(tokens).unwrap_err();
+    assert!(matches!(&e[0], ErrorType::SyntaxError { message } if message.contains("too deeply nested")), "{:?}", e[0]);
+}
+
+/// This test checks that thousands of nested unary expressions such as `-(-(-(1)))`, conditional expressions such as
+/// `x = 1 ? 1 : 1 ? 1 : 1;`, and blocks such as `{{{}}}` produce a SyntaxError instead of overflowing the stack.
+#[test]
+fn test_deeply_nested_constructs() {
+    let mut unary: Vec<Token> = Vec::new();
+    for _ in 0..100_000 {
+        unary.extend([Token::DASH, Token::LPAREN]);
+    }
+    unary.push(Token::NUMBER(Symbol::intern("1")));
+    unary.extend(vec![Token::RPAREN; 100_000]);
+    unary.extend([Token::SEMICOLON, Token::EOF]);
+    assert_too_deeply_nested(unary);
+
+    let mut conditional: Vec<Token> = vec![Token::IDENTIFIER(Symbol::intern("x")), Token::EQUAL];
+    for _ in 0..100_000 {
+        conditional.extend([Token::NUMBER(Symbol::intern("1")), Token::CTRUE, Token::NUMBER(Symbol::intern("1")), Token::COLON]);
+    }
+    conditional.extend([Token::NUMBER(Symbol::intern("1")), Token::SEMICOLON, Token::EOF]);
+    assert_too_deeply_nested(conditional);
+
+    let mut blocks: Vec<Token> = vec![Token::LBRACKET; 100_000];
+    blocks.extend(vec![Token::RBRACKET; 100_000]);
+    blocks.push(Token::EOF);
+    assert_too_deeply_nested(blocks);
+}
+
+/// The stack size of rayon workers, server workers and test threads, which every input must parse within.
+const WORKER_STACK_SIZE: usize = 2 * 1024 * 1024;
+
+/// Parses the tokens on a thread with the stack of a worker thread, failing the test if the stack overflows.
+fn parse_on_worker_stack(tokens: Vec<Token>) -> Result<(), Vec<ErrorType>> {
+    std::thread::Builder::new()
+        .stack_size(WORKER_STACK_SIZE)
+        .spawn(move || Parser::parse(tokens).map(|_| ()))
+        .expect("Failed to spawn parser thread")
+        .join()
+        .expect("Parser thread panicked")
+}
+
+/// Builds `int g() { return f(f(...f(1)...)); }` with `depth` nested calls.
+fn nested_calls(depth: usize) -> Vec<Token> {
+    let mut tokens = vec![
+        Token::TINTEGER, Token::IDENTIFIER(Symbol::intern("g")), Token::LPAREN, Token::RPAREN, Token::LBRACKET, Token::RETURN,
+    ];
+    for _ in 0..depth {
+        tokens.extend([Token::IDENTIFIER(Symbol::intern("f")), Token::LPAREN]);
+    }
+    tokens.push(Token::NUMBER(Symbol::intern("1")));
+    tokens.extend(vec![Token::RPAREN; depth]);
+    tokens.extend([Token::SEMICOLON, Token::RBRACKET, Token::EOF]);
+    tokens
+}
+
+/// Builds `int g() { if (a) { if (a) { ... } } }` with `depth` nested `if` statements.
+fn nested_ifs(depth: usize) -> Vec<Token> {
+    let mut tokens = vec![Token::TINTEGER, Token::IDENTIFIER(Symbol::intern("g")), Token::LPAREN, Token::RPAREN, Token::LBRACKET];
+    for _ in 0..depth {
+        tokens.extend([Token::IF, Token::LPAREN, Token::IDENTIFIER(Symbol::intern("a")), Token::RPAREN, Token::LBRACKET]);
+    }
+    tokens.extend(vec![Token::RBRACKET; depth]);
+    tokens.extend([Token::RBRACKET, Token::EOF]);
+    tokens
+}
+
+/// Builds `int g() { for (;;) { for (;;) { ... } } }` with `depth` nested `for` loops.
+fn nested_fors(depth: usize) -> Vec<Token> {
+    let mut tokens = vec![Token::TINTEGER, Token::IDENTIFIER(Symbol::intern("g")), Token::LPAREN, Token::RPAREN, Token::LBRACKET];
+    for _ in 0..depth {
+        tokens.extend([Token::FOR, Token::LPAREN, Token::SEMICOLON, Token::SEMICOLON, Token::RPAREN, Token::LBRACKET]);
+    }
+    tokens.extend(vec![Token::RBRACKET; depth]);
+    tokens.extend([Token::RBRACKET, Token::EOF]);
+    tokens
+}
+
+/// This test checks that calls, `if` statements and `for` loops nested just under the default nesting limit parse on
+/// a worker's 2 MiB stack, and that nesting them past it produces a SyntaxError there instead of overflowing it.
+#[test]
+fn test_nesting_limit_fits_worker_stack() {
+    let depth = DEFAULT_MAX_NESTING_DEPTH - 8;
+    for tokens in [nested_calls(depth), nested_ifs(depth), nested_fors(depth)] {
+        assert_eq!(parse_on_worker_stack(tokens), Ok(()));
+    }
+
+    let depth = DEFAULT_MAX_NESTING_DEPTH * 2;
+    for tokens in [nested_calls(depth), nested_ifs(depth), nested_fors(depth)] {
+        let e = parse_on_worker_stack(tokens).unwrap_err();
+        assert!(matches!(&e[0], ErrorType::SyntaxError { message } if message.contains("too deeply nested")), "{:?}", e[0]);
+    }
+}
+
+/// This test checks that the nesting limit passed to the parser is honored for nested blocks.
+#[test]
+fn test_nesting_limit_for_blocks() {
+    let mut tokens: Vec<Token> = vec![Token::LBRACKET; 5];
+    tokens.extend(vec![Token::RBRACKET; 5]);
+    tokens.push(Token::EOF);
+
+    assert!(Parser::parse_with_max_depth(tokens.clone(), 10).is_ok());
+    let e = Parser::parse_with_max_depth(tokens, 4).unwrap_err();
+    assert!(matches!(e[0], ErrorType::SyntaxError { .. }));
+}
+
+/// This test checks that a loop body without braces is rejected unless braceless bodies are enabled.
+#[test]
+fn test_braceless_body_disabled() {
+    let tokens: Vec<Token> = vec![
+        Token::WHILE,
+        Token::LPAREN,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::RPAREN,
+        Token::BREAK,
+        Token::SEMICOLON,
+        Token::EOF,
+    ];
+
+    let e = Parser::parse_with_config(tokens.clone(), LanguageConfig::strict()).unwrap_err();
+    assert!(matches!(e[0], ErrorType::SyntaxError { .. }));
+    assert!(Parser::parse_with_config(tokens, LanguageConfig { braceless_bodies: true, ..LanguageConfig::strict() }).is_ok());
+}
+
+/// This test checks that `typedef`, attributes, and struct methods are rejected when they are not enabled.
+#[test]
+fn test_disabled_language_features() {
+    let typedef_tokens: Vec<Token> = vec![
+        Token::TYPEDEF,
+        Token::TINTEGER,
+        Token::IDENTIFIER(Symbol::intern("n")),
+        Token::SEMICOLON,
+        Token::EOF,
+    ];
+    let attribute_tokens: Vec<Token> = vec![
+        Token::AT,
+        Token::IDENTIFIER(Symbol::intern("p")),
+        Token::STRUCT,
+        Token::IDENTIFIER(Symbol::intern("S")),
+        Token::LBRACE,
+        Token::RBRACE,
+        Token::EOF,
+    ];
+    let method_tokens: Vec<Token> = vec![
+        Token::STRUCT,
+        Token::IDENTIFIER(Symbol::intern("S")),
+        Token::LBRACE,
+        Token::TVOID,
+        Token::IDENTIFIER(Symbol::intern("f")),
+        Token::LPAREN,
+        Token::RPAREN,
+        Token::LBRACKET,
+        Token::RBRACKET,
+        Token::RBRACE,
+        Token::EOF,
+    ];
+
+    for tokens in [typedef_tokens, attribute_tokens, method_tokens] {
+        let e = Parser::parse(tokens).unwrap_err();
+        assert!(matches!(e[0], ErrorType::SyntaxError { .. }));
+    }
+}
+
+/// This test checks that syntax errors are rendered from the message catalog, in the requested language.
+#[test]
+fn test_localized_syntax_error() {
+    let tokens: Vec<Token> = vec![
+        Token::LBRACKET,
+    ];
+
+    let e = Parser::parse(tokens).unwrap_err();
+    assert_eq!(e[0].code(), "E0004");
+    assert_eq!(e[0].to_string(), "error[E0004]: syntax error: Unclosed block");
+    assert_eq!(e[0].localized(Language::Spanish), "error[E0004]: error de sintaxis: Unclosed block");
+    assert_eq!("es".parse::<Language>(), Ok(Language::Spanish));
+}
+
+/// This test checks that a keyword used as a variable name is reported as a reserved keyword rather than a generic syntax error.
+#[test]
+fn test_reserved_keyword_as_variable_name() {
+    let tokens: Vec<Token> = vec![
+        Token::TINTEGER,
+        Token::IF,
+        Token::EQUAL,
+        Token::NUMBER(Symbol::intern("3")),
+        Token::SEMICOLON,
+        Token::EOF,
+    ];
+
+    let e = Parser::parse(tokens).unwrap_err();
+    assert_eq!(e[0], ErrorType::ReservedKeyword { keyword: "if".to_string() });
+    assert_eq!(e[0].to_string(), "error[E0011]: 'if' is a reserved keyword and cannot be used as a variable name");
+}
+
+/// This test checks that the parser returns a SyntaxError when an expression statement is not terminated by a semicolon.
+#[test]
+fn test_expression_statement_requires_semicolon() {
+    let tokens: Vec<Token> = vec![
+        Token::LBRACKET,
+        Token::IDENTIFIER(Symbol::intern("foo")),
+        Token::LPAREN,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::RPAREN,
+        Token::RBRACKET,
+        Token::EOF,
+    ];
+
+    let e = Parser::parse(tokens).unwrap_err();
+    assert_eq!(e, vec![ErrorType::SyntaxError { message: "Expected ';' after expression".to_string() }]);
+}
+
+/// This test checks that a function defined inside another function is reported, and that parsing recovers after
+/// its body so later errors are still found.
+#[test]
+fn test_nested_function_definition() {
+    let tokens: Vec<Token> = vec![
+        Token::TVOID,
+        Token::IDENTIFIER(Symbol::intern("f")),
+        Token::LPAREN,
+        Token::RPAREN,
+        Token::LBRACKET,
+        Token::TINTEGER,
+        Token::IDENTIFIER(Symbol::intern("g")),
+        Token::LPAREN,
+        Token::TINTEGER,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::RPAREN,
+        Token::LBRACKET,
+        Token::RETURN,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::SEMICOLON,
+        Token::RBRACKET,
+        Token::BREAK,
+        Token::SEMICOLON,
+        Token::RBRACKET,
+        Token::TINTEGER,
+        Token::IF,
+        Token::SEMICOLON,
+        Token::EOF,
+    ];
+
+    let e = Parser::parse(tokens).unwrap_err();
+    assert_eq!(e, vec![
+        ErrorType::SyntaxError {
+            message: "nested function definitions are not supported: `g` is defined inside another function".to_string(),
+        },
+        ErrorType::ReservedKeyword { keyword: "if".to_string() },
+    ]);
+}
+
+/// This test checks that an assignment to a struct member is reported as unsupported rather than as a missing
+/// variable.
+#[test]
+fn test_member_assignment_unsupported() {
+    let tokens: Vec<Token> = vec![
+        Token::LBRACKET,
+        Token::IDENTIFIER(Symbol::intern("p")),
+        Token::DOT,
+        Token::IDENTIFIER(Symbol::intern("x")),
+        Token::EQUAL,
+        Token::NUMBER(Symbol::intern("3")),
+        Token::SEMICOLON,
+        Token::RBRACKET,
+        Token::EOF,
+    ];
+
+    let e = Parser::parse(tokens).unwrap_err();
+    assert_eq!(e, vec![ErrorType::SyntaxError { message: "Assignment to a struct member with '=' is not supported".to_string() }]);
+}
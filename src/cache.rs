@@ -0,0 +1,152 @@
+//! A persistent cache of compiled modules, kept in a directory between runs of the driver like Cargo's `target/`.
+//!
+//! Each artifact is stored under a key derived from the source it was compiled from, the version of the compiler,
+//! and the flags it was compiled with, so that changing any of them misses the cache rather than reusing a stale
+//! artifact. Keys hash their inputs with FNV-1a, which unlike the standard library's hasher gives the same hash in
+//! every build, and each entry starts with a header naming all three, which is checked when it is loaded so that a
+//! hash collision is a miss too. Entries are written to a temporary file and then renamed into place, so a run that
+//! is interrupted, or races another, never leaves a partial entry behind.
+
+use std::{fs, io, path::PathBuf};
+
+use common::ast::core::AST;
+use sts::{cache, core::SymbolTableStack};
+
+/// The version of the compiler, which is part of every key.
+const COMPILER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Identifies the artifacts compiled from one source with one compiler and set of flags.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    /// The header of the entries stored under the key, naming the compiler version, flags, and source.
+    header: String,
+}
+
+impl CacheKey {
+    /// Derives the key of a source compiled with the running compiler.
+    ///
+    /// # Parameters
+    ///
+    /// * `source` - The source code of the file.
+    /// * `flags` - Every option that changes what is compiled, in a fixed order.
+    pub fn new(source: &str, flags: &[String]) -> Self {
+        let flags = flags.iter().flat_map(|flag| flag.bytes().chain([0])).collect::<Vec<u8>>();
+        Self {
+            header: format!(
+                "sicc {} flags {:016x} source {:016x} {}\n",
+                COMPILER_VERSION,
+                fnv1a(&flags),
+                fnv1a(source.as_bytes()),
+                source.len(),
+            ),
+        }
+    }
+
+    /// Names the entry of an artifact stored under the key.
+    fn file_name(&self, extension: &str) -> String {
+        format!("{:016x}.{}", fnv1a(self.header.as_bytes()), extension)
+    }
+}
+
+/// Hashes bytes with 64-bit FNV-1a.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// A directory of cached artifacts.
+#[derive(Debug, Clone)]
+pub struct ModuleCache {
+    directory: PathBuf,
+}
+
+impl ModuleCache {
+    /// Opens a cache directory, creating it if it does not exist.
+    ///
+    /// # Parameters
+    ///
+    /// * `directory` - The directory the cache is kept in.
+    ///
+    /// # Errors
+    ///
+    /// * Returns the error of creating the directory.
+    pub fn open(directory: impl Into<PathBuf>) -> io::Result<Self> {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)?;
+        Ok(Self { directory })
+    }
+
+    /// Loads the typed AST and symbol table stack analyzed from a source, if they are cached.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` - The key of the source.
+    ///
+    /// # Returns
+    ///
+    /// The typed AST and symbol table stack, or None if they are not cached under the key or the entry is invalid.
+    pub fn load_analysis(&self, key: &CacheKey) -> Option<(AST, SymbolTableStack)> {
+        cache::decode(&self.load(key, "ast")?)
+    }
+
+    /// Stores the typed AST and symbol table stack analyzed from a source.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` - The key of the source.
+    /// * `ast` - The typed AST.
+    /// * `sym_table_stack` - The symbol table stack.
+    ///
+    /// # Errors
+    ///
+    /// * Returns the error of writing the entry.
+    pub fn store_analysis(&self, key: &CacheKey, ast: &AST, sym_table_stack: &SymbolTableStack) -> io::Result<()> {
+        self.store(key, "ast", &cache::encode(ast, sym_table_stack))
+    }
+
+    /// Loads the object compiled from a source, if it is cached.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` - The key of the source.
+    ///
+    /// # Returns
+    ///
+    /// The object, or None if it is not cached under the key.
+    pub fn load_object(&self, key: &CacheKey) -> Option<Vec<u8>> {
+        self.load(key, "o")
+    }
+
+    /// Stores the object compiled from a source.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` - The key of the source.
+    /// * `object` - The object.
+    ///
+    /// # Errors
+    ///
+    /// * Returns the error of writing the entry.
+    pub fn store_object(&self, key: &CacheKey, object: &[u8]) -> io::Result<()> {
+        self.store(key, "o", object)
+    }
+
+    fn load(&self, key: &CacheKey, extension: &str) -> Option<Vec<u8>> {
+        let mut entry = fs::read(self.directory.join(key.file_name(extension))).ok()?;
+        if !entry.starts_with(key.header.as_bytes()) {
+            return None;
+        }
+        entry.drain(..key.header.len());
+        Some(entry)
+    }
+
+    fn store(&self, key: &CacheKey, extension: &str, artifact: &[u8]) -> io::Result<()> {
+        let path = self.directory.join(key.file_name(extension));
+        let temporary = path.with_extension(format!("{}.{}.tmp", extension, std::process::id()));
+        let mut entry = key.header.as_bytes().to_vec();
+        entry.extend_from_slice(artifact);
+        fs::write(&temporary, entry)?;
+        fs::rename(&temporary, &path).inspect_err(|_| {
+            let _ = fs::remove_file(&temporary);
+        })
+    }
+}
@@ -0,0 +1,61 @@
+//! Generates a compilation database in the `compile_commands.json` format, which tools such as clangd-like indexers
+//! read to see what was compiled and with which flags.
+//!
+//! The driver compiles one source file per invocation, so each invocation contributes one command, recorded with
+//! the exact arguments the driver was run with.
+
+use std::path::PathBuf;
+
+use sts::dump::json_string;
+
+/// One entry of a compilation database: a file and the command that compiled it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompileCommand {
+    /// The working directory the command was run in, which relative paths are resolved against.
+    pub directory: PathBuf,
+    /// The source file compiled, as passed to the command.
+    pub file: PathBuf,
+    /// The command line, starting with the program.
+    pub arguments: Vec<String>,
+}
+
+impl CompileCommand {
+    /// Creates the entry of a command.
+    ///
+    /// # Parameters
+    ///
+    /// * `directory` - The working directory the command was run in.
+    /// * `file` - The source file compiled.
+    /// * `arguments` - The command line, starting with the program.
+    pub fn new(directory: impl Into<PathBuf>, file: impl Into<PathBuf>, arguments: Vec<String>) -> Self {
+        Self { directory: directory.into(), file: file.into(), arguments }
+    }
+}
+
+/// Serializes commands as a compilation database, a JSON array of objects with the fields `directory`, `file`, and
+/// `arguments`.
+///
+/// # Parameters
+///
+/// * `commands` - The commands to serialize.
+///
+/// # Returns
+///
+/// The JSON text.
+pub fn compile_commands_json(commands: &[CompileCommand]) -> String {
+    let objects: Vec<String> = commands.iter().map(|command| {
+        let arguments: Vec<String> = command.arguments.iter().map(|argument| json_string(argument)).collect();
+        format!(
+            "  {{\"directory\": {}, \"file\": {}, \"arguments\": [{}]}}",
+            json_string(&command.directory.display().to_string()),
+            json_string(&command.file.display().to_string()),
+            arguments.join(", "),
+        )
+    }).collect();
+
+    if objects.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[\n{}\n]", objects.join(",\n"))
+    }
+}
@@ -0,0 +1,53 @@
+//! Lexes and parses several source files at once on a thread pool.
+//!
+//! The lexer and parser keep all of their state in the values they are run on, and the only state shared by the
+//! whole process is the symbol interner, which is guarded by a lock, so every file can be handled on its own thread.
+//! The results are collected in the order the files were given rather than the order they finish in, so the
+//! diagnostics of a run do not depend on how the threads were scheduled.
+
+use std::path::{Path, PathBuf};
+
+use common::{ast::core::AST, error::ErrorType};
+use rayon::prelude::*;
+
+use crate::pipeline::parse_source;
+
+/// The result of lexing and parsing one source file.
+#[derive(Debug, Clone)]
+pub struct ParsedFile {
+    /// The path of the file.
+    pub path: PathBuf,
+    /// The AST of the file, or the errors of the first phase that failed on it.
+    pub result: Result<AST, Vec<ErrorType>>,
+}
+
+/// Lexes and parses each file on rayon's thread pool.
+///
+/// # Parameters
+///
+/// * `files` - The path and source code of each file.
+///
+/// # Returns
+///
+/// One `ParsedFile` per file, in the same order as `files`.
+pub fn parse_files(files: &[(PathBuf, String)]) -> Vec<ParsedFile> {
+    files.par_iter()
+        .map(|(path, source)| ParsedFile {
+            path: path.clone(),
+            result: parse_source(source),
+        })
+        .collect()
+}
+
+/// Returns the errors of every file with the path of the file they were found in, ordered by the position of the
+/// file in `parsed` and then by the order they were reported in.
+///
+/// # Parameters
+///
+/// * `parsed` - The files returned by `parse_files`.
+pub fn diagnostics(parsed: &[ParsedFile]) -> Vec<(&Path, &ErrorType)> {
+    parsed.iter()
+        .filter_map(|file| file.result.as_ref().err().map(|errors| (file.path.as_path(), errors)))
+        .flat_map(|(path, errors)| errors.iter().map(move |error| (path, error)))
+        .collect()
+}
@@ -0,0 +1,43 @@
+//! Type information for editors to show when the cursor hovers over an expression.
+//!
+//! As with refactorings, an identifier of the AST is matched with its token by order, since the AST does not record
+//! where the name of a declaration came from.
+
+use common::ast::{core::{ASTNode, AST}, data_type::DataType, node_type::NodeType};
+use lexer::{core::Lexer, token::Token};
+use sts::typecheck::type_at_path;
+
+use crate::refactor::parse_identifiers;
+
+/// Finds the type of the variable, function, or literal under the cursor.
+///
+/// # Parameters
+///
+/// * `source` - The source code of the program.
+/// * `offset` - The byte offset of the cursor, on a character of an identifier or literal.
+///
+/// # Returns
+///
+/// The declared type of the variable, the `Function` type for a function, or the type of the literal, or None if the
+/// source does not lex or parse, the cursor is not on an identifier or literal, or its type cannot be determined.
+pub fn type_at(source: &str, offset: usize) -> Option<DataType> {
+    let tokens = Lexer::lex_with_spans(source).ok()?;
+    let (token, cursor_span) = tokens.iter()
+        .find(|(_, span)| span.offset <= offset && offset < span.end())
+        .cloned()?;
+
+    // A literal has the same type wherever it is written
+    let literal = match token {
+        Token::IDENTIFIER(name) => {
+            let (ast, identifiers) = parse_identifiers(tokens, name).ok()?;
+            let (_, path) = identifiers?.into_iter().find(|(span, _)| *span == cursor_span)?;
+            return type_at_path(&ast, &path);
+        }
+        Token::NUMBER(number) => number.as_str().to_string(),
+        Token::STRINGLITERAL(contents) => format!("\"{}\"", contents.escaped()),
+        Token::CHAR(c) => format!("'{}'", c),
+        _ => return None,
+    };
+    type_at_path(&AST::new(ASTNode::new(NodeType::Literal(literal))), &[])
+}
+
@@ -0,0 +1,271 @@
+//! A small JSON value type with a parser and a compact printer, for the requests and responses of the compile
+//! server.
+
+use std::fmt;
+
+use sts::dump::json_string;
+
+/// A JSON value. Object members keep the order they were written in.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    /// `null`.
+    Null,
+    /// `true` or `false`.
+    Bool(bool),
+    /// A number.
+    Number(f64),
+    /// A string.
+    String(String),
+    /// An array.
+    Array(Vec<JsonValue>),
+    /// An object, as its members in order.
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// Builds an object from its members.
+    pub fn object(members: Vec<(&str, JsonValue)>) -> Self {
+        JsonValue::Object(members.into_iter().map(|(key, value)| (key.to_string(), value)).collect())
+    }
+
+    /// Returns the member of an object with the given key, or None if the value is not an object or has no such
+    /// member.
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(members) => members.iter().find(|(name, _)| name == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Returns the string, or None if the value is not a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements, or None if the value is not an array.
+    pub fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            JsonValue::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(value: &str) -> Self {
+        JsonValue::String(value.to_string())
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(value: String) -> Self {
+        JsonValue::String(value)
+    }
+}
+
+impl<T: Into<JsonValue>> From<Option<T>> for JsonValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(JsonValue::Null, Into::into)
+    }
+}
+
+/// Prints a value as compact JSON on a single line, with integral numbers printed without a fraction.
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonValue::Null => write!(f, "null"),
+            JsonValue::Bool(value) => write!(f, "{}", value),
+            JsonValue::Number(value) if value.fract() == 0.0 && value.abs() < 1e15 => write!(f, "{}", *value as i64),
+            JsonValue::Number(value) if value.is_finite() => write!(f, "{}", value),
+            JsonValue::Number(_) => write!(f, "null"),
+            JsonValue::String(value) => write!(f, "{}", json_string(value)),
+            JsonValue::Array(values) => {
+                write!(f, "[")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            JsonValue::Object(members) => {
+                write!(f, "{{")?;
+                for (index, (key, value)) in members.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}:{}", json_string(key), value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+/// Parses a JSON text holding a single value.
+///
+/// # Parameters
+///
+/// * `text` - The JSON text.
+///
+/// # Returns
+///
+/// The value, or a message naming what was expected where the text is not valid JSON.
+pub fn parse(text: &str) -> Result<JsonValue, String> {
+    let mut parser = JsonParser { chars: text.chars().collect(), position: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.position < parser.chars.len() {
+        return Err(parser.expected("the end of the text"));
+    }
+    Ok(value)
+}
+
+/// Reads a JSON value character by character.
+struct JsonParser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl JsonParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.position += 1;
+        c
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.position += 1;
+        }
+    }
+
+    fn expected(&self, what: &str) -> String {
+        format!("expected {} at character {}", what, self.position)
+    }
+
+    /// Consumes `word` if the text continues with it.
+    fn eat(&mut self, word: &str) -> bool {
+        let matches = word.chars().enumerate().all(|(offset, c)| self.chars.get(self.position + offset) == Some(&c));
+        if matches {
+            self.position += word.chars().count();
+        }
+        matches
+    }
+
+    fn value(&mut self) -> Result<JsonValue, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => self.string().map(JsonValue::String),
+            Some('-' | '0'..='9') => self.number(),
+            _ if self.eat("true") => Ok(JsonValue::Bool(true)),
+            _ if self.eat("false") => Ok(JsonValue::Bool(false)),
+            _ if self.eat("null") => Ok(JsonValue::Null),
+            _ => Err(self.expected("a value")),
+        }
+    }
+
+    fn object(&mut self) -> Result<JsonValue, String> {
+        self.position += 1; // Skip '{'
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.eat("}") {
+            return Ok(JsonValue::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.expected("a member name"));
+            }
+            let key = self.string()?;
+            self.skip_whitespace();
+            if !self.eat(":") {
+                return Err(self.expected("':'"));
+            }
+            members.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(JsonValue::Object(members)),
+                _ => return Err(self.expected("',' or '}'")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<JsonValue, String> {
+        self.position += 1; // Skip '['
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.eat("]") {
+            return Ok(JsonValue::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(JsonValue::Array(values)),
+                _ => return Err(self.expected("',' or ']'")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.position += 1; // Skip '"'
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(value),
+                Some('\\') => match self.next() {
+                    Some('"') => value.push('"'),
+                    Some('\\') => value.push('\\'),
+                    Some('/') => value.push('/'),
+                    Some('b') => value.push('\u{8}'),
+                    Some('f') => value.push('\u{c}'),
+                    Some('n') => value.push('\n'),
+                    Some('r') => value.push('\r'),
+                    Some('t') => value.push('\t'),
+                    Some('u') => {
+                        let high = self.hex_escape()?;
+                        // A character outside the basic plane is escaped as a surrogate pair
+                        let code = if (0xD800..0xDC00).contains(&high) && self.eat("\\u") {
+                            let low = self.hex_escape()?;
+                            0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF)
+                        } else {
+                            high
+                        };
+                        value.push(char::from_u32(code).ok_or_else(|| self.expected("a valid character escape"))?);
+                    }
+                    _ => return Err(self.expected("an escape sequence")),
+                },
+                Some(c) if (c as u32) >= 0x20 => value.push(c),
+                _ => return Err(self.expected("'\"'")),
+            }
+        }
+    }
+
+    /// Reads the four hex digits of a `\u` escape.
+    fn hex_escape(&mut self) -> Result<u32, String> {
+        let digits: String = (0..4).filter_map(|_| self.next()).collect();
+        u32::from_str_radix(&digits, 16).map_err(|_| self.expected("four hex digits"))
+    }
+
+    fn number(&mut self) -> Result<JsonValue, String> {
+        let start = self.position;
+        while matches!(self.peek(), Some('-' | '+' | '.' | 'e' | 'E' | '0'..='9')) {
+            self.position += 1;
+        }
+        let text: String = self.chars[start..self.position].iter().collect();
+        text.parse().map(JsonValue::Number).map_err(|_| format!("invalid number `{}` at character {}", text, start))
+    }
+}
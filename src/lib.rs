@@ -1,3 +1,30 @@
 //! sicc
 extern crate llvm_sys as llvm;
-extern crate threadpool;
\ No newline at end of file
+extern crate threadpool;
+
+/// A persistent on-disk cache of compiled modules, keyed by their source, the compiler version, and flags.
+pub mod cache;
+
+/// Compilation databases recording what the driver compiled and with which flags.
+pub mod compdb;
+
+/// Lexing and parsing several source files in parallel.
+pub mod frontend;
+
+/// Type information for editors, such as the type of the expression under the cursor.
+pub mod hover;
+
+/// A minimal JSON value type for the messages of the compile server.
+pub mod json;
+
+/// Runs the compiler phase by phase and records the output of each phase.
+pub mod pipeline;
+
+/// An interactive session whose functions can be redefined between runs of the program.
+pub mod repl;
+
+/// Refactorings on source text, such as renaming a symbol, for editors.
+pub mod refactor;
+
+/// A compile server answering JSON-RPC requests over a local socket, keeping its caches warm between requests.
+pub mod server;
@@ -0,0 +1,365 @@
+//! The `sicc` command line interface.
+
+use std::{
+    env, fs,
+    io::{self, BufRead},
+    path::PathBuf,
+    process,
+    sync::Arc,
+};
+
+use clap::{Parser, Subcommand};
+use common::{
+    config::{CodegenConfig, DeadStoreMode, Environment, OptLevel, OverflowMode, PreprocessorConfig},
+    constants::DEFAULT_SWITCH_TABLE_THRESHOLD,
+    diagnostic::{DiagnosticOrder, DiagnosticSink},
+    localization::Language,
+};
+use sicc::{
+    cache::{CacheKey, ModuleCache},
+    compdb::{compile_commands_json, CompileCommand},
+    frontend::{diagnostics, parse_files},
+    pipeline::{analyze, explain_pipeline_reporting, ir_statistics, local_variables, preprocess_source},
+    repl::Session,
+    server::{serve, Server, DEFAULT_CACHE_CAPACITY},
+};
+use sts::dump::{collect_global_symbols, symbols_to_json};
+
+/// Command line arguments accepted by `sicc`.
+#[derive(Parser, Debug)]
+#[command(name = "sicc", version, about = "A compiler for a subset of C", subcommand_negates_reqs = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// The source files to compile. Every mode except `--syntax-only` takes a single file.
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// Lexes and parses every input file in parallel and prints their errors in the order the files were given.
+    #[arg(long)]
+    syntax_only: bool,
+
+    /// Prints the tokens, AST, symbol table, typed AST, and IR produced for the program.
+    #[arg(long)]
+    explain_pipeline: bool,
+
+    /// Prints every function and global variable with its type, linkage, and mangled name after semantic analysis,
+    /// then every local variable with its type, mutability, and where it is declared.
+    #[arg(long)]
+    emit_symbols: bool,
+
+    /// Prints the number of instructions, basic blocks, and allocas of every function in the generated IR.
+    #[arg(long)]
+    stats: bool,
+
+    /// Writes the functions and global variables to the given file as JSON after semantic analysis.
+    #[arg(long, value_name = "FILE")]
+    emit_symbols_json: Option<PathBuf>,
+
+    /// The language diagnostics are printed in, as an ISO 639-1 code such as `en` or `es`.
+    #[arg(long, default_value = "en")]
+    lang: Language,
+
+    /// The order `--explain-pipeline` lists diagnostics in: as `reported`, sorted by `location`, or grouped by
+    /// `code`.
+    #[arg(long, default_value = "reported")]
+    diagnostic_order: DiagnosticOrder,
+
+    /// Prints each diagnostic of `--explain-pipeline` to stderr as soon as it is found.
+    #[arg(long)]
+    stream_diagnostics: bool,
+
+    /// What signed integer `+`, `-`, and `*` do on overflow: `wrap` around, `trap` at runtime, or leave it
+    /// `undefined` as in C so the optimizer can assume it does not happen.
+    #[arg(long, default_value = "wrap")]
+    overflow: OverflowMode,
+
+    /// Marks the IR printed by `--explain-pipeline` with comments naming the function, loop, `if`, or `switch` each
+    /// part was generated from and its source line.
+    #[arg(long)]
+    annotate_ir: bool,
+
+    /// The fewest cases a switch over a dense range of constants needs to be lowered to an LLVM `switch`, which can
+    /// become a jump table, instead of a chain of compares.
+    #[arg(long, default_value_t = DEFAULT_SWITCH_TABLE_THRESHOLD)]
+    switch_table_threshold: usize,
+
+    /// Moves declarations whose initializer computes the same value on every iteration out of loop bodies.
+    #[arg(long)]
+    hoist_loop_invariants: bool,
+
+    /// What happens to stores to local variables whose value is never read: `keep` them, `remove` them, or keep
+    /// them and `warn` about each one.
+    #[arg(long, default_value = "keep")]
+    dead_stores: DeadStoreMode,
+
+    /// The optimization level: `0` runs no passes, `1` hoists loop invariants and drops the branches constant
+    /// conditions never take, and `2` also propagates constant arguments into the functions they are passed to and
+    /// reduces loop multiplications to additions.
+    #[arg(short = 'O', default_value = "0")]
+    opt_level: OptLevel,
+
+    /// Compiles for an environment without the C library, such as a kernel: `main` is not required and is an ordinary
+    /// function, every function keeps external linkage for the environment to call, calls to `printf` and its
+    /// relatives are not checked against the C library's formats, and comparing strings, which calls `strcmp`, is
+    /// an error.
+    #[arg(long)]
+    freestanding: bool,
+
+    /// Serves programs as a library, which does not need to define `main`. The programs `sicc serve` compiles are
+    /// otherwise executables run from `main`, while the other modes inspect a program without running it and never
+    /// require `main`.
+    #[arg(long)]
+    no_main: bool,
+
+    /// Writes a `compile_commands.json` compilation database to the given file, recording the input and the command
+    /// line it is compiled with, for external tooling.
+    #[arg(long, value_name = "FILE")]
+    emit_compile_commands: Option<PathBuf>,
+
+    /// A directory `#include` looks for files in, after the directory of the including file for a quoted name. Each
+    /// `-I` adds one, searched in the order given.
+    #[arg(short = 'I', value_name = "DIR")]
+    include_paths: Vec<PathBuf>,
+
+    /// Defines a macro before the input is read, as `NAME=replacement`, or `NAME` alone to define it as `1`.
+    #[arg(short = 'D', value_name = "NAME[=VALUE]")]
+    defines: Vec<String>,
+
+    /// Keeps the result of analyzing the input in the given directory, and reuses it while the input, the compiler,
+    /// and the flags are unchanged.
+    #[arg(long, value_name = "DIR")]
+    cache_dir: Option<PathBuf>,
+}
+
+/// Modes of `sicc` that do not compile a given file.
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Answers compile, diagnose, and run requests as JSON-RPC messages over a Unix socket until a `shutdown`
+    /// request, compiling every program with the codegen flags given before `serve`.
+    Serve {
+        /// The path of the socket to listen on, which must not exist.
+        #[arg(long, value_name = "PATH")]
+        socket: PathBuf,
+
+        /// The number of connections handled at once.
+        #[arg(long, default_value_t = 4)]
+        workers: usize,
+    },
+
+    /// Reads functions from standard input, each entry ended by an empty line, and compiles each function into a
+    /// module of its own, so redefining a function replaces its body in later runs. `:run` runs the program from
+    /// `main` with the arguments that follow it, and `:quit` or the end of the input ends the session.
+    Repl,
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let codegen_config = CodegenConfig {
+        overflow: cli.overflow,
+        annotate_ir: cli.annotate_ir,
+        switch_table_threshold: cli.switch_table_threshold,
+        hoist_loop_invariants: cli.hoist_loop_invariants,
+        dead_stores: cli.dead_stores,
+        opt_level: cli.opt_level,
+        environment: if cli.freestanding { Environment::Freestanding } else { Environment::Hosted },
+        // Only the server runs programs, so only it links them into executables
+        require_main: matches!(cli.command, Some(Command::Serve { .. })) && !cli.no_main,
+    };
+
+    if let Some(Command::Serve { socket, workers }) = &cli.command {
+        let server = Arc::new(Server::new(codegen_config, DEFAULT_CACHE_CAPACITY));
+        if let Err(e) = serve(server, socket, *workers) {
+            eprintln!("error: could not serve on {}: {}", socket.display(), e);
+            process::exit(1);
+        }
+        return;
+    }
+    if let Some(Command::Repl) = &cli.command {
+        repl(Session::new(codegen_config), cli.lang);
+        return;
+    }
+
+    let preprocessor_config = cli.defines.iter().fold(
+        PreprocessorConfig { include_paths: cli.include_paths.clone(), ..Default::default() },
+        |config, definition| config.define(definition),
+    );
+    let files: Vec<(PathBuf, String)> = cli.inputs.iter().map(|input| match fs::read_to_string(input) {
+        Ok(source) => match preprocess_source(input, source, &preprocessor_config) {
+            Ok(source) => (input.clone(), source),
+            Err(errors) => {
+                for error in errors {
+                    eprintln!("{}: {}", input.display(), error.localized(cli.lang));
+                }
+                process::exit(1);
+            }
+        },
+        Err(e) => {
+            eprintln!("error: could not read {}: {}", input.display(), e);
+            process::exit(1);
+        }
+    }).collect();
+
+    if let Some(path) = &cli.emit_compile_commands {
+        let directory = env::current_dir().unwrap_or_default();
+        let commands: Vec<CompileCommand> = cli.inputs.iter()
+            .map(|input| CompileCommand::new(directory.clone(), input, env::args().collect()))
+            .collect();
+        if let Err(e) = fs::write(path, compile_commands_json(&commands)) {
+            eprintln!("error: could not write {}: {}", path.display(), e);
+            process::exit(1);
+        }
+    }
+
+    if cli.syntax_only {
+        let parsed = parse_files(&files);
+        let diagnostics = diagnostics(&parsed);
+        for (path, error) in &diagnostics {
+            eprintln!("{}: {}", path.display(), error.localized(cli.lang));
+        }
+        if !diagnostics.is_empty() {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let source = match files.as_slice() {
+        [(_, source)] => source,
+        _ => {
+            eprintln!("error: only --syntax-only accepts more than one input file");
+            process::exit(2);
+        }
+    };
+
+    if cli.explain_pipeline {
+        let mut diagnostics = DiagnosticSink::with_order(cli.diagnostic_order);
+        if cli.stream_diagnostics {
+            let lang = cli.lang;
+            diagnostics = diagnostics.streaming(move |diagnostic| eprintln!("{}", diagnostic.localized(lang)));
+        }
+        let explanation = explain_pipeline_reporting(source, codegen_config, diagnostics);
+        print!("{}", explanation.render(cli.lang));
+        if !explanation.is_complete() {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if cli.stats {
+        match ir_statistics(source, codegen_config) {
+            Ok(stats) => {
+                for function in stats {
+                    println!("{}", function);
+                }
+            }
+            Err(errors) => {
+                for error in errors {
+                    eprintln!("{}", error.localized(cli.lang));
+                }
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if cli.emit_symbols || cli.emit_symbols_json.is_some() {
+        let cache = cli.cache_dir.as_ref().map(|directory| match ModuleCache::open(directory) {
+            Ok(cache) => cache,
+            Err(e) => {
+                eprintln!("error: could not open the cache {}: {}", directory.display(), e);
+                process::exit(1);
+            }
+        });
+        let key = CacheKey::new(source, &[format!("{:?}", codegen_config)]);
+        let (ast, sym_table_stack) = match cache.as_ref().and_then(|cache| cache.load_analysis(&key)) {
+            Some(analysis) => analysis,
+            None => match analyze(source) {
+                Ok((ast, sym_table_stack)) => {
+                    if let Some(Err(e)) = cache.as_ref().map(|cache| cache.store_analysis(&key, &ast, &sym_table_stack)) {
+                        eprintln!("warning: could not write to the cache: {}", e);
+                    }
+                    (ast, sym_table_stack)
+                }
+                Err(errors) => {
+                    for error in errors {
+                        eprintln!("{}", error.localized(cli.lang));
+                    }
+                    process::exit(1);
+                }
+            },
+        };
+        let symbols = collect_global_symbols(&ast);
+
+        if cli.emit_symbols {
+            for symbol in &symbols {
+                println!("{}", symbol);
+            }
+            match local_variables(ast, sym_table_stack, codegen_config) {
+                Ok(locals) => {
+                    for local in locals.lines() {
+                        println!("local\t{}", local);
+                    }
+                }
+                Err(errors) => {
+                    for error in errors {
+                        eprintln!("{}", error.localized(cli.lang));
+                    }
+                    process::exit(1);
+                }
+            }
+        }
+        if let Some(path) = &cli.emit_symbols_json {
+            if let Err(e) = fs::write(path, symbols_to_json(&symbols)) {
+                eprintln!("error: could not write {}: {}", path.display(), e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if cli.emit_compile_commands.is_some() {
+        return;
+    }
+    eprintln!("error: no mode selected; pass --explain-pipeline, --emit-symbols, --stats, --syntax-only, or --emit-compile-commands, or run `sicc serve`");
+    process::exit(2);
+}
+
+/// Reads the entries and commands of an interactive session from standard input until `:quit` or its end.
+fn repl(mut session: Session, lang: Language) {
+    let mut entry = String::new();
+    for line in io::stdin().lock().lines().map_while(Result::ok) {
+        let command: Vec<&str> = line.split_whitespace().collect();
+        match command.as_slice() {
+            [":quit"] => return,
+            [":run", args @ ..] => match session.run(args) {
+                Ok(output) => {
+                    print!("{}", String::from_utf8_lossy(&output.stdout));
+                    eprint!("{}", String::from_utf8_lossy(&output.stderr));
+                    match output.status.code() {
+                        Some(code) => println!("exited with {}", code),
+                        None => println!("terminated by a signal"),
+                    }
+                }
+                Err(e) => eprintln!("error: could not run the session: {}", e),
+            },
+            // An empty line ends the entry being read
+            [] if !entry.trim().is_empty() => {
+                match session.define(&entry) {
+                    Ok(names) => println!("defined {}", names.join(", ")),
+                    Err(errors) => {
+                        for error in errors {
+                            eprintln!("{}", error.localized(lang));
+                        }
+                    }
+                }
+                entry.clear();
+            }
+            _ => {
+                entry.push_str(&line);
+                entry.push('\n');
+            }
+        }
+    }
+}
@@ -0,0 +1,533 @@
+//! Runs the compiler phase by phase and records the output of each one, so the whole pipeline can be
+//! inspected (or visualized) for a single program.
+
+use std::{fmt, path::Path};
+
+use common::{
+    ast::core::AST,
+    config::{CodegenConfig, DeadStoreMode, Environment, LanguageConfig, PreprocessorConfig},
+    constants::DEFAULT_PRIORITY_MODELEMENT,
+    diagnostic::{Diagnostic, DiagnosticSink},
+    error::ErrorType,
+    localization::Language,
+    location::{SourceLocation, Span},
+    source::LineIndex,
+    symbol::Symbol,
+    warning::WarningType,
+};
+use integration::module::{ast_stitch, ModElement};
+use ir::{core::{IRGenerator, SourceConstruct}, stats::{function_stats, FunctionStats}};
+use lexer::{core::Lexer, detokenize::detokenize_with_locations, preprocess::Preprocessor, token::Token};
+use parser::core::Parser;
+use safe_llvm::common::io;
+use sts::{
+    core::SymbolTableStack,
+    desugar::desugar_string_switches,
+    dump::find_main,
+    lint::{
+        check_assignments_in_conditions, check_dead_stores, check_format_strings, check_implicit_narrowing,
+        check_operator_precedence, check_string_comparisons, check_unused_results,
+    },
+    optimize::{PassManager, DEAD_STORE_ELIMINATION, LOOP_INVARIANT_MOTION},
+    typecheck::check_program,
+};
+
+/// The output of every compilation phase for one program.
+///
+/// Phases run in order: lexing, parsing, type checking, symbol table generation, and IR generation. When a phase
+/// fails its errors are stored in `errors` and every later phase is left as `None`.
+#[derive(Debug, Clone, Default)]
+pub struct PipelineExplanation {
+    /// The tokens produced by the lexer.
+    pub tokens: Vec<Token>,
+    /// The abstract syntax tree produced by the parser.
+    pub ast: Option<AST>,
+    /// The symbol table stack produced by the analysis phase.
+    pub symbol_table: Option<SymbolTableStack>,
+    /// The abstract syntax tree as returned by the analysis phase.
+    pub typed_ast: Option<AST>,
+    /// The textual LLVM IR of the module.
+    pub ir: Option<String>,
+    /// The errors reported by the phase that failed, if any.
+    pub errors: Vec<ErrorType>,
+    /// The location each error was found at, if any, in the same order as `errors`.
+    pub error_locations: Vec<Option<SourceLocation>>,
+    /// The warnings reported by the lint passes run on the AST.
+    pub warnings: Vec<WarningType>,
+    /// The location each warning was found at, if any, in the same order as `warnings`.
+    pub warning_locations: Vec<Option<SourceLocation>>,
+}
+
+/// Runs the front end of the compiler on `source`: lexing, parsing, type checking, and semantic analysis.
+///
+/// # Parameters
+///
+/// * `source` - The source code of the program.
+///
+/// # Returns
+///
+/// The AST and symbol table stack produced by semantic analysis, or the errors of the first phase that failed.
+pub fn analyze(source: &str) -> Result<(AST, SymbolTableStack), Vec<ErrorType>> {
+    analyze_checked(parse_source(source)?, Some(&line_index(source)))
+}
+
+/// Lexes and parses `source`, giving every statement, declaration and expression of the AST the range of the
+/// source it was parsed from.
+///
+/// # Parameters
+///
+/// * `source` - The source code of the program.
+///
+/// # Returns
+///
+/// The AST of the program, or the errors of the first phase that failed.
+pub fn parse_source(source: &str) -> Result<AST, Vec<ErrorType>> {
+    Parser::parse_with_spans(lex_with_spans(source)?, LanguageConfig::default())
+        .map_err(|errors| errors.into_iter().map(|(error, _)| error).collect())
+}
+
+/// Indexes the lines of `source` with its `#line` directives, so diagnostics located from spans point at the file
+/// and line the code was generated or preprocessed from.
+fn line_index(source: &str) -> LineIndex<'_> {
+    Lexer::line_index(source, LanguageConfig::default())
+}
+
+/// Lexes `source`, pairing every token with the range of the source it covers and dropping the ranges of errors.
+fn lex_with_spans(source: &str) -> Result<Vec<(Token, Span)>, Vec<ErrorType>> {
+    Lexer::lex_with_spans(source).map_err(|errors| errors.into_iter().map(|(error, _)| error).collect())
+}
+
+/// Expands the preprocessing directives of a source file into source text the rest of the pipeline compiles. A
+/// source without directives is returned as it is when the configuration defines no macros, so its locations do not
+/// change. Otherwise the file is preprocessed and its tokens are written back at their locations, with `#line`
+/// directives where they move to another file or line.
+///
+/// # Parameters
+///
+/// * `path` - The path of the file, which is read again when it has directives so its includes are found next to it.
+/// * `source` - The source code of the file.
+/// * `config` - The include paths and macros to preprocess with.
+///
+/// # Returns
+///
+/// The source to compile, or the errors found reading, lexing, or preprocessing the file or the files it includes.
+pub fn preprocess_source(path: &Path, source: String, config: &PreprocessorConfig) -> Result<String, Vec<ErrorType>> {
+    // A source that does not lex is left to the lexer to report with the locations of its errors
+    let has_directives = Lexer::lex_with_config(&source, config.language)
+        .is_ok_and(|tokens| tokens.iter().any(|token| matches!(token, Token::DIRECTIVE(_))));
+    if !has_directives && config.defines.is_empty() {
+        return Ok(source);
+    }
+    Preprocessor::preprocess_file_with_locations(path, config).map(|tokens| detokenize_with_locations(&tokens))
+}
+
+/// Runs type checking and semantic analysis on a program that was built in code rather than parsed, such as with
+/// `common::ast::build`.
+///
+/// # Parameters
+///
+/// * `ast` - The program to analyze.
+///
+/// # Returns
+///
+/// The AST and symbol table stack produced by semantic analysis, or the errors of the first phase that failed.
+pub fn analyze_ast(ast: AST) -> Result<(AST, SymbolTableStack), Vec<ErrorType>> {
+    analyze_checked(ast, None)
+}
+
+/// Runs type checking and semantic analysis on a program, locating its type errors in `source` if it was parsed
+/// from one.
+fn analyze_checked(ast: AST, source: Option<&LineIndex>) -> Result<(AST, SymbolTableStack), Vec<ErrorType>> {
+    let mut diagnostics = DiagnosticSink::new();
+    check_program(&ast, source, &mut diagnostics);
+    diagnostics.finish(())?;
+    SymbolTableStack::gen_sym_table_stack(desugar_string_switches(&ast))
+}
+
+/// Runs every phase of the compiler on `source` and returns the output of each one.
+///
+/// # Parameters
+///
+/// * `source` - The source code of the program.
+///
+/// # Returns
+///
+/// A `PipelineExplanation` holding the output of each phase up to the first one that failed.
+pub fn explain_pipeline(source: &str) -> PipelineExplanation {
+    explain_pipeline_with_config(source, CodegenConfig::default())
+}
+
+/// Runs every phase of the compiler on `source`, generating IR with the given options, and returns the output of
+/// each one.
+///
+/// # Parameters
+///
+/// * `source` - The source code of the program.
+/// * `codegen_config` - The options to generate IR with, such as what signed arithmetic does on overflow.
+///
+/// # Returns
+///
+/// A `PipelineExplanation` holding the output of each phase up to the first one that failed.
+pub fn explain_pipeline_with_config(source: &str, codegen_config: CodegenConfig) -> PipelineExplanation {
+    explain_pipeline_reporting(source, codegen_config, DiagnosticSink::new())
+}
+
+/// Runs every phase of the compiler on `source` like `explain_pipeline_with_config`, reporting diagnostics to the
+/// given sink, which decides the order the explanation lists them in and may stream them as they are found. Lexer
+/// errors are reported with their location in the source.
+///
+/// # Parameters
+///
+/// * `source` - The source code of the program.
+/// * `codegen_config` - The options to generate IR with.
+/// * `diagnostics` - The sink to report diagnostics to, which has none yet.
+///
+/// # Returns
+///
+/// A `PipelineExplanation` holding the output of each phase up to the first one that failed.
+pub fn explain_pipeline_reporting(
+    source: &str,
+    codegen_config: CodegenConfig,
+    mut diagnostics: DiagnosticSink,
+) -> PipelineExplanation {
+    let mut explanation = PipelineExplanation::default();
+
+    match Lexer::lex_with_spans(source) {
+        Ok(tokens) => {
+            explanation.tokens = tokens.iter().map(|(token, _)| token.clone()).collect();
+            match Parser::parse_with_spans(tokens, LanguageConfig::default()) {
+                Ok(ast) => explain_from_ast(&mut explanation, &mut diagnostics, ast, Some(source), codegen_config),
+                Err(errors) => {
+                    let index = line_index(source);
+                    for (error, span) in errors {
+                        match span {
+                            Some(span) => diagnostics.error_at(error, index.location(span.offset)),
+                            None => diagnostics.error(error),
+                        }
+                    }
+                }
+            }
+        }
+        Err(errors) => {
+            let index = line_index(source);
+            for (error, span) in errors {
+                diagnostics.error_at(error, index.location(span.offset));
+            }
+        }
+    }
+    explanation.with_diagnostics(diagnostics)
+}
+
+/// Runs every phase after parsing on a program that was built in code rather than parsed, such as with
+/// `common::ast::build`, generating IR with the given options, and returns the output of each one. The explanation
+/// has no tokens, and the IR is not annotated with source lines.
+///
+/// # Parameters
+///
+/// * `ast` - The program to compile.
+/// * `codegen_config` - The options to generate IR with.
+///
+/// # Returns
+///
+/// A `PipelineExplanation` holding the output of each phase up to the first one that failed.
+pub fn explain_ast(ast: AST, codegen_config: CodegenConfig) -> PipelineExplanation {
+    let mut explanation = PipelineExplanation::default();
+    let mut diagnostics = DiagnosticSink::new();
+    explain_from_ast(&mut explanation, &mut diagnostics, ast, None, codegen_config);
+    explanation.with_diagnostics(diagnostics)
+}
+
+/// Runs every phase after parsing on `ast`, recording their output in `explanation` and reporting their errors and
+/// warnings to `diagnostics`. The source, when there is one, is used to annotate the IR. A hosted program linked
+/// into an executable fails to compile if it does not define `main`.
+fn explain_from_ast(
+    explanation: &mut PipelineExplanation,
+    diagnostics: &mut DiagnosticSink,
+    ast: AST,
+    source: Option<&str>,
+    codegen_config: CodegenConfig,
+) {
+    // Without the C library, `printf` and its relatives are whatever the program defines them to be
+    if codegen_config.environment == Environment::Hosted {
+        diagnostics.warnings_from(check_format_strings(&ast));
+    }
+    diagnostics.warnings_from(check_unused_results(&ast));
+    diagnostics.warnings_from(check_string_comparisons(&ast));
+    diagnostics.warnings_from(check_operator_precedence(&ast));
+    diagnostics.warnings_from(check_assignments_in_conditions(&ast));
+    diagnostics.warnings_from(check_implicit_narrowing(&ast));
+    if codegen_config.dead_stores == DeadStoreMode::Warn {
+        diagnostics.warnings_from(check_dead_stores(&ast));
+    }
+    explanation.ast = Some(ast.clone());
+
+    check_program(&ast, source.map(line_index).as_ref(), diagnostics);
+    if codegen_config.requires_main() && find_main(&ast).is_none() {
+        diagnostics.error(ErrorType::MissingMain);
+    }
+    if diagnostics.has_errors() {
+        return;
+    }
+
+    let ast = desugar_string_switches(&ast);
+    let mut passes = PassManager::for_level(codegen_config.opt_level);
+    passes.set_overflow(codegen_config.overflow);
+    if codegen_config.hoist_loop_invariants {
+        passes.add(LOOP_INVARIANT_MOTION);
+    }
+    if codegen_config.dead_stores == DeadStoreMode::Remove {
+        passes.add(DEAD_STORE_ELIMINATION);
+    }
+    let ast = passes.run(&ast);
+    let (typed_ast, sym_table_stack) = match diagnostics.absorb(SymbolTableStack::gen_sym_table_stack(ast)) {
+        Some(result) => result,
+        None => return,
+    };
+    explanation.symbol_table = Some(sym_table_stack.clone());
+    explanation.typed_ast = Some(typed_ast.clone());
+
+    let module = ast_stitch(vec![ModElement::new(typed_ast, sym_table_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+    let mut ir_generator = IRGenerator::with_config(codegen_config);
+    let module_tag = match ir_generator.generate_ir_reporting(module, diagnostics) {
+        Some(module_tag) => module_tag,
+        None => return,
+    };
+
+    let resource_pools = ir_generator.get_resource_pools();
+    let pools = match resource_pools.lock() {
+        Ok(pools) => pools,
+        Err(_) => {
+            return diagnostics.error(ErrorType::DevError {
+                message: "Failed to lock the IR resource pools".to_string(),
+            });
+        }
+    };
+    let module = match pools.get_module(module_tag) {
+        Some(module) => module,
+        None => {
+            return diagnostics.error(ErrorType::DevError {
+                message: "Generated module was not found in the IR resource pools".to_string(),
+            });
+        }
+    };
+
+    match io::write_to_string(module) {
+        Ok(ir) => match source.filter(|_| codegen_config.annotate_ir) {
+            Some(source) => {
+                let tokens = Lexer::lex_with_locations(source).unwrap_or_default();
+                explanation.ir = Some(annotate_ir(&ir, ir_generator.get_annotations(), &tokens));
+            }
+            None => explanation.ir = Some(ir),
+        },
+        Err(e) => diagnostics.error(ErrorType::DevError {
+            message: format!("Failed to print the generated IR: {}", e),
+        }),
+    }
+}
+
+/// Generates IR for an analyzed program and describes every variable it declares, with its type, whether it is
+/// `const`, and where in the source it is declared, as printed by `--emit-symbols`. Declarations are located by the
+/// spans the parser gave their names, so a program built in code has none.
+///
+/// # Parameters
+///
+/// * `ast` - The AST produced by semantic analysis.
+/// * `sym_table_stack` - The symbol table stack produced by semantic analysis.
+/// * `codegen_config` - The options to generate IR with.
+///
+/// # Returns
+///
+/// One line per variable, as described by `IRGenerator::dump_locals`, or the errors of IR generation.
+pub fn local_variables(
+    ast: AST,
+    sym_table_stack: SymbolTableStack,
+    codegen_config: CodegenConfig,
+) -> Result<String, Vec<ErrorType>> {
+    let mut diagnostics = DiagnosticSink::new();
+    let module = ast_stitch(vec![ModElement::new(ast, sym_table_stack, DEFAULT_PRIORITY_MODELEMENT)]);
+    let mut ir_generator = IRGenerator::with_config(codegen_config);
+    ir_generator.generate_ir_reporting(module, &mut diagnostics);
+    diagnostics.finish(ir_generator.dump_locals())
+}
+
+/// Compiles `source` and measures each function of the generated IR, as printed by `--stats`.
+///
+/// # Parameters
+///
+/// * `source` - The source code of the program.
+/// * `codegen_config` - The options to generate IR with, such as the optimization level.
+///
+/// # Returns
+///
+/// The instruction, basic block, and alloca counts of each defined function, in the order they are defined, or the
+/// errors of the first phase that failed.
+pub fn ir_statistics(source: &str, codegen_config: CodegenConfig) -> Result<Vec<FunctionStats>, Vec<ErrorType>> {
+    let explanation = explain_pipeline_with_config(source, codegen_config);
+    match explanation.ir {
+        Some(ir) if explanation.errors.is_empty() => Ok(function_stats(&ir)),
+        _ => Err(explanation.errors),
+    }
+}
+
+/// Inserts a comment before the first block of each annotated construct in printed IR, naming the construct and the
+/// source line it starts on, as in `; for-loop at line 12` or `; for-loop at main.c:12` after a `#line` directive.
+///
+/// Constructs are matched with their source lines in order: the n-th annotated loop of a kind starts at the n-th
+/// keyword of that kind, and a function starts at the first occurrence of its name followed by `(`. Constructs
+/// without a matching keyword are left unannotated.
+///
+/// # Parameters
+///
+/// * `ir` - The printed IR of the module.
+/// * `annotations` - The label of the first block of each construct, in the order the constructs were generated.
+/// * `tokens` - The tokens of the program with their locations.
+///
+/// # Returns
+///
+/// The IR with a comment line before each annotated block label.
+pub fn annotate_ir(ir: &str, annotations: &[(String, SourceConstruct)], tokens: &[(Token, SourceLocation)]) -> String {
+    let mut keyword_locations: Vec<(SourceConstruct, Vec<&SourceLocation>)> = [
+        (SourceConstruct::For, Token::FOR),
+        (SourceConstruct::While, Token::WHILE),
+        (SourceConstruct::DoWhile, Token::DO),
+        (SourceConstruct::If, Token::IF),
+        (SourceConstruct::Switch, Token::SWITCH),
+    ].into_iter().map(|(construct, keyword)| {
+        let locations = tokens.iter().enumerate()
+            .filter(|(index, (token, _))| *token == keyword && !(keyword == Token::WHILE && ends_do_while(tokens, *index)))
+            .map(|(_, (_, location))| location)
+            .rev()
+            .collect();
+        (construct, locations)
+    }).collect();
+
+    let mut comments = Vec::new();
+    for (label, construct) in annotations {
+        let location = match construct {
+            SourceConstruct::Function(name) => tokens.windows(2)
+                .find(|pair| pair[0].0 == Token::IDENTIFIER(Symbol::intern(name)) && pair[1].0 == Token::LPAREN)
+                .map(|pair| &pair[0].1),
+            _ => keyword_locations.iter_mut()
+                .find(|(kind, _)| kind == construct)
+                .and_then(|(_, locations)| locations.pop()),
+        };
+        if let Some(location) = location {
+            let position = match &location.file {
+                Some(file) => format!("{}:{}", file, location.line),
+                None => format!("line {}", location.line),
+            };
+            comments.push((label.as_str(), format!("; {} at {}", construct, position)));
+        }
+    }
+
+    let mut annotated = String::new();
+    for line in ir.lines() {
+        let label = line.split_once(':').map(|(label, _)| label);
+        if let Some((_, comment)) = comments.iter().find(|(block, _)| Some(*block) == label) {
+            annotated.push_str(comment);
+            annotated.push('\n');
+        }
+        annotated.push_str(line);
+        annotated.push('\n');
+    }
+    annotated
+}
+
+/// Checks whether the `while` keyword at `index` ends a do-while loop, where its condition is followed by `;`.
+fn ends_do_while(tokens: &[(Token, SourceLocation)], index: usize) -> bool {
+    let mut depth = 0;
+    for (offset, (token, _)) in tokens[index + 1..].iter().enumerate() {
+        match token {
+            Token::LPAREN => depth += 1,
+            Token::RPAREN if depth == 1 => {
+                return tokens.get(index + offset + 2).is_some_and(|(next, _)| *next == Token::SEMICOLON);
+            }
+            Token::RPAREN => depth -= 1,
+            _ => {}
+        }
+    }
+    false
+}
+
+impl PipelineExplanation {
+    /// Records the errors and warnings reported by the phases that ran.
+    fn with_diagnostics(mut self, diagnostics: DiagnosticSink) -> Self {
+        self.error_locations = diagnostics.error_locations().to_vec();
+        self.warning_locations = diagnostics.warning_locations().to_vec();
+        (self.errors, self.warnings) = diagnostics.into_parts();
+        self
+    }
+
+    /// Returns true if every phase ran without errors.
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty() && self.ir.is_some()
+    }
+
+    /// Renders one section per phase, with any errors rendered in the given language.
+    ///
+    /// # Parameters
+    ///
+    /// * `language` - The language to render error messages in.
+    pub fn render(&self, language: Language) -> String {
+        let mut rendered = String::new();
+        // Writing to a `String` cannot fail
+        let _ = self.write_sections(&mut rendered, language);
+        rendered
+    }
+
+    /// Writes one section per phase to `f`, with any errors rendered in the given language and prefixed with the
+    /// location they were found at, if any.
+    fn write_sections(&self, f: &mut impl fmt::Write, language: Language) -> fmt::Result {
+        writeln!(f, "=== Tokens ===")?;
+        for token in &self.tokens {
+            writeln!(f, "{:?}", token)?;
+        }
+
+        writeln!(f, "\n=== AST ===")?;
+        match &self.ast {
+            Some(ast) => writeln!(f, "{}", ast)?,
+            None => writeln!(f, "(not produced)")?,
+        }
+
+        writeln!(f, "\n=== Symbol Table ===")?;
+        match &self.symbol_table {
+            Some(sym_table_stack) => writeln!(f, "{}", sym_table_stack)?,
+            None => writeln!(f, "(not produced)")?,
+        }
+
+        writeln!(f, "\n=== Typed AST ===")?;
+        match &self.typed_ast {
+            Some(ast) => writeln!(f, "{}", ast)?,
+            None => writeln!(f, "(not produced)")?,
+        }
+
+        writeln!(f, "\n=== IR ===")?;
+        match &self.ir {
+            Some(ir) => writeln!(f, "{}", ir)?,
+            None => writeln!(f, "(not produced)")?,
+        }
+
+        if !self.warnings.is_empty() {
+            writeln!(f, "\n=== Warnings ===")?;
+            for (warning, location) in self.warnings.iter().zip(&self.warning_locations) {
+                writeln!(f, "{}", Diagnostic::Warning(warning, location.as_ref()).localized(language))?;
+            }
+        }
+
+        if !self.errors.is_empty() {
+            writeln!(f, "\n=== Errors ===")?;
+            for (error, location) in self.errors.iter().zip(&self.error_locations) {
+                writeln!(f, "{}", Diagnostic::Error(error, location.as_ref()).localized(language))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Provides a display implementation for `PipelineExplanation`, printing one section per phase.
+impl fmt::Display for PipelineExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_sections(f, Language::default())
+    }
+}
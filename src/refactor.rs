@@ -0,0 +1,101 @@
+//! Refactorings on source text, which editors apply to the open file as text edits.
+//!
+//! The AST does not record where the name of a declaration came from, so an identifier of the AST is matched with
+//! its token by order: the identifiers with one name appear in the AST, walked parent first, in the order their tokens appear in
+//! the source.
+
+use common::{
+    ast::{build, core::AST, node_type::NodeType, rewrite},
+    error::ErrorType,
+    location::Span,
+    symbol::Symbol,
+};
+use lexer::{core::Lexer, token::Token};
+use parser::{core::Parser, incremental::TextEdit};
+use sts::rename::rename_paths;
+
+/// The span of each identifier with one name, in source order, paired with the path of its node in the AST.
+pub(crate) type IdentifierPaths = Vec<(Span, Vec<usize>)>;
+
+/// Renames the variable, parameter, or function named at a position of the source, everywhere it is declared and
+/// referenced.
+///
+/// # Parameters
+///
+/// * `source` - The source code of the program.
+/// * `offset` - The byte offset of the cursor, anywhere in or just after an identifier naming the symbol.
+/// * `new_name` - The name to rename the symbol to.
+///
+/// # Returns
+///
+/// The edits replacing each identifier naming the symbol, in source order, or the errors that prevent the rename.
+///
+/// # Errors
+///
+/// * Returns the errors of lexing and parsing the source.
+/// * Returns an `InvalidRename` error if there is no identifier naming a declared symbol at the offset, or if the
+///   rename is refused by `sts::rename::rename_paths`.
+/// * Returns a `SyntaxError` or `ReservedKeyword` error if the new name is not a valid identifier.
+pub fn rename_at(source: &str, offset: usize, new_name: &str) -> Result<Vec<TextEdit>, Vec<ErrorType>> {
+    let tokens = Lexer::lex_with_spans(source)
+        .map_err(|errors| errors.into_iter().map(|(error, _)| error).collect::<Vec<_>>())?;
+    let invalid = |symbol: &str, reason: &str| vec![ErrorType::InvalidRename {
+        symbol: symbol.to_string(),
+        name: new_name.to_string(),
+        reason: reason.to_string(),
+    }];
+
+    let cursor = tokens.iter().find_map(|(token, span)| match token {
+        Token::IDENTIFIER(name) if span.offset <= offset && offset <= span.end() => Some((*name, *span)),
+        _ => None,
+    });
+    let (symbol, cursor_span) = cursor.ok_or_else(|| invalid("", "there is no identifier at the cursor"))?;
+    let name_errors = build::validate(&build::identifier(new_name));
+    if !name_errors.is_empty() {
+        return Err(name_errors);
+    }
+
+    let (ast, identifiers) = parse_identifiers(tokens, symbol)?;
+    let identifiers = identifiers
+        .ok_or_else(|| invalid(symbol.as_str(), "its identifiers in the program cannot be matched with the source"))?;
+    let (spans, paths): (Vec<Span>, Vec<Vec<usize>>) = identifiers.into_iter().unzip();
+
+    let at_cursor = spans.iter().position(|span| *span == cursor_span).expect("the cursor is on one of the spans");
+    let mut edits: Vec<TextEdit> = rename_paths(&ast, &paths[at_cursor], new_name)?.iter()
+        .filter_map(|path| paths.iter().position(|candidate| candidate == path))
+        .map(|index| TextEdit::new(spans[index], new_name))
+        .collect();
+    edits.sort_by_key(|edit| edit.range.offset);
+    Ok(edits)
+}
+
+/// Parses a lexed source and pairs each identifier with a name with its path in the AST.
+///
+/// # Parameters
+///
+/// * `tokens` - The tokens of the source, with their spans.
+/// * `name` - The name of the identifiers.
+///
+/// # Returns
+///
+/// The AST, and the span and path of each identifier with the name in source order, or None for them if the
+/// identifiers in the AST cannot be matched with the tokens.
+///
+/// # Errors
+///
+/// * Returns the errors of parsing the source.
+pub(crate) fn parse_identifiers(
+    tokens: Vec<(Token, Span)>,
+    name: Symbol,
+) -> Result<(AST, Option<IdentifierPaths>), Vec<ErrorType>> {
+    let spans: Vec<Span> = tokens.iter()
+        .filter(|(token, _)| *token == Token::IDENTIFIER(name))
+        .map(|(_, span)| *span)
+        .collect();
+    let ast = Parser::parse(tokens.into_iter().map(|(token, _)| token).collect())?;
+    let paths = rewrite::find(&ast, |node| node.get_node_type() == NodeType::Identifier(name.to_string()));
+    if paths.len() != spans.len() {
+        return Ok((ast, None));
+    }
+    Ok((ast, Some(spans.into_iter().zip(paths).collect())))
+}
@@ -0,0 +1,178 @@
+//! An interactive session that runs a program while its functions are being written, so a function can be
+//! redefined and the program run again with the new body without restarting the session.
+//!
+//! Each function is compiled into a module of its own, which declares the other functions of the session by their
+//! prototypes. Redefining a function recompiles only its module, unless its prototype changed, in which case the
+//! modules declaring it are recompiled too. A run hands `lli` the module defining `main` and every other module as an
+//! extra module, so each call is resolved against the bodies the session holds at the time of the run.
+
+use std::{
+    collections::BTreeMap,
+    env, fs, io,
+    path::PathBuf,
+    process::{self, Command, Output},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use common::{ast::node_type::NodeType, config::CodegenConfig, error::ErrorType};
+
+use crate::pipeline::{explain_pipeline_with_config, parse_source};
+
+/// The number of sessions created by this process, which names the directory each one writes its modules to.
+static SESSIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// A function of a session.
+///
+/// # Fields
+/// * `definition` - The source of the function's definition.
+/// * `prototype` - The source of the function's prototype, which the modules of the other functions declare it by.
+/// * `ir` - The IR of the function's module.
+#[derive(Debug, Clone)]
+struct SessionFunction {
+    definition: String,
+    prototype: String,
+    ir: String,
+}
+
+/// The functions defined so far in an interactive session, each compiled into a module of its own.
+#[derive(Debug)]
+pub struct Session {
+    codegen_config: CodegenConfig,
+    functions: BTreeMap<String, SessionFunction>,
+    lli: PathBuf,
+    directory: PathBuf,
+}
+
+impl Session {
+    /// Creates an empty session compiling with the given options. Programs are run with the `lli` named by the
+    /// `SICC_LLI` environment variable, or the one on the `PATH` if it is not set.
+    ///
+    /// # Parameters
+    ///
+    /// * `codegen_config` - The options every function is compiled with. Every function keeps external linkage,
+    ///   since the modules of the other functions call it.
+    pub fn new(codegen_config: CodegenConfig) -> Self {
+        let session = SESSIONS.fetch_add(1, Ordering::SeqCst);
+        Session {
+            codegen_config: CodegenConfig { require_main: false, ..codegen_config },
+            functions: BTreeMap::new(),
+            lli: env::var_os("SICC_LLI").map_or_else(|| PathBuf::from("lli"), PathBuf::from),
+            directory: env::temp_dir().join(format!("sicc-session-{}-{}", process::id(), session)),
+        }
+    }
+
+    /// Defines or redefines the functions of an entry, which may also declare prototypes of functions it calls
+    /// before they are defined. The entry is compiled as a whole: if any of its functions, or any function whose
+    /// module must be recompiled because a prototype changed, fails to compile, the session is left unchanged.
+    ///
+    /// # Parameters
+    ///
+    /// * `source` - The entry, made of function definitions and prototypes.
+    ///
+    /// # Returns
+    ///
+    /// The names of the functions the entry defines, or the errors it failed to compile with.
+    pub fn define(&mut self, source: &str) -> Result<Vec<String>, Vec<ErrorType>> {
+        let ast = parse_source(source)?;
+        let mut functions = self.functions.clone();
+        let mut defined = Vec::new();
+        let mut prototypes = Vec::new();
+        for node in ast.get_root().get_children() {
+            let text = node.get_span().map_or("", |span| &source[span.offset..span.end()]);
+            let name = match node.get_children().first().map(|c| c.get_node_type()) {
+                Some(NodeType::Identifier(name)) => name,
+                _ => String::new(),
+            };
+            match node.get_node_type() {
+                NodeType::FunctionDeclaration => {
+                    // A definition's prototype is everything before its body
+                    let prototype = format!("{};", text[..text.find('{').unwrap_or(text.len())].trim_end());
+                    functions.insert(name.clone(), SessionFunction {
+                        definition: text.to_string(),
+                        prototype,
+                        ir: String::new(),
+                    });
+                    defined.push(name);
+                }
+                NodeType::FunctionPrototype => prototypes.push((name, text.to_string())),
+                _ => {
+                    return Err(vec![ErrorType::SyntaxError {
+                        message: "Only functions can be defined in a session".to_string(),
+                    }]);
+                }
+            }
+        }
+        // Prototypes of functions the session defines come from their definitions instead
+        prototypes.retain(|(name, _)| !functions.contains_key(name));
+
+        // The other modules declare a function by its prototype, so they are recompiled when it changes
+        let changed_prototype = defined.iter()
+            .any(|name| self.functions.get(name).is_none_or(|old| old.prototype != functions[name].prototype));
+        let stale: Vec<String> = functions.keys()
+            .filter(|name| defined.contains(name) || (changed_prototype && self.functions.contains_key(*name)))
+            .cloned()
+            .collect();
+
+        let mut errors = Vec::new();
+        for name in stale {
+            let declarations: Vec<&str> = functions.iter()
+                .filter(|(other, _)| **other != name)
+                .map(|(_, function)| function.prototype.as_str())
+                .chain(prototypes.iter().map(|(_, prototype)| prototype.as_str()))
+                .collect();
+            let module = format!("{}\n{}", declarations.join("\n"), functions[&name].definition);
+            let explanation = explain_pipeline_with_config(&module, self.codegen_config);
+            match explanation.ir {
+                Some(ir) if explanation.errors.is_empty() => functions.get_mut(&name).unwrap().ir = ir,
+                _ => errors.extend(explanation.errors),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        self.functions = functions;
+        Ok(defined)
+    }
+
+    /// Returns the names of the functions defined so far, in alphabetical order.
+    pub fn functions(&self) -> impl Iterator<Item = &str> {
+        self.functions.keys().map(String::as_str)
+    }
+
+    /// Returns the IR of the module a function was compiled into, or None if the session does not define it.
+    pub fn module_ir(&self, name: &str) -> Option<&str> {
+        self.functions.get(name).map(|function| function.ir.as_str())
+    }
+
+    /// Runs the program the session defines with `lli`, starting at `main`, and waits for it to exit.
+    ///
+    /// # Parameters
+    ///
+    /// * `args` - The arguments passed to the program.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session does not define `main`, or if the modules could not be written or `lli`
+    /// could not be started.
+    pub fn run(&self, args: &[&str]) -> io::Result<Output> {
+        if !self.functions.contains_key("main") {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "no `main` function is defined"));
+        }
+        fs::create_dir_all(&self.directory)?;
+        let mut command = Command::new(&self.lli);
+        for (name, function) in &self.functions {
+            let path = self.directory.join(format!("{}.ll", name));
+            fs::write(&path, &function.ir)?;
+            if name != "main" {
+                command.arg(format!("-extra-module={}", path.display()));
+            }
+        }
+        command.arg(self.directory.join("main.ll")).args(args).output()
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.directory);
+    }
+}
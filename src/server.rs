@@ -0,0 +1,295 @@
+//! A compile server answering requests over a local socket, so build tools and editors can compile many programs
+//! without starting the compiler and initializing LLVM for each one.
+//!
+//! Requests and responses are JSON-RPC 2.0 messages, one per line. Every method takes the source of a program as
+//! `source` and optionally the language of its diagnostics as `lang`:
+//!
+//! * `compile` returns the IR of the program, or null if a phase failed, with the `errors` and `warnings`.
+//! * `diagnose` returns only the `errors` and `warnings`.
+//! * `run` compiles the program and runs its IR with `lli`, passing it `args`, and returns its `exit_code`,
+//!   `stdout`, and `stderr` with the diagnostics. The program is not run if it failed to compile.
+//! * `shutdown` stops the server once the connection that sent it is answered.
+//!
+//! Each error and warning is an object with its `code` and localized `message`, and the `line` and `column` it was
+//! found at when it has a location. Warnings also carry `fixes`, the replacements for the reported code that would
+//! resolve them, most likely first.
+//!
+//! The explanation of each program is kept in a bounded cache keyed like `cache::ModuleCache`, so a program that
+//! is compiled again, as an editor does on every keystroke that does not change it, is answered without running the
+//! pipeline.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    env,
+    io::{self, BufRead, BufReader, Write},
+    os::unix::net::{UnixListener, UnixStream},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use common::{config::CodegenConfig, error::ErrorType, localization::Language, location::SourceLocation, warning::WarningType};
+use threadpool::ThreadPool;
+
+use crate::{
+    cache::CacheKey,
+    json::{self, JsonValue},
+    pipeline::{explain_pipeline_with_config, PipelineExplanation},
+};
+
+/// The number of programs whose explanation a server keeps by default.
+pub const DEFAULT_CACHE_CAPACITY: usize = 64;
+
+/// The JSON-RPC error code of a request that is not valid JSON.
+pub const PARSE_ERROR: i64 = -32700;
+/// The JSON-RPC error code of a request that is not a JSON-RPC request.
+pub const INVALID_REQUEST: i64 = -32600;
+/// The JSON-RPC error code of a request for a method the server does not have.
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// The JSON-RPC error code of a request whose parameters are missing or of the wrong type.
+pub const INVALID_PARAMS: i64 = -32602;
+/// The JSON-RPC error code of a request the server failed on.
+pub const INTERNAL_ERROR: i64 = -32603;
+/// The error code of a `run` request whose program could not be started.
+pub const RUN_FAILED: i64 = -32000;
+
+/// The explanations of the programs compiled most recently, evicting the oldest once full.
+#[derive(Debug, Default)]
+struct WarmCache {
+    entries: HashMap<CacheKey, Arc<PipelineExplanation>>,
+    order: VecDeque<CacheKey>,
+}
+
+/// Answers requests with a fixed set of codegen options, sharing its cache between every connection.
+#[derive(Debug)]
+pub struct Server {
+    codegen_config: CodegenConfig,
+    capacity: usize,
+    cache: Mutex<WarmCache>,
+    lli: PathBuf,
+    shutting_down: AtomicBool,
+}
+
+impl Server {
+    /// Creates a server compiling with the given options. Programs are run with the `lli` named by the `SICC_LLI`
+    /// environment variable, or the one on the `PATH`.
+    ///
+    /// # Parameters
+    ///
+    /// * `codegen_config` - The options every program is compiled with.
+    /// * `capacity` - The number of programs whose explanation is kept.
+    pub fn new(codegen_config: CodegenConfig, capacity: usize) -> Self {
+        Self {
+            codegen_config,
+            capacity,
+            cache: Mutex::new(WarmCache::default()),
+            lli: env::var_os("SICC_LLI").map_or_else(|| PathBuf::from("lli"), PathBuf::from),
+            shutting_down: AtomicBool::new(false),
+        }
+    }
+
+    /// Checks whether a `shutdown` request has been answered.
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+
+    /// Answers one line of a connection.
+    ///
+    /// # Parameters
+    ///
+    /// * `line` - The request, as a JSON-RPC message.
+    ///
+    /// # Returns
+    ///
+    /// The response, or None if the request is a notification, which has no `id` and is not answered.
+    pub fn handle_request(&self, line: &str) -> Option<String> {
+        let request = match json::parse(line) {
+            Ok(request) => request,
+            Err(message) => return Some(error_response(JsonValue::Null, PARSE_ERROR, &message).to_string()),
+        };
+        let id = request.get("id").cloned();
+        let response = match request.get("method").and_then(JsonValue::as_str) {
+            Some(method) => {
+                let params = request.get("params").cloned().unwrap_or(JsonValue::Object(Vec::new()));
+                // A panic in the compiler fails the request rather than the connection
+                panic::catch_unwind(AssertUnwindSafe(|| self.dispatch(method, &params)))
+                    .unwrap_or_else(|_| Err((INTERNAL_ERROR, format!("the compiler panicked on `{}`", method))))
+            }
+            None => Err((INVALID_REQUEST, "the request has no method".to_string())),
+        };
+        let id = id?;
+        Some(match response {
+            Ok(result) => JsonValue::object(vec![("jsonrpc", "2.0".into()), ("id", id), ("result", result)]),
+            Err((code, message)) => error_response(id, code, &message),
+        }.to_string())
+    }
+
+    fn dispatch(&self, method: &str, params: &JsonValue) -> Result<JsonValue, (i64, String)> {
+        if method == "shutdown" {
+            self.shutting_down.store(true, Ordering::SeqCst);
+            return Ok(JsonValue::Null);
+        }
+        if !matches!(method, "compile" | "diagnose" | "run") {
+            return Err((METHOD_NOT_FOUND, format!("no method `{}`", method)));
+        }
+        let source = params.get("source").and_then(JsonValue::as_str)
+            .ok_or_else(|| (INVALID_PARAMS, "`source` must be a string".to_string()))?;
+        let language = match params.get("lang") {
+            None => Language::default(),
+            Some(lang) => lang.as_str().ok_or_else(|| (INVALID_PARAMS, "`lang` must be a string".to_string()))?
+                .parse().map_err(|message| (INVALID_PARAMS, message))?,
+        };
+        let explanation = self.explain(source);
+        let diagnostics = diagnostics(&explanation, language);
+        match method {
+            "compile" => {
+                let mut result = vec![("ir", explanation.ir.clone().into())];
+                result.extend(diagnostics);
+                Ok(JsonValue::object(result))
+            }
+            "diagnose" => Ok(JsonValue::object(diagnostics)),
+            _ => {
+                let args = match params.get("args") {
+                    None => Vec::new(),
+                    Some(args) => args.as_array().and_then(|args| args.iter().map(JsonValue::as_str).collect())
+                        .ok_or_else(|| (INVALID_PARAMS, "`args` must be an array of strings".to_string()))?,
+                };
+                let mut result = match &explanation.ir {
+                    Some(ir) => self.run(ir, &args).map_err(|e| (RUN_FAILED, format!("could not run `lli`: {}", e)))?,
+                    None => vec![("exit_code", JsonValue::Null), ("stdout", "".into()), ("stderr", "".into())],
+                };
+                result.extend(diagnostics);
+                Ok(JsonValue::object(result))
+            }
+        }
+    }
+
+    /// Returns the explanation of a program, running the pipeline only if it is not cached.
+    fn explain(&self, source: &str) -> Arc<PipelineExplanation> {
+        let key = CacheKey::new(source, &[format!("{:?}", self.codegen_config)]);
+        if let Some(explanation) = self.cache.lock().unwrap().entries.get(&key) {
+            return Arc::clone(explanation);
+        }
+        // The lock is not held while compiling, so other connections are answered meanwhile
+        let explanation = Arc::new(explain_pipeline_with_config(source, self.codegen_config));
+        let mut cache = self.cache.lock().unwrap();
+        if self.capacity > 0 && cache.entries.insert(key.clone(), Arc::clone(&explanation)).is_none() {
+            cache.order.push_back(key);
+            while cache.order.len() > self.capacity {
+                let oldest = cache.order.pop_front().unwrap();
+                cache.entries.remove(&oldest);
+            }
+        }
+        explanation
+    }
+
+    /// Runs IR with `lli`, feeding it the IR on its standard input.
+    fn run(&self, ir: &str, args: &[&str]) -> io::Result<Vec<(&'static str, JsonValue)>> {
+        let mut child = Command::new(&self.lli)
+            .arg("-")
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        child.stdin.take().unwrap().write_all(ir.as_bytes())?;
+        let output = child.wait_with_output()?;
+        Ok(vec![
+            ("exit_code", output.status.code().map_or(JsonValue::Null, |code| JsonValue::Number(code.into()))),
+            ("stdout", String::from_utf8_lossy(&output.stdout).into_owned().into()),
+            ("stderr", String::from_utf8_lossy(&output.stderr).into_owned().into()),
+        ])
+    }
+}
+
+/// Lists the errors and warnings of an explanation, each as its code, localized message, and the line and column
+/// it was found at, if any. Warnings also list their fix-its as `fixes`.
+fn diagnostics(explanation: &PipelineExplanation, language: Language) -> Vec<(&'static str, JsonValue)> {
+    let diagnostic = |code: &str, message: String, location: &Option<SourceLocation>| {
+        let mut fields = vec![("code", code.into()), ("message", message.into())];
+        if let Some(location) = location {
+            fields.push(("line", JsonValue::Number(location.line as f64)));
+            fields.push(("column", JsonValue::Number(location.column as f64)));
+        }
+        fields
+    };
+    let error = |(e, location): (&ErrorType, &Option<SourceLocation>)| {
+        JsonValue::object(diagnostic(e.code(), e.localized(language), location))
+    };
+    let warning = |(w, location): (&WarningType, &Option<SourceLocation>)| {
+        let mut fields = diagnostic(w.code(), w.localized(language), location);
+        fields.push(("fixes", JsonValue::Array(w.fixes().into_iter().map(JsonValue::from).collect())));
+        JsonValue::object(fields)
+    };
+    vec![
+        ("errors", JsonValue::Array(explanation.errors.iter().zip(&explanation.error_locations).map(error).collect())),
+        ("warnings", JsonValue::Array(explanation.warnings.iter().zip(&explanation.warning_locations).map(warning).collect())),
+    ]
+}
+
+fn error_response(id: JsonValue, code: i64, message: &str) -> JsonValue {
+    let error = JsonValue::object(vec![("code", JsonValue::Number(code as f64)), ("message", message.into())]);
+    JsonValue::object(vec![("jsonrpc", "2.0".into()), ("id", id), ("error", error)])
+}
+
+/// Listens on a Unix socket and answers the requests of every connection until a `shutdown` request, handling up to
+/// `workers` connections at once. The socket file is removed when the server stops accepting connections.
+///
+/// # Parameters
+///
+/// * `server` - The server answering the requests.
+/// * `socket` - The path of the socket, which must not exist.
+/// * `workers` - The number of connections handled at once.
+///
+/// # Errors
+///
+/// * Returns the error of binding the socket or accepting a connection.
+pub fn serve(server: Arc<Server>, socket: &Path, workers: usize) -> io::Result<()> {
+    let listener = UnixListener::bind(socket)?;
+    let pool = ThreadPool::new(workers.max(1));
+    for stream in listener.incoming() {
+        if server.is_shutting_down() {
+            break;
+        }
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                let _ = std::fs::remove_file(socket);
+                return Err(e);
+            }
+        };
+        let server = Arc::clone(&server);
+        let socket = socket.to_path_buf();
+        pool.execute(move || {
+            let _ = handle_connection(&server, stream);
+            if server.is_shutting_down() {
+                // Wakes the accept loop, which is blocked until the next connection
+                let _ = UnixStream::connect(&socket);
+            }
+        });
+    }
+    // Connections still open are not waited for, as a client may keep one open without sending anything
+    std::fs::remove_file(socket)
+}
+
+/// Answers the requests of one connection until it is closed or the server shuts down.
+fn handle_connection(server: &Server, stream: UnixStream) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = server.handle_request(&line) {
+            writeln!(writer, "{}", response)?;
+        }
+        if server.is_shutting_down() {
+            break;
+        }
+    }
+    Ok(())
+}
@@ -1,13 +1,16 @@
 //! This file contains symbol table stack generation functions for nodes that contain code blocks.
 
 use common::{
-    ast::core::ASTNode,
+    ast::{core::ASTNode, data_type::DataType, node_type::NodeType},
     error::ErrorType,
 };
-use crate::core::SymbolTableStack;
+use crate::core::{declared_symbol, SymbolInfo, SymbolTableStack, SymbolValue};
 
 impl SymbolTableStack {
     /// Processes function declarations from an AST and pushes information to the STS.
+    ///
+    /// The function is added to the current scope with its return type and parameters. A function with a body opens
+    /// a scope for it, holding its parameters along with the variables declared directly in the body.
     /// 
     /// # Parameters
     ///
@@ -18,11 +21,38 @@ impl SymbolTableStack {
     /// Returns a `Result` with Ok indicating the process was successful or Err containing a 
     /// vector if errors if there were any.
     pub fn sym_table_fn(&mut self, node: &ASTNode) -> Result<(), Vec<ErrorType>> {
-        let _ = node;
-        unimplemented!();
+        let children = node.get_children();
+        let name = match children.first().map(|c| c.get_node_type()) {
+            Some(NodeType::Identifier(name)) => name,
+            _ => return Err(vec![ErrorType::DevError { message: "Function declaration has no name".to_string() }]),
+        };
+        let return_type = children.iter()
+            .find_map(|c| match c.get_node_type() {
+                NodeType::Type(data_type) => Some(data_type),
+                _ => None,
+            })
+            .unwrap_or(DataType::Void);
+        let parameters: Vec<(String, DataType)> = children.iter()
+            .filter(|c| c.get_node_type() == NodeType::Parameter)
+            .filter_map(declared_symbol)
+            .collect();
+        self.add_element(name, SymbolInfo::new(return_type, SymbolValue::FunctionValue { parameters: parameters.clone() }))
+            .map_err(|e| vec![e])?;
+
+        let Some(body) = children.iter().find(|c| c.get_node_type() == NodeType::BlockExpression) else {
+            return Ok(());
+        };
+        self.open_scope();
+        for (name, data_type) in parameters {
+            self.add_element(name, SymbolInfo::new(data_type, SymbolValue::NoAssociatedValue)).map_err(|e| vec![e])?;
+        }
+        let result = self.sym_table_children(body);
+        self.close_scope();
+        result
     }
 
-    /// Processes for loop declarations from an AST and pushes a new stack for the for loop's condition.
+    /// Processes for loop declarations from an AST and pushes a new stack for the for loop's condition, which holds
+    /// the variable its initializer declares. The loop's body opens a scope of its own inside it.
     /// 
     /// # Parameters
     ///
@@ -33,22 +63,26 @@ impl SymbolTableStack {
     /// Returns a `Result` with Ok indicating the process was successful or Err containing a 
     /// vector if errors if there were any.
     pub fn sym_table_for(&mut self, node: &ASTNode) -> Result<(), Vec<ErrorType>> {
-        let _ = node;
-        unimplemented!();
+        self.open_scope();
+        let result = self.sym_table_children(node);
+        self.close_scope();
+        result
     }
 
     /// Processes block expressions from an AST by pushing a new table onto the STS. 
     /// 
     /// # Parameters
     ///
-    /// - `for_node`: A reference to an `ASTNode` containing a for loop definition.
+    /// - `block_node`: A reference to an `ASTNode` containing a block expression.
     /// 
     /// # Returns
     ///
     /// Returns a `Result` with Ok indicating the process was successful or Err containing a 
     /// vector if errors if there were any.
     pub fn sym_table_block(&mut self, node: &ASTNode) -> Result<(), Vec<ErrorType>> {
-        let _ = node;
-        unimplemented!();
+        self.open_scope();
+        let result = self.sym_table_children(node);
+        self.close_scope();
+        result
     }
 }
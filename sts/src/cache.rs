@@ -0,0 +1,117 @@
+//! Encodes an analyzed program, its AST and symbol table stack, for incremental compilation caches to keep between
+//! compilations instead of analyzing unchanged source again.
+//!
+//! The encoding uses the binary format of `common::ast::encode`: the AST is followed by each table of the stack in
+//! order, and each table by its symbols sorted by name, so that the same program always has the same encoding.
+
+use common::ast::{
+    core::AST,
+    encode::{Decoder, Encoder},
+};
+
+use crate::core::{SymbolInfo, SymbolTable, SymbolTableStack, SymbolValue};
+
+/// The tags of the kinds of symbol value.
+const NO_VALUE_TAG: usize = 0;
+const ENUM_TAG: usize = 1;
+const STRUCT_TAG: usize = 2;
+const FUNCTION_TAG: usize = 3;
+
+/// Encodes an analyzed program.
+///
+/// # Parameters
+///
+/// * `ast` - The AST returned by the analysis.
+/// * `sym_table_stack` - The symbol table stack returned by the analysis.
+///
+/// # Returns
+///
+/// The encoding.
+pub fn encode(ast: &AST, sym_table_stack: &SymbolTableStack) -> Vec<u8> {
+    let mut encoder = Encoder::new();
+    encoder.write_node(&ast.get_root());
+    encoder.write_usize(sym_table_stack.size());
+    for table in sym_table_stack.get_elements() {
+        let entries = table.entries();
+        encoder.write_usize(entries.len());
+        for (name, info) in entries {
+            encoder.write_str(name);
+            encoder.write_data_type(info.get_data_type());
+            write_value(&mut encoder, &info.get_value());
+        }
+    }
+    encoder.finish()
+}
+
+fn write_value(encoder: &mut Encoder, value: &SymbolValue) {
+    match value {
+        SymbolValue::NoAssociatedValue => encoder.write_usize(NO_VALUE_TAG),
+        SymbolValue::EnumValue { variants } => {
+            encoder.write_usize(ENUM_TAG);
+            encoder.write_usize(variants.len());
+            for variant in variants {
+                encoder.write_str(variant);
+            }
+        }
+        SymbolValue::StructValue { fields: members } | SymbolValue::FunctionValue { parameters: members } => {
+            let tag = if matches!(value, SymbolValue::StructValue { .. }) { STRUCT_TAG } else { FUNCTION_TAG };
+            encoder.write_usize(tag);
+            encoder.write_usize(members.len());
+            for (name, data_type) in members {
+                encoder.write_str(name);
+                encoder.write_data_type(*data_type);
+            }
+        }
+    }
+}
+
+/// Decodes an analyzed program encoded by `encode`.
+///
+/// # Parameters
+///
+/// * `bytes` - The encoding.
+///
+/// # Returns
+///
+/// The AST and symbol table stack, or None if the bytes are not exactly the encoding of an analyzed program in this
+/// format and version, in which case the program should be analyzed again.
+pub fn decode(bytes: &[u8]) -> Option<(AST, SymbolTableStack)> {
+    let mut decoder = Decoder::new(bytes)?;
+    let ast = AST::new(decoder.read_node()?);
+    let mut sym_table_stack = SymbolTableStack::new();
+    for _ in 0..decoder.read_usize()? {
+        let mut table = SymbolTable::new();
+        for _ in 0..decoder.read_usize()? {
+            let name = decoder.read_str()?.to_string();
+            let data_type = decoder.read_data_type()?;
+            table.add(name, SymbolInfo::new(data_type, read_value(&mut decoder)?));
+        }
+        sym_table_stack.push(table);
+    }
+    decoder.is_finished().then_some((ast, sym_table_stack))
+}
+
+fn read_value(decoder: &mut Decoder) -> Option<SymbolValue> {
+    let tag = decoder.read_usize()?;
+    if tag == NO_VALUE_TAG {
+        return Some(SymbolValue::NoAssociatedValue);
+    }
+    let count = decoder.read_usize()?;
+    let value = match tag {
+        ENUM_TAG => SymbolValue::EnumValue {
+            variants: (0..count).map(|_| decoder.read_str().map(str::to_string)).collect::<Option<_>>()?,
+        },
+        STRUCT_TAG | FUNCTION_TAG => {
+            let members = (0..count)
+                .map(|_| Some((decoder.read_str()?.to_string(), decoder.read_data_type()?)))
+                .collect::<Option<Vec<_>>>()?;
+            if tag == STRUCT_TAG {
+                SymbolValue::StructValue { fields: members }
+            } else {
+                SymbolValue::FunctionValue { parameters: members }
+            }
+        }
+        _ => return None,
+    };
+    Some(value)
+}
@@ -5,6 +5,7 @@ use common::{
     ast::{
         core::{ASTNode, AST}, 
         data_type::DataType,
+        node_type::NodeType,
     }, 
     error::ErrorType
 };
@@ -140,6 +141,18 @@ impl SymbolTable {
     pub fn is_empty(&self) -> bool {
         self.values.is_empty()
     }
+
+    /// Lists the symbols of the table.
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<(&String, &SymbolInfo)>` - The name and `SymbolInfo` of each symbol, sorted by name.
+    ///
+    pub fn entries(&self) -> Vec<(&String, &SymbolInfo)> {
+        let mut entries: Vec<(&String, &SymbolInfo)> = self.values.iter().collect();
+        entries.sort_by_key(|(name, _)| *name);
+        entries
+    }
 }
 
 impl fmt::Debug for SymbolTable {
@@ -152,10 +165,32 @@ impl fmt::Debug for SymbolTable {
     }
 }
 
+/// Provides a display implementation for `SymbolInfo`, as in `Integer function(count: Integer)`.
+impl fmt::Display for SymbolInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |items: Vec<String>| items.join(", ");
+        match &self.value {
+            SymbolValue::NoAssociatedValue => write!(f, "{}", self.data_type),
+            SymbolValue::EnumValue { variants } => write!(f, "{} {{{}}}", self.data_type, variants.join(", ")),
+            SymbolValue::StructValue { fields } => write!(f, "{} {{{}}}", self.data_type, list(
+                fields.iter().map(|(name, data_type)| format!("{}: {}", name, data_type)).collect(),
+            )),
+            SymbolValue::FunctionValue { parameters } => write!(f, "{} function({})", self.data_type, list(
+                parameters.iter().map(|(name, data_type)| format!("{}: {}", name, data_type)).collect(),
+            )),
+        }
+    }
+}
+
 /// A stack of symbol tables, used to represent different levels of scopes for an AST's symbols.
+///
+/// Tables are kept in the order their scopes open in the program, and are not removed when their scopes close, so
+/// the stack lists every scope of the program. While the stack is generated, symbols are added to the innermost
+/// scope still open.
 #[derive(Clone)]
 pub struct SymbolTableStack {
     elements: Vec<SymbolTable>,
+    open_scopes: Vec<usize>,
 }
 
 impl SymbolTableStack {
@@ -170,8 +205,11 @@ impl SymbolTableStack {
     /// Returns a `Result` containing an `AST` and `SymbolTableStack` pair if Ok and a vector
     /// of errors if any errors were encountered.
     pub fn gen_sym_table_stack(ast: AST) -> Result<(AST, SymbolTableStack), Vec<ErrorType>> {
-        let _ = ast;
-        unimplemented!()
+        let mut stack = SymbolTableStack::new();
+        stack.open_scope();
+        stack.sym_table_stack_router(&ast.get_root())?;
+        stack.close_scope();
+        Ok((ast, stack))
     }
 
     /// Routes the generation of the SymbolTableStack based on the type of node encountered.
@@ -189,8 +227,47 @@ impl SymbolTableStack {
     ///
     /// Returns a vector of errors if there was a problem during STS generation.
     pub fn sym_table_stack_router(&mut self, node: &ASTNode) -> Result<(), Vec<ErrorType>> {
-        let _ = node;
-        unimplemented!()
+        match node.get_node_type() {
+            NodeType::FunctionDeclaration | NodeType::FunctionPrototype => self.sym_table_fn(node),
+            NodeType::ForLoop => self.sym_table_for(node),
+            NodeType::BlockExpression => self.sym_table_block(node),
+            NodeType::Initialization => self.sym_table_init(node),
+            NodeType::StructDeclaration => self.sym_table_struct(node),
+            NodeType::EnumDeclaration => self.sym_table_enum(node),
+            _ => self.sym_table_children(node),
+        }
+    }
+
+    /// Routes each child of a node in turn, continuing past children that fail so that every error is reported.
+    ///
+    /// # Parameters
+    ///
+    /// - `node`: A reference to the `ASTNode` whose children are routed.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` with Ok indicating the process was successful or Err containing the errors of every
+    /// child that failed.
+    pub(crate) fn sym_table_children(&mut self, node: &ASTNode) -> Result<(), Vec<ErrorType>> {
+        let mut errors = Vec::new();
+        for child in node.get_children() {
+            if let Err(child_errors) = self.sym_table_stack_router(&child) {
+                errors.extend(child_errors);
+            }
+        }
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+
+    /// Pushes a new table onto the stack and makes it the scope symbols are added to until it is closed.
+    pub(crate) fn open_scope(&mut self) {
+        self.open_scopes.push(self.elements.len());
+        self.elements.push(SymbolTable::new());
+    }
+
+    /// Closes the innermost open scope, so symbols are again added to the scope enclosing it. Its table stays on
+    /// the stack.
+    pub(crate) fn close_scope(&mut self) {
+        self.open_scopes.pop();
     }
 
 
@@ -205,6 +282,7 @@ impl SymbolTableStack {
     pub fn new() -> Self {
         SymbolTableStack {
             elements: Vec::new(),
+            open_scopes: Vec::new(),
         }
     }
 
@@ -238,7 +316,7 @@ impl SymbolTableStack {
         &self.elements
     }
 
-    /// Adds an element to the symbol table at the specified index (or global scope by default).
+    /// Adds an element to the table of the innermost open scope, or to the top table if no scope is open.
     /// 
     /// # Parameters
     ///
@@ -250,8 +328,8 @@ impl SymbolTableStack {
     /// Returns a `Result<(), Vec<ErrorType>>` with Ok indicating the process was successful or
     /// Err containing an error encountered if any were encountered.
     pub fn add_element(&mut self, name: String, info: SymbolInfo) -> Result<(), ErrorType> {
-        let index = self.size() - 1;
-        if let Some(table) = self.elements.get_mut(index) {
+        let index = self.open_scopes.last().copied().or_else(|| self.size().checked_sub(1));
+        if let Some(table) = index.and_then(|index| self.elements.get_mut(index)) {
             table.add(name, info);
             Ok(())
         } else {
@@ -285,9 +363,27 @@ impl SymbolTableStack {
     }
 }
 
+/// Provides a display implementation for `SymbolTableStack`, listing each scope by its index in the stack with its
+/// symbols sorted by name, as in:
+///
+/// ```text
+/// scope 0:
+///   main: Integer function()
+/// scope 1:
+///   x: Integer
+/// ```
 impl fmt::Display for SymbolTableStack {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.to_string())
+        for (index, table) in self.elements.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "scope {}:", index)?;
+            for (name, info) in table.entries() {
+                write!(f, "\n  {}: {}", name, info)?;
+            }
+        }
+        Ok(())
     }
 }
 
@@ -337,4 +433,22 @@ impl PartialEq for SymbolTableStack {
     }
 }
 
-impl Eq for SymbolTableStack {}
\ No newline at end of file
+impl Eq for SymbolTableStack {}
+
+/// Reads the name and type of a declaration whose children are its `Identifier` and its `Type`, such as a
+/// `Variable` or `Parameter` node.
+///
+/// # Parameters
+///
+/// - `node`: A reference to the declaring `ASTNode`.
+///
+/// # Returns
+///
+/// Returns the declared name and `DataType`, or None if the node does not have both.
+pub(crate) fn declared_symbol(node: &ASTNode) -> Option<(String, DataType)> {
+    let children = node.get_children();
+    match (children.first().map(|c| c.get_node_type()), children.get(1).map(|c| c.get_node_type())) {
+        (Some(NodeType::Identifier(name)), Some(NodeType::Type(data_type))) => Some((name, data_type)),
+        _ => None,
+    }
+}
\ No newline at end of file
@@ -0,0 +1,520 @@
+//! Contains data-flow facts computed over a parsed program, which optimization passes consult to decide whether a
+//! transformation preserves the program's behavior.
+//!
+//! The facts are conservative: a variable whose address is taken counts as written, since it may be written through
+//! the pointer, and any function call is reported so that passes can assume it reads and writes everything.
+//!
+//! The live-variables analysis finds, at each point of a function, the local variables whose current value may still
+//! be read, and from it the stores whose value never is.
+
+use std::collections::{HashMap, HashSet};
+
+use common::ast::{core::{ASTNode, AST}, node_type::NodeType};
+
+/// Counts the writes to each variable in a piece of code: assignments, compound assignments, declarations, `++` and
+/// `--`, and taking the variable's address.
+///
+/// # Parameters
+///
+/// * `node` - The code to inspect.
+///
+/// # Returns
+///
+/// The number of places each written variable is written, keyed by name.
+pub fn written_variables(node: &ASTNode) -> HashMap<String, usize> {
+    let mut writes = HashMap::new();
+    let mut pending = vec![node.clone()];
+    while let Some(node) = pending.pop() {
+        let children = node.get_children();
+        let target = match node.get_node_type() {
+            NodeType::Assignment | NodeType::CompoundAssignment => children.first().cloned(),
+            NodeType::Initialization => children.first().and_then(|variable| variable.get_children().first().cloned()),
+            NodeType::UnaryExpression => match children.first().map(|c| c.get_node_type()) {
+                Some(NodeType::Operator(op)) if matches!(op.as_str(), "++" | "--" | "&") => children.get(1).cloned(),
+                _ => None,
+            },
+            NodeType::PostfixExpression => children.first().cloned(),
+            _ => None,
+        };
+        if let Some(NodeType::Identifier(name)) = target.map(|t| t.get_node_type()) {
+            *writes.entry(name).or_insert(0) += 1;
+        }
+        pending.extend(children);
+    }
+    writes
+}
+
+/// Counts the mentions of each name in a piece of code, whether it is read, written, or declared.
+///
+/// # Parameters
+///
+/// * `node` - The code to inspect.
+///
+/// # Returns
+///
+/// The number of `Identifier` nodes naming each name, keyed by name.
+pub fn mentioned_variables(node: &ASTNode) -> HashMap<String, usize> {
+    let mut mentions = HashMap::new();
+    let mut pending = vec![node.clone()];
+    while let Some(node) = pending.pop() {
+        if let NodeType::Identifier(name) = node.get_node_type() {
+            *mentions.entry(name).or_insert(0) += 1;
+        }
+        pending.extend(node.get_children());
+    }
+    mentions
+}
+
+/// Finds the variables declared `volatile` in a piece of code, whose accesses must never be moved or removed.
+///
+/// # Parameters
+///
+/// * `node` - The code to inspect.
+///
+/// # Returns
+///
+/// The names of the `volatile` variables.
+pub fn volatile_variables(node: &ASTNode) -> HashSet<String> {
+    let mut volatile = HashSet::new();
+    let mut pending = vec![node.clone()];
+    while let Some(node) = pending.pop() {
+        let children = node.get_children();
+        if node.get_node_type() == NodeType::Variable {
+            let declares_volatile = children.iter()
+                .filter(|child| matches!(child.get_node_type(), NodeType::Type(_)))
+                .flat_map(|type_node| type_node.get_children())
+                .any(|qualifier| qualifier.get_node_type() == NodeType::Qualifier("volatile".to_string()));
+            if let (true, Some(NodeType::Identifier(name))) = (declares_volatile, children.first().map(|c| c.get_node_type())) {
+                volatile.insert(name);
+            }
+        }
+        pending.extend(children);
+    }
+    volatile
+}
+
+/// Checks whether a piece of code calls a function, which may read or write any global or escaped variable.
+///
+/// # Parameters
+///
+/// * `node` - The code to inspect.
+///
+/// # Returns
+///
+/// True if the code contains a `FunctionCall` node.
+pub fn contains_call(node: &ASTNode) -> bool {
+    node.get_node_type() == NodeType::FunctionCall || node.get_children().iter().any(contains_call)
+}
+
+/// A store to a local variable whose value is never read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadStore {
+    /// The position of the `Assignment` or `Initialization` node, as the index of each node on the way down from
+    /// the root of the program.
+    pub path: Vec<usize>,
+    /// The name of the variable stored to.
+    pub variable: String,
+    /// Whether the stored value can be computed without side effects, so the store can be removed entirely.
+    pub removable: bool,
+}
+
+/// The live-variables analysis of one function, a backward analysis over its statements that finds the variables
+/// whose current value may still be read.
+struct LivenessAnalysis {
+    /// The local variables that are tracked: those declared exactly once, never at the top level, and neither
+    /// `volatile` nor address-taken, so that no store to them can be observed except by reading the name.
+    tracked: HashSet<String>,
+    /// The live variables at the targets of `break` and `continue` in the enclosing loops and switches, innermost last.
+    break_targets: Vec<HashSet<String>>,
+    continue_targets: Vec<HashSet<String>>,
+    /// The position of the node being analyzed.
+    path: Vec<usize>,
+    /// Whether dead stores are recorded, which is only done once the live variables around every loop are final.
+    recording: bool,
+    dead_stores: Vec<DeadStore>,
+}
+
+impl LivenessAnalysis {
+    /// Computes the variables live before a statement from those live after it.
+    fn statement(&mut self, node: &ASTNode, live_after: &HashSet<String>) -> HashSet<String> {
+        let children = node.get_children();
+        match node.get_node_type() {
+            NodeType::BlockExpression | NodeType::ElseStatement | NodeType::LoopInitializer | NodeType::LoopIncrement => {
+                (0..children.len()).rev().fold(live_after.clone(), |live, index| self.child(&children, index, &live))
+            }
+            NodeType::Assignment | NodeType::Initialization => self.store(node, live_after),
+            NodeType::Return => read_variables(node),
+            NodeType::Break => self.break_targets.last().cloned().unwrap_or_else(|| live_after.clone()),
+            NodeType::Continue => self.continue_targets.last().cloned().unwrap_or_else(|| live_after.clone()),
+            NodeType::IfStatement if !children.is_empty() => {
+                // Without an else branch, the conditions may skip straight past the statement
+                let has_else = children.len() > 2
+                    && children.last().is_some_and(|c| c.get_node_type() != NodeType::ElseIf);
+                let mut live = if has_else { HashSet::new() } else { live_after.clone() };
+                for index in 1..children.len() {
+                    live.extend(self.child(&children, index, live_after));
+                }
+                live.extend(read_variables(&children[0]));
+                live
+            }
+            // An else if is only reached when the conditions before it are false, which the if it belongs to
+            // accounts for
+            NodeType::ElseIf if children.len() == 2 => {
+                let mut live = self.child(&children, 1, live_after);
+                live.extend(read_variables(&children[0]));
+                live
+            }
+            NodeType::WhileLoop if children.len() == 2 => {
+                let condition = read_variables(&children[0]);
+                self.fixpoint(live_after, |analysis, head| {
+                    analysis.continue_targets.push(head.clone());
+                    let mut live = analysis.child(&children, 1, head);
+                    analysis.continue_targets.pop();
+                    live.extend(live_after.iter().cloned());
+                    live.extend(condition.iter().cloned());
+                    live
+                })
+            }
+            NodeType::DoWhileLoop if children.len() == 2 => {
+                let condition = read_variables(&children[1]);
+                self.fixpoint(live_after, |analysis, head| {
+                    let mut test = head.clone();
+                    test.extend(live_after.iter().cloned());
+                    test.extend(condition.iter().cloned());
+                    analysis.continue_targets.push(test.clone());
+                    let live = analysis.child(&children, 0, &test);
+                    analysis.continue_targets.pop();
+                    live
+                })
+            }
+            NodeType::ForLoop if children.len() == 4 => {
+                let condition = read_variables(&children[1]);
+                let head = self.fixpoint(live_after, |analysis, head| {
+                    let increment = analysis.child(&children, 2, head);
+                    analysis.continue_targets.push(increment.clone());
+                    let mut live = analysis.child(&children, 3, &increment);
+                    analysis.continue_targets.pop();
+                    live.extend(live_after.iter().cloned());
+                    live.extend(condition.iter().cloned());
+                    live
+                });
+                self.child(&children, 0, &head)
+            }
+            NodeType::SwitchStatement if children.len() == 2 => {
+                let arms = children[1].get_children();
+                let has_default = arms.iter().any(|arm| arm.get_node_type() == NodeType::Default);
+                let mut live = if has_default { HashSet::new() } else { live_after.clone() };
+
+                // Each arm falls through to the next, and the last one to the end of the switch
+                self.break_targets.push(live_after.clone());
+                self.path.push(1);
+                let mut next = live_after.clone();
+                for (index, arm) in arms.iter().enumerate().rev() {
+                    let arm_children = arm.get_children();
+                    if let Some(body) = arm_children.len().checked_sub(1) {
+                        self.path.push(index);
+                        next = self.child(&arm_children, body, &next);
+                        self.path.pop();
+                    }
+                    live.extend(next.iter().cloned());
+                }
+                self.path.pop();
+                self.break_targets.pop();
+
+                live.extend(read_variables(&children[0]));
+                live
+            }
+            _ => {
+                // An unrecognized statement reads everything it mentions, and may jump to any enclosing target
+                let mut live = live_after.clone();
+                live.extend(read_variables(node));
+                if contains_jump(node) {
+                    live.extend(self.break_targets.iter().chain(&self.continue_targets).flatten().cloned());
+                }
+                live
+            }
+        }
+    }
+
+    /// Analyzes the child at `index` of the node being analyzed.
+    fn child(&mut self, children: &[ASTNode], index: usize, live_after: &HashSet<String>) -> HashSet<String> {
+        self.path.push(index);
+        let live = self.statement(&children[index], live_after);
+        self.path.pop();
+        live
+    }
+
+    /// Analyzes an `Assignment` or `Initialization`, recording it as a dead store if its variable is tracked and not
+    /// live after it.
+    fn store(&mut self, node: &ASTNode, live_after: &HashSet<String>) -> HashSet<String> {
+        let children = node.get_children();
+        let (target, value) = match node.get_node_type() {
+            NodeType::Initialization => (
+                children.first().and_then(|variable| variable.get_children().first().cloned()),
+                children.get(1).and_then(|assigned| assigned.get_children().first().cloned()),
+            ),
+            _ => (children.first().cloned(), children.get(1).cloned()),
+        };
+        let name = match target.map(|t| t.get_node_type()) {
+            Some(NodeType::Identifier(name)) if self.tracked.contains(&name) => name,
+            _ => {
+                let mut live = live_after.clone();
+                live.extend(read_variables(node));
+                return live;
+            }
+        };
+
+        let mut live = live_after.clone();
+        live.remove(&name);
+        if let Some(value) = value {
+            if self.recording && !live_after.contains(&name) {
+                self.dead_stores.push(DeadStore {
+                    path: self.path.clone(),
+                    variable: name,
+                    removable: !has_side_effects(&value),
+                });
+            }
+            live.extend(read_variables(&value));
+        }
+        live
+    }
+
+    /// Iterates the body of a loop until the variables live at its head stop changing, then analyzes it once more
+    /// to record its dead stores.
+    ///
+    /// # Parameters
+    ///
+    /// * `live_after` - The variables live after the loop, which are live at every `break`.
+    /// * `iterate` - Computes the variables live at the head of the loop from an estimate of them.
+    ///
+    /// # Returns
+    ///
+    /// The variables live at the head of the loop.
+    fn fixpoint<F>(&mut self, live_after: &HashSet<String>, mut iterate: F) -> HashSet<String>
+    where
+        F: FnMut(&mut Self, &HashSet<String>) -> HashSet<String>,
+    {
+        self.break_targets.push(live_after.clone());
+        let recording = self.recording;
+        self.recording = false;
+        let mut head = HashSet::new();
+        loop {
+            let next = iterate(self, &head);
+            if next == head {
+                break;
+            }
+            head = next;
+        }
+        self.recording = recording;
+        let head = iterate(self, &head);
+        self.break_targets.pop();
+        head
+    }
+}
+
+/// Finds the variables a piece of code reads: every name it mentions except the targets of its stores.
+fn read_variables(node: &ASTNode) -> HashSet<String> {
+    let mut reads = HashSet::new();
+    let mut pending = vec![node.clone()];
+    while let Some(node) = pending.pop() {
+        let children = node.get_children();
+        match node.get_node_type() {
+            NodeType::Identifier(name) => {
+                reads.insert(name);
+            }
+            NodeType::Assignment => pending.extend(children.into_iter().skip(1)),
+            NodeType::Variable => {}
+            _ => pending.extend(children),
+        }
+    }
+    reads
+}
+
+/// Checks whether an expression may have an effect besides computing its value.
+pub fn has_side_effects(node: &ASTNode) -> bool {
+    let writes = matches!(
+        node.get_node_type(),
+        NodeType::FunctionCall | NodeType::Assignment | NodeType::CompoundAssignment | NodeType::PostfixExpression
+    ) || (node.get_node_type() == NodeType::UnaryExpression && matches!(
+            node.get_children().first().map(|c| c.get_node_type()),
+            Some(NodeType::Operator(op)) if op == "++" || op == "--"));
+    writes || node.get_children().iter().any(has_side_effects)
+}
+
+/// Checks whether a piece of code contains a `break` or `continue`.
+fn contains_jump(node: &ASTNode) -> bool {
+    matches!(node.get_node_type(), NodeType::Break | NodeType::Continue) || node.get_children().iter().any(contains_jump)
+}
+
+/// Finds the stores to local variables whose value is never read, using a live-variables analysis of each function.
+/// A store is dead when the variable is overwritten or goes out of scope on every path before it is read. Only
+/// variables declared once in their function, and neither `volatile`, address-taken, nor shadowing a global, are
+/// considered, since a store to any other variable may be observed without reading its name.
+///
+/// # Parameters
+///
+/// * `ast` - The program to analyze.
+///
+/// # Returns
+///
+/// The dead stores, in program order.
+pub fn dead_stores(ast: &AST) -> Vec<DeadStore> {
+    let root = ast.get_root();
+    let globals: HashSet<String> = root.get_children().iter()
+        .filter(|node| node.get_node_type() != NodeType::FunctionDeclaration)
+        .flat_map(|node| written_variables(node).into_keys())
+        .collect();
+
+    let mut dead_stores = Vec::new();
+    for (index, function) in root.get_children().iter().enumerate() {
+        if function.get_node_type() != NodeType::FunctionDeclaration {
+            continue;
+        }
+        let children = function.get_children();
+        let Some(body) = children.iter().rposition(|c| c.get_node_type() == NodeType::BlockExpression) else {
+            continue;
+        };
+
+        let mut declarations: HashMap<String, usize> = HashMap::new();
+        let mut pending = vec![function.clone()];
+        while let Some(node) = pending.pop() {
+            if matches!(node.get_node_type(), NodeType::Variable | NodeType::Parameter) {
+                if let Some(NodeType::Identifier(name)) = node.get_children().first().map(|c| c.get_node_type()) {
+                    *declarations.entry(name).or_insert(0) += 1;
+                }
+            }
+            pending.extend(node.get_children());
+        }
+        let volatile = volatile_variables(function);
+        let address_taken = address_taken_variables(function);
+        let tracked = declarations.into_iter()
+            .filter(|(name, count)| {
+                *count == 1 && !globals.contains(name) && !volatile.contains(name) && !address_taken.contains(name)
+            })
+            .map(|(name, _)| name)
+            .collect();
+
+        let mut analysis = LivenessAnalysis {
+            tracked,
+            break_targets: Vec::new(),
+            continue_targets: Vec::new(),
+            path: vec![index],
+            recording: true,
+            dead_stores: Vec::new(),
+        };
+        analysis.child(&children, body, &HashSet::new());
+        dead_stores.extend(analysis.dead_stores);
+    }
+    dead_stores.sort_by(|a, b| a.path.cmp(&b.path));
+    dead_stores
+}
+
+/// Finds the variables whose address is taken with `&`, which may be read or written through the pointer.
+fn address_taken_variables(node: &ASTNode) -> HashSet<String> {
+    let mut taken = HashSet::new();
+    let mut pending = vec![node.clone()];
+    while let Some(node) = pending.pop() {
+        let children = node.get_children();
+        if node.get_node_type() == NodeType::UnaryExpression
+            && children.first().map(|c| c.get_node_type()) == Some(NodeType::Operator("&".to_string()))
+        {
+            if let Some(NodeType::Identifier(name)) = children.get(1).map(|c| c.get_node_type()) {
+                taken.insert(name);
+            }
+        }
+        pending.extend(children);
+    }
+    taken
+}
+
+/// A basic induction variable of a `for` loop: a variable set to a constant before the first iteration and changed
+/// by a constant step after each one, and written nowhere else in the loop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InductionVariable {
+    /// The name of the variable.
+    pub name: String,
+    /// The value of the variable on the first iteration.
+    pub initial: i64,
+    /// The amount added to the variable after each iteration.
+    pub step: i64,
+}
+
+/// Finds the basic induction variable of a `for` loop of the form `for (i = a; ...; i = i + b)` or
+/// `for (i = a; ...; i = i - b)`, where `a` and `b` are integer literals and the body never writes `i`. The
+/// initializer may also declare `i`, as in `int i = a`, and the increment may be `i++` or `i--`.
+///
+/// # Parameters
+///
+/// * `for_loop` - The `ForLoop` node, whose children are its initializer, condition, increment, and body.
+///
+/// # Returns
+///
+/// The induction variable, or `None` if the loop does not have one.
+pub fn induction_variable(for_loop: &ASTNode) -> Option<InductionVariable> {
+    let children = for_loop.get_children();
+    if for_loop.get_node_type() != NodeType::ForLoop || children.len() != 4 {
+        return None;
+    }
+    let integer = |node: &ASTNode| match node.get_node_type() {
+        NodeType::Literal(value) => value.parse::<i64>().ok(),
+        _ => None,
+    };
+
+    let (name, initial) = match children[0].get_children().as_slice() {
+        [assignment] if assignment.get_node_type() == NodeType::Assignment => match assignment.get_children().as_slice() {
+            [target, value] => match target.get_node_type() {
+                NodeType::Identifier(name) => (name, integer(value)?),
+                _ => return None,
+            },
+            _ => return None,
+        },
+        [declaration] if declaration.get_node_type() == NodeType::Initialization => match declaration.get_children().as_slice() {
+            [variable, assigned_value] => match (variable.get_children().first().map(|v| v.get_node_type()), assigned_value.get_children().as_slice()) {
+                (Some(NodeType::Identifier(name)), [value]) => (name, integer(value)?),
+                _ => return None,
+            },
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let step = match children[2].get_children().as_slice() {
+        [unary] if unary.get_node_type() == NodeType::UnaryExpression => match unary.get_children().as_slice() {
+            [operator, target] if target.get_node_type() == NodeType::Identifier(name.clone()) => match operator.get_node_type() {
+                NodeType::Operator(op) if op == "++" => 1,
+                NodeType::Operator(op) if op == "--" => -1,
+                _ => return None,
+            },
+            _ => return None,
+        },
+        [assignment] if assignment.get_node_type() == NodeType::Assignment => match assignment.get_children().as_slice() {
+            [target, update] if target.get_node_type() == NodeType::Identifier(name.clone()) => {
+                match update.get_children().as_slice() {
+                    [left, operator, right] if left.get_node_type() == NodeType::Identifier(name.clone()) => {
+                        match operator.get_node_type() {
+                            NodeType::Operator(op) if op == "+" => integer(right)?,
+                            NodeType::Operator(op) if op == "-" => integer(right)?.checked_neg()?,
+                            _ => return None,
+                        }
+                    }
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        },
+        [assignment] if assignment.get_node_type() == NodeType::CompoundAssignment => match assignment.get_children().as_slice() {
+            [target, operator, right] if target.get_node_type() == NodeType::Identifier(name.clone()) => {
+                match operator.get_node_type() {
+                    NodeType::Operator(op) if op == "+=" => integer(right)?,
+                    NodeType::Operator(op) if op == "-=" => integer(right)?.checked_neg()?,
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let body_writes = written_variables(&children[3]).contains_key(&name) || written_variables(&children[1]).contains_key(&name);
+    (!body_writes).then_some(InductionVariable { name, initial, step })
+}
@@ -0,0 +1,144 @@
+//! Contains desugaring passes, which rewrite constructs the code generator does not support into equivalent ones
+//! that it does.
+//!
+//! A switch on strings, whose case labels are string literals, is rewritten into a chain of `if`-`else if`
+//! statements comparing the scrutinee with each label by `==`, which the code generator lowers to `strcmp`. The
+//! chain stores the position of the matching case in a new integer variable, and a switch on that variable then
+//! runs the cases in their original order, so fallthrough, `break`, and `default` keep their meaning. The pass
+//! expects the program to have passed `check_switch_labels`, so labels are distinct and the scrutinee can be
+//! evaluated once per label.
+
+use common::ast::{core::{ASTNode, AST}, data_type::DataType, node_type::NodeType};
+
+/// Rewrites the switches on strings of a program, numbering the variables it introduces.
+struct SwitchDesugarer {
+    next_selector: usize,
+}
+
+impl SwitchDesugarer {
+    /// Rewrites a node and its children, replacing each switch on strings in a block with the statements it
+    /// desugars to.
+    fn visit(&mut self, node: &ASTNode) -> ASTNode {
+        let mut rewritten = node.with_children(Vec::new());
+        for child in node.get_children() {
+            let child = self.visit(&child);
+            let in_block = matches!(node.get_node_type(), NodeType::BlockExpression | NodeType::TopLevelExpression);
+            if in_block && is_string_switch(&child) {
+                rewritten.add_children(self.desugar(&child));
+            } else {
+                rewritten.add_child(child);
+            }
+        }
+        rewritten
+    }
+
+    /// Desugars one switch on strings into the declaration of its selector, the comparison chain that sets it, and
+    /// the switch on it.
+    fn desugar(&mut self, switch: &ASTNode) -> Vec<ASTNode> {
+        let selector = format!("switch.case{}", self.next_selector);
+        self.next_selector += 1;
+        let children = switch.get_children();
+        let scrutinee = &children[0];
+
+        let mut comparisons = Vec::new();
+        let mut arms = ASTNode::new(NodeType::BlockExpression);
+        for arm in children[1].get_children() {
+            if arm.get_node_type() != NodeType::Case {
+                arms.add_child(arm);
+                continue;
+            }
+            let index = comparisons.len().to_string();
+            let arm_children = arm.get_children();
+            comparisons.push((arm_children[0].clone(), index.clone()));
+
+            let mut case = ASTNode::new(NodeType::Case);
+            case.add_child(ASTNode::new(NodeType::Literal(index)));
+            case.add_children(arm_children[1..].to_vec());
+            arms.add_child(case);
+        }
+
+        // The comparisons are one `if` with an `else if` for each label after the first
+        let mut chain: Option<ASTNode> = None;
+        for (label, index) in comparisons {
+            let mut comparison = ASTNode::new(NodeType::BinaryExpression);
+            comparison.add_child(scrutinee.clone());
+            comparison.add_child(ASTNode::new(NodeType::Operator("==".to_string())));
+            comparison.add_child(label);
+            let mut condition = ASTNode::new(NodeType::Condition);
+            condition.add_child(comparison);
+
+            let mut then_block = ASTNode::new(NodeType::BlockExpression);
+            then_block.add_child(assignment(&selector, &index));
+
+            match chain.as_mut() {
+                Some(if_statement) => {
+                    let mut else_if = ASTNode::new(NodeType::ElseIf);
+                    else_if.add_child(condition);
+                    else_if.add_child(then_block);
+                    if_statement.add_child(else_if);
+                }
+                None => {
+                    let mut if_statement = ASTNode::new(NodeType::IfStatement);
+                    if_statement.add_child(condition);
+                    if_statement.add_child(then_block);
+                    chain = Some(if_statement);
+                }
+            }
+        }
+
+        let mut selector_switch = ASTNode::new(NodeType::SwitchStatement);
+        selector_switch.add_child(ASTNode::new(NodeType::Identifier(selector.clone())));
+        selector_switch.add_child(arms);
+
+        let mut statements = vec![declaration(&selector, "-1")];
+        statements.extend(chain);
+        statements.push(selector_switch);
+        statements
+    }
+}
+
+/// Checks whether a node is a switch statement with a string literal case label.
+fn is_string_switch(node: &ASTNode) -> bool {
+    let children = node.get_children();
+    node.get_node_type() == NodeType::SwitchStatement && children.len() == 2 && children[1].get_children().iter()
+        .filter(|arm| arm.get_node_type() == NodeType::Case)
+        .any(|arm| matches!(arm.get_children().first().map(|label| label.get_node_type()),
+            Some(NodeType::Literal(value)) if value.starts_with('"')))
+}
+
+/// Builds the declaration `int name = value;`.
+fn declaration(name: &str, value: &str) -> ASTNode {
+    let mut variable = ASTNode::new(NodeType::Variable);
+    variable.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+    variable.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    let mut assigned_value = ASTNode::new(NodeType::AssignedValue);
+    assigned_value.add_child(ASTNode::new(NodeType::Literal(value.to_string())));
+    let mut initialization = ASTNode::new(NodeType::Initialization);
+    initialization.add_child(variable);
+    initialization.add_child(assigned_value);
+    initialization
+}
+
+/// Builds the assignment `name = value;`.
+fn assignment(name: &str, value: &str) -> ASTNode {
+    let mut assignment = ASTNode::new(NodeType::Assignment);
+    assignment.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+    assignment.add_child(ASTNode::new(NodeType::Literal(value.to_string())));
+    assignment
+}
+
+/// Rewrites every switch on strings in a program into a chain of string comparisons that selects a case, followed
+/// by a switch on the selected case's position. A case matches when `strcmp` of the scrutinee and its label is zero,
+/// and when no case matches the `default` case runs, if there is one.
+///
+/// # Parameters
+///
+/// * `ast` - The program to rewrite, which must have passed `check_switch_labels`.
+///
+/// # Returns
+///
+/// The rewritten program, or a copy of it if it has no switch on strings.
+pub fn desugar_string_switches(ast: &AST) -> AST {
+    let mut desugarer = SwitchDesugarer { next_selector: 0 };
+    AST::new(desugarer.visit(&ast.get_root()))
+}
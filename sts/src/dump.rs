@@ -0,0 +1,291 @@
+//! Contains the symbol dump, which lists every function and global variable of a program with its type, linkage,
+//! and mangled name, as text or JSON, for debugging linking issues and for external tooling.
+
+use std::fmt;
+
+use common::ast::{
+    core::{ASTNode, AST},
+    data_type::DataType,
+    node_type::NodeType,
+};
+
+/// The kind of entity a global symbol names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    /// A function, including a method declared inside a struct.
+    Function,
+    /// A global variable.
+    Global,
+}
+
+/// The linkage of a global symbol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Linkage {
+    /// Visible to other modules.
+    External,
+    /// Visible only within its own module.
+    Internal,
+}
+
+/// A function or global variable of a program, as it will appear to the linker.
+///
+/// # Fields
+/// * `name` - The name as written in the source; methods are written `Struct.method`.
+/// * `kind` - Whether the symbol names a function or a global variable.
+/// * `data_type` - The C spelling of the symbol's type, such as `int (int, char)` for a function.
+/// * `linkage` - The linkage of the symbol.
+/// * `mangled_name` - The name emitted to the object file. C names are not mangled, and methods are emitted as
+///   `Struct__method`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobalSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub data_type: String,
+    pub linkage: Linkage,
+    pub mangled_name: String,
+}
+
+/// Collects the functions and global variables declared at the top level of a program, in declaration order.
+///
+/// # Parameters
+///
+/// * `ast` - The program, as returned by semantic analysis.
+///
+/// # Returns
+///
+/// The program's global symbols.
+pub fn collect_global_symbols(ast: &AST) -> Vec<GlobalSymbol> {
+    let mut symbols = Vec::new();
+
+    for node in ast.get_root().get_children() {
+        match node.get_node_type() {
+            NodeType::FunctionDeclaration => {
+                if let Some((name, data_type)) = function_signature(&node) {
+                    symbols.push(GlobalSymbol {
+                        mangled_name: name.clone(),
+                        name,
+                        kind: SymbolKind::Function,
+                        data_type,
+                        linkage: Linkage::External,
+                    });
+                }
+            }
+            NodeType::Initialization => {
+                let variable = node.get_children().into_iter().find(|c| c.get_node_type() == NodeType::Variable);
+                if let Some((name, data_type)) = variable.as_ref().and_then(variable_signature) {
+                    symbols.push(GlobalSymbol {
+                        mangled_name: name.clone(),
+                        name,
+                        kind: SymbolKind::Global,
+                        data_type,
+                        linkage: Linkage::External,
+                    });
+                }
+            }
+            NodeType::StructDeclaration => {
+                let children = node.get_children();
+                let struct_name = match children.first().map(|c| c.get_node_type()) {
+                    Some(NodeType::Identifier(name)) => name,
+                    _ => continue,
+                };
+                for method in children.iter().filter(|c| c.get_node_type() == NodeType::FunctionDeclaration) {
+                    if let Some((name, data_type)) = function_signature(method) {
+                        symbols.push(GlobalSymbol {
+                            name: format!("{}.{}", struct_name, name),
+                            kind: SymbolKind::Function,
+                            data_type,
+                            linkage: Linkage::External,
+                            mangled_name: format!("{}__{}", struct_name, name),
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    symbols
+}
+
+/// Finds the `main` function a hosted program starts at. Methods named `main` are not entry points.
+///
+/// # Parameters
+///
+/// * `ast` - The program.
+///
+/// # Returns
+///
+/// The `main` function, or None if the program does not define one at the top level.
+pub fn find_main(ast: &AST) -> Option<GlobalSymbol> {
+    collect_global_symbols(ast).into_iter().find(|symbol| symbol.kind == SymbolKind::Function && symbol.name == "main")
+}
+
+/// Returns the name and C type of a `FunctionDeclaration` node, whose children are its identifier, its parameters,
+/// its return type, and its body. A variadic function's type ends its parameters with `...`.
+fn function_signature(node: &ASTNode) -> Option<(String, String)> {
+    let children = node.get_children();
+    let name = match children.first()?.get_node_type() {
+        NodeType::Identifier(name) => name,
+        _ => return None,
+    };
+    let mut parameters: Vec<String> = children.iter()
+        .filter(|c| c.get_node_type() == NodeType::Parameter)
+        .filter_map(|p| variable_signature(p).map(|(_, data_type)| data_type))
+        .collect();
+    if children.iter().any(|c| c.get_node_type() == NodeType::Variadic) {
+        parameters.push("...".to_string());
+    }
+    let return_type = children.iter().find_map(|c| match c.get_node_type() {
+        NodeType::Type(data_type) => Some(data_type),
+        _ => None,
+    })?;
+
+    let parameters = if parameters.is_empty() { "void".to_string() } else { parameters.join(", ") };
+    Some((name, format!("{} ({})", c_type_name(return_type), parameters)))
+}
+
+/// Returns the name and C type of a `Variable` or `Parameter` node, whose children are its identifier and type.
+fn variable_signature(node: &ASTNode) -> Option<(String, String)> {
+    let children = node.get_children();
+    let name = match children.first()?.get_node_type() {
+        NodeType::Identifier(name) => name,
+        _ => return None,
+    };
+    let type_node = children.get(1)?;
+    let data_type = type_spelling(type_node)?;
+    let qualifiers: Vec<String> = type_node.get_children().iter()
+        .filter_map(|c| match c.get_node_type() {
+            NodeType::Qualifier(qualifier) => Some(qualifier),
+            _ => None,
+        })
+        .collect();
+
+    Some((name, qualifiers.into_iter().chain([data_type]).collect::<Vec<_>>().join(" ")))
+}
+
+/// Returns the C spelling of the type described by a `Type` node, such as `int`, `struct Point`, or
+/// `int (*)(int, int)`, or `None` if the node is not a type.
+pub(crate) fn type_spelling(type_node: &ASTNode) -> Option<String> {
+    match type_node.get_node_type() {
+        NodeType::Type(DataType::Function) => function_pointer_type(type_node),
+        NodeType::Type(DataType::Struct) => match type_node.get_children().first().map(|c| c.get_node_type()) {
+            Some(NodeType::Identifier(name)) => Some(format!("struct {}", name)),
+            _ => Some(c_type_name(DataType::Struct).to_string()),
+        },
+        NodeType::Type(data_type) => Some(c_type_name(data_type).to_string()),
+        _ => None,
+    }
+}
+
+/// Returns the C type of a function pointer's `Type(Function)` node, whose children are the return type followed
+/// by the parameter types.
+fn function_pointer_type(node: &ASTNode) -> Option<String> {
+    let types: Vec<DataType> = node.get_children().iter().filter_map(|c| match c.get_node_type() {
+        NodeType::Type(data_type) => Some(data_type),
+        _ => None,
+    }).collect();
+    let (return_type, parameters) = types.split_first()?;
+    let parameters = if parameters.is_empty() {
+        "void".to_string()
+    } else {
+        parameters.iter().map(|t| c_type_name(*t)).collect::<Vec<_>>().join(", ")
+    };
+    Some(format!("{} (*)({})", c_type_name(*return_type), parameters))
+}
+
+/// Returns the C spelling of a data type.
+pub(crate) fn c_type_name(data_type: DataType) -> &'static str {
+    match data_type {
+        DataType::Integer => "int",
+        DataType::Float => "float",
+        DataType::Double => "double",
+        DataType::Long => "long",
+        DataType::Boolean => "bool",
+        DataType::String => "char *",
+        DataType::Char => "char",
+        DataType::Function => "function",
+        DataType::Struct => "struct",
+        DataType::Enum => "enum",
+        DataType::None => "none",
+        DataType::Void => "void",
+        DataType::Unsign => "unsigned int",
+        DataType::Sign => "signed int",
+        DataType::UnsignedLong => "unsigned long",
+        DataType::LongLong => "long long",
+        DataType::UnsignedLongLong => "unsigned long long",
+        DataType::SignedChar => "signed char",
+        DataType::UnsignedChar => "unsigned char",
+    }
+}
+
+impl SymbolKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SymbolKind::Function => "function",
+            SymbolKind::Global => "global",
+        }
+    }
+}
+
+impl Linkage {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Linkage::External => "external",
+            Linkage::Internal => "internal",
+        }
+    }
+}
+
+/// Provides a display implementation for `GlobalSymbol`, printing one tab-separated line with its kind, name,
+/// type, linkage, and mangled name.
+impl fmt::Display for GlobalSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}\t{}\t{}\t{}\t{}", self.kind.as_str(), self.name, self.data_type, self.linkage.as_str(), self.mangled_name)
+    }
+}
+
+/// Serializes global symbols as a JSON array of objects with the fields `name`, `kind`, `type`, `linkage`, and
+/// `mangled_name`.
+///
+/// # Parameters
+///
+/// * `symbols` - The symbols to serialize.
+///
+/// # Returns
+///
+/// The JSON text.
+pub fn symbols_to_json(symbols: &[GlobalSymbol]) -> String {
+    let objects: Vec<String> = symbols.iter().map(|symbol| {
+        format!(
+            "  {{\"name\": {}, \"kind\": {}, \"type\": {}, \"linkage\": {}, \"mangled_name\": {}}}",
+            json_string(&symbol.name),
+            json_string(symbol.kind.as_str()),
+            json_string(&symbol.data_type),
+            json_string(symbol.linkage.as_str()),
+            json_string(&symbol.mangled_name),
+        )
+    }).collect();
+
+    if objects.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[\n{}\n]", objects.join(",\n"))
+    }
+}
+
+/// Quotes and escapes a string as a JSON string literal.
+pub fn json_string(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
@@ -10,6 +10,18 @@
 //! * `core`: Defines the symbol table stack structure and drives the generation process.
 //! * `statement`: Handles stack generation for statements and operations within blocks.
 //! * `block`: Handles stack generation for code blocks.
+//! * `dump`: Lists the functions and global variables of a program for debugging and external tooling.
+//! * `lint`: Checks a parsed program for likely mistakes, such as printf-like calls that do not match their format string,
+//!   expression statements whose result is unused, or strings compared with `==`.
+//! * `typecheck`: Checks a parsed program for type errors, such as struct arguments that do not match their parameter
+//!   or literal initializers that do not match their variable, and infers the types of expressions for editors.
+//! * `desugar`: Rewrites constructs the code generator does not support, such as switches on strings, into ones it does.
+//! * `dataflow`: Computes facts about which variables code reads and writes, for optimization passes to consult.
+//! * `optimize`: Rewrites a parsed program into a faster equivalent one, such as by hoisting loop-invariant code.
+//! * `rename`: Renames a variable or function everywhere it is referenced, refusing renames that change what names
+//!   refer to.
+//! * `cache`: Encodes an analyzed program and its symbol table stack in a compact binary format, for incremental
+//!   compilation caches.
 
 /// Definitions of the symbol table stack structure and core of the symbol table stack generation process
 pub mod core;
@@ -18,4 +30,28 @@ pub mod core;
 mod block;
 
 /// Stack generation for nodes within code blocks
-mod statement;
\ No newline at end of file
+mod statement;
+
+/// Lint passes reporting warnings for likely mistakes
+pub mod lint;
+
+/// Listing of a program's global symbols as text or JSON
+pub mod dump;
+
+/// Type checks reporting errors for ill-typed programs
+pub mod typecheck;
+
+/// Desugaring passes rewriting unsupported constructs into supported ones
+pub mod desugar;
+
+/// Data-flow facts about the variables a program reads and writes
+pub mod dataflow;
+
+/// Optimization passes rewriting a program into a faster equivalent one
+pub mod optimize;
+
+/// Scope-aware renaming of variables and functions
+pub mod rename;
+
+/// Binary encoding of analyzed programs for incremental compilation caches
+pub mod cache;
@@ -0,0 +1,632 @@
+//! Contains lint passes, which inspect a parsed program for code that is valid but likely wrong and report it as
+//! warnings without stopping compilation.
+//!
+//! The format-string pass checks calls to printf-like functions whose format string is a literal: the format is
+//! parsed at compile time, and the number and types of the arguments after it are checked against its conversion
+//! specifiers.
+//!
+//! The unused-result pass checks expression statements, warning when the expression has no effect, such as
+//! `x + 1;`, so its result is computed only to be discarded.
+//!
+//! The string-comparison pass checks `==` and `!=` between two strings, which are lowered to a `strcmp` call that
+//! compares their contents, and suggests calling `strcmp` explicitly.
+//!
+//! The dead-store pass reports stores to local variables whose value is never read, which the optimizer would
+//! otherwise remove silently.
+//!
+//! The operator-precedence pass checks operands written without parentheses whose grouping is easily misread, such
+//! as the comparison in `a & b == c`, which is parsed as `a & (b == c)`.
+//!
+//! The assignment-in-condition pass checks conditions that test the result of an assignment, such as `if (x = 0)`.
+//! Like the operator-precedence pass, it uses the parentheses recorded by the parser, so `if ((x = 0))` is not
+//! reported.
+//!
+//! The narrowing pass checks initializations and assignments whose value is implicitly converted to a narrower
+//! type, such as a `double` stored in a `float`, using the type checker's inference and conversion rules.
+
+use std::collections::HashMap;
+
+use common::{
+    ast::{core::{ASTNode, AST}, data_type::DataType, node_type::NodeType},
+    literal::string_bytes,
+    warning::WarningType,
+};
+
+use crate::{
+    dataflow::dead_stores,
+    dump::c_type_name,
+    typecheck::{narrows, type_at_path},
+};
+
+/// The printf-like functions that are checked, each paired with the 0-based position of its format argument.
+const PRINTF_LIKE_FUNCTIONS: &[(&str, usize)] = &[
+    ("printf", 0),
+    ("fprintf", 1),
+    ("dprintf", 1),
+    ("sprintf", 1),
+    ("snprintf", 2),
+];
+
+/// The kind of value an argument has, as far as format checking is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArgumentKind {
+    Int,
+    Long,
+    Char,
+    Floating,
+    String,
+    Pointer,
+}
+
+impl ArgumentKind {
+    /// Classifies a declared type, returning `None` for types that cannot be checked.
+    fn from_data_type(data_type: DataType) -> Option<Self> {
+        match data_type {
+            DataType::Integer | DataType::Boolean | DataType::Sign | DataType::Unsign => Some(ArgumentKind::Int),
+            DataType::Long | DataType::UnsignedLong | DataType::LongLong | DataType::UnsignedLongLong => Some(ArgumentKind::Long),
+            DataType::Char | DataType::SignedChar | DataType::UnsignedChar => Some(ArgumentKind::Char),
+            DataType::Float | DataType::Double => Some(ArgumentKind::Floating),
+            DataType::String => Some(ArgumentKind::String),
+            DataType::Function => Some(ArgumentKind::Pointer),
+            _ => None,
+        }
+    }
+
+    /// The C spelling of this kind, used in warning messages.
+    fn name(&self) -> &'static str {
+        match self {
+            ArgumentKind::Int => "int",
+            ArgumentKind::Long => "long",
+            ArgumentKind::Char => "char",
+            ArgumentKind::Floating => "double",
+            ArgumentKind::String => "char *",
+            ArgumentKind::Pointer => "void *",
+        }
+    }
+}
+
+/// One conversion specifier parsed from a format string.
+struct Conversion {
+    /// The specifier as written, such as `%5ld`.
+    text: String,
+    /// The kinds of argument the specifier accepts, and the name of the type it expects; `None` for `%n`, whose
+    /// argument is not checked.
+    expected: Option<(&'static [ArgumentKind], &'static str)>,
+    /// The number of `*` widths and precisions, each of which consumes an `int` argument before the value.
+    star_arguments: usize,
+}
+
+/// Parses the conversion specifiers of a format string, in order. `%%` is skipped since it consumes no argument.
+///
+/// # Errors
+///
+/// * Returns the text of the first specifier whose conversion character is not recognized.
+fn parse_format(format: &str) -> Result<Vec<Conversion>, String> {
+    let mut conversions = Vec::new();
+    let mut chars = format.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+        let mut text = String::from("%");
+        let mut star_arguments = 0;
+
+        // Flags, width, and precision
+        while let Some(&next) = chars.peek() {
+            match next {
+                '-' | '+' | ' ' | '#' | '0'..='9' | '.' => text.push(next),
+                '*' => {
+                    text.push(next);
+                    star_arguments += 1;
+                }
+                _ => break,
+            }
+            chars.next();
+        }
+
+        // Length modifier
+        let mut length = String::new();
+        while let Some(&next) = chars.peek() {
+            if !matches!(next, 'h' | 'l' | 'L' | 'z' | 'j' | 't') {
+                break;
+            }
+            length.push(next);
+            chars.next();
+        }
+        text.push_str(&length);
+        let is_long = matches!(length.as_str(), "l" | "ll" | "z" | "j" | "t");
+
+        let conversion = chars.next();
+        if let Some(conversion) = conversion {
+            text.push(conversion);
+        }
+        let expected: Option<(&'static [ArgumentKind], &'static str)> = match conversion {
+            Some('%') if text == "%%" => continue,
+            Some('d' | 'i' | 'u' | 'o' | 'x' | 'X') if is_long => Some((&[ArgumentKind::Long], "long")),
+            Some('d' | 'i' | 'u' | 'o' | 'x' | 'X') => Some((&[ArgumentKind::Int, ArgumentKind::Char], "int")),
+            Some('c') => Some((&[ArgumentKind::Char, ArgumentKind::Int], "char")),
+            Some('f' | 'F' | 'e' | 'E' | 'g' | 'G' | 'a' | 'A') => Some((&[ArgumentKind::Floating], "double")),
+            Some('s') => Some((&[ArgumentKind::String], "char *")),
+            Some('p') => Some((&[ArgumentKind::Pointer, ArgumentKind::String], "void *")),
+            Some('n') => None,
+            _ => return Err(text),
+        };
+
+        conversions.push(Conversion { text, expected, star_arguments });
+    }
+
+    Ok(conversions)
+}
+
+/// Walks a program and checks its printf-like calls, tracking the declared type of each variable in scope.
+struct FormatChecker {
+    scopes: Vec<HashMap<String, DataType>>,
+    warnings: Vec<WarningType>,
+}
+
+impl FormatChecker {
+    fn visit(&mut self, node: &ASTNode) {
+        match node.get_node_type() {
+            NodeType::FunctionDeclaration | NodeType::FunctionPrototype | NodeType::BlockExpression => {
+                self.scopes.push(HashMap::new());
+                for child in node.get_children() {
+                    self.visit(&child);
+                }
+                self.scopes.pop();
+                return;
+            }
+            NodeType::Variable | NodeType::Parameter => self.declare(node),
+            NodeType::FunctionCall => self.check_call(node),
+            _ => {}
+        }
+        for child in node.get_children() {
+            self.visit(&child);
+        }
+    }
+
+    /// Records the type of a `Variable` or `Parameter` node whose children are its identifier and type.
+    fn declare(&mut self, node: &ASTNode) {
+        let children = node.get_children();
+        if let (Some(NodeType::Identifier(name)), Some(NodeType::Type(data_type))) = (
+            children.first().map(|c| c.get_node_type()),
+            children.get(1).map(|c| c.get_node_type()),
+        ) {
+            if let Some(scope) = self.scopes.last_mut() {
+                scope.insert(name, data_type);
+            }
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<DataType> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name).copied())
+    }
+
+    /// Determines the kind of an argument expression, or `None` if it cannot be determined.
+    fn kind_of(&self, node: &ASTNode) -> Option<ArgumentKind> {
+        match node.get_node_type() {
+            NodeType::Literal(value) if value.starts_with('"') => Some(ArgumentKind::String),
+            NodeType::Literal(value) if value.starts_with('\'') => Some(ArgumentKind::Char),
+            NodeType::Literal(value) if value == "true" || value == "false" => Some(ArgumentKind::Int),
+            NodeType::Literal(value) if value.contains(['.', 'e', 'E']) => Some(ArgumentKind::Floating),
+            NodeType::Literal(value) if value.parse::<i32>().is_ok() => Some(ArgumentKind::Int),
+            NodeType::Literal(value) if value.parse::<i64>().is_ok() => Some(ArgumentKind::Long),
+            NodeType::Identifier(name) => self.lookup(&name).and_then(ArgumentKind::from_data_type),
+            NodeType::UnaryExpression => match node.get_children().first().map(|c| c.get_node_type()) {
+                Some(NodeType::Operator(op)) if op == "&" => Some(ArgumentKind::Pointer),
+                _ => node.get_children().get(1).and_then(|operand| self.kind_of(operand)),
+            },
+            _ => None,
+        }
+    }
+
+    /// Checks one call against its format string, if the callee is a printf-like function and the format is a
+    /// string literal.
+    fn check_call(&mut self, node: &ASTNode) {
+        let children = node.get_children();
+        let function = match children.first().map(|c| c.get_node_type()) {
+            Some(NodeType::Identifier(name)) => name,
+            _ => return,
+        };
+        let format_index = match PRINTF_LIKE_FUNCTIONS.iter().find(|(name, _)| *name == function) {
+            Some((_, index)) => *index,
+            None => return,
+        };
+        let arguments = &children[1..];
+        let format = match arguments.get(format_index).map(|a| a.get_node_type()) {
+            Some(NodeType::Literal(value)) if value.len() >= 2 && value.starts_with('"') => {
+                String::from_utf8_lossy(&string_bytes(&value[1..value.len() - 1])).into_owned()
+            }
+            _ => return,
+        };
+
+        let conversions = match parse_format(&format) {
+            Ok(conversions) => conversions,
+            Err(specifier) => {
+                self.warnings.push(WarningType::InvalidFormatSpecifier { function, specifier });
+                return;
+            }
+        };
+
+        let values = &arguments[format_index + 1..];
+        let expected_count: usize = conversions.iter().map(|c| c.star_arguments + 1).sum();
+        if expected_count != values.len() {
+            self.warnings.push(WarningType::FormatArgumentCount {
+                function: function.clone(),
+                expected: expected_count,
+                found: values.len(),
+            });
+        }
+
+        let mut position = 0;
+        for conversion in &conversions {
+            let mut checks: Vec<(&'static [ArgumentKind], &'static str)> =
+                vec![(&[ArgumentKind::Int, ArgumentKind::Char], "int"); conversion.star_arguments];
+            checks.extend(conversion.expected);
+            // The value is consumed even for `%n`, whose argument is not checked
+            let consumed = conversion.star_arguments + 1;
+
+            for (offset, (accepted, expected)) in checks.into_iter().enumerate() {
+                let Some(value) = values.get(position + offset) else { break };
+                let Some(kind) = self.kind_of(value) else { continue };
+                if !accepted.contains(&kind) {
+                    self.warnings.push(WarningType::FormatArgumentType {
+                        function: function.clone(),
+                        argument_index: format_index + position + offset + 2,
+                        argument: describe(value),
+                        specifier: conversion.text.clone(),
+                        expected: expected.to_string(),
+                        found: kind.name().to_string(),
+                    });
+                }
+            }
+            position += consumed;
+        }
+    }
+}
+
+/// Renders an expression for a warning message, keeping the parentheses it was written with.
+pub fn describe(node: &ASTNode) -> String {
+    let described = match node.get_node_type() {
+        NodeType::Literal(value) | NodeType::Identifier(value) => value,
+        NodeType::UnaryExpression | NodeType::PostfixExpression => node.get_children().iter().map(describe).collect(),
+        NodeType::BinaryExpression => node.get_children().iter().map(describe).collect::<Vec<_>>().join(" "),
+        NodeType::Operator(op) => op,
+        NodeType::Assignment => node.get_children().iter().map(describe).collect::<Vec<_>>().join(" = "),
+        NodeType::CompoundAssignment => node.get_children().iter().map(describe).collect::<Vec<_>>().join(" "),
+        NodeType::TernaryExpression => match node.get_children().as_slice() {
+            [condition, then_branch, else_branch] => {
+                format!("{} ? {} : {}", describe(condition), describe(then_branch), describe(else_branch))
+            }
+            _ => "expression".to_string(),
+        },
+        _ => "expression".to_string(),
+    };
+    if node.is_parenthesized() { format!("({})", described) } else { described }
+}
+
+/// Checks every call to a printf-like function (`printf`, `fprintf`, `dprintf`, `sprintf`, and `snprintf`) whose
+/// format string is a literal, warning when the number of arguments differs from the number the format consumes,
+/// when an argument's type does not match its conversion specifier, or when a specifier is not recognized.
+/// Arguments whose type cannot be determined are not checked.
+///
+/// # Parameters
+///
+/// * `ast` - The program to check.
+///
+/// # Returns
+///
+/// The warnings found, in program order. Type warnings identify the mismatched argument by its position in the call.
+pub fn check_format_strings(ast: &AST) -> Vec<WarningType> {
+    let mut checker = FormatChecker { scopes: vec![HashMap::new()], warnings: Vec::new() };
+    checker.visit(&ast.get_root());
+    checker.warnings
+}
+
+/// Checks whether evaluating an expression can have an effect beyond producing its value: a call, an assignment,
+/// or an increment or decrement anywhere inside it.
+fn has_effect(node: &ASTNode) -> bool {
+    match node.get_node_type() {
+        NodeType::FunctionCall | NodeType::Assignment | NodeType::CompoundAssignment => true,
+        NodeType::Operator(op) if op == "++" || op == "--" => true,
+        _ => node.get_children().iter().any(has_effect),
+    }
+}
+
+/// Checks every expression statement, warning when its expression has no effect and its result is therefore
+/// unused, as in `x + 1;`. Calls are not reported, since they are commonly made for their effects alone.
+///
+/// # Parameters
+///
+/// * `ast` - The program to check.
+///
+/// # Returns
+///
+/// The warnings found, in program order.
+pub fn check_unused_results(ast: &AST) -> Vec<WarningType> {
+    let mut warnings = Vec::new();
+    let mut pending = vec![ast.get_root()];
+    while let Some(node) = pending.pop() {
+        if node.get_node_type() == NodeType::ExpressionStatement {
+            if let Some(expression) = node.get_children().first().filter(|e| !has_effect(e)) {
+                warnings.push(WarningType::UnusedResult { expression: describe(expression) });
+            }
+        }
+        pending.extend(node.get_children().into_iter().rev());
+    }
+    warnings
+}
+
+/// Walks a program and checks its `==` and `!=` comparisons, tracking the declared type of each variable in scope.
+struct StringComparisonChecker {
+    scopes: Vec<HashMap<String, DataType>>,
+    warnings: Vec<WarningType>,
+}
+
+impl StringComparisonChecker {
+    fn visit(&mut self, node: &ASTNode) {
+        match node.get_node_type() {
+            NodeType::FunctionDeclaration | NodeType::FunctionPrototype | NodeType::BlockExpression => {
+                self.scopes.push(HashMap::new());
+                for child in node.get_children() {
+                    self.visit(&child);
+                }
+                self.scopes.pop();
+                return;
+            }
+            NodeType::Variable | NodeType::Parameter => self.declare(node),
+            NodeType::BinaryExpression => self.check_comparison(node),
+            _ => {}
+        }
+        for child in node.get_children() {
+            self.visit(&child);
+        }
+    }
+
+    /// Records the type of a `Variable` or `Parameter` node whose children are its identifier and type.
+    fn declare(&mut self, node: &ASTNode) {
+        let children = node.get_children();
+        if let (Some(NodeType::Identifier(name)), Some(NodeType::Type(data_type))) = (
+            children.first().map(|c| c.get_node_type()),
+            children.get(1).map(|c| c.get_node_type()),
+        ) {
+            if let Some(scope) = self.scopes.last_mut() {
+                scope.insert(name, data_type);
+            }
+        }
+    }
+
+    /// Checks whether an operand is a string literal or a variable declared as a string.
+    fn is_string(&self, node: &ASTNode) -> bool {
+        let name = match node.get_node_type() {
+            NodeType::Literal(value) => return value.starts_with('"'),
+            NodeType::Identifier(name) => name,
+            NodeType::Variable => match node.get_children().first().map(|c| c.get_node_type()) {
+                Some(NodeType::Identifier(name)) => name,
+                _ => return false,
+            },
+            _ => return false,
+        };
+        self.scopes.iter().rev().find_map(|scope| scope.get(&name)) == Some(&DataType::String)
+    }
+
+    fn check_comparison(&mut self, node: &ASTNode) {
+        let children = node.get_children();
+        if let [left, operator, right] = children.as_slice() {
+            let compares = matches!(operator.get_node_type(), NodeType::Operator(op) if op == "==" || op == "!=");
+            if compares && self.is_string(left) && self.is_string(right) {
+                self.warnings.push(WarningType::StringComparison { expression: describe(node) });
+            }
+        }
+    }
+}
+
+/// Checks every `==` and `!=` whose operands are both strings, as in `name == "main"`, warning that the comparison
+/// is made on the contents of the strings with `strcmp` and suggesting an explicit call instead. Operands whose type
+/// cannot be determined are not checked.
+///
+/// # Parameters
+///
+/// * `ast` - The program to check.
+///
+/// # Returns
+///
+/// The warnings found, in program order.
+pub fn check_string_comparisons(ast: &AST) -> Vec<WarningType> {
+    let mut checker = StringComparisonChecker { scopes: vec![HashMap::new()], warnings: Vec::new() };
+    checker.visit(&ast.get_root());
+    checker.warnings
+}
+
+/// Checks every function for stores to local variables whose value is never read before the variable is overwritten
+/// or goes out of scope, such as the first store in `x = 1; x = 2;`.
+///
+/// # Parameters
+///
+/// * `ast` - The program to check.
+///
+/// # Returns
+///
+/// A `DeadStore` warning for each dead store, in program order.
+pub fn check_dead_stores(ast: &AST) -> Vec<WarningType> {
+    dead_stores(ast).into_iter().map(|store| WarningType::DeadStore { variable: store.variable }).collect()
+}
+
+/// The operator of a binary expression, if the node is one.
+fn binary_operator(node: &ASTNode) -> Option<String> {
+    if node.get_node_type() != NodeType::BinaryExpression {
+        return None;
+    }
+    match node.get_children().get(1).map(|c| c.get_node_type()) {
+        Some(NodeType::Operator(op)) => Some(op),
+        _ => None,
+    }
+}
+
+/// Checks whether an operand of a binary operator is easily misread as grouping before the operator: a comparison
+/// under `&`, `^`, or `|`, or a `&&` under `||`.
+fn is_misleading_operand(op: &str, operand: &ASTNode) -> bool {
+    if operand.is_parenthesized() {
+        return false;
+    }
+    match (op, binary_operator(operand).as_deref()) {
+        ("&" | "^" | "|", Some(inner)) => matches!(inner, "==" | "!=" | "<" | ">" | "<=" | ">="),
+        ("||", Some(inner)) => inner == "&&",
+        _ => false,
+    }
+}
+
+/// Checks every binary expression with an operand whose grouping is easily misread, warning with the expression as
+/// written and with parentheses showing how it is parsed, as in `a & b == c`, which is parsed as `a & (b == c)`.
+/// Operands are misread when they are comparisons under `&`, `^`, or `|`, or `&&` under `||`, and are not when
+/// they were written in parentheses.
+///
+/// # Parameters
+///
+/// * `ast` - The program to check.
+///
+/// # Returns
+///
+/// The warnings found, in program order, one per expression.
+pub fn check_operator_precedence(ast: &AST) -> Vec<WarningType> {
+    let mut warnings = Vec::new();
+    let mut pending = vec![ast.get_root()];
+    while let Some(node) = pending.pop() {
+        if let Some(op) = binary_operator(&node) {
+            let mut children = node.get_children();
+            let mut misleading = false;
+            for index in [0, 2] {
+                if let Some(operand) = children.get_mut(index).filter(|operand| is_misleading_operand(&op, operand)) {
+                    operand.set_parenthesized(true);
+                    misleading = true;
+                }
+            }
+            if misleading {
+                let mut parsed = node.clone();
+                parsed.set_children(children);
+                warnings.push(WarningType::OperatorPrecedence { expression: describe(&node), parsed: describe(&parsed) });
+            }
+        }
+        pending.extend(node.get_children().into_iter().rev());
+    }
+    warnings
+}
+
+/// Collects the unparenthesized assignments a condition evaluates as truth values: the condition itself, or the
+/// operands of `&&`, `||`, and `!` within it.
+fn collect_condition_assignments(node: &ASTNode, assignments: &mut Vec<ASTNode>) {
+    if node.is_parenthesized() {
+        return;
+    }
+    let children = node.get_children();
+    match node.get_node_type() {
+        NodeType::Assignment => assignments.push(node.clone()),
+        NodeType::BinaryExpression if matches!(binary_operator(node).as_deref(), Some("&&" | "||")) => {
+            collect_condition_assignments(&children[0], assignments);
+            collect_condition_assignments(&children[2], assignments);
+        }
+        NodeType::UnaryExpression if children.first().map(|c| c.get_node_type()) == Some(NodeType::Operator("!".to_string())) => {
+            if let Some(operand) = children.get(1) {
+                collect_condition_assignments(operand, assignments);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Renders an assignment as the comparison it was likely meant to be, `target == value`. A value that would not
+/// be grouped as the right operand of `==` is parenthesized, so `x = a || b` becomes `x == (a || b)`.
+fn describe_as_comparison(assignment: &ASTNode) -> String {
+    let children = assignment.get_children();
+    let (Some(target), Some(value)) = (children.first(), children.get(1)) else {
+        return describe(assignment);
+    };
+    let mut value = value.clone();
+    if matches!(binary_operator(&value).as_deref(), Some("==" | "!=" | "&" | "^" | "|" | "&&" | "||")) {
+        value.set_parenthesized(true);
+    }
+    format!("{} == {}", describe(target), describe(&value))
+}
+
+/// Checks the condition of every `if` and loop, warning when it tests the result of an assignment, as in
+/// `if (x = 0)`, which is commonly a mistyped `==`, and suggesting both the comparison and the parenthesized
+/// assignment as fixes. Assignments written in parentheses, as in
+/// `while ((c = next()))`, or compared with another value, as in `while ((c = next()) != 0)`, are intended and
+/// not reported.
+///
+/// # Parameters
+///
+/// * `ast` - The program to check.
+///
+/// # Returns
+///
+/// The warnings found, in program order.
+pub fn check_assignments_in_conditions(ast: &AST) -> Vec<WarningType> {
+    let mut warnings = Vec::new();
+    let mut pending = vec![ast.get_root()];
+    while let Some(node) = pending.pop() {
+        if node.get_node_type() == NodeType::Condition {
+            let mut assignments = Vec::new();
+            for expression in node.get_children() {
+                collect_condition_assignments(&expression, &mut assignments);
+            }
+            warnings.extend(assignments.iter().map(|assignment| WarningType::AssignmentInCondition {
+                expression: describe(assignment),
+                comparison: describe_as_comparison(assignment),
+            }));
+        }
+        pending.extend(node.get_children().into_iter().rev());
+    }
+    warnings
+}
+
+/// Checks every initialization and assignment, warning when its value is implicitly converted to a narrower type
+/// than its own, as in `char c = total;` with an `int` total or `float f = ratio;` with a `double` ratio, and
+/// suggesting an explicit conversion. Values and targets whose type cannot be determined are not checked.
+///
+/// # Parameters
+///
+/// * `ast` - The program to check.
+///
+/// # Returns
+///
+/// The warnings found, in program order.
+pub fn check_implicit_narrowing(ast: &AST) -> Vec<WarningType> {
+    let mut warnings = Vec::new();
+    let mut pending = vec![(ast.get_root(), Vec::new())];
+    while let Some((node, path)) = pending.pop() {
+        let children = node.get_children();
+        let value_path: Option<&[usize]> = match node.get_node_type() {
+            NodeType::Initialization if children.get(1).is_some_and(|c| c.get_node_type() == NodeType::AssignedValue) => {
+                Some(&[1, 0])
+            }
+            NodeType::Assignment => Some(&[1]),
+            _ => None,
+        };
+        let value = value_path.and_then(|relative| {
+            relative.iter().try_fold(node.clone(), |node, index| node.get_children().get(*index).cloned())
+        });
+        if let (Some(relative), Some(value), Some(target)) = (value_path, value, children.first()) {
+            let type_of = |relative: &[usize]| type_at_path(ast, &[path.as_slice(), relative].concat());
+            if let (Some(from), Some(to)) = (type_of(relative), type_of(&[0])) {
+                if narrows(&value, from, to) {
+                    // A declaration names its variable by its first child
+                    let variable = match target.get_node_type() {
+                        NodeType::Variable => target.get_children().first().map(describe).unwrap_or_default(),
+                        _ => describe(target),
+                    };
+                    warnings.push(WarningType::ImplicitNarrowing {
+                        variable,
+                        expression: describe(&value),
+                        from: c_type_name(from).to_string(),
+                        to: c_type_name(to).to_string(),
+                    });
+                }
+            }
+        }
+        for (index, child) in children.into_iter().enumerate().rev() {
+            pending.push((child, path.iter().copied().chain([index]).collect()));
+        }
+    }
+    warnings
+}
@@ -0,0 +1,735 @@
+//! Contains optimization passes, which rewrite a parsed program into an equivalent one that runs faster.
+//!
+//! The loop-invariant pass hoists declarations out of `while` and `for` loop bodies when their initializer computes
+//! the same value on every iteration, such as `int limit = n * 4;` in a loop that never writes `n`. The hoisted
+//! declaration runs once before the loop instead of once per iteration. Only initializers without side effects or
+//! traps are hoisted, since they also run when the loop body never does: division and remainder, which trap on a
+//! zero divisor, and function calls are left in place, as are loops that call a function. When signed overflow
+//! traps, as with `--overflow trap`, `+`, `-`, and `*` can trap too and are left in place as well.
+//!
+//! The dead-store pass removes stores to local variables whose value is never read, as found by the live-variables
+//! analysis. A dead assignment is removed, and a dead initializer is dropped from its declaration, unless computing
+//! the stored value has side effects.
+//!
+//! The constant-propagation pass finds parameters that every call in the program passes the same constant for, and
+//! replaces the parameter inside the function with the constant, folding the arithmetic it makes constant. The
+//! constant is first converted to the parameter's declared type, as the call would, so `2.5` passed for an `int` is
+//! `2` and `1` passed for a `double` is `1.0`. Only arithmetic on signed integer constants is folded, and only when
+//! it does not overflow the type C evaluates it in.
+//!
+//! The strength-reduction pass rewrites products of a `for` loop's induction variable and a constant, such as
+//! `i * 4`, into a new variable that starts at the product's first value and is advanced by an addition each time the
+//! induction variable is, replacing a multiplication per use with an addition per iteration.
+//!
+//! Passes are run by a `PassManager`, which holds them in order and can be built for an optimization level.
+
+use std::collections::{HashMap, HashSet};
+
+use common::{
+    ast::{core::{ASTNode, AST}, data_type::DataType, node_type::NodeType},
+    config::{OptLevel, OverflowMode},
+};
+
+use crate::{
+    dataflow::{contains_call, dead_stores, induction_variable, mentioned_variables, volatile_variables, written_variables},
+    typecheck::integer_range,
+};
+
+/// The binary operators that cannot trap or have side effects, so they can be evaluated before a loop that might
+/// not run.
+const PURE_BINARY_OPERATORS: &[&str] = &[
+    "+", "-", "*", "<", ">", "<=", ">=", "==", "!=", "&&", "||", "&", "|", "^", "<<", ">>",
+];
+
+/// The binary operators that trap when their result overflows and overflow is `OverflowMode::Trap`.
+const OVERFLOW_TRAPPING_OPERATORS: &[&str] = &["+", "-", "*"];
+
+/// Hoists loop-invariant declarations out of the loops of one function.
+struct LoopInvariantHoister {
+    /// The number of mentions of each name in the function, so a declaration is only hoisted when every use of its
+    /// name is inside the loop and moving it cannot shadow or be shadowed by another variable.
+    mentions: HashMap<String, usize>,
+    volatile: HashSet<String>,
+    /// What signed arithmetic does on overflow, which decides whether `+`, `-`, and `*` can trap.
+    overflow: OverflowMode,
+}
+
+impl LoopInvariantHoister {
+    fn new(scope: &ASTNode, overflow: OverflowMode) -> Self {
+        Self { mentions: mentioned_variables(scope), volatile: volatile_variables(scope), overflow }
+    }
+
+    /// Checks whether a binary operator can be evaluated before a loop that might not run.
+    fn is_pure_operator(&self, op: &str) -> bool {
+        PURE_BINARY_OPERATORS.contains(&op)
+            && !(self.overflow == OverflowMode::Trap && OVERFLOW_TRAPPING_OPERATORS.contains(&op))
+    }
+
+    /// Rewrites a node and its children, hoisting invariant declarations out of each loop into the enclosing block.
+    fn visit(&self, node: &ASTNode) -> ASTNode {
+        let mut rewritten = node.with_children(Vec::new());
+        match node.get_node_type() {
+            NodeType::FunctionDeclaration => {
+                let hoister = LoopInvariantHoister::new(node, self.overflow);
+                rewritten.set_children(node.get_children().iter().map(|child| hoister.visit(child)).collect());
+            }
+            NodeType::BlockExpression => {
+                for child in node.get_children() {
+                    let child = self.visit(&child);
+                    if matches!(child.get_node_type(), NodeType::WhileLoop | NodeType::ForLoop) {
+                        let (hoisted, loop_node) = self.hoist(&child);
+                        rewritten.add_children(hoisted);
+                        rewritten.add_child(loop_node);
+                    } else {
+                        rewritten.add_child(child);
+                    }
+                }
+            }
+            _ => rewritten.set_children(node.get_children().iter().map(|child| self.visit(child)).collect()),
+        }
+        rewritten
+    }
+
+    /// Splits the invariant declarations off the body of a loop, in order.
+    ///
+    /// # Returns
+    ///
+    /// The hoisted declarations, and the loop without them.
+    fn hoist(&self, loop_node: &ASTNode) -> (Vec<ASTNode>, ASTNode) {
+        let mut children = loop_node.get_children();
+        let Some(body) = children.pop().filter(|body| body.get_node_type() == NodeType::BlockExpression) else {
+            return (Vec::new(), loop_node.clone());
+        };
+        if contains_call(loop_node) {
+            return (Vec::new(), loop_node.clone());
+        }
+
+        let mut writes = written_variables(loop_node);
+        let loop_mentions = mentioned_variables(loop_node);
+        let mut hoisted = Vec::new();
+        let mut kept = body.with_children(Vec::new());
+        for statement in body.get_children() {
+            match self.invariant_declaration(&statement, &writes, &loop_mentions) {
+                Some(name) => {
+                    // Later declarations may use the hoisted variable, which no longer changes inside the loop
+                    writes.remove(&name);
+                    hoisted.push(statement);
+                }
+                None => kept.add_child(statement),
+            }
+        }
+
+        let mut rewritten = loop_node.with_children(children);
+        rewritten.add_child(kept);
+        (hoisted, rewritten)
+    }
+
+    /// Checks whether a statement of a loop body declares a variable with an invariant initializer that is worth
+    /// hoisting, and that the variable is written nowhere else and used only inside the loop.
+    ///
+    /// # Returns
+    ///
+    /// The name of the declared variable if the declaration can be hoisted.
+    fn invariant_declaration(&self, statement: &ASTNode, writes: &HashMap<String, usize>, loop_mentions: &HashMap<String, usize>) -> Option<String> {
+        if statement.get_node_type() != NodeType::Initialization {
+            return None;
+        }
+        let children = statement.get_children();
+        let name = match children.first()?.get_children().first()?.get_node_type() {
+            NodeType::Identifier(name) => name,
+            _ => return None,
+        };
+        let value = children.get(1).filter(|c| c.get_node_type() == NodeType::AssignedValue)?.get_children().first()?.clone();
+
+        let computes = matches!(value.get_node_type(), NodeType::BinaryExpression | NodeType::UnaryExpression);
+        let declared_once = writes.get(&name) == Some(&1) && !self.volatile.contains(&name);
+        let local_to_loop = self.mentions.get(&name) == loop_mentions.get(&name);
+        (computes && declared_once && local_to_loop && self.is_invariant(&value, writes)).then_some(name)
+    }
+
+    /// Checks whether an expression computes the same value on every iteration of a loop that writes `writes`, and
+    /// can be evaluated before the loop without side effects or traps.
+    fn is_invariant(&self, node: &ASTNode, writes: &HashMap<String, usize>) -> bool {
+        let children = node.get_children();
+        match node.get_node_type() {
+            NodeType::Literal(_) => true,
+            NodeType::Identifier(name) => !writes.contains_key(&name) && !self.volatile.contains(&name),
+            NodeType::BinaryExpression => match children.get(1).map(|c| c.get_node_type()) {
+                Some(NodeType::Operator(op)) if self.is_pure_operator(&op) && children.len() == 3 => {
+                    self.is_invariant(&children[0], writes) && self.is_invariant(&children[2], writes)
+                }
+                _ => false,
+            },
+            NodeType::UnaryExpression => match children.first().map(|c| c.get_node_type()) {
+                Some(NodeType::Operator(op)) if matches!(op.as_str(), "-" | "!" | "~") && children.len() == 2 => {
+                    self.is_invariant(&children[1], writes)
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+/// Hoists loop-invariant declarations out of the body of every `while` and `for` loop, placing them just before the
+/// loop. A declaration is hoisted when its initializer is an expression over literals and variables the loop never
+/// writes, the declared variable is not written again in the loop or mentioned outside it, and the loop makes no
+/// function calls. Declarations hoisted from an inner loop may be hoisted again out of the outer one. Signed
+/// overflow is assumed to wrap; use `hoist_loop_invariants_with` when it might trap.
+///
+/// # Parameters
+///
+/// * `ast` - The program to optimize.
+///
+/// # Returns
+///
+/// The optimized program.
+pub fn hoist_loop_invariants(ast: &AST) -> AST {
+    hoist_loop_invariants_with(ast, OverflowMode::Wrap)
+}
+
+/// Hoists loop-invariant declarations like `hoist_loop_invariants`, for a program whose signed arithmetic overflows
+/// as `overflow` selects. With `OverflowMode::Trap`, initializers that add, subtract, or multiply are not hoisted,
+/// since they could trap before a loop that would never have run them.
+///
+/// # Parameters
+///
+/// * `ast` - The program to optimize.
+/// * `overflow` - What signed `+`, `-`, and `*` do when their result overflows.
+///
+/// # Returns
+///
+/// The optimized program.
+pub fn hoist_loop_invariants_with(ast: &AST, overflow: OverflowMode) -> AST {
+    let root = ast.get_root();
+    AST::new(LoopInvariantHoister::new(&root, overflow).visit(&root))
+}
+
+/// Rebuilds a node without the dead stores at the given positions below it.
+///
+/// # Parameters
+///
+/// * `node` - The node to rebuild.
+/// * `path` - The position of `node` in the program.
+/// * `removed` - The positions of the removable dead stores.
+fn remove_stores(node: &ASTNode, path: &mut Vec<usize>, removed: &HashSet<Vec<usize>>) -> ASTNode {
+    let mut rebuilt = node.with_children(Vec::new());
+    for (index, child) in node.get_children().iter().enumerate() {
+        path.push(index);
+        let dead = removed.contains(path);
+        match child.get_node_type() {
+            // Assignments are statements of their own only directly inside a block
+            NodeType::Assignment if dead && node.get_node_type() == NodeType::BlockExpression => {}
+            NodeType::Initialization if dead => {
+                rebuilt.add_child(child.with_children(child.get_children().into_iter()
+                    .filter(|c| c.get_node_type() != NodeType::AssignedValue)
+                    .collect()));
+            }
+            _ => rebuilt.add_child(remove_stores(child, path, removed)),
+        }
+        path.pop();
+    }
+    rebuilt
+}
+
+/// Removes the stores to local variables whose value is never read, as found by `dataflow::dead_stores`. A dead
+/// assignment statement is removed, and a dead initializer is dropped, leaving the declaration. Stores whose value
+/// has side effects, such as a function call, are kept.
+///
+/// # Parameters
+///
+/// * `ast` - The program to optimize.
+///
+/// # Returns
+///
+/// The optimized program.
+pub fn eliminate_dead_stores(ast: &AST) -> AST {
+    let removed = dead_stores(ast).into_iter()
+        .filter(|store| store.removable)
+        .map(|store| store.path)
+        .collect();
+    AST::new(remove_stores(&ast.get_root(), &mut Vec::new(), &removed))
+}
+
+/// Finds the constant each call passes for every parameter of a function, if all calls agree.
+///
+/// # Parameters
+///
+/// * `root` - The root of the program.
+/// * `name` - The name of the function.
+/// * `parameter_count` - The number of parameters of the function.
+///
+/// # Returns
+///
+/// The constant shared by every call for each parameter, or `None` for a parameter that calls pass different or
+/// non-constant arguments for. Every entry is `None` if the function is never called, or is used other than by
+/// calling it, such as by taking its address.
+fn constant_arguments(root: &ASTNode, name: &str, parameter_count: usize) -> Vec<Option<String>> {
+    let mut constants: Option<Vec<Option<String>>> = None;
+    let mut calls = 0;
+    let mut pending = vec![root.clone()];
+    while let Some(node) = pending.pop() {
+        let children = node.get_children();
+        if node.get_node_type() == NodeType::FunctionCall
+            && children.first().map(|c| c.get_node_type()) == Some(NodeType::Identifier(name.to_string()))
+        {
+            calls += 1;
+            let arguments: Vec<Option<String>> = (1..=parameter_count)
+                .map(|index| match children.get(index).map(|c| c.get_node_type()) {
+                    Some(NodeType::Literal(value)) if !value.starts_with('"') => Some(value),
+                    _ => None,
+                })
+                .collect();
+            constants = Some(match constants {
+                None => arguments,
+                Some(previous) => previous.into_iter().zip(arguments)
+                    .map(|(a, b)| a.filter(|a| Some(a) == b.as_ref()))
+                    .collect(),
+            });
+        }
+        pending.extend(children);
+    }
+
+    // Any mention of the name besides the callee of a call might let it be called with other arguments
+    let mentions = mentioned_variables(root).get(name).copied().unwrap_or(0);
+    let declarations = root.get_children().iter()
+        .filter(|node| matches!(node.get_node_type(), NodeType::FunctionDeclaration))
+        .filter(|node| node.get_children().first().map(|c| c.get_node_type()) == Some(NodeType::Identifier(name.to_string())))
+        .count();
+    match constants {
+        Some(constants) if mentions == calls + declarations => constants,
+        _ => vec![None; parameter_count],
+    }
+}
+
+/// Converts a constant argument to the declared type of the parameter it is passed for, as passing it does: a
+/// fractional number passed for an integer is truncated toward zero, and a number passed for a `float` or `double`
+/// is written as a fractional number so that arithmetic on it is not integer arithmetic.
+///
+/// # Returns
+///
+/// The converted constant, or `None` if the parameter's type is not a number, or the conversion is not exact
+/// enough to substitute, such as a value out of the range of an integer type or a character passed for a `double`.
+fn convert_constant(value: &str, data_type: DataType) -> Option<String> {
+    if value.starts_with('\'') {
+        return (data_type != DataType::Boolean && integer_range(data_type).is_some()).then(|| value.to_string());
+    }
+    let fractional = value.contains(['.', 'e', 'E']) && !value.starts_with("0x") && !value.starts_with("0X");
+    let number = if fractional {
+        value.trim_end_matches(['f', 'F']).parse::<f64>().ok().filter(|number| number.is_finite())?
+    } else {
+        value.parse::<i64>().ok()? as f64
+    };
+    match data_type {
+        DataType::Double => Some(format!("{:?}", number)),
+        DataType::Float => Some(format!("{:?}", number as f32)),
+        DataType::Boolean => Some(((number != 0.0) as i64).to_string()),
+        _ => {
+            let (min, max) = integer_range(data_type)?;
+            let integer = if fractional {
+                let truncated = number.trunc();
+                if truncated < min as f64 || truncated > max as f64 {
+                    return None;
+                }
+                truncated as i128
+            } else {
+                value.parse::<i128>().ok()?
+            };
+            (min..=max).contains(&integer).then(|| integer.to_string())
+        }
+    }
+}
+
+/// Replaces a parameter with a constant in a node, and folds the arithmetic on integer constants that results when
+/// `fold` is set. The field name of a member access, such as `x` in `p.x`, is not the parameter.
+fn substitute_constant(node: &ASTNode, parameter: &str, value: &str, fold: bool) -> ASTNode {
+    if node.get_node_type() == NodeType::Identifier(parameter.to_string()) {
+        return in_place_of(node, NodeType::Literal(value.to_string()));
+    }
+    let is_member_access = node.get_node_type() == NodeType::MemberAccess;
+    let rewritten = node.with_children(node.get_children().iter().enumerate()
+        .map(|(index, child)| match index {
+            0 => substitute_constant(child, parameter, value, fold),
+            _ if is_member_access => child.clone(),
+            _ => substitute_constant(child, parameter, value, fold),
+        })
+        .collect());
+    if fold { fold_constant(rewritten) } else { rewritten }
+}
+
+/// Folds a binary or unary expression on integer literals into a single literal. As in C, arithmetic on two literals
+/// that fit in an `int` is done in an `int`, and otherwise in a `long`. Any other node, including expressions on
+/// fractional or character literals, and expressions that would overflow or divide by zero, are returned unchanged.
+fn fold_constant(node: ASTNode) -> ASTNode {
+    let children = node.get_children();
+    let integer = |child: &ASTNode| match child.get_node_type() {
+        NodeType::Literal(value) => value.parse::<i64>().ok(),
+        _ => None,
+    };
+    let in_int = |value: i64| i32::try_from(value).is_ok();
+    let folded = match (node.get_node_type(), children.as_slice()) {
+        (NodeType::BinaryExpression, [left, operator, right]) => {
+            match (integer(left), operator.get_node_type(), integer(right)) {
+                (Some(left), NodeType::Operator(op), Some(right)) => match op.as_str() {
+                    "+" => left.checked_add(right),
+                    "-" => left.checked_sub(right),
+                    "*" => left.checked_mul(right),
+                    "/" => left.checked_div(right),
+                    "%" => left.checked_rem(right),
+                    "<" => Some((left < right) as i64),
+                    ">" => Some((left > right) as i64),
+                    "<=" => Some((left <= right) as i64),
+                    ">=" => Some((left >= right) as i64),
+                    "==" => Some((left == right) as i64),
+                    "!=" => Some((left != right) as i64),
+                    _ => None,
+                }.filter(|result| !(in_int(left) && in_int(right)) || in_int(*result)),
+                _ => None,
+            }
+        }
+        (NodeType::UnaryExpression, [operator, operand]) => match (operator.get_node_type(), integer(operand)) {
+            (NodeType::Operator(op), Some(value)) if op == "-" => {
+                value.checked_neg().filter(|result| !in_int(value) || in_int(*result))
+            }
+            _ => None,
+        },
+        _ => None,
+    };
+    match folded {
+        Some(value) => in_place_of(&node, NodeType::Literal(value.to_string())),
+        None => node,
+    }
+}
+
+/// Propagates constant arguments into the functions they are passed to. When every call to a function in the program
+/// passes the same number or character literal for a parameter of a number type, and the function never writes the
+/// parameter or declares another variable of the same name, each use of the parameter in the function is replaced by
+/// the literal converted to the parameter's type. The arithmetic this makes constant is folded when the parameter is
+/// a signed integer. The parameter itself is kept, so calls are unchanged.
+///
+/// # Parameters
+///
+/// * `ast` - The program to optimize.
+///
+/// # Returns
+///
+/// The optimized program.
+pub fn propagate_constant_arguments(ast: &AST) -> AST {
+    let root = ast.get_root();
+    let mut rebuilt = root.with_children(Vec::new());
+    for node in root.get_children() {
+        if node.get_node_type() != NodeType::FunctionDeclaration {
+            rebuilt.add_child(node);
+            continue;
+        }
+        let children = node.get_children();
+        let (Some(NodeType::Identifier(name)), Some(body)) = (children.first().map(|c| c.get_node_type()), children.last()) else {
+            rebuilt.add_child(node);
+            continue;
+        };
+        let parameters: Vec<(String, Option<DataType>)> = children.iter()
+            .filter(|c| c.get_node_type() == NodeType::Parameter)
+            .filter_map(|p| {
+                let parameter = p.get_children();
+                let data_type = match parameter.get(1).map(|c| c.get_node_type()) {
+                    Some(NodeType::Type(data_type)) => Some(data_type),
+                    _ => None,
+                };
+                match parameter.first().map(|c| c.get_node_type()) {
+                    Some(NodeType::Identifier(name)) => Some((name, data_type)),
+                    _ => None,
+                }
+            })
+            .collect();
+
+        let writes = written_variables(body);
+        let volatile = volatile_variables(&node);
+        let mut body = body.clone();
+        for ((parameter, data_type), constant) in parameters.iter().zip(constant_arguments(&root, &name, parameters.len())) {
+            let Some(data_type) = *data_type else { continue };
+            let converted = constant
+                .filter(|_| !writes.contains_key(parameter) && !volatile.contains(parameter))
+                .and_then(|constant| convert_constant(&constant, data_type));
+            if let Some(constant) = converted {
+                let fold = matches!(data_type,
+                    DataType::Integer | DataType::Sign | DataType::Long | DataType::LongLong | DataType::Char
+                    | DataType::SignedChar);
+                body = substitute_constant(&body, parameter, &constant, fold);
+            }
+        }
+
+        let mut function = node.with_children(children[..children.len() - 1].to_vec());
+        function.add_child(body);
+        rebuilt.add_child(function);
+    }
+    AST::new(rebuilt)
+}
+
+/// Rewrites products of induction variables and constants in the `for` loops of one function.
+struct StrengthReducer {
+    /// The integer type of each variable declared exactly once in the function and not `volatile`, since only their
+    /// products can be computed by repeated addition.
+    integer_variables: HashMap<String, DataType>,
+}
+
+impl StrengthReducer {
+    fn new(scope: &ASTNode) -> Self {
+        let volatile = volatile_variables(scope);
+        let mut declarations: HashMap<String, Vec<Option<DataType>>> = HashMap::new();
+        let mut pending = vec![scope.clone()];
+        while let Some(node) = pending.pop() {
+            let children = node.get_children();
+            if matches!(node.get_node_type(), NodeType::Variable | NodeType::Parameter) {
+                if let Some(NodeType::Identifier(name)) = children.first().map(|c| c.get_node_type()) {
+                    let data_type = match children.get(1).map(|c| c.get_node_type()) {
+                        Some(NodeType::Type(data_type)) => Some(data_type),
+                        _ => None,
+                    };
+                    declarations.entry(name).or_default().push(data_type);
+                }
+            }
+            pending.extend(children);
+        }
+
+        let integer_variables = declarations.into_iter()
+            .filter(|(name, _)| !volatile.contains(name))
+            .filter_map(|(name, types)| match types.as_slice() {
+                [Some(data_type)] if matches!(data_type,
+                    DataType::Integer | DataType::Long | DataType::Sign | DataType::Unsign | DataType::UnsignedLong
+                    | DataType::LongLong | DataType::UnsignedLongLong) => Some((name, *data_type)),
+                _ => None,
+            })
+            .collect();
+        Self { integer_variables }
+    }
+
+    /// Rewrites a node and its children, declaring the variables that replace products just before each loop.
+    fn visit(&self, node: &ASTNode) -> ASTNode {
+        let mut rewritten = node.with_children(Vec::new());
+        match node.get_node_type() {
+            NodeType::FunctionDeclaration => {
+                let reducer = StrengthReducer::new(node);
+                rewritten.set_children(node.get_children().iter().map(|child| reducer.visit(child)).collect());
+            }
+            NodeType::BlockExpression => {
+                for child in node.get_children() {
+                    let child = self.visit(&child);
+                    if child.get_node_type() == NodeType::ForLoop {
+                        let (declarations, loop_node) = self.reduce(&child);
+                        rewritten.add_children(declarations);
+                        rewritten.add_child(loop_node);
+                    } else {
+                        rewritten.add_child(child);
+                    }
+                }
+            }
+            _ => rewritten.set_children(node.get_children().iter().map(|child| self.visit(child)).collect()),
+        }
+        rewritten
+    }
+
+    /// Replaces the products of a loop's induction variable and a constant in its body.
+    ///
+    /// # Returns
+    ///
+    /// The declarations of the variables that replace the products, and the rewritten loop.
+    fn reduce(&self, for_loop: &ASTNode) -> (Vec<ASTNode>, ASTNode) {
+        let Some(induction) = induction_variable(for_loop) else {
+            return (Vec::new(), for_loop.clone());
+        };
+        let Some(data_type) = self.integer_variables.get(&induction.name).copied() else {
+            return (Vec::new(), for_loop.clone());
+        };
+
+        let children = for_loop.get_children();
+        let mut factors = Vec::new();
+        collect_factors(&children[3], &induction.name, &mut factors);
+
+        let mut body = children[3].clone();
+        let mut increment = children[2].clone();
+        let mut declarations = Vec::new();
+        for factor in factors {
+            let (Some(initial), Some(step)) = (induction.initial.checked_mul(factor), induction.step.checked_mul(factor)) else {
+                continue;
+            };
+            // `.` cannot appear in a source identifier, so the name never clashes with a variable of the program
+            let name = format!("{}.mul{}", induction.name, factor);
+            body = replace_product(&body, &induction.name, factor, &name);
+
+            let mut update = ASTNode::new(NodeType::BinaryExpression);
+            update.add_child(ASTNode::new(NodeType::Identifier(name.clone())));
+            update.add_child(ASTNode::new(NodeType::Operator("+".to_string())));
+            update.add_child(ASTNode::new(NodeType::Literal(step.to_string())));
+            let mut assignment = ASTNode::new(NodeType::Assignment);
+            assignment.add_child(ASTNode::new(NodeType::Identifier(name.clone())));
+            assignment.add_child(update);
+            increment.add_child(assignment);
+
+            let mut variable = ASTNode::new(NodeType::Variable);
+            variable.add_child(ASTNode::new(NodeType::Identifier(name)));
+            variable.add_child(ASTNode::new(NodeType::Type(data_type)));
+            let mut assigned_value = ASTNode::new(NodeType::AssignedValue);
+            assigned_value.add_child(ASTNode::new(NodeType::Literal(initial.to_string())));
+            let mut declaration = ASTNode::new(NodeType::Initialization);
+            declaration.add_child(variable);
+            declaration.add_child(assigned_value);
+            declarations.push(declaration);
+        }
+
+        (declarations, for_loop.with_children(vec![children[0].clone(), children[1].clone(), increment, body]))
+    }
+}
+
+/// Returns the constant factor of a product of `variable` and an integer literal, in either order.
+fn product_factor(node: &ASTNode, variable: &str) -> Option<i64> {
+    let children = node.get_children();
+    if node.get_node_type() != NodeType::BinaryExpression || children.len() != 3
+        || children[1].get_node_type() != NodeType::Operator("*".to_string())
+    {
+        return None;
+    }
+    let is_variable = |child: &ASTNode| child.get_node_type() == NodeType::Identifier(variable.to_string());
+    let constant = |child: &ASTNode| match child.get_node_type() {
+        NodeType::Literal(value) => value.parse::<i64>().ok(),
+        _ => None,
+    };
+    match (&children[0], &children[2]) {
+        (left, right) if is_variable(left) => constant(right),
+        (left, right) if is_variable(right) => constant(left),
+        _ => None,
+    }
+}
+
+/// Collects the distinct constant factors `variable` is multiplied by in a node, in order of first appearance.
+fn collect_factors(node: &ASTNode, variable: &str, factors: &mut Vec<i64>) {
+    match product_factor(node, variable) {
+        Some(factor) if !factors.contains(&factor) => factors.push(factor),
+        Some(_) => {}
+        None => node.get_children().iter().for_each(|child| collect_factors(child, variable, factors)),
+    }
+}
+
+/// Replaces each product of `variable` and `factor` in a node with the variable `replacement`.
+fn replace_product(node: &ASTNode, variable: &str, factor: i64, replacement: &str) -> ASTNode {
+    if product_factor(node, variable) == Some(factor) {
+        return in_place_of(node, NodeType::Identifier(replacement.to_string()));
+    }
+    node.with_children(node.get_children().iter().map(|child| replace_product(child, variable, factor, replacement)).collect())
+}
+
+/// Builds a node that replaces another, keeping whether the node it replaces was parenthesized and its source range.
+fn in_place_of(replaced: &ASTNode, node_type: NodeType) -> ASTNode {
+    let mut node = ASTNode::new(node_type);
+    node.set_parenthesized(replaced.is_parenthesized());
+    if let Some(span) = replaced.get_span() {
+        node.set_span(span);
+    }
+    node
+}
+
+/// Rewrites each product of a `for` loop's induction variable and an integer constant in the loop body, such as `i * 4`
+/// in `for (i = 0; i < n; i = i + 1)`, into a new variable declared before the loop with the product's first value
+/// and advanced by the product of the step and the constant alongside the induction variable. Only integer
+/// induction variables that are set to a constant, advanced by a constant step, and not written by the body are
+/// rewritten; see `dataflow::induction_variable`.
+///
+/// # Parameters
+///
+/// * `ast` - The program to optimize.
+///
+/// # Returns
+///
+/// The optimized program.
+pub fn reduce_loop_strength(ast: &AST) -> AST {
+    let root = ast.get_root();
+    AST::new(StrengthReducer::new(&root).visit(&root))
+}
+
+/// An optimization pass, which rewrites a program into an equivalent one.
+#[derive(Debug, Clone, Copy)]
+pub struct Pass {
+    /// The name of the pass, which identifies it in a `PassManager`.
+    pub name: &'static str,
+    /// Runs the pass on a program whose signed arithmetic overflows as the given mode selects.
+    pub run: fn(&AST, OverflowMode) -> AST,
+}
+
+/// Hoists loop-invariant declarations out of loops, as `hoist_loop_invariants_with`.
+pub const LOOP_INVARIANT_MOTION: Pass = Pass { name: "loop-invariant-motion", run: hoist_loop_invariants_with };
+
+/// Removes stores whose value is never read, as `eliminate_dead_stores`.
+pub const DEAD_STORE_ELIMINATION: Pass = Pass {
+    name: "dead-store-elimination",
+    run: |ast, _| eliminate_dead_stores(ast),
+};
+
+/// Propagates constant arguments into the functions they are passed to, as `propagate_constant_arguments`.
+pub const CONSTANT_ARGUMENT_PROPAGATION: Pass = Pass {
+    name: "constant-argument-propagation",
+    run: |ast, _| propagate_constant_arguments(ast),
+};
+
+/// Rewrites products of induction variables and constants into additions, as `reduce_loop_strength`.
+pub const LOOP_STRENGTH_REDUCTION: Pass = Pass { name: "loop-strength-reduction", run: |ast, _| reduce_loop_strength(ast) };
+
+/// Runs a sequence of optimization passes over a program, in the order they were added.
+#[derive(Debug, Clone, Default)]
+pub struct PassManager {
+    passes: Vec<Pass>,
+    overflow: OverflowMode,
+}
+
+impl PassManager {
+    /// Creates a pass manager with no passes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a pass manager with the passes run at an optimization level.
+    ///
+    /// # Parameters
+    ///
+    /// * `level` - The optimization level. `O1` hoists loop invariants, and `O2` first propagates constant arguments,
+    ///   which can make more declarations invariant, and reduces the strength of loop multiplications.
+    pub fn for_level(level: OptLevel) -> Self {
+        let mut manager = Self::new();
+        if level >= OptLevel::O2 {
+            manager.add(CONSTANT_ARGUMENT_PROPAGATION);
+            manager.add(LOOP_STRENGTH_REDUCTION);
+        }
+        if level >= OptLevel::O1 {
+            manager.add(LOOP_INVARIANT_MOTION);
+        }
+        manager
+    }
+
+    /// Adds a pass to run after the passes already added. A pass that was already added is not added again.
+    pub fn add(&mut self, pass: Pass) {
+        if !self.passes.iter().any(|added| added.name == pass.name) {
+            self.passes.push(pass);
+        }
+    }
+
+    /// Sets what signed arithmetic does on overflow in the programs the passes run on, which is wrapping until set.
+    pub fn set_overflow(&mut self, overflow: OverflowMode) {
+        self.overflow = overflow;
+    }
+
+    /// Returns the names of the passes, in the order they run.
+    pub fn pass_names(&self) -> Vec<&'static str> {
+        self.passes.iter().map(|pass| pass.name).collect()
+    }
+
+    /// Runs every pass over a program in order.
+    ///
+    /// # Parameters
+    ///
+    /// * `ast` - The program to optimize.
+    ///
+    /// # Returns
+    ///
+    /// The program after every pass has run.
+    pub fn run(&self, ast: &AST) -> AST {
+        self.passes.iter().fold(ast.clone(), |ast, pass| (pass.run)(&ast, self.overflow))
+    }
+}
@@ -0,0 +1,244 @@
+//! Renames a variable, parameter, function, or enum variant everywhere it is referenced.
+//!
+//! Names are resolved through the scopes of the program as the symbol table sees them: the top level, each function,
+//! and each block. Functions are visible throughout the top level, and any other name from its declaration to the end
+//! of its scope. Struct, enum, and field names live apart from these names and are not renamed.
+//!
+//! A rename is refused rather than changing what the program means. The new name must not already be declared in the
+//! scope of the symbol, nor be declared in a scope enclosing it or nested in it, where one declaration would shadow
+//! the other, nor be referenced anywhere in its scope, where the reference would start to refer to the renamed symbol.
+
+use std::collections::HashMap;
+
+use common::{
+    ast::{build, core::{ASTNode, AST}, node_type::NodeType, rewrite::{self, Transaction}},
+    error::ErrorType,
+};
+
+/// A name declared in the program.
+struct Declaration {
+    /// The path of the identifier naming it.
+    path: Vec<usize>,
+    name: String,
+    /// The scope it is declared in.
+    scope: usize,
+}
+
+/// A use of a name, which refers to the declaration it resolves to, if any.
+struct Reference {
+    /// The path of the identifier.
+    path: Vec<usize>,
+    name: String,
+    /// The scope it occurs in.
+    scope: usize,
+    declaration: Option<usize>,
+}
+
+/// Walks a program and resolves each identifier to its declaration, recording the scopes it passes through.
+#[derive(Default)]
+struct Resolver {
+    /// The parent of each scope, or None for the top level.
+    parents: Vec<Option<usize>>,
+    /// The scopes enclosing the current node, innermost last, with the declarations made in each so far.
+    open: Vec<(usize, HashMap<String, usize>)>,
+    declarations: Vec<Declaration>,
+    references: Vec<Reference>,
+    path: Vec<usize>,
+}
+
+impl Resolver {
+    fn visit(&mut self, node: &ASTNode) {
+        let children = node.get_children();
+        match node.get_node_type() {
+            NodeType::TopLevelExpression => {
+                self.open_scope();
+                // Functions can be called before they are declared. A function with a prototype is declared by the
+                // first of its prototypes and definition, and named by the others
+                for (index, child) in children.iter().enumerate() {
+                    if !matches!(child.get_node_type(), NodeType::FunctionDeclaration | NodeType::FunctionPrototype) {
+                        continue;
+                    }
+                    let declared = match child.get_children().first().map(|c| c.get_node_type()) {
+                        Some(NodeType::Identifier(name)) => {
+                            self.open.last().is_some_and(|(_, names)| names.contains_key(&name))
+                        }
+                        _ => false,
+                    };
+                    if !declared {
+                        self.declare(child.get_children().first(), &[index, 0]);
+                    }
+                }
+                self.visit_children(&children, 0);
+                self.open.pop();
+            }
+            NodeType::FunctionDeclaration | NodeType::FunctionPrototype => {
+                let name_path: Vec<usize> = self.path.iter().copied().chain([0]).collect();
+                if !self.declarations.iter().any(|declaration| declaration.path == name_path) {
+                    if self.path.len() == 1 {
+                        self.visit_children(&children[..1], 0);
+                    } else {
+                        self.declare(children.first(), &[0]);
+                    }
+                }
+                self.open_scope();
+                self.visit_children(&children, 1);
+                self.open.pop();
+            }
+            NodeType::BlockExpression => {
+                self.open_scope();
+                self.visit_children(&children, 0);
+                self.open.pop();
+            }
+            NodeType::Variable | NodeType::Parameter | NodeType::Variant => {
+                self.declare(children.first(), &[0]);
+                self.visit_children(&children, 1);
+            }
+            // Struct and enum names, fields, type aliases, and attributes are not ordinary names
+            NodeType::EnumDeclaration => self.visit_children(&children, 1),
+            NodeType::StructDeclaration | NodeType::TypeDefinition | NodeType::Attribute(_) => {}
+            NodeType::Identifier(name) => {
+                let declaration = self.open.iter().rev().find_map(|(_, names)| names.get(&name).copied());
+                let scope = self.current_scope();
+                self.references.push(Reference { path: self.path.clone(), name, scope, declaration });
+            }
+            _ => self.visit_children(&children, 0),
+        }
+    }
+
+    /// Visits the children of the current node from the given index on.
+    fn visit_children(&mut self, children: &[ASTNode], from: usize) {
+        for (index, child) in children.iter().enumerate().skip(from) {
+            self.path.push(index);
+            self.visit(child);
+            self.path.pop();
+        }
+    }
+
+    fn open_scope(&mut self) {
+        let parent = self.open.last().map(|(scope, _)| *scope);
+        self.parents.push(parent);
+        self.open.push((self.parents.len() - 1, HashMap::new()));
+    }
+
+    fn current_scope(&self) -> usize {
+        self.open.last().map_or(0, |(scope, _)| *scope)
+    }
+
+    /// Declares a name in the current scope, given the identifier naming it and its path below the current node.
+    fn declare(&mut self, identifier: Option<&ASTNode>, relative: &[usize]) {
+        let name = match identifier.map(|identifier| identifier.get_node_type()) {
+            Some(NodeType::Identifier(name)) => name,
+            _ => return,
+        };
+        let path: Vec<usize> = self.path.iter().chain(relative).copied().collect();
+        let scope = self.current_scope();
+        self.declarations.push(Declaration { path, name: name.clone(), scope });
+        let index = self.declarations.len() - 1;
+        if let Some((_, names)) = self.open.last_mut() {
+            names.insert(name, index);
+        }
+    }
+
+    /// Checks whether `scope` is `ancestor` or nested in it.
+    fn is_within(&self, mut scope: usize, ancestor: usize) -> bool {
+        loop {
+            if scope == ancestor {
+                return true;
+            }
+            match self.parents[scope] {
+                Some(parent) => scope = parent,
+                None => return false,
+            }
+        }
+    }
+}
+
+/// Finds every identifier that has to change to rename a symbol, checking that the rename is safe.
+///
+/// # Parameters
+///
+/// * `ast` - The program.
+/// * `path` - The path of an identifier declaring or referring to the symbol, as `rewrite::find` gives it.
+/// * `new_name` - The name to rename the symbol to.
+///
+/// # Returns
+///
+/// The paths of the identifiers declaring and referring to the symbol, the declaration first and the references in
+/// program order, or the reasons the rename is refused.
+///
+/// # Errors
+///
+/// * Returns an `InvalidRename` error if the path is not an identifier naming a declared symbol, or if the new name
+///   would collide with or shadow another declaration, or capture a reference to another symbol.
+pub fn rename_paths(ast: &AST, path: &[usize], new_name: &str) -> Result<Vec<Vec<usize>>, Vec<ErrorType>> {
+    let root = ast.get_root();
+    let mut resolver = Resolver::default();
+    resolver.visit(&root);
+
+    let symbol = match rewrite::node_at(&root, path).map(|node| node.get_node_type()) {
+        Some(NodeType::Identifier(name)) => name,
+        _ => String::new(),
+    };
+    let invalid = |reason: String| ErrorType::InvalidRename {
+        symbol: symbol.clone(),
+        name: new_name.to_string(),
+        reason,
+    };
+    let target = resolver.declarations.iter().position(|declaration| declaration.path == path)
+        .or_else(|| resolver.references.iter().find(|reference| reference.path == path)?.declaration);
+    let (index, target) = match target {
+        Some(index) => (index, &resolver.declarations[index]),
+        None => return Err(vec![invalid("it does not name a declared variable, parameter, or function".to_string())]),
+    };
+    let mut paths = vec![target.path.clone()];
+    paths.extend(resolver.references.iter()
+        .filter(|reference| reference.declaration == Some(index))
+        .map(|reference| reference.path.clone()));
+    if new_name == target.name {
+        return Ok(paths);
+    }
+
+    let mut errors = Vec::new();
+    for declaration in resolver.declarations.iter().filter(|declaration| declaration.name == new_name) {
+        if declaration.scope == target.scope {
+            errors.push(invalid(format!("`{}` is already declared in the same scope", new_name)));
+        } else if resolver.is_within(target.scope, declaration.scope) {
+            errors.push(invalid(format!("it would shadow the `{}` declared in an enclosing scope", new_name)));
+        } else if resolver.is_within(declaration.scope, target.scope) {
+            errors.push(invalid(format!("the `{}` declared in a nested scope would shadow it", new_name)));
+        }
+    }
+    let captures = resolver.references.iter().any(|reference| {
+        reference.name == new_name && resolver.is_within(reference.scope, target.scope)
+    });
+    if captures {
+        errors.push(invalid(format!("a reference to another `{}` in its scope would refer to it instead", new_name)));
+    }
+    errors.dedup();
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(paths)
+}
+
+/// Renames a symbol everywhere it is declared and referenced.
+///
+/// # Parameters
+///
+/// * `ast` - The program.
+/// * `path` - The path of an identifier declaring or referring to the symbol, as `rewrite::find` gives it.
+/// * `new_name` - The name to rename the symbol to.
+///
+/// # Returns
+///
+/// The renamed program, or the reasons the rename is refused.
+///
+/// # Errors
+///
+/// * Returns the errors of `rename_paths`, and a `SyntaxError` or `ReservedKeyword` error if the new name is not a
+///   valid identifier.
+pub fn rename_symbol(ast: &AST, path: &[usize], new_name: &str) -> Result<AST, Vec<ErrorType>> {
+    rename_paths(ast, path, new_name)?.iter()
+        .fold(Transaction::new(), |transaction, path| transaction.replace(path, build::identifier(new_name)))
+        .commit(ast)
+}
@@ -2,10 +2,10 @@
 //! code blocks.
 
 use common::{
-    ast::core::ASTNode, 
+    ast::{core::ASTNode, data_type::DataType, node_type::NodeType},
     error::ErrorType
 };
-use crate::core::SymbolTableStack;
+use crate::core::{declared_symbol, SymbolInfo, SymbolTableStack, SymbolValue};
 
 impl SymbolTableStack {
     /// Processes an initialization node in the symbol table stack.
@@ -19,8 +19,83 @@ impl SymbolTableStack {
     /// Returns a `Result` with Ok indicating the process was successful or Err containing a 
     /// vector if errors if there were any.
     pub fn sym_table_init(&mut self, node: &ASTNode) -> Result<(), Vec<ErrorType>> {
-        let _ = node;
-        unimplemented!();
-    }    
+        // The parser wraps the declared name and type in a `Variable` node
+        let declared = match node.get_children().first() {
+            Some(variable) if variable.get_node_type() == NodeType::Variable => declared_symbol(variable),
+            _ => declared_symbol(node),
+        };
+        let (name, data_type) = declared.ok_or_else(|| vec![ErrorType::DevError {
+            message: "Initialization does not declare a named variable".to_string(),
+        }])?;
+        self.add_element(name, SymbolInfo::new(data_type, SymbolValue::NoAssociatedValue)).map_err(|e| vec![e])
+    }
 
+    /// Processes a struct declaration in the symbol table stack, adding the struct with its fields to the current
+    /// scope. The fields of anonymous struct and union members are listed as fields of the struct, since they are
+    /// accessed as such.
+    /// 
+    /// # Parameters
+    ///
+    /// - `struct_node`: A reference to an `ASTNode` containing a struct declaration.
+    /// 
+    /// # Returns
+    ///
+    /// Returns a `Result` with Ok indicating the process was successful or Err containing a 
+    /// vector if errors if there were any.
+    pub fn sym_table_struct(&mut self, node: &ASTNode) -> Result<(), Vec<ErrorType>> {
+        let Some(NodeType::Identifier(name)) = node.get_children().first().map(|c| c.get_node_type()) else {
+            return Ok(());
+        };
+        let mut fields = Vec::new();
+        struct_fields(node, &mut fields);
+        self.add_element(name, SymbolInfo::new(DataType::Struct, SymbolValue::StructValue { fields })).map_err(|e| vec![e])
+    }
+
+    /// Processes an enum declaration in the symbol table stack, adding the enum with its variants to the current
+    /// scope.
+    /// 
+    /// # Parameters
+    ///
+    /// - `enum_node`: A reference to an `ASTNode` containing an enum declaration.
+    /// 
+    /// # Returns
+    ///
+    /// Returns a `Result` with Ok indicating the process was successful or Err containing a 
+    /// vector if errors if there were any.
+    pub fn sym_table_enum(&mut self, node: &ASTNode) -> Result<(), Vec<ErrorType>> {
+        let children = node.get_children();
+        let Some(NodeType::Identifier(name)) = children.first().map(|c| c.get_node_type()) else {
+            return Err(vec![ErrorType::DevError { message: "Enum declaration has no name".to_string() }]);
+        };
+        let variants = children.iter()
+            .filter(|c| c.get_node_type() == NodeType::Variant)
+            .filter_map(|variant| match variant.get_children().first().map(|c| c.get_node_type()) {
+                Some(NodeType::Identifier(variant)) => Some(variant),
+                _ => None,
+            })
+            .collect();
+        self.add_element(name, SymbolInfo::new(DataType::Enum, SymbolValue::EnumValue { variants })).map_err(|e| vec![e])
+    }
+}
+
+/// Collects the name and type of each field of a struct or union, in order, including the fields of its anonymous
+/// members.
+fn struct_fields(aggregate: &ASTNode, fields: &mut Vec<(String, DataType)>) {
+    for member in aggregate.get_children() {
+        match member.get_node_type() {
+            NodeType::Field => {
+                let children = member.get_children();
+                if let (Some(NodeType::Literal(name)), Some(NodeType::Type(data_type))) =
+                    (children.first().map(|c| c.get_node_type()), children.get(1).map(|c| c.get_node_type())) {
+                    fields.push((name, data_type));
+                }
+            }
+            NodeType::StructDeclaration | NodeType::UnionDeclaration
+                if !matches!(member.get_children().first().map(|c| c.get_node_type()), Some(NodeType::Identifier(_))) =>
+            {
+                struct_fields(&member, fields);
+            }
+            _ => {}
+        }
+    }
 }
@@ -0,0 +1,943 @@
+//! Contains type checks that run on a parsed program and report errors that stop compilation.
+//!
+//! The argument check matches the arguments of calls to functions declared in the program against the declared
+//! parameter types. Structs are passed by value, so a struct parameter only accepts a variable of the same struct
+//! type, and a struct variable can only be passed where that struct is expected.
+//!
+//! The initializer check matches literal initializers against the declared type of the variable they initialize,
+//! so that a string does not initialize a number and a fractional number does not initialize a `bool` or integer.
+//!
+//! The switch check rejects a switch statement with two cases of the same label, and a switch on strings whose
+//! labels are not all strings or whose scrutinee has side effects, since it is compared once per case.
+//!
+//! Each case of a switch is its own scope. The case declaration check reports a case that uses a variable declared
+//! in an earlier case, which C would allow while jumping past its initialization.
+//!
+//! The fields of a struct's anonymous struct and union members are accessed as if they were fields of the struct.
+//! `field_path` resolves such a field to the members leading to it, and the member check reports a struct that
+//! declares the same name twice once its anonymous members are flattened.
+//!
+//! An enum's constants are stored as its underlying type, `int` unless another integer type is chosen. The enum
+//! check reports a constant whose value does not fit in that type.
+//!
+//! A variable declared `const` cannot be written after it is initialized. The const check reports every assignment,
+//! compound assignment, increment, or decrement of one, following shadowing by declarations in inner scopes.
+//!
+//! Each check records the source range of the node an error was found at, when the parser gave it one, so that
+//! `check_program` can report where in the source each error is.
+//!
+//! `type_at_path` infers the type of an expression anywhere in the program, for editors to show on hover, and
+//! `narrows` decides whether storing a value implicitly converts it to a narrower type, for the narrowing lint.
+
+use std::collections::HashMap;
+
+use common::{
+    ast::{core::{ASTNode, AST}, data_type::DataType, node_type::NodeType},
+    diagnostic::DiagnosticSink,
+    error::ErrorType,
+    location::Span,
+    source::LineIndex,
+};
+
+use crate::{dataflow::has_side_effects, dump::{c_type_name, type_spelling}};
+
+/// An error found by a check, with the source range of the node it was found at, if the node has one.
+type LocatedError = (ErrorType, Option<Span>);
+
+/// Drops the source ranges of the errors found by a check.
+fn without_spans(errors: Vec<LocatedError>) -> Vec<ErrorType> {
+    errors.into_iter().map(|(error, _)| error).collect()
+}
+
+/// Walks a program and checks its calls, tracking the declared type of each variable in scope.
+struct ArgumentChecker {
+    /// The C spelling of each parameter type of each function declared at the top level.
+    signatures: HashMap<String, Vec<String>>,
+    scopes: Vec<HashMap<String, String>>,
+    errors: Vec<LocatedError>,
+}
+
+impl ArgumentChecker {
+    fn visit(&mut self, node: &ASTNode) {
+        match node.get_node_type() {
+            NodeType::FunctionDeclaration | NodeType::FunctionPrototype | NodeType::BlockExpression => {
+                self.scopes.push(HashMap::new());
+                for child in node.get_children() {
+                    self.visit(&child);
+                }
+                self.scopes.pop();
+                return;
+            }
+            NodeType::Variable | NodeType::Parameter => self.declare(node),
+            NodeType::FunctionCall => self.check_call(node),
+            _ => {}
+        }
+        for child in node.get_children() {
+            self.visit(&child);
+        }
+    }
+
+    /// Records the type of a `Variable` or `Parameter` node whose children are its identifier and type.
+    fn declare(&mut self, node: &ASTNode) {
+        let children = node.get_children();
+        let name = match children.first().map(|c| c.get_node_type()) {
+            Some(NodeType::Identifier(name)) => name,
+            _ => return,
+        };
+        if let (Some(data_type), Some(scope)) = (children.get(1).and_then(type_spelling), self.scopes.last_mut()) {
+            scope.insert(name, data_type);
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<&String> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    /// Determines the C type of an argument expression, or `None` if it cannot be determined.
+    fn type_of(&self, node: &ASTNode) -> Option<String> {
+        match node.get_node_type() {
+            NodeType::Identifier(name) => self.lookup(&name).cloned(),
+            NodeType::Variable => match node.get_children().first().map(|c| c.get_node_type()) {
+                Some(NodeType::Identifier(name)) => self.lookup(&name).cloned(),
+                _ => None,
+            },
+            NodeType::AssignedValue => node.get_children().first().and_then(|child| self.type_of(child)),
+            NodeType::Literal(value) => Some(literal_type(&value).to_string()),
+            _ => None,
+        }
+    }
+
+    /// Checks the arguments of one call against the parameters of the called function, if it is declared in the
+    /// program.
+    fn check_call(&mut self, node: &ASTNode) {
+        let children = node.get_children();
+        let parameters = match children.first().map(|c| c.get_node_type()) {
+            Some(NodeType::Identifier(name)) => match self.signatures.get(&name) {
+                Some(parameters) => parameters.clone(),
+                None => return,
+            },
+            _ => return,
+        };
+
+        for (argument, parameter) in children[1..].iter().zip(&parameters) {
+            let Some(argument_type) = self.type_of(argument) else { continue };
+            let passes_struct = parameter.starts_with("struct ") || argument_type.starts_with("struct ");
+            if passes_struct && argument_type != *parameter {
+                self.errors.push((ErrorType::TypeMismatch {
+                    left_type: parameter.clone(),
+                    right_type: argument_type,
+                }, argument.get_span().or(node.get_span())));
+            }
+        }
+    }
+}
+
+/// Checks the arguments of every call to a function declared at the top level of the program, reporting a type
+/// mismatch when a struct argument is passed for a parameter of a different type, or any other argument is passed
+/// for a struct parameter. Arguments whose type cannot be determined are not checked.
+///
+/// # Parameters
+///
+/// * `ast` - The program to check.
+///
+/// # Returns
+///
+/// The errors found, in program order. Each mismatch reports the parameter type as the left type and the argument
+/// type as the right type.
+pub fn check_function_arguments(ast: &AST) -> Vec<ErrorType> {
+    without_spans(located_argument_errors(ast))
+}
+
+/// Checks calls like `check_function_arguments`, recording the range of each mismatched argument.
+fn located_argument_errors(ast: &AST) -> Vec<LocatedError> {
+    let mut signatures = HashMap::new();
+    for node in ast.get_root().get_children() {
+        if !matches!(node.get_node_type(), NodeType::FunctionDeclaration | NodeType::FunctionPrototype) {
+            continue;
+        }
+        let children = node.get_children();
+        if let Some(NodeType::Identifier(name)) = children.first().map(|c| c.get_node_type()) {
+            let parameters = children.iter()
+                .filter(|c| c.get_node_type() == NodeType::Parameter)
+                .map(|p| p.get_children().get(1).and_then(type_spelling).unwrap_or_default())
+                .collect();
+            signatures.insert(name, parameters);
+        }
+    }
+
+    let mut checker = ArgumentChecker { signatures, scopes: vec![HashMap::new()], errors: Vec::new() };
+    checker.visit(&ast.get_root());
+    checker.errors
+}
+
+/// Returns the C type of a literal as stored by the parser.
+fn literal_type(value: &str) -> &'static str {
+    if value.starts_with('"') {
+        "char *"
+    } else if value.starts_with('\'') {
+        "char"
+    } else if value.contains(['.', 'e', 'E']) && value.ends_with(['f', 'F']) {
+        "float"
+    } else if value.contains(['.', 'e', 'E']) {
+        "double"
+    } else {
+        "int"
+    }
+}
+
+/// Checks whether a literal of the given C type can initialize a variable of a declared type, or `None` if the
+/// declared type is not checked.
+fn literal_initializes(declared: DataType, literal: &str) -> Option<bool> {
+    let accepts_fraction = matches!(declared, DataType::Float | DataType::Double);
+    let accepts_number = accepts_fraction || matches!(declared,
+        DataType::Integer | DataType::Long | DataType::Boolean | DataType::Char | DataType::Unsign | DataType::Sign
+        | DataType::UnsignedLong | DataType::LongLong | DataType::UnsignedLongLong | DataType::SignedChar
+        | DataType::UnsignedChar);
+    if !accepts_number && declared != DataType::String {
+        return None;
+    }
+    Some(match literal {
+        "char *" => declared == DataType::String,
+        "double" | "float" => accepts_fraction,
+        _ => accepts_number,
+    })
+}
+
+/// Checks the literal initializer of every variable declaration against the variable's declared type, reporting a
+/// type mismatch when a string initializes a number, a number initializes a string, or a fractional number
+/// initializes a `bool` or integer, as in `bool b = 3.5;` or `int x = "str";`. Initializers that are not literals,
+/// and variables of types other than numbers, `bool`, `char`, and strings, are not checked.
+///
+/// # Parameters
+///
+/// * `ast` - The program to check.
+///
+/// # Returns
+///
+/// The errors found, in program order. Each mismatch reports the declared type as the left type and the literal's
+/// type as the right type.
+pub fn check_initializers(ast: &AST) -> Vec<ErrorType> {
+    without_spans(located_initializer_errors(ast))
+}
+
+/// Checks initializers like `check_initializers`, recording the range of each mismatched declaration.
+fn located_initializer_errors(ast: &AST) -> Vec<LocatedError> {
+    let mut errors = Vec::new();
+    let mut pending = vec![ast.get_root()];
+    while let Some(node) = pending.pop() {
+        if node.get_node_type() == NodeType::Initialization {
+            let children = node.get_children();
+            let type_node = children.first().and_then(|variable| variable.get_children().get(1).cloned());
+            let value = children.get(1)
+                .filter(|c| c.get_node_type() == NodeType::AssignedValue)
+                .and_then(|assigned| assigned.get_children().first().map(|c| c.get_node_type()));
+            if let (Some(type_node), Some(NodeType::Literal(value))) = (type_node, value) {
+                if let NodeType::Type(declared) = type_node.get_node_type() {
+                    let literal = literal_type(&value);
+                    if literal_initializes(declared, literal) == Some(false) {
+                        errors.push((ErrorType::TypeMismatch {
+                            left_type: type_spelling(&type_node).unwrap_or_default(),
+                            right_type: literal.to_string(),
+                        }, node.get_span()));
+                    }
+                }
+            }
+        }
+        pending.extend(node.get_children().into_iter().rev());
+    }
+    errors
+}
+
+/// Returns the value a case label stands for, so that labels written differently but with the same value, such as
+/// `65`, `0x41`, and `'A'`, are found to be equal.
+fn case_label_key(value: &str) -> String {
+    let mut chars = value.chars();
+    if let (Some('\''), Some(c), Some('\''), None) = (chars.next(), chars.next(), chars.next(), chars.next()) {
+        return (c as u32).to_string();
+    }
+    let parsed = match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(hex) => i64::from_str_radix(hex, 16).ok(),
+        None => value.parse::<i64>().ok(),
+    };
+    parsed.map(|n| n.to_string()).unwrap_or_else(|| value.to_string())
+}
+
+/// Checks that the case labels of a switch match the type of its scrutinee when it is known. A switch on a
+/// floating-point value is invalid, and on an integer, `char`, or enum value every label must be an integer or
+/// character literal or an enum constant whose value the scrutinee's type can hold, so that the case can be reached.
+fn check_label_types(
+    switch: &ASTNode,
+    scrutinee_type: Option<DataType>,
+    labels: &[ASTNode],
+    constants: &HashMap<String, (i64, DataType)>,
+    errors: &mut Vec<LocatedError>,
+) {
+    let Some(scrutinee_type) = scrutinee_type else { return };
+    if matches!(scrutinee_type, DataType::Float | DataType::Double) {
+        errors.push((ErrorType::InvalidSwitch {
+            reason: format!("the scrutinee of a switch cannot have the type `{}`", c_type_name(scrutinee_type)),
+        }, switch.get_span()));
+        return;
+    }
+    let Some((min, max)) = integer_range(scrutinee_type) else { return };
+    for label in labels {
+        let (written, value) = match &label.get_node_type() {
+            NodeType::Literal(value) => (value.clone(), case_label_key(value).parse::<i128>().ok()),
+            NodeType::Identifier(name) => match constants.get(name) {
+                Some((value, _)) => (name.clone(), Some(*value as i128)),
+                None => continue,
+            },
+            _ => continue,
+        };
+        let reason = match value {
+            Some(value) if value < min || value > max => format!(
+                "case label `{}` does not fit in the type `{}` of the scrutinee", written, c_type_name(scrutinee_type)
+            ),
+            Some(_) => continue,
+            None => format!(
+                "case label `{}` does not match the type `{}` of the scrutinee", written, c_type_name(scrutinee_type)
+            ),
+        };
+        errors.push((ErrorType::InvalidSwitch { reason }, label.get_span().or(switch.get_span())));
+    }
+}
+
+/// Checks the cases of every switch statement, reporting a duplicate case label when two cases have the same value,
+/// as in `case "a": ... case "a":` or `case 65: ... case 'A':`. A switch with a string label is a switch on strings,
+/// and is also reported as invalid when any other label is not a string, or when its scrutinee has side effects, since
+/// it is evaluated once for each case it is compared with. A label that does not match the type of the scrutinee,
+/// such as `case 300:` in a switch on a `char`, is reported as invalid too.
+///
+/// # Parameters
+///
+/// * `ast` - The program to check.
+///
+/// # Returns
+///
+/// The errors found, in program order. Each duplicate reports the label of the later case as written.
+pub fn check_switch_labels(ast: &AST) -> Vec<ErrorType> {
+    without_spans(located_switch_errors(ast))
+}
+
+/// Checks switches like `check_switch_labels`, recording the range of each offending label, or of the switch when
+/// the error is not about one label.
+fn located_switch_errors(ast: &AST) -> Vec<LocatedError> {
+    let mut errors = Vec::new();
+    let constants = enum_constants(ast);
+    let mut pending = vec![(ast.get_root(), Vec::new())];
+    while let Some((node, path)) = pending.pop() {
+        let children = node.get_children();
+        if node.get_node_type() == NodeType::SwitchStatement && children.len() == 2 {
+            let labels: Vec<ASTNode> = children[1].get_children().iter()
+                .filter(|arm| arm.get_node_type() == NodeType::Case)
+                .filter_map(|arm| arm.get_children().first().cloned())
+                .collect();
+
+            let mut seen = Vec::new();
+            for label in &labels {
+                let (written, key) = match label.get_node_type() {
+                    NodeType::Literal(value) => (value.clone(), case_label_key(&value)),
+                    NodeType::Identifier(name) => (name.clone(), name),
+                    _ => continue,
+                };
+                if seen.contains(&key) {
+                    errors.push((ErrorType::DuplicateCaseLabel { label: written }, label.get_span().or(node.get_span())));
+                } else {
+                    seen.push(key);
+                }
+            }
+
+            let is_string = |label: &ASTNode| {
+                matches!(label.get_node_type(), NodeType::Literal(value) if value.starts_with('"'))
+            };
+            if labels.iter().any(is_string) {
+                if !labels.iter().all(is_string) {
+                    errors.push((ErrorType::InvalidSwitch {
+                        reason: "a switch on strings can only have string case labels".to_string(),
+                    }, node.get_span()));
+                }
+                if has_side_effects(&children[0]) {
+                    errors.push((ErrorType::InvalidSwitch {
+                        reason: "the scrutinee of a switch on strings cannot have side effects".to_string(),
+                    }, children[0].get_span().or(node.get_span())));
+                }
+            } else {
+                let scrutinee_path: Vec<usize> = path.iter().copied().chain([0]).collect();
+                check_label_types(&node, type_at_path(ast, &scrutinee_path), &labels, &constants, &mut errors);
+            }
+        }
+        pending.extend(children.into_iter().enumerate().rev().map(|(index, child)| {
+            let mut child_path = path.clone();
+            child_path.push(index);
+            (child, child_path)
+        }));
+    }
+    errors
+}
+
+/// Returns the names declared by the statements directly in a block, ignoring those in blocks nested in it.
+fn declared_in_block(block: &ASTNode) -> Vec<String> {
+    block.get_children().iter()
+        .filter(|statement| statement.get_node_type() == NodeType::Initialization)
+        .filter_map(|statement| statement.get_children().first()?.get_children().first().map(|c| c.get_node_type()))
+        .filter_map(|name| match name {
+            NodeType::Identifier(name) => Some(name),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Checks the cases of every switch statement, reporting a case that uses a variable declared directly in the body of
+/// an earlier case without declaring it again, as in `case 1: int x = 1; break; case 2: x++;`. Jumping to the later
+/// case would skip the initialization, so each case is given its own scope and the use is rejected.
+///
+/// # Parameters
+///
+/// * `ast` - The program to check.
+///
+/// # Returns
+///
+/// The errors found, in program order, one for each variable used in each case that jumps past its initialization.
+pub fn check_case_declarations(ast: &AST) -> Vec<ErrorType> {
+    without_spans(located_case_declaration_errors(ast))
+}
+
+/// Checks switch cases like `check_case_declarations`, recording the range of the first offending use of each
+/// variable.
+fn located_case_declaration_errors(ast: &AST) -> Vec<LocatedError> {
+    let mut errors = Vec::new();
+    let mut pending = vec![ast.get_root()];
+    while let Some(node) = pending.pop() {
+        let children = node.get_children();
+        if node.get_node_type() == NodeType::SwitchStatement && children.len() == 2 {
+            let mut earlier: Vec<String> = Vec::new();
+            for arm in children[1].get_children() {
+                let arm_children = arm.get_children();
+                let label = match (arm.get_node_type(), arm_children.first().map(|c| c.get_node_type())) {
+                    (NodeType::Case, Some(NodeType::Literal(value) | NodeType::Identifier(value))) => {
+                        format!("case {}", value)
+                    }
+                    (NodeType::Default, _) => "default".to_string(),
+                    _ => continue,
+                };
+                let Some(body) = arm_children.last() else { continue };
+                let declared = declared_in_block(body);
+
+                let mut reported: Vec<String> = Vec::new();
+                let mut uses = vec![body.clone()];
+                while let Some(node) = uses.pop() {
+                    let mut node_children = node.get_children();
+                    if node.get_node_type() == NodeType::MemberAccess {
+                        // The field name of `p.x` is not a variable
+                        node_children.truncate(1);
+                    }
+                    if let NodeType::Identifier(name) = node.get_node_type() {
+                        if earlier.contains(&name) && !declared.contains(&name) && !reported.contains(&name) {
+                            errors.push((
+                                ErrorType::JumpPastInitialization { variable: name.clone(), label: label.clone() },
+                                node.get_span().or(arm.get_span()),
+                            ));
+                            reported.push(name);
+                        }
+                    }
+                    uses.extend(node_children.into_iter().rev());
+                }
+                earlier.extend(declared);
+            }
+        }
+        pending.extend(children.into_iter().rev());
+    }
+    errors
+}
+
+/// Returns the members of a struct or union that have storage: its `Field` nodes and anonymous members, in order.
+fn aggregate_members(aggregate: &ASTNode) -> Vec<ASTNode> {
+    aggregate.get_children().into_iter()
+        .filter(|member| match member.get_node_type() {
+            NodeType::Field | NodeType::UnionDeclaration => true,
+            NodeType::StructDeclaration => !matches!(
+                member.get_children().first().map(|c| c.get_node_type()),
+                Some(NodeType::Identifier(_))
+            ),
+            _ => false,
+        })
+        .collect()
+}
+
+/// Resolves a field of a struct, looking through its anonymous struct and union members as C does.
+///
+/// # Parameters
+///
+/// * `aggregate` - The `StructDeclaration` or `UnionDeclaration` the field is accessed on.
+/// * `field` - The name of the field.
+///
+/// # Returns
+///
+/// The index of each member on the way to the field, counting only fields and anonymous members, so a field
+/// directly in the struct has a path of one index. `None` if the struct has no such field.
+pub fn field_path(aggregate: &ASTNode, field: &str) -> Option<Vec<usize>> {
+    aggregate_members(aggregate).iter().enumerate().find_map(|(index, member)| match member.get_node_type() {
+        NodeType::Field => match member.get_children().first().map(|c| c.get_node_type()) {
+            Some(NodeType::Literal(name)) if name == field => Some(vec![index]),
+            _ => None,
+        },
+        _ => field_path(member, field).map(|mut path| {
+            path.insert(0, index);
+            path
+        }),
+    })
+}
+
+/// Collects the names of the fields of a struct or union, including those of its anonymous members.
+fn flattened_field_names(aggregate: &ASTNode, names: &mut Vec<String>) {
+    for member in aggregate_members(aggregate) {
+        match member.get_children().first().map(|c| c.get_node_type()) {
+            Some(NodeType::Literal(name)) if member.get_node_type() == NodeType::Field => names.push(name),
+            _ => flattened_field_names(&member, names),
+        }
+    }
+}
+
+/// Checks every struct declared at the top level, reporting a name declared by two of its fields, counting the
+/// fields of its anonymous members as its own, since accessing that name would be ambiguous.
+///
+/// # Parameters
+///
+/// * `ast` - The program to check.
+///
+/// # Returns
+///
+/// One `DuplicateMember` error for each name declared more than once in a struct.
+pub fn check_struct_members(ast: &AST) -> Vec<ErrorType> {
+    without_spans(located_member_errors(ast))
+}
+
+/// Checks structs like `check_struct_members`, recording the range of each offending struct declaration.
+fn located_member_errors(ast: &AST) -> Vec<LocatedError> {
+    let mut errors = Vec::new();
+    for declaration in ast.get_root().get_children() {
+        let structure = match (declaration.get_node_type(), declaration.get_children().first().map(|c| c.get_node_type())) {
+            (NodeType::StructDeclaration, Some(NodeType::Identifier(name))) => name,
+            _ => continue,
+        };
+        let mut names = Vec::new();
+        flattened_field_names(&declaration, &mut names);
+        let mut reported: Vec<String> = Vec::new();
+        for (index, name) in names.iter().enumerate() {
+            if names[..index].contains(name) && !reported.contains(name) {
+                errors.push((
+                    ErrorType::DuplicateMember { structure: structure.clone(), member: name.clone() },
+                    declaration.get_span(),
+                ));
+                reported.push(name.clone());
+            }
+        }
+    }
+    errors
+}
+
+/// Returns the underlying type of an enum declaration, the type its constants are stored as.
+fn underlying_type(declaration: &ASTNode) -> DataType {
+    declaration.get_children().iter().find_map(|child| match child.get_node_type() {
+        NodeType::Type(data_type) => Some(data_type),
+        _ => None,
+    }).unwrap_or(DataType::Integer)
+}
+
+/// Returns the smallest and largest values an integer type holds, or `None` if it is not an integer type.
+pub(crate) fn integer_range(data_type: DataType) -> Option<(i128, i128)> {
+    match data_type {
+        DataType::Boolean => Some((0, 1)),
+        DataType::Char | DataType::SignedChar => Some((i8::MIN.into(), i8::MAX.into())),
+        DataType::UnsignedChar => Some((0, u8::MAX.into())),
+        DataType::Integer | DataType::Sign => Some((i32::MIN.into(), i32::MAX.into())),
+        DataType::Unsign => Some((0, u32::MAX.into())),
+        DataType::Long | DataType::LongLong => Some((i64::MIN.into(), i64::MAX.into())),
+        DataType::UnsignedLong | DataType::UnsignedLongLong => Some((0, u64::MAX.into())),
+        _ => None,
+    }
+}
+
+/// Finds the constants of the enums declared at the top level, with their values and the underlying type of their
+/// enum.
+fn enum_constants(ast: &AST) -> HashMap<String, (i64, DataType)> {
+    let mut constants = HashMap::new();
+    for declaration in ast.get_root().get_children() {
+        if declaration.get_node_type() != NodeType::EnumDeclaration {
+            continue;
+        }
+        let underlying = underlying_type(&declaration);
+        let variants = declaration.get_children().into_iter().filter(|c| c.get_node_type() == NodeType::Variant);
+        for (value, variant) in variants.enumerate() {
+            if let Some(NodeType::Identifier(name)) = variant.get_children().first().map(|c| c.get_node_type()) {
+                constants.insert(name, (value as i64, underlying));
+            }
+        }
+    }
+    constants
+}
+
+/// Checks every enum declared at the top level, reporting each constant whose value does not fit in the enum's
+/// underlying type. The constants are numbered from 0 in declaration order.
+///
+/// # Parameters
+///
+/// * `ast` - The program to check.
+///
+/// # Returns
+///
+/// One `EnumeratorOutOfRange` error for each constant that does not fit.
+pub fn check_enum_declarations(ast: &AST) -> Vec<ErrorType> {
+    without_spans(located_enum_errors(ast))
+}
+
+/// Checks enums like `check_enum_declarations`, recording the range of each offending constant, or of its enum
+/// declaration if the constant has none.
+fn located_enum_errors(ast: &AST) -> Vec<LocatedError> {
+    let mut errors = Vec::new();
+    for declaration in ast.get_root().get_children() {
+        let children = declaration.get_children();
+        let enumeration = match (declaration.get_node_type(), children.first().map(|c| c.get_node_type())) {
+            (NodeType::EnumDeclaration, Some(NodeType::Identifier(name))) => name,
+            _ => continue,
+        };
+        let underlying = underlying_type(&declaration);
+        let Some((_, max)) = integer_range(underlying) else { continue };
+        let variants = children.iter().filter(|c| c.get_node_type() == NodeType::Variant);
+        for (value, variant) in variants.enumerate() {
+            if let (true, Some(NodeType::Identifier(enumerator))) =
+                (value as i128 > max, variant.get_children().first().map(|c| c.get_node_type()))
+            {
+                errors.push((ErrorType::EnumeratorOutOfRange {
+                    enumeration: enumeration.clone(),
+                    enumerator,
+                    value: value.to_string(),
+                    underlying: c_type_name(underlying).to_string(),
+                }, variant.get_span().or(declaration.get_span())));
+            }
+        }
+    }
+    errors
+}
+
+/// Walks a program and checks its writes, tracking whether each variable in scope is declared `const`.
+struct ConstChecker {
+    scopes: Vec<HashMap<String, bool>>,
+    errors: Vec<LocatedError>,
+}
+
+impl ConstChecker {
+    fn visit(&mut self, node: &ASTNode) {
+        let children = node.get_children();
+        let target = match node.get_node_type() {
+            NodeType::FunctionDeclaration | NodeType::FunctionPrototype | NodeType::BlockExpression => {
+                self.scopes.push(HashMap::new());
+                for child in &children {
+                    self.visit(child);
+                }
+                self.scopes.pop();
+                return;
+            }
+            NodeType::Variable | NodeType::Parameter => {
+                self.declare(node);
+                None
+            }
+            NodeType::Assignment | NodeType::CompoundAssignment | NodeType::PostfixExpression => children.first(),
+            NodeType::UnaryExpression => match children.first().map(|c| c.get_node_type()) {
+                Some(NodeType::Operator(op)) if op == "++" || op == "--" => children.get(1),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(NodeType::Identifier(name)) = target.map(|t| t.get_node_type()) {
+            if self.scopes.iter().rev().find_map(|scope| scope.get(&name)) == Some(&true) {
+                self.errors.push((ErrorType::AssignmentToConst { variable: name }, node.get_span()));
+            }
+        }
+        for child in &children {
+            self.visit(child);
+        }
+    }
+
+    /// Records whether a `Variable` or `Parameter` node, whose children are its identifier and type, is `const`.
+    fn declare(&mut self, node: &ASTNode) {
+        let children = node.get_children();
+        let Some(NodeType::Identifier(name)) = children.first().map(|c| c.get_node_type()) else { return };
+        let is_const = children.get(1).is_some_and(|type_node| {
+            type_node.get_children().iter().any(|c| c.get_node_type() == NodeType::Qualifier("const".to_string()))
+        });
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name, is_const);
+        }
+    }
+}
+
+/// Checks every write to a variable, reporting an assignment, compound assignment, increment, or decrement of a
+/// variable declared `const`. Its initializer is not a write.
+///
+/// # Parameters
+///
+/// * `ast` - The program to check.
+///
+/// # Returns
+///
+/// The errors found, in program order, one for each write to a `const` variable.
+pub fn check_const_assignments(ast: &AST) -> Vec<ErrorType> {
+    without_spans(located_const_errors(ast))
+}
+
+/// Checks writes like `check_const_assignments`, recording the range of each offending write.
+fn located_const_errors(ast: &AST) -> Vec<LocatedError> {
+    let mut checker = ConstChecker { scopes: vec![HashMap::new()], errors: Vec::new() };
+    checker.visit(&ast.get_root());
+    checker.errors
+}
+
+/// Runs every type check on a program: the arguments of calls, then literal initializers, then switch labels, then
+/// declarations in switch cases, then the members of structs, then the constants of enums, then writes to `const`
+/// variables.
+///
+/// An error is reported at the start of the node it was found at when the program was parsed from `source` with
+/// source ranges, and without a location otherwise.
+///
+/// # Parameters
+///
+/// * `ast` - The program to check.
+/// * `source` - The lines of the source the program was parsed from, if it was parsed from one.
+/// * `diagnostics` - The sink the errors found are reported to, in the order the checks run.
+pub fn check_program(ast: &AST, source: Option<&LineIndex>, diagnostics: &mut DiagnosticSink) {
+    let checks: [fn(&AST) -> Vec<LocatedError>; 7] = [
+        located_argument_errors,
+        located_initializer_errors,
+        located_switch_errors,
+        located_case_declaration_errors,
+        located_member_errors,
+        located_enum_errors,
+        located_const_errors,
+    ];
+    for check in checks {
+        for (error, span) in check(ast) {
+            match (source, span) {
+                (Some(index), Some(span)) => diagnostics.error_at(error, index.location(span.offset)),
+                _ => diagnostics.error(error),
+            }
+        }
+    }
+}
+
+/// Walks a program to the node at a path, tracking the declared type of each variable in scope.
+struct TypeResolver<'a> {
+    target: &'a [usize],
+    /// The return type of each function declared at the top level.
+    functions: HashMap<String, DataType>,
+    /// The value and underlying type of each enum constant declared at the top level.
+    constants: HashMap<String, (i64, DataType)>,
+    scopes: Vec<HashMap<String, DataType>>,
+    path: Vec<usize>,
+}
+
+impl TypeResolver<'_> {
+    /// Visits a node, returning the type of the target once it is reached, or `None` if it is not under the node.
+    fn visit(&mut self, node: &ASTNode) -> Option<Option<DataType>> {
+        let node_type = node.get_node_type();
+        if matches!(node_type, NodeType::Variable | NodeType::Parameter) {
+            self.declare(node);
+        }
+        if self.path == self.target {
+            return Some(self.type_of(node));
+        }
+        let opens_scope = matches!(
+            node_type,
+            NodeType::FunctionDeclaration | NodeType::FunctionPrototype | NodeType::BlockExpression
+        );
+        // A node before the target can still declare variables in scope of it, unless its declarations are local
+        if !self.target.starts_with(&self.path) && opens_scope {
+            return None;
+        }
+        if opens_scope {
+            self.scopes.push(HashMap::new());
+        }
+        let mut found = None;
+        for (index, child) in node.get_children().iter().enumerate() {
+            self.path.push(index);
+            found = self.visit(child);
+            self.path.pop();
+            if found.is_some() {
+                break;
+            }
+        }
+        if opens_scope {
+            self.scopes.pop();
+        }
+        found
+    }
+
+    /// Records the type of a `Variable` or `Parameter` node whose children are its identifier and type.
+    fn declare(&mut self, node: &ASTNode) {
+        let children = node.get_children();
+        if let (Some(NodeType::Identifier(name)), Some(NodeType::Type(data_type))) = (
+            children.first().map(|c| c.get_node_type()),
+            children.get(1).map(|c| c.get_node_type()),
+        ) {
+            if let Some(scope) = self.scopes.last_mut() {
+                scope.insert(name, data_type);
+            }
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<DataType> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name).copied())
+    }
+
+    /// Infers the type of an expression, or `None` if it cannot be determined.
+    fn type_of(&self, node: &ASTNode) -> Option<DataType> {
+        let children = node.get_children();
+        match node.get_node_type() {
+            NodeType::Literal(value) => literal_data_type(&value),
+            NodeType::Identifier(name) => self.lookup(&name)
+                .or_else(|| self.functions.contains_key(&name).then_some(DataType::Function))
+                .or_else(|| self.constants.get(&name).map(|(_, underlying)| *underlying)),
+            NodeType::Variable | NodeType::Parameter => match children.get(1).map(|c| c.get_node_type()) {
+                Some(NodeType::Type(data_type)) => Some(data_type),
+                _ => None,
+            },
+            NodeType::FunctionCall => match children.first().map(|c| c.get_node_type()) {
+                Some(NodeType::Identifier(name)) if self.lookup(&name).is_none() => self.functions.get(&name).copied(),
+                _ => None,
+            },
+            NodeType::BinaryExpression => match children.get(1).map(|c| c.get_node_type()) {
+                Some(NodeType::Operator(op)) if ["==", "!=", "<", ">", "<=", ">=", "&&", "||"].contains(&op.as_str()) => {
+                    Some(DataType::Boolean)
+                }
+                _ => arithmetic_type(self.type_of(children.first()?)?, self.type_of(children.get(2)?)?),
+            },
+            NodeType::UnaryExpression => match children.first().map(|c| c.get_node_type()) {
+                Some(NodeType::Operator(op)) if op == "!" => Some(DataType::Boolean),
+                // Pointers have no data type
+                Some(NodeType::Operator(op)) if op == "&" || op == "*" => None,
+                _ => self.type_of(children.get(1)?),
+            },
+            NodeType::PostfixExpression => self.type_of(children.first()?),
+            NodeType::TernaryExpression => {
+                // The branches are converted to a common type, as the operands of a binary operator are
+                let (then_type, else_type) = (self.type_of(children.get(1)?)?, self.type_of(children.get(2)?)?);
+                arithmetic_type(then_type, else_type).or_else(|| (then_type == else_type).then_some(then_type))
+            }
+            NodeType::Assignment | NodeType::CompoundAssignment | NodeType::AssignedValue | NodeType::Initialization => {
+                self.type_of(children.first()?)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Returns the type of a literal as stored by the parser.
+fn literal_data_type(value: &str) -> Option<DataType> {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    if value.starts_with('"') {
+        Some(DataType::String)
+    } else if value.starts_with('\'') {
+        Some(DataType::Char)
+    } else if value == "true" || value == "false" {
+        Some(DataType::Boolean)
+    } else if digits.starts_with("0x") || digits.starts_with("0X") {
+        Some(DataType::Integer)
+    } else {
+        match literal_type(value) {
+            "float" => Some(DataType::Float),
+            "double" => Some(DataType::Double),
+            _ if digits.parse::<i32>().is_ok() => Some(DataType::Integer),
+            _ if digits.parse::<i64>().is_ok() => Some(DataType::Long),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the type of an arithmetic expression on operands of the given types, the wider of the two, or `None` if
+/// either is not a number.
+fn arithmetic_type(left: DataType, right: DataType) -> Option<DataType> {
+    let rank = |data_type: DataType| match data_type {
+        DataType::Boolean | DataType::Char | DataType::SignedChar | DataType::UnsignedChar | DataType::Integer
+        | DataType::Sign | DataType::Enum => Some(0),
+        DataType::Unsign => Some(1),
+        DataType::Long => Some(2),
+        DataType::UnsignedLong => Some(3),
+        DataType::LongLong => Some(4),
+        DataType::UnsignedLongLong => Some(5),
+        DataType::Float => Some(6),
+        DataType::Double => Some(7),
+        _ => None,
+    };
+    // Types narrower than `int` are promoted to it
+    let widest = if rank(left)? >= rank(right)? { left } else { right };
+    Some(if rank(widest)? == 0 { DataType::Integer } else { widest })
+}
+
+/// Checks whether storing a value of type `from` in a target of type `to` implicitly converts it to a narrower type,
+/// which may lose part of its value: a floating-point value converted to an integer or to `float` from `double`, or
+/// an integer converted to an integer type that holds fewer values. A literal is a constant, so an integer literal
+/// only narrows when its own value does not fit, and a floating-point literal only when it is converted to an
+/// integer. Converting to `bool` keeps whether the value is zero, so it never narrows.
+///
+/// # Parameters
+///
+/// * `value` - The value stored.
+/// * `from` - The type of the value.
+/// * `to` - The type of the target.
+pub fn narrows(value: &ASTNode, from: DataType, to: DataType) -> bool {
+    let span = |data_type: DataType| integer_range(data_type).map(|(min, max)| max - min);
+    let literal = match value.get_node_type() {
+        NodeType::Literal(literal) => Some(literal),
+        _ => None,
+    };
+    match (from, to) {
+        (_, DataType::Boolean) => false,
+        (DataType::Double, DataType::Float) => literal.is_none(),
+        (DataType::Float | DataType::Double, to) => integer_range(to).is_some(),
+        (from, to) => match (literal, integer_range(to)) {
+            (Some(literal), Some((min, max))) => case_label_key(&literal)
+                .parse::<i128>()
+                .is_ok_and(|value| value < min || value > max),
+            _ => matches!((span(from), span(to)), (Some(from), Some(to)) if to < from),
+        },
+    }
+}
+
+/// Infers the type of the expression at a path, such as a variable, a literal, a call, or an operation on them, as
+/// an editor shows it on hover. A variable has its declared type wherever it is named, a function name has the
+/// `Function` type, and a call has the return type of the function it calls.
+///
+/// # Parameters
+///
+/// * `ast` - The program.
+/// * `path` - The path of the expression, as `rewrite::find` gives it.
+///
+/// # Returns
+///
+/// The type of the expression, or `None` if there is no expression at the path or its type cannot be determined,
+/// such as for a pointer or a struct member.
+pub fn type_at_path(ast: &AST, path: &[usize]) -> Option<DataType> {
+    let root = ast.get_root();
+    let mut functions = HashMap::new();
+    let declares_function =
+        |node: &&ASTNode| matches!(node.get_node_type(), NodeType::FunctionDeclaration | NodeType::FunctionPrototype);
+    for node in root.get_children().iter().filter(declares_function) {
+        let children = node.get_children();
+        let return_type = children.iter().find_map(|c| match c.get_node_type() {
+            NodeType::Type(data_type) => Some(data_type),
+            _ => None,
+        });
+        if let (Some(NodeType::Identifier(name)), Some(return_type)) = (children.first().map(|c| c.get_node_type()), return_type) {
+            functions.insert(name, return_type);
+        }
+    }
+
+    let mut resolver = TypeResolver {
+        target: path,
+        functions,
+        constants: enum_constants(ast),
+        scopes: vec![HashMap::new()],
+        path: Vec::new(),
+    };
+    resolver.visit(&root).flatten()
+}
@@ -0,0 +1,50 @@
+//! This file contains tests for encoding analyzed programs, making sure the AST and symbol table stack decode to
+//! themselves.
+
+use common::ast::{build, data_type::DataType};
+use sts::{
+    cache::{decode, encode},
+    core::{SymbolInfo, SymbolTable, SymbolTableStack, SymbolValue},
+};
+
+/// Builds a symbol table stack with a symbol of every kind of value.
+fn sym_table_stack() -> SymbolTableStack {
+    let mut globals = SymbolTable::new();
+    globals.add("main".to_string(), SymbolInfo::new(DataType::Integer, SymbolValue::FunctionValue {
+        parameters: vec![("argc".to_string(), DataType::Integer)],
+    }));
+    globals.add("Color".to_string(), SymbolInfo::new(DataType::Enum, SymbolValue::EnumValue {
+        variants: vec!["Red".to_string(), "Green".to_string()],
+    }));
+    globals.add("Point".to_string(), SymbolInfo::new(DataType::Struct, SymbolValue::StructValue {
+        fields: vec![("x".to_string(), DataType::Double), ("y".to_string(), DataType::Double)],
+    }));
+    let mut locals = SymbolTable::new();
+    locals.add("argc".to_string(), SymbolInfo::new(DataType::Integer, SymbolValue::NoAssociatedValue));
+
+    let mut stack = SymbolTableStack::new();
+    stack.push(globals);
+    stack.push(locals);
+    stack.push(SymbolTable::new());
+    stack
+}
+
+/// This test checks that an analyzed program decodes to itself, and that its encoding does not depend on the order
+/// its symbols were added in.
+#[test]
+fn test_round_trip() {
+    let ast = build::program(vec![
+        build::function("main", DataType::Integer)
+            .param("argc", DataType::Integer)
+            .body(vec![build::ret(Some(build::identifier("argc")))])
+            .build()
+            .expect("main should be well-formed"),
+    ]).expect("The program should be well-formed");
+    let stack = sym_table_stack();
+
+    let bytes = encode(&ast, &stack);
+    assert_eq!(decode(&bytes), Some((ast.clone(), stack.clone())));
+    assert_eq!(encode(&ast, &sym_table_stack()), bytes);
+    assert_eq!(decode(&bytes[..bytes.len() - 1]), None);
+    assert_eq!(decode(&common::ast::encode::encode(&ast)), None);
+}
@@ -0,0 +1,103 @@
+use common::ast::{
+    core::{ASTNode, AST},
+    data_type::DataType,
+    node_type::NodeType,
+};
+use sts::desugar::desugar_string_switches;
+
+fn identifier(name: &str) -> ASTNode {
+    ASTNode::new(NodeType::Identifier(name.to_string()))
+}
+
+fn literal(value: &str) -> ASTNode {
+    ASTNode::new(NodeType::Literal(value.to_string()))
+}
+
+fn assignment(name: &str, value: &str) -> ASTNode {
+    let mut assignment = ASTNode::new(NodeType::Assignment);
+    assignment.add_child(identifier(name));
+    assignment.add_child(literal(value));
+    assignment
+}
+
+/// Builds a block holding the given statements.
+fn block(statements: Vec<ASTNode>) -> ASTNode {
+    let mut block = ASTNode::new(NodeType::BlockExpression);
+    block.add_children(statements);
+    block
+}
+
+/// Builds the arm `case label: x = value; break;`, or `default: x = value; break;` without a label.
+fn arm(label: Option<&str>, value: &str) -> ASTNode {
+    let mut arm = ASTNode::new(if label.is_some() { NodeType::Case } else { NodeType::Default });
+    arm.add_children(label.map(literal).into_iter().collect());
+    arm.add_child(block(vec![assignment("x", value), ASTNode::new(NodeType::Break)]));
+    arm
+}
+
+fn switch(scrutinee: &str, arms: Vec<ASTNode>) -> ASTNode {
+    let mut switch = ASTNode::new(NodeType::SwitchStatement);
+    switch.add_child(identifier(scrutinee));
+    switch.add_child(block(arms));
+    switch
+}
+
+/// Builds `if (s == label) { switch.case0 = index; }`, or the same `else if` branch when `node_type` is `ElseIf`.
+fn comparison(label: &str, index: &str, node_type: NodeType) -> ASTNode {
+    let mut equality = ASTNode::new(NodeType::BinaryExpression);
+    equality.add_child(identifier("s"));
+    equality.add_child(ASTNode::new(NodeType::Operator("==".to_string())));
+    equality.add_child(literal(label));
+    let mut condition = ASTNode::new(NodeType::Condition);
+    condition.add_child(equality);
+
+    let mut branch = ASTNode::new(node_type);
+    branch.add_child(condition);
+    branch.add_child(block(vec![assignment("switch.case0", index)]));
+    branch
+}
+
+/// Builds a function `void f() { statements }`.
+fn program(statements: Vec<ASTNode>) -> AST {
+    let mut function = ASTNode::new(NodeType::FunctionDeclaration);
+    function.add_child(identifier("f"));
+    function.add_child(ASTNode::new(NodeType::Type(DataType::Void)));
+    function.add_child(block(statements));
+
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(function);
+    AST::new(root)
+}
+
+// switch (s) { case "go": x = 1; break; case "stop": x = 2; break; default: x = 3; break; } selects its case with
+// strcmp comparisons, then switches on the selected case's position
+#[test]
+fn test_desugars_string_switch() {
+    let ast = program(vec![switch("s", vec![
+        arm(Some("\"go\""), "1"),
+        arm(Some("\"stop\""), "2"),
+        arm(None, "3"),
+    ])]);
+
+    let mut selector = ASTNode::new(NodeType::Variable);
+    selector.add_child(identifier("switch.case0"));
+    selector.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    let mut assigned_value = ASTNode::new(NodeType::AssignedValue);
+    assigned_value.add_child(literal("-1"));
+    let mut declaration = ASTNode::new(NodeType::Initialization);
+    declaration.add_child(selector);
+    declaration.add_child(assigned_value);
+
+    let mut chain = comparison("\"go\"", "0", NodeType::IfStatement);
+    chain.add_child(comparison("\"stop\"", "1", NodeType::ElseIf));
+    let selector_switch = switch("switch.case0", vec![arm(Some("0"), "1"), arm(Some("1"), "2"), arm(None, "3")]);
+
+    assert_eq!(desugar_string_switches(&ast), program(vec![declaration, chain, selector_switch]));
+}
+
+// switch (n) { case 1: ... case 2: ... } is already supported and is left unchanged
+#[test]
+fn test_keeps_integer_switch() {
+    let ast = program(vec![switch("n", vec![arm(Some("1"), "1"), arm(Some("2"), "2")])]);
+    assert_eq!(desugar_string_switches(&ast), ast);
+}
@@ -0,0 +1,123 @@
+use common::ast::{
+    core::{ASTNode, AST},
+    data_type::DataType,
+    node_type::NodeType,
+};
+use sts::dump::{collect_global_symbols, symbols_to_json, GlobalSymbol, Linkage, SymbolKind};
+
+fn typed(node_type: NodeType, name: &str, data_type: DataType) -> ASTNode {
+    let mut node = ASTNode::new(node_type);
+    node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+    node.add_child(ASTNode::new(NodeType::Type(data_type)));
+    node
+}
+
+fn function(name: &str, parameters: Vec<ASTNode>, return_type: DataType) -> ASTNode {
+    let mut node = ASTNode::new(NodeType::FunctionDeclaration);
+    node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+    node.add_children(parameters);
+    node.add_child(ASTNode::new(NodeType::Type(return_type)));
+    node.add_child(ASTNode::new(NodeType::BlockExpression));
+    node
+}
+
+// int add(int a, char b) { }  long total;  struct Counter [ void reset() { } ]
+#[test]
+fn test_collect_global_symbols() {
+    let mut global = ASTNode::new(NodeType::Initialization);
+    global.add_child(typed(NodeType::Variable, "total", DataType::Long));
+
+    let mut counter = ASTNode::new(NodeType::StructDeclaration);
+    counter.add_child(ASTNode::new(NodeType::Identifier("Counter".to_string())));
+    counter.add_child(function("reset", vec![], DataType::Void));
+
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(function("add", vec![
+        typed(NodeType::Parameter, "a", DataType::Integer),
+        typed(NodeType::Parameter, "b", DataType::Char),
+    ], DataType::Integer));
+    root.add_child(global);
+    root.add_child(counter);
+
+    let symbols = collect_global_symbols(&AST::new(root));
+
+    assert_eq!(symbols, vec![
+        GlobalSymbol {
+            name: "add".to_string(),
+            kind: SymbolKind::Function,
+            data_type: "int (int, char)".to_string(),
+            linkage: Linkage::External,
+            mangled_name: "add".to_string(),
+        },
+        GlobalSymbol {
+            name: "total".to_string(),
+            kind: SymbolKind::Global,
+            data_type: "long".to_string(),
+            linkage: Linkage::External,
+            mangled_name: "total".to_string(),
+        },
+        GlobalSymbol {
+            name: "Counter.reset".to_string(),
+            kind: SymbolKind::Function,
+            data_type: "void (void)".to_string(),
+            linkage: Linkage::External,
+            mangled_name: "Counter__reset".to_string(),
+        },
+    ]);
+    assert_eq!(symbols[0].to_string(), "function\tadd\tint (int, char)\texternal\tadd");
+}
+
+// int log(int level, ...) { }
+#[test]
+fn test_variadic_function_symbol() {
+    let mut log = function("log", vec![typed(NodeType::Parameter, "level", DataType::Integer)], DataType::Integer);
+    let mut children = log.get_children();
+    children.insert(2, ASTNode::new(NodeType::Variadic));
+    log.set_children(children);
+
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(log);
+
+    let symbols = collect_global_symbols(&AST::new(root));
+    assert_eq!(symbols[0].data_type, "int (int, ...)");
+}
+
+// int (*op)(int, int);
+#[test]
+fn test_symbols_to_json() {
+    let mut function_type = ASTNode::new(NodeType::Type(DataType::Function));
+    function_type.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    function_type.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    function_type.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    let mut variable = ASTNode::new(NodeType::Variable);
+    variable.add_child(ASTNode::new(NodeType::Identifier("op".to_string())));
+    variable.add_child(function_type);
+    let mut global = ASTNode::new(NodeType::Initialization);
+    global.add_child(variable);
+
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(global);
+
+    let json = symbols_to_json(&collect_global_symbols(&AST::new(root)));
+
+    assert_eq!(json, "[\n  {\"name\": \"op\", \"kind\": \"global\", \"type\": \"int (*)(int, int)\", \"linkage\": \"external\", \"mangled_name\": \"op\"}\n]");
+    assert_eq!(symbols_to_json(&[]), "[]");
+}
+
+// const int limit = 5;
+#[test]
+fn test_const_global_symbol() {
+    let mut type_node = ASTNode::new(NodeType::Type(DataType::Integer));
+    type_node.add_child(ASTNode::new(NodeType::Qualifier("const".to_string())));
+    let mut variable = ASTNode::new(NodeType::Variable);
+    variable.add_child(ASTNode::new(NodeType::Identifier("limit".to_string())));
+    variable.add_child(type_node);
+    let mut global = ASTNode::new(NodeType::Initialization);
+    global.add_child(variable);
+
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(global);
+
+    let symbols = collect_global_symbols(&AST::new(root));
+    assert_eq!(symbols.iter().map(|symbol| symbol.data_type.as_str()).collect::<Vec<_>>(), vec!["const int"]);
+}
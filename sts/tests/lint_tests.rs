@@ -0,0 +1,386 @@
+use common::{
+    ast::{
+        core::{ASTNode, AST},
+        data_type::DataType,
+        node_type::NodeType,
+    },
+    warning::WarningType,
+};
+use sts::lint::{
+    check_assignments_in_conditions, check_dead_stores, check_format_strings, check_implicit_narrowing,
+    check_operator_precedence, check_string_comparisons, check_unused_results,
+};
+
+/// Builds `name(args...)` as a top-level call, preceded by the declaration `int x;`.
+fn program_with_call(name: &str, args: Vec<ASTNode>) -> AST {
+    let mut variable = ASTNode::new(NodeType::Variable);
+    variable.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    variable.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    let mut initialization = ASTNode::new(NodeType::Initialization);
+    initialization.add_child(variable);
+
+    let mut call = ASTNode::new(NodeType::FunctionCall);
+    call.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+    call.add_children(args);
+
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(initialization);
+    root.add_child(call);
+    AST::new(root)
+}
+
+fn literal(value: &str) -> ASTNode {
+    ASTNode::new(NodeType::Literal(value.to_string()))
+}
+
+// printf("%d items: %s\n", x, "apples") matches its format string
+#[test]
+fn test_format_string_matches() {
+    let ast = program_with_call("printf", vec![
+        literal("\"%d items: %s\\n\""),
+        ASTNode::new(NodeType::Identifier("x".to_string())),
+        literal("\"apples\""),
+    ]);
+
+    assert_eq!(check_format_strings(&ast), vec![]);
+}
+
+// printf("%s %d", x) passes an int for `%s` and is missing the argument for `%d`
+#[test]
+fn test_format_string_mismatches() {
+    let ast = program_with_call("printf", vec![
+        literal("\"%s %d\""),
+        ASTNode::new(NodeType::Identifier("x".to_string())),
+    ]);
+
+    assert_eq!(check_format_strings(&ast), vec![
+        WarningType::FormatArgumentCount {
+            function: "printf".to_string(),
+            expected: 2,
+            found: 1,
+        },
+        WarningType::FormatArgumentType {
+            function: "printf".to_string(),
+            argument_index: 2,
+            argument: "x".to_string(),
+            specifier: "%s".to_string(),
+            expected: "char *".to_string(),
+            found: "int".to_string(),
+        },
+    ]);
+}
+
+// snprintf(buf, 8, "%*.2f %%", x, 1) takes its format third, and `*` consumes an int before the double
+#[test]
+fn test_format_string_star_width_and_position() {
+    let ast = program_with_call("snprintf", vec![
+        ASTNode::new(NodeType::Identifier("buf".to_string())),
+        literal("8"),
+        literal("\"%*.2f %%\""),
+        ASTNode::new(NodeType::Identifier("x".to_string())),
+        literal("1"),
+    ]);
+
+    let warnings = check_format_strings(&ast);
+    assert_eq!(warnings.len(), 1);
+    assert!(matches!(&warnings[0], WarningType::FormatArgumentType { argument_index: 5, specifier, .. } if specifier == "%*.2f"));
+    assert_eq!(
+        warnings[0].to_string(),
+        "warning[W0002]: argument 5 (`1`) of `snprintf` has type `int`, but `%*.2f` expects `double`"
+    );
+}
+
+// printf("%q") uses an unknown conversion, and calls to other functions are not checked
+#[test]
+fn test_format_string_unknown_specifier() {
+    let ast = program_with_call("printf", vec![literal("\"%q\"")]);
+    assert_eq!(check_format_strings(&ast), vec![
+        WarningType::InvalidFormatSpecifier {
+            function: "printf".to_string(),
+            specifier: "%q".to_string(),
+        },
+    ]);
+
+    let ast = program_with_call("puts", vec![literal("\"%q\"")]);
+    assert_eq!(check_format_strings(&ast), vec![]);
+}
+
+/// Builds a block holding the expression statement `expression;`.
+fn program_with_statement(expression: ASTNode) -> AST {
+    let mut statement = ASTNode::new(NodeType::ExpressionStatement);
+    statement.add_child(expression);
+    let mut block = ASTNode::new(NodeType::BlockExpression);
+    block.add_child(statement);
+
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(block);
+    AST::new(root)
+}
+
+// x + 1; computes a value that is never used
+#[test]
+fn test_unused_result() {
+    let mut sum = ASTNode::new(NodeType::BinaryExpression);
+    sum.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    sum.add_child(ASTNode::new(NodeType::Operator("+".to_string())));
+    sum.add_child(literal("1"));
+
+    assert_eq!(check_unused_results(&program_with_statement(sum)), vec![WarningType::UnusedResult {
+        expression: "x + 1".to_string(),
+    }]);
+}
+
+// foo(x); is made for its effects, so its discarded result is not reported
+#[test]
+fn test_call_statement_is_not_unused() {
+    let mut call = ASTNode::new(NodeType::FunctionCall);
+    call.add_child(ASTNode::new(NodeType::Identifier("foo".to_string())));
+    call.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+
+    assert!(check_unused_results(&program_with_statement(call)).is_empty());
+}
+
+/// Builds the declarations `char *s;` and `int x;` followed by the condition `left op "main"`.
+fn program_comparing(left: &str, op: &str) -> AST {
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    for (name, data_type) in [("s", DataType::String), ("x", DataType::Integer)] {
+        let mut variable = ASTNode::new(NodeType::Variable);
+        variable.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+        variable.add_child(ASTNode::new(NodeType::Type(data_type)));
+        let mut initialization = ASTNode::new(NodeType::Initialization);
+        initialization.add_child(variable);
+        root.add_child(initialization);
+    }
+
+    let mut comparison = ASTNode::new(NodeType::BinaryExpression);
+    comparison.add_child(ASTNode::new(NodeType::Identifier(left.to_string())));
+    comparison.add_child(ASTNode::new(NodeType::Operator(op.to_string())));
+    comparison.add_child(literal("\"main\""));
+    let mut condition = ASTNode::new(NodeType::Condition);
+    condition.add_child(comparison);
+    root.add_child(condition);
+    AST::new(root)
+}
+
+// s == "main" and s != "main" compare the contents of the strings
+#[test]
+fn test_string_comparison() {
+    assert_eq!(check_string_comparisons(&program_comparing("s", "==")), vec![WarningType::StringComparison {
+        expression: "s == \"main\"".to_string(),
+    }]);
+    assert_eq!(check_string_comparisons(&program_comparing("s", "!=")).len(), 1);
+}
+
+// x == "main" does not compare two strings, and s < "main" is not an equality comparison
+#[test]
+fn test_non_string_comparison() {
+    assert!(check_string_comparisons(&program_comparing("x", "==")).is_empty());
+    assert!(check_string_comparisons(&program_comparing("s", "<")).is_empty());
+}
+
+/// Builds `int f() { int x = 1; x = second; return x; }`.
+fn program_storing_twice(second: ASTNode) -> AST {
+    let mut variable = ASTNode::new(NodeType::Variable);
+    variable.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    variable.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    let mut assigned_value = ASTNode::new(NodeType::AssignedValue);
+    assigned_value.add_child(literal("1"));
+    let mut initialization = ASTNode::new(NodeType::Initialization);
+    initialization.add_child(variable);
+    initialization.add_child(assigned_value);
+
+    let mut assignment = ASTNode::new(NodeType::Assignment);
+    assignment.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    assignment.add_child(second);
+    let mut return_node = ASTNode::new(NodeType::Return);
+    return_node.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+
+    let mut body = ASTNode::new(NodeType::BlockExpression);
+    body.add_children(vec![initialization, assignment, return_node]);
+    let mut function = ASTNode::new(NodeType::FunctionDeclaration);
+    function.add_child(ASTNode::new(NodeType::Identifier("f".to_string())));
+    function.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    function.add_child(body);
+
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(function);
+    AST::new(root)
+}
+
+// x = 1 is overwritten by x = 2 before it is read
+#[test]
+fn test_dead_store() {
+    assert_eq!(check_dead_stores(&program_storing_twice(literal("2"))), vec![WarningType::DeadStore {
+        variable: "x".to_string(),
+    }]);
+}
+
+// x = x + 2 reads the value stored by x = 1
+#[test]
+fn test_store_read_later() {
+    let mut sum = ASTNode::new(NodeType::BinaryExpression);
+    sum.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    sum.add_child(ASTNode::new(NodeType::Operator("+".to_string())));
+    sum.add_child(literal("2"));
+    assert!(check_dead_stores(&program_storing_twice(sum)).is_empty());
+}
+
+/// Builds the binary expression `left op right`.
+fn binary(left: ASTNode, op: &str, right: ASTNode) -> ASTNode {
+    let mut expression = ASTNode::new(NodeType::BinaryExpression);
+    expression.add_child(left);
+    expression.add_child(ASTNode::new(NodeType::Operator(op.to_string())));
+    expression.add_child(right);
+    expression
+}
+
+/// Builds a program whose only statement is the expression `expression;`.
+fn program_with_expression(expression: ASTNode) -> AST {
+    let mut statement = ASTNode::new(NodeType::ExpressionStatement);
+    statement.add_child(expression);
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(statement);
+    AST::new(root)
+}
+
+// a & b == c is parsed as a & (b == c), and a == b | c == d || a && b groups both comparisons and the &&
+#[test]
+fn test_operator_precedence() {
+    let comparison = binary(literal("b"), "==", literal("c"));
+    let ast = program_with_expression(binary(literal("a"), "&", comparison));
+    assert_eq!(check_operator_precedence(&ast), vec![WarningType::OperatorPrecedence {
+        expression: "a & b == c".to_string(),
+        parsed: "a & (b == c)".to_string(),
+    }]);
+
+    let bitwise = binary(binary(literal("a"), "==", literal("b")), "|", binary(literal("c"), "==", literal("d")));
+    let ast = program_with_expression(binary(bitwise, "||", binary(literal("a"), "&&", literal("b"))));
+    let parsed: Vec<_> = check_operator_precedence(&ast).into_iter().map(|warning| match warning {
+        WarningType::OperatorPrecedence { parsed, .. } => parsed,
+        other => panic!("unexpected warning {}", other),
+    }).collect();
+    assert_eq!(parsed, vec!["a == b | c == d || (a && b)", "(a == b) | (c == d)"]);
+}
+
+// (a & b) == c and a & (b == c) are grouped as written, and a + b == c is not misread
+#[test]
+fn test_parenthesized_operands() {
+    let mut bitwise = binary(literal("a"), "&", literal("b"));
+    bitwise.set_parenthesized(true);
+    assert!(check_operator_precedence(&program_with_expression(binary(bitwise, "==", literal("c")))).is_empty());
+
+    let mut comparison = binary(literal("b"), "==", literal("c"));
+    comparison.set_parenthesized(true);
+    assert!(check_operator_precedence(&program_with_expression(binary(literal("a"), "&", comparison))).is_empty());
+
+    let sum = binary(binary(literal("a"), "+", literal("b")), "==", literal("c"));
+    assert!(check_operator_precedence(&program_with_expression(sum)).is_empty());
+}
+
+/// Builds `name = value`.
+fn assignment(name: &str, value: ASTNode) -> ASTNode {
+    let mut assignment = ASTNode::new(NodeType::Assignment);
+    assignment.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+    assignment.add_child(value);
+    assignment
+}
+
+/// Builds a program whose only statement is `if (condition) {}`.
+fn program_testing(condition: ASTNode) -> AST {
+    let mut condition_node = ASTNode::new(NodeType::Condition);
+    condition_node.add_child(condition);
+    let mut if_statement = ASTNode::new(NodeType::IfStatement);
+    if_statement.add_child(condition_node);
+    if_statement.add_child(ASTNode::new(NodeType::BlockExpression));
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(if_statement);
+    AST::new(root)
+}
+
+// if (x = 0) and if (a && y = b || c) each test an assignment, which was likely meant to be a comparison
+#[test]
+fn test_assignment_in_condition() {
+    let ast = program_testing(assignment("x", literal("0")));
+    let warnings = check_assignments_in_conditions(&ast);
+    assert_eq!(warnings, vec![WarningType::AssignmentInCondition {
+        expression: "x = 0".to_string(),
+        comparison: "x == 0".to_string(),
+    }]);
+    assert_eq!(warnings[0].fixes(), vec!["x == 0", "(x = 0)"]);
+    assert_eq!(
+        warnings[0].to_string(),
+        "warning[W0008]: condition `x = 0` is an assignment; write `x == 0` to compare, or `(x = 0)` if the assignment is intended",
+    );
+
+    let ast = program_testing(binary(literal("a"), "&&", assignment("y", binary(literal("b"), "||", literal("c")))));
+    assert_eq!(check_assignments_in_conditions(&ast), vec![WarningType::AssignmentInCondition {
+        expression: "y = b || c".to_string(),
+        comparison: "y == (b || c)".to_string(),
+    }]);
+}
+
+// if ((x = next())) and if ((x = next()) != 0) assign on purpose
+#[test]
+fn test_intended_assignment_in_condition() {
+    let mut parenthesized = assignment("x", literal("next"));
+    parenthesized.set_parenthesized(true);
+    assert!(check_assignments_in_conditions(&program_testing(parenthesized.clone())).is_empty());
+    assert!(check_assignments_in_conditions(&program_testing(binary(parenthesized, "!=", literal("0")))).is_empty());
+}
+
+/// Builds the declaration `data_type name = value;`, or `data_type name;` without a value.
+fn declaration(name: &str, data_type: DataType, value: Option<ASTNode>) -> ASTNode {
+    let mut variable = ASTNode::new(NodeType::Variable);
+    variable.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+    variable.add_child(ASTNode::new(NodeType::Type(data_type)));
+    let mut initialization = ASTNode::new(NodeType::Initialization);
+    initialization.add_child(variable);
+    if let Some(value) = value {
+        let mut assigned_value = ASTNode::new(NodeType::AssignedValue);
+        assigned_value.add_child(value);
+        initialization.add_child(assigned_value);
+    }
+    initialization
+}
+
+fn narrowing(variable: &str, expression: &str, from: &str, to: &str) -> WarningType {
+    WarningType::ImplicitNarrowing {
+        variable: variable.to_string(),
+        expression: expression.to_string(),
+        from: from.to_string(),
+        to: to.to_string(),
+    }
+}
+
+// int total; double ratio; then `char c = total;`, `float f = ratio;`, `char big = 300;`, and `total = ratio;` each
+// narrow their value, while `char letter = 65;`, `long wide = total;`, `bool flag = total;`, and `f = total;` do not
+#[test]
+fn test_implicit_narrowing() {
+    let identifier = |name: &str| ASTNode::new(NodeType::Identifier(name.to_string()));
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_children(vec![
+        declaration("total", DataType::Integer, None),
+        declaration("ratio", DataType::Double, None),
+        declaration("c", DataType::Char, Some(identifier("total"))),
+        declaration("f", DataType::Float, Some(identifier("ratio"))),
+        declaration("big", DataType::Char, Some(literal("300"))),
+        declaration("letter", DataType::Char, Some(literal("65"))),
+        declaration("wide", DataType::Long, Some(identifier("total"))),
+        declaration("flag", DataType::Boolean, Some(identifier("total"))),
+        assignment("f", identifier("total")),
+        assignment("total", identifier("ratio")),
+    ]);
+    let warnings = check_implicit_narrowing(&AST::new(root));
+
+    assert_eq!(warnings, vec![
+        narrowing("c", "total", "int", "char"),
+        narrowing("f", "ratio", "double", "float"),
+        narrowing("big", "300", "int", "char"),
+        narrowing("total", "ratio", "double", "int"),
+    ]);
+    assert_eq!(
+        warnings[0].to_string(),
+        "warning[W0010]: `total` of type `int` is implicitly converted to the narrower type `char` of `c`, which may \
+         lose part of its value; note: write `(char) total` to convert it explicitly",
+    );
+}
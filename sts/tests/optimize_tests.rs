@@ -0,0 +1,407 @@
+use common::{
+    ast::{
+        core::{ASTNode, AST},
+        data_type::DataType,
+        node_type::NodeType,
+    },
+    config::{OptLevel, OverflowMode},
+    location::Span,
+};
+use sts::optimize::{
+    eliminate_dead_stores, hoist_loop_invariants, hoist_loop_invariants_with, propagate_constant_arguments,
+    reduce_loop_strength, PassManager, LOOP_INVARIANT_MOTION,
+};
+
+fn identifier(name: &str) -> ASTNode {
+    ASTNode::new(NodeType::Identifier(name.to_string()))
+}
+
+fn literal(value: &str) -> ASTNode {
+    ASTNode::new(NodeType::Literal(value.to_string()))
+}
+
+fn binary(left: ASTNode, op: &str, right: ASTNode) -> ASTNode {
+    let mut expression = ASTNode::new(NodeType::BinaryExpression);
+    expression.add_child(left);
+    expression.add_child(ASTNode::new(NodeType::Operator(op.to_string())));
+    expression.add_child(right);
+    expression
+}
+
+/// Builds the declaration `int name = value;`.
+fn declaration(name: &str, value: ASTNode) -> ASTNode {
+    let mut variable = ASTNode::new(NodeType::Variable);
+    variable.add_child(identifier(name));
+    variable.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    let mut assigned_value = ASTNode::new(NodeType::AssignedValue);
+    assigned_value.add_child(value);
+    let mut initialization = ASTNode::new(NodeType::Initialization);
+    initialization.add_child(variable);
+    initialization.add_child(assigned_value);
+    initialization
+}
+
+fn assignment(name: &str, value: ASTNode) -> ASTNode {
+    let mut assignment = ASTNode::new(NodeType::Assignment);
+    assignment.add_child(identifier(name));
+    assignment.add_child(value);
+    assignment
+}
+
+/// Builds `int f() { int n = 3; int i = 0; while (i < 10) { body } }`.
+fn program_looping(body: Vec<ASTNode>) -> AST {
+    let mut condition = ASTNode::new(NodeType::Condition);
+    condition.add_child(binary(identifier("i"), "<", literal("10")));
+    let mut loop_body = ASTNode::new(NodeType::BlockExpression);
+    loop_body.add_children(body);
+    let mut while_loop = ASTNode::new(NodeType::WhileLoop);
+    while_loop.add_child(condition);
+    while_loop.add_child(loop_body);
+
+    let mut function_body = ASTNode::new(NodeType::BlockExpression);
+    function_body.add_child(declaration("n", literal("3")));
+    function_body.add_child(declaration("i", literal("0")));
+    function_body.add_child(while_loop);
+
+    let mut function = ASTNode::new(NodeType::FunctionDeclaration);
+    function.add_child(identifier("f"));
+    function.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    function.add_child(function_body);
+
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(function);
+    AST::new(root)
+}
+
+/// Returns the statements of the function body and of the loop body of a program built by `program_looping`.
+fn statements(ast: &AST) -> (Vec<ASTNode>, Vec<ASTNode>) {
+    let function_body = ast.get_root().get_children()[0].get_children()[2].get_children();
+    let loop_node = function_body.iter().find(|s| s.get_node_type() == NodeType::WhileLoop).unwrap().clone();
+    (function_body, loop_node.get_children()[1].get_children())
+}
+
+// int limit = n * 4; int twice = limit + limit; only read `n`, which the loop never writes
+#[test]
+fn test_hoists_invariant_declarations() {
+    let limit = declaration("limit", binary(identifier("n"), "*", literal("4")));
+    let twice = declaration("twice", binary(identifier("limit"), "+", identifier("limit")));
+    let step = assignment("i", binary(identifier("i"), "+", identifier("twice")));
+    let ast = hoist_loop_invariants(&program_looping(vec![limit.clone(), twice.clone(), step.clone()]));
+
+    let (function_body, loop_body) = statements(&ast);
+    assert_eq!(function_body[2], limit);
+    assert_eq!(function_body[3], twice);
+    assert_eq!(function_body[4].get_node_type(), NodeType::WhileLoop);
+    assert_eq!(loop_body, vec![step]);
+}
+
+// A hoisted declaration keeps its source range, and its value keeps its range and parentheses
+#[test]
+fn test_hoisting_keeps_spans_and_parentheses() {
+    let mut product = binary(identifier("n"), "*", literal("4"));
+    product.set_parenthesized(true);
+    product.set_span(Span::new(44, 7));
+    let mut limit = declaration("limit", product);
+    limit.set_span(Span::new(32, 20));
+    let ast = hoist_loop_invariants(&program_looping(vec![limit]));
+
+    let (function_body, _) = statements(&ast);
+    assert_eq!(function_body[2].get_span(), Some(Span::new(32, 20)));
+    let value = &function_body[2].get_children()[1].get_children()[0];
+    assert!(value.is_parenthesized());
+    assert_eq!(value.get_span(), Some(Span::new(44, 7)));
+}
+
+// int next = i + 1; reads `i`, which the loop writes, and int q = n / 2; could trap if hoisted
+#[test]
+fn test_keeps_variant_declarations() {
+    let next = declaration("next", binary(identifier("i"), "+", literal("1")));
+    let half = declaration("q", binary(identifier("n"), "/", literal("2")));
+    let step = assignment("i", identifier("next"));
+    let program = program_looping(vec![next, half, step]);
+
+    assert_eq!(hoist_loop_invariants(&program), program);
+}
+
+// int limit = n * 4; is not hoisted when the loop also calls a function, which could write `n` through a pointer
+// int limit = n * 4; can overflow, so it is left in the loop when overflow traps, with the pass manager too
+#[test]
+fn test_keeps_trapping_arithmetic_when_overflow_traps() {
+    let limit = declaration("limit", binary(identifier("n"), "*", literal("4")));
+    let less = declaration("less", binary(identifier("n"), "<", literal("4")));
+    let step = assignment("i", binary(identifier("i"), "+", identifier("limit")));
+    let program = program_looping(vec![limit.clone(), less.clone(), step.clone()]);
+
+    let (function_body, loop_body) = statements(&hoist_loop_invariants_with(&program, OverflowMode::Trap));
+    assert_eq!(function_body[2], less);
+    assert_eq!(loop_body, vec![limit.clone(), step.clone()]);
+
+    let mut manager = PassManager::new();
+    manager.add(LOOP_INVARIANT_MOTION);
+    manager.set_overflow(OverflowMode::Trap);
+    assert_eq!(statements(&manager.run(&program)).1, vec![limit, step]);
+}
+
+#[test]
+fn test_keeps_declarations_in_loops_with_calls() {
+    let limit = declaration("limit", binary(identifier("n"), "*", literal("4")));
+    let mut call = ASTNode::new(NodeType::FunctionCall);
+    call.add_child(identifier("update"));
+    let step = assignment("i", binary(identifier("i"), "+", identifier("limit")));
+    let program = program_looping(vec![limit, call, step]);
+
+    assert_eq!(hoist_loop_invariants(&program), program);
+}
+
+// In `int n = 3; int i = 0; while (i < 10) { int t = n * 4; i = i + 1; t = i; }`, `t = i` is never read, and the
+// value of `int t = n * 4` is overwritten before it is
+#[test]
+fn test_eliminates_dead_stores() {
+    let product = declaration("t", binary(identifier("n"), "*", literal("4")));
+    let step = assignment("i", binary(identifier("i"), "+", literal("1")));
+    let copy = assignment("t", identifier("i"));
+    let ast = eliminate_dead_stores(&program_looping(vec![product.clone(), step.clone(), copy]));
+
+    let (function_body, loop_body) = statements(&ast);
+    let mut declaration_only = ASTNode::new(NodeType::Initialization);
+    declaration_only.add_child(product.get_children()[0].clone());
+    assert_eq!(loop_body, vec![declaration_only, step]);
+    // `i` is read by the loop condition and `n` by the loop body, so their initializers stay
+    assert_eq!(function_body.len(), 3);
+    assert_eq!(function_body[0].get_children().len(), 2);
+    assert_eq!(function_body[1].get_children().len(), 2);
+}
+
+// `i = i + 1` at the end of the loop body is read by the condition on the next iteration
+#[test]
+fn test_keeps_stores_read_by_next_iteration() {
+    let step = assignment("i", binary(identifier("i"), "+", literal("1")));
+    let ast = eliminate_dead_stores(&program_looping(vec![step.clone()]));
+
+    let (function_body, loop_body) = statements(&ast);
+    assert_eq!(loop_body, vec![step]);
+    assert_eq!(function_body[1], declaration("i", literal("0")));
+    // `n` is never read at all
+    assert_eq!(function_body[0].get_children().len(), 1);
+}
+
+/// Builds `int scale(int x, int k) { return x * k + k; }` and calls `scale(a, k)` for each argument pair.
+fn program_calling_scale(arguments: &[(ASTNode, ASTNode)]) -> AST {
+    let mut function = ASTNode::new(NodeType::FunctionDeclaration);
+    function.add_child(identifier("scale"));
+    for name in ["x", "k"] {
+        let mut parameter = ASTNode::new(NodeType::Parameter);
+        parameter.add_child(identifier(name));
+        parameter.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+        function.add_child(parameter);
+    }
+    function.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    let mut return_node = ASTNode::new(NodeType::Return);
+    return_node.add_child(binary(binary(identifier("x"), "*", identifier("k")), "+", identifier("k")));
+    let mut body = ASTNode::new(NodeType::BlockExpression);
+    body.add_child(return_node);
+    function.add_child(body);
+
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(function);
+    for (x, k) in arguments {
+        let mut call = ASTNode::new(NodeType::FunctionCall);
+        call.add_child(identifier("scale"));
+        call.add_child(x.clone());
+        call.add_child(k.clone());
+        root.add_child(call);
+    }
+    AST::new(root)
+}
+
+/// Returns the value returned by the function of a program built by `program_calling_scale` or `program_calling_f`.
+fn scale_result(ast: &AST) -> ASTNode {
+    let function = ast.get_root().get_children()[0].clone();
+    function.get_children().last().unwrap().get_children()[0].get_children()[0].clone()
+}
+
+// Every call passes 4 for `k`, so `x * k + k` becomes `x * 4 + 4`; the calls disagree on `x`
+#[test]
+fn test_propagates_constant_arguments() {
+    let program = program_calling_scale(&[(identifier("a"), literal("4")), (literal("2"), literal("4"))]);
+    let ast = propagate_constant_arguments(&program);
+    assert_eq!(scale_result(&ast), binary(binary(identifier("x"), "*", literal("4")), "+", literal("4")));
+
+    // With the same constant for `x` too, the whole expression folds
+    let program = program_calling_scale(&[(literal("3"), literal("4")), (literal("3"), literal("4"))]);
+    assert_eq!(scale_result(&propagate_constant_arguments(&program)), literal("16"));
+}
+
+// Calls passing 4 and 5 for `k` leave `scale` unchanged
+#[test]
+fn test_keeps_differing_arguments() {
+    let program = program_calling_scale(&[(literal("3"), literal("4")), (literal("3"), literal("5"))]);
+    assert_eq!(scale_result(&propagate_constant_arguments(&program)), binary(binary(literal("3"), "*", identifier("k")), "+", identifier("k")));
+}
+
+/// Builds `t f(t p) { return value; }`, where `t` is `data_type` and `p` is `parameter`, and calls `f(argument)`.
+fn program_calling_f(data_type: DataType, parameter: &str, value: ASTNode, argument: ASTNode) -> AST {
+    let mut function = ASTNode::new(NodeType::FunctionDeclaration);
+    function.add_child(identifier("f"));
+    let mut parameter_node = ASTNode::new(NodeType::Parameter);
+    parameter_node.add_child(identifier(parameter));
+    parameter_node.add_child(ASTNode::new(NodeType::Type(data_type)));
+    function.add_child(parameter_node);
+    function.add_child(ASTNode::new(NodeType::Type(data_type)));
+    let mut return_node = ASTNode::new(NodeType::Return);
+    return_node.add_child(value);
+    let mut body = ASTNode::new(NodeType::BlockExpression);
+    body.add_child(return_node);
+    function.add_child(body);
+
+    let mut call = ASTNode::new(NodeType::FunctionCall);
+    call.add_child(identifier("f"));
+    call.add_child(argument);
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(function);
+    root.add_child(call);
+    AST::new(root)
+}
+
+// double f(double d) { return d / 2; } called as f(1) divides 1.0 by 2, not 1 by 2
+#[test]
+fn test_propagates_integers_into_doubles_as_fractions() {
+    let program = program_calling_f(DataType::Double, "d", binary(identifier("d"), "/", literal("2")), literal("1"));
+    assert_eq!(scale_result(&propagate_constant_arguments(&program)), binary(literal("1.0"), "/", literal("2")));
+}
+
+// int f(int x) { return x; } called as f(2.5) returns 2, and f(2147483647) does not fold x + 1 past the int range
+#[test]
+fn test_propagates_fractions_into_integers_truncated() {
+    let program = program_calling_f(DataType::Integer, "x", identifier("x"), literal("2.5"));
+    assert_eq!(scale_result(&propagate_constant_arguments(&program)), literal("2"));
+
+    let program = program_calling_f(DataType::Integer, "x", binary(identifier("x"), "+", literal("1")), literal("2147483647"));
+    assert_eq!(scale_result(&propagate_constant_arguments(&program)), binary(literal("2147483647"), "+", literal("1")));
+}
+
+// int f(int x) { return p.x + x; } called as f(5) returns p.x + 5, since the field `x` is not the parameter
+#[test]
+fn test_keeps_field_names_of_member_accesses() {
+    let mut member_access = ASTNode::new(NodeType::MemberAccess);
+    member_access.add_child(identifier("p"));
+    member_access.add_child(ASTNode::new(NodeType::Operator(".".to_string())));
+    member_access.add_child(identifier("x"));
+    let program = program_calling_f(DataType::Integer, "x", binary(member_access.clone(), "+", identifier("x")), literal("5"));
+    assert_eq!(scale_result(&propagate_constant_arguments(&program)), binary(member_access, "+", literal("5")));
+}
+
+#[test]
+fn test_pass_manager_levels() {
+    assert!(PassManager::for_level(OptLevel::O0).pass_names().is_empty());
+    assert_eq!(PassManager::for_level(OptLevel::O1).pass_names(), vec!["loop-invariant-motion"]);
+    let mut manager = PassManager::for_level(OptLevel::O2);
+    manager.add(LOOP_INVARIANT_MOTION);
+    assert_eq!(manager.pass_names(), vec![
+        "constant-argument-propagation",
+        "loop-strength-reduction",
+        "loop-invariant-motion",
+    ]);
+
+    let program = program_calling_scale(&[(literal("3"), literal("4"))]);
+    assert_eq!(scale_result(&manager.run(&program)), literal("16"));
+}
+
+/// Builds `int f() { int i; int s = 0; for (i = 0; i < 10; i = i + 2) { body } }`.
+fn program_counting(body: Vec<ASTNode>) -> AST {
+    let mut variable = ASTNode::new(NodeType::Variable);
+    variable.add_child(identifier("i"));
+    variable.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    let mut counter = ASTNode::new(NodeType::Initialization);
+    counter.add_child(variable);
+
+    let mut initializer = ASTNode::new(NodeType::LoopInitializer);
+    initializer.add_child(assignment("i", literal("0")));
+    let mut condition = ASTNode::new(NodeType::Condition);
+    condition.add_child(binary(identifier("i"), "<", literal("10")));
+    let mut increment = ASTNode::new(NodeType::LoopIncrement);
+    increment.add_child(assignment("i", binary(identifier("i"), "+", literal("2"))));
+    let mut loop_body = ASTNode::new(NodeType::BlockExpression);
+    loop_body.add_children(body);
+    let mut for_loop = ASTNode::new(NodeType::ForLoop);
+    for_loop.add_children(vec![initializer, condition, increment, loop_body]);
+
+    let mut function_body = ASTNode::new(NodeType::BlockExpression);
+    function_body.add_children(vec![counter, declaration("s", literal("0")), for_loop]);
+    let mut function = ASTNode::new(NodeType::FunctionDeclaration);
+    function.add_child(identifier("f"));
+    function.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    function.add_child(function_body);
+
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(function);
+    AST::new(root)
+}
+
+// s = s + i * 4; becomes s = s + i.mul4; with i.mul4 starting at 0 and advancing by 8 alongside i
+#[test]
+fn test_reduces_induction_products() {
+    let sum = assignment("s", binary(identifier("s"), "+", binary(identifier("i"), "*", literal("4"))));
+    let ast = reduce_loop_strength(&program_counting(vec![sum]));
+
+    let function_body = ast.get_root().get_children()[0].get_children()[2].get_children();
+    assert_eq!(function_body[2], declaration("i.mul4", literal("0")));
+    let loop_children = function_body[3].get_children();
+    assert_eq!(loop_children[2].get_children()[1], assignment("i.mul4", binary(identifier("i.mul4"), "+", literal("8"))));
+    assert_eq!(loop_children[3].get_children(), vec![assignment("s", binary(identifier("s"), "+", identifier("i.mul4")))]);
+}
+
+// i = i + 1; in the body means `i` does not advance by a constant step
+#[test]
+fn test_keeps_products_of_non_induction_variables() {
+    let sum = assignment("s", binary(identifier("s"), "+", binary(literal("4"), "*", identifier("i"))));
+    let program = program_counting(vec![sum, assignment("i", binary(identifier("i"), "+", literal("1")))]);
+
+    assert_eq!(reduce_loop_strength(&program), program);
+}
+
+// i += 1; in the body writes `i` just as i = i + 1; does
+#[test]
+fn test_compound_assignments_write_their_target() {
+    let sum = assignment("s", binary(identifier("s"), "+", binary(identifier("i"), "*", literal("4"))));
+    let mut step = ASTNode::new(NodeType::CompoundAssignment);
+    step.add_child(identifier("i"));
+    step.add_child(ASTNode::new(NodeType::Operator("+=".to_string())));
+    step.add_child(literal("1"));
+    let program = program_counting(vec![sum, step]);
+
+    assert_eq!(reduce_loop_strength(&program), program);
+}
+
+// for (int i = 0; i < 10; i++) steps `i` by 1, so i.mul4 advances by 4
+#[test]
+fn test_reduces_products_of_declared_counters() {
+    let mut initializer = ASTNode::new(NodeType::LoopInitializer);
+    initializer.add_child(declaration("i", literal("0")));
+    let mut condition = ASTNode::new(NodeType::Condition);
+    condition.add_child(binary(identifier("i"), "<", literal("10")));
+    let mut step = ASTNode::new(NodeType::UnaryExpression);
+    step.add_child(ASTNode::new(NodeType::Operator("++".to_string())));
+    step.add_child(identifier("i"));
+    let mut increment = ASTNode::new(NodeType::LoopIncrement);
+    increment.add_child(step);
+    let mut loop_body = ASTNode::new(NodeType::BlockExpression);
+    loop_body.add_child(assignment("s", binary(identifier("s"), "+", binary(identifier("i"), "*", literal("4")))));
+    let mut for_loop = ASTNode::new(NodeType::ForLoop);
+    for_loop.add_children(vec![initializer, condition, increment, loop_body]);
+
+    let mut function_body = ASTNode::new(NodeType::BlockExpression);
+    function_body.add_children(vec![declaration("s", literal("0")), for_loop]);
+    let mut function = ASTNode::new(NodeType::FunctionDeclaration);
+    function.add_child(identifier("f"));
+    function.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    function.add_child(function_body);
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(function);
+
+    let ast = reduce_loop_strength(&AST::new(root));
+    let function_body = ast.get_root().get_children()[0].get_children()[2].get_children();
+    assert_eq!(function_body[1], declaration("i.mul4", literal("0")));
+    let loop_children = function_body[2].get_children();
+    assert_eq!(loop_children[2].get_children()[1], assignment("i.mul4", binary(identifier("i.mul4"), "+", literal("4"))));
+}
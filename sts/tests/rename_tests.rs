@@ -0,0 +1,126 @@
+//! This file contains tests for renaming symbols, making sure every reference is renamed and unsafe renames are
+//! refused.
+
+use common::{
+    ast::{build, core::{ASTNode, AST}, data_type::DataType, node_type::NodeType, rewrite},
+    error::ErrorType,
+};
+use sts::rename::rename_symbol;
+
+/// Builds a program with `helper` and a `main` whose body starts with `prelude`:
+///
+/// ```c
+/// int helper(int n) { return n; }
+/// int main() {
+///     int total = helper(1);
+///     { int count = total; total = count; }
+///     return total;
+/// }
+/// ```
+fn program(prelude: Vec<ASTNode>) -> AST {
+    let helper = build::function("helper", DataType::Integer)
+        .param("n", DataType::Integer)
+        .body(vec![build::ret(Some(build::identifier("n")))])
+        .build()
+        .expect("helper should be well-formed");
+    let mut body = prelude;
+    body.extend([
+        build::declare("total", DataType::Integer, Some(build::call("helper", vec![build::literal("1")]))),
+        build::block(vec![
+            build::declare("count", DataType::Integer, Some(build::identifier("total"))),
+            build::assign("total", build::identifier("count")),
+        ]),
+        build::ret(Some(build::identifier("total"))),
+    ]);
+    let main = build::function("main", DataType::Integer)
+        .body(body)
+        .build()
+        .expect("main should be well-formed");
+    build::program(vec![helper, main]).expect("The program should be well-formed")
+}
+
+fn identifier_paths(ast: &AST, name: &str) -> Vec<Vec<usize>> {
+    rewrite::find(ast, |node: &ASTNode| node.get_node_type() == NodeType::Identifier(name.to_string()))
+}
+
+fn refusal(symbol: &str, name: &str, reason: &str) -> ErrorType {
+    ErrorType::InvalidRename { symbol: symbol.to_string(), name: name.to_string(), reason: reason.to_string() }
+}
+
+/// This test checks that renaming a variable from one of its references renames its declaration and every
+/// reference, including those in nested blocks.
+#[test]
+fn test_rename_variable() {
+    let ast = program(Vec::new());
+    let paths = identifier_paths(&ast, "total");
+    let renamed = rename_symbol(&ast, &paths[2], "sum").expect("The rename should apply");
+
+    assert!(identifier_paths(&renamed, "total").is_empty());
+    assert_eq!(identifier_paths(&renamed, "sum"), paths);
+}
+
+/// This test checks that renaming a function renames its calls.
+#[test]
+fn test_rename_function() {
+    let ast = program(Vec::new());
+    let paths = identifier_paths(&ast, "helper");
+    let renamed = rename_symbol(&ast, &paths[0], "identity").expect("The rename should apply");
+    assert_eq!(identifier_paths(&renamed, "identity"), paths);
+}
+
+/// This test checks that renaming a function with a prototype renames the prototype, the definition, and the calls.
+#[test]
+fn test_rename_function_with_prototype() {
+    let ast = program(Vec::new());
+    let definition = ast.get_root().get_children()[0].clone();
+    let mut prototype = ASTNode::new(NodeType::FunctionPrototype);
+    prototype.set_children(definition.get_children()[..3].to_vec());
+    let mut root = ast.get_root();
+    let mut children = root.get_children();
+    children.insert(0, prototype);
+    root.set_children(children);
+    let ast = AST::new(root);
+
+    let paths = identifier_paths(&ast, "helper");
+    assert_eq!(paths.len(), 3);
+    let renamed = rename_symbol(&ast, &paths[1], "identity").expect("The rename should apply");
+    assert_eq!(identifier_paths(&renamed, "identity"), paths);
+}
+
+/// This test checks that a rename is refused when the new name is declared in the same, an enclosing, or a nested
+/// scope.
+#[test]
+fn test_rename_refused_for_collisions() {
+    let ast = program(Vec::new());
+    let total = identifier_paths(&ast, "total")[0].clone();
+    let count = identifier_paths(&ast, "count")[0].clone();
+
+    assert_eq!(rename_symbol(&ast, &total, "main"), Err(vec![
+        refusal("total", "main", "it would shadow the `main` declared in an enclosing scope"),
+    ]));
+    assert_eq!(rename_symbol(&ast, &total, "count"), Err(vec![
+        refusal("total", "count", "the `count` declared in a nested scope would shadow it"),
+        refusal("total", "count", "a reference to another `count` in its scope would refer to it instead"),
+    ]));
+    assert_eq!(rename_symbol(&ast, &count, "total"), Err(vec![
+        refusal("count", "total", "it would shadow the `total` declared in an enclosing scope"),
+        refusal("count", "total", "a reference to another `total` in its scope would refer to it instead"),
+    ]));
+    assert_eq!(rename_symbol(&ast, &identifier_paths(&ast, "helper")[0], "main"), Err(vec![
+        refusal("helper", "main", "`main` is already declared in the same scope"),
+    ]));
+}
+
+/// This test checks that a rename is refused when a function the program calls but does not declare would be
+/// captured, and when the path does not name a declared symbol.
+#[test]
+fn test_rename_refused_for_undeclared_names() {
+    let ast = program(vec![build::expression_statement(build::call("puts", vec![build::string("hi")]))]);
+    let total = identifier_paths(&ast, "total")[0].clone();
+    assert_eq!(rename_symbol(&ast, &total, "puts"), Err(vec![
+        refusal("total", "puts", "a reference to another `puts` in its scope would refer to it instead"),
+    ]));
+    assert_eq!(rename_symbol(&ast, &identifier_paths(&ast, "puts")[0], "print"), Err(vec![
+        refusal("puts", "print", "it does not name a declared variable, parameter, or function"),
+    ]));
+}
@@ -0,0 +1,456 @@
+use common::{
+    ast::{
+        build,
+        core::{ASTNode, AST},
+        data_type::DataType,
+        node_type::NodeType,
+        rewrite,
+    },
+    error::ErrorType,
+};
+use sts::typecheck::{
+    check_case_declarations, check_const_assignments, check_enum_declarations, check_function_arguments, check_initializers,
+    check_struct_members, check_switch_labels, field_path, type_at_path,
+};
+
+fn struct_type(name: &str) -> ASTNode {
+    let mut type_node = ASTNode::new(NodeType::Type(DataType::Struct));
+    type_node.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+    type_node
+}
+
+/// Builds `void show(struct P p) { }`, declares `q` with the given type, and calls `show(q)`.
+fn program_passing(argument_type: ASTNode) -> AST {
+    let mut parameter = ASTNode::new(NodeType::Parameter);
+    parameter.add_child(ASTNode::new(NodeType::Identifier("p".to_string())));
+    parameter.add_child(struct_type("P"));
+
+    let mut function = ASTNode::new(NodeType::FunctionDeclaration);
+    function.add_child(ASTNode::new(NodeType::Identifier("show".to_string())));
+    function.add_child(parameter);
+    function.add_child(ASTNode::new(NodeType::Type(DataType::Void)));
+    function.add_child(ASTNode::new(NodeType::BlockExpression));
+
+    let mut variable = ASTNode::new(NodeType::Variable);
+    variable.add_child(ASTNode::new(NodeType::Identifier("q".to_string())));
+    variable.add_child(argument_type);
+    let mut initialization = ASTNode::new(NodeType::Initialization);
+    initialization.add_child(variable);
+
+    let mut call = ASTNode::new(NodeType::FunctionCall);
+    call.add_child(ASTNode::new(NodeType::Identifier("show".to_string())));
+    call.add_child(ASTNode::new(NodeType::Identifier("q".to_string())));
+
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(function);
+    root.add_child(initialization);
+    root.add_child(call);
+    AST::new(root)
+}
+
+// show(q) with `struct P q` passes the struct by value
+#[test]
+fn test_struct_argument_matches() {
+    assert!(check_function_arguments(&program_passing(struct_type("P"))).is_empty());
+}
+
+// show(q) with `struct Q q` or `int q` does not match `struct P`
+#[test]
+fn test_struct_argument_mismatch() {
+    assert_eq!(check_function_arguments(&program_passing(struct_type("Q"))), vec![ErrorType::TypeMismatch {
+        left_type: "struct P".to_string(),
+        right_type: "struct Q".to_string(),
+    }]);
+    assert_eq!(check_function_arguments(&program_passing(ASTNode::new(NodeType::Type(DataType::Integer)))), vec![ErrorType::TypeMismatch {
+        left_type: "struct P".to_string(),
+        right_type: "int".to_string(),
+    }]);
+}
+
+/// Builds the declaration `type x = value;` at the top level.
+fn program_initializing(data_type: DataType, value: &str) -> AST {
+    let mut variable = ASTNode::new(NodeType::Variable);
+    variable.add_child(ASTNode::new(NodeType::Identifier("x".to_string())));
+    variable.add_child(ASTNode::new(NodeType::Type(data_type)));
+    let mut assigned_value = ASTNode::new(NodeType::AssignedValue);
+    assigned_value.add_child(ASTNode::new(NodeType::Literal(value.to_string())));
+    let mut initialization = ASTNode::new(NodeType::Initialization);
+    initialization.add_child(variable);
+    initialization.add_child(assigned_value);
+
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(initialization);
+    AST::new(root)
+}
+
+// int x = 3; double x = 3; double x = 3.5; char *x = "str"; are all compatible
+#[test]
+fn test_initializers_match() {
+    assert!(check_initializers(&program_initializing(DataType::Integer, "3")).is_empty());
+    assert!(check_initializers(&program_initializing(DataType::Double, "3")).is_empty());
+    assert!(check_initializers(&program_initializing(DataType::Double, "3.5")).is_empty());
+    assert!(check_initializers(&program_initializing(DataType::Float, "2.5f")).is_empty());
+    assert!(check_initializers(&program_initializing(DataType::Double, "1e-9")).is_empty());
+    assert!(check_initializers(&program_initializing(DataType::String, "\"str\"")).is_empty());
+}
+
+// bool x = 3.5; and int x = "str"; initialize a variable with a literal of an incompatible type
+#[test]
+fn test_initializer_mismatch() {
+    assert_eq!(check_initializers(&program_initializing(DataType::Boolean, "3.5")), vec![ErrorType::TypeMismatch {
+        left_type: "bool".to_string(),
+        right_type: "double".to_string(),
+    }]);
+    assert_eq!(check_initializers(&program_initializing(DataType::Integer, "\"str\"")), vec![ErrorType::TypeMismatch {
+        left_type: "int".to_string(),
+        right_type: "char *".to_string(),
+    }]);
+    assert_eq!(check_initializers(&program_initializing(DataType::Integer, "1e3")), vec![ErrorType::TypeMismatch {
+        left_type: "int".to_string(),
+        right_type: "double".to_string(),
+    }]);
+}
+
+/// Builds `switch (scrutinee) { case label: break; ... }` at the top level.
+fn program_switching(scrutinee: ASTNode, labels: &[&str]) -> AST {
+    let mut arms = ASTNode::new(NodeType::BlockExpression);
+    for label in labels {
+        let mut body = ASTNode::new(NodeType::BlockExpression);
+        body.add_child(ASTNode::new(NodeType::Break));
+        let mut case = ASTNode::new(NodeType::Case);
+        case.add_child(ASTNode::new(NodeType::Literal(label.to_string())));
+        case.add_child(body);
+        arms.add_child(case);
+    }
+    let mut switch = ASTNode::new(NodeType::SwitchStatement);
+    switch.add_child(scrutinee);
+    switch.add_child(arms);
+
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(switch);
+    AST::new(root)
+}
+
+// case "go": case "stop": and case 1: case 2: have distinct labels
+#[test]
+fn test_switch_labels_distinct() {
+    let s = ASTNode::new(NodeType::Identifier("s".to_string()));
+    assert!(check_switch_labels(&program_switching(s.clone(), &["\"go\"", "\"stop\""])).is_empty());
+    assert!(check_switch_labels(&program_switching(s, &["1", "2"])).is_empty());
+}
+
+// case "go": case "go": and case 65: case 'A': repeat a label
+#[test]
+fn test_duplicate_switch_labels() {
+    let s = ASTNode::new(NodeType::Identifier("s".to_string()));
+    assert_eq!(check_switch_labels(&program_switching(s.clone(), &["\"go\"", "\"go\""])), vec![
+        ErrorType::DuplicateCaseLabel { label: "\"go\"".to_string() },
+    ]);
+    assert_eq!(check_switch_labels(&program_switching(s, &["65", "'A'"])), vec![
+        ErrorType::DuplicateCaseLabel { label: "'A'".to_string() },
+    ]);
+}
+
+// case "go": case 1: mixes labels, and switch (next()) on strings would call next() once per case
+#[test]
+fn test_invalid_string_switches() {
+    let s = ASTNode::new(NodeType::Identifier("s".to_string()));
+    assert_eq!(check_switch_labels(&program_switching(s, &["\"go\"", "1"])), vec![ErrorType::InvalidSwitch {
+        reason: "a switch on strings can only have string case labels".to_string(),
+    }]);
+
+    let mut call = ASTNode::new(NodeType::FunctionCall);
+    call.add_child(ASTNode::new(NodeType::Identifier("next".to_string())));
+    assert_eq!(check_switch_labels(&program_switching(call, &["\"go\""])), vec![ErrorType::InvalidSwitch {
+        reason: "the scrutinee of a switch on strings cannot have side effects".to_string(),
+    }]);
+}
+
+/// Builds `void f(<parameter_type> c) { switch (c) { case label: break; ... } }` after `enum Flag { F0, F1 }`, with
+/// each label that is not a literal written as an enum constant.
+fn program_switching_on(parameter_type: DataType, labels: &[&str]) -> AST {
+    let mut arms = ASTNode::new(NodeType::BlockExpression);
+    for label in labels {
+        let mut body = ASTNode::new(NodeType::BlockExpression);
+        body.add_child(ASTNode::new(NodeType::Break));
+        let mut case = ASTNode::new(NodeType::Case);
+        if label.starts_with('F') {
+            case.add_child(ASTNode::new(NodeType::Identifier(label.to_string())));
+        } else {
+            case.add_child(ASTNode::new(NodeType::Literal(label.to_string())));
+        }
+        case.add_child(body);
+        arms.add_child(case);
+    }
+    let mut switch = ASTNode::new(NodeType::SwitchStatement);
+    switch.add_child(ASTNode::new(NodeType::Identifier("c".to_string())));
+    switch.add_child(arms);
+    let mut body = ASTNode::new(NodeType::BlockExpression);
+    body.add_child(switch);
+
+    let mut parameter = ASTNode::new(NodeType::Parameter);
+    parameter.add_child(ASTNode::new(NodeType::Identifier("c".to_string())));
+    parameter.add_child(ASTNode::new(NodeType::Type(parameter_type)));
+    let mut function = ASTNode::new(NodeType::FunctionDeclaration);
+    function.add_child(ASTNode::new(NodeType::Identifier("f".to_string())));
+    function.add_child(parameter);
+    function.add_child(ASTNode::new(NodeType::Type(DataType::Void)));
+    function.add_child(body);
+
+    let mut declaration = ASTNode::new(NodeType::EnumDeclaration);
+    declaration.add_child(ASTNode::new(NodeType::Identifier("Flag".to_string())));
+    for name in ["F0", "F1"] {
+        let mut variant = ASTNode::new(NodeType::Variant);
+        variant.add_child(ASTNode::new(NodeType::Identifier(name.to_string())));
+        declaration.add_child(variant);
+    }
+
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(declaration);
+    root.add_child(function);
+    AST::new(root)
+}
+
+#[test]
+fn test_switch_labels_match_scrutinee_type() {
+    assert!(check_switch_labels(&program_switching_on(DataType::Char, &["'a'", "-1", "F1"])).is_empty());
+    assert!(check_switch_labels(&program_switching_on(DataType::UnsignedChar, &["255", "F0"])).is_empty());
+
+    assert_eq!(check_switch_labels(&program_switching_on(DataType::Char, &["'a'", "300", "1.5"])), vec![
+        ErrorType::InvalidSwitch {
+            reason: "case label `300` does not fit in the type `char` of the scrutinee".to_string(),
+        },
+        ErrorType::InvalidSwitch {
+            reason: "case label `1.5` does not match the type `char` of the scrutinee".to_string(),
+        },
+    ]);
+    assert_eq!(check_switch_labels(&program_switching_on(DataType::UnsignedChar, &["-1"])), vec![
+        ErrorType::InvalidSwitch {
+            reason: "case label `-1` does not fit in the type `unsigned char` of the scrutinee".to_string(),
+        },
+    ]);
+    assert_eq!(check_switch_labels(&program_switching_on(DataType::Double, &["1"])), vec![ErrorType::InvalidSwitch {
+        reason: "the scrutinee of a switch cannot have the type `double`".to_string(),
+    }]);
+}
+
+/// Builds `switch (s) { case 1: int x = 1; break; case 2: <second> }` at the top level.
+fn program_declaring_in_case(second: Vec<ASTNode>) -> AST {
+    let first = build::block(vec![
+        build::declare("x", DataType::Integer, Some(build::literal("1"))),
+        ASTNode::new(NodeType::Break),
+    ]);
+    let mut first_case = ASTNode::new(NodeType::Case);
+    first_case.add_child(build::literal("1"));
+    first_case.add_child(first);
+
+    let mut second_case = ASTNode::new(NodeType::Case);
+    second_case.add_child(build::literal("2"));
+    second_case.add_child(build::block(second));
+
+    let mut arms = ASTNode::new(NodeType::BlockExpression);
+    arms.add_child(first_case);
+    arms.add_child(second_case);
+    let mut switch = ASTNode::new(NodeType::SwitchStatement);
+    switch.add_child(build::identifier("s"));
+    switch.add_child(arms);
+
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(switch);
+    AST::new(root)
+}
+
+// case 2: x = 2; uses the x of case 1, whose initialization the jump to case 2 skips
+#[test]
+fn test_case_jumps_past_initialization() {
+    let assign = build::assign("x", build::literal("2"));
+    assert_eq!(check_case_declarations(&program_declaring_in_case(vec![assign])), vec![
+        ErrorType::JumpPastInitialization { variable: "x".to_string(), label: "case 2".to_string() },
+    ]);
+}
+
+// case 2: int x = 2; declares its own x in the scope of the case
+#[test]
+fn test_case_redeclares_variable() {
+    let init = build::declare("x", DataType::Integer, Some(build::literal("2")));
+    assert!(check_case_declarations(&program_declaring_in_case(vec![init])).is_empty());
+}
+
+/// Builds `double scale(int n) { ... }` and a `main` declaring `int count = 2;`, then `{ float count; }`, and
+/// evaluating `total` as the last statement.
+fn program_typing(total: ASTNode) -> AST {
+    let scale = build::function("scale", DataType::Double)
+        .param("n", DataType::Integer)
+        .body(vec![build::ret(Some(build::identifier("n")))])
+        .build()
+        .expect("scale should be well-formed");
+    let main = build::function("main", DataType::Integer)
+        .body(vec![
+            build::declare("count", DataType::Integer, Some(build::literal("2"))),
+            build::block(vec![build::declare("count", DataType::Float, None)]),
+            build::expression_statement(total),
+        ])
+        .build()
+        .expect("main should be well-formed");
+    build::program(vec![scale, main]).expect("The program should be well-formed")
+}
+
+#[test]
+fn test_type_at_path_of_names() {
+    let ast = program_typing(build::call("scale", vec![build::identifier("count")]));
+    let count = rewrite::find(&ast, |node| node.get_node_type() == NodeType::Identifier("count".to_string()));
+    let types: Vec<Option<DataType>> = count.iter().map(|path| type_at_path(&ast, path)).collect();
+    assert_eq!(types, vec![Some(DataType::Integer), Some(DataType::Float), Some(DataType::Integer)]);
+
+    let n = rewrite::find(&ast, |node| node.get_node_type() == NodeType::Identifier("n".to_string()));
+    assert_eq!(type_at_path(&ast, &n[1]), Some(DataType::Integer));
+    let scale = rewrite::find(&ast, |node| node.get_node_type() == NodeType::Identifier("scale".to_string()));
+    assert_eq!(type_at_path(&ast, &scale[1]), Some(DataType::Function));
+    let call = rewrite::find(&ast, |node| node.get_node_type() == NodeType::FunctionCall);
+    assert_eq!(type_at_path(&ast, &call[0]), Some(DataType::Double));
+}
+
+#[test]
+fn test_type_at_path_of_operations() {
+    let sum = build::binary(build::identifier("count"), "+", build::literal("1.5f"));
+    let ast = program_typing(build::binary(sum, "<", build::literal("'a'")));
+    let operations = rewrite::find(&ast, |node| node.get_node_type() == NodeType::BinaryExpression);
+    assert_eq!(type_at_path(&ast, &operations[0]), Some(DataType::Boolean));
+    assert_eq!(type_at_path(&ast, &operations[1]), Some(DataType::Float));
+
+    let ast = program_typing(build::unary("&", build::identifier("count")));
+    let address = rewrite::find(&ast, |node| node.get_node_type() == NodeType::UnaryExpression);
+    assert_eq!(type_at_path(&ast, &address[0]), None);
+    assert_eq!(type_at_path(&ast, &[9]), None);
+}
+
+fn field(name: &str, data_type: DataType) -> ASTNode {
+    let mut field = ASTNode::new(NodeType::Field);
+    field.add_child(ASTNode::new(NodeType::Literal(name.to_string())));
+    field.add_child(ASTNode::new(NodeType::Type(data_type)));
+    field
+}
+
+/// Builds `struct Value { tag: int, union { i: int, struct { x: float, <last>: float } } }`.
+fn program_with_anonymous_members(last: &str) -> AST {
+    let mut inner = ASTNode::new(NodeType::StructDeclaration);
+    inner.add_child(field("x", DataType::Float));
+    inner.add_child(field(last, DataType::Float));
+    let mut union = ASTNode::new(NodeType::UnionDeclaration);
+    union.add_child(field("i", DataType::Integer));
+    union.add_child(inner);
+
+    let mut value = ASTNode::new(NodeType::StructDeclaration);
+    value.add_child(ASTNode::new(NodeType::Identifier("Value".to_string())));
+    value.add_child(field("tag", DataType::Integer));
+    value.add_child(union);
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(value);
+    AST::new(root)
+}
+
+#[test]
+fn test_field_path_through_anonymous_members() {
+    let ast = program_with_anonymous_members("y");
+    let value = &ast.get_root().get_children()[0];
+    assert_eq!(field_path(value, "tag"), Some(vec![0]));
+    assert_eq!(field_path(value, "i"), Some(vec![1, 0]));
+    assert_eq!(field_path(value, "y"), Some(vec![1, 1, 1]));
+    assert_eq!(field_path(value, "z"), None);
+    assert!(check_struct_members(&ast).is_empty());
+}
+
+#[test]
+fn test_duplicate_member_in_anonymous_member() {
+    let ast = program_with_anonymous_members("tag");
+    assert_eq!(check_struct_members(&ast), vec![ErrorType::DuplicateMember {
+        structure: "Value".to_string(),
+        member: "tag".to_string(),
+    }]);
+}
+
+/// Builds `enum Flag : <underlying> { F0, F1, ... }` with the given number of constants, then `int main() { F1; }`.
+fn program_with_enum(underlying: Option<DataType>, constants: usize) -> AST {
+    let mut declaration = ASTNode::new(NodeType::EnumDeclaration);
+    declaration.add_child(ASTNode::new(NodeType::Identifier("Flag".to_string())));
+    if let Some(underlying) = underlying {
+        declaration.add_child(ASTNode::new(NodeType::Type(underlying)));
+    }
+    for index in 0..constants {
+        let mut variant = ASTNode::new(NodeType::Variant);
+        variant.add_child(ASTNode::new(NodeType::Identifier(format!("F{}", index))));
+        declaration.add_child(variant);
+    }
+
+    let mut body = ASTNode::new(NodeType::BlockExpression);
+    body.add_child(ASTNode::new(NodeType::Identifier("F1".to_string())));
+    let mut main = ASTNode::new(NodeType::FunctionDeclaration);
+    main.add_child(ASTNode::new(NodeType::Identifier("main".to_string())));
+    main.add_child(ASTNode::new(NodeType::Type(DataType::Integer)));
+    main.add_child(body);
+
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(declaration);
+    root.add_child(main);
+    AST::new(root)
+}
+
+#[test]
+fn test_enum_constants_have_the_underlying_type() {
+    let ast = program_with_enum(Some(DataType::Char), 2);
+    assert!(check_enum_declarations(&ast).is_empty());
+    assert_eq!(type_at_path(&ast, &[1, 2, 0]), Some(DataType::Char));
+
+    let ast = program_with_enum(None, 2);
+    assert_eq!(type_at_path(&ast, &[1, 2, 0]), Some(DataType::Integer));
+}
+
+#[test]
+fn test_enumerator_out_of_range() {
+    let ast = program_with_enum(Some(DataType::Boolean), 3);
+    assert_eq!(check_enum_declarations(&ast), vec![ErrorType::EnumeratorOutOfRange {
+        enumeration: "Flag".to_string(),
+        enumerator: "F2".to_string(),
+        value: "2".to_string(),
+        underlying: "bool".to_string(),
+    }]);
+    assert!(check_enum_declarations(&program_with_enum(Some(DataType::UnsignedChar), 3)).is_empty());
+}
+
+/// Builds `const int <name> = 5;`.
+fn declare_const(name: &str) -> ASTNode {
+    let mut type_node = ASTNode::new(NodeType::Type(DataType::Integer));
+    type_node.add_child(ASTNode::new(NodeType::Qualifier("const".to_string())));
+    let mut variable = ASTNode::new(NodeType::Variable);
+    variable.add_child(build::identifier(name));
+    variable.add_child(type_node);
+    let mut value = ASTNode::new(NodeType::AssignedValue);
+    value.add_child(build::literal("5"));
+    let mut initialization = ASTNode::new(NodeType::Initialization);
+    initialization.add_child(variable);
+    initialization.add_child(value);
+    initialization
+}
+
+#[test]
+fn test_writes_to_const_variables() {
+    let body = build::block(vec![
+        declare_const("x"),
+        build::declare("y", DataType::Integer, Some(build::identifier("x"))),
+        build::assign("y", build::literal("1")),
+        build::assign("x", build::literal("1")),
+        build::compound_assign("x", "+=", build::literal("1")),
+        build::expression_statement(build::postfix(build::identifier("x"), "++")),
+        build::expression_statement(build::unary("--", build::identifier("x"))),
+        // An inner `x` that is not `const` can be written
+        build::block(vec![
+            build::declare("x", DataType::Integer, None),
+            build::assign("x", build::literal("2")),
+        ]),
+    ]);
+    let mut root = ASTNode::new(NodeType::TopLevelExpression);
+    root.add_child(body);
+
+    let error = ErrorType::AssignmentToConst { variable: "x".to_string() };
+    assert_eq!(check_const_assignments(&AST::new(root)), vec![error.clone(), error.clone(), error.clone(), error]);
+}
@@ -0,0 +1,62 @@
+//! This file contains tests for the module cache, making sure artifacts are reused only for the same source,
+//! compiler, and flags.
+
+use std::{fs, path::PathBuf};
+
+use common::ast::{build, data_type::DataType};
+use sicc::cache::{CacheKey, ModuleCache};
+use sts::core::{SymbolInfo, SymbolTable, SymbolTableStack, SymbolValue};
+
+/// Creates an empty cache directory for one test.
+fn directory(test: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("sicc-cache-{}-{}", std::process::id(), test));
+    let _ = fs::remove_dir_all(&directory);
+    directory
+}
+
+/// This test checks that a stored analysis is loaded back under the same key only.
+#[test]
+fn test_analysis_reused_for_same_key() {
+    let cache = ModuleCache::open(directory("analysis")).expect("The cache should open");
+    let ast = build::program(vec![
+        build::function("main", DataType::Integer).build().expect("main should be well-formed"),
+    ]).expect("The program should be well-formed");
+    let mut globals = SymbolTable::new();
+    globals.add("main".to_string(), SymbolInfo::new(DataType::Integer, SymbolValue::FunctionValue {
+        parameters: Vec::new(),
+    }));
+    let mut sym_table_stack = SymbolTableStack::new();
+    sym_table_stack.push(globals);
+
+    let source = "int main() {\n}\n";
+    let key = CacheKey::new(source, &["-O1".to_string()]);
+    assert_eq!(cache.load_analysis(&key), None);
+    cache.store_analysis(&key, &ast, &sym_table_stack).expect("The analysis should be stored");
+
+    assert_eq!(cache.load_analysis(&key), Some((ast, sym_table_stack)));
+    assert_eq!(cache.load_analysis(&CacheKey::new(source, &["-O2".to_string()])), None);
+    assert_eq!(cache.load_analysis(&CacheKey::new("int main() {\n    return 0;\n}\n", &["-O1".to_string()])), None);
+    assert_eq!(cache.load_object(&key), None);
+}
+
+/// This test checks that objects are stored per key, and that an entry whose header does not match its key, as after
+/// a hash collision or a corrupted write, is a miss.
+#[test]
+fn test_objects_and_invalid_entries() {
+    let directory = directory("objects");
+    let cache = ModuleCache::open(&directory).expect("The cache should open");
+    let first = CacheKey::new("int x;", &[]);
+    let second = CacheKey::new("int y;", &[]);
+    cache.store_object(&first, b"first").expect("The object should be stored");
+    cache.store_object(&second, b"second").expect("The object should be stored");
+    assert_eq!(cache.load_object(&first), Some(b"first".to_vec()));
+    assert_eq!(cache.load_object(&second), Some(b"second".to_vec()));
+
+    let entries: Vec<PathBuf> = fs::read_dir(&directory).unwrap().map(|entry| entry.unwrap().path()).collect();
+    assert_eq!(entries.len(), 2);
+    for entry in entries {
+        fs::write(entry, b"sicc 0.0.0 flags 0 source 0 0\nstale").unwrap();
+    }
+    assert_eq!(cache.load_object(&first), None);
+    assert_eq!(cache.load_object(&second), None);
+}
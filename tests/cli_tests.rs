@@ -0,0 +1,111 @@
+//! This file contains tests that run the `sicc` binary, making sure each mode works end to end on a valid program.
+
+use std::{fs, path::{Path, PathBuf}, process::{Command, Output}};
+
+/// A valid program with a global variable, a function, and locals.
+const PROGRAM: &str = "int count = 3;\nint twice(int x) { return (x + x); }\nint main() { int y = twice(count); return y; }\n";
+
+/// The global symbols of `PROGRAM` as written by `--emit-symbols-json`.
+const PROGRAM_SYMBOLS_JSON: &str = concat!(
+    "[\n",
+    "  {\"name\": \"count\", \"kind\": \"global\", \"type\": \"int\", \"linkage\": \"external\", \"mangled_name\": \"count\"},\n",
+    "  {\"name\": \"twice\", \"kind\": \"function\", \"type\": \"int (int)\", \"linkage\": \"external\", \"mangled_name\": \"twice\"},\n",
+    "  {\"name\": \"main\", \"kind\": \"function\", \"type\": \"int (void)\", \"linkage\": \"external\", \"mangled_name\": \"main\"}\n",
+    "]",
+);
+
+/// Creates an empty directory for one test and writes `PROGRAM` to `main.c` in it.
+fn workspace(test: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("sicc-cli-{}-{}", std::process::id(), test));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).expect("The test directory should be created");
+    fs::write(directory.join("main.c"), PROGRAM).expect("The program should be written");
+    directory
+}
+
+/// Runs `sicc` on `main.c` in `directory` with the given flags, failing the test if it does not succeed.
+fn sicc(directory: &Path, flags: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_sicc"))
+        .arg(directory.join("main.c"))
+        .args(flags)
+        .current_dir(directory)
+        .output()
+        .expect("sicc should run");
+    assert!(output.status.success(), "sicc {:?} failed:\n{}", flags, String::from_utf8_lossy(&output.stderr));
+    output
+}
+
+/// This test checks that `--emit-symbols-json` writes the global symbols of a valid program, and that with
+/// `--cache-dir` a second run reuses the cached analysis and writes the same symbols.
+#[test]
+fn test_emit_symbols_json_with_cache() {
+    let directory = workspace("json");
+    let flags = ["--emit-symbols-json", "symbols.json", "--cache-dir", "cache"];
+
+    sicc(&directory, &flags);
+    let first = fs::read_to_string(directory.join("symbols.json")).expect("The symbols should be written");
+    assert_eq!(first, PROGRAM_SYMBOLS_JSON);
+    assert_eq!(fs::read_dir(directory.join("cache")).expect("The cache should be created").count(), 1);
+
+    fs::remove_file(directory.join("symbols.json")).unwrap();
+    sicc(&directory, &flags);
+    assert_eq!(fs::read_to_string(directory.join("symbols.json")).expect("The symbols should be rewritten"), first);
+}
+
+/// This test checks that `--emit-symbols` prints every global symbol of a valid program, followed by its locals.
+#[test]
+fn test_emit_symbols() {
+    let directory = workspace("symbols");
+
+    let output = sicc(&directory, &["--emit-symbols"]);
+
+    let stdout = String::from_utf8(output.stdout).expect("The output should be UTF-8");
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[..3], [
+        "global\tcount\tint\texternal\tcount",
+        "function\ttwice\tint (int)\texternal\ttwice",
+        "function\tmain\tint (void)\texternal\tmain",
+    ]);
+    assert!(lines[3..].iter().all(|line| line.starts_with("local\t")), "{}", stdout);
+}
+
+/// This test checks that the includes and macros of the input are expanded before it is compiled, so a program split
+/// across a header has the same symbols as `PROGRAM`.
+#[test]
+fn test_preprocesses_input() {
+    let directory = workspace("preprocess");
+    fs::write(directory.join("count.h"), "int count = 3;\n").unwrap();
+    fs::write(directory.join("main.c"), concat!(
+        "#include \"count.h\"\n",
+        "#define TWICE twice\n",
+        "int twice(int x) { return (x + x); }\n",
+        "int main() { int y = TWICE(count); return y; }\n",
+    )).unwrap();
+
+    sicc(&directory, &["--syntax-only"]);
+    sicc(&directory, &["--emit-symbols-json", "symbols.json"]);
+    assert_eq!(fs::read_to_string(directory.join("symbols.json")).expect("The symbols should be written"), PROGRAM_SYMBOLS_JSON);
+}
+
+/// This test checks that `-I` adds a directory to look for included files in, and that `-D` defines a macro for the
+/// input and the files it includes.
+#[test]
+fn test_include_paths_and_defines() {
+    let directory = workspace("include_paths");
+    fs::create_dir_all(directory.join("include")).unwrap();
+    fs::write(directory.join("include/count.h"), "int count = COUNT;\n").unwrap();
+    fs::write(directory.join("main.c"), PROGRAM.replace("int count = 3;", "#include <count.h>")).unwrap();
+
+    sicc(&directory, &["-I", "include", "-D", "COUNT=3", "--emit-symbols-json", "symbols.json"]);
+    assert_eq!(fs::read_to_string(directory.join("symbols.json")).expect("The symbols should be written"), PROGRAM_SYMBOLS_JSON);
+}
+
+/// This test checks that a macro defined with `-D` is expanded in an input without any directives.
+#[test]
+fn test_defines_without_directives() {
+    let directory = workspace("defines");
+    fs::write(directory.join("main.c"), PROGRAM.replace("twice", "TWICE")).unwrap();
+
+    sicc(&directory, &["-DTWICE=twice", "--emit-symbols-json", "symbols.json"]);
+    assert_eq!(fs::read_to_string(directory.join("symbols.json")).expect("The symbols should be written"), PROGRAM_SYMBOLS_JSON);
+}
@@ -0,0 +1,21 @@
+//! This file contains tests for compilation databases, making sure each command is recorded in the
+//! `compile_commands.json` format.
+
+use sicc::compdb::{compile_commands_json, CompileCommand};
+
+/// This test checks that every command is written with its directory, file, and arguments, escaped as JSON.
+#[test]
+fn test_compile_commands_json() {
+    let commands = vec![
+        CompileCommand::new("/work", "main.c", vec!["sicc".to_string(), "main.c".to_string(), "-O".to_string(), "2".to_string()]),
+        CompileCommand::new("/work", "say \"hi\".c", vec!["sicc".to_string(), "say \"hi\".c".to_string()]),
+    ];
+
+    assert_eq!(compile_commands_json(&commands), concat!(
+        "[\n",
+        "  {\"directory\": \"/work\", \"file\": \"main.c\", \"arguments\": [\"sicc\", \"main.c\", \"-O\", \"2\"]},\n",
+        "  {\"directory\": \"/work\", \"file\": \"say \\\"hi\\\".c\", \"arguments\": [\"sicc\", \"say \\\"hi\\\".c\"]}\n",
+        "]",
+    ));
+    assert_eq!(compile_commands_json(&[]), "[]");
+}
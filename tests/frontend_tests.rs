@@ -0,0 +1,50 @@
+//! This file contains tests for the parallel front end, making sure files lexed and parsed on a thread pool give the
+//! same results as one at a time, and that their diagnostics are ordered by file.
+
+use std::path::{Path, PathBuf};
+
+use common::{ast::core::AST, error::ErrorType, symbol::Symbol};
+use lexer::{core::Lexer, preprocess::Preprocessor, token::Token};
+use parser::core::Parser;
+use sicc::frontend::{diagnostics, parse_files};
+
+/// Compiles only if `T` can be moved to and shared between threads.
+fn assert_thread_safe<T: Send + Sync>() {}
+
+/// This test checks that the lexer, preprocessor, parser, and everything they produce can be used from any thread.
+#[test]
+fn test_frontend_is_thread_safe() {
+    assert_thread_safe::<Lexer<'static>>();
+    assert_thread_safe::<Preprocessor>();
+    assert_thread_safe::<Parser>();
+    assert_thread_safe::<Token>();
+    assert_thread_safe::<Symbol>();
+    assert_thread_safe::<AST>();
+    assert_thread_safe::<ErrorType>();
+}
+
+/// This test checks that many files parsed in parallel match the files parsed one at a time, and that the errors of
+/// the files that fail are reported in the order the files were given.
+#[test]
+fn test_parse_files_in_order() {
+    let files: Vec<(PathBuf, String)> = (0..64).map(|index| {
+        let source = if index % 16 == 5 {
+            format!("int f{}() {{ return 0 }}", index)
+        } else {
+            format!("int f{}() {{ int x{} = {}; return x{}; }}", index, index, index, index)
+        };
+        (PathBuf::from(format!("file{}.c", index)), source)
+    }).collect();
+
+    let parsed = parse_files(&files);
+
+    assert_eq!(parsed.len(), files.len());
+    for ((path, source), file) in files.iter().zip(&parsed) {
+        assert_eq!(&file.path, path);
+        assert_eq!(file.result, Lexer::lex(source).and_then(Parser::parse));
+    }
+
+    let mut failing: Vec<&Path> = diagnostics(&parsed).into_iter().map(|(path, _)| path).collect();
+    failing.dedup();
+    assert_eq!(failing, vec![Path::new("file5.c"), Path::new("file21.c"), Path::new("file37.c"), Path::new("file53.c")]);
+}
@@ -0,0 +1,29 @@
+//! This file contains tests for hover information, making sure the type shown is the one of the token under the
+//! cursor.
+
+use common::ast::data_type::DataType;
+use sicc::hover::type_at;
+
+const SOURCE: &str = "double half(int x) {\n    return x;\n}\nint main() {\n    float x = 2.5f;\n    half(3);\n    return 0;\n}\n";
+
+/// This test checks that the same name has the type of the declaration it refers to at each place it is written.
+#[test]
+fn test_type_at_identifiers() {
+    let parameter = SOURCE.find("x)").expect("The source has the parameter");
+    let returned = SOURCE.find("x;").expect("The source returns the parameter");
+    let local = SOURCE.find("x =").expect("The source declares a local");
+    let function = SOURCE.find("half(3)").expect("The source calls half");
+
+    assert_eq!(type_at(SOURCE, parameter), Some(DataType::Integer));
+    assert_eq!(type_at(SOURCE, returned), Some(DataType::Integer));
+    assert_eq!(type_at(SOURCE, local), Some(DataType::Float));
+    assert_eq!(type_at(SOURCE, function + 2), Some(DataType::Function));
+}
+
+/// This test checks the types of literals, and that there is no type between tokens.
+#[test]
+fn test_type_at_literals() {
+    assert_eq!(type_at(SOURCE, SOURCE.find("2.5f").expect("The source has a float")), Some(DataType::Float));
+    assert_eq!(type_at(SOURCE, SOURCE.find("3)").expect("The source has an integer")), Some(DataType::Integer));
+    assert_eq!(type_at(SOURCE, SOURCE.find(" x =").expect("The source has a space")), None);
+}
@@ -0,0 +1,297 @@
+//! This file contains tests for the pipeline explanation mode, making sure each phase's output is recorded and
+//! that later phases are skipped once one fails.
+
+use std::{
+    env,
+    ffi::OsStr,
+    io::Write,
+    process::{Command, Stdio},
+    sync::{Arc, Mutex},
+};
+
+use common::{
+    config::{CodegenConfig, Environment},
+    diagnostic::{DiagnosticOrder, DiagnosticSink},
+    error::ErrorType,
+    localization::Language,
+    location::SourceLocation,
+};
+use ir::{core::SourceConstruct, stats::{function_stats, FunctionStats}};
+use lexer::{core::Lexer, token::Token};
+use sicc::pipeline::{
+    analyze, annotate_ir, explain_pipeline, explain_pipeline_reporting, explain_pipeline_with_config,
+    ir_statistics, local_variables,
+};
+
+/// This test checks that a lexer error is reported and that no later phase is run.
+#[test]
+fn test_explain_pipeline_lexer_error() {
+    let explanation = explain_pipeline("int x = 1 @ 2;");
+
+    assert!(explanation.tokens.is_empty());
+    assert!(explanation.ast.is_none());
+    assert!(explanation.symbol_table.is_none());
+    assert!(explanation.typed_ast.is_none());
+    assert!(explanation.ir.is_none());
+    assert!(matches!(explanation.errors[0], ErrorType::UnrecognizedToken { .. }));
+    assert!(!explanation.is_complete());
+}
+
+/// This test checks that lexer errors are reported to the given sink with their line and column, and streamed as
+/// they are found.
+#[test]
+fn test_explain_pipeline_reporting_lexer_errors() {
+    let streamed = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&streamed);
+    let diagnostics = DiagnosticSink::with_order(DiagnosticOrder::Location)
+        .streaming(move |diagnostic| sink.lock().unwrap().push(diagnostic.localized(Language::English)));
+
+    let explanation = explain_pipeline_reporting("int x = 1 @ 2;\nint y = $;", CodegenConfig::default(), diagnostics);
+
+    assert_eq!(explanation.errors.len(), 2);
+    let streamed = streamed.lock().unwrap();
+    assert!(streamed[0].starts_with(&format!("{}: error[E0007]", SourceLocation::new(None, 1, 11))), "{:?}", streamed);
+    assert!(streamed[1].starts_with("2:9: error[E0007]"), "{:?}", streamed);
+}
+
+/// This test checks that type errors are reported at the line and column of the node they were found at.
+#[test]
+fn test_explain_pipeline_reporting_type_errors() {
+    let streamed = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&streamed);
+    let diagnostics = DiagnosticSink::new()
+        .streaming(move |diagnostic| sink.lock().unwrap().push(diagnostic.localized(Language::English)));
+    let source = "int main() {\n    const int x = 1;\n    int s = \"a\";\n    x = 2;\n    return 0;\n}";
+
+    let explanation = explain_pipeline_reporting(source, CodegenConfig::default(), diagnostics);
+
+    assert!(matches!(explanation.errors[0], ErrorType::TypeMismatch { .. }));
+    assert!(matches!(explanation.errors[1], ErrorType::AssignmentToConst { .. }));
+    let streamed = streamed.lock().unwrap();
+    assert!(streamed[0].starts_with("3:5: error["), "{:?}", streamed);
+    assert!(streamed[1].starts_with("4:5: error["), "{:?}", streamed);
+}
+
+/// This test checks that the tokens are kept when the parser fails, that the error is located, and that analysis and
+/// IR generation are skipped.
+#[test]
+fn test_explain_pipeline_parser_error() {
+    let explanation = explain_pipeline("int x = ;");
+
+    assert_eq!(explanation.tokens[0], Token::TINTEGER);
+    assert!(explanation.ast.is_none());
+    assert!(explanation.symbol_table.is_none());
+    assert!(explanation.ir.is_none());
+    assert!(matches!(explanation.errors[0], ErrorType::SyntaxError { .. }));
+    // The error is located at the `;` where the value should be, and printed with its location
+    assert_eq!(explanation.error_locations, vec![Some(SourceLocation::new(None, 1, 9))]);
+    assert!(explanation.to_string().contains("\n1:9: error["), "{}", explanation);
+}
+
+/// This test checks that errors after a `#line` directive are located in the file and line it names, for the parser
+/// and for type checking.
+#[test]
+fn test_explain_pipeline_line_directives() {
+    let explanation = explain_pipeline("#line 40 \"gen.c\"\nint x = ;");
+    assert_eq!(explanation.error_locations, vec![Some(SourceLocation::new(Some("gen.c".to_string()), 40, 9))]);
+
+    let explanation = explain_pipeline("int main() {\n#line 12 \"gen.c\"\n    int s = \"a\";\n    return 0;\n}");
+    assert_eq!(explanation.error_locations, vec![Some(SourceLocation::new(Some("gen.c".to_string()), 12, 5))]);
+    assert!(explanation.to_string().contains("\ngen.c:12:5: error["), "{}", explanation);
+}
+
+/// This test checks that a hosted executable must define `main`, while a library or a program that is only
+/// explained need not, and that a freestanding one neither needs `main` nor has its `printf` calls checked. Each
+/// program has a type error, so analysis is not run.
+#[test]
+fn test_explain_pipeline_entry_point() {
+    let mismatch = ErrorType::TypeMismatch { left_type: "int".to_string(), right_type: "char *".to_string() };
+    let executable = CodegenConfig { require_main: true, ..CodegenConfig::default() };
+    let library = CodegenConfig::default();
+    let freestanding = CodegenConfig { environment: Environment::Freestanding, require_main: true, ..CodegenConfig::default() };
+
+    assert_eq!(explain_pipeline_with_config("int x = \"str\";", executable).errors, vec![mismatch.clone(), ErrorType::MissingMain]);
+    assert_eq!(explain_pipeline_with_config("int main() { int x = \"str\"; }", executable).errors, vec![mismatch.clone()]);
+    assert_eq!(explain_pipeline("int x = \"str\";").errors, vec![mismatch.clone()]);
+
+    let source = "int f() { printf(\"%d\"); int x = \"str\"; }";
+    assert_eq!(explain_pipeline_with_config(source, library).warnings.len(), 1);
+    let explanation = explain_pipeline_with_config(source, freestanding);
+    assert_eq!(explanation.errors, vec![mismatch]);
+    assert!(explanation.warnings.is_empty());
+}
+
+/// This test checks that a freestanding program cannot compare strings, which is lowered to a call to the C
+/// library's `strcmp`, whether by `==` or by a `switch`, while a hosted one can.
+#[test]
+fn test_explain_pipeline_freestanding_string_comparison() {
+    let freestanding = CodegenConfig { environment: Environment::Freestanding, ..CodegenConfig::default() };
+    let sources = [
+        "int f() { if (\"on\" == \"off\") { return 1; } return 0; }",
+        "int f() { switch (\"on\") { case \"on\": return 1; default: return 0; } }",
+    ];
+    for source in sources {
+        let explanation = explain_pipeline_with_config(source, freestanding);
+        assert_eq!(explanation.errors, vec![ErrorType::FreestandingStringComparison], "{}", source);
+        assert!(explanation.ir.is_none(), "{}", source);
+
+        let explanation = explain_pipeline(source);
+        assert!(explanation.errors.is_empty(), "{}: {:?}", source, explanation.errors);
+        assert!(explanation.ir.is_some_and(|ir| ir.contains("declare i32 @strcmp(ptr, ptr)")), "{}", source);
+    }
+}
+
+/// This test checks that an executable gives every function but `main` internal linkage, since only the C library's
+/// startup code calls into it, while a library keeps every function external.
+#[test]
+fn test_explain_pipeline_executable_linkage() {
+    let source = "int twice(int x) { return (x + x); }\nint main() { return twice(2); }";
+    let executable = CodegenConfig { require_main: true, ..CodegenConfig::default() };
+
+    let ir = explain_pipeline_with_config(source, executable).ir.expect("Program should compile");
+    assert!(ir.contains("define internal i64 @twice("), "{}", ir);
+    assert!(ir.contains("define i64 @main("), "{}", ir);
+
+    let ir = explain_pipeline(source).ir.expect("Program should compile");
+    assert!(ir.contains("define i64 @twice("), "{}", ir);
+}
+
+/// This test checks that a valid program goes through every phase, and that its symbol table lists each scope with
+/// the symbols declared in it.
+#[test]
+fn test_explain_pipeline_valid_program() {
+    let source = "int twice(int x) { return (x + x); }\nint main() { int y = 2; for (int i = 0; i < 1; i++) { y = twice(y); } return y; }";
+
+    let (_, sym_table_stack) = analyze(source).expect("Program should analyze");
+    assert_eq!(
+        sym_table_stack.to_string(),
+        "scope 0:\n  main: Integer function()\n  twice: Integer function(x: Integer)\nscope 1:\n  x: Integer\nscope 2:\n  y: Integer\nscope 3:\n  i: Integer\nscope 4:",
+    );
+
+    let explanation = explain_pipeline(source);
+    assert!(explanation.errors.is_empty(), "{:?}", explanation.errors);
+    assert!(explanation.to_string().contains("=== Symbol Table ===\nscope 0:\n  main: Integer function()\n"));
+    assert!(explanation.is_complete());
+    assert!(explanation.ir.unwrap().contains("define i64 @main()"));
+}
+
+/// This test checks that the printed explanation has a section for every phase.
+#[test]
+fn test_explain_pipeline_display_sections() {
+    let printed = explain_pipeline("int x = ;").to_string();
+
+    for section in ["=== Tokens ===", "=== AST ===", "=== Symbol Table ===", "=== Typed AST ===", "=== IR ===", "=== Errors ==="] {
+        assert!(printed.contains(section), "missing section {}", section);
+    }
+}
+
+/// This test checks that annotated blocks are preceded by a comment naming their construct and source line, and that
+/// the `while` ending a do-while loop is not mistaken for a while loop.
+#[test]
+fn test_annotate_ir() {
+    let source = "int main() {\n    do {\n    } while (1);\n    while (0) {\n    }\n}\n";
+    let tokens = Lexer::lex_with_locations(source).expect("Source should lex");
+    let ir = "define i64 @main() {\nentryID0:\n  br label %do_bodyID1\n\ndo_bodyID1:                                       ; preds = %entryID0\n  br label %while_condID2\n\nwhile_condID2:\n  ret i64 0\n}\n";
+    let annotations = vec![
+        ("entryID0".to_string(), SourceConstruct::Function("main".to_string())),
+        ("do_bodyID1".to_string(), SourceConstruct::DoWhile),
+        ("while_condID2".to_string(), SourceConstruct::While),
+    ];
+
+    let annotated = annotate_ir(ir, &annotations, &tokens);
+
+    assert!(annotated.contains("; function `main` at line 1\nentryID0:"), "{}", annotated);
+    assert!(annotated.contains("; do-while loop at line 2\ndo_bodyID1:"), "{}", annotated);
+    assert!(annotated.contains("; while-loop at line 4\nwhile_condID2:"), "{}", annotated);
+    assert!(annotated.contains("  br label %while_condID2\n"), "{}", annotated);
+}
+
+/// This test checks that a global is reported as declared at its own name, which the parser recorded on its
+/// declaration, though a local of the same name is declared before it in the source.
+#[test]
+fn test_local_variable_spans() {
+    let source = "int f() { int count = 1; return count; }\nint count = 2;\nint main() { return count; }";
+    let (ast, sym_table_stack) = analyze(source).unwrap();
+
+    let locals = local_variables(ast, sym_table_stack, CodegenConfig::default()).unwrap();
+
+    let offset = source.find("count = 2").unwrap();
+    let expected = format!("count: Integer, mutable, declared at bytes {}..{}\n", offset, offset + "count".len());
+    assert!(locals.contains(&expected), "{}", locals);
+}
+
+/// This test checks that each defined function is measured on its own, that an unlabeled entry block and annotation
+/// comments are counted correctly, and that declared functions are left out.
+#[test]
+fn test_function_stats() {
+    let ir = "declare i64 @printf(ptr, ...)\n\ndefine i64 @twice(i64 %x) {\n  %addtmp = add i64 %x, %x\n  ret i64 %addtmp\n}\n\ndefine i64 @main() {\n; function `main` at line 1\nentryID0:\n  %x = alloca i64, align 8\n  %y = alloca i64, align 8\n  store i64 1, ptr %x, align 8\n  br label %mergeID1\n\nmergeID1:                                         ; preds = %entryID0\n  %calltmp = call i64 @twice(i64 2)\n  ret i64 %calltmp\n}\n";
+
+    let stats = function_stats(ir);
+
+    assert_eq!(stats, vec![
+        FunctionStats { name: "twice".to_string(), instructions: 2, basic_blocks: 1, allocas: 0 },
+        FunctionStats { name: "main".to_string(), instructions: 6, basic_blocks: 2, allocas: 2 },
+    ]);
+    assert_eq!(stats[1].to_string(), "main: 6 instructions, 2 basic blocks, 2 allocas");
+}
+
+/// This test checks the counts of functions compiled from source: each function is measured in definition order,
+/// each local adds an alloca and the store of its initial value, and an `if` adds basic blocks.
+#[test]
+fn test_ir_statistics() {
+    let measure = |source: &str| ir_statistics(source, CodegenConfig::default()).expect("Program should compile");
+
+    let one = measure("int helper() { return 0; }\nint main() { int x = 1; return x; }");
+    let two = measure("int helper() { return 0; }\nint main() { int x = 1; int y = 2; return x; }");
+    let branching = measure("int helper() { return 0; }\nint main() { int x = 1; if (x) { x = 2; } return x; }");
+
+    let names: Vec<&str> = one.iter().map(|function| function.name.as_str()).collect();
+    assert_eq!(names, vec!["helper", "main"]);
+    assert_eq!(one[0].allocas, 0);
+    assert_eq!(one[0].basic_blocks, 1);
+    assert!(one[1].allocas >= 1, "{}", one[1]);
+    assert_eq!(two[1].allocas, one[1].allocas + 1);
+    assert_eq!(two[1].instructions, one[1].instructions + 2);
+    assert_eq!(two[1].basic_blocks, one[1].basic_blocks);
+    assert!(branching[1].basic_blocks > one[1].basic_blocks, "{} vs {}", branching[1], one[1]);
+    assert_eq!(one, function_stats(&explain_pipeline("int helper() { return 0; }\nint main() { int x = 1; return x; }").ir.unwrap()));
+}
+
+/// Runs IR with `lli`, passing it `flags`, and returns the exit code of `main`.
+fn run_ir(lli: &OsStr, flags: &[&str], ir: &str) -> Option<i32> {
+    let mut child = Command::new(lli)
+        .args(flags)
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .expect("Failed to start lli");
+    child.stdin.take().unwrap().write_all(ir.as_bytes()).expect("Failed to write IR to lli");
+    child.wait().expect("Failed to wait for lli").code()
+}
+
+/// This test checks that call arguments and binary operands are evaluated from left to right by running programs
+/// whose functions record the order they are called in, both with LLVM's interpreter and with its JIT, which must
+/// agree. Running needs `lli`, so the test only runs when `SICC_LLI` names one.
+#[test]
+fn test_evaluation_order_when_run() {
+    let Some(lli) = env::var_os("SICC_LLI") else {
+        return;
+    };
+    // `g` appends the digit 1 to `trace` and `h` appends 2, so `trace` spells out the order of the calls
+    let prelude = "int trace = 0;\n\
+        int g() { trace = trace * 10 + 1; return 1; }\n\
+        int h() { trace = trace * 10 + 2; return 2; }\n\
+        int pair(int a, int b) { return trace; }\n";
+    let programs = [
+        ("int main() { return pair(g(), h()); }", 12),
+        ("int main() { int x = g() - h(); return trace; }", 12),
+        ("int main() { int x = pair(h(), g()) + g(); return trace; }", 211),
+    ];
+    for (main, order) in programs {
+        let source = format!("{}{}", prelude, main);
+        let ir = explain_pipeline(&source).ir.unwrap_or_else(|| panic!("{} should compile", main));
+        assert_eq!(run_ir(&lli, &["-force-interpreter"], &ir), Some(order), "{}", main);
+        assert_eq!(run_ir(&lli, &[], &ir), Some(order), "{}", main);
+    }
+}
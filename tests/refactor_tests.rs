@@ -0,0 +1,42 @@
+//! This file contains tests for refactoring source text, making sure the edits cover exactly the text to change.
+
+use common::error::ErrorType;
+use parser::incremental::TextEdit;
+use sicc::refactor::rename_at;
+
+const SOURCE: &str = "int scale(int x) {\n    return x;\n}\nint main() {\n    int x = 2;\n    x = scale(x);\n    return x;\n}\n";
+
+/// Applies edits sorted by offset to a source.
+fn apply(source: &str, edits: &[TextEdit]) -> String {
+    edits.iter().rev().fold(source.to_string(), |text, edit| edit.apply(&text).expect("The edit should fit the source"))
+}
+
+/// This test checks that renaming a variable from the middle of one of its references only edits the identifiers
+/// naming it, leaving the parameter of the same name alone.
+#[test]
+fn test_rename_at_variable() {
+    let offset = SOURCE.find("x = scale").expect("The source has the assignment") + 1;
+    let edits = rename_at(SOURCE, offset, "factor").expect("The rename should apply");
+
+    assert_eq!(edits.len(), 4);
+    assert_eq!(
+        apply(SOURCE, &edits),
+        "int scale(int x) {\n    return x;\n}\nint main() {\n    int factor = 2;\n    factor = scale(factor);\n    return factor;\n}\n",
+    );
+}
+
+/// This test checks that a function can be renamed from its declaration, and that an unsafe rename is refused.
+#[test]
+fn test_rename_at_function() {
+    let edits = rename_at(SOURCE, SOURCE.find("scale").expect("The source declares scale"), "twice")
+        .expect("The rename should apply");
+    assert_eq!(apply(SOURCE, &edits).matches("twice").count(), 2);
+
+    let errors = rename_at(SOURCE, SOURCE.find("scale").expect("The source declares scale"), "main")
+        .expect_err("The rename should be refused");
+    assert_eq!(errors, vec![ErrorType::InvalidRename {
+        symbol: "scale".to_string(),
+        name: "main".to_string(),
+        reason: "`main` is already declared in the same scope".to_string(),
+    }]);
+}
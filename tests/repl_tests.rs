@@ -0,0 +1,57 @@
+//! This file contains tests for interactive sessions, making sure each function is compiled into a module of its
+//! own, that redefining a function recompiles only the modules that need it, and that an entry that fails to compile
+//! leaves the session unchanged.
+
+use std::env;
+
+use common::{config::CodegenConfig, error::ErrorType};
+use sicc::repl::Session;
+
+/// This test checks that an entry that is not made of functions, or that fails to compile, defines nothing.
+#[test]
+fn test_define_rejects_failing_entries() {
+    let mut session = Session::new(CodegenConfig::default());
+
+    let errors = session.define("int x = 1;").unwrap_err();
+    assert!(matches!(errors[..], [ErrorType::SyntaxError { .. }]), "{:?}", errors);
+    let errors = session.define("int f() { int x = \"str\"; return x; }").unwrap_err();
+    assert!(matches!(errors[..], [ErrorType::TypeMismatch { .. }]), "{:?}", errors);
+    assert_eq!(session.functions().count(), 0);
+    assert!(session.run(&[]).is_err());
+}
+
+/// This test checks that each function is compiled into a module declaring the others, and that redefining a
+/// function with the same prototype leaves the modules of its callers as they were.
+#[test]
+fn test_redefinition_recompiles_only_its_module() {
+    let mut session = Session::new(CodegenConfig::default());
+    let defined = session.define("int f() { return 1; }\n\nint main() { return f(); }").expect("Entry should compile");
+    assert_eq!(defined, vec!["f", "main"]);
+    assert_eq!(session.functions().collect::<Vec<_>>(), vec!["f", "main"]);
+
+    let main_ir = session.module_ir("main").unwrap().to_string();
+    assert!(main_ir.contains("declare i64 @f()"), "{}", main_ir);
+    assert!(!main_ir.contains("define i64 @f()"), "{}", main_ir);
+    let f_ir = session.module_ir("f").unwrap().to_string();
+    assert!(f_ir.contains("define i64 @f()"), "{}", f_ir);
+
+    assert_eq!(session.define("int f() { return 2; }"), Ok(vec!["f".to_string()]));
+    assert_eq!(session.module_ir("main"), Some(main_ir.as_str()));
+    assert_ne!(session.module_ir("f"), Some(f_ir.as_str()));
+}
+
+/// This test checks that a run calls the body a function has at the time of the run. Running needs `lli`, so the
+/// test only runs when `SICC_LLI` names one.
+#[test]
+fn test_run_calls_latest_definition() {
+    if env::var_os("SICC_LLI").is_none() {
+        return;
+    }
+    let mut session = Session::new(CodegenConfig::default());
+    session.define("int f();\nint main() { return f() + 1; }").expect("Entry should compile");
+    session.define("int f() { return 1; }").expect("Entry should compile");
+    assert_eq!(session.run(&[]).expect("Failed to run lli").status.code(), Some(2));
+
+    session.define("int f() { return 41; }").expect("Entry should compile");
+    assert_eq!(session.run(&[]).expect("Failed to run lli").status.code(), Some(42));
+}
@@ -0,0 +1,120 @@
+//! This file contains tests for the compile server, making sure requests are answered as JSON-RPC responses with the
+//! diagnostics of the program, that malformed requests are rejected with the right error, and that a server on a
+//! socket answers until it is shut down.
+
+use std::{
+    env,
+    io::{BufRead, BufReader, Write},
+    os::unix::net::UnixStream,
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+use common::config::CodegenConfig;
+use sicc::{
+    json::{self, JsonValue},
+    server::{serve, Server, INVALID_PARAMS, INVALID_REQUEST, METHOD_NOT_FOUND, PARSE_ERROR},
+};
+
+fn respond(server: &Server, request: &str) -> JsonValue {
+    json::parse(&server.handle_request(request).expect("Expected a response")).expect("Invalid response")
+}
+
+fn error_code(response: &JsonValue) -> Option<f64> {
+    match response.get("error")?.get("code")? {
+        JsonValue::Number(code) => Some(*code),
+        _ => None,
+    }
+}
+
+/// This test checks that the diagnostics of a program are returned with their codes, localized messages, and
+/// locations, and that a program that fails to compile has no IR.
+#[test]
+fn test_diagnose_and_compile() {
+    let server = Server::new(CodegenConfig::default(), 4);
+    let response = respond(&server, r#"{"jsonrpc": "2.0", "id": 1, "method": "diagnose", "params": {"source": "int x = ;"}}"#);
+    assert_eq!(response.get("id"), Some(&JsonValue::Number(1.0)));
+    let errors = response.get("result").and_then(|result| result.get("errors")).and_then(JsonValue::as_array).unwrap();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].get("code").and_then(JsonValue::as_str).unwrap().starts_with('E'));
+    // The error is found at the `;` where the value should be
+    assert_eq!(errors[0].get("line"), Some(&JsonValue::Number(1.0)));
+    assert_eq!(errors[0].get("column"), Some(&JsonValue::Number(9.0)));
+
+    let request = r#"{"jsonrpc": "2.0", "id": "a", "method": "compile", "params": {"source": "int x = 1 @ 2;", "lang": "es"}}"#;
+    let result = respond(&server, request).get("result").cloned().unwrap();
+    assert_eq!(result.get("ir"), Some(&JsonValue::Null));
+    assert_eq!(result.get("warnings"), Some(&JsonValue::Array(Vec::new())));
+    // A second request for the same program is answered from the cache with the same result
+    assert_eq!(respond(&server, request).get("result"), Some(&result));
+
+    let response = respond(&server, r#"{"id": 2, "method": "run", "params": {"source": "int x = ;"}}"#);
+    assert_eq!(response.get("result").and_then(|result| result.get("exit_code")), Some(&JsonValue::Null));
+}
+
+/// This test checks that a valid program compiles to IR without diagnostics, and that running it returns the exit
+/// code of `main`. Running needs `lli`, so that part only runs when `SICC_LLI` names one.
+#[test]
+fn test_compile_and_run_valid_program() {
+    let server = Server::new(CodegenConfig::default(), 4);
+    let source = r#""source": "int twice(int x) { return (x + x); }\nint main() { int y = twice(3); return y; }""#;
+
+    let response = respond(&server, &format!(r#"{{"id": 1, "method": "compile", "params": {{{}}}}}"#, source));
+    assert_eq!(error_code(&response), None, "{}", response);
+    let result = response.get("result").unwrap();
+    assert_eq!(result.get("errors"), Some(&JsonValue::Array(Vec::new())));
+    assert!(result.get("ir").and_then(JsonValue::as_str).unwrap().contains("define i64 @main()"));
+
+    if env::var_os("SICC_LLI").is_none() {
+        return;
+    }
+    let response = respond(&server, &format!(r#"{{"id": 2, "method": "run", "params": {{{}}}}}"#, source));
+    assert_eq!(error_code(&response), None, "{}", response);
+    assert_eq!(response.get("result").and_then(|result| result.get("exit_code")), Some(&JsonValue::Number(6.0)));
+}
+
+/// This test checks that malformed requests are answered with the JSON-RPC error for their mistake, and that
+/// notifications are not answered.
+#[test]
+fn test_request_errors() {
+    let server = Server::new(CodegenConfig::default(), 4);
+    assert_eq!(error_code(&respond(&server, "{\"id\": 1,")), Some(PARSE_ERROR as f64));
+    assert_eq!(error_code(&respond(&server, r#"{"id": 1}"#)), Some(INVALID_REQUEST as f64));
+    assert_eq!(error_code(&respond(&server, r#"{"id": 1, "method": "link"}"#)), Some(METHOD_NOT_FOUND as f64));
+    assert_eq!(error_code(&respond(&server, r#"{"id": 1, "method": "compile", "params": {}}"#)), Some(INVALID_PARAMS as f64));
+    let request = r#"{"id": 1, "method": "diagnose", "params": {"source": "", "lang": "xx"}}"#;
+    assert_eq!(error_code(&respond(&server, request)), Some(INVALID_PARAMS as f64));
+    assert_eq!(server.handle_request(r#"{"method": "diagnose", "params": {"source": "int x = ;"}}"#), None);
+}
+
+/// This test checks that a server on a socket answers each request on its own line and stops after `shutdown`,
+/// removing its socket.
+#[test]
+fn test_serve_until_shutdown() {
+    let socket = env::temp_dir().join(format!("sicc-server-test-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&socket);
+    let server = Arc::new(Server::new(CodegenConfig::default(), 4));
+    let handle = {
+        let socket = socket.clone();
+        thread::spawn(move || serve(server, &socket, 2))
+    };
+
+    let stream = (0..100)
+        .find_map(|_| UnixStream::connect(&socket).ok().or_else(|| { thread::sleep(Duration::from_millis(10)); None }))
+        .expect("The server did not start");
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut writer = stream;
+    writeln!(writer, r#"{{"id": 1, "method": "diagnose", "params": {{"source": "int x = ;"}}}}"#).unwrap();
+    writeln!(writer, r#"{{"id": 2, "method": "shutdown"}}"#).unwrap();
+
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    assert!(json::parse(&line).unwrap().get("result").and_then(|result| result.get("errors")).is_some());
+    line.clear();
+    reader.read_line(&mut line).unwrap();
+    assert_eq!(json::parse(&line).unwrap().get("result"), Some(&JsonValue::Null));
+
+    handle.join().unwrap().expect("The server failed");
+    assert!(!socket.exists());
+}